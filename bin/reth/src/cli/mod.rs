@@ -9,9 +9,9 @@ use clap::{value_parser, Parser, Subcommand};
 use reth_chainspec::ChainSpec;
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
-    config_cmd, db, dump_genesis, import, init_cmd, init_state,
+    config_cmd, db, dump_genesis, exex, export_era, import, import_era, init_cmd, init_state,
     node::{self, NoArgs},
-    p2p, prune, recover, stage,
+    p2p, prune, recover, stage, static_file,
 };
 use reth_cli_runner::CliRunner;
 use reth_db::DatabaseEnv;
@@ -164,6 +164,12 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>, Ext: clap::Args + fmt::Debug> Cl
             Commands::Import(command) => runner.run_blocking_until_ctrl_c(
                 command.execute::<EthereumNode, _, _>(EthExecutorProvider::ethereum),
             ),
+            Commands::ImportEra(command) => runner.run_blocking_until_ctrl_c(
+                command.execute::<EthereumNode, _, _>(EthExecutorProvider::ethereum),
+            ),
+            Commands::ExportEra(command) => {
+                runner.run_blocking_until_ctrl_c(command.execute::<EthereumNode>())
+            }
             Commands::DumpGenesis(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Db(command) => {
                 runner.run_blocking_until_ctrl_c(command.execute::<EthereumNode>())
@@ -172,6 +178,8 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>, Ext: clap::Args + fmt::Debug> Cl
                 command.execute::<EthereumNode, _, _>(ctx, EthExecutorProvider::ethereum)
             }),
             Commands::P2P(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::Exex(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::StaticFile(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             #[cfg(feature = "dev")]
             Commands::TestVectors(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::Config(command) => runner.run_until_ctrl_c(command.execute()),
@@ -210,6 +218,12 @@ pub enum Commands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
     /// This syncs RLP encoded blocks from a file.
     #[command(name = "import")]
     Import(import::ImportCommand<C>),
+    /// This syncs pre-merge blocks from ERA1 files.
+    #[command(name = "import-era")]
+    ImportEra(import_era::ImportEraCommand<C>),
+    /// Exports pre-merge blocks to ERA1 files.
+    #[command(name = "export-era")]
+    ExportEra(export_era::ExportEraCommand<C>),
     /// Dumps genesis block JSON configuration to stdout.
     DumpGenesis(dump_genesis::DumpGenesisCommand<C>),
     /// Database debugging utilities
@@ -221,6 +235,12 @@ pub enum Commands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
     /// P2P Debugging utilities
     #[command(name = "p2p")]
     P2P(p2p::Command<C>),
+    /// ExEx debugging utilities
+    #[command(name = "exex")]
+    Exex(exex::Command<C>),
+    /// Static file utilities
+    #[command(name = "static-file")]
+    StaticFile(static_file::Command<C>),
     /// Generate Test Vectors
     #[cfg(feature = "dev")]
     #[command(name = "test-vectors")]
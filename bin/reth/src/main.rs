@@ -39,6 +39,13 @@ pub struct EngineArgs {
     /// Configure the target number of blocks to keep in memory.
     #[arg(long = "engine.memory-block-buffer-target", requires = "experimental", default_value_t = DEFAULT_MEMORY_BLOCK_BUFFER_TARGET)]
     pub memory_block_buffer_target: u64,
+
+    /// Configure the size of the in-memory cache of database trie nodes, in megabytes.
+    ///
+    /// The cache speeds up state root computation for consecutive payloads. It's disabled if not
+    /// set.
+    #[arg(long = "engine.trie-node-cache-size", value_name = "MB")]
+    pub trie_node_cache_size: Option<usize>,
 }
 
 impl Default for EngineArgs {
@@ -48,6 +55,7 @@ impl Default for EngineArgs {
             legacy: false,
             persistence_threshold: DEFAULT_PERSISTENCE_THRESHOLD,
             memory_block_buffer_target: DEFAULT_MEMORY_BLOCK_BUFFER_TARGET,
+            trie_node_cache_size: None,
         }
     }
 }
@@ -71,7 +79,10 @@ fn main() {
                 false => {
                     let engine_tree_config = TreeConfig::default()
                         .with_persistence_threshold(engine_args.persistence_threshold)
-                        .with_memory_block_buffer_target(engine_args.memory_block_buffer_target);
+                        .with_memory_block_buffer_target(engine_args.memory_block_buffer_target)
+                        .with_trie_node_cache_size(
+                            engine_args.trie_node_cache_size.map(|size| size * 1024 * 1024),
+                        );
                     let handle = builder
                         .with_types_and_provider::<EthereumNode, BlockchainProvider2<_>>()
                         .with_components(EthereumNode::components())
@@ -871,10 +871,13 @@ mod tests {
     use reth_errors::ProviderResult;
     use reth_primitives::{Account, Bytecode, Receipt};
     use reth_storage_api::{
-        AccountReader, BlockHashReader, StateProofProvider, StateProvider, StateRootProvider,
-        StorageRootProvider,
+        AccountReader, BlockHashReader, StateProofProvider, StateProvider, StateRangeProvider,
+        StateRootProvider, StorageRootProvider,
+    };
+    use reth_trie::{
+        range::{AccountRange, StorageRange},
+        AccountProof, HashedStorage, MultiProof, StorageProof, TrieInput,
     };
-    use reth_trie::{AccountProof, HashedStorage, MultiProof, StorageProof, TrieInput};
 
     fn create_mock_state(
         test_block_builder: &mut TestBlockBuilder,
@@ -1013,6 +1016,38 @@ mod tests {
         }
     }
 
+    impl StateRangeProvider for MockStateProvider {
+        fn account_range(
+            &self,
+            _input: TrieInput,
+            _start: B256,
+            _limit: usize,
+            _storage_limit: usize,
+        ) -> ProviderResult<AccountRange> {
+            Ok(AccountRange::default())
+        }
+
+        fn storage_range(
+            &self,
+            _address: Address,
+            _hashed_storage: HashedStorage,
+            _start: B256,
+            _limit: usize,
+        ) -> ProviderResult<StorageRange> {
+            Ok(StorageRange::default())
+        }
+
+        fn hashed_storage_range(
+            &self,
+            _hashed_address: B256,
+            _hashed_storage: HashedStorage,
+            _start: B256,
+            _limit: usize,
+        ) -> ProviderResult<StorageRange> {
+            Ok(StorageRange::default())
+        }
+    }
+
     #[test]
     fn test_in_memory_state_impl_state_by_hash() {
         let mut state_by_hash = HashMap::default();
@@ -8,10 +8,12 @@ use reth_errors::ProviderResult;
 use reth_primitives::{Account, Bytecode};
 use reth_storage_api::{
     AccountReader, BlockHashReader, StateProofProvider, StateProvider, StateProviderBox,
-    StateRootProvider, StorageRootProvider,
+    StateRangeProvider, StateRootProvider, StorageRootProvider,
 };
 use reth_trie::{
-    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof, TrieInput,
+    range::{AccountRange, StorageRange},
+    updates::TrieUpdates,
+    AccountProof, HashedPostState, HashedStorage, MultiProof, TrieInput,
 };
 use std::sync::OnceLock;
 
@@ -188,6 +190,47 @@ impl StateProofProvider for MemoryOverlayStateProvider {
     }
 }
 
+impl StateRangeProvider for MemoryOverlayStateProvider {
+    fn account_range(
+        &self,
+        mut input: TrieInput,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> ProviderResult<AccountRange> {
+        let MemoryOverlayTrieState { nodes, state } = self.trie_state().clone();
+        input.prepend_cached(nodes, state);
+        self.historical.account_range(input, start, limit, storage_limit)
+    }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        let state = &self.trie_state().state;
+        let mut hashed_storage =
+            state.storages.get(&keccak256(address)).cloned().unwrap_or_default();
+        hashed_storage.extend(&storage);
+        self.historical.storage_range(address, hashed_storage, start, limit)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_address: B256,
+        storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        let state = &self.trie_state().state;
+        let mut hashed_storage = state.storages.get(&hashed_address).cloned().unwrap_or_default();
+        hashed_storage.extend(&storage);
+        self.historical.hashed_storage_range(hashed_address, hashed_storage, start, limit)
+    }
+}
+
 impl StateProvider for MemoryOverlayStateProvider {
     fn storage(
         &self,
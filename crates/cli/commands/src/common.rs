@@ -151,7 +151,13 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Environmen
                     config.stages.clone(),
                     prune_modes.clone(),
                 ))
-                .build(factory.clone(), StaticFileProducer::new(factory.clone(), prune_modes));
+                .build(
+                    factory.clone(),
+                    StaticFileProducer::new(factory.clone(), prune_modes).with_change_sets(
+                        config.stages.index_account_history.static_file_change_sets,
+                        config.stages.index_storage_history.static_file_change_sets,
+                    ),
+                );
 
             // Move all applicable data from database to static files.
             pipeline.move_to_static_files()?;
@@ -1,10 +1,16 @@
 use alloy_primitives::{hex, BlockHash};
 use clap::Parser;
 use reth_db::{
-    static_file::{ColumnSelectorOne, ColumnSelectorTwo, HeaderMask, ReceiptMask, TransactionMask},
+    static_file::{
+        AccountChangeSetsMask, BlobSidecarsMask, ColumnSelectorOne, ColumnSelectorTwo, HeaderMask,
+        ReceiptMask, StorageChangeSetsMask, TransactionMask,
+    },
     tables, RawKey, RawTable, Receipts, TableViewer, Transactions,
 };
-use reth_db_api::table::{Decompress, DupSort, Table};
+use reth_db_api::{
+    models::{StoredBlockAccountChangeSet, StoredBlockBlobSidecars, StoredBlockStorageChangeSet},
+    table::{Decompress, DupSort, Table},
+};
 use reth_db_common::DbTool;
 use reth_node_builder::NodeTypesWithDB;
 use reth_primitives::Header;
@@ -71,6 +77,18 @@ impl Command {
                         table_key::<tables::Receipts>(&key)?,
                         <ReceiptMask<<Receipts as Table>::Value>>::MASK,
                     ),
+                    StaticFileSegment::BlobSidecars => (
+                        table_key::<tables::Headers>(&key)?,
+                        <BlobSidecarsMask<StoredBlockBlobSidecars>>::MASK,
+                    ),
+                    StaticFileSegment::AccountChangeSets => (
+                        table_key::<tables::Headers>(&key)?,
+                        <AccountChangeSetsMask<StoredBlockAccountChangeSet>>::MASK,
+                    ),
+                    StaticFileSegment::StorageChangeSets => (
+                        table_key::<tables::Headers>(&key)?,
+                        <StorageChangeSetsMask<StoredBlockStorageChangeSet>>::MASK,
+                    ),
                 };
 
                 let content = tool.provider_factory.static_file_provider().find_static_file(
@@ -112,6 +130,23 @@ impl Command {
                                     )?;
                                     println!("{}", serde_json::to_string_pretty(&receipt)?);
                                 }
+                                StaticFileSegment::BlobSidecars => {
+                                    let sidecars =
+                                        StoredBlockBlobSidecars::decompress(content[0].as_slice())?;
+                                    println!("{}", serde_json::to_string_pretty(&sidecars)?);
+                                }
+                                StaticFileSegment::AccountChangeSets => {
+                                    let change_set = StoredBlockAccountChangeSet::decompress(
+                                        content[0].as_slice(),
+                                    )?;
+                                    println!("{}", serde_json::to_string_pretty(&change_set)?);
+                                }
+                                StaticFileSegment::StorageChangeSets => {
+                                    let change_set = StoredBlockStorageChangeSet::decompress(
+                                        content[0].as_slice(),
+                                    )?;
+                                    println!("{}", serde_json::to_string_pretty(&change_set)?);
+                                }
                             }
                         }
                     }
@@ -12,6 +12,8 @@ mod clear;
 mod diff;
 mod get;
 mod list;
+mod restore;
+mod snapshot;
 mod stats;
 /// DB List TUI
 mod tui;
@@ -47,6 +49,11 @@ pub enum Subcommands {
     },
     /// Deletes all table entries
     Clear(clear::Command),
+    /// Creates a consistent snapshot of the database and static files, which can be taken while
+    /// the node is running
+    Snapshot(snapshot::Command),
+    /// Verifies a snapshot and restores it into the datadir
+    Restore(restore::Command),
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
@@ -72,15 +79,18 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
         let db_path = data_dir.db();
         let static_files_path = data_dir.static_files();
 
-        // ensure the provided datadir exist
-        eyre::ensure!(
-            data_dir.data_dir().is_dir(),
-            "Datadir does not exist: {:?}",
-            data_dir.data_dir()
-        );
-
-        // ensure the provided database exist
-        eyre::ensure!(db_path.is_dir(), "Database does not exist: {:?}", db_path);
+        // restoring a snapshot doesn't require an existing datadir
+        if !matches!(self.command, Subcommands::Restore(_)) {
+            // ensure the provided datadir exist
+            eyre::ensure!(
+                data_dir.data_dir().is_dir(),
+                "Datadir does not exist: {:?}",
+                data_dir.data_dir()
+            );
+
+            // ensure the provided database exist
+            eyre::ensure!(db_path.is_dir(), "Database does not exist: {:?}", db_path);
+        }
 
         match self.command {
             // TODO: We'll need to add this on the DB trait.
@@ -133,6 +143,14 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                 let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
                 command.execute(provider_factory)?;
             }
+            Subcommands::Snapshot(command) => {
+                db_ro_exec!(self.env, tool, N, {
+                    command.execute(&tool.provider_factory)?;
+                });
+            }
+            Subcommands::Restore(command) => {
+                command.execute(&db_path, &static_files_path)?;
+            }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
@@ -0,0 +1,31 @@
+use clap::Parser;
+use reth_db_common::snapshot::restore_snapshot;
+use std::path::{Path, PathBuf};
+
+/// The arguments for the `reth db restore` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The directory of the snapshot to restore.
+    path: PathBuf,
+
+    /// Deletes the existing database and static files before restoring the snapshot, once the
+    /// snapshot has been verified.
+    #[arg(long)]
+    force: bool,
+}
+
+impl Command {
+    /// Execute `db restore` command
+    pub fn execute(self, db_path: &Path, static_files_path: &Path) -> eyre::Result<()> {
+        let manifest = restore_snapshot(&self.path, db_path, static_files_path, self.force)?;
+
+        println!(
+            "Restored snapshot of block {} ({}) to {}",
+            manifest.block_number,
+            manifest.block_hash,
+            db_path.display()
+        );
+
+        Ok(())
+    }
+}
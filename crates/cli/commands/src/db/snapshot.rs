@@ -0,0 +1,31 @@
+use clap::Parser;
+use reth_db_common::snapshot::create_snapshot;
+use reth_provider::{providers::ProviderNodeTypes, ProviderFactory};
+use std::path::PathBuf;
+
+/// The arguments for the `reth db snapshot` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The directory to write the snapshot to. It must either not exist or be empty.
+    path: PathBuf,
+}
+
+impl Command {
+    /// Execute `db snapshot` command
+    pub fn execute<N: ProviderNodeTypes>(
+        self,
+        provider_factory: &ProviderFactory<N>,
+    ) -> eyre::Result<()> {
+        let manifest = create_snapshot(provider_factory, &self.path)?;
+
+        println!(
+            "Created snapshot of block {} ({}) with {} files at {}",
+            manifest.block_number,
+            manifest.block_hash,
+            manifest.files.len(),
+            self.path.display()
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,250 @@
+//! `reth exex wal` command.
+
+use std::{path::Path, sync::Arc};
+
+use alloy_primitives::BlockNumber;
+use clap::{Parser, Subcommand};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_db::{open_db_read_only, tables};
+use reth_db_api::{database::Database, transaction::DbTx};
+use reth_exex::{Storage, WalRecord};
+use reth_node_core::args::{DatabaseArgs, DatadirArgs};
+use tracing::{info, warn};
+
+/// `reth exex wal` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = C::help_message(),
+        default_value = C::SUPPORTED_CHAINS[0],
+        value_parser = C::parser()
+    )]
+    chain: Arc<C::ChainSpec>,
+
+    #[command(flatten)]
+    datadir: DatadirArgs,
+
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth exex wal` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Print the segments and records of the WAL.
+    Inspect,
+    /// Check the checksums of all records and decode them.
+    Verify,
+    /// Remove records from the end of the WAL.
+    ///
+    /// The node must be stopped while the WAL is truncated.
+    Truncate {
+        /// Remove the notification with this ID and all records after it.
+        ///
+        /// Notifications with blocks at or below the finalized block can't be removed.
+        ///
+        /// By default, the WAL is truncated at the first invalid record.
+        #[arg(long, value_name = "ID", verbatim_doc_comment)]
+        from_notification: Option<u32>,
+    },
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `exex wal` command
+    pub fn execute(self) -> eyre::Result<()> {
+        let data_dir = self.datadir.clone().resolve_datadir(self.chain.chain());
+        let wal_dir = data_dir.exex_wal();
+        if !wal_dir.exists() {
+            eyre::bail!("ExEx WAL directory {} does not exist", wal_dir.display());
+        }
+
+        match self.command {
+            Subcommands::Inspect => inspect(&wal_dir),
+            Subcommands::Verify => verify(&wal_dir),
+            Subcommands::Truncate { from_notification } => {
+                let finalized_block = if from_notification.is_some() {
+                    let db =
+                        open_db_read_only(&data_dir.db(), DatabaseArgs::default().database_args())?;
+                    db.view(|tx| {
+                        tx.get::<tables::ChainState>(tables::ChainStateKey::LastFinalizedBlock)
+                    })??
+                } else {
+                    None
+                };
+                truncate(&wal_dir, from_notification, finalized_block)
+            }
+        }
+    }
+}
+
+fn inspect(wal_dir: &Path) -> eyre::Result<()> {
+    for segment in Storage::segments(wal_dir)? {
+        let records = segment.read()?;
+        println!(
+            "Segment {} ({} bytes, {} records)",
+            segment.id,
+            records.size,
+            records.records.len()
+        );
+
+        for (offset, size, record) in records.records {
+            match record {
+                WalRecord::Notification { id, notification } => println!(
+                    "  {offset:>10} {size:>10} notification {id}: reverted {:?}, committed {:?}",
+                    notification.reverted_chain().map(|chain| chain.range()),
+                    notification.committed_chain().map(|chain| chain.range()),
+                ),
+                WalRecord::Finalized { ids } => {
+                    println!("  {offset:>10} {size:>10} finalized {ids:?}")
+                }
+            }
+        }
+        if let Some(invalid) = records.invalid {
+            println!("  invalid: {invalid}");
+        }
+    }
+
+    Ok(())
+}
+
+fn verify(wal_dir: &Path) -> eyre::Result<()> {
+    let segments = Storage::segments(wal_dir)?;
+    let last_segment = segments.last().map(|segment| segment.id);
+
+    let mut notifications = 0;
+    let mut errors = 0;
+    for segment in segments {
+        let records = segment.read()?;
+        notifications += records
+            .records
+            .iter()
+            .filter(|(_, _, record)| matches!(record, WalRecord::Notification { .. }))
+            .count();
+
+        if let Some(invalid) = records.invalid {
+            if Some(segment.id) == last_segment {
+                warn!(target: "reth::cli", path = ?segment.path, %invalid, "Torn record at the end of the WAL, it will be truncated on startup");
+            } else {
+                warn!(target: "reth::cli", path = ?segment.path, %invalid, "Corrupted WAL segment");
+                errors += 1;
+            }
+        }
+    }
+
+    if errors > 0 {
+        eyre::bail!("{errors} WAL segments are corrupted")
+    }
+    info!(target: "reth::cli", notifications, "WAL verified");
+
+    Ok(())
+}
+
+fn truncate(
+    wal_dir: &Path,
+    from_notification: Option<u32>,
+    finalized_block: Option<BlockNumber>,
+) -> eyre::Result<()> {
+    for segment in Storage::segments(wal_dir)? {
+        let records = segment.read()?;
+
+        let offset = match from_notification {
+            Some(from_notification) => {
+                let Some((offset, notification)) =
+                    records.records.iter().find_map(|(offset, _, record)| match record {
+                        WalRecord::Notification { id, notification }
+                            if *id == from_notification =>
+                        {
+                            Some((*offset, notification))
+                        }
+                        _ => None,
+                    })
+                else {
+                    continue
+                };
+
+                // the blocks of the notification and all later ones must not be finalized yet
+                let first_block = [notification.reverted_chain(), notification.committed_chain()]
+                    .into_iter()
+                    .flatten()
+                    .map(|chain| chain.first().number)
+                    .min();
+                if let Some((first_block, finalized_block)) = first_block.zip(finalized_block) {
+                    if first_block <= finalized_block {
+                        eyre::bail!(
+                            "block {first_block} of notification {from_notification} is finalized"
+                        )
+                    }
+                }
+
+                Some(offset)
+            }
+            None => records.invalid.map(|invalid| invalid.offset()),
+        };
+
+        if let Some(offset) = offset {
+            Storage::truncate(wal_dir, segment.id, offset)?;
+            info!(target: "reth::cli", segment = segment.id, offset, "WAL truncated");
+            return Ok(())
+        }
+    }
+
+    match from_notification {
+        Some(id) => eyre::bail!("notification {id} not found in the WAL"),
+        None => {
+            info!(target: "reth::cli", "No invalid records found, nothing to truncate");
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+    use reth_exex::{ExExNotification, Wal};
+    use reth_primitives::{Header, SealedBlock, SealedBlockWithSenders, SealedHeader};
+    use reth_provider::Chain;
+
+    #[test]
+    fn parse_truncate_command() {
+        let args: Command<EthereumChainSpecParser> =
+            Command::parse_from(["reth", "truncate", "--from-notification", "5"]);
+        assert!(matches!(args.command, Subcommands::Truncate { from_notification: Some(5) }));
+    }
+
+    #[test]
+    fn truncate_refuses_finalized_notifications() {
+        let wal_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(&wal_dir).unwrap();
+        for number in [5, 6] {
+            let header =
+                SealedHeader::new(Header { number, ..Default::default() }, Default::default());
+            let block = SealedBlockWithSenders::new(
+                SealedBlock::new(header, Default::default()),
+                Vec::new(),
+            )
+            .unwrap();
+            let chain = Chain::new(vec![block], Default::default(), None);
+            wal.commit(&ExExNotification::ChainCommitted { new: Arc::new(chain) }).unwrap();
+        }
+        drop(wal);
+
+        // the notification with block 5 is finalized
+        assert!(truncate(wal_dir.path(), Some(0), Some(5)).is_err());
+        assert!(truncate(wal_dir.path(), Some(1), Some(6)).is_err());
+
+        truncate(wal_dir.path(), Some(1), Some(5)).unwrap();
+        let notifications = Storage::segments(wal_dir.path())
+            .unwrap()
+            .iter()
+            .map(|segment| segment.read().unwrap().records.len())
+            .sum::<usize>();
+        assert_eq!(notifications, 1);
+    }
+}
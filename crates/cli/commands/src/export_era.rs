@@ -0,0 +1,121 @@
+//! Command that exports pre-merge history to ERA1 files.
+use crate::common::{AccessRights, Environment, EnvironmentArgs};
+use alloy_primitives::BlockNumber;
+use clap::Parser;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_downloaders::era1::{era1_file_name, Era1Block, Era1Writer, MAX_BLOCKS_PER_ERA1};
+use reth_node_builder::NodeTypesWithEngine;
+use reth_provider::{BlockNumReader, BlockReader, HeaderProvider, ProviderError, ReceiptProvider};
+use std::{io::BufWriter, path::PathBuf};
+use tracing::info;
+
+/// Exports pre-merge blocks to ERA1 files.
+#[derive(Debug, Parser)]
+pub struct ExportEraCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The first block to export.
+    ///
+    /// ERA1 files are split at multiples of 8192 blocks, so this should be the first block of an
+    /// epoch for the files to match the files of other clients.
+    #[arg(long, value_name = "BLOCK", default_value_t = 0, verbatim_doc_comment)]
+    first_block: BlockNumber,
+
+    /// The last block to export.
+    ///
+    /// Defaults to the last pre-merge block in the database.
+    #[arg(long, value_name = "BLOCK", verbatim_doc_comment)]
+    last_block: Option<BlockNumber>,
+
+    /// The directory to write the ERA1 files to.
+    #[arg(long, value_name = "EXPORT_DIR", verbatim_doc_comment)]
+    out_dir: PathBuf,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ExportEraCommand<C> {
+    /// Execute `export-era` command
+    pub async fn execute<N: NodeTypesWithEngine<ChainSpec = C::ChainSpec>>(
+        self,
+    ) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+        let provider = provider_factory.provider()?;
+        let network = provider.chain_spec().chain().to_string();
+
+        let last_block = self.last_block.unwrap_or(provider.last_block_number()?);
+        reth_fs_util::create_dir_all(&self.out_dir)?;
+
+        let mut block_number = self.first_block;
+        while block_number <= last_block {
+            let epoch = block_number / MAX_BLOCKS_PER_ERA1 as u64;
+            let epoch_end = (epoch + 1) * MAX_BLOCKS_PER_ERA1 as u64 - 1;
+
+            let tmp_path = self.out_dir.join(format!("{network}-{epoch:05}.era1.tmp"));
+            let mut writer =
+                Era1Writer::new(BufWriter::new(reth_fs_util::create_file(&tmp_path)?))?;
+            let mut merged = false;
+            while block_number <= last_block.min(epoch_end) {
+                let block = provider
+                    .block_by_number(block_number)?
+                    .ok_or(ProviderError::HeaderNotFound(block_number.into()))?;
+
+                // ERA1 files only contain proof of work blocks
+                if block_number > 0 && block.header.difficulty.is_zero() {
+                    merged = true;
+                    break
+                }
+
+                let total_difficulty = provider
+                    .header_td_by_number(block_number)?
+                    .ok_or(ProviderError::TotalDifficultyNotFound(block_number))?;
+                let receipts = provider
+                    .receipts_by_block(block_number.into())?
+                    .ok_or(ProviderError::ReceiptNotFound(block_number.into()))?;
+                writer.append(&Era1Block {
+                    header: block.header,
+                    body: block.body,
+                    receipts: receipts.into_iter().map(|receipt| receipt.with_bloom()).collect(),
+                    total_difficulty,
+                })?;
+                block_number += 1;
+            }
+
+            if writer.is_empty() {
+                reth_fs_util::remove_file(&tmp_path)?;
+                break
+            }
+            let blocks = writer.len();
+            let (_, accumulator) = writer.finish()?;
+            let path = self.out_dir.join(era1_file_name(&network, epoch, accumulator));
+            reth_fs_util::rename(&tmp_path, &path)?;
+            info!(target: "reth::cli", ?path, blocks, "Exported ERA1 file");
+
+            if merged {
+                info!(target: "reth::cli", block_number, "Reached the merge");
+                break
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::{EthereumChainSpecParser, SUPPORTED_CHAINS};
+
+    #[test]
+    fn parse_common_export_era_command_chain_args() {
+        for chain in SUPPORTED_CHAINS {
+            let args: ExportEraCommand<EthereumChainSpecParser> =
+                ExportEraCommand::parse_from(["reth", "--chain", chain, "--out-dir", "."]);
+            assert_eq!(
+                Ok(args.env.chain.chain),
+                chain.parse::<reth_chainspec::Chain>(),
+                "failed to parse chain {chain}"
+            );
+        }
+    }
+}
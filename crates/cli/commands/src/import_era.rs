@@ -0,0 +1,187 @@
+//! Command that imports pre-merge history from ERA1 files.
+use crate::{
+    common::{AccessRights, Environment, EnvironmentArgs},
+    import::build_import_pipeline,
+};
+use clap::Parser;
+use reth_beacon_consensus::EthBeaconConsensus;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_downloaders::era1::{EpochRoots, Era1File, ERA1_FILE_EXTENSION};
+use reth_evm::execute::BlockExecutorProvider;
+use reth_node_builder::NodeTypesWithEngine;
+use reth_node_core::version::SHORT_VERSION;
+use reth_provider::{BlockNumReader, ChainSpecProvider, StageCheckpointReader};
+use reth_prune::PruneModes;
+use reth_stages::StageId;
+use reth_static_file::StaticFileProducer;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::{debug, info};
+
+/// Syncs blocks from ERA1 files.
+#[derive(Debug, Parser)]
+pub struct ImportEraCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// Disables stages that require state.
+    #[arg(long, verbatim_doc_comment)]
+    no_state: bool,
+
+    /// The path to a file with the canonical accumulator roots of all epochs of the chain.
+    ///
+    /// The file contains one hex encoded root per line, starting at epoch 0. Files whose
+    /// accumulator doesn't match the root of their epoch are rejected.
+    ///
+    /// Required if no roots are bundled for the chain.
+    #[arg(long, value_name = "FILE", verbatim_doc_comment)]
+    epoch_roots: Option<PathBuf>,
+
+    /// The path to an ERA1 file or a directory of ERA1 files for import.
+    ///
+    /// The files of a directory are imported in the order of their names. Files with blocks that
+    /// are already in the database are skipped.
+    #[arg(value_name = "IMPORT_PATH", verbatim_doc_comment)]
+    path: PathBuf,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportEraCommand<C> {
+    /// Execute `import-era` command
+    pub async fn execute<N, E, F>(self, executor: F) -> eyre::Result<()>
+    where
+        N: NodeTypesWithEngine<ChainSpec = C::ChainSpec>,
+        E: BlockExecutorProvider,
+        F: FnOnce(Arc<N::ChainSpec>) -> E,
+    {
+        info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
+
+        if self.no_state {
+            info!(target: "reth::cli", "Disabled stages requiring state");
+        }
+
+        let files = era1_files(&self.path)?;
+        info!(target: "reth::cli", files = files.len(), "Found ERA1 files");
+
+        let epoch_roots = match &self.epoch_roots {
+            Some(path) => EpochRoots::open(path).await?,
+            None => {
+                let network = self.env.chain.chain().to_string();
+                EpochRoots::bundled(&network).ok_or_else(|| {
+                    eyre::eyre!("no epoch roots are bundled for {network}, use --epoch-roots")
+                })?
+            }
+        };
+        info!(target: "reth::cli", epochs = epoch_roots.len(), "Loaded canonical epoch roots");
+
+        let Environment { provider_factory, config, .. } = self.env.init::<N>(AccessRights::RW)?;
+
+        let executor = executor(provider_factory.chain_spec());
+        let consensus = Arc::new(EthBeaconConsensus::new(self.env.chain.clone()));
+        info!(target: "reth::cli", "Consensus engine initialized");
+
+        let mut total_imported_blocks = 0;
+        for path in files {
+            let file = Era1File::open(&path).await?;
+            let Some(start_block) = file.start_block() else {
+                debug!(target: "reth::cli", ?path, "Skipping empty ERA1 file");
+                continue
+            };
+            file.verify_epoch_root(&epoch_roots)?;
+            let end_block = start_block + file.blocks.len() as u64 - 1;
+
+            let last_block_number = provider_factory.last_block_number()?;
+            if end_block <= last_block_number {
+                debug!(target: "reth::cli", ?path, "Skipping imported ERA1 file");
+                continue
+            }
+            if start_block > last_block_number + 1 {
+                eyre::bail!(
+                    "ERA1 file {} starts at block {start_block}, but the database ends at block {last_block_number}",
+                    path.display()
+                );
+            }
+            info!(target: "reth::cli", ?path, blocks = ?start_block..=end_block, "Importing ERA1 file");
+
+            let file_client = file.into_file_client();
+            let tip = file_client.tip().ok_or(eyre::eyre!("file client has no tip"))?;
+
+            let (mut pipeline, events) = build_import_pipeline(
+                &config,
+                provider_factory.clone(),
+                &consensus,
+                Arc::new(file_client),
+                StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
+                self.no_state,
+                executor.clone(),
+            )?;
+
+            // override the tip
+            pipeline.set_tip(tip);
+            debug!(target: "reth::cli", ?tip, "Tip manually set");
+
+            let latest_block_number = provider_factory
+                .provider()?
+                .get_stage_checkpoint(StageId::Finish)?
+                .map(|ch| ch.block_number);
+            tokio::spawn(reth_node_events::node::handle_events(None, latest_block_number, events));
+
+            // Run pipeline
+            info!(target: "reth::cli", "Starting sync pipeline");
+            tokio::select! {
+                res = pipeline.run() => res?,
+                _ = tokio::signal::ctrl_c() => return Ok(()),
+            }
+
+            total_imported_blocks += provider_factory.last_block_number()? - last_block_number;
+        }
+
+        info!(target: "reth::cli", total_imported_blocks, "ERA1 files imported");
+
+        Ok(())
+    }
+}
+
+/// Returns the ERA1 file at the path or the ERA1 files of the directory, sorted by name.
+fn era1_files(path: &Path) -> eyre::Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()])
+    }
+
+    let mut files = Vec::new();
+    for entry in reth_fs_util::read_dir(path)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == ERA1_FILE_EXTENSION) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::{EthereumChainSpecParser, SUPPORTED_CHAINS};
+
+    #[test]
+    fn parse_common_import_era_command_chain_args() {
+        for chain in SUPPORTED_CHAINS {
+            let args: ImportEraCommand<EthereumChainSpecParser> = ImportEraCommand::parse_from([
+                "reth",
+                "--chain",
+                chain,
+                "--epoch-roots",
+                "roots.txt",
+                ".",
+            ]);
+            assert_eq!(
+                Ok(args.env.chain.chain),
+                chain.parse::<reth_chainspec::Chain>(),
+                "failed to parse chain {chain}"
+            );
+        }
+    }
+}
@@ -12,7 +12,10 @@ pub mod common;
 pub mod config_cmd;
 pub mod db;
 pub mod dump_genesis;
+pub mod exex;
+pub mod export_era;
 pub mod import;
+pub mod import_era;
 pub mod init_cmd;
 pub mod init_state;
 pub mod node;
@@ -20,6 +23,7 @@ pub mod p2p;
 pub mod prune;
 pub mod recover;
 pub mod stage;
+pub mod static_file;
 #[cfg(feature = "dev")]
 pub mod test_vectors;
 
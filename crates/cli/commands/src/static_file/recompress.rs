@@ -0,0 +1,344 @@
+//! `reth static-file recompress` command.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use clap::{Parser, ValueEnum};
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use eyre::WrapErr;
+use human_bytes::human_bytes;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_db::lockfile::StorageLock;
+use reth_nippy_jar::{
+    compression::{Compressors, Lz4, Zstd},
+    recover_replacements, NippyJar,
+};
+use reth_node_core::args::DatadirArgs;
+use reth_static_file_types::{SegmentHeader, SegmentRangeInclusive, StaticFileSegment};
+use tracing::info;
+
+/// Name of the directory inside the static files directory holding jars while they are being
+/// re-encoded.
+const RECOMPRESS_DIR: &str = "recompress";
+
+/// `reth static-file recompress` command
+///
+/// The node must be stopped while static files are recompressed. The latest static file of each
+/// segment is left as is, since the node keeps appending to it.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = C::help_message(),
+        default_value = C::SUPPORTED_CHAINS[0],
+        value_parser = C::parser()
+    )]
+    chain: Arc<C::ChainSpec>,
+
+    #[command(flatten)]
+    datadir: DatadirArgs,
+
+    /// Static file segments to recompress. All segments are recompressed if none are given.
+    #[arg(long, value_delimiter = ',')]
+    segments: Vec<StaticFileSegment>,
+
+    /// Compression algorithm to re-encode static files with.
+    #[arg(long, value_enum, default_value_t = Compression::Zstd)]
+    compression: Compression,
+
+    /// Zstd compression level. A level of `0` uses zstd's default.
+    #[arg(long = "zstd.level", default_value_t = 0)]
+    zstd_level: i32,
+
+    /// Maximum size of the zstd dictionary trained for each column, in bytes.
+    #[arg(long = "zstd.max-dict-size", default_value_t = 112_640)]
+    zstd_max_dict_size: usize,
+
+    /// Disable training per-column zstd dictionaries.
+    #[arg(long = "zstd.no-dict")]
+    zstd_no_dict: bool,
+}
+
+/// Compression algorithms static files can be re-encoded with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Compression {
+    /// LZ4 compression.
+    Lz4,
+    /// Zstandard compression, with per-column dictionaries unless disabled.
+    Zstd,
+}
+
+/// Size of a segment's static files before and after recompression.
+#[derive(Debug, Default)]
+struct SegmentSizes {
+    files: usize,
+    before: u64,
+    after: u64,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `static-file recompress` command
+    pub fn execute(self) -> eyre::Result<()> {
+        let data_dir = self.datadir.clone().resolve_datadir(self.chain.chain());
+        let static_files_dir = data_dir.static_files();
+        if !static_files_dir.exists() {
+            eyre::bail!("Static files directory {} does not exist", static_files_dir.display());
+        }
+
+        // Fails if a node is running on top of the same static files.
+        let _lock = StorageLock::try_acquire(&static_files_dir)?;
+
+        // Finishes swapping the files of a jar if a previous run was interrupted.
+        recover_replacements(&static_files_dir)?;
+
+        let tmp_dir = static_files_dir.join(RECOMPRESS_DIR);
+        let mut sizes = BTreeMap::<StaticFileSegment, SegmentSizes>::new();
+
+        for (segment, block_range, path) in self.static_files(&static_files_dir)? {
+            let jar = NippyJar::<SegmentHeader>::load(&path)?;
+            if jar.rows() == 0 {
+                continue
+            }
+
+            // Start every jar from an empty directory, so leftovers of a previously interrupted
+            // run are never picked up.
+            if tmp_dir.exists() {
+                reth_fs_util::remove_dir_all(&tmp_dir)?;
+            }
+            reth_fs_util::create_dir_all(&tmp_dir)?;
+
+            let tmp_path = tmp_dir.join(segment.filename(&block_range));
+            let new_jar = jar
+                .recompress(&tmp_path, self.compressor(jar.columns()))
+                .wrap_err_with(|| format!("failed to recompress {segment} file {block_range}"))?;
+
+            let before = jar_size(&jar)?;
+            let after = jar_size(&new_jar)?;
+
+            jar.replace_with(&new_jar)?;
+
+            info!(
+                target: "reth::cli",
+                ?segment,
+                ?block_range,
+                before = human_bytes(before as f64),
+                after = human_bytes(after as f64),
+                "Recompressed static file"
+            );
+
+            let entry = sizes.entry(segment).or_default();
+            entry.files += 1;
+            entry.before += before;
+            entry.after += after;
+        }
+
+        if tmp_dir.exists() {
+            reth_fs_util::remove_dir_all(&tmp_dir)?;
+        }
+
+        println!("{}", sizes_table(&sizes));
+
+        Ok(())
+    }
+
+    /// Returns the static files of the selected segments, sorted by segment and block range.
+    ///
+    /// The latest static file of each segment is excluded, since the node keeps appending to it.
+    fn static_files(
+        &self,
+        static_files_dir: &Path,
+    ) -> eyre::Result<Vec<(StaticFileSegment, SegmentRangeInclusive, PathBuf)>> {
+        let mut files = Vec::new();
+        for entry in reth_fs_util::read_dir(static_files_dir)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_file() {
+                continue
+            }
+
+            let Some((segment, block_range)) =
+                entry.file_name().to_str().and_then(StaticFileSegment::parse_filename)
+            else {
+                continue
+            };
+
+            if self.segments.is_empty() || self.segments.contains(&segment) {
+                files.push((segment, block_range, entry.path()));
+            }
+        }
+
+        files.sort_by_key(|(segment, block_range, _)| (*segment, block_range.start()));
+
+        let mut files = files.into_iter().peekable();
+        let mut sealed = Vec::new();
+        while let Some(file) = files.next() {
+            if files.peek().is_some_and(|next| next.0 == file.0) {
+                sealed.push(file);
+            } else {
+                info!(
+                    target: "reth::cli",
+                    segment = ?file.0,
+                    block_range = ?file.1,
+                    "Skipping latest static file of segment"
+                );
+            }
+        }
+
+        Ok(sealed)
+    }
+
+    /// Returns the compressor to re-encode a jar with the given number of columns.
+    fn compressor(&self, columns: usize) -> Option<Compressors> {
+        Some(match self.compression {
+            Compression::Lz4 => Compressors::Lz4(Lz4::default()),
+            Compression::Zstd => Compressors::Zstd(
+                Zstd::new(!self.zstd_no_dict, self.zstd_max_dict_size, columns)
+                    .with_level(self.zstd_level),
+            ),
+        })
+    }
+}
+
+/// Returns the total size of the data, offsets and configuration files of a jar.
+fn jar_size(jar: &NippyJar<SegmentHeader>) -> eyre::Result<u64> {
+    let mut size = 0;
+    for path in [jar.data_path().to_path_buf(), jar.offsets_path(), jar.config_path()] {
+        size += reth_fs_util::metadata(path)?.len();
+    }
+    Ok(size)
+}
+
+/// Renders the sizes of the recompressed segments as a table.
+fn sizes_table(sizes: &BTreeMap<StaticFileSegment, SegmentSizes>) -> ComfyTable {
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header(["Segment", "Files", "Size Before", "Size After", "Saved"]);
+
+    let mut total = SegmentSizes::default();
+    for (segment, segment_sizes) in sizes {
+        table.add_row(sizes_row(segment.as_str(), segment_sizes));
+        total.files += segment_sizes.files;
+        total.before += segment_sizes.before;
+        total.after += segment_sizes.after;
+    }
+    table.add_row(sizes_row("Total", &total));
+
+    table
+}
+
+fn sizes_row(name: &str, sizes: &SegmentSizes) -> Row {
+    // Recompressing can grow files, e.g. when switching from zstd to lz4.
+    let (sign, saved) = if sizes.after > sizes.before {
+        ("-", sizes.after - sizes.before)
+    } else {
+        ("", sizes.before - sizes.after)
+    };
+    let saved_percentage =
+        if sizes.before == 0 { 0.0 } else { saved as f64 / sizes.before as f64 * 100.0 };
+
+    let mut row = Row::new();
+    row.add_cell(Cell::new(name))
+        .add_cell(Cell::new(sizes.files))
+        .add_cell(Cell::new(human_bytes(sizes.before as f64)))
+        .add_cell(Cell::new(human_bytes(sizes.after as f64)))
+        .add_cell(Cell::new(format!(
+            "{sign}{} ({sign}{saved_percentage:.2}%)",
+            human_bytes(saved as f64)
+        )));
+    row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{keccak256, U256};
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+    use reth_primitives::Header;
+    use reth_provider::{
+        providers::{StaticFileProvider, StaticFileWriter},
+        HeaderProvider,
+    };
+
+    #[test]
+    fn parse_recompress_command() {
+        let cmd = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "--segments",
+            "headers,receipts",
+            "--compression",
+            "zstd",
+            "--zstd.level",
+            "19",
+        ])
+        .unwrap();
+        assert_eq!(cmd.segments, vec![StaticFileSegment::Headers, StaticFileSegment::Receipts]);
+        assert_eq!(cmd.compression, Compression::Zstd);
+        assert_eq!(cmd.zstd_level, 19);
+        assert!(!cmd.zstd_no_dict);
+    }
+
+    #[test]
+    fn recompress_static_files() {
+        let static_files_dir = tempfile::tempdir().unwrap();
+        let blocks_per_file = 1000;
+        let tip = 3 * blocks_per_file - 1;
+
+        let header = |number| Header { number, gas_limit: 30_000_000, ..Default::default() };
+        let td = |number: u64| U256::from(number) << 64;
+        let hash = |number: u64| keccak256(number.to_be_bytes());
+
+        {
+            let provider = StaticFileProvider::read_write(static_files_dir.path())
+                .unwrap()
+                .with_custom_blocks_per_file(blocks_per_file);
+            let mut writer = provider.latest_writer(StaticFileSegment::Headers).unwrap();
+            for number in 0..=tip {
+                writer.append_header(&header(number), td(number), &hash(number)).unwrap();
+            }
+            writer.commit().unwrap();
+        }
+
+        let cmd = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "--datadir.static-files",
+            static_files_dir.path().to_str().unwrap(),
+        ])
+        .unwrap();
+        cmd.execute().unwrap();
+
+        // Only the sealed static files are recompressed.
+        for (start, recompressed) in
+            [(0, true), (blocks_per_file, true), (2 * blocks_per_file, false)]
+        {
+            let block_range = SegmentRangeInclusive::new(start, start + blocks_per_file - 1);
+            let jar = NippyJar::<SegmentHeader>::load(
+                &static_files_dir.path().join(StaticFileSegment::Headers.filename(&block_range)),
+            )
+            .unwrap();
+            assert_eq!(
+                matches!(jar.compressor(), Some(Compressors::Zstd(zstd)) if zstd.use_dict),
+                recompressed
+            );
+        }
+        assert!(!static_files_dir.path().join(RECOMPRESS_DIR).exists());
+
+        // Data is unchanged and the node can keep appending to the latest static file.
+        let provider = StaticFileProvider::read_write(static_files_dir.path())
+            .unwrap()
+            .with_custom_blocks_per_file(blocks_per_file);
+        for number in 0..=tip {
+            assert_eq!(provider.header_by_number(number).unwrap(), Some(header(number)));
+            assert_eq!(provider.header_td_by_number(number).unwrap(), Some(td(number)));
+        }
+        let mut writer = provider.latest_writer(StaticFileSegment::Headers).unwrap();
+        writer.append_header(&header(tip + 1), td(tip + 1), &hash(tip + 1)).unwrap();
+        writer.commit().unwrap();
+    }
+}
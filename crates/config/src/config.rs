@@ -4,6 +4,7 @@ use eyre::eyre;
 use reth_network_types::{PeersConfig, SessionsConfig};
 use reth_prune_types::PruneModes;
 use reth_stages_types::ExecutionStageThresholds;
+use reth_transaction_pool::AdmissionConfig;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::{
     ffi::OsStr,
@@ -31,6 +32,8 @@ pub struct Config {
     pub peers: PeersConfig,
     /// Configuration for peer sessions.
     pub sessions: SessionsConfig,
+    /// Configuration for the transaction pool.
+    pub txpool: TxPoolConfig,
 }
 
 impl Config {
@@ -120,6 +123,10 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Address Transactions stage configuration.
+    pub index_address_transactions: IndexAddressTransactionsConfig,
+    /// Snap state stage configuration.
+    pub snap_state: SnapStateConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -365,11 +372,57 @@ impl EtlConfig {
 pub struct IndexHistoryConfig {
     /// The maximum number of blocks to process before committing progress to the database.
     pub commit_threshold: u64,
+    /// Whether changesets are moved to static files once the history index has been built from
+    /// them. Disabled by default.
+    pub static_file_change_sets: bool,
 }
 
 impl Default for IndexHistoryConfig {
     fn default() -> Self {
-        Self { commit_threshold: 100_000 }
+        Self { commit_threshold: 100_000, static_file_change_sets: false }
+    }
+}
+
+/// Address transactions index stage configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct IndexAddressTransactionsConfig {
+    /// Whether the stage is part of the pipeline and run by the engine on persisted blocks.
+    /// Disabled by default.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexAddressTransactionsConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 10_000 }
+    }
+}
+
+/// Snap state stage configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct SnapStateConfig {
+    /// Whether the stage is part of the pipeline. Disabled by default.
+    pub enabled: bool,
+    /// The distance of the pivot block to the sync target. The hashed state of the pivot block
+    /// is downloaded, the hashing and merkle stages continue from it.
+    pub pivot_distance: u64,
+    /// The maximum number of accounts to download before writing the results to the database.
+    pub commit_threshold: u64,
+    /// The soft limit of the response size of a single request, in bytes.
+    pub response_bytes: u64,
+}
+
+impl Default for SnapStateConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            pivot_distance: 64,
+            commit_threshold: 100_000,
+            response_bytes: 512 * 1024,
+        }
     }
 }
 
@@ -410,6 +463,7 @@ impl PruneConfig {
                     account_history,
                     storage_history,
                     receipts_log_filter,
+                    blob_sidecars,
                 },
         } = other;
 
@@ -424,6 +478,7 @@ impl PruneConfig {
         self.segments.receipts = self.segments.receipts.or(receipts);
         self.segments.account_history = self.segments.account_history.or(account_history);
         self.segments.storage_history = self.segments.storage_history.or(storage_history);
+        self.segments.blob_sidecars = self.segments.blob_sidecars.or(blob_sidecars);
 
         if self.segments.receipts_log_filter.0.is_empty() && !receipts_log_filter.0.is_empty() {
             self.segments.receipts_log_filter = receipts_log_filter;
@@ -431,6 +486,14 @@ impl PruneConfig {
     }
 }
 
+/// Transaction pool configuration.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct TxPoolConfig {
+    /// Rules that decide whether a transaction is admitted into the pool.
+    pub admission: AdmissionConfig,
+}
+
 /// Helper type to support older versions of Duration deserialization.
 fn deserialize_duration<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
 where
@@ -947,6 +1010,7 @@ receipts = 'full'
                     Address::random(),
                     PruneMode::Full,
                 )])),
+                blob_sidecars: None,
             },
         };
 
@@ -962,6 +1026,7 @@ receipts = 'full'
                     (Address::random(), PruneMode::Distance(1000)),
                     (Address::random(), PruneMode::Before(2000)),
                 ])),
+                blob_sidecars: Some(PruneMode::Distance(4096)),
             },
         };
 
@@ -977,6 +1042,7 @@ receipts = 'full'
         assert_eq!(config1.segments.account_history, Some(PruneMode::Distance(2000)));
         assert_eq!(config1.segments.storage_history, Some(PruneMode::Before(5000)));
         assert_eq!(config1.segments.receipts_log_filter, original_filter);
+        assert_eq!(config1.segments.blob_sidecars, Some(PruneMode::Distance(4096)));
     }
 
     #[test]
@@ -1019,4 +1085,26 @@ connect_trusted_nodes_only = true
             assert!(conf.peers.trusted_nodes.contains(&node));
         }
     }
+
+    #[test]
+    fn test_txpool_admission_config() {
+        let reth_toml = r#"
+[txpool.admission]
+deny_senders = ["0x0000000000000000000000000000000000000001"]
+max_sender_calldata = 131072
+
+[txpool.admission.sender_rate_limit]
+max_transactions = 16
+window = "1m"
+"#;
+
+        let conf: Config = toml::from_str(reth_toml).unwrap();
+        let admission = &conf.txpool.admission;
+        assert_eq!(admission.deny_senders, vec![alloy_primitives::Address::with_last_byte(1)]);
+        assert_eq!(admission.max_sender_calldata, Some(131072));
+        let rate_limit = admission.sender_rate_limit.unwrap();
+        assert_eq!(rate_limit.max_transactions, 16);
+        assert_eq!(rate_limit.window, Duration::from_secs(60));
+        assert!(admission.allow_senders.is_empty());
+    }
 }
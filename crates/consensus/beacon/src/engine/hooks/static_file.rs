@@ -116,6 +116,9 @@ where
                         headers: Some(finalized_block_number),
                         receipts: Some(finalized_block_number),
                         transactions: Some(finalized_block_number),
+                        blob_sidecars: None,
+                        account_change_sets: Some(finalized_block_number),
+                        storage_change_sets: Some(finalized_block_number),
                     })?;
 
                 // Check if the moving data to static files has been requested.
@@ -12,6 +12,6 @@ pub mod miner;
 pub mod payload;
 pub mod service;
 
-pub use miner::MiningMode;
+pub use miner::{LocalMinerHandle, MiningMode};
 pub use payload::LocalPayloadAttributesBuilder;
 pub use service::LocalEngineService;
@@ -5,8 +5,10 @@ use alloy_rpc_types_engine::{CancunPayloadFields, ForkchoiceState};
 use eyre::OptionExt;
 use futures_util::{stream::Fuse, StreamExt};
 use reth_beacon_consensus::BeaconEngineMessage;
+use reth_chain_state::ExecutedBlock;
 use reth_chainspec::EthereumHardforks;
 use reth_engine_primitives::EngineTypes;
+use reth_evm::state_overlay::StateOverlay;
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{
     BuiltPayload, PayloadAttributesBuilder, PayloadBuilder, PayloadKind, PayloadTypes,
@@ -21,7 +23,10 @@ use std::{
     time::{Duration, UNIX_EPOCH},
 };
 use tokio::{
-    sync::{mpsc::UnboundedSender, oneshot},
+    sync::{
+        mpsc::{self, UnboundedReceiver, UnboundedSender},
+        oneshot,
+    },
     time::Interval,
 };
 use tokio_stream::wrappers::ReceiverStream;
@@ -35,6 +40,8 @@ pub enum MiningMode {
     Instant(Fuse<ReceiverStream<TxHash>>),
     /// In this mode a block is built at a fixed interval.
     Interval(Interval),
+    /// In this mode blocks are only built on request, see [`LocalMinerHandle::mine`].
+    Manual,
 }
 
 impl MiningMode {
@@ -49,6 +56,11 @@ impl MiningMode {
         let start = tokio::time::Instant::now() + duration;
         Self::Interval(tokio::time::interval_at(start, duration))
     }
+
+    /// Returns true if a block is built as soon as a transaction reaches the pool.
+    pub const fn is_instant(&self) -> bool {
+        matches!(self, Self::Instant(_))
+    }
 }
 
 impl Future for MiningMode {
@@ -70,10 +82,97 @@ impl Future for MiningMode {
                 }
                 Poll::Pending
             }
+            Self::Manual => Poll::Pending,
         }
     }
 }
 
+/// Commands that can be sent to the [`LocalMiner`] through a [`LocalMinerHandle`].
+#[derive(Debug)]
+enum MinerCommand {
+    /// Mines the given number of blocks, with the given number of seconds between the
+    /// timestamps of consecutive blocks.
+    Mine { blocks: u64, interval: Option<u64>, tx: oneshot::Sender<eyre::Result<Vec<B256>>> },
+    /// Replaces the mining mode.
+    SetMode(MiningMode),
+    /// Returns whether the miner is in [`MiningMode::Instant`].
+    IsInstant(oneshot::Sender<bool>),
+    /// Sets the timestamp of the next block.
+    SetNextBlockTimestamp { timestamp: u64, tx: oneshot::Sender<eyre::Result<()>> },
+    /// Shifts the clock of the miner by the given number of seconds and returns the total offset.
+    IncreaseTime { seconds: u64, tx: oneshot::Sender<i64> },
+    /// Sets the clock of the miner to the given timestamp.
+    SetTime(u64),
+    /// Sets a fixed interval between the timestamps of consecutive blocks.
+    SetBlockTimestampInterval { interval: Option<u64>, tx: oneshot::Sender<bool> },
+}
+
+/// A handle to interact with a spawned [`LocalMiner`].
+#[derive(Debug, Clone)]
+pub struct LocalMinerHandle {
+    to_miner: UnboundedSender<MinerCommand>,
+}
+
+impl LocalMinerHandle {
+    /// Mines the given number of blocks regardless of the mining mode and returns their hashes.
+    ///
+    /// If an interval is given, the timestamps of consecutive blocks are that many seconds apart.
+    pub async fn mine(&self, blocks: u64, interval: Option<u64>) -> eyre::Result<Vec<B256>> {
+        let (tx, rx) = oneshot::channel();
+        self.send(MinerCommand::Mine { blocks, interval, tx })?;
+        rx.await?
+    }
+
+    /// Replaces the mining mode of the miner.
+    pub fn set_mining_mode(&self, mode: MiningMode) -> eyre::Result<()> {
+        self.send(MinerCommand::SetMode(mode))
+    }
+
+    /// Returns true if the miner builds a block as soon as a transaction reaches the pool.
+    pub async fn is_instant(&self) -> eyre::Result<bool> {
+        let (tx, rx) = oneshot::channel();
+        self.send(MinerCommand::IsInstant(tx))?;
+        Ok(rx.await?)
+    }
+
+    /// Sets the timestamp of the next block.
+    ///
+    /// Fails if the timestamp is not greater than the timestamp of the latest block.
+    pub async fn set_next_block_timestamp(&self, timestamp: u64) -> eyre::Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.send(MinerCommand::SetNextBlockTimestamp { timestamp, tx })?;
+        rx.await?
+    }
+
+    /// Moves the clock of the miner forward by the given number of seconds.
+    ///
+    /// Returns the total offset of the clock of the miner, in seconds.
+    pub async fn increase_time(&self, seconds: u64) -> eyre::Result<i64> {
+        let (tx, rx) = oneshot::channel();
+        self.send(MinerCommand::IncreaseTime { seconds, tx })?;
+        Ok(rx.await?)
+    }
+
+    /// Sets the clock of the miner to the given timestamp.
+    pub fn set_time(&self, timestamp: u64) -> eyre::Result<()> {
+        self.send(MinerCommand::SetTime(timestamp))
+    }
+
+    /// Sets a fixed interval between the timestamps of consecutive blocks, or removes it if
+    /// `None` is given.
+    ///
+    /// Returns true if an interval was set before.
+    pub async fn set_block_timestamp_interval(&self, interval: Option<u64>) -> eyre::Result<bool> {
+        let (tx, rx) = oneshot::channel();
+        self.send(MinerCommand::SetBlockTimestampInterval { interval, tx })?;
+        Ok(rx.await?)
+    }
+
+    fn send(&self, command: MinerCommand) -> eyre::Result<()> {
+        self.to_miner.send(command).map_err(|_| eyre::eyre!("local miner is not running"))
+    }
+}
+
 /// Local miner advancing the chain/
 #[derive(Debug)]
 pub struct LocalMiner<EngineT: EngineTypes, Provider, B> {
@@ -83,6 +182,8 @@ pub struct LocalMiner<EngineT: EngineTypes, Provider, B> {
     payload_attributes_builder: B,
     /// Sender for events to engine.
     to_engine: UnboundedSender<BeaconEngineMessage<EngineT>>,
+    /// Sender for built blocks that are inserted into the tree before their payload.
+    to_tree: UnboundedSender<ExecutedBlock>,
     /// The mining mode for the engine
     mode: MiningMode,
     /// The payload builder for the engine
@@ -91,6 +192,16 @@ pub struct LocalMiner<EngineT: EngineTypes, Provider, B> {
     last_timestamp: u64,
     /// Stores latest mined blocks.
     last_block_hashes: Vec<B256>,
+    /// Receiver for commands from [`LocalMinerHandle`]s.
+    from_handle: UnboundedReceiver<MinerCommand>,
+    /// Offset in seconds that is added to the system time when computing block timestamps.
+    time_offset: i64,
+    /// Timestamp of the next block, if set explicitly.
+    next_timestamp: Option<u64>,
+    /// Fixed interval between the timestamps of consecutive blocks, if set.
+    timestamp_interval: Option<u64>,
+    /// Out-of-band state changes that are sealed into the next block.
+    state_overlay: StateOverlay,
 }
 
 impl<EngineT, Provider, B> LocalMiner<EngineT, Provider, B>
@@ -99,29 +210,40 @@ where
     Provider: BlockReader + ChainSpecProvider<ChainSpec: EthereumHardforks> + 'static,
     B: PayloadAttributesBuilder<<EngineT as PayloadTypes>::PayloadAttributes>,
 {
-    /// Spawns a new [`LocalMiner`] with the given parameters.
+    /// Spawns a new [`LocalMiner`] with the given parameters and returns a handle to it.
     pub fn spawn_new(
         provider: Provider,
         payload_attributes_builder: B,
         to_engine: UnboundedSender<BeaconEngineMessage<EngineT>>,
+        to_tree: UnboundedSender<ExecutedBlock>,
         mode: MiningMode,
         payload_builder: PayloadBuilderHandle<EngineT>,
-    ) {
+        state_overlay: StateOverlay,
+    ) -> LocalMinerHandle {
         let latest_header =
             provider.sealed_header(provider.best_block_number().unwrap()).unwrap().unwrap();
+        let (to_miner, from_handle) = mpsc::unbounded_channel();
 
         let miner = Self {
             provider,
             payload_attributes_builder,
             to_engine,
+            to_tree,
             mode,
             payload_builder,
             last_timestamp: latest_header.timestamp,
             last_block_hashes: vec![latest_header.hash()],
+            from_handle,
+            time_offset: 0,
+            next_timestamp: None,
+            timestamp_interval: None,
+            state_overlay,
         };
 
         // Spawn the miner
         tokio::spawn(miner.run());
+
+        LocalMinerHandle { to_miner }
     }
 
     /// Runs the [`LocalMiner`] in a loop, polling the miner and building payloads.
@@ -141,10 +263,75 @@ where
                         error!(target: "engine::local", "Error updating fork choice: {:?}", e);
                     }
                 }
+                Some(command) = self.from_handle.recv() => {
+                    self.on_command(command).await;
+                }
+            }
+        }
+    }
+
+    /// Handles a command sent by a [`LocalMinerHandle`].
+    async fn on_command(&mut self, command: MinerCommand) {
+        match command {
+            MinerCommand::Mine { blocks, interval, tx } => {
+                // the number of blocks is user controlled, so the hashes are not preallocated
+                let mut hashes = Vec::new();
+                let mut res = Ok(());
+                for i in 0..blocks {
+                    if let Some(interval) = interval.filter(|_| i > 0) {
+                        self.next_timestamp = Some(self.last_timestamp + interval);
+                    }
+                    match self.advance().await {
+                        Ok(hash) => hashes.push(hash),
+                        Err(err) => {
+                            res = Err(err);
+                            break
+                        }
+                    }
+                }
+                let _ = tx.send(res.map(|_| hashes));
+            }
+            MinerCommand::SetMode(mode) => self.mode = mode,
+            MinerCommand::IsInstant(tx) => {
+                let _ = tx.send(self.mode.is_instant());
+            }
+            MinerCommand::SetNextBlockTimestamp { timestamp, tx } => {
+                let res = if timestamp <= self.last_timestamp {
+                    Err(eyre::eyre!(
+                        "timestamp {timestamp} is not greater than the latest block timestamp {}",
+                        self.last_timestamp
+                    ))
+                } else {
+                    self.next_timestamp = Some(timestamp);
+                    Ok(())
+                };
+                let _ = tx.send(res);
+            }
+            MinerCommand::IncreaseTime { seconds, tx } => {
+                self.time_offset = self.time_offset.saturating_add_unsigned(seconds);
+                let _ = tx.send(self.time_offset);
+            }
+            MinerCommand::SetTime(timestamp) => {
+                self.time_offset = (timestamp as i64).saturating_sub(unix_now() as i64);
+            }
+            MinerCommand::SetBlockTimestampInterval { interval, tx } => {
+                let _ =
+                    tx.send(std::mem::replace(&mut self.timestamp_interval, interval).is_some());
             }
         }
     }
 
+    /// Returns the timestamp for the next block.
+    fn next_block_timestamp(&mut self) -> u64 {
+        if let Some(timestamp) = self.next_timestamp.take() {
+            return timestamp
+        }
+        if let Some(interval) = self.timestamp_interval {
+            return self.last_timestamp + interval
+        }
+        std::cmp::max(self.last_timestamp + 1, unix_now().saturating_add_signed(self.time_offset))
+    }
+
     /// Returns current forkchoice state.
     fn forkchoice_state(&self) -> ForkchoiceState {
         ForkchoiceState {
@@ -179,14 +366,25 @@ where
 
     /// Generates payload attributes for a new block, passes them to FCU and inserts built payload
     /// through newPayload.
-    async fn advance(&mut self) -> eyre::Result<()> {
-        let timestamp = std::cmp::max(
-            self.last_timestamp + 1,
-            std::time::SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .expect("cannot be earlier than UNIX_EPOCH")
-                .as_secs(),
-        );
+    ///
+    /// Returns the hash of the new block.
+    async fn advance(&mut self) -> eyre::Result<B256> {
+        let parent_hash = *self.last_block_hashes.last().expect("at least 1 block exists");
+
+        // queued out-of-band state changes become part of the new block, they are queued again if
+        // it can't be made canonical
+        self.state_overlay.seal(parent_hash);
+        let res = self.build_and_insert().await;
+        match res {
+            Ok(_) => self.state_overlay.commit(parent_hash),
+            Err(_) => self.state_overlay.unseal(parent_hash),
+        }
+        res
+    }
+
+    /// Builds a new block on top of the current head and makes it canonical.
+    async fn build_and_insert(&mut self) -> eyre::Result<B256> {
+        let timestamp = self.next_block_timestamp();
 
         let (tx, rx) = oneshot::channel();
         self.to_engine.send(BeaconEngineMessage::ForkchoiceUpdated {
@@ -208,6 +406,12 @@ where
             eyre::bail!("No payload")
         };
 
+        // the block is inserted with the senders it was built with, since impersonated transactions
+        // don't have a signature their sender can be recovered from
+        if let Some(executed) = payload.executed_block() {
+            self.to_tree.send(executed)?;
+        }
+
         let block = payload.block();
 
         let cancun_fields =
@@ -241,6 +445,14 @@ where
                 self.last_block_hashes.split_off(self.last_block_hashes.len() - 64);
         }
 
-        Ok(())
+        Ok(block.hash())
     }
 }
+
+/// Returns the current unix timestamp in seconds.
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("cannot be earlier than UNIX_EPOCH")
+        .as_secs()
+}
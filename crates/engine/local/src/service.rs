@@ -14,9 +14,10 @@ use std::{
     task::{Context, Poll},
 };
 
-use crate::miner::{LocalMiner, MiningMode};
+use crate::miner::{LocalMiner, LocalMinerHandle, MiningMode};
 use futures_util::{Stream, StreamExt};
 use reth_beacon_consensus::{BeaconConsensusEngineEvent, BeaconEngineMessage, EngineNodeTypes};
+use reth_chain_state::ExecutedBlock;
 use reth_chainspec::EthChainSpec;
 use reth_consensus::Consensus;
 use reth_engine_service::service::EngineMessageStream;
@@ -26,17 +27,18 @@ use reth_engine_tree::{
         EngineApiKind, EngineApiRequest, EngineApiRequestHandler, EngineRequestHandler, FromEngine,
         RequestHandlerEvent,
     },
-    persistence::PersistenceHandle,
+    persistence::{IndexStage, PersistenceHandle},
     tree::{EngineApiTreeHandler, InvalidBlockHook, TreeConfig},
 };
-use reth_evm::execute::BlockExecutorProvider;
+use reth_evm::{execute::BlockExecutorProvider, state_overlay::StateOverlay};
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{PayloadAttributesBuilder, PayloadTypes};
 use reth_payload_validator::ExecutionPayloadValidator;
 use reth_provider::{providers::BlockchainProvider2, ChainSpecProvider, ProviderFactory};
 use reth_prune::PrunerWithFactory;
 use reth_stages_api::MetricEventsSender;
-use tokio::sync::mpsc::UnboundedSender;
+use reth_trie_db::TrieNodeCache;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 use tracing::error;
 
 /// Provides a local dev service engine that can be used to drive the
@@ -54,6 +56,10 @@ where
     handler: EngineApiRequestHandler<EngineApiRequest<N::Engine>>,
     /// Receiver for incoming requests (from the engine API endpoint) that need to be processed.
     incoming_requests: EngineMessageStream<N::Engine>,
+    /// Receiver for blocks built by the [`LocalMiner`].
+    built_blocks: UnboundedReceiver<ExecutedBlock>,
+    /// Handle to the [`LocalMiner`] driving the chain.
+    miner: LocalMinerHandle,
 }
 
 impl<N> LocalEngineService<N>
@@ -76,6 +82,8 @@ where
         from_engine: EngineMessageStream<N::Engine>,
         mode: MiningMode,
         payload_attributes_builder: B,
+        state_overlay: StateOverlay,
+        index_stages: Vec<IndexStage<N>>,
     ) -> Self
     where
         B: PayloadAttributesBuilder<<N::Engine as PayloadTypes>::PayloadAttributes>,
@@ -84,8 +92,14 @@ where
         let engine_kind =
             if chain_spec.is_optimism() { EngineApiKind::OpStack } else { EngineApiKind::Ethereum };
 
-        let persistence_handle =
-            PersistenceHandle::spawn_service(provider, pruner, sync_metrics_tx);
+        let trie_node_cache = tree_config.trie_node_cache_size().map(TrieNodeCache::new);
+        let persistence_handle = PersistenceHandle::spawn_service(
+            provider,
+            pruner,
+            sync_metrics_tx,
+            trie_node_cache,
+            index_stages,
+        );
         let payload_validator = ExecutionPayloadValidator::new(chain_spec);
 
         let canonical_in_memory_state = blockchain_db.canonical_in_memory_state();
@@ -105,15 +119,23 @@ where
 
         let handler = EngineApiRequestHandler::new(to_tree_tx, from_tree);
 
-        LocalMiner::spawn_new(
+        let (to_tree, built_blocks) = unbounded_channel();
+        let miner = LocalMiner::spawn_new(
             blockchain_db,
             payload_attributes_builder,
             to_engine,
+            to_tree,
             mode,
             payload_builder,
+            state_overlay,
         );
 
-        Self { handler, incoming_requests: from_engine }
+        Self { handler, incoming_requests: from_engine, built_blocks, miner }
+    }
+
+    /// Returns a handle to the [`LocalMiner`] driving the chain.
+    pub fn miner_handle(&self) -> LocalMinerHandle {
+        self.miner.clone()
     }
 }
 
@@ -143,6 +165,11 @@ where
             }
         }
 
+        // built blocks are forwarded first, so they are in the tree before their payload arrives
+        while let Poll::Ready(Some(block)) = this.built_blocks.poll_recv(cx) {
+            this.handler.on_event(EngineApiRequest::InsertExecutedBlock(block).into());
+        }
+
         // forward incoming requests to the handler
         while let Poll::Ready(Some(req)) = this.incoming_requests.poll_next_unpin(cx) {
             this.handler.on_event(FromEngine::Request(req.into()));
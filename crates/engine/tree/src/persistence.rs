@@ -1,14 +1,20 @@
 use crate::metrics::PersistenceMetrics;
 use alloy_eips::BlockNumHash;
+use alloy_primitives::BlockNumber;
 use reth_chain_state::ExecutedBlock;
 use reth_errors::ProviderError;
 use reth_provider::{
     providers::ProviderNodeTypes, writer::UnifiedStorageWriter, BlockHashReader,
-    ChainStateBlockWriter, DatabaseProviderFactory, ProviderFactory, StaticFileProviderFactory,
+    ChainStateBlockWriter, DatabaseProviderFactory, ProviderFactory, StageCheckpointReader,
+    StageCheckpointWriter, StaticFileProviderFactory,
 };
 use reth_prune::{PrunerError, PrunerOutput, PrunerWithFactory};
-use reth_stages_api::{MetricEvent, MetricEventsSender};
+use reth_stages_api::{
+    ExecInput, ExecOutput, MetricEvent, MetricEventsSender, Stage, StageError, StageId, UnwindInput,
+};
+use reth_trie_db::TrieNodeCache;
 use std::{
+    fmt,
     sync::mpsc::{Receiver, SendError, Sender},
     time::Instant,
 };
@@ -16,6 +22,10 @@ use thiserror::Error;
 use tokio::sync::oneshot;
 use tracing::{debug, error};
 
+/// A stage that indexes blocks once they were persisted, e.g. the address transactions index.
+pub type IndexStage<N> =
+    Box<dyn Stage<<ProviderFactory<N> as DatabaseProviderFactory>::ProviderRW>>;
+
 /// Writes parts of reth's in memory tree state to the database and static files.
 ///
 /// This is meant to be a spawned service that listens for various incoming persistence operations,
@@ -23,7 +33,6 @@ use tracing::{debug, error};
 ///
 /// This should be spawned in its own thread with [`std::thread::spawn`], since this performs
 /// blocking I/O operations in an endless loop.
-#[derive(Debug)]
 pub struct PersistenceService<N: ProviderNodeTypes> {
     /// The provider factory to use
     provider: ProviderFactory<N>,
@@ -35,6 +44,27 @@ pub struct PersistenceService<N: ProviderNodeTypes> {
     metrics: PersistenceMetrics,
     /// Sender for sync metrics - we only submit sync metrics for persisted blocks
     sync_metrics_tx: MetricEventsSender,
+    /// Cache of database trie nodes, invalidated by the persisted trie updates
+    trie_node_cache: Option<TrieNodeCache>,
+    /// Stages that are run on the persisted blocks and unwound with the removed blocks
+    index_stages: Vec<IndexStage<N>>,
+}
+
+impl<N: ProviderNodeTypes> fmt::Debug for PersistenceService<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PersistenceService")
+            .field("provider", &self.provider)
+            .field("incoming", &self.incoming)
+            .field("pruner", &self.pruner)
+            .field("metrics", &self.metrics)
+            .field("sync_metrics_tx", &self.sync_metrics_tx)
+            .field("trie_node_cache", &self.trie_node_cache)
+            .field(
+                "index_stages",
+                &self.index_stages.iter().map(|stage| stage.id()).collect::<Vec<StageId>>(),
+            )
+            .finish()
+    }
 }
 
 impl<N: ProviderNodeTypes> PersistenceService<N> {
@@ -44,8 +74,18 @@ impl<N: ProviderNodeTypes> PersistenceService<N> {
         incoming: Receiver<PersistenceAction>,
         pruner: PrunerWithFactory<ProviderFactory<N>>,
         sync_metrics_tx: MetricEventsSender,
+        trie_node_cache: Option<TrieNodeCache>,
+        index_stages: Vec<IndexStage<N>>,
     ) -> Self {
-        Self { provider, incoming, pruner, metrics: PersistenceMetrics::default(), sync_metrics_tx }
+        Self {
+            provider,
+            incoming,
+            pruner,
+            metrics: PersistenceMetrics::default(),
+            sync_metrics_tx,
+            trie_node_cache,
+            index_stages,
+        }
     }
 
     /// Prunes block data before the given block hash according to the configured prune
@@ -107,16 +147,68 @@ impl<N: ProviderNodeTypes> PersistenceService<N> {
         Ok(())
     }
 
+    /// Runs the index stages up to the given block, committing after every stage execution.
+    fn run_index_stages(&mut self, tip: BlockNumber) -> Result<(), PersistenceError> {
+        for stage in &mut self.index_stages {
+            let stage_id = stage.id();
+            loop {
+                let provider_rw = self.provider.database_provider_rw()?;
+                let checkpoint = provider_rw.get_stage_checkpoint(stage_id)?.unwrap_or_default();
+                let input = ExecInput { target: Some(tip), checkpoint: Some(checkpoint) };
+                if input.target_reached() {
+                    break
+                }
+
+                debug!(target: "engine::persistence", %stage_id, ?checkpoint, ?tip, "Running index stage");
+                let ExecOutput { checkpoint, done } = stage.execute(&provider_rw, input)?;
+                provider_rw.save_stage_checkpoint(stage_id, checkpoint)?;
+                UnifiedStorageWriter::commit(provider_rw, self.provider.static_file_provider())?;
+                stage.post_execute_commit()?;
+
+                if done {
+                    break
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Unwinds the index stages to the given block, before the blocks above it are removed.
+    fn unwind_index_stages(
+        index_stages: &mut [IndexStage<N>],
+        provider_rw: &<ProviderFactory<N> as DatabaseProviderFactory>::ProviderRW,
+        unwind_to: BlockNumber,
+    ) -> Result<(), PersistenceError> {
+        for stage in index_stages.iter_mut().rev() {
+            let stage_id = stage.id();
+            let Some(mut checkpoint) = provider_rw.get_stage_checkpoint(stage_id)? else {
+                continue
+            };
+            while checkpoint.block_number > unwind_to {
+                debug!(target: "engine::persistence", %stage_id, ?checkpoint, ?unwind_to, "Unwinding index stage");
+                let input = UnwindInput { checkpoint, unwind_to, bad_block: None };
+                checkpoint = stage.unwind(provider_rw, input)?.checkpoint;
+                provider_rw.save_stage_checkpoint(stage_id, checkpoint)?;
+            }
+        }
+        Ok(())
+    }
+
     fn on_remove_blocks_above(
-        &self,
+        &mut self,
         new_tip_num: u64,
     ) -> Result<Option<BlockNumHash>, PersistenceError> {
         debug!(target: "engine::persistence", ?new_tip_num, "Removing blocks");
         let start_time = Instant::now();
+        // The unwound trie nodes are not known here, so the cache is cleared when the guard is
+        // dropped.
+        let _trie_node_cache_write = self.trie_node_cache.as_ref().map(TrieNodeCache::begin_write);
         let provider_rw = self.provider.database_provider_rw()?;
         let sf_provider = self.provider.static_file_provider();
 
         let new_tip_hash = provider_rw.block_hash(new_tip_num)?;
+        // the index stages re-execute the removed blocks, so they're unwound first
+        Self::unwind_index_stages(&mut self.index_stages, &provider_rw, new_tip_num)?;
         UnifiedStorageWriter::from(&provider_rw, &sf_provider).remove_blocks_above(new_tip_num)?;
         UnifiedStorageWriter::commit_unwind(provider_rw, sf_provider)?;
 
@@ -126,7 +218,7 @@ impl<N: ProviderNodeTypes> PersistenceService<N> {
     }
 
     fn on_save_blocks(
-        &self,
+        &mut self,
         blocks: Vec<ExecutedBlock>,
     ) -> Result<Option<BlockNumHash>, PersistenceError> {
         debug!(target: "engine::persistence", first=?blocks.first().map(|b| b.block.num_hash()), last=?blocks.last().map(|b| b.block.num_hash()), "Saving range of blocks");
@@ -136,11 +228,20 @@ impl<N: ProviderNodeTypes> PersistenceService<N> {
             .map(|block| BlockNumHash { hash: block.block().hash(), number: block.block().number });
 
         if last_block_hash_num.is_some() {
+            let trie_node_cache_write =
+                self.trie_node_cache.as_ref().map(TrieNodeCache::begin_write);
             let provider_rw = self.provider.database_provider_rw()?;
             let static_file_provider = self.provider.static_file_provider();
 
             UnifiedStorageWriter::from(&provider_rw, &static_file_provider).save_blocks(&blocks)?;
             UnifiedStorageWriter::commit(provider_rw, static_file_provider)?;
+
+            if let Some(trie_node_cache_write) = trie_node_cache_write {
+                trie_node_cache_write.finish(blocks.iter().map(|block| block.trie_updates()));
+            }
+        }
+        if let Some(BlockNumHash { number, .. }) = last_block_hash_num {
+            self.run_index_stages(number)?;
         }
         self.metrics.save_blocks_duration_seconds.record(start_time.elapsed());
         Ok(last_block_hash_num)
@@ -157,6 +258,10 @@ pub enum PersistenceError {
     /// A provider error
     #[error(transparent)]
     ProviderError(#[from] ProviderError),
+
+    /// An error of an index stage
+    #[error(transparent)]
+    StageError(#[from] StageError),
 }
 
 /// A signal to the persistence service that part of the tree state can be persisted.
@@ -191,29 +296,43 @@ pub enum PersistenceAction {
 pub struct PersistenceHandle {
     /// The channel used to communicate with the persistence service
     sender: Sender<PersistenceAction>,
+    /// Cache of database trie nodes kept consistent by the persistence service
+    trie_node_cache: Option<TrieNodeCache>,
 }
 
 impl PersistenceHandle {
     /// Create a new [`PersistenceHandle`] from a [`Sender<PersistenceAction>`].
     pub const fn new(sender: Sender<PersistenceAction>) -> Self {
-        Self { sender }
+        Self { sender, trie_node_cache: None }
     }
 
     /// Create a new [`PersistenceHandle`], and spawn the persistence service.
+    ///
+    /// If a trie node cache is given, the persistence service invalidates it on every write. The
+    /// given index stages are run on the persisted blocks, and unwound when blocks are removed.
     pub fn spawn_service<N: ProviderNodeTypes>(
         provider_factory: ProviderFactory<N>,
         pruner: PrunerWithFactory<ProviderFactory<N>>,
         sync_metrics_tx: MetricEventsSender,
+        trie_node_cache: Option<TrieNodeCache>,
+        index_stages: Vec<IndexStage<N>>,
     ) -> Self {
         // create the initial channels
         let (db_service_tx, db_service_rx) = std::sync::mpsc::channel();
 
         // construct persistence handle
-        let persistence_handle = Self::new(db_service_tx);
+        let persistence_handle =
+            Self { sender: db_service_tx, trie_node_cache: trie_node_cache.clone() };
 
         // spawn the persistence service
-        let db_service =
-            PersistenceService::new(provider_factory, db_service_rx, pruner, sync_metrics_tx);
+        let db_service = PersistenceService::new(
+            provider_factory,
+            db_service_rx,
+            pruner,
+            sync_metrics_tx,
+            trie_node_cache,
+            index_stages,
+        );
         std::thread::Builder::new()
             .name("Persistence Service".to_string())
             .spawn(|| {
@@ -226,6 +345,11 @@ impl PersistenceHandle {
         persistence_handle
     }
 
+    /// Returns the cache of database trie nodes kept consistent by the persistence service.
+    pub const fn trie_node_cache(&self) -> Option<&TrieNodeCache> {
+        self.trie_node_cache.as_ref()
+    }
+
     /// Sends a specific [`PersistenceAction`] in the contained channel. The caller is responsible
     /// for creating any channels for the given action.
     pub fn send_action(
@@ -313,7 +437,7 @@ mod tests {
             Pruner::new_with_factory(provider.clone(), vec![], 5, 0, None, finished_exex_height_rx);
 
         let (sync_metrics_tx, _sync_metrics_rx) = unbounded_channel();
-        PersistenceHandle::spawn_service(provider, pruner, sync_metrics_tx)
+        PersistenceHandle::spawn_service(provider, pruner, sync_metrics_tx, None, Vec::new())
     }
 
     #[tokio::test]
@@ -32,6 +32,9 @@ pub struct TreeConfig {
     /// This is used as a cutoff to prevent long-running sequential block execution when we receive
     /// a batch of downloaded blocks.
     max_execute_block_batch_size: usize,
+    /// Maximum memory, in bytes, of the cache of database trie nodes used for state root
+    /// computation. The cache is disabled if `None`.
+    trie_node_cache_size: Option<usize>,
 }
 
 impl Default for TreeConfig {
@@ -42,6 +45,7 @@ impl Default for TreeConfig {
             block_buffer_limit: DEFAULT_BLOCK_BUFFER_LIMIT,
             max_invalid_header_cache_length: DEFAULT_MAX_INVALID_HEADER_CACHE_LENGTH,
             max_execute_block_batch_size: DEFAULT_MAX_EXECUTE_BLOCK_BATCH_SIZE,
+            trie_node_cache_size: None,
         }
     }
 }
@@ -61,6 +65,7 @@ impl TreeConfig {
             block_buffer_limit,
             max_invalid_header_cache_length,
             max_execute_block_batch_size,
+            trie_node_cache_size: None,
         }
     }
 
@@ -89,6 +94,11 @@ impl TreeConfig {
         self.max_execute_block_batch_size
     }
 
+    /// Return the maximum memory of the trie node cache, if enabled.
+    pub const fn trie_node_cache_size(&self) -> Option<usize> {
+        self.trie_node_cache_size
+    }
+
     /// Setter for persistence threshold.
     pub const fn with_persistence_threshold(mut self, persistence_threshold: u64) -> Self {
         self.persistence_threshold = persistence_threshold;
@@ -127,4 +137,10 @@ impl TreeConfig {
         self.max_execute_block_batch_size = max_execute_block_batch_size;
         self
     }
+
+    /// Setter for the maximum memory of the trie node cache. `None` disables the cache.
+    pub const fn with_trie_node_cache_size(mut self, trie_node_cache_size: Option<usize>) -> Self {
+        self.trie_node_cache_size = trie_node_cache_size;
+        self
+    }
 }
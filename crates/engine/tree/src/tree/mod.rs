@@ -36,9 +36,9 @@ use reth_primitives::{
     Block, GotExpected, Header, SealedBlock, SealedBlockWithSenders, SealedHeader,
 };
 use reth_provider::{
-    providers::ConsistentDbView, BlockReader, DatabaseProviderFactory, ExecutionOutcome,
-    ProviderError, StateProviderBox, StateProviderFactory, StateReader, StateRootProvider,
-    TransactionVariant,
+    providers::ConsistentDbView, BlockReader, ChangeSetReader, DatabaseProviderFactory,
+    ExecutionOutcome, ProviderError, StateProviderBox, StateProviderFactory, StateReader,
+    StateRootProvider, StorageChangeSetReader, TransactionVariant,
 };
 use reth_revm::database::StateProviderDatabase;
 use reth_stages_api::ControlFlow;
@@ -533,7 +533,8 @@ impl<P: Debug, E: Debug, T: EngineTypes + Debug, Spec: Debug> std::fmt::Debug
 impl<P, E, T, Spec> EngineApiTreeHandler<P, E, T, Spec>
 where
     P: DatabaseProviderFactory + BlockReader + StateProviderFactory + StateReader + Clone + 'static,
-    <P as DatabaseProviderFactory>::Provider: BlockReader,
+    <P as DatabaseProviderFactory>::Provider:
+        BlockReader + ChangeSetReader + StorageChangeSetReader,
     E: BlockExecutorProvider,
     T: EngineTypes,
     Spec: Send + Sync + EthereumHardforks + 'static,
@@ -1296,6 +1297,11 @@ where
         debug!(target: "engine::tree", "received backfill sync finished event");
         self.backfill_sync_state = BackfillSyncState::Idle;
 
+        // The pipeline writes trie nodes without going through the persistence service.
+        if let Some(trie_node_cache) = self.persistence.trie_node_cache() {
+            trie_node_cache.clear();
+        }
+
         // Pipeline unwound, memorize the invalid block and wait for CL for next sync target.
         if let ControlFlow::Unwind { bad_block, .. } = ctrl {
             warn!(target: "engine::tree", invalid_hash=?bad_block.hash(), invalid_number=?bad_block.number, "Bad block detected in unwind");
@@ -2119,6 +2125,12 @@ where
         &mut self,
         block: SealedBlock,
     ) -> Result<InsertPayloadOk2, InsertBlockErrorTwo> {
+        // blocks inserted as executed blocks already have their senders, which can't always be
+        // recovered from the signatures, e.g. for impersonated transactions on dev chains
+        if self.state.tree_state.executed_block_by_hash(block.hash()).is_some() {
+            return Ok(InsertPayloadOk2::AlreadySeen(BlockStatus2::Valid))
+        }
+
         match block.try_seal_with_senders() {
             Ok(block) => self.insert_block(block),
             Err(block) => Err(InsertBlockErrorTwo::sender_recovery_error(block)),
@@ -2318,7 +2330,9 @@ where
         // Extend with block we are validating root for.
         input.append_ref(hashed_state);
 
-        ParallelStateRoot::new(consistent_view, input).incremental_root_with_updates()
+        ParallelStateRoot::new(consistent_view, input)
+            .with_trie_node_cache(self.persistence.trie_node_cache().cloned())
+            .incremental_root_with_updates()
     }
 
     /// Handles an error that occurred while inserting a block.
@@ -17,6 +17,7 @@ use reth_evm::{
         BlockExecutionStrategyFactory, BlockValidationError, ExecuteOutput, ProviderError,
     },
     state_change::post_block_balance_increments,
+    state_overlay::StateOverlay,
     system_calls::{OnStateHook, SystemCaller},
     ConfigureEvm,
 };
@@ -34,6 +35,8 @@ pub struct EthExecutionStrategyFactory<EvmConfig = EthEvmConfig> {
     chain_spec: Arc<ChainSpec>,
     /// How to create an EVM.
     evm_config: EvmConfig,
+    /// Out-of-band state changes applied before executing a block, used by dev chains.
+    state_overlay: Option<StateOverlay>,
 }
 
 impl EthExecutionStrategyFactory {
@@ -51,7 +54,14 @@ impl EthExecutionStrategyFactory {
 impl<EvmConfig> EthExecutionStrategyFactory<EvmConfig> {
     /// Creates a new executor strategy factory.
     pub const fn new(chain_spec: Arc<ChainSpec>, evm_config: EvmConfig) -> Self {
-        Self { chain_spec, evm_config }
+        Self { chain_spec, evm_config, state_overlay: None }
+    }
+
+    /// Applies the changes of the given [`StateOverlay`] sealed for the parent of a block before
+    /// executing it.
+    pub fn with_state_overlay(mut self, state_overlay: StateOverlay) -> Self {
+        self.state_overlay = Some(state_overlay);
+        self
     }
 }
 
@@ -69,7 +79,10 @@ where
     {
         let state =
             State::builder().with_database(db).with_bundle_update().without_state_clear().build();
-        EthExecutionStrategy::new(state, self.chain_spec.clone(), self.evm_config.clone())
+        let mut strategy =
+            EthExecutionStrategy::new(state, self.chain_spec.clone(), self.evm_config.clone());
+        strategy.state_overlay = self.state_overlay.clone();
+        strategy
     }
 }
 
@@ -87,6 +100,8 @@ where
     state: State<DB>,
     /// Utility to call system smart contracts.
     system_caller: SystemCaller<EvmConfig, ChainSpec>,
+    /// Out-of-band state changes applied before executing a block.
+    state_overlay: Option<StateOverlay>,
 }
 
 impl<DB, EvmConfig> EthExecutionStrategy<DB, EvmConfig>
@@ -96,7 +111,7 @@ where
     /// Creates a new [`EthExecutionStrategy`]
     pub fn new(state: State<DB>, chain_spec: Arc<ChainSpec>, evm_config: EvmConfig) -> Self {
         let system_caller = SystemCaller::new(evm_config.clone(), (*chain_spec).clone());
-        Self { state, chain_spec, evm_config, system_caller }
+        Self { state, chain_spec, evm_config, system_caller, state_overlay: None }
     }
 }
 
@@ -140,6 +155,12 @@ where
             (*self.chain_spec).is_spurious_dragon_active_at_block(block.header.number);
         self.state.set_state_clear_flag(state_clear_flag);
 
+        if let Some(state_overlay) = &self.state_overlay {
+            state_overlay
+                .apply(block.parent_hash, &mut self.state)
+                .map_err(|err| BlockExecutionError::from(Into::<ProviderError>::into(err)))?;
+        }
+
         let env = self.evm_env_for_block(&block.header, total_difficulty);
         let mut evm = self.evm_config.evm_with_env(&mut self.state, env);
 
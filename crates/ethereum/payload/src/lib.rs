@@ -19,7 +19,9 @@ use reth_basic_payload_builder::{
 use reth_chain_state::ExecutedBlock;
 use reth_chainspec::ChainSpec;
 use reth_errors::RethError;
-use reth_evm::{system_calls::SystemCaller, ConfigureEvm, NextBlockEnvAttributes};
+use reth_evm::{
+    state_overlay::StateOverlay, system_calls::SystemCaller, ConfigureEvm, NextBlockEnvAttributes,
+};
 use reth_evm_ethereum::{eip6110::parse_deposits_from_receipts, EthEvmConfig};
 use reth_execution_types::ExecutionOutcome;
 use reth_payload_builder::{EthBuiltPayload, EthPayloadBuilderAttributes};
@@ -46,16 +48,25 @@ use std::sync::Arc;
 use tracing::{debug, trace, warn};
 
 /// Ethereum payload builder
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct EthereumPayloadBuilder<EvmConfig = EthEvmConfig> {
     /// The type responsible for creating the evm.
     evm_config: EvmConfig,
+    /// Out-of-band state changes applied on top of the parent state, used by dev chains.
+    state_overlay: Option<StateOverlay>,
 }
 
 impl<EvmConfig> EthereumPayloadBuilder<EvmConfig> {
     /// `EthereumPayloadBuilder` constructor.
     pub const fn new(evm_config: EvmConfig) -> Self {
-        Self { evm_config }
+        Self { evm_config, state_overlay: None }
+    }
+
+    /// Applies the changes of the given [`StateOverlay`] sealed for the parent of a payload when
+    /// building it.
+    pub fn with_state_overlay(mut self, state_overlay: StateOverlay) -> Self {
+        self.state_overlay = Some(state_overlay);
+        self
     }
 }
 
@@ -75,7 +86,12 @@ where
             suggested_fee_recipient: config.attributes.suggested_fee_recipient(),
             prev_randao: config.attributes.prev_randao(),
         };
-        self.evm_config.next_cfg_and_block_env(parent, next_attributes)
+        let (cfg_env, mut block_env) =
+            self.evm_config.next_cfg_and_block_env(parent, next_attributes);
+        if let Some(state_overlay) = &self.state_overlay {
+            state_overlay.apply_block_env(config.parent_block.hash(), &mut block_env);
+        }
+        (cfg_env, block_env)
     }
 }
 
@@ -94,7 +110,13 @@ where
         args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
     ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError> {
         let (cfg_env, block_env) = self.cfg_and_block_env(&args.config, &args.config.parent_block);
-        default_ethereum_payload(self.evm_config.clone(), args, cfg_env, block_env)
+        default_ethereum_payload(
+            self.evm_config.clone(),
+            args,
+            cfg_env,
+            block_env,
+            self.state_overlay.as_ref(),
+        )
     }
 
     fn build_empty_payload(
@@ -112,9 +134,15 @@ where
             best_payload: None,
         };
         let (cfg_env, block_env) = self.cfg_and_block_env(&args.config, &args.config.parent_block);
-        default_ethereum_payload(self.evm_config.clone(), args, cfg_env, block_env)?
-            .into_payload()
-            .ok_or_else(|| PayloadBuilderError::MissingPayload)
+        default_ethereum_payload(
+            self.evm_config.clone(),
+            args,
+            cfg_env,
+            block_env,
+            self.state_overlay.as_ref(),
+        )?
+        .into_payload()
+        .ok_or_else(|| PayloadBuilderError::MissingPayload)
     }
 }
 
@@ -123,12 +151,16 @@ where
 /// Given build arguments including an Ethereum client, transaction pool,
 /// and configuration, this function creates a transaction payload. Returns
 /// a result indicating success with the payload or an error in case of failure.
+///
+/// If a [`StateOverlay`] is given, the changes sealed for the parent block are applied before any
+/// system call.
 #[inline]
 pub fn default_ethereum_payload<EvmConfig, Pool, Client>(
     evm_config: EvmConfig,
     args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
     initialized_cfg: CfgEnvWithHandlerCfg,
     initialized_block_env: BlockEnv,
+    state_overlay: Option<&StateOverlay>,
 ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError>
 where
     EvmConfig: ConfigureEvm<Header = Header>,
@@ -153,15 +185,26 @@ where
     let mut executed_txs = Vec::new();
     let mut executed_senders = Vec::new();
 
-    let mut best_txs = pool.best_transactions_with_attributes(BestTransactionsAttributes::new(
-        base_fee,
-        initialized_block_env.get_blob_gasprice().map(|gasprice| gasprice as u64),
-    ));
+    let mut best_txs = pool.best_transactions_with_attributes(
+        BestTransactionsAttributes::new(
+            base_fee,
+            initialized_block_env.get_blob_gasprice().map(|gasprice| gasprice as u64),
+        )
+        .with_block(
+            parent_block.hash(),
+            initialized_block_env.number.to::<u64>(),
+            initialized_block_env.timestamp.to::<u64>(),
+        ),
+    );
 
     let mut total_fees = U256::ZERO;
 
     let block_number = initialized_block_env.number.to::<u64>();
 
+    if let Some(state_overlay) = state_overlay {
+        state_overlay.apply(parent_block.hash(), &mut db)?;
+    }
+
     let mut system_caller = SystemCaller::new(evm_config.clone(), chain_spec.clone());
 
     // apply eip-4788 pre block contract call
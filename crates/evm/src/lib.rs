@@ -32,6 +32,8 @@ pub mod metrics;
 pub mod noop;
 pub mod provider;
 pub mod state_change;
+#[cfg(feature = "std")]
+pub mod state_overlay;
 pub mod system_calls;
 
 #[cfg(any(test, feature = "test-utils"))]
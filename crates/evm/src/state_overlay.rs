@@ -0,0 +1,326 @@
+//! Out-of-band state changes for dev chains.
+//!
+//! A [`StateOverlay`] collects changes to accounts and to the block environment that don't
+//! originate from transactions, e.g. `anvil_setBalance`. Before a block is built, the pending
+//! changes are sealed into a batch keyed by the hash of the parent block. Both the payload builder
+//! and the block executor apply that batch on top of the parent state before any system call, so
+//! the changes become part of the block's state transition and its changesets.
+
+use alloy_primitives::{map::HashMap, Address, Bytes, B256, U256};
+use parking_lot::Mutex;
+use revm::{db::State, Database, DatabaseCommit};
+use revm_primitives::{Account, AccountStatus, BlockEnv, Bytecode, EvmStorageSlot};
+use std::sync::Arc;
+
+/// Changes to a single account.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountOverlay {
+    /// The new balance of the account.
+    pub balance: Option<U256>,
+    /// The new nonce of the account.
+    pub nonce: Option<u64>,
+    /// The new code of the account.
+    pub code: Option<Bytes>,
+    /// The new values of individual storage slots.
+    pub storage: HashMap<U256, U256>,
+}
+
+impl AccountOverlay {
+    /// Merges the given, newer, changes into this overlay.
+    pub fn extend(&mut self, other: Self) {
+        if other.balance.is_some() {
+            self.balance = other.balance;
+        }
+        if other.nonce.is_some() {
+            self.nonce = other.nonce;
+        }
+        if other.code.is_some() {
+            self.code = other.code;
+        }
+        self.storage.extend(other.storage);
+    }
+}
+
+/// Overrides of the block environment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlockEnvOverlay {
+    /// The beneficiary of all following blocks.
+    pub coinbase: Option<Address>,
+    /// The gas limit of all following blocks.
+    pub gas_limit: Option<u64>,
+    /// The base fee of the next block only.
+    pub base_fee: Option<u64>,
+}
+
+impl BlockEnvOverlay {
+    /// Applies the overrides to the given [`BlockEnv`].
+    pub fn apply(&self, block_env: &mut BlockEnv) {
+        if let Some(coinbase) = self.coinbase {
+            block_env.coinbase = coinbase;
+        }
+        if let Some(gas_limit) = self.gas_limit {
+            block_env.gas_limit = U256::from(gas_limit);
+        }
+        if let Some(base_fee) = self.base_fee {
+            block_env.basefee = U256::from(base_fee);
+        }
+    }
+}
+
+/// Changes sealed for the block built on top of a specific parent.
+#[derive(Debug, Clone, Default)]
+struct SealedBatch {
+    accounts: HashMap<Address, AccountOverlay>,
+    block_env: BlockEnvOverlay,
+}
+
+#[derive(Debug, Default)]
+struct StateOverlayInner {
+    /// Changes that are not yet assigned to a block.
+    pending: HashMap<Address, AccountOverlay>,
+    /// Overrides of the block environment for the next blocks.
+    block_env: BlockEnvOverlay,
+    /// Batches of changes, keyed by the hash of the parent of the block they are applied in.
+    sealed: HashMap<B256, SealedBatch>,
+}
+
+/// Shared collection of out-of-band state changes, see the [module docs](self).
+///
+/// Cloning the overlay is cheap, all clones share the same changes.
+#[derive(Debug, Clone, Default)]
+pub struct StateOverlay {
+    inner: Arc<Mutex<StateOverlayInner>>,
+}
+
+impl StateOverlay {
+    /// Queues changes to the given account, they are merged into any changes queued before.
+    pub fn update(&self, address: Address, changes: AccountOverlay) {
+        self.inner.lock().pending.entry(address).or_default().extend(changes);
+    }
+
+    /// Returns a copy of all queued account changes.
+    pub fn pending(&self) -> HashMap<Address, AccountOverlay> {
+        self.inner.lock().pending.clone()
+    }
+
+    /// Replaces all queued account changes.
+    pub fn set_pending(&self, pending: HashMap<Address, AccountOverlay>) {
+        self.inner.lock().pending = pending;
+    }
+
+    /// Returns the overrides of the block environment.
+    pub fn block_env(&self) -> BlockEnvOverlay {
+        self.inner.lock().block_env
+    }
+
+    /// Modifies the overrides of the block environment.
+    pub fn update_block_env(&self, f: impl FnOnce(&mut BlockEnvOverlay)) {
+        f(&mut self.inner.lock().block_env)
+    }
+
+    /// Seals all queued changes for the block built on top of `parent_hash`.
+    ///
+    /// The one-shot base fee override is moved into the batch as well.
+    pub fn seal(&self, parent_hash: B256) {
+        let mut inner = self.inner.lock();
+        let accounts = std::mem::take(&mut inner.pending);
+        let block_env = inner.block_env;
+        inner.block_env.base_fee = None;
+        inner.sealed.insert(parent_hash, SealedBatch { accounts, block_env });
+    }
+
+    /// Drops the batch sealed for `parent_hash`, after a block containing it was made canonical.
+    pub fn commit(&self, parent_hash: B256) {
+        self.inner.lock().sealed.remove(&parent_hash);
+    }
+
+    /// Moves the batch sealed for `parent_hash` back to the queued changes, if no block could be
+    /// made canonical with it.
+    ///
+    /// Changes queued in the meantime take precedence.
+    pub fn unseal(&self, parent_hash: B256) {
+        let mut inner = self.inner.lock();
+        let Some(SealedBatch { mut accounts, block_env }) = inner.sealed.remove(&parent_hash)
+        else {
+            return
+        };
+        for (address, changes) in std::mem::take(&mut inner.pending) {
+            accounts.entry(address).or_default().extend(changes);
+        }
+        inner.pending = accounts;
+        if inner.block_env.base_fee.is_none() {
+            inner.block_env.base_fee = block_env.base_fee;
+        }
+    }
+
+    /// Applies the block environment overrides sealed for `parent_hash` to the given
+    /// [`BlockEnv`].
+    pub fn apply_block_env(&self, parent_hash: B256, block_env: &mut BlockEnv) {
+        if let Some(batch) = self.inner.lock().sealed.get(&parent_hash) {
+            batch.block_env.apply(block_env);
+        }
+    }
+
+    /// Commits the account changes sealed for `parent_hash` to the given [`State`].
+    pub fn apply<DB: Database>(
+        &self,
+        parent_hash: B256,
+        state: &mut State<DB>,
+    ) -> Result<(), DB::Error> {
+        let Some(accounts) =
+            self.inner.lock().sealed.get(&parent_hash).map(|batch| batch.accounts.clone())
+        else {
+            return Ok(())
+        };
+
+        let mut changes = HashMap::with_capacity_and_hasher(accounts.len(), Default::default());
+        for (address, overlay) in accounts {
+            // loads the account into the cache, which is required to commit changes to it
+            let mut info = state.basic(address)?.unwrap_or_default();
+            if let Some(balance) = overlay.balance {
+                info.balance = balance;
+            }
+            if let Some(nonce) = overlay.nonce {
+                info.nonce = nonce;
+            }
+            if let Some(code) = overlay.code {
+                let code = Bytecode::new_raw(code);
+                info.code_hash = code.hash_slow();
+                info.code = Some(code);
+            }
+
+            let mut storage = HashMap::default();
+            for (slot, value) in overlay.storage {
+                let original = state.storage(address, slot)?;
+                storage.insert(slot, EvmStorageSlot::new_changed(original, value));
+            }
+
+            changes.insert(address, Account { info, storage, status: AccountStatus::Touched });
+        }
+        state.commit(changes);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::db::{states::bundle_state::BundleRetention, EmptyDB};
+    use revm_primitives::{AccountInfo, KECCAK_EMPTY};
+
+    fn state() -> State<EmptyDB> {
+        let mut state = State::builder().with_bundle_update().build();
+        state.insert_account_with_storage(
+            Address::with_last_byte(1),
+            AccountInfo { balance: U256::from(1), nonce: 1, ..Default::default() },
+            HashMap::from_iter([(U256::from(1), U256::from(1))]),
+        );
+        state
+    }
+
+    #[test]
+    fn apply_sealed_batch() {
+        let overlay = StateOverlay::default();
+        let parent = B256::with_last_byte(1);
+        let existing = Address::with_last_byte(1);
+        let new = Address::with_last_byte(2);
+
+        overlay.update(
+            existing,
+            AccountOverlay {
+                balance: Some(U256::from(10)),
+                storage: HashMap::from_iter([(U256::from(1), U256::from(2))]),
+                ..Default::default()
+            },
+        );
+        overlay.update(existing, AccountOverlay { nonce: Some(5), ..Default::default() });
+        overlay.update(
+            new,
+            AccountOverlay { code: Some(Bytes::from_static(&[0x60, 0x00])), ..Default::default() },
+        );
+
+        // nothing is applied before the changes are sealed
+        let mut db = state();
+        overlay.apply(parent, &mut db).unwrap();
+        db.merge_transitions(BundleRetention::Reverts);
+        assert!(db.take_bundle().state.is_empty());
+
+        overlay.seal(parent);
+        assert!(overlay.pending().is_empty());
+
+        // a block on another parent is not affected
+        let mut db = state();
+        overlay.apply(B256::ZERO, &mut db).unwrap();
+        db.merge_transitions(BundleRetention::Reverts);
+        assert!(db.take_bundle().state.is_empty());
+
+        let mut db = state();
+        overlay.apply(parent, &mut db).unwrap();
+        db.merge_transitions(BundleRetention::Reverts);
+        let bundle = db.take_bundle();
+
+        let account = bundle.account(&existing).unwrap();
+        let info = account.info.as_ref().unwrap();
+        assert_eq!(info.balance, U256::from(10));
+        assert_eq!(info.nonce, 5);
+        assert_eq!(info.code_hash, KECCAK_EMPTY);
+        assert_eq!(account.storage_slot(U256::from(1)), Some(U256::from(2)));
+        assert_eq!(account.original_info.as_ref().unwrap().balance, U256::from(1));
+
+        let info = bundle.account(&new).unwrap().info.as_ref().unwrap();
+        assert_eq!(info.code_hash, alloy_primitives::keccak256([0x60, 0x00]));
+        assert_eq!(info.balance, U256::ZERO);
+
+        overlay.commit(parent);
+        let mut db = state();
+        overlay.apply(parent, &mut db).unwrap();
+        db.merge_transitions(BundleRetention::Reverts);
+        assert!(db.take_bundle().state.is_empty());
+    }
+
+    #[test]
+    fn unseal_keeps_newer_changes() {
+        let overlay = StateOverlay::default();
+        let parent = B256::with_last_byte(1);
+        let address = Address::with_last_byte(1);
+
+        overlay.update(
+            address,
+            AccountOverlay { balance: Some(U256::from(1)), nonce: Some(1), ..Default::default() },
+        );
+        overlay.update_block_env(|env| {
+            env.base_fee = Some(7);
+            env.gas_limit = Some(30_000_000);
+        });
+        overlay.seal(parent);
+
+        // the base fee override is consumed by the sealed batch, the gas limit is kept
+        assert_eq!(
+            overlay.block_env(),
+            BlockEnvOverlay { gas_limit: Some(30_000_000), ..Default::default() }
+        );
+        let mut block_env = BlockEnv::default();
+        overlay.apply_block_env(parent, &mut block_env);
+        assert_eq!(block_env.basefee, U256::from(7));
+        assert_eq!(block_env.gas_limit, U256::from(30_000_000));
+
+        overlay
+            .update(address, AccountOverlay { balance: Some(U256::from(2)), ..Default::default() });
+        overlay.unseal(parent);
+
+        assert_eq!(
+            overlay.pending().get(&address),
+            Some(&AccountOverlay {
+                balance: Some(U256::from(2)),
+                nonce: Some(1),
+                ..Default::default()
+            })
+        );
+        assert_eq!(overlay.block_env().base_fee, Some(7));
+
+        let mut block_env = BlockEnv::default();
+        overlay.apply_block_env(parent, &mut block_env);
+        assert_eq!(block_env, BlockEnv::default());
+    }
+}
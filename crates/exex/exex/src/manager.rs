@@ -9,7 +9,7 @@ use reth_chainspec::Head;
 use reth_metrics::{metrics::Counter, Metrics};
 use reth_primitives::{BlockNumHash, SealedHeader};
 use reth_provider::HeaderProvider;
-use reth_tracing::tracing::debug;
+use reth_tracing::tracing::{debug, warn};
 use std::{
     collections::VecDeque,
     fmt::Debug,
@@ -46,6 +46,91 @@ pub enum ExExNotificationSource {
     BlockchainTree,
 }
 
+/// The policy that the [`ExExManager`] applies to an `ExEx` that can't keep up with the
+/// notifications.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExExBackpressurePolicy {
+    /// The manager waits for the `ExEx`.
+    ///
+    /// The notifications are buffered until the `ExEx` receives them, and the finished height of
+    /// the node and the finalization of the WAL are held back by the `ExEx`.
+    #[default]
+    Blocking,
+    /// The `ExEx` is detached once it lags more than `max_lag` notifications behind the manager.
+    ///
+    /// A detached `ExEx` doesn't receive any notifications anymore, and doesn't hold back the
+    /// notifications buffer, the finished height of the node or the finalization of the WAL.
+    BoundedLag {
+        /// The maximum number of notifications that the `ExEx` can lag behind.
+        max_lag: usize,
+    },
+    /// The `ExEx` is detached once it lags more than `max_lag` notifications behind the manager,
+    /// and catches up from the WAL once it receives notifications again.
+    ///
+    /// The catch-up starts at the notification that committed the finished height of the `ExEx`,
+    /// and the notifications are read from the WAL one by one on a blocking task.
+    ///
+    /// While detached, the `ExEx` doesn't hold back the notifications buffer and the finished
+    /// height of the node, but the WAL isn't finalized beyond its finished height, so that the
+    /// notifications it missed can be replayed from the WAL. Notifications that weren't committed
+    /// to the WAL, i.e. notifications from the pipeline, are not replayed.
+    CatchUpFromWal {
+        /// The maximum number of notifications that the `ExEx` can lag behind.
+        max_lag: usize,
+    },
+}
+
+impl ExExBackpressurePolicy {
+    /// Returns the maximum number of notifications that the `ExEx` can lag behind, if any.
+    pub const fn max_lag(&self) -> Option<usize> {
+        match self {
+            Self::Blocking => None,
+            Self::BoundedLag { max_lag } | Self::CatchUpFromWal { max_lag } => Some(*max_lag),
+        }
+    }
+}
+
+/// An `ExEx` that was detached by the [`ExExManager`] according to its
+/// [`ExExBackpressurePolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetachedExEx {
+    /// The execution extension's ID.
+    pub id: String,
+    /// The policy that detached the `ExEx`.
+    ///
+    /// An `ExEx` with the [`ExExBackpressurePolicy::CatchUpFromWal`] policy is attached again
+    /// once it catches up.
+    pub policy: ExExBackpressurePolicy,
+    /// The number of notifications the `ExEx` lagged behind when it was detached.
+    pub lag: usize,
+}
+
+/// The state of an `ExEx` in the [`ExExManager`].
+#[derive(Debug)]
+enum ExExState {
+    /// The `ExEx` receives notifications from the buffer.
+    Attached,
+    /// The `ExEx` lagged too far behind and doesn't receive notifications until it has capacity
+    /// to catch up from the WAL.
+    Lagging {
+        /// The number of notifications the `ExEx` lagged behind when it was detached.
+        lag: usize,
+    },
+    /// The `ExEx` receives the notifications replayed from the WAL before it receives
+    /// notifications from the buffer again.
+    CatchingUp {
+        /// The notifications read from the WAL by a blocking task.
+        notifications: mpsc::Receiver<eyre::Result<ExExNotification>>,
+        /// A notification that was read from the WAL, but not sent to the `ExEx` yet.
+        pending: Option<ExExNotification>,
+    },
+    /// The `ExEx` lagged too far behind and doesn't receive any notifications anymore.
+    Detached {
+        /// The number of notifications the `ExEx` lagged behind when it was detached.
+        lag: usize,
+    },
+}
+
 /// Metrics for an `ExEx`.
 #[derive(Metrics)]
 #[metrics(scope = "exex")]
@@ -54,6 +139,8 @@ struct ExExMetrics {
     notifications_sent_total: Counter,
     /// The total number of events an `ExEx` has sent to the manager.
     events_sent_total: Counter,
+    /// The number of buffered notifications that an `ExEx` has not received yet.
+    notifications_lag: Gauge,
 }
 
 /// A handle to an `ExEx` used by the [`ExExManager`] to communicate with `ExEx`'s.
@@ -67,6 +154,10 @@ pub struct ExExHandle {
     id: String,
     /// Metrics for an `ExEx`.
     metrics: ExExMetrics,
+    /// The backpressure policy of the `ExEx`.
+    policy: ExExBackpressurePolicy,
+    /// The state of the `ExEx` according to its backpressure policy.
+    state: ExExState,
     /// Channel to send [`ExExNotification`]s to the `ExEx`.
     sender: PollSender<ExExNotification>,
     /// Channel to receive [`ExExEvent`]s from the `ExEx`.
@@ -100,6 +191,8 @@ impl ExExHandle {
             Self {
                 id: id.clone(),
                 metrics: ExExMetrics::new_with_labels(&[("exex", id)]),
+                policy: ExExBackpressurePolicy::default(),
+                state: ExExState::Attached,
                 sender: PollSender::new(notification_tx),
                 receiver: event_rx,
                 next_notification_id: 0,
@@ -110,6 +203,38 @@ impl ExExHandle {
         )
     }
 
+    /// Sets the backpressure policy of the `ExEx`.
+    ///
+    /// Defaults to [`ExExBackpressurePolicy::Blocking`].
+    pub const fn with_backpressure_policy(mut self, policy: ExExBackpressurePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Returns the backpressure policy of the `ExEx`.
+    pub const fn backpressure_policy(&self) -> ExExBackpressurePolicy {
+        self.policy
+    }
+
+    /// Returns the detached `ExEx`, if it's detached.
+    fn detached(&self) -> Option<DetachedExEx> {
+        let lag = match self.state {
+            ExExState::Attached | ExExState::CatchingUp { .. } => return None,
+            ExExState::Lagging { lag } | ExExState::Detached { lag } => lag,
+        };
+        Some(DetachedExEx { id: self.id.clone(), policy: self.policy, lag })
+    }
+
+    /// Returns `true` if the `ExEx` receives notifications from the buffer.
+    const fn is_attached(&self) -> bool {
+        matches!(self.state, ExExState::Attached | ExExState::CatchingUp { .. })
+    }
+
+    /// Returns `true` if the `ExEx` was detached and will not be attached again.
+    const fn is_detached_permanently(&self) -> bool {
+        matches!(self.state, ExExState::Detached { .. })
+    }
+
     /// Reserves a slot in the `PollSender` channel and sends the notification if the slot was
     /// successfully reserved.
     ///
@@ -118,6 +243,76 @@ impl ExExHandle {
         &mut self,
         cx: &mut Context<'_>,
         (notification_id, notification): &(usize, ExExNotification),
+    ) -> Poll<Result<(), PollSendError<ExExNotification>>> {
+        let result = ready!(self.send_notification(cx, Some(*notification_id), notification));
+        if result.is_ok() {
+            self.next_notification_id = notification_id + 1;
+        }
+        Poll::Ready(result)
+    }
+
+    /// Starts replaying the notifications from the WAL, beginning with the notification that
+    /// committed the finished height of the `ExEx`.
+    ///
+    /// The notifications are read and decoded on a blocking task and handed over through a
+    /// bounded channel, so that only a few of them are held in memory at a time.
+    fn start_catch_up(&mut self, wal: &Wal, next_notification_id: usize) {
+        let wal_notifications =
+            wal.iter_notifications_from(self.finished_height.map(|height| height.hash));
+        let (tx, rx) = mpsc::channel(1);
+        tokio::task::spawn_blocking(move || {
+            for notification in wal_notifications {
+                let is_err = notification.is_err();
+                if tx.blocking_send(notification).is_err() || is_err {
+                    break
+                }
+            }
+        });
+
+        self.state = ExExState::CatchingUp { notifications: rx, pending: None };
+        self.next_notification_id = next_notification_id;
+    }
+
+    /// Sends the next notification replayed from the WAL, and attaches the `ExEx` again once all
+    /// replayed notifications were sent.
+    fn send_catch_up(&mut self, cx: &mut Context<'_>) -> Poll<eyre::Result<()>> {
+        let ExExState::CatchingUp { notifications, pending } = &mut self.state else {
+            return Poll::Ready(Ok(()))
+        };
+        let notification = match pending.take() {
+            Some(notification) => notification,
+            None => match ready!(notifications.poll_recv(cx)) {
+                Some(notification) => notification?,
+                None => {
+                    debug!(target: "exex::manager", exex_id = %self.id, "ExEx caught up from the WAL");
+                    self.state = ExExState::Attached;
+                    return Poll::Ready(Ok(()))
+                }
+            },
+        };
+
+        match self.send_notification(cx, None, &notification) {
+            Poll::Pending => {
+                if let ExExState::CatchingUp { pending, .. } = &mut self.state {
+                    *pending = Some(notification);
+                }
+                Poll::Pending
+            }
+            Poll::Ready(result) => {
+                // continue with the next notification
+                cx.waker().wake_by_ref();
+                Poll::Ready(result.map_err(Into::into))
+            }
+        }
+    }
+
+    /// Sends the notification, unless the `ExEx` has already processed it according to its
+    /// finished height.
+    fn send_notification(
+        &mut self,
+        cx: &mut Context<'_>,
+        notification_id: Option<usize>,
+        notification: &ExExNotification,
     ) -> Poll<Result<(), PollSendError<ExExNotification>>> {
         if let Some(finished_height) = self.finished_height {
             match notification {
@@ -129,13 +324,12 @@ impl ExExHandle {
                         debug!(
                             target: "exex::manager",
                             exex_id = %self.id,
-                            %notification_id,
+                            ?notification_id,
                             ?finished_height,
                             new_tip = %new.tip().number,
                             "Skipping notification"
                         );
 
-                        self.next_notification_id = notification_id + 1;
                         return Poll::Ready(Ok(()))
                     }
                 }
@@ -150,7 +344,7 @@ impl ExExHandle {
         debug!(
             target: "exex::manager",
             exex_id = %self.id,
-            %notification_id,
+            ?notification_id,
             "Reserving slot for notification"
         );
         match self.sender.poll_reserve(cx) {
@@ -161,12 +355,11 @@ impl ExExHandle {
         debug!(
             target: "exex::manager",
             exex_id = %self.id,
-            %notification_id,
+            ?notification_id,
             "Sending notification"
         );
         match self.sender.send_item(notification.clone()) {
             Ok(()) => {
-                self.next_notification_id = notification_id + 1;
                 self.metrics.notifications_sent_total.increment(1);
                 Poll::Ready(Ok(()))
             }
@@ -189,6 +382,24 @@ pub struct ExExManagerMetrics {
     buffer_size: Gauge,
     /// Current number of `ExEx`'s on the node.
     num_exexs: Gauge,
+    /// Number of `ExEx`'s with the [`ExExBackpressurePolicy::Blocking`] policy.
+    num_blocking_exexs: Gauge,
+    /// Number of `ExEx`'s with the [`ExExBackpressurePolicy::BoundedLag`] policy.
+    num_bounded_lag_exexs: Gauge,
+    /// Number of `ExEx`'s with the [`ExExBackpressurePolicy::CatchUpFromWal`] policy.
+    num_catch_up_from_wal_exexs: Gauge,
+    /// Current number of `ExEx`'s that were detached by the
+    /// [`ExExBackpressurePolicy::BoundedLag`] policy.
+    detached_exexs: Gauge,
+    /// Current number of `ExEx`'s that were detached by the
+    /// [`ExExBackpressurePolicy::CatchUpFromWal`] policy and didn't catch up yet.
+    lagging_exexs: Gauge,
+    /// The total number of times an `ExEx` was detached by the
+    /// [`ExExBackpressurePolicy::BoundedLag`] policy.
+    exexs_detached_total: Counter,
+    /// The total number of times an `ExEx` started catching up from the WAL with the
+    /// [`ExExBackpressurePolicy::CatchUpFromWal`] policy.
+    exex_catch_ups_total: Counter,
 }
 
 /// The execution extension manager.
@@ -232,6 +443,8 @@ pub struct ExExManager<P> {
 
     /// The finished height of all `ExEx`'s.
     finished_height: watch::Sender<FinishedExExHeight>,
+    /// The `ExEx`'s that were detached according to their backpressure policies.
+    detached_exexs: watch::Sender<Vec<DetachedExEx>>,
 
     /// Write-Ahead Log for the [`ExExNotification`]s.
     wal: Wal,
@@ -250,8 +463,9 @@ impl<P> ExExManager<P> {
     /// You must provide an [`ExExHandle`] for each `ExEx` and the maximum capacity of the
     /// notification buffer in the manager.
     ///
-    /// When the capacity is exceeded (which can happen if an `ExEx` is slow) no one can send
-    /// notifications over [`ExExManagerHandle`]s until there is capacity again.
+    /// When the capacity is exceeded (which can happen if an `ExEx` with the
+    /// [`ExExBackpressurePolicy::Blocking`] policy is slow) no one can send notifications over
+    /// [`ExExManagerHandle`]s until there is capacity again.
     pub fn new(
         provider: P,
         handles: Vec<ExExHandle>,
@@ -269,11 +483,25 @@ impl<P> ExExManager<P> {
             FinishedExExHeight::NotReady
         });
 
+        let (detached_exexs_tx, detached_exexs_rx) = watch::channel(Vec::new());
+
         let current_capacity = Arc::new(AtomicUsize::new(max_capacity));
 
         let metrics = ExExManagerMetrics::default();
         metrics.max_capacity.set(max_capacity as f64);
         metrics.num_exexs.set(num_exexs as f64);
+        let num_exexs_with_policy = |f: fn(&ExExBackpressurePolicy) -> bool| {
+            handles.iter().filter(|handle| f(&handle.policy)).count() as f64
+        };
+        metrics.num_blocking_exexs.set(num_exexs_with_policy(|policy| {
+            matches!(policy, ExExBackpressurePolicy::Blocking)
+        }));
+        metrics.num_bounded_lag_exexs.set(num_exexs_with_policy(|policy| {
+            matches!(policy, ExExBackpressurePolicy::BoundedLag { .. })
+        }));
+        metrics.num_catch_up_from_wal_exexs.set(num_exexs_with_policy(|policy| {
+            matches!(policy, ExExBackpressurePolicy::CatchUpFromWal { .. })
+        }));
 
         Self {
             provider,
@@ -290,6 +518,7 @@ impl<P> ExExManager<P> {
 
             is_ready: is_ready_tx,
             finished_height: finished_height_tx,
+            detached_exexs: detached_exexs_tx,
 
             wal,
             finalized_header_stream,
@@ -301,6 +530,7 @@ impl<P> ExExManager<P> {
                 is_ready: ReusableBoxFuture::new(make_wait_future(is_ready_rx)),
                 current_capacity,
                 finished_height: finished_height_rx,
+                detached_exexs: detached_exexs_rx,
             },
             metrics,
         }
@@ -324,6 +554,23 @@ impl<P> ExExManager<P> {
         let _ = self.is_ready.send(capacity > 0);
     }
 
+    /// Updates the detached `ExEx`'s reported to the [`ExExManagerHandle`]s and the metrics.
+    fn update_detached_exexs(&self) {
+        let detached_exexs =
+            self.exex_handles.iter().filter_map(ExExHandle::detached).collect::<Vec<_>>();
+        let (permanently, lagging): (Vec<_>, Vec<_>) = detached_exexs
+            .iter()
+            .partition(|exex| matches!(exex.policy, ExExBackpressurePolicy::BoundedLag { .. }));
+        self.metrics.detached_exexs.set(permanently.len() as f64);
+        self.metrics.lagging_exexs.set(lagging.len() as f64);
+
+        self.detached_exexs.send_if_modified(|current| {
+            let modified = *current != detached_exexs;
+            *current = detached_exexs;
+            modified
+        });
+    }
+
     /// Pushes a new notification into the managers internal buffer, assigning the notification a
     /// unique ID.
     fn push_notification(&mut self, notification: ExExNotification) {
@@ -348,6 +595,8 @@ where
         let exex_finished_heights = self
             .exex_handles
             .iter()
+            // Detached ExExes will never need the notifications from the WAL
+            .filter(|exex_handle| !exex_handle.is_detached_permanently())
             // Get ID and finished height for each ExEx
             .map(|exex_handle| (&exex_handle.id, exex_handle.finished_height))
             // Deduplicate all hashes
@@ -468,25 +717,79 @@ where
         this.update_capacity();
 
         // Advance all poll senders
-        let mut min_id = usize::MAX;
+        let mut min_id = this.next_id;
         for idx in (0..this.exex_handles.len()).rev() {
             let mut exex = this.exex_handles.swap_remove(idx);
 
-            // It is a logic error for this to ever underflow since the manager manages the
-            // notification IDs
-            let notification_index = exex
-                .next_notification_id
-                .checked_sub(this.min_id)
-                .expect("exex expected notification ID outside the manager's range");
-            if let Some(notification) = this.buffer.get(notification_index) {
-                if let Poll::Ready(Err(err)) = exex.send(cx, notification) {
+            // Detach the ExEx if it lags too far behind according to its policy. An ExEx that is
+            // catching up from the WAL only advances its notification ID once it's attached
+            // again, so it's not checked until then.
+            if matches!(exex.state, ExExState::Attached) {
+                let lag = this.next_id - exex.next_notification_id;
+                exex.metrics.notifications_lag.set(lag as f64);
+                match exex.policy {
+                    ExExBackpressurePolicy::BoundedLag { max_lag } if lag > max_lag => {
+                        warn!(target: "exex::manager", exex_id = %exex.id, %lag, %max_lag, "ExEx lags too far behind, detaching");
+                        exex.state = ExExState::Detached { lag };
+                        this.metrics.exexs_detached_total.increment(1);
+                    }
+                    ExExBackpressurePolicy::CatchUpFromWal { max_lag } if lag > max_lag => {
+                        warn!(target: "exex::manager", exex_id = %exex.id, %lag, %max_lag, "ExEx lags too far behind, detaching until it can catch up from the WAL");
+                        exex.state = ExExState::Lagging { lag };
+                    }
+                    ExExBackpressurePolicy::Blocking |
+                    ExExBackpressurePolicy::BoundedLag { .. } |
+                    ExExBackpressurePolicy::CatchUpFromWal { .. } => {}
+                }
+            }
+
+            // Replay the notifications from the WAL once a lagging ExEx can receive notifications
+            // again
+            if matches!(exex.state, ExExState::Lagging { .. }) {
+                match exex.sender.poll_reserve(cx) {
+                    Poll::Ready(Ok(())) => {
+                        debug!(target: "exex::manager", exex_id = %exex.id, finished_height = ?exex.finished_height, "ExEx is catching up from the WAL");
+                        exex.start_catch_up(&this.wal, this.next_id);
+                        this.metrics.exex_catch_ups_total.increment(1);
+                    }
                     // The channel was closed, which is irrecoverable for the manager
-                    return Poll::Ready(Err(err.into()))
+                    Poll::Ready(Err(err)) => return Poll::Ready(Err(err.into())),
+                    Poll::Pending => {}
+                }
+            }
+
+            match &exex.state {
+                ExExState::Attached => {
+                    // It is a logic error for this to ever underflow since the manager manages the
+                    // notification IDs
+                    let notification_index = exex
+                        .next_notification_id
+                        .checked_sub(this.min_id)
+                        .expect("exex expected notification ID outside the manager's range");
+                    if let Some(notification) = this.buffer.get(notification_index) {
+                        if let Poll::Ready(Err(err)) = exex.send(cx, notification) {
+                            // The channel was closed, which is irrecoverable for the manager
+                            return Poll::Ready(Err(err.into()))
+                        }
+                    }
+                }
+                ExExState::CatchingUp { .. } => {
+                    if let Poll::Ready(Err(err)) = exex.send_catch_up(cx) {
+                        // The channel was closed or the WAL couldn't be read, which is
+                        // irrecoverable for the manager
+                        return Poll::Ready(Err(err))
+                    }
                 }
+                ExExState::Lagging { .. } | ExExState::Detached { .. } => {}
+            }
+
+            // Only attached ExExes hold back the buffer
+            if exex.is_attached() {
+                min_id = min_id.min(exex.next_notification_id);
             }
-            min_id = min_id.min(exex.next_notification_id);
             this.exex_handles.push(exex);
         }
+        this.update_detached_exexs();
 
         // Remove processed buffered notifications
         debug!(target: "exex::manager", %min_id, "Updating lowest notification id in buffer");
@@ -496,12 +799,18 @@ where
         // Update capacity
         this.update_capacity();
 
-        // Update watch channel block number
-        let finished_height = this.exex_handles.iter_mut().try_fold(u64::MAX, |curr, exex| {
-            exex.finished_height.map_or(Err(()), |height| Ok(height.number.min(curr)))
-        });
-        if let Ok(finished_height) = finished_height {
-            let _ = this.finished_height.send(FinishedExExHeight::Height(finished_height));
+        // Update watch channel block number. Detached ExExes don't hold back the finished height.
+        let mut attached_exexs =
+            this.exex_handles.iter().filter(|exex| exex.is_attached()).peekable();
+        if attached_exexs.peek().is_none() {
+            let _ = this.finished_height.send(FinishedExExHeight::NoExExs);
+        } else {
+            let finished_height = attached_exexs.try_fold(u64::MAX, |curr, exex| {
+                exex.finished_height.map_or(Err(()), |height| Ok(height.number.min(curr)))
+            });
+            if let Ok(finished_height) = finished_height {
+                let _ = this.finished_height.send(FinishedExExHeight::Height(finished_height));
+            }
         }
 
         Poll::Pending
@@ -528,6 +837,8 @@ pub struct ExExManagerHandle {
     current_capacity: Arc<AtomicUsize>,
     /// The finished height of all `ExEx`'s.
     finished_height: watch::Receiver<FinishedExExHeight>,
+    /// The `ExEx`'s that were detached according to their backpressure policies.
+    detached_exexs: watch::Receiver<Vec<DetachedExEx>>,
 }
 
 impl ExExManagerHandle {
@@ -540,6 +851,7 @@ impl ExExManagerHandle {
         let (exex_tx, _) = mpsc::unbounded_channel();
         let (_, is_ready_rx) = watch::channel(true);
         let (_, finished_height_rx) = watch::channel(FinishedExExHeight::NoExExs);
+        let (_, detached_exexs_rx) = watch::channel(Vec::new());
 
         Self {
             exex_tx,
@@ -548,6 +860,7 @@ impl ExExManagerHandle {
             is_ready: ReusableBoxFuture::new(make_wait_future(is_ready_rx)),
             current_capacity: Arc::new(AtomicUsize::new(0)),
             finished_height: finished_height_rx,
+            detached_exexs: detached_exexs_rx,
         }
     }
 
@@ -598,6 +911,13 @@ impl ExExManagerHandle {
         self.finished_height.clone()
     }
 
+    /// The `ExEx`'s that are currently detached according to their backpressure policies.
+    ///
+    /// See [`ExExBackpressurePolicy`] for more details.
+    pub fn detached_exexs(&self) -> watch::Receiver<Vec<DetachedExEx>> {
+        self.detached_exexs.clone()
+    }
+
     /// Wait until the manager is ready for new notifications.
     pub async fn ready(&mut self) {
         poll_fn(|cx| self.poll_ready(cx)).await
@@ -628,6 +948,7 @@ impl Clone for ExExManagerHandle {
             is_ready: ReusableBoxFuture::new(make_wait_future(self.is_ready_receiver.clone())),
             current_capacity: self.current_capacity.clone(),
             finished_height: self.finished_height.clone(),
+            detached_exexs: self.detached_exexs.clone(),
         }
     }
 }
@@ -1314,4 +1635,169 @@ mod tests {
 
         Ok(())
     }
+
+    fn committed_notification(block_number: u64) -> ExExNotification {
+        let mut block = SealedBlockWithSenders::default();
+        block.block.header.set_hash(B256::with_last_byte(block_number as u8));
+        block.block.header.set_block_number(block_number);
+        ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(vec![block], Default::default(), Default::default())),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bounded_lag_detaches_exex() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let (exex_handle, _, _notifications) =
+            ExExHandle::new("test_exex".to_string(), Head::default(), (), (), wal.handle());
+        let exex_handle =
+            exex_handle.with_backpressure_policy(ExExBackpressurePolicy::BoundedLag { max_lag: 2 });
+
+        let mut exex_manager = std::pin::pin!(ExExManager::new(
+            create_test_provider_factory(),
+            vec![exex_handle],
+            10,
+            wal,
+            empty_finalized_header_stream()
+        ));
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+        // The ExEx doesn't receive any notifications, so it lags behind
+        for block_number in 1..=4 {
+            exex_manager
+                .handle
+                .send(ExExNotificationSource::Pipeline, committed_notification(block_number))
+                .unwrap();
+        }
+        assert!(exex_manager.as_mut().poll(&mut cx).is_pending());
+
+        // The ExEx is detached and doesn't hold back the buffer and the finished height anymore
+        assert_eq!(
+            *exex_manager.handle.detached_exexs().borrow(),
+            vec![DetachedExEx {
+                id: "test_exex".to_string(),
+                policy: ExExBackpressurePolicy::BoundedLag { max_lag: 2 },
+                lag: 4,
+            }]
+        );
+        assert!(exex_manager.buffer.is_empty());
+        assert_eq!(*exex_manager.handle.finished_height().borrow(), FinishedExExHeight::NoExExs);
+    }
+
+    #[tokio::test]
+    async fn test_catching_up_exex_is_not_detached() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let (exex_handle, _, _notifications) =
+            ExExHandle::new("test_exex".to_string(), Head::default(), (), (), wal.handle());
+        let mut exex_handle = exex_handle
+            .with_backpressure_policy(ExExBackpressurePolicy::CatchUpFromWal { max_lag: 1 });
+        exex_handle.start_catch_up(&wal, 0);
+
+        let mut exex_manager = std::pin::pin!(ExExManager::new(
+            create_test_provider_factory(),
+            vec![exex_handle],
+            10,
+            wal,
+            empty_finalized_header_stream()
+        ));
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+        // The notifications are buffered while the ExEx is catching up, which doesn't count as
+        // lagging behind
+        for block_number in 1..=3 {
+            exex_manager
+                .handle
+                .send(ExExNotificationSource::Pipeline, committed_notification(block_number))
+                .unwrap();
+        }
+        assert!(exex_manager.as_mut().poll(&mut cx).is_pending());
+
+        assert!(!matches!(exex_manager.exex_handles[0].state, ExExState::Lagging { .. }));
+        assert!(exex_manager.handle.detached_exexs().borrow().is_empty());
+        assert_eq!(exex_manager.buffer.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_catch_up_from_wal() -> eyre::Result<()> {
+        let provider_factory = create_test_provider_factory();
+        init_genesis(&provider_factory)?;
+        let provider = BlockchainProvider2::new(provider_factory)?;
+
+        let temp_dir = tempfile::tempdir()?;
+        let wal = Wal::new(temp_dir.path())?;
+
+        let (exex_handle, events_tx, mut notifications) = ExExHandle::new(
+            "test_exex".to_string(),
+            Head::default(),
+            provider.clone(),
+            EthExecutorProvider::mainnet(),
+            wal.handle(),
+        );
+        let exex_handle = exex_handle
+            .with_backpressure_policy(ExExBackpressurePolicy::CatchUpFromWal { max_lag: 1 });
+
+        let mut exex_manager = std::pin::pin!(ExExManager::new(
+            provider,
+            vec![exex_handle],
+            10,
+            wal,
+            empty_finalized_header_stream()
+        ));
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+        // The first notification is delivered to the ExEx
+        let first_notification = committed_notification(1);
+        exex_manager
+            .handle
+            .send(ExExNotificationSource::BlockchainTree, first_notification.clone())?;
+        assert!(exex_manager.as_mut().poll(&mut cx).is_pending());
+        assert!(exex_manager.handle.detached_exexs().borrow().is_empty());
+
+        // The ExEx doesn't receive the next notifications, so it lags behind and is detached
+        let next_notifications = (2..=4).map(committed_notification).collect::<Vec<_>>();
+        for notification in &next_notifications {
+            exex_manager
+                .handle
+                .send(ExExNotificationSource::BlockchainTree, notification.clone())?;
+        }
+        assert!(exex_manager.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(exex_manager.handle.detached_exexs().borrow().len(), 1);
+        assert!(exex_manager.buffer.is_empty());
+
+        // The ExEx receives the first notification and catches up from the WAL, skipping the
+        // notification it has already processed
+        assert_eq!(notifications.next().await.transpose()?, Some(first_notification));
+        events_tx.send(ExExEvent::FinishedHeight(BlockNumHash::new(1, B256::with_last_byte(1))))?;
+
+        // The notifications are read from the WAL by a blocking task, so the manager is driven
+        // with a real waker
+        let mut received = Vec::new();
+        while received.len() < next_notifications.len() {
+            let notification = poll_fn(|cx| {
+                assert!(exex_manager.as_mut().poll(cx).is_pending());
+                notifications.poll_next_unpin(cx)
+            })
+            .await;
+            received.push(notification.transpose()?.expect("notifications stream ended"));
+        }
+        assert_eq!(received, next_notifications);
+
+        // The ExEx is attached again once the WAL is exhausted
+        poll_fn(|cx| {
+            assert!(exex_manager.as_mut().poll(cx).is_pending());
+            if matches!(exex_manager.exex_handles[0].state, ExExState::Attached) {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+        assert!(exex_manager.handle.detached_exexs().borrow().is_empty());
+
+        Ok(())
+    }
 }
@@ -13,7 +13,7 @@ use reth_exex_types::ExExNotification;
 /// notification corresponding to a block or a block corresponding to a hash.
 #[derive(Debug, Default)]
 pub struct BlockCache {
-    /// A min heap of `(Block Number, File ID)` tuples.
+    /// A min heap of `(Block Number, Notification ID)` tuples.
     ///
     /// Contains one highest block in notification. In a notification with both committed and
     /// reverted chain, the highest block is chosen between both chains.
@@ -35,30 +35,30 @@ impl BlockCache {
         self.notification_max_blocks.is_empty()
     }
 
-    /// Removes all files from the cache that has notifications with a tip block less than or equal
-    /// to the given block number.
+    /// Removes all notifications from the cache that has notifications with a tip block less than
+    /// or equal to the given block number.
     ///
     /// # Returns
     ///
-    /// A set of file IDs that were removed.
+    /// A set of notification IDs that were removed.
     pub(super) fn remove_before(&mut self, block_number: BlockNumber) -> HashSet<u32> {
-        let mut file_ids = HashSet::default();
+        let mut notification_ids = HashSet::default();
 
-        while let Some(block @ Reverse((max_block, file_id))) =
+        while let Some(block @ Reverse((max_block, notification_id))) =
             self.notification_max_blocks.peek().copied()
         {
             if max_block <= block_number {
                 let popped_block = self.notification_max_blocks.pop().unwrap();
                 debug_assert_eq!(popped_block, block);
-                file_ids.insert(file_id);
+                notification_ids.insert(notification_id);
             } else {
                 break
             }
         }
 
         let (mut lowest_committed_block_height, mut highest_committed_block_height) = (None, None);
-        self.committed_blocks.retain(|_, (file_id, block)| {
-            let retain = !file_ids.contains(file_id);
+        self.committed_blocks.retain(|_, (notification_id, block)| {
+            let retain = !notification_ids.contains(notification_id);
 
             if retain {
                 lowest_committed_block_height = Some(
@@ -76,19 +76,22 @@ impl BlockCache {
         self.lowest_committed_block_height = lowest_committed_block_height;
         self.highest_committed_block_height = highest_committed_block_height;
 
-        file_ids
+        notification_ids
     }
 
-    /// Returns the file ID for the notification containing the given committed block hash, if it
-    /// exists.
-    pub(super) fn get_file_id_by_committed_block_hash(&self, block_hash: &B256) -> Option<u32> {
+    /// Returns the notification ID for the notification containing the given committed block hash,
+    /// if it exists.
+    pub(super) fn get_notification_id_by_committed_block_hash(
+        &self,
+        block_hash: &B256,
+    ) -> Option<u32> {
         self.committed_blocks.get(block_hash).map(|entry| entry.0)
     }
 
-    /// Inserts the blocks from the notification into the cache with the given file ID.
-    pub(super) fn insert_notification_blocks_with_file_id(
+    /// Inserts the blocks from the notification into the cache with the given notification ID.
+    pub(super) fn insert_notification_blocks_with_notification_id(
         &mut self,
-        file_id: u32,
+        notification_id: u32,
         notification: &ExExNotification,
     ) {
         let reverted_chain = notification.reverted_chain();
@@ -97,7 +100,7 @@ impl BlockCache {
         let max_block =
             reverted_chain.iter().chain(&committed_chain).map(|chain| chain.tip().number).max();
         if let Some(max_block) = max_block {
-            self.notification_max_blocks.push(Reverse((max_block, file_id)));
+            self.notification_max_blocks.push(Reverse((max_block, notification_id)));
         }
 
         if let Some(committed_chain) = &committed_chain {
@@ -106,7 +109,7 @@ impl BlockCache {
                     block: (block.number, block.hash()).into(),
                     parent_hash: block.parent_hash,
                 };
-                self.committed_blocks.insert(block.hash(), (file_id, cached_block));
+                self.committed_blocks.insert(block.hash(), (notification_id, cached_block));
             }
 
             self.highest_committed_block_height = Some(committed_chain.tip().number);
@@ -3,7 +3,7 @@
 mod cache;
 pub use cache::BlockCache;
 mod storage;
-pub use storage::Storage;
+pub use storage::{InvalidRecord, Segment, SegmentRecords, Storage, WalRecord};
 mod metrics;
 use metrics::Metrics;
 
@@ -23,7 +23,7 @@ use reth_tracing::tracing::{debug, instrument};
 
 /// WAL is a write-ahead log (WAL) that stores the notifications sent to ExExes.
 ///
-/// WAL is backed by a directory of binary files represented by [`Storage`] and a block cache
+/// WAL is backed by a directory of segment files represented by [`Storage`] and a block cache
 /// represented by [`BlockCache`]. The role of the block cache is to avoid walking the WAL directory
 /// and decoding notifications every time we want to iterate or finalize the WAL.
 ///
@@ -66,13 +66,42 @@ impl Wal {
     ) -> eyre::Result<Box<dyn Iterator<Item = eyre::Result<ExExNotification>> + '_>> {
         self.inner.iter_notifications()
     }
+
+    /// Returns an iterator over the notifications in the WAL, starting with the notification that
+    /// committed the block with the given hash, or with the first notification if the block isn't
+    /// in the WAL.
+    ///
+    /// Only the notifications that are in the WAL at the time of the call are returned. They are
+    /// read lazily, and notifications that are finalized in the meantime are skipped.
+    pub fn iter_notifications_from(
+        &self,
+        block_hash: Option<B256>,
+    ) -> impl Iterator<Item = eyre::Result<ExExNotification>> + Send + 'static {
+        let inner = self.inner.clone();
+        let notification_ids = inner.storage.notifications_range().map_or(1..=0, |range| {
+            let start = block_hash
+                .and_then(|hash| {
+                    inner.block_cache().get_notification_id_by_committed_block_hash(&hash)
+                })
+                .unwrap_or(*range.start());
+            start..=*range.end()
+        });
+
+        notification_ids.filter_map(move |notification_id| {
+            inner
+                .storage
+                .read_notification(notification_id)
+                .map(|entry| entry.map(|(notification, _)| notification))
+                .transpose()
+        })
+    }
 }
 
 /// Inner type for the WAL.
 #[derive(Debug)]
 struct WalInner {
-    next_file_id: AtomicU32,
-    /// The underlying WAL storage backed by a file.
+    next_notification_id: AtomicU32,
+    /// The underlying WAL storage backed by segment files.
     storage: Storage,
     /// WAL block cache. See [`cache::BlockCache`] docs for more details.
     block_cache: RwLock<BlockCache>,
@@ -82,7 +111,7 @@ struct WalInner {
 impl WalInner {
     fn new(directory: impl AsRef<Path>) -> eyre::Result<Self> {
         let mut wal = Self {
-            next_file_id: AtomicU32::new(0),
+            next_notification_id: AtomicU32::new(0),
             storage: Storage::new(directory)?,
             block_cache: RwLock::new(BlockCache::default()),
             metrics: Metrics::default(),
@@ -98,14 +127,14 @@ impl WalInner {
     /// Fills the block cache with the notifications from the storage.
     #[instrument(skip(self))]
     fn fill_block_cache(&mut self) -> eyre::Result<()> {
-        let Some(files_range) = self.storage.files_range()? else { return Ok(()) };
-        self.next_file_id.store(files_range.end() + 1, Ordering::Relaxed);
+        self.next_notification_id.store(self.storage.next_notification_id(), Ordering::Relaxed);
+        let Some(notifications_range) = self.storage.notifications_range() else { return Ok(()) };
 
         let mut block_cache = self.block_cache.write();
         let mut notifications_size = 0;
 
-        for entry in self.storage.iter_notifications(files_range) {
-            let (file_id, size, notification) = entry?;
+        for entry in self.storage.iter_notifications(notifications_range) {
+            let (notification_id, size, notification) = entry?;
 
             notifications_size += size;
 
@@ -114,13 +143,14 @@ impl WalInner {
 
             debug!(
                 target: "exex::wal",
-                ?file_id,
+                ?notification_id,
                 reverted_block_range = ?reverted_chain.as_ref().map(|chain| chain.range()),
                 committed_block_range = ?committed_chain.as_ref().map(|chain| chain.range()),
                 "Inserting block cache entries"
             );
 
-            block_cache.insert_notification_blocks_with_file_id(file_id, &notification);
+            block_cache
+                .insert_notification_blocks_with_notification_id(notification_id, &notification);
         }
 
         self.update_metrics(&block_cache, notifications_size as i64);
@@ -135,11 +165,11 @@ impl WalInner {
     fn commit(&self, notification: &ExExNotification) -> eyre::Result<()> {
         let mut block_cache = self.block_cache.write();
 
-        let file_id = self.next_file_id.fetch_add(1, Ordering::Relaxed);
-        let size = self.storage.write_notification(file_id, notification)?;
+        let notification_id = self.next_notification_id.fetch_add(1, Ordering::Relaxed);
+        let size = self.storage.write_notification(notification_id, notification)?;
 
-        debug!(target: "exex::wal", ?file_id, "Inserting notification blocks into the block cache");
-        block_cache.insert_notification_blocks_with_file_id(file_id, notification);
+        debug!(target: "exex::wal", ?notification_id, "Inserting notification blocks into the block cache");
+        block_cache.insert_notification_blocks_with_notification_id(notification_id, notification);
 
         self.update_metrics(&block_cache, size as i64);
 
@@ -149,15 +179,16 @@ impl WalInner {
     #[instrument(skip(self))]
     fn finalize(&self, to_block: BlockNumHash) -> eyre::Result<()> {
         let mut block_cache = self.block_cache.write();
-        let file_ids = block_cache.remove_before(to_block.number);
+        let notification_ids = block_cache.remove_before(to_block.number);
 
         // Remove notifications from the storage.
-        if file_ids.is_empty() {
+        if notification_ids.is_empty() {
             debug!(target: "exex::wal", "No notifications were finalized from the storage");
             return Ok(())
         }
 
-        let (removed_notifications, removed_size) = self.storage.remove_notifications(file_ids)?;
+        let (removed_notifications, removed_size) =
+            self.storage.remove_notifications(notification_ids)?;
         debug!(target: "exex::wal", ?removed_notifications, ?removed_size, "Storage was finalized");
 
         self.update_metrics(&block_cache, -(removed_size as i64));
@@ -183,7 +214,7 @@ impl WalInner {
     fn iter_notifications(
         &self,
     ) -> eyre::Result<Box<dyn Iterator<Item = eyre::Result<ExExNotification>> + '_>> {
-        let Some(range) = self.storage.files_range()? else {
+        let Some(range) = self.storage.notifications_range() else {
             return Ok(Box::new(std::iter::empty()))
         };
 
@@ -203,14 +234,15 @@ impl WalHandle {
         &self,
         block_hash: &B256,
     ) -> eyre::Result<Option<ExExNotification>> {
-        let Some(file_id) = self.wal.block_cache().get_file_id_by_committed_block_hash(block_hash)
+        let Some(notification_id) =
+            self.wal.block_cache().get_notification_id_by_committed_block_hash(block_hash)
         else {
             return Ok(None)
         };
 
         self.wal
             .storage
-            .read_notification(file_id)
+            .read_notification(notification_id)
             .map(|entry| entry.map(|(notification, _)| notification))
     }
 }
@@ -231,11 +263,13 @@ mod tests {
     use crate::wal::{cache::CachedBlock, Wal};
 
     fn read_notifications(wal: &Wal) -> eyre::Result<Vec<ExExNotification>> {
-        let Some(files_range) = wal.inner.storage.files_range()? else { return Ok(Vec::new()) };
+        let Some(notifications_range) = wal.inner.storage.notifications_range() else {
+            return Ok(Vec::new())
+        };
 
         wal.inner
             .storage
-            .iter_notifications(files_range)
+            .iter_notifications(notifications_range)
             .map(|entry| Ok(entry?.2))
             .collect::<eyre::Result<_>>()
     }
@@ -455,6 +489,18 @@ mod tests {
                 .concat()
             )
         );
+        // Iterating from a committed block starts with the notification that committed it, and
+        // iterating from an unknown block starts with the first notification
+        assert_eq!(
+            wal.iter_notifications_from(Some(block_1_reorged.hash()))
+                .collect::<eyre::Result<Vec<_>>>()?,
+            vec![committed_notification_2.clone(), reorged_notification.clone()]
+        );
+        assert_eq!(
+            wal.iter_notifications_from(Some(B256::random()))
+                .collect::<eyre::Result<Vec<_>>>()?,
+            read_notifications(&wal)?
+        );
         assert_eq!(
             read_notifications(&wal)?,
             vec![
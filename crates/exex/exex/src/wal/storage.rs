@@ -1,211 +1,698 @@
 use std::{
-    fs::File,
+    collections::{BTreeMap, BTreeSet},
+    fs::{File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
     ops::RangeInclusive,
     path::{Path, PathBuf},
 };
 
 use eyre::OptionExt;
+use parking_lot::Mutex;
 use reth_exex_types::ExExNotification;
-use reth_tracing::tracing::debug;
+use reth_tracing::tracing::{debug, warn};
 use tracing::instrument;
 
-static FILE_EXTENSION: &str = "wal";
+/// Extension of the segment files.
+static SEGMENT_FILE_EXTENSION: &str = "seg";
+/// Extension of the files of the legacy storage, that stored one MessagePack-encoded notification
+/// per file.
+static LEGACY_FILE_EXTENSION: &str = "wal";
 
-/// The underlying WAL storage backed by a directory of files.
-///
-/// Each notification is represented by a single file that contains a MessagePack-encoded
+/// Magic bytes at the start of every segment file, including the version of the format.
+const SEGMENT_MAGIC: [u8; 8] = *b"RETHWAL\x01";
+/// Length of the header of a record: the length of the record body and its CRC32 checksum.
+const RECORD_HEADER_LEN: usize = 8;
+/// The active segment is rotated once appending a record would grow it beyond this size.
+const MAX_SEGMENT_SIZE: u64 = 64 * 1024 * 1024;
+
+/// A record with a notification: the notification ID followed by the bincode-encoded
 /// notification.
-#[derive(Debug, Clone)]
+const NOTIFICATION_RECORD: u8 = 0;
+/// A record with the IDs of notifications that were removed from the WAL.
+const FINALIZED_RECORD: u8 = 1;
+
+/// The underlying WAL storage backed by a directory of append-only segment files.
+///
+/// Each segment starts with a magic header, followed by records:
+///
+/// ```text
+/// record := length (u32) | crc32 (u32) | kind (u8) | payload
+/// ```
+///
+/// The length and the checksum cover the kind and the payload. Notifications are appended as
+/// records, and removed notifications are recorded by appending a record with their IDs. Segments
+/// are deleted once all of their notifications and the notifications of all preceding segments
+/// were removed.
+///
+/// On startup, a torn record at the end of the last segment, left by a crash in the middle of a
+/// write, is truncated, and a torn header of the last segment is rewritten. An invalid record
+/// anywhere else is reported as an error.
+#[derive(Debug)]
 pub struct Storage {
-    /// The path to the WAL file.
+    /// The path to the WAL directory.
     path: PathBuf,
+    state: Mutex<StorageState>,
+}
+
+#[derive(Debug, Default)]
+struct StorageState {
+    /// Segments by ID, with the IDs of their notifications that weren't removed yet.
+    segments: BTreeMap<u32, BTreeSet<u32>>,
+    /// Locations of the notifications that weren't removed yet, by notification ID.
+    notifications: BTreeMap<u32, RecordLocation>,
+    /// The segment that records are appended to.
+    active: Option<ActiveSegment>,
+    /// One more than the highest notification ID in the segments.
+    next_notification_id: u32,
+}
+
+#[derive(Debug)]
+struct ActiveSegment {
+    id: u32,
+    file: File,
+    size: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RecordLocation {
+    segment: u32,
+    offset: u64,
+    size: u64,
 }
 
 impl Storage {
-    /// Creates a new instance of [`Storage`] backed by the file at the given path and creates
-    /// it doesn't exist.
+    /// Creates a new instance of [`Storage`] backed by the directory at the given path and creates
+    /// it if it doesn't exist.
+    ///
+    /// Notifications of the legacy storage with one file per notification are migrated to
+    /// segments.
     pub(super) fn new(path: impl AsRef<Path>) -> eyre::Result<Self> {
         reth_fs_util::create_dir_all(&path)?;
 
-        Ok(Self { path: path.as_ref().to_path_buf() })
+        let storage = Self { path: path.as_ref().to_path_buf(), state: Default::default() };
+        storage.recover()?;
+        storage.migrate_legacy_files()?;
+        Ok(storage)
     }
 
-    fn file_path(&self, id: u32) -> PathBuf {
-        self.path.join(format!("{id}.{FILE_EXTENSION}"))
+    /// Returns the segments of the WAL in the given directory, ordered by ID.
+    pub fn segments(directory: impl AsRef<Path>) -> eyre::Result<Vec<Segment>> {
+        let mut segments = Vec::new();
+        for entry in reth_fs_util::read_dir(directory.as_ref())? {
+            let path = entry?.path();
+            if path.extension() == Some(SEGMENT_FILE_EXTENSION.as_ref()) {
+                let id = parse_file_id(&path, SEGMENT_FILE_EXTENSION)?;
+                segments.push(Segment { id, path });
+            }
+        }
+        segments.sort_unstable_by_key(|segment| segment.id);
+        Ok(segments)
     }
 
-    fn parse_filename(filename: &str) -> eyre::Result<u32> {
-        filename
-            .strip_suffix(".wal")
-            .and_then(|s| s.parse().ok())
-            .ok_or_eyre(format!("failed to parse file name: {filename}"))
+    /// Truncates the WAL in the given directory at the given offset of the given segment.
+    ///
+    /// All records starting at the offset and all later segments are removed. If the offset
+    /// points at the first record of the segment, the whole segment is removed.
+    pub fn truncate(directory: impl AsRef<Path>, segment_id: u32, offset: u64) -> eyre::Result<()> {
+        for segment in Self::segments(directory)? {
+            if segment.id > segment_id || (segment.id == segment_id && offset <= header_len()) {
+                reth_fs_util::remove_file(&segment.path)?;
+            } else if segment.id == segment_id {
+                segment.truncate(offset)?;
+            }
+        }
+        Ok(())
     }
 
-    /// Removes notification for the given file ID from the storage.
-    ///
-    /// # Returns
-    ///
-    /// The size of the file that was removed in bytes, if any.
+    fn segment_path(&self, id: u32) -> PathBuf {
+        self.path.join(format!("{id}.{SEGMENT_FILE_EXTENSION}"))
+    }
+
+    /// Replays the segments to restore the locations of the notifications, and truncates a torn
+    /// record or rewrites a torn header at the end of the last segment.
     #[instrument(skip(self))]
-    fn remove_notification(&self, file_id: u32) -> Option<u64> {
-        let path = self.file_path(file_id);
-        let size = path.metadata().ok()?.len();
-
-        match reth_fs_util::remove_file(self.file_path(file_id)) {
-            Ok(()) => {
-                debug!(target: "exex::wal::storage", "Notification was removed from the storage");
-                Some(size)
+    fn recover(&self) -> eyre::Result<()> {
+        let mut state = self.state.lock();
+        let segments = Self::segments(&self.path)?;
+        let last_segment = segments.last().map(|segment| segment.id);
+
+        for segment in segments {
+            let buf = reth_fs_util::read(&segment.path)?;
+            state.segments.insert(segment.id, BTreeSet::new());
+
+            let mut size = buf.len() as u64;
+            for record in RawRecords::new(&buf) {
+                match record {
+                    Ok(record) => state.replay(segment.id, &record)?,
+                    // the segment was created, but its header wasn't fully written
+                    Err(InvalidRecord::Header)
+                        if Some(segment.id) == last_segment && size < header_len() =>
+                    {
+                        warn!(target: "exex::wal::storage", path = ?segment.path, "Rewriting torn header at the end of the WAL");
+                        segment.truncate(0)?;
+                        size = header_len();
+                    }
+                    Err(err)
+                        if Some(segment.id) == last_segment &&
+                            !matches!(err, InvalidRecord::Header) =>
+                    {
+                        warn!(target: "exex::wal::storage", path = ?segment.path, %err, "Truncating torn record at the end of the WAL");
+                        size = err.offset();
+                        segment.truncate(size)?;
+                    }
+                    Err(err) => {
+                        eyre::bail!("WAL segment {:?} is corrupted: {err}", segment.path)
+                    }
+                }
             }
-            Err(err) => {
-                debug!(target: "exex::wal::storage", ?err, "Failed to remove notification from the storage");
-                None
+
+            if Some(segment.id) == last_segment {
+                let file = OpenOptions::new().append(true).open(&segment.path)?;
+                state.active = Some(ActiveSegment { id: segment.id, file, size });
             }
         }
-    }
-
-    /// Returns the range of file IDs in the storage.
-    ///
-    /// If there are no files in the storage, returns `None`.
-    pub(super) fn files_range(&self) -> eyre::Result<Option<RangeInclusive<u32>>> {
-        let mut min_id = None;
-        let mut max_id = None;
 
-        for entry in reth_fs_util::read_dir(&self.path)? {
-            let entry = entry?;
+        self.remove_finalized_segments(&mut state)?;
 
-            if entry.path().extension() == Some(FILE_EXTENSION.as_ref()) {
-                let file_name = entry.file_name();
-                let file_id = Self::parse_filename(&file_name.to_string_lossy())?;
+        Ok(())
+    }
 
-                min_id = min_id.map_or(Some(file_id), |min_id: u32| Some(min_id.min(file_id)));
-                max_id = max_id.map_or(Some(file_id), |max_id: u32| Some(max_id.max(file_id)));
+    /// Moves the notifications of the legacy storage to segments and removes the legacy files.
+    #[instrument(skip(self))]
+    fn migrate_legacy_files(&self) -> eyre::Result<()> {
+        let mut ids = Vec::new();
+        for entry in reth_fs_util::read_dir(&self.path)? {
+            let path = entry?.path();
+            if path.extension() == Some(LEGACY_FILE_EXTENSION.as_ref()) {
+                ids.push(parse_file_id(&path, LEGACY_FILE_EXTENSION)?);
+            }
+        }
+        if ids.is_empty() {
+            return Ok(())
+        }
+        ids.sort_unstable();
+
+        debug!(target: "exex::wal::storage", notifications = ids.len(), "Migrating legacy WAL files");
+        for id in ids {
+            let path = self.path.join(format!("{id}.{LEGACY_FILE_EXTENSION}"));
+            // the notification was already migrated before a crash if the ID is known
+            if id >= self.next_notification_id() {
+                let mut file = File::open(&path)?;
+                let notification: reth_exex_types::serde_bincode_compat::ExExNotification<'_> =
+                    rmp_serde::decode::from_read(&mut file).map_err(|err| {
+                        eyre::eyre!("failed to decode notification from {path:?}: {err:?}")
+                    })?;
+                self.write_notification(id, &notification.into())?;
             }
+            reth_fs_util::remove_file(&path)?;
         }
 
-        Ok(min_id.zip(max_id).map(|(min_id, max_id)| min_id..=max_id))
+        Ok(())
     }
 
-    /// Removes notifications from the storage according to the given list of file IDs.
+    /// Returns one more than the highest notification ID in the storage.
+    pub(super) fn next_notification_id(&self) -> u32 {
+        self.state.lock().next_notification_id
+    }
+
+    /// Returns the range of notification IDs in the storage.
+    ///
+    /// If there are no notifications in the storage, returns `None`.
+    pub(super) fn notifications_range(&self) -> Option<RangeInclusive<u32>> {
+        let state = self.state.lock();
+        let first = state.notifications.keys().next()?;
+        let last = state.notifications.keys().next_back()?;
+        Some(*first..=*last)
+    }
+
+    /// Removes notifications from the storage according to the given list of notification IDs.
     ///
     /// # Returns
     ///
-    /// Number of removed notifications and the total size of the removed files in bytes.
+    /// Number of removed notifications and the total size of their records in bytes.
     pub(super) fn remove_notifications(
         &self,
-        file_ids: impl IntoIterator<Item = u32>,
+        notification_ids: impl IntoIterator<Item = u32>,
     ) -> eyre::Result<(usize, u64)> {
-        let mut deleted_total = 0;
-        let mut deleted_size = 0;
-
-        for id in file_ids {
-            if let Some(size) = self.remove_notification(id) {
-                deleted_total += 1;
-                deleted_size += size;
+        let mut state = self.state.lock();
+
+        let mut removed_ids = Vec::new();
+        let mut removed_size = 0;
+        for id in notification_ids {
+            if let Some(location) = state.notifications.remove(&id) {
+                if let Some(segment) = state.segments.get_mut(&location.segment) {
+                    segment.remove(&id);
+                }
+                removed_ids.push(id);
+                removed_size += location.size;
             }
         }
+        if removed_ids.is_empty() {
+            return Ok((0, 0))
+        }
+        removed_ids.sort_unstable();
+
+        let payload = removed_ids.iter().flat_map(|id| id.to_le_bytes()).collect::<Vec<_>>();
+        self.append_record(&mut state, FINALIZED_RECORD, &payload)?;
+        self.remove_finalized_segments(&mut state)?;
+
+        Ok((removed_ids.len(), removed_size))
+    }
+
+    /// Removes the segments from the start of the WAL that have no notifications left.
+    ///
+    /// Only a prefix of the segments is removed, so that the records of removed notifications are
+    /// never removed before the notifications themselves.
+    fn remove_finalized_segments(&self, state: &mut StorageState) -> eyre::Result<()> {
+        let active_id = state.active.as_ref().map(|active| active.id);
+        while let Some(entry) = state.segments.first_entry() {
+            if !entry.get().is_empty() || Some(*entry.key()) == active_id {
+                break
+            }
 
-        Ok((deleted_total, deleted_size))
+            let id = entry.remove_entry().0;
+            reth_fs_util::remove_file(self.segment_path(id))?;
+            debug!(target: "exex::wal::storage", segment_id = id, "Segment was removed from the storage");
+        }
+        Ok(())
     }
 
     pub(super) fn iter_notifications(
         &self,
         range: RangeInclusive<u32>,
     ) -> impl Iterator<Item = eyre::Result<(u32, u64, ExExNotification)>> + '_ {
-        range.map(move |id| {
+        let ids =
+            self.state.lock().notifications.range(range).map(|(id, _)| *id).collect::<Vec<_>>();
+        ids.into_iter().map(move |id| {
             let (notification, size) =
-                self.read_notification(id)?.ok_or_eyre("notification {id} not found")?;
+                self.read_notification(id)?.ok_or_eyre(format!("notification {id} not found"))?;
 
             Ok((id, size, notification))
         })
     }
 
-    /// Reads the notification from the file with the given ID.
+    /// Reads the notification with the given ID.
+    ///
+    /// # Returns
+    ///
+    /// The notification and the size of its record in bytes, if any.
     #[instrument(skip(self))]
     pub(super) fn read_notification(
         &self,
-        file_id: u32,
+        notification_id: u32,
     ) -> eyre::Result<Option<(ExExNotification, u64)>> {
-        let file_path = self.file_path(file_id);
-        debug!(target: "exex::wal::storage", ?file_path, "Reading notification from WAL");
-
-        let mut file = match File::open(&file_path) {
-            Ok(file) => file,
-            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
-            Err(err) => return Err(reth_fs_util::FsPathError::open(err, &file_path).into()),
+        let Some(location) = self.state.lock().notifications.get(&notification_id).copied() else {
+            return Ok(None)
         };
-        let size = file.metadata()?.len();
-
-        // Deserialize using the bincode- and msgpack-compatible serde wrapper
-        let notification: reth_exex_types::serde_bincode_compat::ExExNotification<'_> =
-            rmp_serde::decode::from_read(&mut file).map_err(|err| {
-                eyre::eyre!("failed to decode notification from {file_path:?}: {err:?}")
-            })?;
-
-        Ok(Some((notification.into(), size)))
+        let path = self.segment_path(location.segment);
+        debug!(target: "exex::wal::storage", ?path, offset = location.offset, "Reading notification from WAL");
+
+        let mut file =
+            File::open(&path).map_err(|err| reth_fs_util::FsPathError::open(err, &path))?;
+        file.seek(SeekFrom::Start(location.offset))?;
+        let mut buf = vec![0; location.size as usize];
+        file.read_exact(&mut buf)?;
+
+        let record = RawRecords { buf: &buf, offset: location.offset, done: false }
+            .next()
+            .ok_or_eyre("notification record not found")?
+            .map_err(|err| eyre::eyre!("invalid notification record in {path:?}: {err}"))?;
+        match record.decode()? {
+            WalRecord::Notification { notification, .. } => Ok(Some((notification, location.size))),
+            WalRecord::Finalized { .. } => eyre::bail!("expected notification record in {path:?}"),
+        }
     }
 
-    /// Writes the notification to the file with the given ID.
+    /// Appends the notification with the given ID to the storage.
     ///
     /// # Returns
     ///
-    /// The size of the file that was written in bytes.
+    /// The size of the record that was written in bytes.
     #[instrument(skip(self, notification))]
     pub(super) fn write_notification(
         &self,
-        file_id: u32,
+        notification_id: u32,
         notification: &ExExNotification,
     ) -> eyre::Result<u64> {
-        let file_path = self.file_path(file_id);
-        debug!(target: "exex::wal::storage", ?file_path, "Writing notification to WAL");
+        debug!(target: "exex::wal::storage", "Writing notification to WAL");
 
-        // Serialize using the bincode- and msgpack-compatible serde wrapper
+        // Serialize using the bincode-compatible serde wrapper
         let notification =
             reth_exex_types::serde_bincode_compat::ExExNotification::from(notification);
+        let mut payload = notification_id.to_le_bytes().to_vec();
+        bincode::serialize_into(&mut payload, &notification)?;
+
+        let mut state = self.state.lock();
+        let location = self.append_record(&mut state, NOTIFICATION_RECORD, &payload)?;
+        state.insert_notification(notification_id, location);
+
+        Ok(location.size)
+    }
+
+    /// Appends the record to the active segment and syncs it to disk.
+    ///
+    /// A new segment is started if there is no active segment or the active segment is full.
+    fn append_record(
+        &self,
+        state: &mut StorageState,
+        kind: u8,
+        payload: &[u8],
+    ) -> eyre::Result<RecordLocation> {
+        let mut body = Vec::with_capacity(1 + payload.len());
+        body.push(kind);
+        body.extend_from_slice(payload);
+        let body_len =
+            u32::try_from(body.len()).map_err(|_| eyre::eyre!("WAL record too large"))?;
+
+        let mut record = Vec::with_capacity(RECORD_HEADER_LEN + body.len());
+        record.extend_from_slice(&body_len.to_le_bytes());
+        record.extend_from_slice(&crc32fast::hash(&body).to_le_bytes());
+        record.extend_from_slice(&body);
+        let size = record.len() as u64;
+
+        let rotate = state.active.as_ref().map_or(true, |active| {
+            active.size > header_len() && active.size + size > MAX_SEGMENT_SIZE
+        });
+        if rotate {
+            let id = state.active.as_ref().map_or(0, |active| active.id + 1);
+            let path = self.segment_path(id);
+            debug!(target: "exex::wal::storage", ?path, "Starting new segment");
+
+            let mut file = reth_fs_util::create_file(&path)?;
+            file.write_all(&SEGMENT_MAGIC)?;
+            file.sync_all()?;
+            state.segments.insert(id, BTreeSet::new());
+            state.active = Some(ActiveSegment { id, file, size: header_len() });
+        }
 
-        reth_fs_util::atomic_write_file(&file_path, |file| {
-            rmp_serde::encode::write(file, &notification)
-        })?;
+        let active = state.active.as_mut().expect("active segment");
+        let location = RecordLocation { segment: active.id, offset: active.size, size };
+        active.file.write_all(&record)?;
+        active.file.sync_data()?;
+        active.size += size;
 
-        Ok(file_path.metadata()?.len())
+        Ok(location)
     }
 }
 
+impl StorageState {
+    fn insert_notification(&mut self, id: u32, location: RecordLocation) {
+        self.segments.entry(location.segment).or_default().insert(id);
+        self.notifications.insert(id, location);
+        self.next_notification_id = self.next_notification_id.max(id + 1);
+    }
+
+    /// Applies the record of the given segment.
+    fn replay(&mut self, segment: u32, record: &RawRecord<'_>) -> eyre::Result<()> {
+        match record.kind {
+            NOTIFICATION_RECORD => {
+                let id = record.notification_id()?;
+                let location = RecordLocation { segment, offset: record.offset, size: record.size };
+                self.insert_notification(id, location);
+            }
+            FINALIZED_RECORD => {
+                for id in record.finalized_ids()? {
+                    if let Some(location) = self.notifications.remove(&id) {
+                        if let Some(segment) = self.segments.get_mut(&location.segment) {
+                            segment.remove(&id);
+                        }
+                    }
+                    self.next_notification_id = self.next_notification_id.max(id + 1);
+                }
+            }
+            kind => eyre::bail!("unknown WAL record kind {kind} at offset {}", record.offset),
+        }
+        Ok(())
+    }
+}
+
+/// A segment file of the WAL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    /// The ID of the segment.
+    pub id: u32,
+    /// The path to the segment file.
+    pub path: PathBuf,
+}
+
+impl Segment {
+    /// Reads and decodes the records of the segment.
+    ///
+    /// Reading stops at the first invalid record.
+    pub fn read(&self) -> eyre::Result<SegmentRecords> {
+        let buf = reth_fs_util::read(&self.path)?;
+
+        let mut records = Vec::new();
+        let mut invalid = None;
+        for record in RawRecords::new(&buf) {
+            let (offset, size) = match &record {
+                Ok(record) => (record.offset, record.size),
+                Err(err) => (err.offset(), 0),
+            };
+            match record.and_then(|record| {
+                record.decode().map_err(|err| InvalidRecord::Decode(offset, err.to_string()))
+            }) {
+                Ok(record) => records.push((offset, size, record)),
+                Err(err) => {
+                    invalid = Some(err);
+                    break
+                }
+            }
+        }
+
+        Ok(SegmentRecords { size: buf.len() as u64, records, invalid })
+    }
+
+    /// Truncates the segment at the given offset and syncs it to disk.
+    fn truncate(&self, offset: u64) -> eyre::Result<()> {
+        let file = OpenOptions::new().write(true).open(&self.path)?;
+        if offset < header_len() {
+            file.set_len(0)?;
+            (&file).write_all(&SEGMENT_MAGIC)?;
+        } else {
+            file.set_len(offset)?;
+        }
+        file.sync_all()?;
+        Ok(())
+    }
+}
+
+/// The records of a [`Segment`].
+#[derive(Debug)]
+pub struct SegmentRecords {
+    /// The size of the segment file in bytes.
+    pub size: u64,
+    /// The valid records with their offsets and sizes in bytes.
+    pub records: Vec<(u64, u64, WalRecord)>,
+    /// The first invalid record, if any.
+    pub invalid: Option<InvalidRecord>,
+}
+
+/// A decoded record of a WAL [`Segment`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WalRecord {
+    /// A notification that was committed to the WAL.
+    Notification {
+        /// The ID of the notification.
+        id: u32,
+        /// The notification.
+        notification: ExExNotification,
+    },
+    /// The IDs of notifications that were removed from the WAL.
+    Finalized {
+        /// The IDs of the removed notifications.
+        ids: Vec<u32>,
+    },
+}
+
+/// An invalid record of a WAL [`Segment`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum InvalidRecord {
+    /// The segment doesn't start with the segment header.
+    #[error("invalid segment header")]
+    Header,
+    /// The record at the given offset extends beyond the end of the segment.
+    #[error("record at offset {0} is truncated")]
+    Truncated(u64),
+    /// The checksum of the record at the given offset doesn't match its contents.
+    #[error("record at offset {0} has an invalid checksum")]
+    Checksum(u64),
+    /// The record at the given offset can't be decoded.
+    #[error("record at offset {0} can't be decoded: {1}")]
+    Decode(u64, String),
+}
+
+impl InvalidRecord {
+    /// Returns the offset of the invalid record in the segment.
+    pub const fn offset(&self) -> u64 {
+        match self {
+            Self::Header => 0,
+            Self::Truncated(offset) | Self::Checksum(offset) | Self::Decode(offset, _) => *offset,
+        }
+    }
+}
+
+/// A record with a valid checksum that wasn't decoded yet.
+#[derive(Debug)]
+struct RawRecord<'a> {
+    offset: u64,
+    size: u64,
+    kind: u8,
+    payload: &'a [u8],
+}
+
+impl RawRecord<'_> {
+    fn notification_id(&self) -> eyre::Result<u32> {
+        let id = self.payload.get(..4).ok_or_eyre("notification record too short")?;
+        Ok(u32::from_le_bytes(id.try_into().expect("4 bytes")))
+    }
+
+    fn finalized_ids(&self) -> eyre::Result<Vec<u32>> {
+        if self.payload.len() % 4 != 0 {
+            eyre::bail!("invalid finalized record length")
+        }
+        Ok(self
+            .payload
+            .chunks_exact(4)
+            .map(|id| u32::from_le_bytes(id.try_into().expect("4 bytes")))
+            .collect())
+    }
+
+    fn decode(&self) -> eyre::Result<WalRecord> {
+        match self.kind {
+            NOTIFICATION_RECORD => {
+                let id = self.notification_id()?;
+                // Deserialize using the bincode-compatible serde wrapper
+                let notification: reth_exex_types::serde_bincode_compat::ExExNotification<'_> =
+                    bincode::deserialize(&self.payload[4..])?;
+                Ok(WalRecord::Notification { id, notification: notification.into() })
+            }
+            FINALIZED_RECORD => Ok(WalRecord::Finalized { ids: self.finalized_ids()? }),
+            kind => eyre::bail!("unknown record kind {kind}"),
+        }
+    }
+}
+
+/// Iterator over the records of a segment that stops at the first invalid record.
+#[derive(Debug)]
+struct RawRecords<'a> {
+    buf: &'a [u8],
+    offset: u64,
+    done: bool,
+}
+
+impl<'a> RawRecords<'a> {
+    /// Creates an iterator over the records of the segment file contents.
+    fn new(segment: &'a [u8]) -> Self {
+        match segment.strip_prefix(&SEGMENT_MAGIC) {
+            Some(buf) => Self { buf, offset: header_len(), done: false },
+            None => Self { buf: segment, offset: 0, done: false },
+        }
+    }
+}
+
+impl<'a> Iterator for RawRecords<'a> {
+    type Item = Result<RawRecord<'a>, InvalidRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None
+        }
+        if self.offset == 0 {
+            self.done = true;
+            return Some(Err(InvalidRecord::Header))
+        }
+        if self.buf.is_empty() {
+            return None
+        }
+
+        let offset = self.offset;
+        let Some((header, rest)) = self.buf.split_first_chunk::<RECORD_HEADER_LEN>() else {
+            self.done = true;
+            return Some(Err(InvalidRecord::Truncated(offset)))
+        };
+        let body_len = u32::from_le_bytes(header[..4].try_into().expect("4 bytes")) as usize;
+        let checksum = u32::from_le_bytes(header[4..].try_into().expect("4 bytes"));
+        if rest.len() < body_len || body_len == 0 {
+            self.done = true;
+            return Some(Err(InvalidRecord::Truncated(offset)))
+        }
+
+        let (body, rest) = rest.split_at(body_len);
+        if crc32fast::hash(body) != checksum {
+            self.done = true;
+            return Some(Err(InvalidRecord::Checksum(offset)))
+        }
+
+        let size = (RECORD_HEADER_LEN + body_len) as u64;
+        self.buf = rest;
+        self.offset += size;
+        Some(Ok(RawRecord { offset, size, kind: body[0], payload: &body[1..] }))
+    }
+}
+
+const fn header_len() -> u64 {
+    SEGMENT_MAGIC.len() as u64
+}
+
+fn parse_file_id(path: &Path, extension: &str) -> eyre::Result<u32> {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.strip_suffix(extension))
+        .and_then(|name| name.strip_suffix('.'))
+        .and_then(|id| id.parse().ok())
+        .ok_or_eyre(format!("failed to parse file name: {path:?}"))
+}
+
 #[cfg(test)]
 mod tests {
-    use std::{fs::File, sync::Arc};
+    use std::{fs::OpenOptions, io::Write, sync::Arc};
 
     use eyre::OptionExt;
     use reth_exex_types::ExExNotification;
     use reth_provider::Chain;
     use reth_testing_utils::generators::{self, random_block};
 
-    use super::Storage;
-
-    #[test]
-    fn test_roundtrip() -> eyre::Result<()> {
-        let mut rng = generators::rng();
-
-        let temp_dir = tempfile::tempdir()?;
-        let storage = Storage::new(&temp_dir)?;
+    use super::{InvalidRecord, Storage, WalRecord, SEGMENT_MAGIC};
 
-        let old_block = random_block(&mut rng, 0, Default::default())
+    fn notification(rng: &mut impl rand::Rng) -> eyre::Result<ExExNotification> {
+        let old_block = random_block(rng, 0, Default::default())
             .seal_with_senders()
             .ok_or_eyre("failed to recover senders")?;
-        let new_block = random_block(&mut rng, 0, Default::default())
+        let new_block = random_block(rng, 0, Default::default())
             .seal_with_senders()
             .ok_or_eyre("failed to recover senders")?;
 
-        let notification = ExExNotification::ChainReorged {
+        Ok(ExExNotification::ChainReorged {
             new: Arc::new(Chain::new(vec![new_block], Default::default(), None)),
             old: Arc::new(Chain::new(vec![old_block], Default::default(), None)),
-        };
+        })
+    }
+
+    #[test]
+    fn test_roundtrip() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir()?;
+        let storage = Storage::new(&temp_dir)?;
+
+        let notification = notification(&mut rng)?;
 
         // Do a round trip serialization and deserialization
-        let file_id = 0;
-        storage.write_notification(file_id, &notification)?;
-        let deserialized_notification = storage.read_notification(file_id)?;
+        let notification_id = 0;
+        storage.write_notification(notification_id, &notification)?;
+        let deserialized_notification = storage.read_notification(notification_id)?;
         assert_eq!(
             deserialized_notification.map(|(notification, _)| notification),
+            Some(notification.clone())
+        );
+
+        // The notification is read back from the segment after a restart
+        drop(storage);
+        let storage = Storage::new(&temp_dir)?;
+        assert_eq!(storage.notifications_range(), Some(0..=0));
+        assert_eq!(
+            storage.read_notification(notification_id)?.map(|(notification, _)| notification),
             Some(notification)
         );
 
@@ -213,21 +700,143 @@ mod tests {
     }
 
     #[test]
-    fn test_files_range() -> eyre::Result<()> {
+    fn test_remove_notifications() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir()?;
+        let storage = Storage::new(&temp_dir)?;
+
+        let notifications =
+            (0..3).map(|_| notification(&mut rng)).collect::<eyre::Result<Vec<_>>>()?;
+        for (id, notification) in notifications.iter().enumerate() {
+            storage.write_notification(id as u32, notification)?;
+        }
+
+        // Remove the notification in the middle
+        assert_eq!(storage.remove_notifications([1, 5])?.0, 1);
+        assert_eq!(storage.read_notification(1)?, None);
+        assert_eq!(
+            storage
+                .iter_notifications(0..=2)
+                .map(|entry| Ok(entry?.0))
+                .collect::<eyre::Result<Vec<_>>>()?,
+            vec![0, 2]
+        );
+
+        // The removal survives a restart and the IDs aren't reused
+        drop(storage);
+        let storage = Storage::new(&temp_dir)?;
+        assert_eq!(storage.read_notification(1)?, None);
+        assert_eq!(storage.notifications_range(), Some(0..=2));
+        assert_eq!(storage.next_notification_id(), 3);
+
+        let segments = Storage::segments(&temp_dir)?;
+        assert_eq!(segments.len(), 1);
+        let records = segments[0].read()?;
+        assert_eq!(records.invalid, None);
+        assert_eq!(records.records.len(), 4);
+        assert_eq!(records.records[3].2, WalRecord::Finalized { ids: vec![1] });
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_torn_record_recovery() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir()?;
+        let storage = Storage::new(&temp_dir)?;
+        let notification = notification(&mut rng)?;
+        storage.write_notification(0, &notification)?;
+        let size = storage.write_notification(1, &notification)?;
+        drop(storage);
+
+        // Simulate a crash in the middle of writing the second notification
+        let segment = Storage::segments(&temp_dir)?.remove(0);
+        let len = segment.path.metadata()?.len();
+        OpenOptions::new().write(true).open(&segment.path)?.set_len(len - size / 2)?;
+        assert_eq!(segment.read()?.invalid, Some(InvalidRecord::Truncated(len - size)));
+
+        // The torn record is truncated and the WAL can be appended to again
+        let storage = Storage::new(&temp_dir)?;
+        assert_eq!(storage.notifications_range(), Some(0..=0));
+        assert_eq!(segment.path.metadata()?.len(), len - size);
+        storage.write_notification(1, &notification)?;
+        assert_eq!(segment.read()?.invalid, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_torn_header_recovery() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let notification = notification(&mut rng)?;
+
+        // Simulate a crash before or in the middle of writing the header of a new segment
+        for header in [&b""[..], b"RETH"] {
+            let temp_dir = tempfile::tempdir()?;
+            let path = temp_dir.path().join("0.seg");
+            std::fs::write(&path, header)?;
+
+            // The header is rewritten and the WAL can be appended to again
+            let storage = Storage::new(&temp_dir)?;
+            assert_eq!(std::fs::read(&path)?, SEGMENT_MAGIC);
+            storage.write_notification(0, &notification)?;
+            drop(storage);
+
+            let storage = Storage::new(&temp_dir)?;
+            assert_eq!(storage.notifications_range(), Some(0..=0));
+            assert_eq!(
+                storage.read_notification(0)?.map(|(notification, _)| notification),
+                Some(notification.clone())
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_corrupted_record() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
         let temp_dir = tempfile::tempdir()?;
         let storage = Storage::new(&temp_dir)?;
+        let notification = notification(&mut rng)?;
+        storage.write_notification(0, &notification)?;
+        drop(storage);
+
+        // Flip a byte of the notification
+        let segment = Storage::segments(&temp_dir)?.remove(0);
+        let mut contents = std::fs::read(&segment.path)?;
+        let last = contents.len() - 1;
+        contents[last] ^= 1;
+        std::fs::write(&segment.path, contents)?;
+        assert_eq!(segment.read()?.invalid, Some(InvalidRecord::Checksum(8)));
+
+        Ok(())
+    }
 
-        // Create WAL files
-        File::create(storage.file_path(1))?;
-        File::create(storage.file_path(2))?;
-        File::create(storage.file_path(3))?;
+    #[test]
+    fn test_migrate_legacy_files() -> eyre::Result<()> {
+        let mut rng = generators::rng();
 
-        // Create non-WAL files that should be ignored
-        File::create(temp_dir.path().join("0.tmp"))?;
-        File::create(temp_dir.path().join("4.tmp"))?;
+        let temp_dir = tempfile::tempdir()?;
+        let notification = notification(&mut rng)?;
+        for id in [3, 4] {
+            let mut file = std::fs::File::create(temp_dir.path().join(format!("{id}.wal")))?;
+            let encoded = rmp_serde::encode::to_vec(
+                &reth_exex_types::serde_bincode_compat::ExExNotification::from(&notification),
+            )?;
+            file.write_all(&encoded)?;
+        }
 
-        // Check files range
-        assert_eq!(storage.files_range()?, Some(1..=3));
+        let storage = Storage::new(&temp_dir)?;
+        assert_eq!(storage.notifications_range(), Some(3..=4));
+        assert_eq!(
+            storage.read_notification(4)?.map(|(notification, _)| notification),
+            Some(notification)
+        );
+        assert!(!temp_dir.path().join("3.wal").exists());
 
         Ok(())
     }
@@ -1,5 +1,5 @@
 use crate::metrics::{BodyDownloaderMetrics, ResponseMetrics};
-use alloy_primitives::B256;
+use alloy_primitives::{BlockNumber, B256};
 use futures::{Future, FutureExt};
 use reth_consensus::Consensus;
 use reth_network_p2p::{
@@ -12,6 +12,7 @@ use reth_primitives::{BlockBody, GotExpected, SealedBlock, SealedHeader};
 use std::{
     collections::VecDeque,
     mem,
+    ops::RangeInclusive,
     pin::Pin,
     sync::Arc,
     task::{ready, Context, Poll},
@@ -104,12 +105,25 @@ where
         hashes.peek().is_some().then(|| hashes.collect())
     }
 
+    /// Returns the range of blocks of the pending non-empty headers.
+    fn next_request_range(&self) -> Option<RangeInclusive<BlockNumber>> {
+        let mut non_empty = self.pending_headers.iter().filter(|h| !h.is_empty());
+        let first = non_empty.next()?.number;
+        let last = non_empty.next_back().map_or(first, |h| h.number);
+        Some(first..=last)
+    }
+
     /// Submit the request with the given priority.
+    ///
+    /// The request is bounded by the range of its blocks, so that it can be sent to a peer that
+    /// serves all of them.
     fn submit_request(&mut self, req: Vec<B256>, priority: Priority) {
         tracing::trace!(target: "downloaders::bodies", request_len = req.len(), "Requesting bodies");
         let client = Arc::clone(&self.client);
+        let range_hint = self.next_request_range();
         self.last_request_len = Some(req.len());
-        self.fut = Some(client.get_block_bodies_with_priority(req, priority));
+        self.fut =
+            Some(client.get_block_bodies_with_priority_and_range_hint(req, priority, range_hint));
     }
 
     /// Process block response.
@@ -0,0 +1,74 @@
+//! The accumulator of an ERA1 file.
+//!
+//! The accumulator is the SSZ hash tree root of the list of `(block_hash, total_difficulty)` header
+//! records of the blocks in the file, as used by the historical roots of the pre-merge chain.
+
+use alloy_primitives::{B256, U256};
+use sha2::{Digest, Sha256};
+
+use super::MAX_BLOCKS_PER_ERA1;
+
+/// Depth of the merkle tree of the header records, `log2(MAX_BLOCKS_PER_ERA1)`.
+const DEPTH: usize = MAX_BLOCKS_PER_ERA1.trailing_zeros() as usize;
+
+/// Computes the accumulator root of the given header records.
+///
+/// # Panics
+///
+/// If there are more than [`MAX_BLOCKS_PER_ERA1`] records.
+pub fn accumulator_root(records: impl IntoIterator<Item = (B256, U256)>) -> B256 {
+    let mut layer = records
+        .into_iter()
+        .map(|(block_hash, total_difficulty)| {
+            hash_pair(block_hash, B256::from(total_difficulty.to_le_bytes::<32>()))
+        })
+        .collect::<Vec<_>>();
+    let len = layer.len();
+    assert!(len <= MAX_BLOCKS_PER_ERA1, "too many header records");
+
+    // merkleize the records, padding each layer with the root of an empty subtree
+    let mut zero = B256::ZERO;
+    for _ in 0..DEPTH {
+        if layer.len() % 2 == 1 {
+            layer.push(zero);
+        }
+        layer = layer.chunks_exact(2).map(|pair| hash_pair(pair[0], pair[1])).collect();
+        zero = hash_pair(zero, zero);
+    }
+    let root = layer.pop().unwrap_or(zero);
+
+    // mix in the length of the list
+    hash_pair(root, B256::from(U256::from(len).to_le_bytes::<32>()))
+}
+
+fn hash_pair(left: B256, right: B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_accumulator() {
+        // the root of an empty list is the zero subtree root mixed with a length of zero
+        let mut zero = B256::ZERO;
+        for _ in 0..DEPTH {
+            zero = hash_pair(zero, zero);
+        }
+        assert_eq!(accumulator_root([]), hash_pair(zero, B256::ZERO));
+    }
+
+    #[test]
+    fn accumulator_depends_on_records() {
+        let records =
+            [(B256::repeat_byte(1), U256::from(1)), (B256::repeat_byte(2), U256::from(3))];
+        let root = accumulator_root(records);
+        assert_eq!(root, accumulator_root(records));
+        assert_ne!(root, accumulator_root(records[..1].iter().copied()));
+        assert_ne!(root, accumulator_root([records[0], (records[1].0, U256::from(4))]));
+    }
+}
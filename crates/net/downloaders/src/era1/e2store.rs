@@ -0,0 +1,120 @@
+//! The `e2store` container format that ERA1 files are built on.
+//!
+//! An `e2store` file is a sequence of entries, each prefixed by an 8 byte header:
+//!
+//! ```text
+//! entry  := header | data
+//! header := type | length | reserved
+//! ```
+//!
+//! The type is a little endian `u16`, the length a little endian `u32` and the reserved bytes are
+//! always zero.
+
+use std::io::{self, Write};
+
+use super::Era1Error;
+
+/// Length of the header of an entry.
+pub const HEADER_LEN: usize = 8;
+
+/// The version entry, which is the first entry of every `e2store` file.
+pub const VERSION: u16 = 0x3265;
+/// A snappy compressed RLP encoded header.
+pub const COMPRESSED_HEADER: u16 = 0x03;
+/// A snappy compressed RLP encoded block body.
+pub const COMPRESSED_BODY: u16 = 0x04;
+/// Snappy compressed RLP encoded receipts of a block.
+pub const COMPRESSED_RECEIPTS: u16 = 0x05;
+/// The total difficulty of a block as a little endian `u256`.
+pub const TOTAL_DIFFICULTY: u16 = 0x06;
+/// The root of the accumulator of the block hashes and total difficulties.
+pub const ACCUMULATOR: u16 = 0x07;
+/// The index of the block offsets in the file.
+pub const BLOCK_INDEX: u16 = 0x3266;
+
+/// A single entry of an `e2store` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry<'a> {
+    /// The type of the entry.
+    pub ty: u16,
+    /// The data of the entry.
+    pub data: &'a [u8],
+}
+
+impl<'a> Entry<'a> {
+    /// Reads the next entry from the buffer and advances it.
+    ///
+    /// Returns `None` if the buffer is empty.
+    pub fn read(buf: &mut &'a [u8]) -> Result<Option<Self>, Era1Error> {
+        if buf.is_empty() {
+            return Ok(None)
+        }
+        if buf.len() < HEADER_LEN {
+            return Err(Era1Error::UnexpectedEof)
+        }
+
+        let ty = u16::from_le_bytes([buf[0], buf[1]]);
+        let len = u32::from_le_bytes([buf[2], buf[3], buf[4], buf[5]]) as usize;
+        if buf[6..HEADER_LEN] != [0, 0] {
+            return Err(Era1Error::InvalidEntry(ty))
+        }
+        let rest = &buf[HEADER_LEN..];
+        if rest.len() < len {
+            return Err(Era1Error::UnexpectedEof)
+        }
+
+        let (data, rest) = rest.split_at(len);
+        *buf = rest;
+        Ok(Some(Self { ty, data }))
+    }
+
+    /// Reads the next entry from the buffer and checks that it has the expected type.
+    pub fn read_expected(buf: &mut &'a [u8], ty: u16) -> Result<Self, Era1Error> {
+        let entry = Self::read(buf)?.ok_or(Era1Error::UnexpectedEof)?;
+        if entry.ty != ty {
+            return Err(Era1Error::UnexpectedEntry { expected: ty, got: entry.ty })
+        }
+        Ok(entry)
+    }
+
+    /// Writes the entry and returns the number of written bytes.
+    pub fn write<W: Write>(&self, writer: &mut W) -> io::Result<usize> {
+        let len = u32::try_from(self.data.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "e2store entry too large"))?;
+        writer.write_all(&self.ty.to_le_bytes())?;
+        writer.write_all(&len.to_le_bytes())?;
+        writer.write_all(&[0, 0])?;
+        writer.write_all(self.data)?;
+        Ok(HEADER_LEN + self.data.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_roundtrip() {
+        let mut buf = Vec::new();
+        let written = Entry { ty: VERSION, data: &[] }.write(&mut buf).unwrap();
+        assert_eq!(written, HEADER_LEN);
+        Entry { ty: TOTAL_DIFFICULTY, data: &[1, 2, 3] }.write(&mut buf).unwrap();
+        assert_eq!(buf[..HEADER_LEN], [0x65, 0x32, 0, 0, 0, 0, 0, 0]);
+
+        let mut reader = &buf[..];
+        assert_eq!(Entry::read(&mut reader).unwrap(), Some(Entry { ty: VERSION, data: &[] }));
+        assert_eq!(
+            Entry::read_expected(&mut reader, TOTAL_DIFFICULTY).unwrap(),
+            Entry { ty: TOTAL_DIFFICULTY, data: &[1, 2, 3] }
+        );
+        assert_eq!(Entry::read(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn truncated_entry() {
+        let mut buf = Vec::new();
+        Entry { ty: COMPRESSED_HEADER, data: &[1, 2, 3] }.write(&mut buf).unwrap();
+        buf.pop();
+        assert!(matches!(Entry::read(&mut &buf[..]), Err(Era1Error::UnexpectedEof)));
+    }
+}
@@ -0,0 +1,622 @@
+//! Reading and writing of [ERA1](https://github.com/ethereum/go-ethereum/pull/26621) archives.
+//!
+//! An ERA1 file stores up to [`MAX_BLOCKS_PER_ERA1`] consecutive pre-merge blocks in the
+//! [`e2store`] format:
+//!
+//! ```text
+//! era1        := Version | block-tuple* | other-entries* | Accumulator | BlockIndex
+//! block-tuple := CompressedHeader | CompressedBody | CompressedReceipts | TotalDifficulty
+//! ```
+//!
+//! Headers, bodies and receipts are RLP encoded and snappy compressed using the framed format.
+//! The accumulator commits to the hashes and total difficulties of all blocks in the file, see
+//! [`accumulator_root`]. A file is only canonical if its accumulator matches the accumulator root
+//! of its epoch on the canonical chain, see [`EpochRoots`].
+
+use std::{
+    collections::HashMap,
+    io::{self, Read, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use alloy_primitives::{BlockNumber, B256, U256};
+use alloy_rlp::{Decodable, Encodable};
+use reth_primitives::{BlockBody, Header, ReceiptWithBloom};
+use thiserror::Error;
+
+use crate::file_client::FileClient;
+
+mod accumulator;
+pub use accumulator::accumulator_root;
+
+pub mod e2store;
+use e2store::Entry;
+
+/// The maximum number of blocks in a single ERA1 file.
+pub const MAX_BLOCKS_PER_ERA1: usize = 8192;
+
+/// The file extension of ERA1 files.
+pub const ERA1_FILE_EXTENSION: &str = "era1";
+
+/// An error that can occur when reading or writing ERA1 files.
+#[derive(Debug, Error)]
+pub enum Era1Error {
+    /// An error occurred when reading or writing the file.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    /// An error occurred when decoding a header, body or receipts.
+    #[error(transparent)]
+    Rlp(#[from] alloy_rlp::Error),
+
+    /// The file ended in the middle of an entry.
+    #[error("unexpected end of file")]
+    UnexpectedEof,
+
+    /// An entry has an invalid header.
+    #[error("invalid e2store entry of type {0:#06x}")]
+    InvalidEntry(u16),
+
+    /// An entry of a different type was expected.
+    #[error("expected e2store entry of type {expected:#06x}, got {got:#06x}")]
+    UnexpectedEntry {
+        /// The expected type.
+        expected: u16,
+        /// The actual type.
+        got: u16,
+    },
+
+    /// The blocks of the file are not consecutive.
+    #[error("expected block {expected}, got {got}")]
+    UnexpectedBlockNumber {
+        /// The expected block number.
+        expected: BlockNumber,
+        /// The actual block number.
+        got: BlockNumber,
+    },
+
+    /// A block follows the block with the maximum block number.
+    #[error("block number overflow")]
+    BlockNumberOverflow,
+
+    /// The total difficulty of a block doesn't match the total difficulty of its parent.
+    #[error("invalid total difficulty of block {0}")]
+    InvalidTotalDifficulty(BlockNumber),
+
+    /// The accumulator doesn't match the blocks of the file.
+    #[error("accumulator mismatch: expected {expected}, got {got}")]
+    AccumulatorMismatch {
+        /// The accumulator stored in the file.
+        expected: B256,
+        /// The accumulator of the blocks of the file.
+        got: B256,
+    },
+
+    /// The block index doesn't match the blocks of the file.
+    #[error("invalid block index: {0}")]
+    InvalidBlockIndex(&'static str),
+
+    /// More than [`MAX_BLOCKS_PER_ERA1`] blocks were written to a file.
+    #[error("an ERA1 file can't contain more than {MAX_BLOCKS_PER_ERA1} blocks")]
+    TooManyBlocks,
+
+    /// The first block of the file is not the first block of an epoch.
+    #[error("ERA1 file starts at block {0}, which is not the first block of an epoch")]
+    UnalignedEpoch(BlockNumber),
+
+    /// The canonical accumulator root of the epoch of the file is unknown.
+    #[error("unknown accumulator root of epoch {0}")]
+    UnknownEpoch(u64),
+
+    /// The accumulator doesn't match the canonical accumulator root of the epoch.
+    #[error("non-canonical accumulator of epoch {epoch}: expected {expected}, got {got}")]
+    NonCanonicalAccumulator {
+        /// The epoch of the file.
+        epoch: u64,
+        /// The canonical accumulator root of the epoch.
+        expected: B256,
+        /// The accumulator of the file.
+        got: B256,
+    },
+
+    /// A line of an epoch roots file is not a hex encoded root.
+    #[error("invalid epoch root on line {0}")]
+    InvalidEpochRoot(usize),
+}
+
+/// A block as stored in an ERA1 file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Era1Block {
+    /// The header of the block.
+    pub header: Header,
+    /// The body of the block.
+    pub body: BlockBody,
+    /// The receipts of the transactions of the block.
+    pub receipts: Vec<ReceiptWithBloom>,
+    /// The total difficulty of the chain, including this block.
+    pub total_difficulty: U256,
+}
+
+/// The decoded contents of an ERA1 file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Era1File {
+    /// The consecutive blocks of the file.
+    pub blocks: Vec<Era1Block>,
+    /// The accumulator root of the blocks.
+    pub accumulator: B256,
+}
+
+impl Era1File {
+    /// Reads and decodes an ERA1 file from the given path.
+    pub async fn open<P: AsRef<Path>>(path: P) -> Result<Self, Era1Error> {
+        let buf = tokio::fs::read(path).await?;
+        Self::decode(&buf)
+    }
+
+    /// Decodes an ERA1 file.
+    ///
+    /// This checks that the blocks are consecutive and that the total difficulties, accumulator
+    /// and block index are consistent with the blocks. The blocks themselves aren't validated.
+    pub fn decode(buf: &[u8]) -> Result<Self, Era1Error> {
+        let mut reader = buf;
+        Entry::read_expected(&mut reader, e2store::VERSION)?;
+
+        let mut blocks = Vec::<Era1Block>::new();
+        let mut block_offsets = Vec::new();
+        let accumulator = loop {
+            let offset = buf.len() - reader.len();
+            let entry = Entry::read(&mut reader)?.ok_or(Era1Error::UnexpectedEof)?;
+            match entry.ty {
+                e2store::COMPRESSED_HEADER => {
+                    if blocks.len() == MAX_BLOCKS_PER_ERA1 {
+                        return Err(Era1Error::TooManyBlocks)
+                    }
+                    let block = decode_block(entry, &mut reader)?;
+                    if let Some(parent) = blocks.last() {
+                        let expected = parent
+                            .header
+                            .number
+                            .checked_add(1)
+                            .ok_or(Era1Error::BlockNumberOverflow)?;
+                        if block.header.number != expected {
+                            return Err(Era1Error::UnexpectedBlockNumber {
+                                expected,
+                                got: block.header.number,
+                            })
+                        }
+                        if block.total_difficulty !=
+                            parent.total_difficulty + block.header.difficulty
+                        {
+                            return Err(Era1Error::InvalidTotalDifficulty(block.header.number))
+                        }
+                    }
+                    blocks.push(block);
+                    block_offsets.push(offset as i64);
+                }
+                e2store::ACCUMULATOR => {
+                    let accumulator: [u8; 32] =
+                        entry.data.try_into().map_err(|_| Era1Error::InvalidEntry(entry.ty))?;
+                    break B256::from(accumulator)
+                }
+                // other entries are allowed between the blocks and the accumulator
+                _ => {}
+            }
+        };
+
+        let index_offset = (buf.len() - reader.len()) as i64;
+        let index = Entry::read_expected(&mut reader, e2store::BLOCK_INDEX)?;
+        if !reader.is_empty() {
+            return Err(Era1Error::InvalidBlockIndex("block index is not the last entry"))
+        }
+        verify_block_index(index.data, index_offset, &blocks, &block_offsets)?;
+
+        let got = accumulator_root(
+            blocks.iter().map(|block| (block.header.hash_slow(), block.total_difficulty)),
+        );
+        if got != accumulator {
+            return Err(Era1Error::AccumulatorMismatch { expected: accumulator, got })
+        }
+
+        Ok(Self { blocks, accumulator })
+    }
+
+    /// Returns the number of the first block of the file, `None` if the file is empty.
+    pub fn start_block(&self) -> Option<BlockNumber> {
+        self.blocks.first().map(|block| block.header.number)
+    }
+
+    /// Checks that the accumulator of the file is the canonical accumulator root of its epoch.
+    ///
+    /// [`Era1File::decode`] only checks that the accumulator matches the blocks of the file, so
+    /// this must be called before the blocks of an untrusted file are imported.
+    pub fn verify_epoch_root(&self, roots: &EpochRoots) -> Result<(), Era1Error> {
+        // an empty file contains no history that could be verified
+        let Some(start_block) = self.start_block() else { return Ok(()) };
+        if start_block % MAX_BLOCKS_PER_ERA1 as u64 != 0 {
+            return Err(Era1Error::UnalignedEpoch(start_block))
+        }
+
+        let epoch = start_block / MAX_BLOCKS_PER_ERA1 as u64;
+        let expected = roots.get(epoch).ok_or(Era1Error::UnknownEpoch(epoch))?;
+        if expected != self.accumulator {
+            return Err(Era1Error::NonCanonicalAccumulator {
+                epoch,
+                expected,
+                got: self.accumulator,
+            })
+        }
+        Ok(())
+    }
+
+    /// Converts the blocks of the file into a [`FileClient`] that serves their headers and
+    /// bodies.
+    pub fn into_file_client(self) -> FileClient {
+        let mut headers = HashMap::with_capacity(self.blocks.len());
+        let mut bodies = HashMap::with_capacity(self.blocks.len());
+        for block in self.blocks {
+            bodies.insert(block.header.hash_slow(), block.body);
+            headers.insert(block.header.number, block.header);
+        }
+        FileClient::default().with_headers(headers).with_bodies(bodies)
+    }
+}
+
+/// The accumulator roots of the canonical ERA1 files of a chain, indexed by epoch.
+///
+/// The roots are the epoch accumulators of the pre-merge historical hashes accumulator, which
+/// commits to the entire pre-merge history of the chain.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EpochRoots(Vec<B256>);
+
+impl EpochRoots {
+    /// Creates the roots from the accumulator roots of all epochs, in order.
+    pub const fn new(roots: Vec<B256>) -> Self {
+        Self(roots)
+    }
+
+    /// Returns the roots bundled with reth for the given network, `None` if there are none.
+    ///
+    /// The network is named as in [`era1_file_name`]. The bundled roots are read from
+    /// `roots/<network>.txt` in the format of [`EpochRoots::from_str`]; a network without bundled
+    /// roots requires the roots to be provided, see [`EpochRoots::open`].
+    pub fn bundled(network: &str) -> Option<Self> {
+        let roots = match network {
+            "mainnet" => include_str!("roots/mainnet.txt"),
+            _ => return None,
+        };
+        roots.parse::<Self>().ok().filter(|roots| !roots.is_empty())
+    }
+
+    /// Reads the roots from the file at the given path, see [`EpochRoots::from_str`].
+    pub async fn open<P: AsRef<Path>>(path: P) -> Result<Self, Era1Error> {
+        tokio::fs::read_to_string(path).await?.parse()
+    }
+
+    /// Returns the canonical accumulator root of the given epoch.
+    pub fn get(&self, epoch: u64) -> Option<B256> {
+        self.0.get(usize::try_from(epoch).ok()?).copied()
+    }
+
+    /// Returns the number of epochs.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no roots.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromStr for EpochRoots {
+    type Err = Era1Error;
+
+    /// Parses one hex encoded root per line, starting at epoch 0. Empty lines are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(idx, line)| {
+                B256::from_str(line.trim()).map_err(|_| Era1Error::InvalidEpochRoot(idx + 1))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+/// Returns the name of the ERA1 file of the given epoch, as `<network>-<epoch>-<root>.era1`.
+///
+/// The root is the hex encoded first 4 bytes of the accumulator root of the file.
+pub fn era1_file_name(network: &str, epoch: u64, accumulator: B256) -> String {
+    format!(
+        "{network}-{epoch:05}-{}.{ERA1_FILE_EXTENSION}",
+        alloy_primitives::hex::encode(&accumulator[..4])
+    )
+}
+
+/// Writes blocks to an ERA1 file.
+///
+/// The blocks must be consecutive. The accumulator and block index are written by
+/// [`Era1Writer::finish`].
+#[derive(Debug)]
+pub struct Era1Writer<W> {
+    writer: W,
+    /// The number of bytes written so far.
+    offset: u64,
+    /// The offsets of the headers of the written blocks.
+    block_offsets: Vec<u64>,
+    /// The header records of the written blocks, used to compute the accumulator.
+    records: Vec<(B256, U256)>,
+    /// The number of the first written block.
+    start_block: Option<BlockNumber>,
+}
+
+impl<W: Write> Era1Writer<W> {
+    /// Creates a new writer and writes the version entry.
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        let offset = Entry { ty: e2store::VERSION, data: &[] }.write(&mut writer)? as u64;
+        Ok(Self {
+            writer,
+            offset,
+            block_offsets: Vec::new(),
+            records: Vec::new(),
+            start_block: None,
+        })
+    }
+
+    /// Returns the number of written blocks.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if no blocks were written yet.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Appends the next block to the file.
+    pub fn append(&mut self, block: &Era1Block) -> Result<(), Era1Error> {
+        if self.records.len() == MAX_BLOCKS_PER_ERA1 {
+            return Err(Era1Error::TooManyBlocks)
+        }
+        let start_block = *self.start_block.get_or_insert(block.header.number);
+        let expected = start_block + self.records.len() as u64;
+        if block.header.number != expected {
+            return Err(Era1Error::UnexpectedBlockNumber { expected, got: block.header.number })
+        }
+
+        self.block_offsets.push(self.offset);
+        let entries = [
+            (e2store::COMPRESSED_HEADER, compress(&block.header)?),
+            (e2store::COMPRESSED_BODY, compress(&block.body)?),
+            (e2store::COMPRESSED_RECEIPTS, compress(&block.receipts)?),
+            (e2store::TOTAL_DIFFICULTY, block.total_difficulty.to_le_bytes::<32>().to_vec()),
+        ];
+        for (ty, data) in entries {
+            self.offset += Entry { ty, data: &data }.write(&mut self.writer)? as u64;
+        }
+
+        self.records.push((block.header.hash_slow(), block.total_difficulty));
+        Ok(())
+    }
+
+    /// Writes the accumulator and the block index.
+    ///
+    /// Returns the inner writer and the accumulator root.
+    pub fn finish(mut self) -> Result<(W, B256), Era1Error> {
+        let accumulator = accumulator_root(self.records);
+        self.offset += Entry { ty: e2store::ACCUMULATOR, data: accumulator.as_slice() }
+            .write(&mut self.writer)? as u64;
+
+        // offsets are relative to the start of the block index entry
+        let index_offset = self.offset as i64;
+        let mut index = Vec::with_capacity((self.block_offsets.len() + 2) * 8);
+        index.extend_from_slice(&self.start_block.unwrap_or_default().to_le_bytes());
+        for offset in &self.block_offsets {
+            index.extend_from_slice(&(*offset as i64 - index_offset).to_le_bytes());
+        }
+        index.extend_from_slice(&(self.block_offsets.len() as u64).to_le_bytes());
+        Entry { ty: e2store::BLOCK_INDEX, data: &index }.write(&mut self.writer)?;
+
+        self.writer.flush()?;
+        Ok((self.writer, accumulator))
+    }
+}
+
+/// Decodes the block tuple that starts with the given header entry.
+fn decode_block<'a>(header: Entry<'a>, reader: &mut &'a [u8]) -> Result<Era1Block, Era1Error> {
+    let body = Entry::read_expected(reader, e2store::COMPRESSED_BODY)?;
+    let receipts = Entry::read_expected(reader, e2store::COMPRESSED_RECEIPTS)?;
+    let total_difficulty = Entry::read_expected(reader, e2store::TOTAL_DIFFICULTY)?;
+    let total_difficulty = U256::try_from_le_slice(total_difficulty.data)
+        .filter(|_| total_difficulty.data.len() == 32)
+        .ok_or(Era1Error::InvalidEntry(e2store::TOTAL_DIFFICULTY))?;
+
+    Ok(Era1Block {
+        header: decompress(header.data)?,
+        body: decompress(body.data)?,
+        receipts: decompress(receipts.data)?,
+        total_difficulty,
+    })
+}
+
+/// Checks that the block index points to the headers of the given blocks.
+fn verify_block_index(
+    index: &[u8],
+    index_offset: i64,
+    blocks: &[Era1Block],
+    block_offsets: &[i64],
+) -> Result<(), Era1Error> {
+    if index.len() != (blocks.len() + 2) * 8 {
+        return Err(Era1Error::InvalidBlockIndex("unexpected length"))
+    }
+    let mut values =
+        index.chunks_exact(8).map(|chunk| i64::from_le_bytes(chunk.try_into().expect("8 bytes")));
+
+    let start_block = values.next().expect("start block") as u64;
+    if blocks.first().is_some_and(|block| block.header.number != start_block) {
+        return Err(Era1Error::InvalidBlockIndex("unexpected starting block"))
+    }
+    for offset in block_offsets {
+        let relative_offset = values.next().expect("block offset");
+        if relative_offset.checked_add(index_offset) != Some(*offset) {
+            return Err(Era1Error::InvalidBlockIndex("block offset doesn't point to a header"))
+        }
+    }
+    if values.next().expect("count") as usize != blocks.len() {
+        return Err(Era1Error::InvalidBlockIndex("unexpected block count"))
+    }
+    Ok(())
+}
+
+/// RLP encodes the value and compresses it with the snappy framed format.
+fn compress<T: Encodable>(value: &T) -> io::Result<Vec<u8>> {
+    let mut encoder = snap::write::FrameEncoder::new(Vec::new());
+    encoder.write_all(&alloy_rlp::encode(value))?;
+    encoder.into_inner().map_err(|err| err.into_error())
+}
+
+/// Decompresses the snappy framed data and RLP decodes the value.
+fn decompress<T: Decodable>(data: &[u8]) -> Result<T, Era1Error> {
+    let mut buf = Vec::new();
+    snap::read::FrameDecoder::new(data).read_to_end(&mut buf)?;
+    Ok(T::decode(&mut &buf[..])?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Receipt, TxType};
+
+    fn blocks(start: BlockNumber, count: usize) -> Vec<Era1Block> {
+        let mut total_difficulty = U256::from(start);
+        (start..start + count as u64)
+            .map(|number| {
+                let header =
+                    Header { number, difficulty: U256::from(number + 1), ..Default::default() };
+                total_difficulty += header.difficulty;
+                let receipt = Receipt {
+                    tx_type: TxType::Legacy,
+                    success: true,
+                    cumulative_gas_used: number,
+                    ..Default::default()
+                };
+                Era1Block {
+                    header,
+                    body: BlockBody::default(),
+                    receipts: vec![receipt.with_bloom()],
+                    total_difficulty,
+                }
+            })
+            .collect()
+    }
+
+    fn encode(blocks: &[Era1Block]) -> (Vec<u8>, B256) {
+        let mut writer = Era1Writer::new(Vec::new()).unwrap();
+        for block in blocks {
+            writer.append(block).unwrap();
+        }
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn era1_roundtrip() {
+        let blocks = blocks(8192, 10);
+        let (buf, accumulator) = encode(&blocks);
+
+        let file = Era1File::decode(&buf).unwrap();
+        assert_eq!(file.accumulator, accumulator);
+        assert_eq!(file.start_block(), Some(8192));
+        assert_eq!(file.blocks, blocks);
+
+        let client = file.into_file_client();
+        assert_eq!(client.min_block(), Some(8192));
+        assert_eq!(client.max_block(), Some(8201));
+        assert_eq!(client.bodies_len(), 10);
+        assert!(client.has_canonical_blocks());
+    }
+
+    #[test]
+    fn reject_non_consecutive_blocks() {
+        let blocks = blocks(0, 3);
+        let mut writer = Era1Writer::new(Vec::new()).unwrap();
+        writer.append(&blocks[0]).unwrap();
+        assert!(matches!(
+            writer.append(&blocks[2]),
+            Err(Era1Error::UnexpectedBlockNumber { expected: 1, got: 2 })
+        ));
+    }
+
+    #[test]
+    fn reject_invalid_accumulator() {
+        let (mut buf, accumulator) = encode(&blocks(0, 3));
+
+        // the accumulator entry precedes the block index of 3 offsets
+        let accumulator_start = buf.len() - (5 * 8 + e2store::HEADER_LEN) - 32;
+        assert_eq!(&buf[accumulator_start..accumulator_start + 32], accumulator.as_slice());
+        buf[accumulator_start] ^= 1;
+
+        assert!(matches!(Era1File::decode(&buf), Err(Era1Error::AccumulatorMismatch { .. })));
+    }
+
+    #[test]
+    fn reject_invalid_total_difficulty() {
+        let mut blocks = blocks(0, 3);
+        blocks[2].total_difficulty += U256::from(1);
+        let (buf, _) = encode(&blocks);
+
+        assert!(matches!(Era1File::decode(&buf), Err(Era1Error::InvalidTotalDifficulty(2))));
+    }
+
+    #[test]
+    fn reject_overflowing_block_index() {
+        let (mut buf, _) = encode(&blocks(0, 3));
+
+        // the first offset follows the starting block of the block index of 3 offsets
+        let first_offset = buf.len() - 4 * 8;
+        buf[first_offset..first_offset + 8].copy_from_slice(&i64::MAX.to_le_bytes());
+
+        assert!(matches!(Era1File::decode(&buf), Err(Era1Error::InvalidBlockIndex(_))));
+    }
+
+    #[test]
+    fn verify_canonical_epoch_roots() {
+        let (buf, accumulator) = encode(&blocks(8192, 10));
+        let file = Era1File::decode(&buf).unwrap();
+
+        let roots: EpochRoots = format!("{}\n\n{accumulator}\n", B256::ZERO).parse().unwrap();
+        assert_eq!(roots.len(), 2);
+        file.verify_epoch_root(&roots).unwrap();
+
+        let roots = EpochRoots::new(vec![B256::ZERO, B256::repeat_byte(1)]);
+        assert!(matches!(
+            file.verify_epoch_root(&roots),
+            Err(Era1Error::NonCanonicalAccumulator { epoch: 1, .. })
+        ));
+        assert!(matches!(
+            file.verify_epoch_root(&EpochRoots::new(vec![accumulator])),
+            Err(Era1Error::UnknownEpoch(1))
+        ));
+
+        let (buf, accumulator) = encode(&blocks(8193, 10));
+        let file = Era1File::decode(&buf).unwrap();
+        let roots = EpochRoots::new(vec![accumulator, accumulator]);
+        assert!(matches!(file.verify_epoch_root(&roots), Err(Era1Error::UnalignedEpoch(8193))));
+
+        assert!(matches!(
+            "0x00\n".parse::<EpochRoots>(),
+            Err(Era1Error::InvalidEpochRoot(1))
+        ));
+
+        // the bundled roots must be well formed
+        include_str!("roots/mainnet.txt").parse::<EpochRoots>().unwrap();
+        assert_eq!(EpochRoots::bundled("unknown"), None);
+    }
+
+    #[test]
+    fn era1_file_names() {
+        let accumulator = B256::repeat_byte(0x5e);
+        assert_eq!(era1_file_name("mainnet", 3, accumulator), "mainnet-00003-5e5e5e5e.era1");
+    }
+}
@@ -33,6 +33,12 @@ pub mod file_client;
 /// files, efficiently buffering receipts for retrieval.
 pub mod receipt_file_client;
 
+/// Module for reading and writing ERA1 archives of pre-merge history.
+///
+/// Contains [`Era1File`](era1::Era1File) to read blocks from ERA1 files, which can be served by a
+/// [`FileClient`](file_client::FileClient), and [`Era1Writer`](era1::Era1Writer) to export blocks.
+pub mod era1;
+
 /// Module with a codec for reading and encoding block bodies in files.
 ///
 /// Enables decoding and encoding `Block` types within file contexts.
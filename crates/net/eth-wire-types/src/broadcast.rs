@@ -70,6 +70,30 @@ impl From<NewBlockHashes> for Vec<BlockHashNumber> {
     }
 }
 
+/// Announces the range of blocks a peer is able to serve, introduced in `eth/69`.
+///
+/// This is sent whenever the node's available range changes, for example when a new block is
+/// imported or old history gets pruned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct BlockRangeUpdate {
+    /// The earliest block the peer can serve.
+    pub earliest: u64,
+    /// The latest block the peer can serve.
+    pub latest: u64,
+    /// The hash of the latest block.
+    pub latest_hash: B256,
+}
+
+impl BlockRangeUpdate {
+    /// Returns `true` if the given block number is within the announced range.
+    pub const fn contains(&self, number: u64) -> bool {
+        self.earliest <= number && number <= self.latest
+    }
+}
+
 /// A new block with the current total difficulty, which includes the difficulty of the returned
 /// block.
 #[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
@@ -154,7 +178,7 @@ impl NewPooledTransactionHashes {
                 matches!(version, EthVersion::Eth67 | EthVersion::Eth66)
             }
             Self::Eth68(_) => {
-                matches!(version, EthVersion::Eth68)
+                matches!(version, EthVersion::Eth68 | EthVersion::Eth69)
             }
         }
     }
@@ -1,4 +1,4 @@
-//! Implements Ethereum wire protocol for versions 66, 67, and 68.
+//! Implements Ethereum wire protocol for versions 66, 67, 68 and 69.
 //! Defines structs/enums for messages, request-response pairs, and broadcasts.
 //! Handles compatibility with [`EthVersion`].
 //!
@@ -7,9 +7,10 @@
 //! Reference: [Ethereum Wire Protocol](https://github.com/ethereum/wiki/wiki/Ethereum-Wire-Protocol).
 
 use super::{
-    broadcast::NewBlockHashes, BlockBodies, BlockHeaders, GetBlockBodies, GetBlockHeaders,
-    GetNodeData, GetPooledTransactions, GetReceipts, NewBlock, NewPooledTransactionHashes66,
-    NewPooledTransactionHashes68, NodeData, PooledTransactions, Receipts, Status, Transactions,
+    broadcast::NewBlockHashes, BlockBodies, BlockHeaders, BlockRangeUpdate, GetBlockBodies,
+    GetBlockHeaders, GetNodeData, GetPooledTransactions, GetReceipts, NewBlock,
+    NewPooledTransactionHashes66, NewPooledTransactionHashes68, NodeData, PooledTransactions,
+    Receipts, Receipts69, Status, StatusEth69, Transactions,
 };
 use crate::{EthVersion, SharedTransactions};
 
@@ -48,7 +49,13 @@ impl ProtocolMessage {
         let message_type = EthMessageID::decode(buf)?;
 
         let message = match message_type {
-            EthMessageID::Status => EthMessage::Status(Status::decode(buf)?),
+            EthMessageID::Status => {
+                if version >= EthVersion::Eth69 {
+                    EthMessage::StatusEth69(StatusEth69::decode(buf)?)
+                } else {
+                    EthMessage::Status(Status::decode(buf)?)
+                }
+            }
             EthMessageID::NewBlockHashes => {
                 EthMessage::NewBlockHashes(NewBlockHashes::decode(buf)?)
             }
@@ -108,8 +115,19 @@ impl ProtocolMessage {
                 EthMessage::GetReceipts(request_pair)
             }
             EthMessageID::Receipts => {
-                let request_pair = RequestPair::<Receipts>::decode(buf)?;
-                EthMessage::Receipts(request_pair)
+                if version >= EthVersion::Eth69 {
+                    let request_pair = RequestPair::<Receipts69>::decode(buf)?;
+                    EthMessage::Receipts69(request_pair)
+                } else {
+                    let request_pair = RequestPair::<Receipts>::decode(buf)?;
+                    EthMessage::Receipts(request_pair)
+                }
+            }
+            EthMessageID::BlockRangeUpdate => {
+                if version < EthVersion::Eth69 {
+                    return Err(MessageError::Invalid(version, EthMessageID::BlockRangeUpdate))
+                }
+                EthMessage::BlockRangeUpdate(BlockRangeUpdate::decode(buf)?)
             }
         };
         Ok(Self { message_type, message })
@@ -162,7 +180,7 @@ impl From<EthBroadcastMessage> for ProtocolBroadcastMessage {
     }
 }
 
-/// Represents a message in the eth wire protocol, versions 66, 67 and 68.
+/// Represents a message in the eth wire protocol, versions 66, 67, 68 and 69.
 ///
 /// The ethereum wire protocol is a set of messages that are broadcast to the network in two
 /// styles:
@@ -179,11 +197,17 @@ impl From<EthBroadcastMessage> for ProtocolBroadcastMessage {
 /// The `eth/68` changes only `NewPooledTransactionHashes` to include `types` and `sized`. For
 /// it, `NewPooledTransactionHashes` is renamed as [`NewPooledTransactionHashes66`] and
 /// [`NewPooledTransactionHashes68`] is defined.
+///
+/// The `eth/69` replaces the total difficulty in the [`Status`] with the range of available
+/// blocks ([`StatusEth69`]), drops the bloom filter from [`Receipts`] ([`Receipts69`]) and adds
+/// the [`BlockRangeUpdate`] announcement.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EthMessage {
     /// Represents a Status message required for the protocol handshake.
     Status(Status),
+    /// Represents a Status message required for the `eth/69` protocol handshake.
+    StatusEth69(StatusEth69),
     /// Represents a `NewBlockHashes` message broadcast to the network.
     NewBlockHashes(NewBlockHashes),
     /// Represents a `NewBlock` message broadcast to the network.
@@ -215,13 +239,17 @@ pub enum EthMessage {
     GetReceipts(RequestPair<GetReceipts>),
     /// Represents a Receipts request-response pair.
     Receipts(RequestPair<Receipts>),
+    /// Represents a Receipts request-response pair for eth/69 version.
+    Receipts69(RequestPair<Receipts69>),
+    /// Represents a `BlockRangeUpdate` message broadcast to the network.
+    BlockRangeUpdate(BlockRangeUpdate),
 }
 
 impl EthMessage {
     /// Returns the message's ID.
     pub const fn message_id(&self) -> EthMessageID {
         match self {
-            Self::Status(_) => EthMessageID::Status,
+            Self::Status(_) | Self::StatusEth69(_) => EthMessageID::Status,
             Self::NewBlockHashes(_) => EthMessageID::NewBlockHashes,
             Self::NewBlock(_) => EthMessageID::NewBlock,
             Self::Transactions(_) => EthMessageID::Transactions,
@@ -237,7 +265,8 @@ impl EthMessage {
             Self::GetNodeData(_) => EthMessageID::GetNodeData,
             Self::NodeData(_) => EthMessageID::NodeData,
             Self::GetReceipts(_) => EthMessageID::GetReceipts,
-            Self::Receipts(_) => EthMessageID::Receipts,
+            Self::Receipts(_) | Self::Receipts69(_) => EthMessageID::Receipts,
+            Self::BlockRangeUpdate(_) => EthMessageID::BlockRangeUpdate,
         }
     }
 }
@@ -246,6 +275,7 @@ impl Encodable for EthMessage {
     fn encode(&self, out: &mut dyn BufMut) {
         match self {
             Self::Status(status) => status.encode(out),
+            Self::StatusEth69(status) => status.encode(out),
             Self::NewBlockHashes(new_block_hashes) => new_block_hashes.encode(out),
             Self::NewBlock(new_block) => new_block.encode(out),
             Self::Transactions(transactions) => transactions.encode(out),
@@ -261,11 +291,14 @@ impl Encodable for EthMessage {
             Self::NodeData(data) => data.encode(out),
             Self::GetReceipts(request) => request.encode(out),
             Self::Receipts(receipts) => receipts.encode(out),
+            Self::Receipts69(receipts) => receipts.encode(out),
+            Self::BlockRangeUpdate(update) => update.encode(out),
         }
     }
     fn length(&self) -> usize {
         match self {
             Self::Status(status) => status.length(),
+            Self::StatusEth69(status) => status.length(),
             Self::NewBlockHashes(new_block_hashes) => new_block_hashes.length(),
             Self::NewBlock(new_block) => new_block.length(),
             Self::Transactions(transactions) => transactions.length(),
@@ -281,6 +314,8 @@ impl Encodable for EthMessage {
             Self::NodeData(data) => data.length(),
             Self::GetReceipts(request) => request.length(),
             Self::Receipts(receipts) => receipts.length(),
+            Self::Receipts69(receipts) => receipts.length(),
+            Self::BlockRangeUpdate(update) => update.length(),
         }
     }
 }
@@ -363,12 +398,23 @@ pub enum EthMessageID {
     GetReceipts = 0x0f,
     /// Represents receipts.
     Receipts = 0x10,
+    /// Announces the range of available blocks.
+    BlockRangeUpdate = 0x11,
 }
 
 impl EthMessageID {
-    /// Returns the max value.
-    pub const fn max() -> u8 {
-        Self::Receipts as u8
+    /// Returns the max value for the given version.
+    pub const fn max(version: EthVersion) -> u8 {
+        if version.is_eth69() {
+            Self::BlockRangeUpdate as u8
+        } else {
+            Self::Receipts as u8
+        }
+    }
+
+    /// Returns the number of message IDs reserved by the given version.
+    pub const fn message_count(version: EthVersion) -> u8 {
+        Self::max(version) + 1
     }
 }
 
@@ -399,6 +445,7 @@ impl Decodable for EthMessageID {
             0x0e => Self::NodeData,
             0x0f => Self::GetReceipts,
             0x10 => Self::Receipts,
+            0x11 => Self::BlockRangeUpdate,
             _ => return Err(alloy_rlp::Error::Custom("Invalid message ID")),
         };
         buf.advance(1);
@@ -426,6 +473,7 @@ impl TryFrom<usize> for EthMessageID {
             0x0e => Ok(Self::NodeData),
             0x0f => Ok(Self::GetReceipts),
             0x10 => Ok(Self::Receipts),
+            0x11 => Ok(Self::BlockRangeUpdate),
             _ => Err("Invalid message ID"),
         }
     }
@@ -494,7 +542,8 @@ where
 mod tests {
     use super::MessageError;
     use crate::{
-        message::RequestPair, EthMessage, EthMessageID, GetNodeData, NodeData, ProtocolMessage,
+        message::RequestPair, BlockRangeUpdate, EthMessage, EthMessageID, EthVersion, GetNodeData,
+        NodeData, ProtocolMessage, Status, StatusEth69,
     };
     use alloy_primitives::hex;
     use alloy_rlp::{Decodable, Encodable, Error};
@@ -524,6 +573,33 @@ mod tests {
         assert!(matches!(msg, Err(MessageError::Invalid(..))));
     }
 
+    #[test]
+    fn test_block_range_update_added_at_eth69() {
+        let update = EthMessage::BlockRangeUpdate(BlockRangeUpdate {
+            earliest: 0,
+            latest: 100,
+            latest_hash: Default::default(),
+        });
+        let buf = encode(ProtocolMessage::from(update.clone()));
+        let msg = ProtocolMessage::decode_message(EthVersion::Eth68, &mut &buf[..]);
+        assert!(matches!(msg, Err(MessageError::Invalid(..))));
+
+        let msg = ProtocolMessage::decode_message(EthVersion::Eth69, &mut &buf[..]).unwrap();
+        assert_eq!(msg.message, update);
+    }
+
+    #[test]
+    fn test_status_decoded_by_version() {
+        let status = StatusEth69::new(Status::default(), BlockRangeUpdate::default());
+        let buf = encode(ProtocolMessage::from(EthMessage::StatusEth69(status)));
+        let msg = ProtocolMessage::decode_message(EthVersion::Eth69, &mut &buf[..]).unwrap();
+        assert_eq!(msg.message, EthMessage::StatusEth69(status));
+
+        let buf = encode(ProtocolMessage::from(EthMessage::Status(Status::default())));
+        let msg = ProtocolMessage::decode_message(EthVersion::Eth68, &mut &buf[..]).unwrap();
+        assert_eq!(msg.message, EthMessage::Status(Status::default()));
+    }
+
     #[test]
     fn request_pair_encode() {
         let request_pair = RequestPair { request_id: 1337, message: vec![5u8] };
@@ -1,9 +1,9 @@
 //! Implements the `GetReceipts` and `Receipts` message types.
 
 use alloy_primitives::B256;
-use alloy_rlp::{RlpDecodableWrapper, RlpEncodableWrapper};
+use alloy_rlp::{BufMut, Decodable, Encodable, Header, RlpDecodableWrapper, RlpEncodableWrapper};
 use reth_codecs_derive::add_arbitrary_tests;
-use reth_primitives::ReceiptWithBloom;
+use reth_primitives::{Receipt, ReceiptWithBloom};
 
 /// A request for transaction receipts from the given block hashes.
 #[derive(Clone, Debug, PartialEq, Eq, RlpEncodableWrapper, RlpDecodableWrapper, Default)]
@@ -26,9 +26,108 @@ pub struct Receipts(
     pub Vec<Vec<ReceiptWithBloom>>,
 );
 
+/// The `eth/69` response to [`GetReceipts`].
+///
+/// Receipts no longer include the bloom filter, which can be recomputed from the logs.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodableWrapper, RlpDecodableWrapper, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Receipts69(
+    /// Each receipt list should correspond to a block hash in the request.
+    pub Vec<Vec<Receipt69>>,
+);
+
+impl From<Receipts> for Receipts69 {
+    fn from(receipts: Receipts) -> Self {
+        Self(
+            receipts
+                .0
+                .into_iter()
+                .map(|block| block.into_iter().map(|r| Receipt69(r.receipt)).collect())
+                .collect(),
+        )
+    }
+}
+
+impl From<Receipts69> for Receipts {
+    /// Recomputes the bloom filter of every receipt.
+    fn from(receipts: Receipts69) -> Self {
+        Self(
+            receipts
+                .0
+                .into_iter()
+                .map(|block| block.into_iter().map(|r| r.0.with_bloom()).collect())
+                .collect(),
+        )
+    }
+}
+
+/// A receipt as encoded in `eth/69`: `[tx-type, post-state-or-status, cumulative-gas, logs]`.
+///
+/// Unlike the consensus encoding, the transaction type is always included as a regular list
+/// element and the logs bloom is omitted.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Receipt69(pub Receipt);
+
+impl Receipt69 {
+    fn payload_length(&self) -> usize {
+        self.0.tx_type.length() +
+            self.0.success.length() +
+            self.0.cumulative_gas_used.length() +
+            self.0.logs.length()
+    }
+}
+
+impl Encodable for Receipt69 {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.payload_length() }.encode(out);
+        self.0.tx_type.encode(out);
+        self.0.success.encode(out);
+        self.0.cumulative_gas_used.encode(out);
+        self.0.logs.encode(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_length();
+        payload_length + alloy_rlp::length_of_length(payload_length)
+    }
+}
+
+impl Decodable for Receipt69 {
+    #[allow(clippy::needless_update)]
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        let started_len = buf.len();
+        let receipt = Receipt {
+            tx_type: Decodable::decode(buf)?,
+            success: Decodable::decode(buf)?,
+            cumulative_gas_used: Decodable::decode(buf)?,
+            logs: Decodable::decode(buf)?,
+            ..Default::default()
+        };
+        let consumed = started_len - buf.len();
+        if consumed != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: consumed,
+            })
+        }
+        Ok(Self(receipt))
+    }
+}
+
+impl From<Receipt> for Receipt69 {
+    fn from(receipt: Receipt) -> Self {
+        Self(receipt)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{message::RequestPair, GetReceipts, Receipts};
+    use crate::{message::RequestPair, GetReceipts, Receipt69, Receipts, Receipts69};
     use alloy_primitives::{hex, Log};
     use alloy_rlp::{Decodable, Encodable};
     use reth_primitives::{Receipt, ReceiptWithBloom, TxType};
@@ -49,6 +148,36 @@ mod tests {
         assert_eq!(receipts, decoded);
     }
 
+    #[test]
+    #[allow(clippy::needless_update)]
+    fn roundtrip_eth69_receipts() {
+        let receipt = Receipt {
+            tx_type: TxType::Eip1559,
+            success: true,
+            cumulative_gas_used: 21_000,
+            logs: vec![Log::new_unchecked(
+                hex!("0000000000000000000000000000000000000011").into(),
+                vec![
+                    hex!("000000000000000000000000000000000000000000000000000000000000dead").into()
+                ],
+                hex!("0100ff")[..].into(),
+            )],
+            ..Default::default()
+        };
+        let receipts = Receipts(vec![vec![receipt.clone().with_bloom()], vec![]]);
+        let receipts69 = Receipts69::from(receipts.clone());
+        assert_eq!(receipts69.0[0], vec![Receipt69(receipt)]);
+
+        let mut out = vec![];
+        receipts69.encode(&mut out);
+        assert_eq!(out.len(), receipts69.length());
+        let decoded = Receipts69::decode(&mut out.as_slice()).unwrap();
+        assert_eq!(receipts69, decoded);
+
+        // the bloom is recomputed from the logs
+        assert_eq!(Receipts::from(decoded), receipts);
+    }
+
     #[test]
     // Test vector from: https://eips.ethereum.org/EIPS/eip-2481
     fn encode_get_receipts() {
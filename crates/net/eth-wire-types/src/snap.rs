@@ -0,0 +1,531 @@
+//! Types for the snap wire protocol: <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+//!
+//! Unlike `eth`, every snap message carries its request id as the first field of the message
+//! itself.
+
+use alloy_consensus::constants::{EMPTY_ROOT_HASH, KECCAK_EMPTY};
+use alloy_primitives::{Bytes, B256, U256};
+use alloy_rlp::{
+    Decodable, Encodable, Header, RlpDecodable, RlpDecodableWrapper, RlpEncodable,
+    RlpEncodableWrapper,
+};
+use bytes::{BufMut, BytesMut};
+use reth_codecs_derive::add_arbitrary_tests;
+
+/// The number of messages of the `snap/1` protocol.
+pub const SNAP_MESSAGE_COUNT: u8 = 8;
+
+/// Represents message IDs for `snap` protocol messages.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SnapMessageId {
+    /// Requests a range of accounts of the state trie.
+    GetAccountRange = 0x00,
+    /// Response to [`SnapMessageId::GetAccountRange`].
+    AccountRange = 0x01,
+    /// Requests ranges of storage slots of a set of accounts.
+    GetStorageRanges = 0x02,
+    /// Response to [`SnapMessageId::GetStorageRanges`].
+    StorageRanges = 0x03,
+    /// Requests contract bytecodes by hash.
+    GetByteCodes = 0x04,
+    /// Response to [`SnapMessageId::GetByteCodes`].
+    ByteCodes = 0x05,
+    /// Requests trie nodes by path.
+    GetTrieNodes = 0x06,
+    /// Response to [`SnapMessageId::GetTrieNodes`].
+    TrieNodes = 0x07,
+}
+
+impl TryFrom<u8> for SnapMessageId {
+    type Error = alloy_rlp::Error;
+
+    fn try_from(id: u8) -> Result<Self, Self::Error> {
+        match id {
+            0x00 => Ok(Self::GetAccountRange),
+            0x01 => Ok(Self::AccountRange),
+            0x02 => Ok(Self::GetStorageRanges),
+            0x03 => Ok(Self::StorageRanges),
+            0x04 => Ok(Self::GetByteCodes),
+            0x05 => Ok(Self::ByteCodes),
+            0x06 => Ok(Self::GetTrieNodes),
+            0x07 => Ok(Self::TrieNodes),
+            _ => Err(alloy_rlp::Error::Custom("invalid snap message id")),
+        }
+    }
+}
+
+/// Requests an unknown number of accounts from a given account trie, starting at the specified
+/// account hash and capped by the maximum allowed response size in bytes.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct GetAccountRange {
+    /// The request id.
+    pub request_id: u64,
+    /// The root hash of the account trie to serve.
+    pub root_hash: B256,
+    /// The account hash of the first account to retrieve.
+    pub starting_hash: B256,
+    /// The account hash after which to stop serving data.
+    pub limit_hash: B256,
+    /// The soft limit at which to stop returning data.
+    pub response_bytes: u64,
+}
+
+/// The response to [`GetAccountRange`].
+///
+/// The accounts are ordered by their hash. The proof covers the requested starting hash and the
+/// last returned account.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct AccountRange {
+    /// The request id this response belongs to.
+    pub request_id: u64,
+    /// The consecutive accounts of the requested range.
+    pub accounts: Vec<AccountData>,
+    /// The trie nodes proving the range.
+    pub proof: Vec<Bytes>,
+}
+
+/// An account of an [`AccountRange`].
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct AccountData {
+    /// The hash of the account address.
+    pub hash: B256,
+    /// The account body.
+    pub body: SlimAccount,
+}
+
+/// The account body in the slim format used by the snap protocol.
+///
+/// This is the same as the account encoding in the state trie, except that the empty storage root
+/// and the empty code hash are encoded as empty strings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+pub struct SlimAccount {
+    /// The account nonce.
+    pub nonce: u64,
+    /// The account balance.
+    pub balance: U256,
+    /// The root of the account's storage trie.
+    pub storage_root: B256,
+    /// The hash of the account's bytecode.
+    pub code_hash: B256,
+}
+
+impl Default for SlimAccount {
+    fn default() -> Self {
+        Self {
+            nonce: 0,
+            balance: U256::ZERO,
+            storage_root: EMPTY_ROOT_HASH,
+            code_hash: KECCAK_EMPTY,
+        }
+    }
+}
+
+impl SlimAccount {
+    fn slim_storage_root(&self) -> &[u8] {
+        if self.storage_root == EMPTY_ROOT_HASH {
+            &[]
+        } else {
+            self.storage_root.as_slice()
+        }
+    }
+
+    fn slim_code_hash(&self) -> &[u8] {
+        if self.code_hash == KECCAK_EMPTY {
+            &[]
+        } else {
+            self.code_hash.as_slice()
+        }
+    }
+
+    fn payload_length(&self) -> usize {
+        self.nonce.length() +
+            self.balance.length() +
+            self.slim_storage_root().length() +
+            self.slim_code_hash().length()
+    }
+}
+
+impl Encodable for SlimAccount {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.payload_length() }.encode(out);
+        self.nonce.encode(out);
+        self.balance.encode(out);
+        self.slim_storage_root().encode(out);
+        self.slim_code_hash().encode(out);
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_length();
+        payload_length + alloy_rlp::length_of_length(payload_length)
+    }
+}
+
+impl Decodable for SlimAccount {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        let started_len = buf.len();
+
+        let nonce = u64::decode(buf)?;
+        let balance = U256::decode(buf)?;
+        let storage_root = decode_slim_hash(buf, EMPTY_ROOT_HASH)?;
+        let code_hash = decode_slim_hash(buf, KECCAK_EMPTY)?;
+
+        let consumed = started_len - buf.len();
+        if consumed != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: consumed,
+            })
+        }
+
+        Ok(Self { nonce, balance, storage_root, code_hash })
+    }
+}
+
+/// Decodes a hash that is encoded as an empty string if it equals the given default.
+fn decode_slim_hash(buf: &mut &[u8], default: B256) -> alloy_rlp::Result<B256> {
+    let bytes = Bytes::decode(buf)?;
+    match bytes.len() {
+        0 => Ok(default),
+        32 => Ok(B256::from_slice(&bytes)),
+        _ => Err(alloy_rlp::Error::UnexpectedLength),
+    }
+}
+
+/// Requests the storage slots of multiple accounts' storage tries.
+///
+/// If the starting or limit hash is empty, the range is unbounded on that side. Only the last
+/// account's storage may be served partially.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct GetStorageRanges {
+    /// The request id.
+    pub request_id: u64,
+    /// The root hash of the account trie to serve.
+    pub root_hash: B256,
+    /// The account hashes of the storage tries to serve.
+    pub account_hashes: Vec<B256>,
+    /// The storage slot hash of the first slot to retrieve, may be empty.
+    pub starting_hash: Bytes,
+    /// The storage slot hash after which to stop serving, may be empty.
+    pub limit_hash: Bytes,
+    /// The soft limit at which to stop returning data.
+    pub response_bytes: u64,
+}
+
+impl GetStorageRanges {
+    /// Returns the first slot hash to serve, defaulting to the lowest possible hash.
+    pub fn starting_hash(&self) -> B256 {
+        if self.starting_hash.len() == 32 {
+            B256::from_slice(&self.starting_hash)
+        } else {
+            B256::ZERO
+        }
+    }
+
+    /// Returns the slot hash after which to stop serving, defaulting to the highest possible
+    /// hash.
+    pub fn limit_hash(&self) -> B256 {
+        if self.limit_hash.len() == 32 {
+            B256::from_slice(&self.limit_hash)
+        } else {
+            B256::repeat_byte(0xff)
+        }
+    }
+}
+
+/// The response to [`GetStorageRanges`].
+///
+/// The proof is only set if the storage of the last account was served partially, or if the
+/// request started at a non-zero slot hash.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct StorageRanges {
+    /// The request id this response belongs to.
+    pub request_id: u64,
+    /// The storage slots of each requested account, in request order.
+    pub slots: Vec<Vec<StorageData>>,
+    /// The trie nodes proving the last range.
+    pub proof: Vec<Bytes>,
+}
+
+/// A storage slot of [`StorageRanges`].
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct StorageData {
+    /// The hash of the storage slot key.
+    pub hash: B256,
+    /// The RLP encoded slot value, as stored in the storage trie.
+    pub data: Bytes,
+}
+
+impl StorageData {
+    /// Creates a new storage slot entry for the given value.
+    pub fn new(hash: B256, value: U256) -> Self {
+        Self { hash, data: alloy_rlp::encode(value).into() }
+    }
+
+    /// Decodes the slot value.
+    pub fn value(&self) -> alloy_rlp::Result<U256> {
+        U256::decode(&mut &self.data[..])
+    }
+}
+
+/// Requests contract bytecodes by their code hash.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct GetByteCodes {
+    /// The request id.
+    pub request_id: u64,
+    /// The code hashes to retrieve.
+    pub hashes: Vec<B256>,
+    /// The soft limit at which to stop returning data.
+    pub response_bytes: u64,
+}
+
+/// The response to [`GetByteCodes`], in request order with unavailable codes skipped.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct ByteCodes {
+    /// The request id this response belongs to.
+    pub request_id: u64,
+    /// The requested bytecodes.
+    pub codes: Vec<Bytes>,
+}
+
+/// A path into the state trie, made of the compact encoded account path followed by the compact
+/// encoded storage paths of that account.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodableWrapper, RlpDecodableWrapper, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct TriePath(pub Vec<Bytes>);
+
+/// Requests trie nodes of the state trie or the storage tries by path.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct GetTrieNodes {
+    /// The request id.
+    pub request_id: u64,
+    /// The root hash of the account trie to serve.
+    pub root_hash: B256,
+    /// The trie node paths to retrieve.
+    pub paths: Vec<TriePath>,
+    /// The soft limit at which to stop returning data.
+    pub response_bytes: u64,
+}
+
+/// The response to [`GetTrieNodes`], in request order.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct TrieNodes {
+    /// The request id this response belongs to.
+    pub request_id: u64,
+    /// The requested trie nodes.
+    pub nodes: Vec<Bytes>,
+}
+
+/// Represents a `snap` protocol message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SnapMessage {
+    /// Represents a [`GetAccountRange`] request.
+    GetAccountRange(GetAccountRange),
+    /// Represents an [`AccountRange`] response.
+    AccountRange(AccountRange),
+    /// Represents a [`GetStorageRanges`] request.
+    GetStorageRanges(GetStorageRanges),
+    /// Represents a [`StorageRanges`] response.
+    StorageRanges(StorageRanges),
+    /// Represents a [`GetByteCodes`] request.
+    GetByteCodes(GetByteCodes),
+    /// Represents a [`ByteCodes`] response.
+    ByteCodes(ByteCodes),
+    /// Represents a [`GetTrieNodes`] request.
+    GetTrieNodes(GetTrieNodes),
+    /// Represents a [`TrieNodes`] response.
+    TrieNodes(TrieNodes),
+}
+
+impl SnapMessage {
+    /// Returns the message's ID.
+    pub const fn message_id(&self) -> SnapMessageId {
+        match self {
+            Self::GetAccountRange(_) => SnapMessageId::GetAccountRange,
+            Self::AccountRange(_) => SnapMessageId::AccountRange,
+            Self::GetStorageRanges(_) => SnapMessageId::GetStorageRanges,
+            Self::StorageRanges(_) => SnapMessageId::StorageRanges,
+            Self::GetByteCodes(_) => SnapMessageId::GetByteCodes,
+            Self::ByteCodes(_) => SnapMessageId::ByteCodes,
+            Self::GetTrieNodes(_) => SnapMessageId::GetTrieNodes,
+            Self::TrieNodes(_) => SnapMessageId::TrieNodes,
+        }
+    }
+
+    /// Returns the request id of the message.
+    pub const fn request_id(&self) -> u64 {
+        match self {
+            Self::GetAccountRange(msg) => msg.request_id,
+            Self::AccountRange(msg) => msg.request_id,
+            Self::GetStorageRanges(msg) => msg.request_id,
+            Self::StorageRanges(msg) => msg.request_id,
+            Self::GetByteCodes(msg) => msg.request_id,
+            Self::ByteCodes(msg) => msg.request_id,
+            Self::GetTrieNodes(msg) => msg.request_id,
+            Self::TrieNodes(msg) => msg.request_id,
+        }
+    }
+
+    /// Returns `true` if the message is a request.
+    pub const fn is_request(&self) -> bool {
+        matches!(
+            self,
+            Self::GetAccountRange(_) |
+                Self::GetStorageRanges(_) |
+                Self::GetByteCodes(_) |
+                Self::GetTrieNodes(_)
+        )
+    }
+
+    /// Encodes the message, prefixed with its message ID.
+    ///
+    /// The message ID is relative to the offset of the protocol in the `RLPx` connection.
+    pub fn encoded(&self) -> BytesMut {
+        let mut buf = BytesMut::new();
+        buf.put_u8(self.message_id() as u8);
+        match self {
+            Self::GetAccountRange(msg) => msg.encode(&mut buf),
+            Self::AccountRange(msg) => msg.encode(&mut buf),
+            Self::GetStorageRanges(msg) => msg.encode(&mut buf),
+            Self::StorageRanges(msg) => msg.encode(&mut buf),
+            Self::GetByteCodes(msg) => msg.encode(&mut buf),
+            Self::ByteCodes(msg) => msg.encode(&mut buf),
+            Self::GetTrieNodes(msg) => msg.encode(&mut buf),
+            Self::TrieNodes(msg) => msg.encode(&mut buf),
+        }
+        buf
+    }
+
+    /// Decodes a message that is prefixed with its message ID.
+    pub fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let (&id, rest) = buf.split_first().ok_or(alloy_rlp::Error::InputTooShort)?;
+        let id = SnapMessageId::try_from(id)?;
+        *buf = rest;
+        let message = match id {
+            SnapMessageId::GetAccountRange => Self::GetAccountRange(Decodable::decode(buf)?),
+            SnapMessageId::AccountRange => Self::AccountRange(Decodable::decode(buf)?),
+            SnapMessageId::GetStorageRanges => Self::GetStorageRanges(Decodable::decode(buf)?),
+            SnapMessageId::StorageRanges => Self::StorageRanges(Decodable::decode(buf)?),
+            SnapMessageId::GetByteCodes => Self::GetByteCodes(Decodable::decode(buf)?),
+            SnapMessageId::ByteCodes => Self::ByteCodes(Decodable::decode(buf)?),
+            SnapMessageId::GetTrieNodes => Self::GetTrieNodes(Decodable::decode(buf)?),
+            SnapMessageId::TrieNodes => Self::TrieNodes(Decodable::decode(buf)?),
+        };
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slim_account_encoding() {
+        let account = SlimAccount { nonce: 1, balance: U256::from(2), ..Default::default() };
+        let encoded = alloy_rlp::encode(account);
+        // [nonce, balance, "", ""]
+        assert_eq!(encoded, [0xc4, 0x01, 0x02, 0x80, 0x80]);
+        assert_eq!(SlimAccount::decode(&mut &encoded[..]).unwrap(), account);
+
+        let account = SlimAccount {
+            storage_root: B256::repeat_byte(1),
+            code_hash: B256::repeat_byte(2),
+            ..account
+        };
+        let encoded = alloy_rlp::encode(account);
+        assert_eq!(SlimAccount::decode(&mut &encoded[..]).unwrap(), account);
+    }
+
+    #[test]
+    fn storage_range_bounds() {
+        let request = GetStorageRanges::default();
+        assert_eq!(request.starting_hash(), B256::ZERO);
+        assert_eq!(request.limit_hash(), B256::repeat_byte(0xff));
+
+        let request = GetStorageRanges {
+            starting_hash: B256::repeat_byte(1).into(),
+            limit_hash: B256::repeat_byte(2).into(),
+            ..Default::default()
+        };
+        assert_eq!(request.starting_hash(), B256::repeat_byte(1));
+        assert_eq!(request.limit_hash(), B256::repeat_byte(2));
+    }
+
+    #[test]
+    fn snap_message_roundtrip() {
+        let messages = [
+            SnapMessage::GetAccountRange(GetAccountRange {
+                request_id: 1,
+                root_hash: B256::repeat_byte(1),
+                starting_hash: B256::ZERO,
+                limit_hash: B256::repeat_byte(0xff),
+                response_bytes: 512 * 1024,
+            }),
+            SnapMessage::AccountRange(AccountRange {
+                request_id: 1,
+                accounts: vec![AccountData {
+                    hash: B256::repeat_byte(2),
+                    body: Default::default(),
+                }],
+                proof: vec![Bytes::from_static(&[0xc0])],
+            }),
+            SnapMessage::StorageRanges(StorageRanges {
+                request_id: 2,
+                slots: vec![vec![StorageData::new(B256::repeat_byte(3), U256::from(4))]],
+                proof: Vec::new(),
+            }),
+            SnapMessage::TrieNodes(TrieNodes { request_id: 3, nodes: Vec::new() }),
+        ];
+        for message in messages {
+            let encoded = message.encoded();
+            assert_eq!(encoded[0], message.message_id() as u8);
+            assert_eq!(SnapMessage::decode(&mut &encoded[..]).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn storage_data_value() {
+        let data = StorageData::new(B256::ZERO, U256::from(0x1234));
+        assert_eq!(data.value().unwrap(), U256::from(0x1234));
+    }
+}
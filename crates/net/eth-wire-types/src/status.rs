@@ -1,4 +1,4 @@
-use crate::EthVersion;
+use crate::{BlockRangeUpdate, EthVersion};
 use alloy_chains::{Chain, NamedChain};
 use alloy_primitives::{hex, B256, U256};
 use alloy_rlp::{RlpDecodable, RlpEncodable};
@@ -134,6 +134,99 @@ impl Default for Status {
     }
 }
 
+/// The `eth/69` status message.
+///
+/// Compared to [`Status`] this drops the total difficulty and instead announces the range of
+/// blocks the peer is able to serve, see also [`BlockRangeUpdate`].
+///
+/// See also <https://eips.ethereum.org/EIPS/eip-7642>
+#[derive(Copy, Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct StatusEth69 {
+    /// The current protocol version, always 69.
+    pub version: u8,
+
+    /// The chain id, as introduced in
+    /// [EIP155](https://eips.ethereum.org/EIPS/eip-155#list-of-chain-ids).
+    pub chain: Chain,
+
+    /// The genesis hash of the peer's chain.
+    pub genesis: B256,
+
+    /// The fork identifier as defined by
+    /// [EIP-2124](https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2124.md).
+    pub forkid: ForkId,
+
+    /// The earliest block the peer can serve.
+    pub earliest: u64,
+
+    /// The latest block the peer can serve.
+    pub latest: u64,
+
+    /// The hash of the latest block.
+    pub blockhash: B256,
+}
+
+impl StatusEth69 {
+    /// Creates the `eth/69` status from the given [`Status`] and the range of available blocks.
+    ///
+    /// The total difficulty of the [`Status`] is dropped.
+    pub const fn new(status: Status, range: BlockRangeUpdate) -> Self {
+        Self {
+            version: EthVersion::Eth69 as u8,
+            chain: status.chain,
+            genesis: status.genesis,
+            forkid: status.forkid,
+            earliest: range.earliest,
+            latest: range.latest,
+            blockhash: range.latest_hash,
+        }
+    }
+
+    /// Returns the range of blocks the peer announced.
+    pub const fn block_range(&self) -> BlockRangeUpdate {
+        BlockRangeUpdate {
+            earliest: self.earliest,
+            latest: self.latest,
+            latest_hash: self.blockhash,
+        }
+    }
+
+    /// Converts this into a [`Status`].
+    ///
+    /// `eth/69` peers no longer announce their total difficulty, so it is set to zero.
+    pub const fn into_status(self) -> Status {
+        Status {
+            version: self.version,
+            chain: self.chain,
+            total_difficulty: U256::ZERO,
+            blockhash: self.blockhash,
+            genesis: self.genesis,
+            forkid: self.forkid,
+        }
+    }
+}
+
+impl Display for StatusEth69 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let hexed_blockhash = hex::encode(self.blockhash);
+        let hexed_genesis = hex::encode(self.genesis);
+        write!(
+            f,
+            "Status {{ version: {}, chain: {}, genesis: {}, forkid: {:X?}, earliest: {}, latest: {}, blockhash: {} }}",
+            self.version,
+            self.chain,
+            hexed_genesis,
+            self.forkid,
+            self.earliest,
+            self.latest,
+            hexed_blockhash,
+        )
+    }
+}
+
 /// Builder for [`Status`] messages.
 ///
 /// # Example
@@ -215,7 +308,7 @@ impl StatusBuilder {
 
 #[cfg(test)]
 mod tests {
-    use crate::{EthVersion, Status};
+    use crate::{BlockRangeUpdate, EthVersion, Status, StatusEth69};
     use alloy_consensus::constants::MAINNET_GENESIS_HASH;
     use alloy_genesis::Genesis;
     use alloy_primitives::{hex, B256, U256};
@@ -387,4 +480,23 @@ mod tests {
         assert_eq!(status.blockhash, head_hash);
         assert_eq!(status.genesis, genesis_hash);
     }
+
+    #[test]
+    fn eth69_status_roundtrip() {
+        let status = Status::default();
+        let range = BlockRangeUpdate { earliest: 10, latest: 100, latest_hash: B256::random() };
+        let eth69 = StatusEth69::new(status, range);
+        assert_eq!(eth69.version, EthVersion::Eth69 as u8);
+        assert_eq!(eth69.block_range(), range);
+
+        let mut rlp_status = vec![];
+        eth69.encode(&mut rlp_status);
+        assert_eq!(StatusEth69::decode(&mut &rlp_status[..]).unwrap(), eth69);
+
+        let legacy = eth69.into_status();
+        assert_eq!(legacy.genesis, status.genesis);
+        assert_eq!(legacy.forkid, status.forkid);
+        assert_eq!(legacy.blockhash, range.latest_hash);
+        assert_eq!(legacy.total_difficulty, U256::ZERO);
+    }
 }
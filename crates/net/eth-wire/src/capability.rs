@@ -158,7 +158,7 @@ impl SharedCapability {
     /// Returns the number of protocol messages supported by this capability.
     pub const fn num_messages(&self) -> u8 {
         match self {
-            Self::Eth { version: _version, .. } => EthMessageID::max() + 1,
+            Self::Eth { version, .. } => EthMessageID::message_count(*version),
             Self::UnknownCapability { messages, .. } => *messages,
         }
     }
@@ -539,4 +539,25 @@ mod tests {
         let shared_eth = shared.find_by_relative_offset(1 + proto.messages()).unwrap();
         assert_eq!(shared_eth.name(), "eth");
     }
+
+    #[test]
+    fn test_snap_offset_after_eth() {
+        for (version, snap_offset) in [
+            (EthVersion::Eth66, MAX_RESERVED_MESSAGE_ID + 1 + 17),
+            (EthVersion::Eth68, MAX_RESERVED_MESSAGE_ID + 1 + 17),
+            (EthVersion::Eth69, MAX_RESERVED_MESSAGE_ID + 1 + 18),
+        ] {
+            let local_capabilities = vec![version.into(), Protocol::snap_1()];
+            let peer_capabilities = vec![version.into(), Capability::snap_1()];
+
+            let shared =
+                SharedCapabilities::try_new(local_capabilities, peer_capabilities).unwrap();
+
+            let snap = shared.find_by_offset(snap_offset).unwrap();
+            assert_eq!(snap.name(), "snap");
+            assert_eq!(snap.message_id_offset(), snap_offset);
+            let eth = shared.find_by_offset(snap_offset - 1).unwrap();
+            assert_eq!(eth.name(), "eth");
+        }
+    }
 }
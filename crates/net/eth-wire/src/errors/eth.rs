@@ -36,6 +36,14 @@ pub enum EthStreamError {
         /// The number of transaction sizes.
         sizes_len: usize,
     },
+    /// Received a `BlockRangeUpdate` whose earliest block is above the latest block.
+    #[error("invalid block range update: earliest={earliest} latest={latest}")]
+    InvalidBlockRangeUpdate {
+        /// The earliest available block.
+        earliest: u64,
+        /// The latest available block.
+        latest: u64,
+    },
     /// Error when data is not received from peer for a prolonged period.
     #[error("never received data from remote peer")]
     StreamTimeout,
@@ -100,4 +108,12 @@ pub enum EthHandshakeError {
         /// The maximum allowed bit length for the total difficulty.
         maximum: usize,
     },
+    #[error("invalid block range in status message: earliest {earliest}, latest {latest}")]
+    /// The `eth/69` status announced an earliest block past the latest block.
+    InvalidBlockRange {
+        /// The announced earliest block.
+        earliest: u64,
+        /// The announced latest block.
+        latest: u64,
+    },
 }
@@ -1,8 +1,9 @@
 use crate::{
     errors::{EthHandshakeError, EthStreamError},
-    message::{EthBroadcastMessage, ProtocolBroadcastMessage},
+    message::{EthBroadcastMessage, ProtocolBroadcastMessage, RequestPair},
     p2pstream::HANDSHAKE_TIMEOUT,
-    CanDisconnect, DisconnectReason, EthMessage, EthVersion, ProtocolMessage, Status,
+    BlockRangeUpdate, CanDisconnect, DisconnectReason, EthMessage, EthVersion, ProtocolMessage,
+    Receipts, Status, StatusEth69,
 };
 use alloy_primitives::bytes::{Bytes, BytesMut};
 use futures::{ready, Sink, SinkExt, StreamExt};
@@ -28,12 +29,23 @@ pub const MAX_MESSAGE_SIZE: usize = 10 * 1024 * 1024;
 pub struct UnauthedEthStream<S> {
     #[pin]
     inner: S,
+    /// The range of blocks announced if `eth/69` is negotiated.
+    block_range: Option<BlockRangeUpdate>,
 }
 
 impl<S> UnauthedEthStream<S> {
     /// Create a new `UnauthedEthStream` from a type `S` which implements `Stream` and `Sink`.
     pub const fn new(inner: S) -> Self {
-        Self { inner }
+        Self { inner, block_range: None }
+    }
+
+    /// Sets the range of blocks this node is able to serve, which is announced in the `eth/69`
+    /// status.
+    ///
+    /// If not set, the range `0..=0` with the status block hash is announced.
+    pub const fn with_block_range(mut self, block_range: BlockRangeUpdate) -> Self {
+        self.block_range = Some(block_range);
+        self
     }
 
     /// Consumes the type and returns the wrapped stream
@@ -76,16 +88,30 @@ where
         status: Status,
         fork_filter: ForkFilter,
     ) -> Result<(EthStream<S>, Status), EthStreamError> {
-        trace!(
-            %status,
-            "sending eth status to peer"
-        );
+        let version = EthVersion::try_from(status.version)?;
 
         // we need to encode and decode here on our own because we don't have an `EthStream` yet
         // The max length for a status with TTD is: <msg id = 1 byte> + <rlp(status) = 88 byte>
-        self.inner
-            .send(alloy_rlp::encode(ProtocolMessage::from(EthMessage::Status(status))).into())
-            .await?;
+        let our_msg = if version >= EthVersion::Eth69 {
+            let block_range = self.block_range.unwrap_or(BlockRangeUpdate {
+                earliest: 0,
+                latest: 0,
+                latest_hash: status.blockhash,
+            });
+            let status = StatusEth69::new(status, block_range);
+            trace!(
+                %status,
+                "sending eth status to peer"
+            );
+            EthMessage::StatusEth69(status)
+        } else {
+            trace!(
+                %status,
+                "sending eth status to peer"
+            );
+            EthMessage::Status(status)
+        };
+        self.inner.send(alloy_rlp::encode(ProtocolMessage::from(our_msg)).into()).await?;
 
         let their_msg_res = self.inner.next().await;
 
@@ -102,7 +128,6 @@ where
             return Err(EthStreamError::MessageTooBig(their_msg.len()))
         }
 
-        let version = EthVersion::try_from(status.version)?;
         let msg = match ProtocolMessage::decode_message(version, &mut their_msg.as_ref()) {
             Ok(m) => m,
             Err(err) => {
@@ -112,71 +137,88 @@ where
             }
         };
 
-        // The following checks should match the checks in go-ethereum:
-        // https://github.com/ethereum/go-ethereum/blob/9244d5cd61f3ea5a7645fdf2a1a96d53421e412f/eth/protocols/eth/handshake.go#L87-L89
-        match msg.message {
+        let (resp, their_block_range) = match msg.message {
             EthMessage::Status(resp) => {
                 trace!(
                     status=%resp,
                     "validating incoming eth status from peer"
                 );
-                if status.genesis != resp.genesis {
-                    self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                    return Err(EthHandshakeError::MismatchedGenesis(
-                        GotExpected { expected: status.genesis, got: resp.genesis }.into(),
-                    )
-                    .into())
-                }
-
-                if status.version != resp.version {
-                    self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                    return Err(EthHandshakeError::MismatchedProtocolVersion(GotExpected {
-                        got: resp.version,
-                        expected: status.version,
-                    })
-                    .into())
-                }
-
-                if status.chain != resp.chain {
-                    self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                    return Err(EthHandshakeError::MismatchedChain(GotExpected {
-                        got: resp.chain,
-                        expected: status.chain,
-                    })
-                    .into())
-                }
-
-                // TD at mainnet block #7753254 is 76 bits. If it becomes 100 million times
-                // larger, it will still fit within 100 bits
-                if status.total_difficulty.bit_len() > 100 {
+                (resp, None)
+            }
+            EthMessage::StatusEth69(resp) => {
+                trace!(
+                    status=%resp,
+                    "validating incoming eth status from peer"
+                );
+                if resp.earliest > resp.latest {
                     self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                    return Err(EthHandshakeError::TotalDifficultyBitLenTooLarge {
-                        got: status.total_difficulty.bit_len(),
-                        maximum: 100,
+                    return Err(EthHandshakeError::InvalidBlockRange {
+                        earliest: resp.earliest,
+                        latest: resp.latest,
                     }
                     .into())
                 }
-
-                if let Err(err) =
-                    fork_filter.validate(resp.forkid).map_err(EthHandshakeError::InvalidFork)
-                {
-                    self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                    return Err(err.into())
-                }
-
-                // now we can create the `EthStream` because the peer has successfully completed
-                // the handshake
-                let stream = EthStream::new(version, self.inner);
-
-                Ok((stream, resp))
+                (resp.into_status(), Some(resp.block_range()))
             }
             _ => {
                 self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                Err(EthStreamError::EthHandshakeError(
+                return Err(EthStreamError::EthHandshakeError(
                     EthHandshakeError::NonStatusMessageInHandshake,
                 ))
             }
+        };
+
+        // The following checks should match the checks in go-ethereum:
+        // https://github.com/ethereum/go-ethereum/blob/9244d5cd61f3ea5a7645fdf2a1a96d53421e412f/eth/protocols/eth/handshake.go#L87-L89
+        if status.genesis != resp.genesis {
+            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+            return Err(EthHandshakeError::MismatchedGenesis(
+                GotExpected { expected: status.genesis, got: resp.genesis }.into(),
+            )
+            .into())
+        }
+
+        if status.version != resp.version {
+            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+            return Err(EthHandshakeError::MismatchedProtocolVersion(GotExpected {
+                got: resp.version,
+                expected: status.version,
+            })
+            .into())
+        }
+
+        if status.chain != resp.chain {
+            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+            return Err(EthHandshakeError::MismatchedChain(GotExpected {
+                got: resp.chain,
+                expected: status.chain,
+            })
+            .into())
         }
+
+        // TD at mainnet block #7753254 is 76 bits. If it becomes 100 million times
+        // larger, it will still fit within 100 bits
+        if status.total_difficulty.bit_len() > 100 {
+            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+            return Err(EthHandshakeError::TotalDifficultyBitLenTooLarge {
+                got: status.total_difficulty.bit_len(),
+                maximum: 100,
+            }
+            .into())
+        }
+
+        if let Err(err) = fork_filter.validate(resp.forkid).map_err(EthHandshakeError::InvalidFork)
+        {
+            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+            return Err(err.into())
+        }
+
+        // now we can create the `EthStream` because the peer has successfully completed
+        // the handshake
+        let mut stream = EthStream::new(version, self.inner);
+        stream.remote_block_range = their_block_range;
+
+        Ok((stream, resp))
     }
 }
 
@@ -187,6 +229,8 @@ where
 pub struct EthStream<S> {
     /// Negotiated eth version.
     version: EthVersion,
+    /// The range of blocks the peer announced in its `eth/69` status.
+    remote_block_range: Option<BlockRangeUpdate>,
     #[pin]
     inner: S,
 }
@@ -196,7 +240,7 @@ impl<S> EthStream<S> {
     /// to manually handshake a peer.
     #[inline]
     pub const fn new(version: EthVersion, inner: S) -> Self {
-        Self { version, inner }
+        Self { version, remote_block_range: None, inner }
     }
 
     /// Returns the eth version.
@@ -205,6 +249,15 @@ impl<S> EthStream<S> {
         self.version
     }
 
+    /// Returns the range of blocks the peer announced during the handshake.
+    ///
+    /// This is only available for `eth/69` and later, subsequent changes are announced with
+    /// [`EthMessage::BlockRangeUpdate`] messages.
+    #[inline]
+    pub const fn remote_block_range(&self) -> Option<BlockRangeUpdate> {
+        self.remote_block_range
+    }
+
     /// Returns the underlying stream.
     #[inline]
     pub const fn inner(&self) -> &S {
@@ -279,13 +332,20 @@ where
             }
         };
 
-        if matches!(msg.message, EthMessage::Status(_)) {
-            return Poll::Ready(Some(Err(EthStreamError::EthHandshakeError(
-                EthHandshakeError::StatusNotInHandshake,
-            ))))
+        match msg.message {
+            EthMessage::Status(_) | EthMessage::StatusEth69(_) => Poll::Ready(Some(Err(
+                EthStreamError::EthHandshakeError(EthHandshakeError::StatusNotInHandshake),
+            ))),
+            // the bloom filter is no longer sent in eth/69, so we recompute it here to provide
+            // the same receipts regardless of the negotiated version
+            EthMessage::Receipts69(RequestPair { request_id, message }) => {
+                Poll::Ready(Some(Ok(EthMessage::Receipts(RequestPair {
+                    request_id,
+                    message: Receipts::from(message),
+                }))))
+            }
+            message => Poll::Ready(Some(Ok(message))),
         }
-
-        Poll::Ready(Some(Ok(msg.message)))
     }
 }
 
@@ -301,7 +361,7 @@ where
     }
 
     fn start_send(self: Pin<&mut Self>, item: EthMessage) -> Result<(), Self::Error> {
-        if matches!(item, EthMessage::Status(_)) {
+        if matches!(item, EthMessage::Status(_) | EthMessage::StatusEth69(_)) {
             // TODO: to disconnect here we would need to do something similar to P2PStream's
             // start_disconnect, which would ideally be a part of the CanDisconnect trait, or at
             // least similar.
@@ -314,6 +374,15 @@ where
             return Err(EthStreamError::EthHandshakeError(EthHandshakeError::StatusNotInHandshake))
         }
 
+        let item = match item {
+            EthMessage::Receipts(RequestPair { request_id, message })
+                if self.version >= EthVersion::Eth69 =>
+            {
+                EthMessage::Receipts69(RequestPair { request_id, message: message.into() })
+            }
+            item => item,
+        };
+
         self.project()
             .inner
             .start_send(Bytes::from(alloy_rlp::encode(ProtocolMessage::from(item))))?;
@@ -347,16 +416,17 @@ mod tests {
         broadcast::BlockHashNumber,
         errors::{EthHandshakeError, EthStreamError},
         hello::DEFAULT_TCP_PORT,
+        message::RequestPair,
         p2pstream::UnauthedP2PStream,
-        EthMessage, EthStream, EthVersion, HelloMessageWithProtocols, PassthroughCodec,
-        ProtocolVersion, Status,
+        BlockRangeUpdate, EthMessage, EthStream, EthVersion, HelloMessageWithProtocols,
+        PassthroughCodec, ProtocolVersion, Receipts, Status,
     };
     use alloy_primitives::{B256, U256};
     use futures::{SinkExt, StreamExt};
     use reth_chainspec::NamedChain;
     use reth_ecies::stream::ECIESStream;
     use reth_network_peers::pk2id;
-    use reth_primitives::{ForkFilter, Head};
+    use reth_primitives::{ForkFilter, Head, Receipt, TxType};
     use secp256k1::{SecretKey, SECP256K1};
     use std::time::Duration;
     use tokio::net::{TcpListener, TcpStream};
@@ -409,6 +479,93 @@ mod tests {
         handle.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn can_handshake_eth69() {
+        let genesis = B256::random();
+        let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
+
+        let status = Status {
+            version: EthVersion::Eth69 as u8,
+            chain: NamedChain::Mainnet.into(),
+            total_difficulty: U256::from(100),
+            blockhash: B256::random(),
+            genesis,
+            forkid: fork_filter.current(),
+        };
+        let server_range =
+            BlockRangeUpdate { earliest: 10, latest: 20, latest_hash: B256::random() };
+        let client_range =
+            BlockRangeUpdate { earliest: 0, latest: 30, latest_hash: B256::random() };
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let fork_filter_clone = fork_filter.clone();
+        let handle = tokio::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let stream = PassthroughCodec::default().framed(incoming);
+            let (stream, their_status) = UnauthedEthStream::new(stream)
+                .with_block_range(server_range)
+                .handshake(status, fork_filter_clone)
+                .await
+                .unwrap();
+
+            assert_eq!(stream.remote_block_range(), Some(client_range));
+            // total difficulty is not part of the eth/69 status
+            assert_eq!(their_status.total_difficulty, U256::ZERO);
+            assert_eq!(their_status.blockhash, client_range.latest_hash);
+        });
+
+        let outgoing = TcpStream::connect(local_addr).await.unwrap();
+        let sink = PassthroughCodec::default().framed(outgoing);
+        let (stream, _) = UnauthedEthStream::new(sink)
+            .with_block_range(client_range)
+            .handshake(status, fork_filter)
+            .await
+            .unwrap();
+        assert_eq!(stream.version(), EthVersion::Eth69);
+        assert_eq!(stream.remote_block_range(), Some(server_range));
+
+        handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn eth69_receipts_include_bloom() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        #[allow(clippy::needless_update)]
+        let receipt = Receipt {
+            tx_type: TxType::Eip1559,
+            success: true,
+            cumulative_gas_used: 21_000,
+            logs: vec![Default::default()],
+            ..Default::default()
+        };
+        let test_msg = EthMessage::Receipts(RequestPair {
+            request_id: 1,
+            message: Receipts(vec![vec![receipt.with_bloom()]]),
+        });
+
+        let test_msg_clone = test_msg.clone();
+        let handle = tokio::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let stream = PassthroughCodec::default().framed(incoming);
+            let mut stream = EthStream::new(EthVersion::Eth69, stream);
+
+            // the bloom filter is recomputed on the receiving side
+            let message = stream.next().await.unwrap().unwrap();
+            assert_eq!(message, test_msg_clone);
+        });
+
+        let outgoing = TcpStream::connect(local_addr).await.unwrap();
+        let sink = PassthroughCodec::default().framed(outgoing);
+        let mut client_stream = EthStream::new(EthVersion::Eth69, sink);
+
+        client_stream.send(test_msg).await.unwrap();
+
+        handle.await.unwrap();
+    }
+
     #[tokio::test]
     async fn pass_handshake_on_low_td_bitlen() {
         let genesis = B256::random();
@@ -20,7 +20,8 @@ use crate::{
     capability::{SharedCapabilities, SharedCapability, UnsupportedCapabilityError},
     errors::{EthStreamError, P2PStreamError},
     p2pstream::DisconnectP2P,
-    CanDisconnect, Capability, DisconnectReason, EthStream, P2PStream, Status, UnauthedEthStream,
+    BlockRangeUpdate, CanDisconnect, Capability, DisconnectReason, EthStream, P2PStream, Status,
+    UnauthedEthStream,
 };
 use bytes::{Bytes, BytesMut};
 use futures::{Sink, SinkExt, Stream, StreamExt, TryStream, TryStreamExt};
@@ -204,9 +205,12 @@ impl<St> RlpxProtocolMultiplexer<St> {
 
     /// Converts this multiplexer into a [`RlpxSatelliteStream`] with eth protocol as the given
     /// primary protocol.
+    ///
+    /// The `block_range` is only announced if `eth/69` or later is negotiated.
     pub async fn into_eth_satellite_stream(
         self,
         status: Status,
+        block_range: BlockRangeUpdate,
         fork_filter: ForkFilter,
     ) -> Result<(RlpxSatelliteStream<St, EthStream<ProtocolProxy>>, Status), EthStreamError>
     where
@@ -216,7 +220,10 @@ impl<St> RlpxProtocolMultiplexer<St> {
         self.into_satellite_stream_with_tuple_handshake(
             &Capability::eth(eth_cap),
             move |proxy| async move {
-                UnauthedEthStream::new(proxy).handshake(status, fork_filter).await
+                UnauthedEthStream::new(proxy)
+                    .with_block_range(block_range)
+                    .handshake(status, fork_filter)
+                    .await
             },
         )
         .await
@@ -731,7 +738,7 @@ mod tests {
             let (conn, _) = UnauthedP2PStream::new(stream).handshake(server_hello).await.unwrap();
 
             let (mut st, _their_status) = RlpxProtocolMultiplexer::new(conn)
-                .into_eth_satellite_stream(other_status, other_fork_filter)
+                .into_eth_satellite_stream(other_status, Default::default(), other_fork_filter)
                 .await
                 .unwrap();
 
@@ -762,7 +769,7 @@ mod tests {
 
         let conn = connect_passthrough(local_addr, test_hello().0).await;
         let (mut st, _their_status) = RlpxProtocolMultiplexer::new(conn)
-            .into_eth_satellite_stream(status, fork_filter)
+            .into_eth_satellite_stream(status, Default::default(), fork_filter)
             .await
             .unwrap();
 
@@ -1,6 +1,6 @@
 //! A Protocol defines a P2P subprotocol in a `RLPx` connection
 
-use crate::{Capability, EthMessageID, EthVersion};
+use crate::{snap::SNAP_MESSAGE_COUNT, Capability, EthMessageID, EthVersion};
 
 /// Type that represents a [Capability] and the number of messages it uses.
 ///
@@ -45,6 +45,16 @@ impl Protocol {
         Self::eth(EthVersion::Eth68)
     }
 
+    /// Returns the [`EthVersion::Eth69`] capability.
+    pub const fn eth_69() -> Self {
+        Self::eth(EthVersion::Eth69)
+    }
+
+    /// Returns the `snap/1` protocol.
+    pub const fn snap_1() -> Self {
+        Self::new(Capability::snap_1(), SNAP_MESSAGE_COUNT)
+    }
+
     /// Consumes the type and returns a tuple of the [Capability] and number of messages.
     #[inline]
     pub(crate) fn split(self) -> (Capability, u8) {
@@ -54,7 +64,9 @@ impl Protocol {
     /// The number of values needed to represent all message IDs of capability.
     pub fn messages(&self) -> u8 {
         if self.cap.is_eth() {
-            return EthMessageID::max() + 1
+            if let Ok(version) = EthVersion::try_from(self.cap.version as u8) {
+                return EthMessageID::message_count(version)
+            }
         }
         self.messages
     }
@@ -1,7 +1,7 @@
 //! API related to syncing blocks.
 
 use futures::Future;
-use reth_network_p2p::BlockClient;
+use reth_network_p2p::{BlockClient, SnapClient};
 use tokio::sync::oneshot;
 
 /// Provides client for downloading blocks.
@@ -14,3 +14,16 @@ pub trait BlockDownloaderProvider {
         &self,
     ) -> impl Future<Output = Result<impl BlockClient + 'static, oneshot::error::RecvError>> + Send;
 }
+
+/// Provides client for downloading state via the `snap` protocol.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait SnapClientProvider {
+    /// Returns a new [`SnapClient`], used for fetching state ranges from peers.
+    ///
+    /// Returns `None` if the `snap` protocol is not enabled.
+    fn snap_client(
+        &self,
+    ) -> impl Future<
+        Output = Result<Option<impl SnapClient + Clone + 'static>, oneshot::error::RecvError>,
+    > + Send;
+}
@@ -26,7 +26,7 @@ use reth_network_p2p::sync::NetworkSyncUpdater;
 pub use reth_network_p2p::BlockClient;
 pub use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
 
-pub use downloaders::BlockDownloaderProvider;
+pub use downloaders::{BlockDownloaderProvider, SnapClientProvider};
 pub use error::NetworkError;
 pub use events::{
     DiscoveredEvent, DiscoveryEvent, NetworkEvent, NetworkEventListenerProvider, PeerRequest,
@@ -44,6 +44,7 @@ pub type PeerId = alloy_primitives::B512;
 /// Helper trait that unifies network API needed to launch node.
 pub trait FullNetwork:
     BlockDownloaderProvider
+    + SnapClientProvider
     + NetworkSyncUpdater
     + NetworkInfo
     + NetworkEventListenerProvider
@@ -56,6 +57,7 @@ pub trait FullNetwork:
 
 impl<T> FullNetwork for T where
     T: BlockDownloaderProvider
+        + SnapClientProvider
         + NetworkSyncUpdater
         + NetworkInfo
         + NetworkEventListenerProvider
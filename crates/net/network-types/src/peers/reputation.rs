@@ -35,6 +35,10 @@ const BAD_PROTOCOL_REPUTATION_CHANGE: i32 = i32::MIN;
 // todo: current value is a hint, needs to be set properly
 const BAD_ANNOUNCEMENT_REPUTATION_CHANGE: i32 = REPUTATION_UNIT;
 
+/// The reputation change to apply to a peer whose transaction gossip score fell below the
+/// configured threshold, e.g. because it announced many transactions it never delivered.
+const POOR_TRANSACTION_GOSSIP_REPUTATION_CHANGE: i32 = 4 * REPUTATION_UNIT;
+
 /// The maximum reputation change that can be applied to a trusted peer.
 /// This is used to prevent a single bad message from a trusted peer to cause a significant change.
 /// This gives a trusted peer more leeway when interacting with the node, which is useful for in
@@ -65,6 +69,9 @@ pub enum ReputationChangeKind {
     /// Peer sent a bad announcement message, e.g. invalid transaction type for the configured
     /// network.
     BadAnnouncement,
+    /// Peer's transaction gossip was not useful over a longer period, e.g. it announced hashes it
+    /// never delivered or re-announced hashes.
+    PoorTransactionGossip,
     /// Peer sent a message that included a hash or transaction that we already received from the
     /// peer.
     ///
@@ -124,6 +131,8 @@ pub struct ReputationChangeWeights {
     pub dropped: Reputation,
     /// Weight for [`ReputationChangeKind::BadAnnouncement`]
     pub bad_announcement: Reputation,
+    /// Weight for [`ReputationChangeKind::PoorTransactionGossip`]
+    pub poor_transaction_gossip: Reputation,
 }
 
 // === impl ReputationChangeWeights ===
@@ -141,6 +150,7 @@ impl ReputationChangeWeights {
             failed_to_connect: 0,
             dropped: 0,
             bad_announcement: 0,
+            poor_transaction_gossip: 0,
         }
     }
 
@@ -159,6 +169,7 @@ impl ReputationChangeWeights {
             ReputationChangeKind::Reset => DEFAULT_REPUTATION.into(),
             ReputationChangeKind::Other(val) => val.into(),
             ReputationChangeKind::BadAnnouncement => self.bad_announcement.into(),
+            ReputationChangeKind::PoorTransactionGossip => self.poor_transaction_gossip.into(),
         }
     }
 }
@@ -175,6 +186,7 @@ impl Default for ReputationChangeWeights {
             failed_to_connect: FAILED_TO_CONNECT_REPUTATION_CHANGE,
             dropped: REMOTE_DISCONNECT_REPUTATION_CHANGE,
             bad_announcement: BAD_ANNOUNCEMENT_REPUTATION_CHANGE,
+            poor_transaction_gossip: POOR_TRANSACTION_GOSSIP_REPUTATION_CHANGE,
         }
     }
 }
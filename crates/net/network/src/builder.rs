@@ -6,6 +6,8 @@ use tokio::sync::mpsc;
 
 use crate::{
     eth_requests::EthRequestHandler,
+    snap::{SnapFetchClient, SnapProtocolHandler},
+    snap_requests::SnapRequestHandler,
     transactions::{TransactionsManager, TransactionsManagerConfig},
     NetworkHandle, NetworkManager,
 };
@@ -14,6 +16,11 @@ use crate::{
 /// 256 requests with malicious 10MB body requests is 2.6GB which can be absorbed by the node.
 pub(crate) const ETH_REQUEST_CHANNEL_CAPACITY: usize = 256;
 
+/// The max channel capacity of the [`SnapRequestHandler`].
+///
+/// Responses are capped at 2MB, so this bounds the buffered responses at roughly 256MB.
+pub(crate) const SNAP_REQUEST_CHANNEL_CAPACITY: usize = 128;
+
 /// A builder that can configure all components of the network.
 #[allow(missing_debug_implementations)]
 pub struct NetworkBuilder<Tx, Eth> {
@@ -79,4 +86,21 @@ impl<Tx, Eth> NetworkBuilder<Tx, Eth> {
         let request_handler = EthRequestHandler::new(client, peers, rx);
         NetworkBuilder { network, request_handler, transactions }
     }
+
+    /// Adds the `snap/1` subprotocol to the network.
+    ///
+    /// Returns the [`SnapRequestHandler`] that serves the requests of `snap` peers, which must be
+    /// spawned, and the [`SnapFetchClient`] to request state from `snap` peers.
+    pub fn snap<Client>(
+        &mut self,
+        client: Client,
+    ) -> (SnapRequestHandler<Client>, SnapFetchClient) {
+        let (tx, rx) = mpsc::channel(SNAP_REQUEST_CHANNEL_CAPACITY);
+        let peers = self.network.handle().peers_handle().clone();
+        let protocol = SnapProtocolHandler::new(peers, Some(tx));
+        let fetch_client = protocol.client();
+        self.network.add_rlpx_sub_protocol(protocol);
+        self.network.set_snap_client(fetch_client.clone());
+        (SnapRequestHandler::new(client, rx), fetch_client)
+    }
 }
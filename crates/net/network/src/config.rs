@@ -6,7 +6,7 @@ use reth_chainspec::{ChainSpecProvider, EthChainSpec, Hardforks};
 use reth_discv4::{Discv4Config, Discv4ConfigBuilder, NatResolver, DEFAULT_DISCOVERY_ADDRESS};
 use reth_discv5::NetworkStackId;
 use reth_dns_discovery::DnsDiscoveryConfig;
-use reth_eth_wire::{HelloMessage, HelloMessageWithProtocols, Status};
+use reth_eth_wire::{BlockRangeUpdate, HelloMessage, HelloMessageWithProtocols, Status};
 use reth_network_peers::{mainnet_nodes, pk2id, sepolia_nodes, PeerId, TrustedPeer};
 use reth_network_types::{PeersConfig, SessionsConfig};
 use reth_primitives::{ForkFilter, Head};
@@ -73,6 +73,8 @@ pub struct NetworkConfig<C> {
     pub executor: Box<dyn TaskSpawner>,
     /// The `Status` message to send to peers at the beginning.
     pub status: Status,
+    /// The range of available blocks to announce to `eth/69` peers at the beginning.
+    pub block_range: BlockRangeUpdate,
     /// Sets the hello message for the p2p handshake in `RLPx`
     pub hello_message: HelloMessageWithProtocols,
     /// Additional protocols to announce and handle in `RLPx`
@@ -193,6 +195,8 @@ pub struct NetworkConfigBuilder {
     extra_protocols: RlpxSubProtocols,
     /// Head used to start set for the fork filter and status.
     head: Option<Head>,
+    /// The earliest block the node can serve, announced to `eth/69` peers.
+    earliest_block: u64,
     /// Whether tx gossip is disabled
     tx_gossip_disabled: bool,
     /// The block importer type
@@ -229,6 +233,7 @@ impl NetworkConfigBuilder {
             hello_message: None,
             extra_protocols: Default::default(),
             head: None,
+            earliest_block: 0,
             tx_gossip_disabled: false,
             block_import: None,
             transactions_manager_config: Default::default(),
@@ -270,6 +275,15 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Sets the earliest block whose header, body and receipts are available, e.g. because older
+    /// blocks were pruned.
+    ///
+    /// This is announced to `eth/69` peers. If not set, the full history is announced.
+    pub const fn earliest_block(mut self, earliest_block: u64) -> Self {
+        self.earliest_block = earliest_block;
+        self
+    }
+
     /// Sets the `HelloMessage` to send when connecting to peers.
     ///
     /// ```
@@ -530,6 +544,7 @@ impl NetworkConfigBuilder {
             hello_message,
             extra_protocols,
             head,
+            earliest_block,
             tx_gossip_disabled,
             block_import,
             transactions_manager_config,
@@ -562,6 +577,12 @@ impl NetworkConfigBuilder {
         // set the status
         let status = Status::spec_builder(&chain_spec, &head).build();
 
+        let block_range = BlockRangeUpdate {
+            earliest: earliest_block.min(head.number),
+            latest: head.number,
+            latest_hash: head.hash,
+        };
+
         // set a fork filter based on the chain spec and head
         let fork_filter = chain_spec.fork_filter(head);
 
@@ -595,6 +616,7 @@ impl NetworkConfigBuilder {
             network_mode,
             executor: executor.unwrap_or_else(|| Box::<TokioTaskExecutor>::default()),
             status,
+            block_range,
             hello_message,
             extra_protocols,
             fork_filter,
@@ -683,4 +705,22 @@ mod tests {
         assert_eq!(status.forkid.hash, genesis_fork_hash);
         assert_eq!(fork_filter.current().hash, genesis_fork_hash);
     }
+
+    #[test]
+    fn test_network_block_range_earliest_block() {
+        let head = Head { number: 100, ..Default::default() };
+
+        let config = builder().set_head(head).build_with_noop_provider(MAINNET.clone());
+        assert_eq!(config.block_range.earliest, 0);
+        assert_eq!(config.block_range.latest, 100);
+
+        let config =
+            builder().set_head(head).earliest_block(40).build_with_noop_provider(MAINNET.clone());
+        assert_eq!(config.block_range.earliest, 40);
+
+        // the earliest block never exceeds the head
+        let config =
+            builder().set_head(head).earliest_block(200).build_with_noop_provider(MAINNET.clone());
+        assert_eq!(config.block_range.earliest, 100);
+    }
 }
@@ -6,6 +6,7 @@ pub use client::FetchClient;
 
 use std::{
     collections::{HashMap, VecDeque},
+    ops::RangeInclusive,
     sync::{
         atomic::{AtomicU64, AtomicUsize, Ordering},
         Arc,
@@ -15,7 +16,7 @@ use std::{
 
 use alloy_primitives::B256;
 use futures::StreamExt;
-use reth_eth_wire::{GetBlockBodies, GetBlockHeaders};
+use reth_eth_wire::{BlockRangeUpdate, GetBlockBodies, GetBlockHeaders};
 use reth_network_api::test_utils::PeersHandle;
 use reth_network_p2p::{
     error::{EthResponseValidator, PeerRequestResult, RequestError, RequestResult},
@@ -89,6 +90,7 @@ impl StateFetcher {
                 state: PeerState::Idle,
                 best_hash,
                 best_number,
+                block_range: None,
                 timeout,
                 last_response_likely_bad: false,
             },
@@ -125,6 +127,19 @@ impl StateFetcher {
         false
     }
 
+    /// Updates the range of blocks the peer announced it can serve.
+    ///
+    /// This also updates the peer's best block if the range's latest block is newer.
+    pub(crate) fn update_peer_block_range(&mut self, peer_id: &PeerId, range: BlockRangeUpdate) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.block_range = Some(range);
+            if range.latest > peer.best_number {
+                peer.best_hash = range.latest_hash;
+                peer.best_number = range.latest;
+            }
+        }
+    }
+
     /// Invoked when an active session is about to be disconnected.
     pub(crate) fn on_pending_disconnect(&mut self, peer_id: &PeerId) {
         if let Some(peer) = self.peers.get_mut(peer_id) {
@@ -135,8 +150,19 @@ impl StateFetcher {
     /// Returns the _next_ idle peer that's ready to accept a request,
     /// prioritizing those with the lowest timeout/latency and those that recently responded with
     /// adequate data.
-    fn next_best_peer(&self) -> Option<PeerId> {
-        let mut idle = self.peers.iter().filter(|(_, peer)| peer.state.is_idle());
+    ///
+    /// If a block range is given, only peers that can serve all blocks of the range are
+    /// considered, unless no such peer is idle. Peers that did not announce a block range are
+    /// assumed to serve all blocks.
+    fn next_best_peer(&self, blocks: Option<&RangeInclusive<u64>>) -> Option<PeerId> {
+        let serves_blocks = |peer: &Peer| blocks.map_or(true, |range| peer.can_serve(range));
+        let has_serving_peer =
+            self.peers.values().any(|peer| peer.state.is_idle() && serves_blocks(peer));
+        let mut idle = self
+            .peers
+            .iter()
+            .filter(|(_, peer)| peer.state.is_idle())
+            .filter(|(_, peer)| !has_serving_peer || serves_blocks(peer));
 
         let mut best_peer = idle.next()?;
 
@@ -165,7 +191,10 @@ impl StateFetcher {
             return PollAction::NoRequests
         }
 
-        let Some(peer_id) = self.next_best_peer() else { return PollAction::NoPeersAvailable };
+        let blocks = self.queued_requests.front().and_then(DownloadRequest::block_range);
+        let Some(peer_id) = self.next_best_peer(blocks.as_ref()) else {
+            return PollAction::NoPeersAvailable
+        };
 
         let request = self.queued_requests.pop_front().expect("not empty");
         let request = self.prepare_block_request(peer_id, request);
@@ -340,6 +369,8 @@ struct Peer {
     best_hash: B256,
     /// Tracks the best number of the peer.
     best_number: u64,
+    /// The range of blocks the peer announced it can serve, only known for `eth/69` peers.
+    block_range: Option<BlockRangeUpdate>,
     /// Tracks the current timeout value we use for the peer.
     timeout: Arc<AtomicU64>,
     /// Tracks whether the peer has recently responded with a likely bad response.
@@ -355,6 +386,13 @@ impl Peer {
     fn timeout(&self) -> u64 {
         self.timeout.load(Ordering::Relaxed)
     }
+
+    /// Returns `true` if the peer can serve all blocks of the given range, assuming it serves all
+    /// blocks if no range was announced.
+    fn can_serve(&self, blocks: &RangeInclusive<u64>) -> bool {
+        self.block_range
+            .map_or(true, |range| range.contains(*blocks.start()) && range.contains(*blocks.end()))
+    }
 }
 
 /// Tracks the state of an individual peer
@@ -417,6 +455,8 @@ pub(crate) enum DownloadRequest {
         request: Vec<B256>,
         response: oneshot::Sender<PeerRequestResult<Vec<BlockBody>>>,
         priority: Priority,
+        /// The range of blocks the requested bodies belong to, if known.
+        range_hint: Option<RangeInclusive<u64>>,
     },
 }
 
@@ -444,6 +484,19 @@ impl DownloadRequest {
     const fn is_normal_priority(&self) -> bool {
         self.get_priority().is_normal()
     }
+
+    /// Returns the range of blocks a peer must serve to handle the request, if known.
+    ///
+    /// For header requests by number this is the first requested block, since the remaining
+    /// headers may not exist yet.
+    fn block_range(&self) -> Option<RangeInclusive<u64>> {
+        match self {
+            Self::GetBlockHeaders { request, .. } => {
+                request.start.as_number().map(|number| number..=number)
+            }
+            Self::GetBlockBodies { range_hint, .. } => range_hint.clone(),
+        }
+    }
 }
 
 /// An action the syncer can emit.
@@ -488,6 +541,7 @@ mod tests {
                 request: vec![],
                 response: tx,
                 priority: Priority::default(),
+                range_hint: None,
             });
             assert!(fetcher.poll(cx).is_pending());
 
@@ -506,17 +560,17 @@ mod tests {
         fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(1)));
         fetcher.new_active_peer(peer2, B256::random(), 2, Arc::new(AtomicU64::new(1)));
 
-        let first_peer = fetcher.next_best_peer().unwrap();
+        let first_peer = fetcher.next_best_peer(None).unwrap();
         assert!(first_peer == peer1 || first_peer == peer2);
         // Pending disconnect for first_peer
         fetcher.on_pending_disconnect(&first_peer);
         // first_peer now isn't idle, so we should get other peer
-        let second_peer = fetcher.next_best_peer().unwrap();
+        let second_peer = fetcher.next_best_peer(None).unwrap();
         assert!(first_peer == peer1 || first_peer == peer2);
         assert_ne!(first_peer, second_peer);
         // without idle peers, returns None
         fetcher.on_pending_disconnect(&second_peer);
-        assert_eq!(fetcher.next_best_peer(), None);
+        assert_eq!(fetcher.next_best_peer(None), None);
     }
 
     #[tokio::test]
@@ -535,13 +589,39 @@ mod tests {
         fetcher.new_active_peer(peer3, B256::random(), 3, Arc::new(AtomicU64::new(50)));
 
         // Must always get peer1 (lowest timeout)
-        assert_eq!(fetcher.next_best_peer(), Some(peer1));
-        assert_eq!(fetcher.next_best_peer(), Some(peer1));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer1));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer1));
         // peer2's timeout changes below peer1's
         peer2_timeout.store(10, Ordering::Relaxed);
         // Then we get peer 2 always (now lowest)
-        assert_eq!(fetcher.next_best_peer(), Some(peer2));
-        assert_eq!(fetcher.next_best_peer(), Some(peer2));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer2));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer2));
+    }
+
+    #[tokio::test]
+    async fn test_peer_prioritization_by_block_range() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher = StateFetcher::new(manager.handle(), Default::default());
+        let peer1 = B512::random();
+        let peer2 = B512::random();
+
+        fetcher.new_active_peer(peer1, B256::random(), 100, Arc::new(AtomicU64::new(10)));
+        fetcher.new_active_peer(peer2, B256::random(), 100, Arc::new(AtomicU64::new(50)));
+        fetcher.update_peer_block_range(
+            &peer1,
+            BlockRangeUpdate { earliest: 50, latest: 100, latest_hash: B256::random() },
+        );
+
+        // peer1 has the lowest timeout but doesn't serve block 10
+        assert_eq!(fetcher.next_best_peer(Some(&(10..=10))), Some(peer2));
+        assert_eq!(fetcher.next_best_peer(Some(&(60..=100))), Some(peer1));
+        // peer1 serves the start but not the end of the range
+        assert_eq!(fetcher.next_best_peer(Some(&(60..=120))), Some(peer2));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer1));
+
+        // falls back to peers that don't cover the block if none are available
+        fetcher.on_pending_disconnect(&peer2);
+        assert_eq!(fetcher.next_best_peer(Some(&(10..=10))), Some(peer1));
     }
 
     #[tokio::test]
@@ -60,6 +60,7 @@ use crate::{
     poll_nested_stream_with_budget,
     protocol::IntoRlpxSubProtocol,
     session::SessionManager,
+    snap::SnapFetchClient,
     state::NetworkState,
     swarm::{Swarm, SwarmEvent},
     transactions::NetworkTransactionEvent,
@@ -104,6 +105,8 @@ pub struct NetworkManager {
     /// requests. This channel size is set at
     /// [`ETH_REQUEST_CHANNEL_CAPACITY`](crate::builder::ETH_REQUEST_CHANNEL_CAPACITY)
     to_eth_request_handler: Option<mpsc::Sender<IncomingEthRequest>>,
+    /// The client for requests to `snap` peers, if the `snap` protocol is installed.
+    snap_client: Option<SnapFetchClient>,
     /// Tracks the number of active session (connected peers).
     ///
     /// This is updated via internal events and shared via `Arc` with the [`NetworkHandle`]
@@ -130,6 +133,11 @@ impl NetworkManager {
         self.to_eth_request_handler = Some(tx);
     }
 
+    /// Sets the client for requests to `snap` peers, see also [`NetworkBuilder::snap`].
+    pub fn set_snap_client(&mut self, client: SnapFetchClient) {
+        self.snap_client = Some(client);
+    }
+
     /// Adds an additional protocol handler to the `RLPx` sub-protocol list.
     pub fn add_rlpx_sub_protocol(&mut self, protocol: impl IntoRlpxSubProtocol) {
         self.swarm.add_rlpx_sub_protocol(protocol)
@@ -183,6 +191,7 @@ impl NetworkManager {
             executor,
             hello_message,
             status,
+            block_range,
             fork_filter,
             dns_discovery_config,
             extra_protocols,
@@ -237,6 +246,7 @@ impl NetworkManager {
             sessions_config,
             executor,
             status,
+            block_range,
             hello_message,
             fork_filter,
             extra_protocols,
@@ -279,6 +289,7 @@ impl NetworkManager {
             event_sender,
             to_transactions_manager: None,
             to_eth_request_handler: None,
+            snap_client: None,
             num_active_peers,
             metrics: Default::default(),
             disconnect_metrics: Default::default(),
@@ -541,6 +552,9 @@ impl NetworkManager {
                     msg,
                 });
             }
+            PeerMessage::BlockRangeUpdated(range) => {
+                self.swarm.state_mut().on_block_range_update(&peer_id, range);
+            }
             PeerMessage::SendTransactions(_) => {
                 unreachable!("Not emitted by session")
             }
@@ -622,6 +636,9 @@ impl NetworkManager {
                     self.swarm.state_mut().update_fork_id(transition.current);
                 }
             }
+            NetworkHandleMessage::EarliestBlockUpdate { earliest } => {
+                self.swarm.sessions_mut().on_earliest_block_update(earliest)
+            }
             NetworkHandleMessage::GetPeerInfos(tx) => {
                 let _ = tx.send(self.get_peer_infos());
             }
@@ -643,6 +660,9 @@ impl NetworkManager {
                     let _ = tx.send(None);
                 }
             }
+            NetworkHandleMessage::GetSnapClient(tx) => {
+                let _ = tx.send(self.snap_client.clone());
+            }
         }
     }
 
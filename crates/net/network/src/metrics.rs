@@ -111,6 +111,22 @@ pub struct TransactionsManagerMetrics {
     /// seen by that peer.
     pub(crate) occurrences_of_transaction_already_seen_by_peer: Counter,
 
+    /* -- Announcement deduplication -- */
+    /// Total number of hashes announced by peers.
+    pub(crate) announced_hashes: Counter,
+    /// Total number of times a hash is announced that is already pending fetch or inflight,
+    /// because another peer announced it before.
+    pub(crate) occurrences_hashes_already_pending_fetch: Counter,
+    /// Total number of hashes that were announced and requested, but not delivered by the peer.
+    pub(crate) undelivered_announced_hashes: Counter,
+
+    /* -- Peer scoring -- */
+    /// Total number of times a peer was reported for poor transaction gossip.
+    pub(crate) reported_poor_transaction_gossip: Counter,
+    /// Total number of transactions dropped because the peer exceeded its inbound bandwidth
+    /// limit.
+    pub(crate) throttled_transactions: Counter,
+
     /* -- Freq txns already in pool -- */
     /// Total number of times a hash is announced that is already in the local pool.
     pub(crate) occurrences_hashes_already_in_pool: Counter,
@@ -329,6 +345,27 @@ pub struct EthRequestHandlerMetrics {
     pub(crate) acc_duration_poll_eth_req_handler: Gauge,
 }
 
+/// Metrics for the `SnapRequestHandler`
+#[derive(Metrics)]
+#[metrics(scope = "network")]
+pub struct SnapRequestHandlerMetrics {
+    /// Number of `GetAccountRange` requests received
+    pub(crate) snap_account_range_requests_received_total: Counter,
+
+    /// Number of `GetStorageRanges` requests received
+    pub(crate) snap_storage_ranges_requests_received_total: Counter,
+
+    /// Number of `GetByteCodes` requests received
+    pub(crate) snap_byte_codes_requests_received_total: Counter,
+
+    /// Number of `GetTrieNodes` requests received
+    pub(crate) snap_trie_nodes_requests_received_total: Counter,
+
+    /// Duration in seconds of call to poll
+    /// [`SnapRequestHandler`](crate::snap_requests::SnapRequestHandler).
+    pub(crate) acc_duration_poll_snap_req_handler: Gauge,
+}
+
 /// Eth67 announcement metrics, track entries by `TxType`
 #[derive(Metrics)]
 #[metrics(scope = "network.transaction_fetcher")]
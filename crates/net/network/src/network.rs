@@ -6,7 +6,7 @@ use std::{
     },
 };
 
-use alloy_primitives::B256;
+use alloy_primitives::{BlockNumber, B256};
 use enr::Enr;
 use parking_lot::Mutex;
 use reth_discv4::{Discv4, NatResolver};
@@ -16,11 +16,11 @@ use reth_network_api::{
     test_utils::{PeersHandle, PeersHandleProvider},
     BlockDownloaderProvider, DiscoveryEvent, NetworkError, NetworkEvent,
     NetworkEventListenerProvider, NetworkInfo, NetworkStatus, PeerInfo, PeerRequest, Peers,
-    PeersInfo,
+    PeersInfo, SnapClientProvider,
 };
 use reth_network_p2p::{
     sync::{NetworkSyncUpdater, SyncState, SyncStateProvider},
-    BlockClient,
+    BlockClient, SnapClient,
 };
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::{PeerAddr, PeerKind, Reputation, ReputationChangeKind};
@@ -34,8 +34,8 @@ use tokio::sync::{
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 use crate::{
-    config::NetworkMode, protocol::RlpxSubProtocol, swarm::NetworkConnectionState,
-    transactions::TransactionsHandle, FetchClient,
+    config::NetworkMode, protocol::RlpxSubProtocol, snap::SnapFetchClient,
+    swarm::NetworkConnectionState, transactions::TransactionsHandle, FetchClient,
 };
 
 /// A _shareable_ network frontend. Used to interact with the network.
@@ -397,6 +397,11 @@ impl NetworkSyncUpdater for NetworkHandle {
     fn update_status(&self, head: Head) {
         self.send_message(NetworkHandleMessage::StatusUpdate { head });
     }
+
+    /// Update the earliest block the node can serve.
+    fn update_earliest_block(&self, earliest: BlockNumber) {
+        self.send_message(NetworkHandleMessage::EarliestBlockUpdate { earliest });
+    }
 }
 
 impl BlockDownloaderProvider for NetworkHandle {
@@ -407,6 +412,16 @@ impl BlockDownloaderProvider for NetworkHandle {
     }
 }
 
+impl SnapClientProvider for NetworkHandle {
+    async fn snap_client(
+        &self,
+    ) -> Result<Option<impl SnapClient + Clone + 'static>, oneshot::error::RecvError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.manager().send(NetworkHandleMessage::GetSnapClient(tx));
+        rx.await
+    }
+}
+
 #[derive(Debug)]
 struct NetworkInner {
     /// Number of active peer sessions the node's currently handling.
@@ -490,6 +505,11 @@ pub(crate) enum NetworkHandleMessage {
         /// The head status to apply.
         head: Head,
     },
+    /// Updates the earliest block announced to `eth/69` peers.
+    EarliestBlockUpdate {
+        /// The earliest block the node can serve.
+        earliest: BlockNumber,
+    },
     /// Retrieves the current status via a oneshot sender.
     GetStatus(oneshot::Sender<NetworkStatus>),
     /// Gets `PeerInfo` for the specified peer IDs.
@@ -504,6 +524,8 @@ pub(crate) enum NetworkHandleMessage {
     GetReputationById(PeerId, oneshot::Sender<Option<Reputation>>),
     /// Retrieves the `TransactionsHandle` via a oneshot sender.
     GetTransactionsHandle(oneshot::Sender<Option<TransactionsHandle>>),
+    /// Retrieves the [`SnapFetchClient`] via a oneshot sender.
+    GetSnapClient(oneshot::Sender<Option<SnapFetchClient>>),
     /// Initiates a graceful shutdown of the network via a oneshot sender.
     Shutdown(oneshot::Sender<()>),
     /// Sets the network state between hibernation and active.
@@ -415,6 +415,7 @@ impl PeersManager {
                         rep,
                         ReputationChangeKind::Dropped |
                             ReputationChangeKind::BadAnnouncement |
+                            ReputationChangeKind::PoorTransactionGossip |
                             ReputationChangeKind::Timeout |
                             ReputationChangeKind::AlreadySeenTransaction
                     ) {
@@ -15,7 +15,7 @@ use futures::{stream::Fuse, SinkExt, StreamExt};
 use reth_eth_wire::{
     errors::{EthHandshakeError, EthStreamError, P2PStreamError},
     message::{EthBroadcastMessage, RequestPair},
-    Capabilities, DisconnectP2P, DisconnectReason, EthMessage,
+    Capabilities, DisconnectP2P, DisconnectReason, EthMessage, EthVersion,
 };
 use reth_metrics::common::mpsc::MeteredPollSender;
 use reth_network_api::PeerRequest;
@@ -172,10 +172,14 @@ impl ActiveSession {
         }
 
         match msg {
-            message @ EthMessage::Status(_) => OnIncomingMessageOutcome::BadMessage {
-                error: EthStreamError::EthHandshakeError(EthHandshakeError::StatusNotInHandshake),
-                message,
-            },
+            message @ (EthMessage::Status(_) | EthMessage::StatusEth69(_)) => {
+                OnIncomingMessageOutcome::BadMessage {
+                    error: EthStreamError::EthHandshakeError(
+                        EthHandshakeError::StatusNotInHandshake,
+                    ),
+                    message,
+                }
+            }
             EthMessage::NewBlockHashes(msg) => {
                 self.try_emit_broadcast(PeerMessage::NewBlockHashes(msg)).into()
             }
@@ -233,6 +237,24 @@ impl ActiveSession {
             EthMessage::Receipts(resp) => {
                 on_response!(resp, GetReceipts)
             }
+            EthMessage::Receipts69(resp) => {
+                // the stream translates these into regular receipts, but handle them anyway
+                let resp =
+                    RequestPair { request_id: resp.request_id, message: resp.message.into() };
+                on_response!(resp, GetReceipts)
+            }
+            EthMessage::BlockRangeUpdate(update) => {
+                if update.earliest > update.latest {
+                    return OnIncomingMessageOutcome::BadMessage {
+                        error: EthStreamError::InvalidBlockRangeUpdate {
+                            earliest: update.earliest,
+                            latest: update.latest,
+                        },
+                        message: EthMessage::BlockRangeUpdate(update),
+                    }
+                }
+                self.try_emit_broadcast(PeerMessage::BlockRangeUpdated(update)).into()
+            }
         }
     }
 
@@ -270,6 +292,11 @@ impl ActiveSession {
             PeerMessage::SendTransactions(msg) => {
                 self.queued_outgoing.push_back(EthBroadcastMessage::Transactions(msg).into());
             }
+            PeerMessage::BlockRangeUpdated(update) => {
+                if self.conn.version() >= EthVersion::Eth69 {
+                    self.queued_outgoing.push_back(EthMessage::BlockRangeUpdate(update).into());
+                }
+            }
             PeerMessage::ReceivedTransaction(_) => {
                 unreachable!("Not emitted by network")
             }
@@ -811,7 +838,7 @@ mod tests {
             F: FnOnce(EthStream<P2PStream<ECIESStream<TcpStream>>>) -> O + Send + 'static,
             O: Future<Output = ()> + Send + Sync,
         {
-            let status = self.status;
+            let mut status = self.status;
             let fork_filter = self.fork_filter.clone();
             let local_peer_id = self.local_peer_id;
             let mut hello = self.hello.clone();
@@ -822,6 +849,7 @@ mod tests {
                 let sink = ECIESStream::connect(outgoing, key, local_peer_id).await.unwrap();
 
                 let (p2p_stream, _) = UnauthedP2PStream::new(sink).handshake(hello).await.unwrap();
+                status.set_eth_version(p2p_stream.shared_capabilities().eth_version().unwrap());
 
                 let (client_stream, _) = UnauthedEthStream::new(p2p_stream)
                     .handshake(status, fork_filter)
@@ -846,6 +874,7 @@ mod tests {
                 self.secret_key,
                 self.hello.clone(),
                 self.status,
+                Default::default(),
                 self.fork_filter.clone(),
                 Default::default(),
             ));
@@ -11,7 +11,7 @@ use reth_eth_wire::{
     errors::EthStreamError,
     message::EthBroadcastMessage,
     multiplex::{ProtocolProxy, RlpxSatelliteStream},
-    EthMessage, EthStream, EthVersion, P2PStream,
+    BlockRangeUpdate, EthMessage, EthStream, EthVersion, P2PStream,
 };
 use tokio::net::TcpStream;
 
@@ -47,6 +47,15 @@ impl EthRlpxConnection {
         }
     }
 
+    /// Returns the range of blocks the peer announced during the handshake.
+    #[inline]
+    pub(crate) const fn remote_block_range(&self) -> Option<BlockRangeUpdate> {
+        match self {
+            Self::EthOnly(conn) => conn.remote_block_range(),
+            Self::Satellite(conn) => conn.primary().remote_block_range(),
+        }
+    }
+
     /// Consumes this type and returns the wrapped [`P2PStream`].
     #[inline]
     pub(crate) fn into_inner(self) -> P2PStream<ECIESStream<TcpStream>> {
@@ -27,8 +27,8 @@ use futures::{future::Either, io, FutureExt, StreamExt};
 use reth_ecies::{stream::ECIESStream, ECIESError};
 use reth_eth_wire::{
     capability::CapabilityMessage, errors::EthStreamError, multiplex::RlpxProtocolMultiplexer,
-    Capabilities, DisconnectReason, EthVersion, HelloMessageWithProtocols, Status,
-    UnauthedEthStream, UnauthedP2PStream,
+    BlockRangeUpdate, Capabilities, DisconnectReason, EthVersion, HelloMessageWithProtocols,
+    Status, UnauthedEthStream, UnauthedP2PStream,
 };
 use reth_metrics::common::mpsc::MeteredPollSender;
 use reth_network_api::PeerRequestSender;
@@ -54,6 +54,9 @@ use crate::{
     session::active::ActiveSession,
 };
 
+/// The number of blocks after which an updated block range is announced to `eth/69` peers.
+const BLOCK_RANGE_UPDATE_INTERVAL: u64 = 32;
+
 /// Internal identifier for active sessions.
 #[derive(Debug, Clone, Copy, PartialOrd, PartialEq, Eq, Hash)]
 pub struct SessionId(usize);
@@ -78,6 +81,10 @@ pub struct SessionManager {
     secret_key: SecretKey,
     /// The `Status` message to send to peers.
     status: Status,
+    /// The range of available blocks announced to `eth/69` peers.
+    block_range: BlockRangeUpdate,
+    /// The latest block announced to active `eth/69` sessions via `BlockRangeUpdate`.
+    last_announced_block: u64,
     /// The `HelloMessage` message to send to peers.
     hello_message: HelloMessageWithProtocols,
     /// The [`ForkFilter`] used to validate the peer's `Status` message.
@@ -123,6 +130,7 @@ impl SessionManager {
         config: SessionsConfig,
         executor: Box<dyn TaskSpawner>,
         status: Status,
+        block_range: BlockRangeUpdate,
         hello_message: HelloMessageWithProtocols,
         fork_filter: ForkFilter,
         extra_protocols: RlpxSubProtocols,
@@ -139,6 +147,8 @@ impl SessionManager {
             pending_session_timeout: config.pending_session_timeout,
             secret_key,
             status,
+            block_range,
+            last_announced_block: block_range.latest,
             hello_message,
             fork_filter,
             session_command_buffer: config.session_command_buffer,
@@ -172,6 +182,11 @@ impl SessionManager {
         self.status
     }
 
+    /// Returns the range of available blocks announced to `eth/69` peers.
+    pub const fn block_range(&self) -> BlockRangeUpdate {
+        self.block_range
+    }
+
     /// Returns the secret key used for authenticating sessions.
     pub const fn secret_key(&self) -> SecretKey {
         self.secret_key
@@ -214,11 +229,44 @@ impl SessionManager {
     pub(crate) fn on_status_update(&mut self, head: Head) -> Option<ForkTransition> {
         self.status.blockhash = head.hash;
         self.status.total_difficulty = head.total_difficulty;
+        self.block_range.latest = head.number;
+        self.block_range.latest_hash = head.hash;
         let transition = self.fork_filter.set_head(head);
         self.status.forkid = self.fork_filter.current();
+
+        // announce the new range to eth/69 peers every `BLOCK_RANGE_UPDATE_INTERVAL` blocks or on
+        // reorgs
+        if head.number < self.last_announced_block ||
+            head.number >= self.last_announced_block + BLOCK_RANGE_UPDATE_INTERVAL
+        {
+            self.last_announced_block = head.number;
+            self.announce_block_range();
+        }
+
         transition
     }
 
+    /// Invoked when the earliest block the node can serve changed, e.g. after older blocks were
+    /// pruned.
+    ///
+    /// The new range is announced to `eth/69` peers along with the next head update.
+    pub(crate) fn on_earliest_block_update(&mut self, earliest: u64) {
+        self.block_range.earliest = earliest.min(self.block_range.latest);
+    }
+
+    /// Sends the current [`BlockRangeUpdate`] to all active sessions that negotiated `eth/69`.
+    fn announce_block_range(&mut self) {
+        let peers = self
+            .active_sessions
+            .iter()
+            .filter(|(_, session)| session.version >= EthVersion::Eth69)
+            .map(|(peer_id, _)| *peer_id)
+            .collect::<Vec<_>>();
+        for peer_id in peers {
+            self.send_message(&peer_id, PeerMessage::BlockRangeUpdated(self.block_range));
+        }
+    }
+
     /// An incoming TCP connection was received. This starts the authentication process to turn this
     /// stream into an active peer session.
     ///
@@ -244,6 +292,7 @@ impl SessionManager {
         let secret_key = self.secret_key;
         let hello_message = self.hello_message.clone();
         let status = self.status;
+        let block_range = self.block_range;
         let fork_filter = self.fork_filter.clone();
         let extra_handlers = self.extra_protocols.on_incoming(remote_addr);
         self.spawn(pending_session_with_timeout(
@@ -261,6 +310,7 @@ impl SessionManager {
                 secret_key,
                 hello_message,
                 status,
+                block_range,
                 fork_filter,
                 extra_handlers,
             ),
@@ -286,6 +336,7 @@ impl SessionManager {
             let hello_message = self.hello_message.clone();
             let fork_filter = self.fork_filter.clone();
             let status = self.status;
+            let block_range = self.block_range;
             let extra_handlers = self.extra_protocols.on_outgoing(remote_addr, remote_peer_id);
             self.spawn(pending_session_with_timeout(
                 self.pending_session_timeout,
@@ -302,6 +353,7 @@ impl SessionManager {
                     secret_key,
                     hello_message,
                     status,
+                    block_range,
                     fork_filter,
                     extra_handlers,
                 ),
@@ -482,6 +534,8 @@ impl SessionManager {
 
                 // negotiated version
                 let version = conn.version();
+                // the range of blocks the peer can serve, if announced
+                let block_range = conn.remote_block_range();
 
                 let session = ActiveSession {
                     next_id: 0,
@@ -536,6 +590,7 @@ impl SessionManager {
                     version,
                     capabilities,
                     status,
+                    block_range,
                     messages,
                     direction,
                     timeout,
@@ -631,6 +686,8 @@ pub enum SessionEvent {
         version: EthVersion,
         /// The Status message the peer sent during the `eth` handshake
         status: Arc<Status>,
+        /// The range of blocks the peer announced during the `eth/69` handshake
+        block_range: Option<BlockRangeUpdate>,
         /// The channel for sending messages to the peer with the session
         messages: PeerRequestSender,
         /// The direction of the session, either `Inbound` or `Outgoing`
@@ -783,6 +840,7 @@ pub(crate) async fn start_pending_incoming_session(
     secret_key: SecretKey,
     hello: HelloMessageWithProtocols,
     status: Status,
+    block_range: BlockRangeUpdate,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
@@ -796,6 +854,7 @@ pub(crate) async fn start_pending_incoming_session(
         Direction::Incoming,
         hello,
         status,
+        block_range,
         fork_filter,
         extra_handlers,
     )
@@ -814,6 +873,7 @@ async fn start_pending_outbound_session(
     secret_key: SecretKey,
     hello: HelloMessageWithProtocols,
     status: Status,
+    block_range: BlockRangeUpdate,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
@@ -846,6 +906,7 @@ async fn start_pending_outbound_session(
         Direction::Outgoing(remote_peer_id),
         hello,
         status,
+        block_range,
         fork_filter,
         extra_handlers,
     )
@@ -864,6 +925,7 @@ async fn authenticate(
     direction: Direction,
     hello: HelloMessageWithProtocols,
     status: Status,
+    block_range: BlockRangeUpdate,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
@@ -893,6 +955,7 @@ async fn authenticate(
         direction,
         hello,
         status,
+        block_range,
         fork_filter,
         extra_handlers,
     )
@@ -945,6 +1008,7 @@ async fn authenticate_stream(
     direction: Direction,
     mut hello: HelloMessageWithProtocols,
     mut status: Status,
+    block_range: BlockRangeUpdate,
     fork_filter: ForkFilter,
     mut extra_handlers: RlpxSubProtocolHandlers,
 ) -> PendingSessionEvent {
@@ -982,7 +1046,7 @@ async fn authenticate_stream(
         //
         // Before trying status handshake, set up the version to negotiated shared version
         status.set_eth_version(eth_version);
-        let eth_unauthed = UnauthedEthStream::new(p2p_stream);
+        let eth_unauthed = UnauthedEthStream::new(p2p_stream).with_block_range(block_range);
         let (eth_stream, their_status) = match eth_unauthed.handshake(status, fork_filter).await {
             Ok(stream_res) => stream_res,
             Err(err) => {
@@ -1010,18 +1074,20 @@ async fn authenticate_stream(
                 .ok();
         }
 
-        let (multiplex_stream, their_status) =
-            match multiplex_stream.into_eth_satellite_stream(status, fork_filter).await {
-                Ok((multiplex_stream, their_status)) => (multiplex_stream, their_status),
-                Err(err) => {
-                    return PendingSessionEvent::Disconnected {
-                        remote_addr,
-                        session_id,
-                        direction,
-                        error: Some(PendingSessionHandshakeError::Eth(err)),
-                    }
+        let (multiplex_stream, their_status) = match multiplex_stream
+            .into_eth_satellite_stream(status, block_range, fork_filter)
+            .await
+        {
+            Ok((multiplex_stream, their_status)) => (multiplex_stream, their_status),
+            Err(err) => {
+                return PendingSessionEvent::Disconnected {
+                    remote_addr,
+                    session_id,
+                    direction,
+                    error: Some(PendingSessionHandshakeError::Eth(err)),
                 }
-            };
+            }
+        };
 
         (multiplex_stream.into(), their_status)
     };
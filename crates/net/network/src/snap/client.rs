@@ -0,0 +1,106 @@
+//! A client that sends `snap` requests to connected peers.
+
+use std::time::Duration;
+
+use rand::seq::IteratorRandom;
+use reth_network_api::test_utils::PeersHandle;
+use reth_network_p2p::{
+    download::DownloadClient,
+    error::{RequestError, RequestResult},
+    snap::client::{
+        AccountRange, ByteCodes, GetAccountRange, GetByteCodes, GetStorageRanges, GetTrieNodes,
+        SnapClient, SnapFut, StorageRanges, TrieNodes,
+    },
+};
+use reth_network_peers::{PeerId, WithPeerId};
+use reth_network_types::ReputationChangeKind;
+use tokio::sync::oneshot;
+
+use super::{connection::SnapCommand, SnapPeers};
+
+/// The default timeout for `snap` requests.
+const SNAP_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Front-end API for requesting state from peers that support the `snap/1` protocol.
+///
+/// Each request is sent to a random connected `snap` peer.
+#[derive(Debug, Clone)]
+pub struct SnapFetchClient {
+    /// The active `snap` connections.
+    peers: SnapPeers,
+    /// The handle to the peers
+    peers_handle: PeersHandle,
+    /// The timeout of a single request.
+    request_timeout: Duration,
+}
+
+// === impl SnapFetchClient ===
+
+impl SnapFetchClient {
+    pub(super) const fn new(peers: SnapPeers, peers_handle: PeersHandle) -> Self {
+        Self { peers, peers_handle, request_timeout: SNAP_REQUEST_TIMEOUT }
+    }
+
+    /// Sets the timeout of a single request.
+    pub const fn with_request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = request_timeout;
+        self
+    }
+
+    /// Sends the command built by `f` to a random `snap` peer and awaits the response.
+    fn send_request<T: Send + Sync + 'static>(
+        &self,
+        f: impl FnOnce(oneshot::Sender<RequestResult<T>>) -> SnapCommand,
+    ) -> SnapFut<T> {
+        let (tx, rx) = oneshot::channel();
+        let cmd = f(tx);
+        let peer_id = {
+            let peers = self.peers.read();
+            let Some((peer_id, to_peer)) = peers.iter().choose(&mut rand::thread_rng()) else {
+                return Box::pin(futures::future::ready(Err(RequestError::UnsupportedCapability)))
+            };
+            if to_peer.send(cmd).is_err() {
+                return Box::pin(futures::future::ready(Err(RequestError::ConnectionDropped)))
+            }
+            *peer_id
+        };
+
+        let request_timeout = self.request_timeout;
+        Box::pin(async move {
+            let res = match tokio::time::timeout(request_timeout, rx).await {
+                Ok(Ok(res)) => res,
+                Ok(Err(_)) => Err(RequestError::ConnectionDropped),
+                Err(_) => Err(RequestError::Timeout),
+            };
+            res.map(|resp| WithPeerId::new(peer_id, resp))
+        })
+    }
+}
+
+impl DownloadClient for SnapFetchClient {
+    fn report_bad_message(&self, peer_id: PeerId) {
+        self.peers_handle.reputation_change(peer_id, ReputationChangeKind::BadMessage);
+    }
+
+    fn num_connected_peers(&self) -> usize {
+        self.peers.read().len()
+    }
+}
+
+impl SnapClient for SnapFetchClient {
+    fn get_account_range(&self, request: GetAccountRange) -> SnapFut<AccountRange> {
+        self.send_request(|response| SnapCommand::GetAccountRange { request, response })
+    }
+
+    fn get_storage_ranges(&self, request: GetStorageRanges) -> SnapFut<StorageRanges> {
+        self.send_request(|response| SnapCommand::GetStorageRanges { request, response })
+    }
+
+    fn get_byte_codes(&self, request: GetByteCodes) -> SnapFut<ByteCodes> {
+        self.send_request(|response| SnapCommand::GetByteCodes { request, response })
+    }
+
+    fn get_trie_nodes(&self, request: GetTrieNodes) -> SnapFut<TrieNodes> {
+        self.send_request(|response| SnapCommand::GetTrieNodes { request, response })
+    }
+}
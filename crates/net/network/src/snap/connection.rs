@@ -0,0 +1,290 @@
+//! A single `snap` connection to a peer.
+
+use std::{
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use alloy_primitives::map::HashMap;
+use alloy_rlp::BytesMut;
+use futures::{future::BoxFuture, stream::FuturesUnordered, FutureExt, Stream, StreamExt};
+use reth_eth_wire::{
+    multiplex::ProtocolConnection,
+    snap::{
+        AccountRange, ByteCodes, GetAccountRange, GetByteCodes, GetStorageRanges, GetTrieNodes,
+        SnapMessage, StorageRanges, TrieNodes,
+    },
+};
+use reth_network_p2p::error::{RequestError, RequestResult};
+use reth_network_peers::PeerId;
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::trace;
+
+use super::SnapPeers;
+use crate::snap_requests::IncomingSnapRequest;
+
+/// A request to send to the peer of a [`SnapConnection`].
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+pub(crate) enum SnapCommand {
+    /// Request a range of accounts.
+    GetAccountRange {
+        request: GetAccountRange,
+        response: oneshot::Sender<RequestResult<AccountRange>>,
+    },
+    /// Request storage ranges.
+    GetStorageRanges {
+        request: GetStorageRanges,
+        response: oneshot::Sender<RequestResult<StorageRanges>>,
+    },
+    /// Request bytecodes.
+    GetByteCodes { request: GetByteCodes, response: oneshot::Sender<RequestResult<ByteCodes>> },
+    /// Request trie nodes.
+    GetTrieNodes { request: GetTrieNodes, response: oneshot::Sender<RequestResult<TrieNodes>> },
+}
+
+/// A request that was sent to the peer and awaits its response.
+#[derive(Debug)]
+enum PendingSnapRequest {
+    AccountRange(oneshot::Sender<RequestResult<AccountRange>>),
+    StorageRanges(oneshot::Sender<RequestResult<StorageRanges>>),
+    ByteCodes(oneshot::Sender<RequestResult<ByteCodes>>),
+    TrieNodes(oneshot::Sender<RequestResult<TrieNodes>>),
+}
+
+impl PendingSnapRequest {
+    /// Returns `true` if the receiver of the response was dropped.
+    fn is_closed(&self) -> bool {
+        match self {
+            Self::AccountRange(tx) => tx.is_closed(),
+            Self::StorageRanges(tx) => tx.is_closed(),
+            Self::ByteCodes(tx) => tx.is_closed(),
+            Self::TrieNodes(tx) => tx.is_closed(),
+        }
+    }
+
+    /// Resolves the request with the given response message.
+    ///
+    /// A response of the wrong type resolves the request with [`RequestError::BadResponse`].
+    fn on_response(self, msg: SnapMessage) {
+        match (self, msg) {
+            (Self::AccountRange(tx), SnapMessage::AccountRange(resp)) => {
+                let _ = tx.send(Ok(resp));
+            }
+            (Self::StorageRanges(tx), SnapMessage::StorageRanges(resp)) => {
+                let _ = tx.send(Ok(resp));
+            }
+            (Self::ByteCodes(tx), SnapMessage::ByteCodes(resp)) => {
+                let _ = tx.send(Ok(resp));
+            }
+            (Self::TrieNodes(tx), SnapMessage::TrieNodes(resp)) => {
+                let _ = tx.send(Ok(resp));
+            }
+            (pending, _) => pending.on_error(RequestError::BadResponse),
+        }
+    }
+
+    fn on_error(self, err: RequestError) {
+        match self {
+            Self::AccountRange(tx) => {
+                let _ = tx.send(Err(err));
+            }
+            Self::StorageRanges(tx) => {
+                let _ = tx.send(Err(err));
+            }
+            Self::ByteCodes(tx) => {
+                let _ = tx.send(Err(err));
+            }
+            Self::TrieNodes(tx) => {
+                let _ = tx.send(Err(err));
+            }
+        }
+    }
+}
+
+/// The connection of the `snap` subprotocol to a single peer.
+///
+/// This yields the encoded messages that should be sent to the peer: the responses to the peer's
+/// requests, which are served by the request handler, and the requests issued via the
+/// [`SnapFetchClient`](super::SnapFetchClient).
+#[derive(Debug)]
+#[must_use = "Connection does nothing unless polled."]
+pub struct SnapConnection {
+    peer_id: PeerId,
+    /// The incoming messages of the peer.
+    conn: ProtocolConnection,
+    /// Requests to send to the peer.
+    commands: UnboundedReceiverStream<SnapCommand>,
+    /// Sender half of the channel to the request handler.
+    to_request_handler: Option<mpsc::Sender<IncomingSnapRequest>>,
+    /// Responses to the peer's requests that are currently being served.
+    served: FuturesUnordered<BoxFuture<'static, SnapMessage>>,
+    /// Requests sent to the peer, by request id.
+    pending: HashMap<u64, PendingSnapRequest>,
+    /// The id of the next request sent to the peer.
+    next_request_id: u64,
+    /// All active `snap` connections, this connection deregisters itself on drop.
+    peers: SnapPeers,
+}
+
+// === impl SnapConnection ===
+
+impl SnapConnection {
+    pub(super) fn new(
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+        commands: UnboundedReceiverStream<SnapCommand>,
+        to_request_handler: Option<mpsc::Sender<IncomingSnapRequest>>,
+        peers: SnapPeers,
+    ) -> Self {
+        Self {
+            peer_id,
+            conn,
+            commands,
+            to_request_handler,
+            served: Default::default(),
+            pending: Default::default(),
+            next_request_id: 0,
+            peers,
+        }
+    }
+
+    /// Registers the request as pending and returns the message to send to the peer.
+    fn on_command(&mut self, cmd: SnapCommand) -> SnapMessage {
+        let request_id = self.next_request_id;
+        self.next_request_id = self.next_request_id.wrapping_add(1);
+        // requests whose receiver is gone will never be awaited, the peer may not respond at all
+        self.pending.retain(|_, req| !req.is_closed());
+
+        let (msg, pending) = match cmd {
+            SnapCommand::GetAccountRange { mut request, response } => {
+                request.request_id = request_id;
+                (SnapMessage::GetAccountRange(request), PendingSnapRequest::AccountRange(response))
+            }
+            SnapCommand::GetStorageRanges { mut request, response } => {
+                request.request_id = request_id;
+                (
+                    SnapMessage::GetStorageRanges(request),
+                    PendingSnapRequest::StorageRanges(response),
+                )
+            }
+            SnapCommand::GetByteCodes { mut request, response } => {
+                request.request_id = request_id;
+                (SnapMessage::GetByteCodes(request), PendingSnapRequest::ByteCodes(response))
+            }
+            SnapCommand::GetTrieNodes { mut request, response } => {
+                request.request_id = request_id;
+                (SnapMessage::GetTrieNodes(request), PendingSnapRequest::TrieNodes(response))
+            }
+        };
+        self.pending.insert(request_id, pending);
+        msg
+    }
+
+    /// Handles a request of the peer by delegating it to the request handler.
+    ///
+    /// If the request can't be delegated, it's answered with an empty response.
+    fn on_request(&self, msg: SnapMessage) {
+        let peer_id = self.peer_id;
+        let request_id = msg.request_id();
+        let Some(to_request_handler) = self.to_request_handler.as_ref() else {
+            self.served.push(futures::future::ready(empty_response(msg)).boxed());
+            return
+        };
+
+        macro_rules! delegate {
+            ($request:ident, $variant:ident, $response:ident) => {{
+                let (tx, rx) = oneshot::channel();
+                let incoming =
+                    IncomingSnapRequest::$variant { peer_id, request: $request, response: tx };
+                let _ = to_request_handler.try_send(incoming);
+                rx.map(move |res| match res {
+                    Ok(Ok(resp)) => SnapMessage::$response(resp),
+                    _ => SnapMessage::$response($response { request_id, ..Default::default() }),
+                })
+                .boxed()
+            }};
+        }
+
+        let fut = match msg {
+            SnapMessage::GetAccountRange(request) => {
+                delegate!(request, GetAccountRange, AccountRange)
+            }
+            SnapMessage::GetStorageRanges(request) => {
+                delegate!(request, GetStorageRanges, StorageRanges)
+            }
+            SnapMessage::GetByteCodes(request) => delegate!(request, GetByteCodes, ByteCodes),
+            SnapMessage::GetTrieNodes(request) => delegate!(request, GetTrieNodes, TrieNodes),
+            _ => return,
+        };
+        self.served.push(fut);
+    }
+
+    /// Handles a response of the peer.
+    fn on_response(&mut self, msg: SnapMessage) {
+        match self.pending.remove(&msg.request_id()) {
+            Some(pending) => pending.on_response(msg),
+            None => {
+                trace!(target: "net::snap", peer_id=?self.peer_id, request_id=msg.request_id(), "received unsolicited response");
+            }
+        }
+    }
+}
+
+impl Stream for SnapConnection {
+    type Item = BytesMut;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.as_mut().get_mut();
+        loop {
+            if let Poll::Ready(Some(msg)) = this.served.poll_next_unpin(cx) {
+                return Poll::Ready(Some(msg.encoded()))
+            }
+
+            if let Poll::Ready(Some(cmd)) = this.commands.poll_next_unpin(cx) {
+                return Poll::Ready(Some(this.on_command(cmd).encoded()))
+            }
+
+            let Some(buf) = ready!(this.conn.poll_next_unpin(cx)) else { return Poll::Ready(None) };
+            match SnapMessage::decode(&mut &buf[..]) {
+                Ok(msg) if msg.is_request() => this.on_request(msg),
+                Ok(msg) => this.on_response(msg),
+                Err(err) => {
+                    trace!(target: "net::snap", peer_id=?this.peer_id, %err, "failed to decode snap message");
+                    // closes the connection
+                    return Poll::Ready(None)
+                }
+            }
+        }
+    }
+}
+
+impl Drop for SnapConnection {
+    fn drop(&mut self) {
+        self.peers.write().remove(&self.peer_id);
+        for (_, pending) in self.pending.drain() {
+            pending.on_error(RequestError::ConnectionDropped);
+        }
+    }
+}
+
+/// Returns the empty response to the given request.
+fn empty_response(request: SnapMessage) -> SnapMessage {
+    let request_id = request.request_id();
+    match request {
+        SnapMessage::GetAccountRange(_) => {
+            SnapMessage::AccountRange(AccountRange { request_id, ..Default::default() })
+        }
+        SnapMessage::GetStorageRanges(_) => {
+            SnapMessage::StorageRanges(StorageRanges { request_id, ..Default::default() })
+        }
+        SnapMessage::GetByteCodes(_) => {
+            SnapMessage::ByteCodes(ByteCodes { request_id, ..Default::default() })
+        }
+        SnapMessage::GetTrieNodes(_) => {
+            SnapMessage::TrieNodes(TrieNodes { request_id, ..Default::default() })
+        }
+        resp => resp,
+    }
+}
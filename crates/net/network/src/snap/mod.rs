@@ -0,0 +1,127 @@
+//! Support for the `snap/1` subprotocol.
+//!
+//! The protocol is offered as an additional `RLPx` subprotocol via [`SnapProtocolHandler`].
+//! Incoming requests are delegated to the
+//! [`SnapRequestHandler`](crate::snap_requests::SnapRequestHandler) and outgoing requests are sent
+//! through the [`SnapFetchClient`].
+//!
+//! See also <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+
+mod client;
+mod connection;
+
+pub use client::SnapFetchClient;
+
+use std::{net::SocketAddr, sync::Arc};
+
+use alloy_primitives::map::HashMap;
+use parking_lot::RwLock;
+use reth_eth_wire::{
+    capability::SharedCapabilities, multiplex::ProtocolConnection, protocol::Protocol,
+};
+use reth_network_api::{test_utils::PeersHandle, Direction, PeerId};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+use crate::{
+    protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler},
+    snap_requests::IncomingSnapRequest,
+};
+use connection::{SnapCommand, SnapConnection};
+
+/// The active `snap` connections, by peer.
+type SnapPeers = Arc<RwLock<HashMap<PeerId, mpsc::UnboundedSender<SnapCommand>>>>;
+
+/// The [`ProtocolHandler`] of the `snap/1` subprotocol.
+#[derive(Debug, Clone)]
+pub struct SnapProtocolHandler {
+    /// Sender half of the channel to the request handler, if requests are served.
+    to_request_handler: Option<mpsc::Sender<IncomingSnapRequest>>,
+    /// The active `snap` connections.
+    peers: SnapPeers,
+    /// The handle to the peers, used for reporting bad responses.
+    peers_handle: PeersHandle,
+}
+
+// === impl SnapProtocolHandler ===
+
+impl SnapProtocolHandler {
+    /// Creates a new handler.
+    ///
+    /// If no request handler is given, all incoming requests are answered with empty responses.
+    pub fn new(
+        peers_handle: PeersHandle,
+        to_request_handler: Option<mpsc::Sender<IncomingSnapRequest>>,
+    ) -> Self {
+        Self { to_request_handler, peers: Default::default(), peers_handle }
+    }
+
+    /// Returns a client that sends requests to the connected `snap` peers.
+    pub fn client(&self) -> SnapFetchClient {
+        SnapFetchClient::new(self.peers.clone(), self.peers_handle.clone())
+    }
+
+    fn connection_handler(&self) -> SnapConnectionHandler {
+        SnapConnectionHandler {
+            to_request_handler: self.to_request_handler.clone(),
+            peers: self.peers.clone(),
+        }
+    }
+}
+
+impl ProtocolHandler for SnapProtocolHandler {
+    type ConnectionHandler = SnapConnectionHandler;
+
+    fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        Some(self.connection_handler())
+    }
+
+    fn on_outgoing(
+        &self,
+        _socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        Some(self.connection_handler())
+    }
+}
+
+/// The [`ConnectionHandler`] of the `snap/1` subprotocol.
+#[derive(Debug)]
+pub struct SnapConnectionHandler {
+    to_request_handler: Option<mpsc::Sender<IncomingSnapRequest>>,
+    peers: SnapPeers,
+}
+
+impl ConnectionHandler for SnapConnectionHandler {
+    type Connection = SnapConnection;
+
+    fn protocol(&self) -> Protocol {
+        Protocol::snap_1()
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        _direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.peers.write().insert(peer_id, tx);
+        SnapConnection::new(
+            peer_id,
+            conn,
+            UnboundedReceiverStream::new(rx),
+            self.to_request_handler,
+            self.peers,
+        )
+    }
+}
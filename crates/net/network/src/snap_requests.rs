@@ -0,0 +1,557 @@
+//! State range management for the `snap` subprotocol.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_primitives::{
+    map::{HashMap, HashSet},
+    Bytes, B256,
+};
+use futures::StreamExt;
+use reth_eth_wire::snap::{
+    AccountData, AccountRange, ByteCodes, GetAccountRange, GetByteCodes, GetStorageRanges,
+    GetTrieNodes, SlimAccount, StorageData, StorageRanges, TrieNodes,
+};
+use reth_network_p2p::error::RequestResult;
+use reth_network_peers::PeerId;
+use reth_storage_api::{BlockNumReader, HeaderProvider, StateProviderBox, StateProviderFactory};
+use reth_trie::{HashedStorage, MultiProof, Nibbles, TrieInput};
+use tokio::sync::{mpsc::Receiver, oneshot};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::debug;
+
+use crate::{
+    budget::DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS, metered_poll_nested_stream_with_budget,
+    metrics::SnapRequestHandlerMetrics,
+};
+
+// Limits: <https://github.com/ethereum/go-ethereum/blob/b0d44338bbcefee044f1f635a84487cbbd8f0538/eth/protocols/snap/handler.go#L32-L50>
+
+/// Maximum size of replies to data retrievals, regardless of the requested size.
+const SOFT_RESPONSE_LIMIT: u64 = 2 * 1024 * 1024;
+
+/// Maximum number of bytecodes to serve.
+///
+/// Used to limit lookups.
+const MAX_CODE_LOOKUPS: usize = 1024;
+
+/// Maximum number of trie nodes to serve.
+///
+/// Used to limit lookups.
+const MAX_TRIE_NODE_LOOKUPS: usize = 1024;
+
+/// Number of accounts or storage slots that are loaded from the state at once.
+const RANGE_PAGE_SIZE: usize = 128;
+
+/// Number of recent blocks whose state is served.
+///
+/// Matches the number of diff layers geth keeps on top of its persisted snapshot.
+const SERVED_STATES: u64 = 128;
+
+/// Manages `snap` requests on top of the p2p network.
+///
+/// The states of the [`SERVED_STATES`] most recent blocks are served. Requests for any other state
+/// root are answered with empty responses, which the protocol permits.
+///
+/// This can be spawned to another task and is supposed to be run as background service.
+#[derive(Debug)]
+#[must_use = "Manager does nothing unless polled."]
+pub struct SnapRequestHandler<C> {
+    /// The client type that can interact with the chain.
+    client: C,
+    /// Incoming requests from the `snap` connections.
+    incoming_requests: ReceiverStream<IncomingSnapRequest>,
+    /// Metrics for the snap request handler.
+    metrics: SnapRequestHandlerMetrics,
+}
+
+// === impl SnapRequestHandler ===
+
+impl<C> SnapRequestHandler<C> {
+    /// Create a new instance
+    pub fn new(client: C, incoming: Receiver<IncomingSnapRequest>) -> Self {
+        Self {
+            client,
+            incoming_requests: ReceiverStream::new(incoming),
+            metrics: Default::default(),
+        }
+    }
+}
+
+impl<C> SnapRequestHandler<C>
+where
+    C: StateProviderFactory + HeaderProvider + BlockNumReader,
+{
+    /// Returns the state of the most recent block, out of the last [`SERVED_STATES`] blocks,
+    /// whose state root matches the requested root.
+    ///
+    /// A historical state builds its revert state once, so it's shared by all pages and proofs of
+    /// a request.
+    fn state_by_root(&self, root: B256) -> Option<StateProviderBox> {
+        let best = self.client.best_block_number().ok()?;
+        let first = best.saturating_sub(SERVED_STATES - 1);
+        let headers = self.client.headers_range(first..=best).ok()?;
+        let header = headers.into_iter().rev().find(|header| header.state_root == root)?;
+        if header.number == best {
+            self.client.latest().ok()
+        } else {
+            self.client.history_by_block_number(header.number).ok()
+        }
+    }
+
+    /// Returns the accounts of the requested range together with the proof of its boundaries.
+    fn get_account_range_response(&self, request: &GetAccountRange) -> Option<AccountRange> {
+        let state = self.state_by_root(request.root_hash)?;
+        let limit_bytes = request.response_bytes.min(SOFT_RESPONSE_LIMIT);
+
+        let mut accounts = Vec::new();
+        let mut size = 0;
+        let mut next = Some(request.starting_hash);
+        'pages: while let Some(start) = next {
+            let page = state.account_range(TrieInput::default(), start, RANGE_PAGE_SIZE, 0).ok()?;
+            for entry in page.accounts {
+                let account = AccountData {
+                    hash: entry.hashed_address,
+                    body: SlimAccount {
+                        nonce: entry.account.nonce,
+                        balance: entry.account.balance,
+                        storage_root: entry.storage_root,
+                        code_hash: entry.account.bytecode_hash.unwrap_or(KECCAK_EMPTY),
+                    },
+                };
+                size += alloy_rlp::Encodable::length(&account) as u64;
+                accounts.push(account);
+
+                // the first account past the limit is included to prove the range
+                let past_limit = entry.hashed_address >= request.limit_hash;
+                if past_limit || size > limit_bytes {
+                    break 'pages
+                }
+            }
+            next = page.next_key;
+        }
+
+        let mut targets = HashMap::default();
+        targets.insert(request.starting_hash, HashSet::default());
+        if let Some(last) = accounts.last() {
+            targets.insert(last.hash, HashSet::default());
+        }
+        let proof = state.multiproof(TrieInput::default(), targets).ok()?;
+
+        Some(AccountRange {
+            request_id: request.request_id,
+            accounts,
+            proof: proof_nodes(proof, None),
+        })
+    }
+
+    /// Returns the storage slots of the requested accounts.
+    ///
+    /// Only the first account's storage is bounded by the requested range, and only the last
+    /// returned account's storage may be incomplete. If so, or if the first account's storage
+    /// doesn't start at the zero hash or is cut short by the limit hash, its boundaries are
+    /// proven by the proof.
+    fn get_storage_ranges_response(&self, request: &GetStorageRanges) -> Option<StorageRanges> {
+        let state = self.state_by_root(request.root_hash)?;
+        let limit_bytes = request.response_bytes.min(SOFT_RESPONSE_LIMIT);
+
+        let mut slots = Vec::new();
+        let mut proof = Vec::new();
+        let mut size = 0;
+        for (idx, hashed_address) in request.account_hashes.iter().copied().enumerate() {
+            if size >= limit_bytes {
+                break
+            }
+
+            let (origin, limit) = if idx == 0 {
+                (request.starting_hash(), request.limit_hash())
+            } else {
+                (B256::ZERO, B256::repeat_byte(0xff))
+            };
+
+            let mut storage = Vec::new();
+            let mut truncated = false;
+            let mut next = Some(origin);
+            'pages: while let Some(start) = next {
+                let page = state
+                    .hashed_storage_range(
+                        hashed_address,
+                        HashedStorage::default(),
+                        start,
+                        RANGE_PAGE_SIZE,
+                    )
+                    .ok()?;
+                for (hashed_slot, value) in page.slots {
+                    let slot = StorageData::new(hashed_slot, value);
+                    size += alloy_rlp::Encodable::length(&slot) as u64;
+                    storage.push(slot);
+
+                    if hashed_slot >= limit || size > limit_bytes {
+                        truncated = true;
+                        break 'pages
+                    }
+                }
+                next = page.next_key;
+            }
+
+            // a proof is needed if the storage is incomplete
+            let needs_proof = origin != B256::ZERO || truncated;
+            if needs_proof {
+                let mut storage_targets = HashSet::default();
+                storage_targets.insert(origin);
+                if let Some(last) = storage.last() {
+                    storage_targets.insert(last.hash);
+                }
+                let mut targets = HashMap::default();
+                targets.insert(hashed_address, storage_targets);
+                let multiproof = state.multiproof(TrieInput::default(), targets).ok()?;
+                proof = proof_nodes(multiproof, Some(hashed_address));
+            }
+
+            slots.push(storage);
+            if needs_proof {
+                break
+            }
+        }
+
+        Some(StorageRanges { request_id: request.request_id, slots, proof })
+    }
+
+    /// Returns the requested bytecodes, skipping unknown codes.
+    fn get_byte_codes_response(&self, request: &GetByteCodes) -> Option<ByteCodes> {
+        let state = self.client.latest().ok()?;
+        let limit_bytes = request.response_bytes.min(SOFT_RESPONSE_LIMIT);
+
+        let mut codes = Vec::new();
+        let mut size = 0;
+        for hash in request.hashes.iter().take(MAX_CODE_LOOKUPS) {
+            let code = if *hash == KECCAK_EMPTY {
+                Bytes::new()
+            } else if let Some(code) = state.bytecode_by_hash(*hash).ok().flatten() {
+                code.original_bytes()
+            } else {
+                continue
+            };
+
+            size += code.len() as u64;
+            codes.push(code);
+
+            if size > limit_bytes {
+                break
+            }
+        }
+
+        Some(ByteCodes { request_id: request.request_id, codes })
+    }
+
+    fn on_account_range_request(
+        &self,
+        _peer_id: PeerId,
+        request: GetAccountRange,
+        response: oneshot::Sender<RequestResult<AccountRange>>,
+    ) {
+        self.metrics.snap_account_range_requests_received_total.increment(1);
+        let range = self.get_account_range_response(&request).unwrap_or_else(|| {
+            debug!(target: "net::snap", root=%request.root_hash, "Unable to serve account range");
+            AccountRange { request_id: request.request_id, ..Default::default() }
+        });
+        let _ = response.send(Ok(range));
+    }
+
+    fn on_storage_ranges_request(
+        &self,
+        _peer_id: PeerId,
+        request: GetStorageRanges,
+        response: oneshot::Sender<RequestResult<StorageRanges>>,
+    ) {
+        self.metrics.snap_storage_ranges_requests_received_total.increment(1);
+        let ranges = self.get_storage_ranges_response(&request).unwrap_or_else(|| {
+            debug!(target: "net::snap", root=%request.root_hash, "Unable to serve storage ranges");
+            StorageRanges { request_id: request.request_id, ..Default::default() }
+        });
+        let _ = response.send(Ok(ranges));
+    }
+
+    fn on_byte_codes_request(
+        &self,
+        _peer_id: PeerId,
+        request: GetByteCodes,
+        response: oneshot::Sender<RequestResult<ByteCodes>>,
+    ) {
+        self.metrics.snap_byte_codes_requests_received_total.increment(1);
+        let codes = self
+            .get_byte_codes_response(&request)
+            .unwrap_or(ByteCodes { request_id: request.request_id, codes: Vec::new() });
+        let _ = response.send(Ok(codes));
+    }
+
+    fn on_trie_nodes_request(
+        &self,
+        _peer_id: PeerId,
+        request: GetTrieNodes,
+        response: oneshot::Sender<RequestResult<TrieNodes>>,
+    ) {
+        self.metrics.snap_trie_nodes_requests_received_total.increment(1);
+        let nodes = self.get_trie_nodes_response(&request).unwrap_or_else(|| {
+            debug!(target: "net::snap", root=%request.root_hash, "Unable to serve trie nodes");
+            TrieNodes { request_id: request.request_id, nodes: Vec::new() }
+        });
+        let _ = response.send(Ok(nodes));
+    }
+
+    /// Returns the requested trie nodes, stopping at the first node that is unavailable.
+    ///
+    /// Only branch nodes are persisted by path, so the nodes are taken from a multiproof of keys
+    /// that start with the requested paths.
+    fn get_trie_nodes_response(&self, request: &GetTrieNodes) -> Option<TrieNodes> {
+        let state = self.state_by_root(request.root_hash)?;
+        let limit_bytes = request.response_bytes.min(SOFT_RESPONSE_LIMIT);
+
+        // Each path set either names a node of the account trie, or a hashed account followed by
+        // nodes of its storage trie.
+        let mut lookups = Vec::new();
+        let mut targets = HashMap::<B256, HashSet<B256>>::default();
+        'paths: for path_set in &request.paths {
+            match path_set.0.as_slice() {
+                [] => break,
+                [account_path] => {
+                    let Some(path) = decode_compact_path(account_path) else { break };
+                    let Some(target) = path_target(&path) else { break };
+                    targets.entry(target).or_default();
+                    lookups.push((None, path));
+                }
+                [account, storage_paths @ ..] => {
+                    if account.len() != B256::len_bytes() {
+                        break
+                    }
+                    let hashed_address = B256::from_slice(account);
+                    let storage_targets = targets.entry(hashed_address).or_default();
+                    for storage_path in storage_paths {
+                        if lookups.len() >= MAX_TRIE_NODE_LOOKUPS {
+                            break 'paths
+                        }
+                        let Some(path) = decode_compact_path(storage_path) else { break 'paths };
+                        let Some(target) = path_target(&path) else { break 'paths };
+                        storage_targets.insert(target);
+                        lookups.push((Some(hashed_address), path));
+                    }
+                }
+            }
+            if lookups.len() >= MAX_TRIE_NODE_LOOKUPS {
+                break
+            }
+        }
+
+        let MultiProof { account_subtree, storages } =
+            state.multiproof(TrieInput::default(), targets).ok()?;
+        let account_nodes =
+            account_subtree.into_nodes_sorted().into_iter().collect::<HashMap<_, _>>();
+        let storage_nodes = storages
+            .into_iter()
+            .map(|(hashed_address, proof)| {
+                (
+                    hashed_address,
+                    proof.subtree.into_nodes_sorted().into_iter().collect::<HashMap<_, _>>(),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+
+        let mut nodes = Vec::new();
+        let mut size = 0;
+        for (hashed_address, path) in lookups {
+            let node = match hashed_address {
+                Some(hashed_address) => {
+                    storage_nodes.get(&hashed_address).and_then(|nodes| nodes.get(&path))
+                }
+                None => account_nodes.get(&path),
+            };
+            let Some(node) = node else { break };
+
+            size += node.len() as u64;
+            nodes.push(node.clone());
+
+            if size > limit_bytes {
+                break
+            }
+        }
+
+        Some(TrieNodes { request_id: request.request_id, nodes })
+    }
+}
+
+/// Decodes a hex-prefix encoded trie path into its nibbles.
+fn decode_compact_path(path: &[u8]) -> Option<Nibbles> {
+    let (&first, rest) = path.split_first()?;
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    // the lowest bit of the flag nibble marks an odd number of nibbles, whose first nibble is
+    // stored next to the flag
+    if first & 0x10 != 0 {
+        nibbles.push(first & 0x0f);
+    }
+    for byte in rest {
+        nibbles.push(byte >> 4);
+        nibbles.push(byte & 0x0f);
+    }
+    Some(Nibbles::from_nibbles_unchecked(nibbles))
+}
+
+/// Returns the smallest key that starts with the given path, or `None` if the path is longer than
+/// a key.
+fn path_target(path: &Nibbles) -> Option<B256> {
+    if path.len() > 2 * B256::len_bytes() {
+        return None
+    }
+    Some(B256::right_padding_from(&path.pack()))
+}
+
+/// Returns the nodes of the account trie or, if an account is given, of its storage trie, ordered
+/// by path from the root.
+fn proof_nodes(proof: MultiProof, storage_of: Option<B256>) -> Vec<Bytes> {
+    let nodes = match storage_of {
+        Some(hashed_address) => match proof.storages.get(&hashed_address) {
+            Some(storage) => storage.subtree.clone(),
+            None => return Vec::new(),
+        },
+        None => proof.account_subtree,
+    };
+    nodes.into_nodes_sorted().into_iter().map(|(_, node)| node).collect()
+}
+
+/// An endless future.
+///
+/// This should be spawned or used as part of `tokio::select!`.
+impl<C> Future for SnapRequestHandler<C>
+where
+    C: StateProviderFactory + HeaderProvider + BlockNumReader + Unpin,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        let mut acc = Duration::ZERO;
+        let maybe_more_incoming_requests = metered_poll_nested_stream_with_budget!(
+            acc,
+            "net::snap",
+            "Incoming snap requests stream",
+            DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS,
+            this.incoming_requests.poll_next_unpin(cx),
+            |incoming| {
+                match incoming {
+                    IncomingSnapRequest::GetAccountRange { peer_id, request, response } => {
+                        this.on_account_range_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetStorageRanges { peer_id, request, response } => {
+                        this.on_storage_ranges_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetByteCodes { peer_id, request, response } => {
+                        this.on_byte_codes_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetTrieNodes { peer_id, request, response } => {
+                        this.on_trie_nodes_request(peer_id, request, response)
+                    }
+                }
+            },
+        );
+
+        this.metrics.acc_duration_poll_snap_req_handler.set(acc.as_secs_f64());
+
+        // stream is fully drained and import futures pending
+        if maybe_more_incoming_requests {
+            // make sure we're woken up again
+            cx.waker().wake_by_ref();
+            return Poll::Pending
+        }
+
+        Poll::Pending
+    }
+}
+
+/// All `snap` requests delegated by the `snap` connections.
+#[derive(Debug)]
+pub enum IncomingSnapRequest {
+    /// Request a range of accounts from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetAccountRange {
+        /// The ID of the peer that sent the request.
+        peer_id: PeerId,
+        /// The requested account range.
+        request: GetAccountRange,
+        /// The channel sender for the response containing the accounts.
+        response: oneshot::Sender<RequestResult<AccountRange>>,
+    },
+    /// Request storage ranges from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetStorageRanges {
+        /// The ID of the peer that sent the request.
+        peer_id: PeerId,
+        /// The requested storage ranges.
+        request: GetStorageRanges,
+        /// The channel sender for the response containing the storage slots.
+        response: oneshot::Sender<RequestResult<StorageRanges>>,
+    },
+    /// Request bytecodes from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetByteCodes {
+        /// The ID of the peer that sent the request.
+        peer_id: PeerId,
+        /// The requested bytecodes.
+        request: GetByteCodes,
+        /// The channel sender for the response containing the bytecodes.
+        response: oneshot::Sender<RequestResult<ByteCodes>>,
+    },
+    /// Request trie nodes from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetTrieNodes {
+        /// The ID of the peer that sent the request.
+        peer_id: PeerId,
+        /// The requested trie nodes.
+        request: GetTrieNodes,
+        /// The channel sender for the response containing the trie nodes.
+        response: oneshot::Sender<RequestResult<TrieNodes>>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_compact_paths() {
+        assert_eq!(decode_compact_path(&[]), None);
+        assert_eq!(decode_compact_path(&[0x00]), Some(Nibbles::default()));
+        assert_eq!(
+            decode_compact_path(&[0x00, 0x12, 0x34]),
+            Some(Nibbles::from_nibbles_unchecked([1, 2, 3, 4]))
+        );
+        assert_eq!(
+            decode_compact_path(&[0x11, 0x23]),
+            Some(Nibbles::from_nibbles_unchecked([1, 2, 3]))
+        );
+        // the leaf flag is ignored
+        assert_eq!(decode_compact_path(&[0x3f]), Some(Nibbles::from_nibbles_unchecked([0xf])));
+    }
+
+    #[test]
+    fn path_targets() {
+        assert_eq!(path_target(&Nibbles::default()), Some(B256::ZERO));
+
+        let mut expected = B256::ZERO;
+        expected[0] = 0x12;
+        expected[1] = 0x30;
+        assert_eq!(path_target(&Nibbles::from_nibbles_unchecked([1, 2, 3])), Some(expected));
+
+        let key = B256::repeat_byte(0xab);
+        assert_eq!(path_target(&Nibbles::unpack(key)), Some(key));
+        assert_eq!(path_target(&Nibbles::from_nibbles_unchecked([0; 65])), None);
+    }
+}
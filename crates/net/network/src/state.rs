@@ -14,7 +14,9 @@ use std::{
 
 use alloy_primitives::B256;
 use rand::seq::SliceRandom;
-use reth_eth_wire::{BlockHashNumber, Capabilities, DisconnectReason, NewBlockHashes, Status};
+use reth_eth_wire::{
+    BlockHashNumber, BlockRangeUpdate, Capabilities, DisconnectReason, NewBlockHashes, Status,
+};
 use reth_network_api::{DiscoveredEvent, DiscoveryEvent, PeerRequest, PeerRequestSender};
 use reth_network_peers::PeerId;
 use reth_network_types::{PeerAddr, PeerKind};
@@ -144,15 +146,21 @@ impl NetworkState {
         peer: PeerId,
         capabilities: Arc<Capabilities>,
         status: Arc<Status>,
+        block_range: Option<BlockRangeUpdate>,
         request_tx: PeerRequestSender,
         timeout: Arc<AtomicU64>,
     ) {
         debug_assert!(!self.active_peers.contains_key(&peer), "Already connected; not possible");
 
         // find the corresponding block number
-        let block_number =
-            self.client.block_number(status.blockhash).ok().flatten().unwrap_or_default();
+        let block_number = match block_range {
+            Some(range) => range.latest,
+            None => self.client.block_number(status.blockhash).ok().flatten().unwrap_or_default(),
+        };
         self.state_fetcher.new_active_peer(peer, status.blockhash, block_number, timeout);
+        if let Some(range) = block_range {
+            self.state_fetcher.update_peer_block_range(&peer, range);
+        }
 
         self.active_peers.insert(
             peer,
@@ -252,6 +260,14 @@ impl NetworkState {
         self.state_fetcher.update_peer_block(peer_id, hash, number);
     }
 
+    /// Invoked when an `eth/69` peer announced a new range of blocks it can serve.
+    pub(crate) fn on_block_range_update(&mut self, peer_id: &PeerId, range: BlockRangeUpdate) {
+        if let Some(peer) = self.active_peers.get_mut(peer_id) {
+            peer.best_hash = range.latest_hash;
+        }
+        self.state_fetcher.update_peer_block_range(peer_id, range);
+    }
+
     /// Invoked when a new [`ForkId`] is activated.
     pub(crate) fn update_fork_id(&self, fork_id: ForkId) {
         self.discovery.update_fork_id(fork_id)
@@ -602,6 +618,7 @@ mod tests {
             peer_id,
             capabilities(),
             Arc::default(),
+            None,
             peer_tx,
             Arc::new(AtomicU64::new(1)),
         );
@@ -55,6 +55,28 @@ pub mod tx_manager {
     pub const DEFAULT_MAX_COUNT_BAD_IMPORTS: u32 = 100 * 1024 / 32;
 }
 
+/// Constants used to score the transaction gossip of peers, see
+/// [`TransactionGossipScoringConfig`](super::TransactionGossipScoringConfig).
+pub mod tx_scoring {
+    /// Default score change for each transaction from a peer that was successfully imported into
+    /// the pool.
+    pub const DEFAULT_USEFUL_TRANSACTION_WEIGHT: i64 = 1;
+
+    /// Default score change for each hash a peer announces that it already announced or received
+    /// from us before.
+    pub const DEFAULT_DUPLICATE_ANNOUNCEMENT_WEIGHT: i64 = -1;
+
+    /// Default score change for each announced hash that a peer didn't deliver when requested.
+    pub const DEFAULT_UNDELIVERED_HASH_WEIGHT: i64 = -1;
+
+    /// Default upper bound of a peer's score, so that a peer can't build up an arbitrarily large
+    /// buffer of good behaviour.
+    pub const DEFAULT_MAX_SCORE: i64 = 256;
+
+    /// Default score at which a peer is reported for poor transaction gossip.
+    pub const DEFAULT_REPORT_THRESHOLD: i64 = -256;
+}
+
 /// Constants used by [`TransactionFetcher`](super::TransactionFetcher).
 pub mod tx_fetcher {
     use crate::transactions::fetcher::TransactionFetcherInfo;
@@ -33,6 +33,7 @@ use std::{
 };
 
 use alloy_primitives::TxHash;
+use alloy_rlp::Encodable;
 use derive_more::{Constructor, Deref};
 use futures::{stream::FuturesUnordered, Future, FutureExt, Stream, StreamExt};
 use pin_project::pin_project;
@@ -927,9 +928,14 @@ impl TransactionFetcher {
                         "received empty `PooledTransactions` response from peer, peer failed to serve hashes it announced"
                     );
 
-                    return FetchEvent::EmptyResponse { peer_id }
+                    return FetchEvent::EmptyResponse {
+                        peer_id,
+                        undelivered: requested_hashes.len(),
+                    }
                 }
 
+                let response_size = transactions.length();
+
                 //
                 // 2. filter out hashes that we didn't request
                 //
@@ -1010,12 +1016,17 @@ impl TransactionFetcher {
                 //
                 // 5. buffer left over hashes
                 //
+                // peers stop filling the response once it reaches the soft limit, so hashes left
+                // out of a full response don't count as undelivered
+                let soft_limit = self.info.soft_limit_byte_size_pooled_transactions_response;
+                let undelivered =
+                    if response_size >= soft_limit { 0 } else { requested_hashes.len() };
                 self.try_buffer_hashes_for_retry(requested_hashes, &peer_id);
 
                 let transactions =
                     valid_payload.into_data().into_values().collect::<PooledTransactions>();
 
-                FetchEvent::TransactionsFetched { peer_id, transactions }
+                FetchEvent::TransactionsFetched { peer_id, transactions, undelivered }
             }
             Ok(Err(req_err)) => {
                 self.try_buffer_hashes_for_retry(requested_hashes, &peer_id);
@@ -1103,6 +1114,8 @@ pub enum FetchEvent {
         peer_id: PeerId,
         /// The transactions that were fetched, if available.
         transactions: PooledTransactions,
+        /// The number of requested hashes the peer didn't deliver.
+        undelivered: usize,
     },
     /// Triggered when there is an error in fetching transactions.
     FetchError {
@@ -1115,6 +1128,8 @@ pub enum FetchEvent {
     EmptyResponse {
         /// The ID of the sender.
         peer_id: PeerId,
+        /// The number of requested hashes, none of which the peer delivered.
+        undelivered: usize,
     },
 }
 
@@ -1565,4 +1580,36 @@ mod test {
         assert_eq!(1, verified_payload.len());
         assert!(verified_payload.contains(&signed_tx_1));
     }
+
+    #[test]
+    fn hashes_left_out_of_full_response_are_not_undelivered() {
+        let input = hex!("02f871018302a90f808504890aef60826b6c94ddf4c5025d1a5742cf12f74eec246d4432c295e487e09c3bbcc12b2b80c080a0f21a4eacd0bf8fea9c5105c543be5a1d8c796516875710fafafdf16d16d8ee23a001280915021bb446d1973501a67f93d2b38894a514b976e7b46dc2fe54598daa");
+        let signed_tx: PooledTransactionsElement =
+            TransactionSigned::decode(&mut &input[..]).unwrap().try_into().unwrap();
+        let response = PooledTransactions(vec![signed_tx.clone()]);
+
+        let resolve = |soft_limit_byte_size_pooled_transactions_response| {
+            let mut tx_fetcher =
+                TransactionFetcher::with_transaction_fetcher_config(&TransactionFetcherConfig {
+                    soft_limit_byte_size_pooled_transactions_response,
+                    ..Default::default()
+                });
+            let requested_hashes = RequestTxHashes::new(
+                [*signed_tx.hash(), B256::from_slice(&[1; 32])].into_iter().collect(),
+            );
+            tx_fetcher.on_resolved_get_pooled_transactions_request_fut(GetPooledTxResponse {
+                peer_id: PeerId::ZERO,
+                requested_hashes,
+                result: Ok(Ok(response.clone())),
+            })
+        };
+
+        // the response is below the soft limit, the peer didn't deliver the second hash
+        let event = resolve(SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE);
+        assert!(matches!(event, FetchEvent::TransactionsFetched { undelivered: 1, .. }));
+
+        // the response reached the soft limit, the peer couldn't deliver the second hash
+        let event = resolve(response.length());
+        assert!(matches!(event, FetchEvent::TransactionsFetched { undelivered: 0, .. }));
+    }
 }
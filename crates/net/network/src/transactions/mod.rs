@@ -6,6 +6,8 @@ pub mod config;
 pub mod constants;
 /// Component responsible for fetching transactions from [`NewPooledTransactionHashes`].
 pub mod fetcher;
+/// Scoring of the transaction gossip of peers.
+pub mod scoring;
 pub mod validation;
 
 pub use self::constants::{
@@ -13,10 +15,13 @@ pub use self::constants::{
     SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
 };
 pub use config::{TransactionFetcherConfig, TransactionPropagationMode, TransactionsManagerConfig};
+pub use scoring::TransactionGossipScoringConfig;
 pub use validation::*;
 
 pub(crate) use fetcher::{FetchEvent, TransactionFetcher};
 
+use self::scoring::{GossipEvent, PeerGossipScore};
+
 use self::constants::{tx_manager::*, DEFAULT_SOFT_LIMIT_BYTE_SIZE_TRANSACTIONS_BROADCAST_MESSAGE};
 use constants::SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE;
 
@@ -32,6 +37,7 @@ use std::{
 };
 
 use alloy_primitives::{TxHash, B256};
+use alloy_rlp::Encodable;
 use futures::{stream::FuturesUnordered, Future, StreamExt};
 use reth_eth_wire::{
     DedupPayload, EthVersion, GetPooledTransactions, HandleMempoolData, HandleVersionedMempoolData,
@@ -646,6 +652,7 @@ where
             return
         };
         let client = peer.client_version.clone();
+        self.metrics.announced_hashes.increment(msg.len() as u64);
 
         // keep track of the transactions the peer knows
         let mut count_txns_already_seen_by_peer = 0;
@@ -672,6 +679,11 @@ where
             );
 
             self.report_already_seen(peer_id);
+            self.score_peer(
+                peer_id,
+                GossipEvent::DuplicateAnnouncement,
+                count_txns_already_seen_by_peer as usize,
+            );
         }
 
         // 1. filter out spam
@@ -742,6 +754,7 @@ where
         //
         // for any seen hashes add the peer as fallback. unseen hashes are loaded into the tx
         // fetcher, hence they should be valid at this point.
+        let hashes_count_pre_fetcher_filter = valid_announcement_data.len();
         let bad_imports = &self.bad_imports;
         self.transaction_fetcher.filter_unseen_and_pending_hashes(
             &mut valid_announcement_data,
@@ -750,6 +763,11 @@ where
             |peer_id| self.peers.contains_key(&peer_id),
             &client,
         );
+        if hashes_count_pre_fetcher_filter > valid_announcement_data.len() {
+            self.metrics.occurrences_hashes_already_pending_fetch.increment(
+                (hashes_count_pre_fetcher_filter - valid_announcement_data.len()) as u64,
+            );
+        }
 
         if valid_announcement_data.is_empty() {
             // nothing to request
@@ -983,6 +1001,26 @@ where
         let Some(peer) = self.peers.get_mut(&peer_id) else { return };
         let mut transactions = transactions.0;
 
+        // enforce the peer's inbound bandwidth limit on unsolicited broadcasts, responses to our
+        // own requests are bounded by the fetcher already
+        if source.is_broadcast() {
+            let bytes = transactions.iter().map(|tx| tx.length()).sum();
+            if !peer.gossip_score.try_consume_inbound_bytes(
+                bytes,
+                &self.config.scoring,
+                Instant::now(),
+            ) {
+                trace!(target: "net::tx",
+                    peer_id=format!("{peer_id:#}"),
+                    txs_len=transactions.len(),
+                    bytes,
+                    "dropping transactions from peer that exceeded its inbound bandwidth limit"
+                );
+                self.metrics.throttled_transactions.increment(transactions.len() as u64);
+                return
+            }
+        }
+
         // mark the transactions as received
         self.transaction_fetcher
             .remove_hashes_from_transaction_fetcher(transactions.iter().map(|tx| *tx.hash()));
@@ -1075,7 +1113,7 @@ where
                 trace!(target: "net::tx::propagation", new_txs_len=?new_txs.len(), "Importing new transactions");
                 let import = Box::pin(async move {
                     let added = new_txs.len();
-                    let res = pool.add_external_transactions(new_txs).await;
+                    let res = pool.add_external_transactions_from_peer(peer_id, new_txs).await;
 
                     // update metrics
                     metric_pending_pool_imports.decrement(added as f64);
@@ -1124,19 +1162,30 @@ where
     /// Processes a [`FetchEvent`].
     fn on_fetch_event(&mut self, fetch_event: FetchEvent) {
         match fetch_event {
-            FetchEvent::TransactionsFetched { peer_id, transactions } => {
+            FetchEvent::TransactionsFetched { peer_id, transactions, undelivered } => {
+                self.on_undelivered_hashes(peer_id, undelivered);
                 self.import_transactions(peer_id, transactions, TransactionSource::Response);
             }
             FetchEvent::FetchError { peer_id, error } => {
                 trace!(target: "net::tx", ?peer_id, %error, "requesting transactions from peer failed");
                 self.on_request_error(peer_id, error);
             }
-            FetchEvent::EmptyResponse { peer_id } => {
+            FetchEvent::EmptyResponse { peer_id, undelivered } => {
                 trace!(target: "net::tx", ?peer_id, "peer returned empty response");
+                self.on_undelivered_hashes(peer_id, undelivered);
             }
         }
     }
 
+    /// Handles hashes that the peer announced but didn't deliver on request.
+    fn on_undelivered_hashes(&mut self, peer_id: PeerId, undelivered: usize) {
+        if undelivered == 0 {
+            return
+        }
+        self.metrics.undelivered_announced_hashes.increment(undelivered as u64);
+        self.score_peer(peer_id, GossipEvent::UndeliveredHash, undelivered);
+    }
+
     /// Runs an operation to fetch hashes that are cached in [`TransactionFetcher`].
     fn on_fetch_hashes_pending_fetch(&mut self) {
         // try drain transaction hashes pending fetch
@@ -1172,14 +1221,29 @@ where
         self.report_peer(peer_id, kind);
     }
 
+    /// Updates the transaction gossip score of the peer and reports the peer if its score dropped
+    /// to the configured threshold.
+    fn score_peer(&mut self, peer_id: PeerId, event: GossipEvent, count: usize) {
+        let Some(peer) = self.peers.get_mut(&peer_id) else { return };
+        if peer.gossip_score.record(event, count, &self.config.scoring) {
+            trace!(target: "net::tx", ?peer_id, "Penalizing peer for poor transaction gossip");
+            self.metrics.reported_poor_transaction_gossip.increment(1);
+            self.report_peer(peer_id, ReputationChangeKind::PoorTransactionGossip);
+        }
+    }
+
     fn report_already_seen(&self, peer_id: PeerId) {
         trace!(target: "net::tx", ?peer_id, "Penalizing peer for already seen transaction");
         self.network.reputation_change(peer_id, ReputationChangeKind::AlreadySeenTransaction);
     }
 
-    /// Clear the transaction
+    /// Clear the transaction and reward the peers that sent it.
     fn on_good_import(&mut self, hash: TxHash) {
-        self.transactions_by_peers.remove(&hash);
+        if let Some(peers) = self.transactions_by_peers.remove(&hash) {
+            for peer_id in peers {
+                self.score_peer(peer_id, GossipEvent::UsefulTransaction, 1);
+            }
+        }
     }
 
     /// Penalize the peers that intentionally sent the bad transaction, and cache it to avoid
@@ -1596,7 +1660,7 @@ impl PooledTransactionsHashesBuilder {
     fn new(version: EthVersion) -> Self {
         match version {
             EthVersion::Eth66 | EthVersion::Eth67 => Self::Eth66(Default::default()),
-            EthVersion::Eth68 => Self::Eth68(Default::default()),
+            EthVersion::Eth68 | EthVersion::Eth69 => Self::Eth68(Default::default()),
         }
     }
 
@@ -1638,6 +1702,8 @@ pub struct PeerMetadata {
     version: EthVersion,
     /// The peer's client version.
     client_version: Arc<str>,
+    /// Score of the peer's transaction gossip.
+    gossip_score: PeerGossipScore,
 }
 
 impl PeerMetadata {
@@ -1653,6 +1719,7 @@ impl PeerMetadata {
             request_tx,
             version,
             client_version,
+            gossip_score: PeerGossipScore::new(),
         }
     }
 }
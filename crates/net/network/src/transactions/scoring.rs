@@ -0,0 +1,216 @@
+use super::constants::tx_scoring::*;
+use std::time::{Duration, Instant};
+
+/// Length of the window in which the inbound transaction bytes of a peer are accounted.
+const INBOUND_BANDWIDTH_WINDOW: Duration = Duration::from_secs(1);
+
+/// Configures how the usefulness of a peer's transaction gossip is scored.
+///
+/// Every peer starts out with a score of `0`. Transactions that are successfully imported into the
+/// pool increase the score, up to [`max_score`](Self::max_score). Duplicate announcements and
+/// announced hashes that aren't delivered on request decrease it. Invalid transactions aren't
+/// scored, because they're already penalized with
+/// [`ReputationChangeKind::BadTransactions`](reth_network_types::ReputationChangeKind::BadTransactions).
+/// Once the score
+/// drops to [`report_threshold`](Self::report_threshold), the peer is reported with
+/// [`ReputationChangeKind::PoorTransactionGossip`](reth_network_types::ReputationChangeKind::PoorTransactionGossip)
+/// and its score is reset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TransactionGossipScoringConfig {
+    /// Score change for each transaction that was successfully imported into the pool.
+    pub useful_transaction_weight: i64,
+    /// Score change for each hash that is announced again by the same peer.
+    pub duplicate_announcement_weight: i64,
+    /// Score change for each announced hash that the peer didn't deliver on request.
+    ///
+    /// Hashes left out of a response that reached the soft limit for the response size aren't
+    /// counted.
+    pub undelivered_hash_weight: i64,
+    /// Upper bound of the score.
+    pub max_score: i64,
+    /// Score at which the peer is reported.
+    pub report_threshold: i64,
+    /// Max number of broadcast transaction bytes per second that are accepted from a single peer,
+    /// `None` for no limit.
+    ///
+    /// The limit shrinks linearly for peers with a negative score, down to a quarter of the limit
+    /// at the [`report_threshold`](Self::report_threshold). Broadcast transactions beyond the
+    /// limit are dropped without being imported. Transactions the peer delivers on our request
+    /// aren't limited.
+    pub max_inbound_bytes_per_sec: Option<usize>,
+}
+
+impl TransactionGossipScoringConfig {
+    /// Returns the score change for the given event.
+    const fn weight(&self, event: GossipEvent) -> i64 {
+        match event {
+            GossipEvent::UsefulTransaction => self.useful_transaction_weight,
+            GossipEvent::DuplicateAnnouncement => self.duplicate_announcement_weight,
+            GossipEvent::UndeliveredHash => self.undelivered_hash_weight,
+        }
+    }
+
+    /// Returns the inbound bandwidth limit for a peer with the given score.
+    fn inbound_bytes_limit(&self, score: i64) -> Option<usize> {
+        let limit = self.max_inbound_bytes_per_sec?;
+        if score >= 0 || self.report_threshold >= 0 {
+            return Some(limit)
+        }
+        // ratio of the way to the report threshold, in [0, 1]
+        let score = score.max(self.report_threshold) as i128;
+        let reduction = limit as i128 * 3 * score / (4 * self.report_threshold as i128);
+        Some(limit - reduction as usize)
+    }
+}
+
+impl Default for TransactionGossipScoringConfig {
+    fn default() -> Self {
+        Self {
+            useful_transaction_weight: DEFAULT_USEFUL_TRANSACTION_WEIGHT,
+            duplicate_announcement_weight: DEFAULT_DUPLICATE_ANNOUNCEMENT_WEIGHT,
+            undelivered_hash_weight: DEFAULT_UNDELIVERED_HASH_WEIGHT,
+            max_score: DEFAULT_MAX_SCORE,
+            report_threshold: DEFAULT_REPORT_THRESHOLD,
+            max_inbound_bytes_per_sec: None,
+        }
+    }
+}
+
+/// Events that affect the transaction gossip score of a peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GossipEvent {
+    /// A transaction sent by the peer was imported into the pool.
+    UsefulTransaction,
+    /// The peer announced a hash that it already announced or received from us before.
+    DuplicateAnnouncement,
+    /// The peer didn't deliver a hash it announced.
+    UndeliveredHash,
+}
+
+/// Tracks the transaction gossip score and the inbound transaction bandwidth of a peer.
+#[derive(Debug, Clone)]
+pub(crate) struct PeerGossipScore {
+    /// The current score.
+    score: i64,
+    /// Start of the current bandwidth accounting window.
+    window_start: Instant,
+    /// Transaction bytes received in the current window.
+    window_bytes: usize,
+}
+
+impl PeerGossipScore {
+    /// Returns a new instance with a neutral score.
+    pub(crate) fn new() -> Self {
+        Self { score: 0, window_start: Instant::now(), window_bytes: 0 }
+    }
+
+    /// Returns the current score.
+    #[cfg(test)]
+    pub(crate) const fn score(&self) -> i64 {
+        self.score
+    }
+
+    /// Applies the given event `count` times.
+    ///
+    /// Returns `true` if the score dropped to the report threshold, in which case the peer should
+    /// be reported. The score is reset in that case.
+    pub(crate) fn record(
+        &mut self,
+        event: GossipEvent,
+        count: usize,
+        config: &TransactionGossipScoringConfig,
+    ) -> bool {
+        let change = config.weight(event).saturating_mul(count as i64);
+        self.score = self.score.saturating_add(change).min(config.max_score);
+        if self.score <= config.report_threshold {
+            self.score = 0;
+            return true
+        }
+        false
+    }
+
+    /// Accounts the given number of inbound transaction bytes.
+    ///
+    /// Returns `false` if this exceeds the peer's inbound bandwidth limit, in which case the bytes
+    /// are not accounted. The first message of each window is always accepted, so that single
+    /// messages larger than the limit don't stall the peer entirely.
+    pub(crate) fn try_consume_inbound_bytes(
+        &mut self,
+        bytes: usize,
+        config: &TransactionGossipScoringConfig,
+        now: Instant,
+    ) -> bool {
+        let Some(limit) = config.inbound_bytes_limit(self.score) else { return true };
+
+        if now.saturating_duration_since(self.window_start) >= INBOUND_BANDWIDTH_WINDOW {
+            self.window_start = now;
+            self.window_bytes = 0;
+        }
+
+        let window_bytes = self.window_bytes.saturating_add(bytes);
+        if self.window_bytes > 0 && window_bytes > limit {
+            return false
+        }
+        self.window_bytes = window_bytes;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_peer_at_threshold() {
+        let config = TransactionGossipScoringConfig::default();
+        let mut score = PeerGossipScore::new();
+
+        assert!(!score.record(GossipEvent::UsefulTransaction, 1_000, &config));
+        assert_eq!(score.score(), config.max_score);
+
+        // undelivered hashes first consume the good standing
+        assert!(!score.record(GossipEvent::UndeliveredHash, 256, &config));
+        assert_eq!(score.score(), 0);
+
+        assert!(!score.record(GossipEvent::DuplicateAnnouncement, 255, &config));
+        assert!(score.record(GossipEvent::UndeliveredHash, 1, &config));
+        assert_eq!(score.score(), 0);
+    }
+
+    #[test]
+    fn inbound_bytes_limit_shrinks_with_score() {
+        let config = TransactionGossipScoringConfig {
+            max_inbound_bytes_per_sec: Some(1_000),
+            ..Default::default()
+        };
+
+        assert_eq!(config.inbound_bytes_limit(10), Some(1_000));
+        assert_eq!(config.inbound_bytes_limit(config.report_threshold / 2), Some(625));
+        assert_eq!(config.inbound_bytes_limit(config.report_threshold), Some(250));
+        assert_eq!(
+            TransactionGossipScoringConfig::default().inbound_bytes_limit(config.report_threshold),
+            None
+        );
+    }
+
+    #[test]
+    fn throttles_inbound_bytes() {
+        let config = TransactionGossipScoringConfig {
+            max_inbound_bytes_per_sec: Some(1_000),
+            ..Default::default()
+        };
+        let mut score = PeerGossipScore::new();
+        let now = Instant::now();
+
+        // the first message of a window is always accepted
+        assert!(score.try_consume_inbound_bytes(1_500, &config, now));
+        assert!(!score.try_consume_inbound_bytes(1, &config, now));
+
+        let now = now + INBOUND_BANDWIDTH_WINDOW;
+        assert!(score.try_consume_inbound_bytes(600, &config, now));
+        assert!(score.try_consume_inbound_bytes(400, &config, now));
+        assert!(!score.try_consume_inbound_bytes(1, &config, now));
+    }
+}
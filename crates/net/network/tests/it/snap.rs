@@ -0,0 +1,65 @@
+//! Tests for the `snap` subprotocol.
+
+use std::time::Duration;
+
+use alloy_primitives::B256;
+use reth_eth_wire::snap::{AccountData, AccountRange, GetAccountRange, GetByteCodes, SlimAccount};
+use reth_network::{
+    snap::SnapProtocolHandler, snap_requests::IncomingSnapRequest, test_utils::Testnet,
+};
+use reth_network_p2p::{download::DownloadClient, snap::client::SnapClient};
+use reth_provider::test_utils::MockEthProvider;
+use tokio::sync::mpsc;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_snap_requests() {
+    reth_tracing::init_test_tracing();
+    let provider = MockEthProvider::default();
+    let mut net = Testnet::create_with(2, provider.clone()).await;
+
+    // peer0 serves requests through the request channel, peer1 doesn't serve any requests
+    let (tx, mut incoming) = mpsc::channel(1);
+    let peer0 = &mut net.peers_mut()[0];
+    let protocol0 = SnapProtocolHandler::new(peer0.network_mut().peers_handle(), Some(tx));
+    let client0 = protocol0.client();
+    peer0.add_rlpx_sub_protocol(protocol0);
+
+    let peer1 = &mut net.peers_mut()[1];
+    let protocol1 = SnapProtocolHandler::new(peer1.network_mut().peers_handle(), None);
+    let client1 = protocol1.client();
+    peer1.add_rlpx_sub_protocol(protocol1);
+
+    let handle = net.spawn();
+    handle.connect_peers().await;
+    while client0.num_connected_peers() == 0 || client1.num_connected_peers() == 0 {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    let accounts =
+        vec![AccountData { hash: B256::with_last_byte(1), body: SlimAccount::default() }];
+    let served = accounts.clone();
+    tokio::spawn(async move {
+        while let Some(req) = incoming.recv().await {
+            if let IncomingSnapRequest::GetAccountRange { request, response, .. } = req {
+                let _ = response.send(Ok(AccountRange {
+                    request_id: request.request_id,
+                    accounts: served.clone(),
+                    proof: vec![],
+                }));
+            }
+        }
+    });
+
+    let request = GetAccountRange { limit_hash: B256::repeat_byte(0xff), ..Default::default() };
+    let response = client1.get_account_range(request).await.unwrap();
+    assert_eq!(response.peer_id(), *handle.peers()[0].peer_id());
+    assert_eq!(response.1.accounts, accounts);
+
+    // requests to peer1 are answered with empty responses
+    let response = client0
+        .get_byte_codes(GetByteCodes { hashes: vec![B256::ZERO], ..Default::default() })
+        .await
+        .unwrap();
+    assert_eq!(response.peer_id(), *handle.peers()[1].peer_id());
+    assert!(response.1.codes.is_empty());
+}
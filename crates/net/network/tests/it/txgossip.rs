@@ -1,13 +1,18 @@
 //! Testing gossiping of transactions.
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use alloy_consensus::TxLegacy;
 use alloy_primitives::U256;
 use futures::StreamExt;
 use rand::thread_rng;
-use reth_network::{test_utils::Testnet, NetworkEvent, NetworkEventListenerProvider};
+use reth_eth_wire::{NewPooledTransactionHashes, NewPooledTransactionHashes68};
+use reth_network::{
+    test_utils::Testnet, transactions::TransactionGossipScoringConfig, NetworkEvent,
+    NetworkEventListenerProvider,
+};
 use reth_network_api::PeersInfo;
+use reth_network_types::{ReputationChangeKind, ReputationChangeWeights};
 use reth_primitives::{Signature, TransactionSigned};
 use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
 use reth_transaction_pool::{test_utils::TransactionGenerator, PoolTransaction, TransactionPool};
@@ -160,3 +165,66 @@ async fn test_sending_invalid_transactions() {
     // ensure txs never made it to the pool
     assert!(tx_listener.try_recv().is_err());
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_poor_transaction_gossip_penalization() {
+    reth_tracing::init_test_tracing();
+    let provider = MockEthProvider::default();
+    let net = Testnet::create_with(2, provider.clone()).await;
+    // install request handlers
+    let net = net.with_eth_pool();
+
+    let handle = net.spawn();
+
+    let peer0 = &handle.peers()[0];
+    let peer1 = &handle.peers()[1];
+
+    // connect all the peers
+    handle.connect_peers().await;
+
+    let mut peer1_tx_listener = peer1.pool().unwrap().pending_transactions_listener();
+
+    let mut gen = TransactionGenerator::new(thread_rng());
+    let tx = gen.gen_eip1559_pooled();
+    let sender = tx.sender();
+    provider.add_account(sender, ExtendedAccount::new(0, U256::from(100_000_000)));
+
+    // gossip the transaction to peer1, which marks it as seen by peer0
+    let hash = peer0.pool().unwrap().add_external_transaction(tx.clone()).await.unwrap();
+    assert_eq!(peer1_tx_listener.recv().await.unwrap(), hash);
+
+    let peer0_reputation_before =
+        peer1.peer_handle().peer_by_id(*peer0.peer_id()).await.unwrap().reputation();
+
+    // every announcement of the same hash is a duplicate, enough of them drop peer0's gossip
+    // score to the report threshold once
+    let threshold = TransactionGossipScoringConfig::default().report_threshold;
+    for _ in 0..threshold.unsigned_abs() + 16 {
+        peer0.network().send_transactions_hashes(
+            *peer1.peer_id(),
+            NewPooledTransactionHashes::Eth68(NewPooledTransactionHashes68 {
+                types: vec![tx.tx_type()],
+                sizes: vec![tx.encoded_length()],
+                hashes: vec![hash],
+            }),
+        );
+    }
+
+    let expected = peer0_reputation_before +
+        ReputationChangeWeights::default()
+            .change(ReputationChangeKind::PoorTransactionGossip)
+            .as_i32();
+    let peer0_reputation_after = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let reputation =
+                peer1.peer_handle().peer_by_id(*peer0.peer_id()).await.unwrap().reputation();
+            if reputation != peer0_reputation_before {
+                return reputation
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("peer0 was not reported");
+    assert_eq!(peer0_reputation_after, expected);
+}
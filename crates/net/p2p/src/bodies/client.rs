@@ -1,4 +1,5 @@
 use std::{
+    ops::RangeInclusive,
     pin::Pin,
     task::{ready, Context, Poll},
 };
@@ -26,6 +27,20 @@ pub trait BodiesClient: DownloadClient {
     fn get_block_bodies_with_priority(&self, hashes: Vec<B256>, priority: Priority)
         -> Self::Output;
 
+    /// Fetches the block bodies for the requested hashes with priority, hinting the range of
+    /// blocks the bodies belong to.
+    ///
+    /// The hint lets the client pick a peer that can serve all of the blocks. By default it is
+    /// ignored.
+    fn get_block_bodies_with_priority_and_range_hint(
+        &self,
+        hashes: Vec<B256>,
+        priority: Priority,
+        _range_hint: Option<RangeInclusive<u64>>,
+    ) -> Self::Output {
+        self.get_block_bodies_with_priority(hashes, priority)
+    }
+
     /// Fetches a single block body for the requested hash.
     fn get_block_body(&self, hash: B256) -> SingleBodyRequest<Self::Output> {
         self.get_block_body_with_priority(hash, Priority::Normal)
@@ -0,0 +1,29 @@
+use crate::{download::DownloadClient, error::PeerRequestResult};
+use futures::Future;
+pub use reth_eth_wire_types::snap::{
+    AccountData, AccountRange, ByteCodes, GetAccountRange, GetByteCodes, GetStorageRanges,
+    GetTrieNodes, SlimAccount, StorageData, StorageRanges, TrieNodes, TriePath,
+};
+use std::pin::Pin;
+
+/// The future type of snap requests.
+pub type SnapFut<T> = Pin<Box<dyn Future<Output = PeerRequestResult<T>> + Send + Sync>>;
+
+/// A client capable of requesting state from peers that support the `snap/1` protocol.
+///
+/// The request id of the given requests is ignored, the client assigns its own id. Responses are
+/// returned as received, verifying the proofs against the requested root is up to the caller.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait SnapClient: DownloadClient {
+    /// Requests a range of accounts of the state trie.
+    fn get_account_range(&self, request: GetAccountRange) -> SnapFut<AccountRange>;
+
+    /// Requests ranges of storage slots of a set of accounts.
+    fn get_storage_ranges(&self, request: GetStorageRanges) -> SnapFut<StorageRanges>;
+
+    /// Requests contract bytecodes by their code hash.
+    fn get_byte_codes(&self, request: GetByteCodes) -> SnapFut<ByteCodes>;
+
+    /// Requests trie nodes by path.
+    fn get_trie_nodes(&self, request: GetTrieNodes) -> SnapFut<TrieNodes>;
+}
@@ -1,5 +1,6 @@
 //! Traits used when interacting with the sync status of the network.
 
+use alloy_primitives::BlockNumber;
 use reth_primitives::Head;
 
 /// A type that provides information about whether the node is currently syncing and the network is
@@ -27,6 +28,9 @@ pub trait NetworkSyncUpdater: std::fmt::Debug + Send + Sync + 'static {
 
     /// Updates the status of the p2p node
     fn update_status(&self, head: Head);
+
+    /// Updates the earliest block the p2p node can serve, e.g. after older blocks were pruned.
+    fn update_earliest_block(&self, earliest: BlockNumber);
 }
 
 /// The state the network is currently in when it comes to synchronization.
@@ -66,4 +70,5 @@ impl SyncStateProvider for NoopSyncStateUpdater {
 impl NetworkSyncUpdater for NoopSyncStateUpdater {
     fn update_sync_state(&self, _state: SyncState) {}
     fn update_status(&self, _: Head) {}
+    fn update_earliest_block(&self, _: BlockNumber) {}
 }
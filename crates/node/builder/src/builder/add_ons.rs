@@ -1,5 +1,6 @@
 //! Node add-ons. Depend on core [`NodeComponents`](crate::NodeComponents).
 
+use reth_exex::ExExBackpressurePolicy;
 use reth_node_api::{FullNodeComponents, NodeAddOns};
 
 use crate::{exex::BoxedLaunchExEx, hooks::NodeHooks};
@@ -10,8 +11,8 @@ use crate::{exex::BoxedLaunchExEx, hooks::NodeHooks};
 pub struct AddOns<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> {
     /// Additional `NodeHooks` that are called at specific points in the node's launch lifecycle.
     pub hooks: NodeHooks<Node, AddOns>,
-    /// The `ExExs` (execution extensions) of the node.
-    pub exexs: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+    /// The `ExExs` (execution extensions) of the node with their backpressure policies.
+    pub exexs: Vec<(String, ExExBackpressurePolicy, Box<dyn BoxedLaunchExEx<Node>>)>,
     /// Additional captured addons.
     pub add_ons: AddOns,
 }
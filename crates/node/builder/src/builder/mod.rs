@@ -23,7 +23,8 @@ use reth_db_api::{
     database::Database,
     database_metrics::{DatabaseMetadata, DatabaseMetrics},
 };
-use reth_exex::ExExContext;
+use reth_evm::state_overlay::StateOverlay;
+use reth_exex::{ExExBackpressurePolicy, ExExContext};
 use reth_network::{
     transactions::TransactionsManagerConfig, NetworkBuilder, NetworkConfig, NetworkConfigBuilder,
     NetworkHandle, NetworkManager,
@@ -39,11 +40,22 @@ use reth_node_core::{
     primitives::Head,
 };
 use reth_primitives::revm_primitives::EnvKzgSettings;
-use reth_provider::{providers::BlockchainProvider, ChainSpecProvider, FullProvider};
+use reth_provider::{
+    providers::BlockchainProvider, CanonStateSubscriptions, ChainSpecProvider, FullProvider,
+    PruneCheckpointReader, StaticFileProviderFactory,
+};
 use reth_tasks::TaskExecutor;
-use reth_transaction_pool::{PoolConfig, TransactionPool};
+use reth_transaction_pool::{
+    blobstore::{DiskFileBlobStoreConfig, OpenDiskFileBlobStore},
+    maintain::{
+        backup_local_transactions_task, maintain_transaction_pool_future,
+        retain_blob_sidecars_task, snapshot_transaction_pool_task, LocalTransactionBackupConfig,
+        TransactionPoolSnapshotConfig,
+    },
+    PoolConfig, TransactionPool, TransactionPoolExt,
+};
 use secp256k1::SecretKey;
-use tracing::{info, trace, warn};
+use tracing::{debug, info, trace, warn};
 
 /// The adapter type for a reth node with the builtin provider type
 // Note: we need to hardcode this because custom components might depend on it in associated types.
@@ -505,6 +517,28 @@ where
         }
     }
 
+    /// Installs an `ExEx` (Execution Extension) in the node with the given backpressure policy.
+    ///
+    /// # Note
+    ///
+    /// The `ExEx` ID must be unique.
+    pub fn install_exex_with_policy<F, R, E>(
+        self,
+        exex_id: impl Into<String>,
+        policy: ExExBackpressurePolicy,
+        exex: F,
+    ) -> Self
+    where
+        F: FnOnce(ExExContext<NodeAdapter<T, CB::Components>>) -> R + Send + 'static,
+        R: Future<Output = eyre::Result<E>> + Send,
+        E: Future<Output = eyre::Result<()>> + Send,
+    {
+        Self {
+            builder: self.builder.install_exex_with_policy(exex_id, policy, exex),
+            task_executor: self.task_executor,
+        }
+    }
+
     /// Installs an `ExEx` (Execution Extension) in the node if the condition is true.
     ///
     /// # Note
@@ -575,17 +609,19 @@ pub struct BuilderContext<Node: FullNodeTypes> {
     pub(crate) executor: TaskExecutor,
     /// Config container
     pub(crate) config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
+    /// Out-of-band state changes shared by the components of a dev node.
+    pub(crate) state_overlay: StateOverlay,
 }
 
 impl<Node: FullNodeTypes> BuilderContext<Node> {
     /// Create a new instance of [`BuilderContext`]
-    pub const fn new(
+    pub fn new(
         head: Head,
         provider: Node::Provider,
         executor: TaskExecutor,
         config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
     ) -> Self {
-        Self { head, provider, executor, config_container }
+        Self { head, provider, executor, config_container, state_overlay: Default::default() }
     }
 
     /// Returns the configured provider to interact with the blockchain.
@@ -625,9 +661,91 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.config().dev.dev
     }
 
+    /// Returns the [`StateOverlay`] of the node.
+    ///
+    /// On dev nodes, the local miner seals the out-of-band state changes of the overlay (e.g.
+    /// `anvil_setBalance`) into the next block, so the block executor and the payload builder
+    /// must apply them.
+    pub const fn state_overlay(&self) -> &StateOverlay {
+        &self.state_overlay
+    }
+
     /// Returns the transaction pool config of the node.
+    ///
+    /// The admission policies are configured in the reth config file.
     pub fn pool_config(&self) -> PoolConfig {
-        self.config().txpool.pool_config()
+        let mut config = self.config().txpool.pool_config();
+        config.admission = self.reth_config().txpool.admission.clone();
+        config
+    }
+
+    /// Returns the config of the disk blob store of the transaction pool.
+    ///
+    /// If the pool snapshot is enabled, the blobs of the snapshot are kept across restarts.
+    pub fn blob_store_config(&self) -> DiskFileBlobStoreConfig {
+        if self.config().txpool.snapshot {
+            DiskFileBlobStoreConfig::default().with_open(OpenDiskFileBlobStore::ReIndex)
+        } else {
+            DiskFileBlobStoreConfig::default()
+        }
+    }
+
+    /// Spawns the tasks that maintain the transaction pool.
+    ///
+    /// This backs up the local transactions, writes the pool snapshot and retains the blob
+    /// sidecars of canonical blocks if enabled, and keeps the pool in sync with the canonical
+    /// chain.
+    pub fn spawn_pool_maintenance_tasks<Pool>(&self, pool: Pool)
+    where
+        Pool: TransactionPoolExt + Clone + 'static,
+    {
+        let data_dir = self.config().datadir();
+        let txpool_args = &self.config().txpool;
+
+        let transactions_backup_config =
+            LocalTransactionBackupConfig::with_local_txs_backup(data_dir.txpool_transactions());
+        let backup_pool = pool.clone();
+        self.executor.spawn_critical_with_graceful_shutdown_signal(
+            "local transactions backup task",
+            |shutdown| {
+                backup_local_transactions_task(shutdown, backup_pool, transactions_backup_config)
+            },
+        );
+
+        if txpool_args.snapshot {
+            let snapshot_config =
+                TransactionPoolSnapshotConfig::with_path(data_dir.txpool_snapshot())
+                    .with_interval(txpool_args.snapshot_interval);
+            let snapshot_pool = pool.clone();
+            self.executor.spawn_critical_with_graceful_shutdown_signal(
+                "txpool snapshot task",
+                |shutdown| snapshot_transaction_pool_task(shutdown, snapshot_pool, snapshot_config),
+            );
+        }
+
+        if txpool_args.retain_blob_sidecars {
+            self.executor.spawn_critical(
+                "txpool blob sidecars retention task",
+                retain_blob_sidecars_task(
+                    pool.clone(),
+                    self.provider().static_file_provider(),
+                    self.provider().canonical_state_stream(),
+                ),
+            );
+        }
+
+        // spawn the maintenance task
+        self.executor.spawn_critical(
+            "txpool maintenance task",
+            maintain_transaction_pool_future(
+                self.provider().clone(),
+                pool,
+                self.provider().canonical_state_stream(),
+                self.executor.clone(),
+                Default::default(),
+            ),
+        );
+        debug!(target: "reth::cli", "Spawned txpool maintenance task");
     }
 
     /// Loads `EnvKzgSettings::Default`.
@@ -655,6 +773,8 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
     ///
     /// Accepts the config for the transaction task.
     ///
+    /// The `snap/1` subprotocol is only served if enabled with `--enable-snap`.
+    ///
     /// Spawns the configured network and associated tasks and returns the [`NetworkHandle`]
     /// connected to that network.
     pub fn start_network_with<Pool>(
@@ -666,10 +786,13 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
     where
         Pool: TransactionPool + Unpin + 'static,
     {
-        let (handle, network, txpool, eth) = builder
-            .transactions(pool, tx_config)
-            .request_handler(self.provider().clone())
-            .split_with_handle();
+        let mut builder =
+            builder.transactions(pool, tx_config).request_handler(self.provider().clone());
+        if self.config().network.enable_snap {
+            let (snap, _) = builder.snap(self.provider().clone());
+            self.executor.spawn_critical("p2p snap request handler", snap);
+        }
+        let (handle, network, txpool, eth) = builder.split_with_handle();
 
         self.executor.spawn_critical("p2p txpool", txpool);
         self.executor.spawn_critical("p2p eth request handler", eth);
@@ -747,7 +870,8 @@ impl<Node: FullNodeTypes<Types: NodeTypes<ChainSpec: Hardforks>>> BuilderContext
                 default_peers_path,
             )
             .with_task_executor(Box::new(self.executor.clone()))
-            .set_head(self.head);
+            .set_head(self.head)
+            .earliest_block(self.provider.earliest_available_block()?);
 
         Ok(builder)
     }
@@ -7,7 +7,7 @@
 
 use std::{fmt, future::Future};
 
-use reth_exex::ExExContext;
+use reth_exex::{ExExBackpressurePolicy, ExExContext};
 use reth_node_api::{
     FullNodeComponents, FullNodeTypes, NodeAddOns, NodeTypes, NodeTypesWithDB, NodeTypesWithEngine,
 };
@@ -212,13 +212,32 @@ where
     /// # Note
     ///
     /// The `ExEx` ID must be unique.
-    pub fn install_exex<F, R, E>(mut self, exex_id: impl Into<String>, exex: F) -> Self
+    pub fn install_exex<F, R, E>(self, exex_id: impl Into<String>, exex: F) -> Self
     where
         F: FnOnce(ExExContext<NodeAdapter<T, CB::Components>>) -> R + Send + 'static,
         R: Future<Output = eyre::Result<E>> + Send,
         E: Future<Output = eyre::Result<()>> + Send,
     {
-        self.add_ons.exexs.push((exex_id.into(), Box::new(exex)));
+        self.install_exex_with_policy(exex_id, ExExBackpressurePolicy::default(), exex)
+    }
+
+    /// Installs an `ExEx` (Execution Extension) in the node with the given backpressure policy.
+    ///
+    /// # Note
+    ///
+    /// The `ExEx` ID must be unique.
+    pub fn install_exex_with_policy<F, R, E>(
+        mut self,
+        exex_id: impl Into<String>,
+        policy: ExExBackpressurePolicy,
+        exex: F,
+    ) -> Self
+    where
+        F: FnOnce(ExExContext<NodeAdapter<T, CB::Components>>) -> R + Send + 'static,
+        R: Future<Output = eyre::Result<E>> + Send,
+        E: Future<Output = eyre::Result<()>> + Send,
+    {
+        self.add_ons.exexs.push((exex_id.into(), policy, Box::new(exex)));
         self
     }
 
@@ -17,7 +17,7 @@ use reth_db_api::database::Database;
 use reth_db_common::init::{init_genesis, InitDatabaseError};
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
 use reth_engine_tree::tree::{InvalidBlockHook, InvalidBlockHooks, NoopInvalidBlockHook};
-use reth_evm::noop::NoopBlockExecutorProvider;
+use reth_evm::{noop::NoopBlockExecutorProvider, state_overlay::StateOverlay};
 use reth_fs_util as fs;
 use reth_invalid_block_hooks::InvalidBlockWitnessHook;
 use reth_network_p2p::headers::client::HeadersClient;
@@ -44,7 +44,7 @@ use reth_provider::{
     ProviderResult, StageCheckpointReader, StateProviderFactory, StaticFileProviderFactory,
     TreeViewer,
 };
-use reth_prune::{PruneModes, PrunerBuilder};
+use reth_prune::{PruneMode, PruneModes, PrunerBuilder, DEFAULT_BLOB_SIDECARS_RETENTION_DISTANCE};
 use reth_rpc_api::clients::EthApiClient;
 use reth_rpc_builder::config::RethRpcServerConfig;
 use reth_rpc_layer::JwtSecret;
@@ -356,14 +356,26 @@ impl<R, ChainSpec: EthChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpe
     /// Returns the configured [`PruneConfig`]
     /// Any configuration set in CLI will take precedence over those set in toml
     pub fn prune_config(&self) -> Option<PruneConfig> {
-        let Some(mut node_prune_config) = self.node_config().prune_config() else {
-            // No CLI config is set, use the toml config.
-            return self.toml_config().prune.clone();
-        };
+        let mut prune_config =
+            if let Some(mut node_prune_config) = self.node_config().prune_config() {
+                // Use the CLI configuration and merge with toml config.
+                node_prune_config.merge(self.toml_config().prune.clone());
+                Some(node_prune_config)
+            } else {
+                // No CLI config is set, use the toml config.
+                self.toml_config().prune.clone()
+            };
+
+        // Retained blob sidecars are always pruned, default to the consensus layer retention.
+        if self.node_config().txpool.retain_blob_sidecars {
+            prune_config
+                .get_or_insert_with(Default::default)
+                .segments
+                .blob_sidecars
+                .get_or_insert(PruneMode::Distance(DEFAULT_BLOB_SIDECARS_RETENTION_DISTANCE));
+        }
 
-        // Otherwise, use the CLI configuration and merge with toml config.
-        node_prune_config.merge(self.toml_config().prune.clone());
-        Some(node_prune_config)
+        prune_config
     }
 
     /// Returns the configured [`PruneModes`], returning the default if no config was available.
@@ -447,7 +459,10 @@ where
                 ))
                 .build(
                     factory.clone(),
-                    StaticFileProducer::new(factory.clone(), self.prune_modes()),
+                    StaticFileProducer::new(factory.clone(), self.prune_modes()).with_change_sets(
+                        self.toml_config().stages.index_account_history.static_file_change_sets,
+                        self.toml_config().stages.index_storage_history.static_file_change_sets,
+                    ),
                 );
 
             // Unwinds to block
@@ -730,6 +745,7 @@ where
             node_adapter,
             head,
             consensus,
+            state_overlay: builder_ctx.state_overlay().clone(),
         };
 
         let ctx = LaunchContextWith {
@@ -774,6 +790,10 @@ where
     /// Creates a new [`StaticFileProducer`] with the attached database.
     pub fn static_file_producer(&self) -> StaticFileProducer<ProviderFactory<T::Types>> {
         StaticFileProducer::new(self.provider_factory().clone(), self.prune_modes())
+            .with_change_sets(
+                self.toml_config().stages.index_account_history.static_file_change_sets,
+                self.toml_config().stages.index_storage_history.static_file_change_sets,
+            )
     }
 
     /// Returns the current head block.
@@ -866,6 +886,11 @@ where
         self.right().consensus.clone()
     }
 
+    /// Returns the [`StateOverlay`] shared with the components.
+    pub const fn state_overlay(&self) -> &StateOverlay {
+        &self.right().state_overlay
+    }
+
     /// Returns the metrics sender.
     pub fn sync_metrics_tx(&self) -> UnboundedSender<MetricEvent> {
         self.right().db_provider_container.metrics_sender.clone()
@@ -1048,6 +1073,7 @@ where
     node_adapter: NodeAdapter<T, CB::Components>,
     head: Head,
     consensus: Arc<dyn Consensus>,
+    state_overlay: StateOverlay,
 }
 
 #[cfg(test)]
@@ -1088,6 +1114,8 @@ mod tests {
                     storage_history_full: false,
                     storage_history_distance: None,
                     storage_history_before: None,
+                    blob_sidecars_distance: None,
+                    blob_sidecars_before: None,
                     receipts_log_filter: vec![],
                 },
                 ..NodeConfig::test()
@@ -17,9 +17,10 @@ use reth_engine_tree::{
 use reth_engine_util::EngineMessageStreamExt;
 use reth_exex::ExExManagerHandle;
 use reth_network::{NetworkSyncUpdater, SyncState};
-use reth_network_api::{BlockDownloaderProvider, NetworkEventListenerProvider};
+use reth_network_api::{BlockDownloaderProvider, NetworkEventListenerProvider, SnapClientProvider};
 use reth_node_api::{
-    BuiltPayload, FullNodeTypes, NodeTypesWithEngine, PayloadAttributesBuilder, PayloadTypes,
+    BuiltPayload, FullNodeComponents, FullNodeTypes, NodeTypesWithEngine, PayloadAttributesBuilder,
+    PayloadTypes,
 };
 use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
@@ -29,10 +30,16 @@ use reth_node_core::{
 use reth_node_events::{cl::ConsensusLayerHealthEvents, node};
 use reth_payload_primitives::PayloadBuilder;
 use reth_primitives::EthereumHardforks;
-use reth_provider::providers::{BlockchainProvider2, ProviderNodeTypes};
+use reth_provider::{
+    providers::{BlockchainProvider2, ProviderNodeTypes},
+    PruneCheckpointReader,
+};
+use reth_rpc::{AnvilApi, ImpersonatedAccounts};
+use reth_rpc_api::{eth::helpers::EthApiSpec, AnvilApiServer};
+use reth_rpc_builder::RethRpcModule;
 use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
-use reth_tracing::tracing::{debug, error, info};
+use reth_tracing::tracing::{debug, error, info, warn};
 use std::sync::Arc;
 use tokio::sync::{mpsc::unbounded_channel, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
@@ -40,8 +47,8 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 use crate::{
     common::{Attached, LaunchContextWith, WithConfigs},
     hooks::NodeHooks,
-    rpc::{RethRpcAddOns, RpcHandle},
-    setup::build_networked_pipeline,
+    rpc::{RethRpcAddOns, RpcContext, RpcHandle},
+    setup::{build_networked_pipeline, build_persistence_index_stages},
     AddOns, AddOnsContext, ExExLauncher, FullNode, LaunchContext, LaunchNode, NodeAdapter,
     NodeBuilderWithComponents, NodeComponents, NodeComponentsBuilder, NodeHandle, NodeTypesAdapter,
 };
@@ -73,7 +80,7 @@ where
     Types: ProviderNodeTypes + NodeTypesWithEngine,
     T: FullNodeTypes<Types = Types, Provider = BlockchainProvider2<Types>>,
     CB: NodeComponentsBuilder<T>,
-    AO: RethRpcAddOns<NodeAdapter<T, CB::Components>>,
+    AO: RethRpcAddOns<NodeAdapter<T, CB::Components>, EthApi: EthApiSpec + 'static>,
     LocalPayloadAttributesBuilder<Types::ChainSpec>: PayloadAttributesBuilder<
         <<Types as NodeTypesWithEngine>::Engine as PayloadTypes>::PayloadAttributes,
     >,
@@ -88,7 +95,7 @@ where
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             components_builder,
-            add_ons: AddOns { hooks, exexs: installed_exex, add_ons },
+            add_ons: AddOns { hooks, exexs: installed_exex, mut add_ons },
             config,
         } = target;
         let NodeHooks { on_component_initialized, on_node_started, .. } = hooks;
@@ -146,6 +153,7 @@ where
 
         // create pipeline
         let network_client = ctx.components().network().fetch_client().await?;
+        let snap_client = ctx.components().network().snap_client().await?;
         let (consensus_engine_tx, consensus_engine_rx) = unbounded_channel();
 
         let node_config = ctx.node_config();
@@ -189,7 +197,9 @@ where
             max_block,
             static_file_producer,
             ctx.components().block_executor().clone(),
+            ctx.components().evm_config().clone(),
             pipeline_exex_handle,
+            snap_client,
         )?;
 
         // The new engine writes directly to static files. This ensures that they're up to the tip.
@@ -207,6 +217,11 @@ where
         let pruner_events = pruner.events();
         info!(target: "reth::cli", prune_config=?ctx.prune_config().unwrap_or_default(), "Pruner initialized");
 
+        let index_stages = build_persistence_index_stages(
+            &ctx.toml_config().stages,
+            ctx.components().evm_config().clone(),
+        );
+
         let mut engine_service = if ctx.is_dev() {
             let mining_mode = if let Some(block_time) = ctx.node_config().dev.block_time {
                 MiningMode::interval(block_time)
@@ -227,6 +242,8 @@ where
                 Box::pin(consensus_engine_stream),
                 mining_mode,
                 LocalPayloadAttributesBuilder::new(ctx.chain_spec()),
+                ctx.state_overlay().clone(),
+                index_stages,
             );
 
             Either::Left(eth_service)
@@ -246,11 +263,38 @@ where
                 engine_tree_config,
                 ctx.invalid_block_hook()?,
                 ctx.sync_metrics_tx(),
+                index_stages,
             );
 
             Either::Right(eth_service)
         };
 
+        // the anvil namespace is only served by nodes running the local dev engine
+        if let Either::Left(eth_service) = &engine_service {
+            let local_miner = eth_service.miner_handle();
+            let state_overlay = ctx.state_overlay().clone();
+            let hooks = add_ons.hooks_mut();
+            let extend_rpc_modules = std::mem::replace(&mut hooks.extend_rpc_modules, Box::new(()));
+            hooks.set_extend_rpc_modules(
+                move |ctx: RpcContext<'_, NodeAdapter<T, CB::Components>, AO::EthApi>| {
+                    // impersonated accounts are served to `eth_sendTransaction` as a signer
+                    let impersonated = ImpersonatedAccounts::default();
+                    ctx.registry.eth_api().signers().write().push(Box::new(impersonated.clone()));
+                    let anvil = AnvilApi::new(
+                        ctx.node.provider().clone(),
+                        ctx.node.pool().clone(),
+                        local_miner,
+                        state_overlay,
+                        impersonated,
+                        Box::new(ctx.node.task_executor().clone()),
+                    );
+                    ctx.modules
+                        .merge_if_module_configured(RethRpcModule::Anvil, anvil.into_rpc())?;
+                    extend_rpc_modules.extend_rpc_modules(ctx)
+                },
+            );
+        }
+
         let event_sender = EventSender::default();
 
         let beacon_engine_handle =
@@ -328,6 +372,7 @@ where
         // Run consensus engine to completion
         let initial_target = ctx.initial_backfill_target()?;
         let network_handle = ctx.components().network().clone();
+        let blockchain_db = ctx.blockchain_db().clone();
         let mut built_payloads = ctx
             .components()
             .payload_builder()
@@ -394,6 +439,12 @@ where
                                             .unwrap_or_default(),
                                     };
                                     network_handle.update_status(head_block);
+
+                                    // the pruner may have removed older blocks in the meantime
+                                    match blockchain_db.earliest_available_block() {
+                                        Ok(earliest) => network_handle.update_earliest_block(earliest),
+                                        Err(err) => warn!(target: "reth::cli", %err, "Failed to read the earliest available block"),
+                                    }
                                 }
                                 event_sender.notify(ev);
                             }
@@ -6,8 +6,8 @@ use futures::future;
 use reth_chain_state::ForkChoiceSubscriptions;
 use reth_chainspec::EthChainSpec;
 use reth_exex::{
-    ExExContext, ExExHandle, ExExManager, ExExManagerHandle, ExExNotificationSource, Wal,
-    DEFAULT_EXEX_MANAGER_CAPACITY,
+    ExExBackpressurePolicy, ExExContext, ExExHandle, ExExManager, ExExManagerHandle,
+    ExExNotificationSource, Wal, DEFAULT_EXEX_MANAGER_CAPACITY,
 };
 use reth_node_api::{FullNodeComponents, NodeTypes};
 use reth_primitives::Head;
@@ -20,7 +20,7 @@ use crate::{common::WithConfigs, exex::BoxedLaunchExEx};
 /// Can launch execution extensions.
 pub struct ExExLauncher<Node: FullNodeComponents> {
     head: Head,
-    extensions: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+    extensions: Vec<(String, ExExBackpressurePolicy, Box<dyn BoxedLaunchExEx<Node>>)>,
     components: Node,
     config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
 }
@@ -30,7 +30,7 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
     pub const fn new(
         head: Head,
         components: Node,
-        extensions: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+        extensions: Vec<(String, ExExBackpressurePolicy, Box<dyn BoxedLaunchExEx<Node>>)>,
         config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
     ) -> Self {
         Self { head, extensions, components, config_container }
@@ -61,7 +61,7 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
         let mut exex_handles = Vec::with_capacity(extensions.len());
         let mut exexes = Vec::with_capacity(extensions.len());
 
-        for (id, exex) in extensions {
+        for (id, policy, exex) in extensions {
             // create a new exex handle
             let (handle, events, notifications) = ExExHandle::new(
                 id.clone(),
@@ -70,7 +70,7 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
                 components.block_executor().clone(),
                 exex_wal.handle(),
             );
-            exex_handles.push(handle);
+            exex_handles.push(handle.with_backpressure_policy(policy));
 
             // create the launch context for the exex
             let context = ExExContext {
@@ -146,7 +146,7 @@ impl<Node: FullNodeComponents> Debug for ExExLauncher<Node> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ExExLauncher")
             .field("head", &self.head)
-            .field("extensions", &self.extensions.iter().map(|(id, _)| id).collect::<Vec<_>>())
+            .field("extensions", &self.extensions.iter().map(|(id, _, _)| id).collect::<Vec<_>>())
             .field("components", &"...")
             .field("config_container", &self.config_container)
             .finish()
@@ -22,7 +22,7 @@ use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_consensus_debug_client::{DebugConsensusClient, EtherscanBlockProvider, RpcBlockProvider};
 use reth_engine_util::EngineMessageStreamExt;
 use reth_exex::ExExManagerHandle;
-use reth_network::{BlockDownloaderProvider, NetworkEventListenerProvider};
+use reth_network::{BlockDownloaderProvider, NetworkEventListenerProvider, SnapClientProvider};
 use reth_node_api::{
     AddOnsContext, FullNodeComponents, FullNodeTypes, NodeTypesWithDB, NodeTypesWithEngine,
 };
@@ -178,6 +178,7 @@ where
 
         // create pipeline
         let network_client = ctx.components().network().fetch_client().await?;
+        let snap_client = ctx.components().network().snap_client().await?;
         let (consensus_engine_tx, consensus_engine_rx) = unbounded_channel();
 
         let node_config = ctx.node_config();
@@ -243,7 +244,9 @@ where
                 max_block,
                 static_file_producer,
                 ctx.components().block_executor().clone(),
+                ctx.components().evm_config().clone(),
                 pipeline_exex_handle,
+                None::<reth_network::snap::SnapFetchClient>,
             )?;
 
             let pipeline_events = pipeline.events();
@@ -264,7 +267,9 @@ where
                 max_block,
                 static_file_producer,
                 ctx.components().block_executor().clone(),
+                ctx.components().evm_config().clone(),
                 pipeline_exex_handle,
+                snap_client,
             )?;
 
             (pipeline, Either::Right(network_client.clone()))
@@ -19,12 +19,12 @@ use reth_node_core::{
 };
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_provider::providers::ProviderNodeTypes;
-use reth_rpc::EthApi;
-use reth_rpc_api::eth::helpers::AddDevSigners;
+use reth_rpc::{AdminSnapshot, EthApi};
+use reth_rpc_api::{eth::helpers::AddDevSigners, AdminSnapshotApiServer};
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
-    RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
+    RethRpcModule, RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
 };
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
 use reth_tasks::TaskExecutor;
@@ -196,6 +196,7 @@ pub struct RpcRegistry<Node: FullNodeComponents, EthApi: EthApiTypes> {
         Node::Provider,
         EthApi,
         Node::Executor,
+        Node::Consensus,
     >,
 }
 
@@ -212,6 +213,7 @@ where
         Node::Provider,
         EthApi,
         Node::Executor,
+        Node::Consensus,
     >;
 
     fn deref(&self) -> &Self::Target {
@@ -440,8 +442,23 @@ where
             .with_executor(node.task_executor().clone())
             .with_evm_config(node.evm_config().clone())
             .with_block_executor(node.block_executor().clone())
+            .with_consensus(node.consensus().clone())
             .build_with_auth_server(module_config, engine_api, eth_api_builder);
 
+        // taking snapshots requires direct access to the database, which isn't available to the
+        // registry, so the endpoint is added to the admin namespace here
+        if let Some(snapshot_dir) = config.rpc.rpc_snapshot_dir.clone() {
+            modules.merge_if_module_configured(
+                RethRpcModule::Admin,
+                AdminSnapshot::new(
+                    node.provider().clone(),
+                    snapshot_dir,
+                    Box::new(node.task_executor().clone()),
+                )
+                .into_rpc(),
+            )?;
+        }
+
         // in dev mode we generate 20 random dev-signer accounts
         if config.dev.dev {
             registry.eth_api().with_dev_accounts();
@@ -9,13 +9,20 @@ use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
     headers::reverse_headers::ReverseHeadersDownloaderBuilder,
 };
-use reth_evm::execute::BlockExecutorProvider;
+use reth_engine_tree::persistence::IndexStage;
+use reth_evm::{execute::BlockExecutorProvider, ConfigureEvm};
 use reth_exex::ExExManagerHandle;
 use reth_network_p2p::{
     bodies::downloader::BodyDownloader, headers::downloader::HeaderDownloader, BlockClient,
+    SnapClient,
 };
+use reth_primitives::Header;
 use reth_provider::{providers::ProviderNodeTypes, ProviderFactory};
-use reth_stages::{prelude::DefaultStages, stages::ExecutionStage, Pipeline, StageSet};
+use reth_stages::{
+    prelude::DefaultStages,
+    stages::{ExecutionStage, IndexAddressTransactionsStage, SnapStateStage},
+    Pipeline, StageId, StageSet,
+};
 use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::debug;
@@ -23,7 +30,7 @@ use tokio::sync::watch;
 
 /// Constructs a [Pipeline] that's wired to the network
 #[allow(clippy::too_many_arguments)]
-pub fn build_networked_pipeline<N, Client, Executor>(
+pub fn build_networked_pipeline<N, Client, Snap, Executor, EvmConfig>(
     config: &StageConfig,
     client: Client,
     consensus: Arc<dyn Consensus>,
@@ -34,12 +41,16 @@ pub fn build_networked_pipeline<N, Client, Executor>(
     max_block: Option<BlockNumber>,
     static_file_producer: StaticFileProducer<ProviderFactory<N>>,
     executor: Executor,
+    evm_config: EvmConfig,
     exex_manager_handle: ExExManagerHandle,
+    snap_client: Option<Snap>,
 ) -> eyre::Result<Pipeline<N>>
 where
     N: ProviderNodeTypes,
     Client: BlockClient + 'static,
+    Snap: SnapClient + Clone + 'static,
     Executor: BlockExecutorProvider,
+    EvmConfig: ConfigureEvm<Header = Header>,
 {
     // building network downloaders using the fetch client
     let header_downloader = ReverseHeadersDownloaderBuilder::new(config.headers)
@@ -61,7 +72,9 @@ where
         prune_config,
         static_file_producer,
         executor,
+        evm_config,
         exex_manager_handle,
+        snap_client,
     )?;
 
     Ok(pipeline)
@@ -69,7 +82,7 @@ where
 
 /// Builds the [Pipeline] with the given [`ProviderFactory`] and downloaders.
 #[allow(clippy::too_many_arguments)]
-pub fn build_pipeline<N, H, B, Executor>(
+pub fn build_pipeline<N, H, B, Snap, Executor, EvmConfig>(
     provider_factory: ProviderFactory<N>,
     stage_config: &StageConfig,
     header_downloader: H,
@@ -80,13 +93,17 @@ pub fn build_pipeline<N, H, B, Executor>(
     prune_config: Option<PruneConfig>,
     static_file_producer: StaticFileProducer<ProviderFactory<N>>,
     executor: Executor,
+    evm_config: EvmConfig,
     exex_manager_handle: ExExManagerHandle,
+    snap_client: Option<Snap>,
 ) -> eyre::Result<Pipeline<N>>
 where
     N: ProviderNodeTypes,
     H: HeaderDownloader + 'static,
     B: BodyDownloader + 'static,
+    Snap: SnapClient + Clone + 'static,
     Executor: BlockExecutorProvider,
+    EvmConfig: ConfigureEvm<Header = Header>,
 {
     let mut builder = Pipeline::<N>::builder();
 
@@ -99,29 +116,77 @@ where
 
     let prune_modes = prune_config.map(|prune| prune.segments).unwrap_or_default();
 
+    let mut stages = DefaultStages::new(
+        provider_factory.clone(),
+        tip_rx,
+        Arc::clone(&consensus),
+        header_downloader,
+        body_downloader,
+        executor.clone(),
+        stage_config.clone(),
+        prune_modes.clone(),
+    )
+    .set(ExecutionStage::new(
+        executor,
+        stage_config.execution.into(),
+        stage_config.execution_external_clean_threshold(),
+        prune_modes,
+        exex_manager_handle,
+    ));
+
+    if stage_config.index_address_transactions.enabled {
+        debug!(target: "reth::cli", "Enabling address transactions index stage");
+        stages = stages.add_after(
+            IndexAddressTransactionsStage::new(
+                evm_config,
+                stage_config.index_address_transactions,
+                stage_config.etl.clone(),
+            ),
+            StageId::IndexAccountHistory,
+        );
+    }
+
+    if stage_config.snap_state.enabled {
+        if let Some(snap_client) = snap_client {
+            debug!(target: "reth::cli", "Enabling snap state stage");
+            stages = stages.add_before(
+                SnapStateStage::new(snap_client, stage_config.snap_state),
+                StageId::SenderRecovery,
+            );
+        } else {
+            debug!(target: "reth::cli", "Snap state stage is enabled but the snap protocol is not available");
+        }
+    }
+
     let pipeline = builder
         .with_tip_sender(tip_tx)
         .with_metrics_tx(metrics_tx)
-        .add_stages(
-            DefaultStages::new(
-                provider_factory.clone(),
-                tip_rx,
-                Arc::clone(&consensus),
-                header_downloader,
-                body_downloader,
-                executor.clone(),
-                stage_config.clone(),
-                prune_modes.clone(),
-            )
-            .set(ExecutionStage::new(
-                executor,
-                stage_config.execution.into(),
-                stage_config.execution_external_clean_threshold(),
-                prune_modes,
-                exex_manager_handle,
-            )),
-        )
+        .add_stages(stages)
         .build(provider_factory, static_file_producer);
 
     Ok(pipeline)
 }
+
+/// Builds the index stages that are run by the engine on the blocks it persists, so that the
+/// indices keep up with the chain after the pipeline finished.
+pub fn build_persistence_index_stages<N, EvmConfig>(
+    stage_config: &StageConfig,
+    evm_config: EvmConfig,
+) -> Vec<IndexStage<N>>
+where
+    N: ProviderNodeTypes,
+    EvmConfig: ConfigureEvm<Header = Header>,
+{
+    let mut stages: Vec<IndexStage<N>> = Vec::new();
+
+    if stage_config.index_address_transactions.enabled {
+        debug!(target: "reth::cli", "Enabling address transactions index on persistence");
+        stages.push(Box::new(IndexAddressTransactionsStage::new(
+            evm_config,
+            stage_config.index_address_transactions,
+            stage_config.etl.clone(),
+        )));
+    }
+
+    stages
+}
@@ -26,7 +26,7 @@ use reth_network::{
                 DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS, DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             },
         },
-        TransactionFetcherConfig, TransactionsManagerConfig,
+        TransactionFetcherConfig, TransactionGossipScoringConfig, TransactionsManagerConfig,
         DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
         SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
     },
@@ -149,6 +149,18 @@ pub struct NetworkArgs {
     #[arg(long = "max-tx-pending-fetch", value_name = "COUNT", default_value_t = DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH, verbatim_doc_comment)]
     pub max_capacity_cache_txns_pending_fetch: u32,
 
+    /// Max number of transaction bytes per second accepted from a single peer.
+    ///
+    /// The limit shrinks for peers whose transaction gossip scores poorly. Unlimited by default.
+    #[arg(long = "max-tx-bytes-per-peer", value_name = "BYTES", verbatim_doc_comment)]
+    pub max_inbound_tx_bytes_per_peer: Option<usize>,
+
+    /// Serve the state of recent blocks to peers over the `snap/1` subprotocol.
+    ///
+    /// Disabled by default.
+    #[arg(long = "enable-snap")]
+    pub enable_snap: bool,
+
     /// Name of network interface used to communicate with peers.
     ///
     /// If flag is set, but no value is passed, the default interface for docker `eth0` is tried.
@@ -227,6 +239,10 @@ impl NetworkArgs {
             ),
             max_transactions_seen_by_peer_history: self.max_seen_tx_history,
             propagation_mode: Default::default(),
+            scoring: TransactionGossipScoringConfig {
+                max_inbound_bytes_per_sec: self.max_inbound_tx_bytes_per_peer,
+                ..Default::default()
+            },
         };
 
         // Configure basic network stack
@@ -331,6 +347,8 @@ impl Default for NetworkArgs {
             max_pending_pool_imports: DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS,
             max_seen_tx_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             max_capacity_cache_txns_pending_fetch: DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH,
+            max_inbound_tx_bytes_per_peer: None,
+            enable_snap: false,
             net_if: None,
         }
     }
@@ -591,6 +609,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_max_tx_bytes_per_peer_args() {
+        let args =
+            CommandParser::<NetworkArgs>::parse_from(["reth", "--max-tx-bytes-per-peer", "65536"])
+                .args;
+
+        assert_eq!(args.max_inbound_tx_bytes_per_peer, Some(65536));
+    }
+
     #[cfg(not(feature = "optimism"))]
     #[test]
     fn network_args_default_sanity_test() {
@@ -82,6 +82,24 @@ pub struct PruningArgs {
     #[arg(long = "prune.storagehistory.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["storage_history_full", "storage_history_distance"])]
     pub storage_history_before: Option<BlockNumber>,
 
+    // Blob Sidecars
+    /// Prune retained blob sidecars before the `head-N` block number. In other words, keep last N
+    /// + 1 blocks.
+    #[arg(
+        long = "prune.blobsidecars.distance",
+        value_name = "BLOCKS",
+        conflicts_with = "blob_sidecars_before"
+    )]
+    pub blob_sidecars_distance: Option<u64>,
+    /// Prune retained blob sidecars before the specified block number. The specified block number
+    /// is not pruned.
+    #[arg(
+        long = "prune.blobsidecars.before",
+        value_name = "BLOCK_NUMBER",
+        conflicts_with = "blob_sidecars_distance"
+    )]
+    pub blob_sidecars_before: Option<BlockNumber>,
+
     // Receipts Log Filter
     /// Configure receipts log filter. Format:
     /// <`address`>:<`prune_mode`>[,<`address`>:<`prune_mode`>...] Where <`prune_mode`> can be
@@ -118,6 +136,7 @@ impl PruningArgs {
                             .into_iter()
                             .collect(),
                     ),
+                    blob_sidecars: None,
                 },
             }
         }
@@ -141,6 +160,9 @@ impl PruningArgs {
         if let Some(mode) = self.storage_history_prune_mode() {
             config.segments.storage_history = Some(mode);
         }
+        if let Some(mode) = self.blob_sidecars_prune_mode() {
+            config.segments.blob_sidecars = Some(mode);
+        }
 
         Some(config)
     }
@@ -203,6 +225,16 @@ impl PruningArgs {
             None
         }
     }
+
+    const fn blob_sidecars_prune_mode(&self) -> Option<PruneMode> {
+        if let Some(distance) = self.blob_sidecars_distance {
+            Some(PruneMode::Distance(distance))
+        } else if let Some(block_number) = self.blob_sidecars_before {
+            Some(PruneMode::Before(block_number))
+        } else {
+            None
+        }
+    }
 }
 
 pub(crate) fn parse_receipts_log_filter(
@@ -183,6 +183,12 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.proof-permits", alias = "rpc-proof-permits", value_name = "COUNT", default_value_t = constants::DEFAULT_PROOF_PERMITS)]
     pub rpc_proof_permits: usize,
 
+    /// Directory that `admin_snapshot` writes snapshots to.
+    ///
+    /// `admin_snapshot` is only available if this is set.
+    #[arg(long = "rpc.snapshot-dir", value_name = "PATH")]
+    pub rpc_snapshot_dir: Option<PathBuf>,
+
     /// State cache configuration.
     #[command(flatten)]
     pub rpc_state_cache: RpcStateCacheArgs,
@@ -318,6 +324,7 @@ impl Default for RpcServerArgs {
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
             rpc_proof_permits: constants::DEFAULT_PROOF_PERMITS,
+            rpc_snapshot_dir: None,
         }
     }
 }
@@ -3,16 +3,23 @@
 use crate::cli::config::RethTransactionPoolConfig;
 use alloy_primitives::Address;
 use clap::Args;
+use reth_cli_util::parse_duration_from_secs;
 use reth_primitives::constants::{ETHEREUM_BLOCK_GAS_LIMIT, MIN_PROTOCOL_BASE_FEE};
 use reth_transaction_pool::{
     blobstore::disk::DEFAULT_MAX_CACHED_BLOBS,
-    pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
+    pool::{
+        TransactionJournalConfig, DEFAULT_TXPOOL_JOURNAL_MAX_EVENTS_PER_TRANSACTION,
+        NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE,
+    },
     validate::DEFAULT_MAX_TX_INPUT_BYTES,
-    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-    DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, REPLACE_BLOB_PRICE_BUMP,
-    TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+    BlobPoolConfig, LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit,
+    TransactionOrderingKind, DEFAULT_PRICE_BUMP, DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS,
+    REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+    TXPOOL_MAX_BLOB_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
     TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
+use std::time::Duration;
+
 /// Parameters for debugging purposes
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "TxPool")]
@@ -58,6 +65,17 @@ pub struct TxPoolArgs {
     #[arg(long = "blobpool.pricebump", default_value_t = REPLACE_BLOB_PRICE_BUMP)]
     pub blob_transaction_price_bump: u128,
 
+    /// Max number of blob transactions a single sender can have in the pool.
+    ///
+    /// Only has an effect if lower than `--txpool.max-account-slots`.
+    #[arg(long = "blobpool.max-account-slots", default_value_t = TXPOOL_MAX_BLOB_ACCOUNT_SLOTS_PER_SENDER)]
+    pub blob_max_account_slots: usize,
+
+    /// Evict parked blob transactions whose fee caps are more than this many (logarithmic) fee
+    /// jumps away from the current fees
+    #[arg(long = "blobpool.max-fee-jumps", value_name = "JUMPS")]
+    pub blob_max_fee_jumps: Option<u64>,
+
     /// Max size in bytes of a single transaction allowed to enter the pool
     #[arg(long = "txpool.max-tx-input-bytes", alias = "txpool.max_tx_input_bytes", default_value_t = DEFAULT_MAX_TX_INPUT_BYTES)]
     pub max_tx_input_bytes: usize,
@@ -86,6 +104,42 @@ pub struct TxPoolArgs {
     /// Maximum number of new transactions to buffer
     #[arg(long = "txpool.max-new-txns", alias = "txpool.max_new_txns", default_value_t = NEW_TX_LISTENER_BUFFER_SIZE)]
     pub new_tx_listener_buffer_size: usize,
+
+    /// Persist a snapshot of all pooled transactions and restore it on startup.
+    #[arg(long = "txpool.snapshot")]
+    pub snapshot: bool,
+
+    /// Interval in seconds at which the transaction pool snapshot is written.
+    #[arg(long = "txpool.snapshot-interval", value_parser = parse_duration_from_secs, default_value = "60", value_name = "SECONDS")]
+    pub snapshot_interval: Duration,
+
+    /// Max number of transactions to keep lifecycle history for.
+    ///
+    /// The journal is disabled by default (`0`).
+    #[arg(long = "txpool.journal-size", default_value_t = 0)]
+    pub journal_size: usize,
+
+    /// Retain the blob sidecars of canonical blocks in static files, instead of deleting them once
+    /// their block is finalized.
+    ///
+    /// Retained sidecars are pruned according to `--prune.blobsidecars.*`, which defaults to the
+    /// last 4096 epochs.
+    #[arg(long = "txpool.retain-blob-sidecars")]
+    pub retain_blob_sidecars: bool,
+
+    /// Accept blob sidecars with cell proofs (EIP-7594) and store all blob sidecars with cell
+    /// proofs, so that they can be served by `engine_getBlobsV2`.
+    ///
+    /// Cell proofs are computed for incoming sidecars with blob proofs.
+    #[arg(long = "txpool.cell-proofs")]
+    pub cell_proofs: bool,
+
+    /// How pending transactions are ordered when building blocks.
+    ///
+    /// One of `coinbase-tip` (by effective tip), `calldata-tip` (by effective tip per calldata
+    /// byte) or `arrival-time` (first come, first served).
+    #[arg(long = "txpool.ordering", value_name = "ORDERING", default_value_t = TransactionOrderingKind::CoinbaseTip)]
+    pub ordering: TransactionOrderingKind,
 }
 
 impl Default for TxPoolArgs {
@@ -102,6 +156,8 @@ impl Default for TxPoolArgs {
             minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE,
             gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
             blob_transaction_price_bump: REPLACE_BLOB_PRICE_BUMP,
+            blob_max_account_slots: TXPOOL_MAX_BLOB_ACCOUNT_SLOTS_PER_SENDER,
+            blob_max_fee_jumps: None,
             max_tx_input_bytes: DEFAULT_MAX_TX_INPUT_BYTES,
             max_cached_entries: DEFAULT_MAX_CACHED_BLOBS,
             no_locals: false,
@@ -110,6 +166,12 @@ impl Default for TxPoolArgs {
             additional_validation_tasks: DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS,
             pending_tx_listener_buffer_size: PENDING_TX_LISTENER_BUFFER_SIZE,
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
+            snapshot: false,
+            snapshot_interval: Duration::from_secs(60),
+            journal_size: 0,
+            retain_blob_sidecars: false,
+            cell_proofs: false,
+            ordering: TransactionOrderingKind::default(),
         }
     }
 }
@@ -144,10 +206,19 @@ impl RethTransactionPoolConfig for TxPoolArgs {
                 default_price_bump: self.price_bump,
                 replace_blob_tx_price_bump: self.blob_transaction_price_bump,
             },
+            blob_pool: BlobPoolConfig {
+                max_account_slots: self.blob_max_account_slots,
+                max_fee_jumps: self.blob_max_fee_jumps,
+            },
             minimal_protocol_basefee: self.minimal_protocol_basefee,
             gas_limit: self.gas_limit,
             pending_tx_listener_buffer_size: self.pending_tx_listener_buffer_size,
             new_tx_listener_buffer_size: self.new_tx_listener_buffer_size,
+            admission: Default::default(),
+            journal: TransactionJournalConfig {
+                max_transactions: self.journal_size,
+                max_events_per_transaction: DEFAULT_TXPOOL_JOURNAL_MAX_EVENTS_PER_TRANSACTION,
+            },
         }
     }
 }
@@ -181,4 +252,42 @@ mod tests {
         .args;
         assert_eq!(args.locals, vec![Address::ZERO]);
     }
+
+    #[test]
+    fn txpool_parse_snapshot() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.snapshot",
+            "--txpool.snapshot-interval",
+            "30",
+        ])
+        .args;
+        assert!(args.snapshot);
+        assert_eq!(args.snapshot_interval, Duration::from_secs(30));
+    }
+
+    #[test]
+    fn txpool_parse_ordering() {
+        let args =
+            CommandParser::<TxPoolArgs>::parse_from(["reth", "--txpool.ordering", "arrival-time"])
+                .args;
+        assert_eq!(args.ordering, TransactionOrderingKind::ArrivalTime);
+
+        assert!(CommandParser::<TxPoolArgs>::try_parse_from(["reth", "--txpool.ordering", "tip"])
+            .is_err());
+    }
+
+    #[test]
+    fn txpool_parse_blob_pool() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--blobpool.max-account-slots",
+            "4",
+            "--blobpool.max-fee-jumps",
+            "3",
+        ])
+        .args;
+        let config = args.pool_config().blob_pool;
+        assert_eq!(config, BlobPoolConfig { max_account_slots: 4, max_fee_jumps: Some(3) });
+    }
 }
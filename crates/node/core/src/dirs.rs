@@ -330,6 +330,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the snapshot file of the whole transaction pool
+    ///
+    /// `<DIR>/<CHAIN_ID>/txpool-snapshot.rlp`
+    pub fn txpool_snapshot(&self) -> PathBuf {
+        self.data_dir().join("txpool-snapshot.rlp")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
@@ -11,6 +11,7 @@ use reth_transaction_pool::{
     TransactionOrigin, TransactionValidationOutcome, TransactionValidationTaskExecutor,
     TransactionValidator,
 };
+use revm::primitives::EnvKzgSettings;
 use std::sync::{
     atomic::{AtomicU64, Ordering},
     Arc,
@@ -216,6 +217,10 @@ where
         self.inner.on_new_head_block(new_tip_block);
         self.update_l1_block_info(&new_tip_block.clone().unseal());
     }
+
+    fn kzg_settings(&self) -> EnvKzgSettings {
+        self.inner.kzg_settings()
+    }
 }
 
 /// Tracks additional infos for the current block.
@@ -187,10 +187,17 @@ where
     let mut executed_txs = Vec::with_capacity(attributes.transactions.len());
     let mut executed_senders = Vec::with_capacity(attributes.transactions.len());
 
-    let mut best_txs = pool.best_transactions_with_attributes(BestTransactionsAttributes::new(
-        base_fee,
-        initialized_block_env.get_blob_gasprice().map(|gasprice| gasprice as u64),
-    ));
+    let mut best_txs = pool.best_transactions_with_attributes(
+        BestTransactionsAttributes::new(
+            base_fee,
+            initialized_block_env.get_blob_gasprice().map(|gasprice| gasprice as u64),
+        )
+        .with_block(
+            parent_block.hash(),
+            initialized_block_env.number.to::<u64>(),
+            initialized_block_env.timestamp.to::<u64>(),
+        ),
+    );
 
     let mut total_fees = U256::ZERO;
 
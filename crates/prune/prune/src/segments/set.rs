@@ -1,6 +1,6 @@
 use crate::segments::{
-    AccountHistory, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory, TransactionLookup,
-    UserReceipts,
+    AccountHistory, BlobSidecars, ReceiptsByLogs, Segment, SenderRecovery, StorageHistory,
+    TransactionLookup, UserReceipts,
 };
 use reth_db::transaction::DbTxMut;
 use reth_provider::{
@@ -9,7 +9,10 @@ use reth_provider::{
 };
 use reth_prune_types::PruneModes;
 
-use super::{StaticFileHeaders, StaticFileReceipts, StaticFileTransactions};
+use super::{
+    StaticFileAccountChangeSets, StaticFileHeaders, StaticFileReceipts,
+    StaticFileStorageChangeSets, StaticFileTransactions,
+};
 
 /// Collection of [Segment]. Thread-safe, allocated on the heap.
 #[derive(Debug)]
@@ -60,6 +63,7 @@ where
             account_history,
             storage_history,
             receipts_log_filter,
+            blob_sidecars,
         } = prune_modes;
 
         Self::default()
@@ -68,7 +72,11 @@ where
             // Static file transactions
             .segment(StaticFileTransactions::new(static_file_provider.clone()))
             // Static file receipts
-            .segment(StaticFileReceipts::new(static_file_provider))
+            .segment(StaticFileReceipts::new(static_file_provider.clone()))
+            // Static file account changesets
+            .segment(StaticFileAccountChangeSets::new(static_file_provider.clone()))
+            // Static file storage changesets
+            .segment(StaticFileStorageChangeSets::new(static_file_provider.clone()))
             // Account history
             .segment_opt(account_history.map(AccountHistory::new))
             // Storage history
@@ -84,6 +92,8 @@ where
             .segment_opt(transaction_lookup.map(TransactionLookup::new))
             // Sender recovery
             .segment_opt(sender_recovery.map(SenderRecovery::new))
+            // Blob sidecars
+            .segment_opt(blob_sidecars.map(|mode| BlobSidecars::new(static_file_provider, mode)))
     }
 }
 
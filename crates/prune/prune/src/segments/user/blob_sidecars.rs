@@ -0,0 +1,59 @@
+use crate::{
+    segments::{PruneInput, Segment},
+    PrunerError,
+};
+use reth_provider::providers::StaticFileProvider;
+use reth_prune_types::{
+    PruneMode, PruneProgress, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+};
+use reth_static_file_types::StaticFileSegment;
+use tracing::{instrument, trace};
+
+/// Prunes the retained blob sidecars.
+///
+/// Blob sidecars only live in static files, so they are deleted with a granularity of whole static
+/// files: a file is only deleted once all of its blocks are below the prune target.
+#[derive(Debug)]
+pub struct BlobSidecars {
+    static_file_provider: StaticFileProvider,
+    mode: PruneMode,
+}
+
+impl BlobSidecars {
+    pub const fn new(static_file_provider: StaticFileProvider, mode: PruneMode) -> Self {
+        Self { static_file_provider, mode }
+    }
+}
+
+impl<Provider> Segment<Provider> for BlobSidecars {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::BlobSidecars
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, _provider), ret)]
+    fn prune(&self, _provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        let (pruned, last_pruned_block) = self
+            .static_file_provider
+            .delete_jars_below(StaticFileSegment::BlobSidecars, input.to_block + 1)?;
+        trace!(target: "pruner", %pruned, ?last_pruned_block, "Pruned blob sidecars");
+
+        let Some(last_pruned_block) = last_pruned_block else { return Ok(SegmentOutput::done()) };
+
+        Ok(SegmentOutput {
+            progress: PruneProgress::Finished,
+            pruned: pruned as usize,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: Some(last_pruned_block),
+                tx_number: None,
+            }),
+        })
+    }
+}
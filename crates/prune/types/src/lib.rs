@@ -24,7 +24,7 @@ pub use pruner::{
 pub use segment::{PrunePurpose, PruneSegment, PruneSegmentError};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
-pub use target::{PruneModes, MINIMUM_PRUNING_DISTANCE};
+pub use target::{PruneModes, DEFAULT_BLOB_SIDECARS_RETENTION_DISTANCE, MINIMUM_PRUNING_DISTANCE};
 
 use alloy_primitives::{Address, BlockNumber};
 
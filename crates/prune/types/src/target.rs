@@ -8,6 +8,10 @@ use serde::{Deserialize, Deserializer, Serialize};
 ///    unwind is required.
 pub const MINIMUM_PRUNING_DISTANCE: u64 = 32 * 2 + 10_000;
 
+/// Default distance from the tip for which retained blob sidecars are kept: 4096 epochs (32 blocks
+/// per epoch), which matches the blob retention period of the consensus layer.
+pub const DEFAULT_BLOB_SIDECARS_RETENTION_DISTANCE: u64 = 4096 * 32;
+
 /// Pruning configuration for every segment of the data that can be pruned.
 #[derive(Debug, Clone, Default, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(default)]
@@ -43,6 +47,9 @@ pub struct PruneModes {
     /// The [`BlockNumber`](`crate::BlockNumber`) represents the starting block from which point
     /// onwards the receipts are preserved.
     pub receipts_log_filter: ReceiptsLogPruneConfig,
+    /// Blob sidecars pruning configuration. Only has an effect if blob sidecars are retained.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blob_sidecars: Option<PruneMode>,
 }
 
 impl PruneModes {
@@ -60,6 +67,7 @@ impl PruneModes {
             account_history: Some(PruneMode::Full),
             storage_history: Some(PruneMode::Full),
             receipts_log_filter: Default::default(),
+            blob_sidecars: Some(PruneMode::Full),
         }
     }
 
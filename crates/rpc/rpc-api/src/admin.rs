@@ -1,5 +1,6 @@
 use alloy_rpc_types_admin::{NodeInfo, PeerInfo};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_db_models::SnapshotManifest;
 use reth_network_peers::{AnyNode, NodeRecord};
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
@@ -46,3 +47,16 @@ pub trait AdminApi {
     #[method(name = "nodeInfo")]
     async fn node_info(&self) -> RpcResult<NodeInfo>;
 }
+
+/// Admin namespace rpc interface for taking snapshots of the node's database.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait AdminSnapshotApi {
+    /// Creates a consistent snapshot of the database and static files in the directory with the
+    /// given name inside the configured snapshot directory. The snapshot directory must either not
+    /// exist or be empty.
+    ///
+    /// Returns the manifest of the snapshot.
+    #[method(name = "snapshot")]
+    async fn snapshot(&self, name: String) -> RpcResult<SnapshotManifest>;
+}
@@ -7,6 +7,8 @@ use alloy_rpc_types_trace::geth::{
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::{BlockId, BlockNumberOrTag};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Debug rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
@@ -163,7 +165,7 @@ pub trait DebugApi {
         nocode: bool,
         nostorage: bool,
         incompletes: bool,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<AccountRangeResult>;
 
     /// Turns on block profiling for the given duration and writes profile data to disk. It uses a
     /// profile rate of 1 for most accurate information. If a different rate is desired, set the
@@ -234,17 +236,18 @@ pub trait DebugApi {
     async fn debug_get_modified_accounts_by_hash(
         &self,
         start_hash: B256,
-        end_hash: B256,
-    ) -> RpcResult<()>;
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Returns all accounts that have changed between the two blocks specified. A change is defined
-    /// as a difference in nonce, balance, code hash or storage hash.
+    /// as a difference in nonce, balance, code hash or storage hash. With one parameter, returns
+    /// the list of accounts modified in the specified block.
     #[method(name = "getModifiedAccountsByNumber")]
     async fn debug_get_modified_accounts_by_number(
         &self,
         start_number: u64,
-        end_number: u64,
-    ) -> RpcResult<()>;
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Turns on Go runtime tracing for the given duration and writes trace data to disk.
     #[method(name = "goTrace")]
@@ -257,7 +260,7 @@ pub trait DebugApi {
         &self,
         block_hash: B256,
         opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<Vec<B256>>;
 
     /// Returns detailed runtime memory statistics.
     #[method(name = "memStats")]
@@ -271,7 +274,7 @@ pub trait DebugApi {
 
     /// Returns the preimage for a sha3 hash, if known.
     #[method(name = "preimage")]
-    async fn debug_preimage(&self, hash: B256) -> RpcResult<()>;
+    async fn debug_preimage(&self, hash: B256) -> RpcResult<Bytes>;
 
     /// Retrieves a block and returns its pretty printed form.
     #[method(name = "printBlock")]
@@ -354,7 +357,7 @@ pub trait DebugApi {
         contract_address: Address,
         key_start: B256,
         max_result: u64,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<StorageRangeResult>;
 
     /// Returns the structured logs created during the execution of EVM against a block pulled
     /// from the pool of bad ones and returns them as a JSON object. For the second parameter see
@@ -387,3 +390,89 @@ pub trait DebugApi {
     #[method(name = "writeMutexProfile")]
     async fn debug_write_mutex_profile(&self, file: String) -> RpcResult<()>;
 }
+
+/// Response of `debug_storageRangeAt`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageRangeResult {
+    /// Storage slots of the page keyed by the hash of the storage key.
+    pub storage: BTreeMap<B256, StorageRangeEntry>,
+    /// Hash of the first storage key of the next page, if any.
+    pub next_key: Option<B256>,
+}
+
+/// A single storage slot of a [`StorageRangeResult`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageRangeEntry {
+    /// The preimage of the hashed storage key, if known.
+    pub key: Option<B256>,
+    /// The value of the storage slot.
+    pub value: B256,
+}
+
+/// Response of `debug_accountRange`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountRangeResult {
+    /// The state root of the block.
+    pub root: B256,
+    /// Accounts keyed by address, or by `pre(<hashed address>)` if the address is unknown.
+    pub accounts: BTreeMap<String, DumpAccount>,
+    /// Hashed address of the first account of the next page, if any.
+    ///
+    /// Same as geth, this is base64 encoded and must be hex encoded to be passed back as the
+    /// `start` parameter.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "base64_bytes")]
+    pub next: Option<Bytes>,
+}
+
+/// (De)serializes optional bytes as standard base64, which is how go encodes byte slices in JSON.
+mod base64_bytes {
+    use alloy_primitives::Bytes;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S: Serializer>(
+        value: &Option<Bytes>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(bytes) => serializer.serialize_str(&STANDARD.encode(bytes)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Bytes>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|encoded| STANDARD.decode(encoded).map(Into::into))
+            .transpose()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// An account of an [`AccountRangeResult`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpAccount {
+    /// Balance of the account as a decimal string.
+    pub balance: String,
+    /// Nonce of the account.
+    pub nonce: u64,
+    /// Storage root of the account.
+    pub root: Bytes,
+    /// Hash of the account code.
+    pub code_hash: Bytes,
+    /// Code of the account, unless omitted by the request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// Storage values keyed by hashed storage key, unless omitted by the request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<B256, String>>,
+    /// Address of the account, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    /// Hashed address of the account.
+    #[serde(rename = "key")]
+    pub address_hash: Bytes,
+}
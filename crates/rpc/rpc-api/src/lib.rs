@@ -30,13 +30,16 @@ mod txpool;
 mod validation;
 mod web3;
 
+pub use debug::{AccountRangeResult, DumpAccount, StorageRangeEntry, StorageRangeResult};
+
 /// re-export of all server traits
 pub use servers::*;
 
 /// Aggregates all server traits.
 pub mod servers {
     pub use crate::{
-        admin::AdminApiServer,
+        admin::{AdminApiServer, AdminSnapshotApiServer},
+        anvil::AnvilApiServer,
         debug::DebugApiServer,
         engine::{EngineApiServer, EngineEthApiServer},
         mev::{MevFullApiServer, MevSimApiServer},
@@ -63,7 +66,7 @@ pub use clients::*;
 #[cfg(feature = "client")]
 pub mod clients {
     pub use crate::{
-        admin::AdminApiClient,
+        admin::{AdminApiClient, AdminSnapshotApiClient},
         anvil::AnvilApiClient,
         debug::DebugApiClient,
         engine::{EngineApiClient, EngineEthApiClient},
@@ -1,5 +1,6 @@
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{Address, B256, U256};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_db_models::{StoredBlobSidecar, StoredBlockBlobSidecars};
 use reth_primitives::BlockId;
 use std::collections::HashMap;
 
@@ -13,4 +14,24 @@ pub trait RethApi {
         &self,
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
+
+    /// Returns the retained blob sidecars of a block, ordered by transaction index, and the
+    /// indices of the blob transactions whose sidecars were missing.
+    ///
+    /// Returns `None` if the block has no retained blob transactions.
+    #[method(name = "getBlobSidecars")]
+    async fn reth_get_blob_sidecars(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<StoredBlockBlobSidecars>>;
+
+    /// Returns the retained blob sidecar which contains the blob with the given versioned hash.
+    ///
+    /// Only the blobs of the most recent 8192 blocks are searched, the sidecars of older blocks
+    /// can be fetched with `reth_getBlobSidecars`.
+    #[method(name = "getBlobSidecarByVersionedHash")]
+    async fn reth_get_blob_sidecar_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+    ) -> RpcResult<Option<StoredBlobSidecar>>;
 }
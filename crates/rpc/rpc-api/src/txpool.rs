@@ -1,7 +1,8 @@
 use alloy_json_rpc::RpcObject;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash};
 use alloy_rpc_types_txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_transaction_pool::{TransactionHistory, TransactionJournalEntry};
 
 /// Txpool rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "txpool"))]
@@ -34,4 +35,20 @@ pub trait TxPoolApi<T: RpcObject> {
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     #[method(name = "content")]
     async fn txpool_content(&self) -> RpcResult<TxpoolContent<T>>;
+
+    /// Returns the current status of the transaction together with its recent lifecycle events:
+    /// which sub-pool it was added to, promotions, replacements, discards and their reason,
+    /// propagation and inclusion.
+    ///
+    /// Only recent transactions are tracked, older history is dropped.
+    #[method(name = "getTransactionStatus")]
+    async fn txpool_get_transaction_status(&self, hash: TxHash) -> RpcResult<TransactionHistory>;
+
+    /// Creates a subscription that yields all recorded transaction lifecycle events.
+    #[subscription(
+        name = "subscribeTransactionEvents" => "transactionEvent",
+        unsubscribe = "unsubscribeTransactionEvents",
+        item = TransactionJournalEntry
+    )]
+    async fn txpool_subscribe_transaction_events(&self) -> jsonrpsee::core::SubscriptionResult;
 }
@@ -27,13 +27,14 @@
 //! use reth_tasks::TokioTaskExecutor;
 //! use reth_transaction_pool::TransactionPool;
 //!
-//! pub async fn launch<Provider, Pool, Network, Events, EvmConfig, BlockExecutor>(
+//! pub async fn launch<Provider, Pool, Network, Events, EvmConfig, BlockExecutor, Consensus>(
 //!     provider: Provider,
 //!     pool: Pool,
 //!     network: Network,
 //!     events: Events,
 //!     evm_config: EvmConfig,
 //!     block_executor: BlockExecutor,
+//!     consensus: Consensus,
 //! ) where
 //!     Provider: FullRpcProvider + AccountReader + ChangeSetReader,
 //!     Pool: TransactionPool + 'static,
@@ -41,6 +42,7 @@
 //!     Events: CanonStateSubscriptions + Clone + 'static,
 //!     EvmConfig: ConfigureEvm<Header = Header>,
 //!     BlockExecutor: BlockExecutorProvider,
+//!     Consensus: reth_consensus::Consensus + Clone + 'static,
 //! {
 //!     // configure the rpc module per transport
 //!     let transports = TransportRpcModuleConfig::default().with_http(vec![
@@ -57,6 +59,7 @@
 //!         events,
 //!         evm_config,
 //!         block_executor,
+//!         consensus,
 //!     )
 //!     .build(transports, Box::new(EthApi::with_spawner));
 //!     let handle = RpcServerConfig::default()
@@ -94,6 +97,7 @@
 //!     EngineT,
 //!     EvmConfig,
 //!     BlockExecutor,
+//!     Consensus,
 //! >(
 //!     provider: Provider,
 //!     pool: Pool,
@@ -102,6 +106,7 @@
 //!     engine_api: EngineApi,
 //!     evm_config: EvmConfig,
 //!     block_executor: BlockExecutor,
+//!     consensus: Consensus,
 //! ) where
 //!     Provider: FullRpcProvider + AccountReader + ChangeSetReader,
 //!     Pool: TransactionPool + 'static,
@@ -111,6 +116,7 @@
 //!     EngineT: EngineTypes,
 //!     EvmConfig: ConfigureEvm<Header = Header>,
 //!     BlockExecutor: BlockExecutorProvider,
+//!     Consensus: reth_consensus::Consensus + Clone + 'static,
 //! {
 //!     // configure the rpc module per transport
 //!     let transports = TransportRpcModuleConfig::default().with_http(vec![
@@ -127,6 +133,7 @@
 //!         events,
 //!         evm_config,
 //!         block_executor,
+//!         consensus,
 //!     );
 //!
 //!     // configure the server modules
@@ -154,6 +161,7 @@ use std::{
     collections::HashMap,
     fmt::Debug,
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    sync::Arc,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
@@ -179,7 +187,7 @@ use reth_provider::{
 };
 use reth_rpc::{
     AdminApi, DebugApi, EngineEthApi, EthBundle, NetApi, OtterscanApi, RPCApi, RethApi, TraceApi,
-    TxPoolApi, Web3Api,
+    TxPoolApi, ValidationApi, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
@@ -231,7 +239,17 @@ pub mod rate_limiter;
 
 /// Convenience function for starting a server in one step.
 #[allow(clippy::too_many_arguments)]
-pub async fn launch<Provider, Pool, Network, Tasks, Events, EvmConfig, EthApi, BlockExecutor>(
+pub async fn launch<
+    Provider,
+    Pool,
+    Network,
+    Tasks,
+    Events,
+    EvmConfig,
+    EthApi,
+    BlockExecutor,
+    Consensus,
+>(
     provider: Provider,
     pool: Pool,
     network: Network,
@@ -242,6 +260,7 @@ pub async fn launch<Provider, Pool, Network, Tasks, Events, EvmConfig, EthApi, B
     evm_config: EvmConfig,
     eth: DynEthApiBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events, EthApi>,
     block_executor: BlockExecutor,
+    consensus: Consensus,
 ) -> Result<RpcServerHandle, RpcError>
 where
     Provider: FullRpcProvider + AccountReader + ChangeSetReader,
@@ -252,6 +271,7 @@ where
     EvmConfig: ConfigureEvm<Header = reth_primitives::Header>,
     EthApi: FullEthApiServer,
     BlockExecutor: BlockExecutorProvider,
+    Consensus: reth_consensus::Consensus + Clone + 'static,
 {
     let module_config = module_config.into();
     server_config
@@ -265,6 +285,7 @@ where
                 events,
                 evm_config,
                 block_executor,
+                consensus,
             )
             .build(module_config, eth),
         )
@@ -275,7 +296,16 @@ where
 ///
 /// This is the main entrypoint and the easiest way to configure an RPC server.
 #[derive(Debug, Clone)]
-pub struct RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor> {
+pub struct RpcModuleBuilder<
+    Provider,
+    Pool,
+    Network,
+    Tasks,
+    Events,
+    EvmConfig,
+    BlockExecutor,
+    Consensus,
+> {
     /// The Provider type to when creating all rpc handlers
     provider: Provider,
     /// The Pool type to when creating all rpc handlers
@@ -290,14 +320,17 @@ pub struct RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig, B
     evm_config: EvmConfig,
     /// The provider for getting a block executor that executes blocks
     block_executor: BlockExecutor,
+    /// The consensus implementation.
+    consensus: Consensus,
 }
 
 // === impl RpcBuilder ===
 
-impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
-    RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
+impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus>
+    RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus>
 {
     /// Create a new instance of the builder
+    #[allow(clippy::too_many_arguments)]
     pub const fn new(
         provider: Provider,
         pool: Pool,
@@ -306,32 +339,54 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
         events: Events,
         evm_config: EvmConfig,
         block_executor: BlockExecutor,
+        consensus: Consensus,
     ) -> Self {
-        Self { provider, pool, network, executor, events, evm_config, block_executor }
+        Self { provider, pool, network, executor, events, evm_config, block_executor, consensus }
     }
 
     /// Configure the provider instance.
     pub fn with_provider<P>(
         self,
         provider: P,
-    ) -> RpcModuleBuilder<P, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
+    ) -> RpcModuleBuilder<P, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus>
     where
         P: BlockReader + StateProviderFactory + EvmEnvProvider + 'static,
     {
-        let Self { pool, network, executor, events, evm_config, block_executor, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config, block_executor }
+        let Self { pool, network, executor, events, evm_config, block_executor, consensus, .. } =
+            self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+        }
     }
 
     /// Configure the transaction pool instance.
     pub fn with_pool<P>(
         self,
         pool: P,
-    ) -> RpcModuleBuilder<Provider, P, Network, Tasks, Events, EvmConfig, BlockExecutor>
+    ) -> RpcModuleBuilder<Provider, P, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus>
     where
         P: TransactionPool + 'static,
     {
-        let Self { provider, network, executor, events, evm_config, block_executor, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config, block_executor }
+        let Self {
+            provider, network, executor, events, evm_config, block_executor, consensus, ..
+        } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+        }
     }
 
     /// Configure a [`NoopTransactionPool`] instance.
@@ -349,8 +404,11 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
         Events,
         EvmConfig,
         BlockExecutor,
+        Consensus,
     > {
-        let Self { provider, executor, events, network, evm_config, block_executor, .. } = self;
+        let Self {
+            provider, executor, events, network, evm_config, block_executor, consensus, ..
+        } = self;
         RpcModuleBuilder {
             provider,
             executor,
@@ -358,6 +416,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
             network,
             evm_config,
             block_executor,
+            consensus,
             pool: NoopTransactionPool::default(),
         }
     }
@@ -366,12 +425,23 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
     pub fn with_network<N>(
         self,
         network: N,
-    ) -> RpcModuleBuilder<Provider, Pool, N, Tasks, Events, EvmConfig, BlockExecutor>
+    ) -> RpcModuleBuilder<Provider, Pool, N, Tasks, Events, EvmConfig, BlockExecutor, Consensus>
     where
         N: NetworkInfo + Peers + 'static,
     {
-        let Self { provider, pool, executor, events, evm_config, block_executor, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config, block_executor }
+        let Self {
+            provider, pool, executor, events, evm_config, block_executor, consensus, ..
+        } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+        }
     }
 
     /// Configure a [`NoopNetwork`] instance.
@@ -381,9 +451,19 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
     /// [`EthApi`](reth_rpc::eth::EthApi) which requires a [`NetworkInfo`] implementation.
     pub fn with_noop_network(
         self,
-    ) -> RpcModuleBuilder<Provider, Pool, NoopNetwork, Tasks, Events, EvmConfig, BlockExecutor>
-    {
-        let Self { provider, pool, executor, events, evm_config, block_executor, .. } = self;
+    ) -> RpcModuleBuilder<
+        Provider,
+        Pool,
+        NoopNetwork,
+        Tasks,
+        Events,
+        EvmConfig,
+        BlockExecutor,
+        Consensus,
+    > {
+        let Self {
+            provider, pool, executor, events, evm_config, block_executor, consensus, ..
+        } = self;
         RpcModuleBuilder {
             provider,
             pool,
@@ -392,6 +472,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
             network: NoopNetwork::default(),
             evm_config,
             block_executor,
+            consensus,
         }
     }
 
@@ -399,12 +480,22 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
     pub fn with_executor<T>(
         self,
         executor: T,
-    ) -> RpcModuleBuilder<Provider, Pool, Network, T, Events, EvmConfig, BlockExecutor>
+    ) -> RpcModuleBuilder<Provider, Pool, Network, T, Events, EvmConfig, BlockExecutor, Consensus>
     where
         T: TaskSpawner + 'static,
     {
-        let Self { pool, network, provider, events, evm_config, block_executor, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config, block_executor }
+        let Self { pool, network, provider, events, evm_config, block_executor, consensus, .. } =
+            self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+        }
     }
 
     /// Configure [`TokioTaskExecutor`] as the task executor to use for additional tasks.
@@ -421,8 +512,10 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
         Events,
         EvmConfig,
         BlockExecutor,
+        Consensus,
     > {
-        let Self { pool, network, provider, events, evm_config, block_executor, .. } = self;
+        let Self { pool, network, provider, events, evm_config, block_executor, consensus, .. } =
+            self;
         RpcModuleBuilder {
             provider,
             network,
@@ -431,6 +524,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
             executor: TokioTaskExecutor::default(),
             evm_config,
             block_executor,
+            consensus,
         }
     }
 
@@ -438,41 +532,90 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
     pub fn with_events<E>(
         self,
         events: E,
-    ) -> RpcModuleBuilder<Provider, Pool, Network, Tasks, E, EvmConfig, BlockExecutor>
+    ) -> RpcModuleBuilder<Provider, Pool, Network, Tasks, E, EvmConfig, BlockExecutor, Consensus>
     where
         E: CanonStateSubscriptions + 'static,
     {
-        let Self { provider, pool, executor, network, evm_config, block_executor, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config, block_executor }
+        let Self {
+            provider, pool, executor, network, evm_config, block_executor, consensus, ..
+        } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+        }
     }
 
     /// Configure the evm configuration type
     pub fn with_evm_config<E>(
         self,
         evm_config: E,
-    ) -> RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, E, BlockExecutor>
+    ) -> RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, E, BlockExecutor, Consensus>
     where
         E: ConfigureEvm + 'static,
     {
-        let Self { provider, pool, executor, network, events, block_executor, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config, block_executor }
+        let Self { provider, pool, executor, network, events, block_executor, consensus, .. } =
+            self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+        }
     }
 
     /// Configure the block executor provider
     pub fn with_block_executor<BE>(
         self,
         block_executor: BE,
-    ) -> RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig, BE>
+    ) -> RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig, BE, Consensus>
     where
         BE: BlockExecutorProvider,
     {
-        let Self { provider, network, pool, executor, events, evm_config, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config, block_executor }
+        let Self { provider, network, pool, executor, events, evm_config, consensus, .. } = self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+        }
+    }
+
+    /// Configure the consensus implementation.
+    pub fn with_consensus<C>(
+        self,
+        consensus: C,
+    ) -> RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, C> {
+        let Self { provider, network, pool, executor, events, evm_config, block_executor, .. } =
+            self;
+        RpcModuleBuilder {
+            provider,
+            network,
+            pool,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+        }
     }
 }
 
-impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
-    RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor>
+impl<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus>
+    RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig, BlockExecutor, Consensus>
 where
     Provider: FullRpcProvider + AccountReader + ChangeSetReader,
     Pool: TransactionPool + 'static,
@@ -481,6 +624,7 @@ where
     Events: CanonStateSubscriptions + Clone + 'static,
     EvmConfig: ConfigureEvm<Header = Header>,
     BlockExecutor: BlockExecutorProvider,
+    Consensus: reth_consensus::Consensus + Clone + 'static,
 {
     /// Configures all [`RpcModule`]s specific to the given [`TransportRpcModuleConfig`] which can
     /// be used to start the transport server(s).
@@ -497,14 +641,23 @@ where
     ) -> (
         TransportRpcModules,
         AuthRpcModule,
-        RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor>,
+        RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>,
     )
     where
         EngineT: EngineTypes,
         EngineApi: EngineApiServer<EngineT>,
         EthApi: FullEthApiServer,
     {
-        let Self { provider, pool, network, executor, events, evm_config, block_executor } = self;
+        let Self {
+            provider,
+            pool,
+            network,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+        } = self;
 
         let config = module_config.config.clone().unwrap_or_default();
 
@@ -518,6 +671,7 @@ where
             evm_config,
             eth,
             block_executor,
+            consensus,
         );
 
         let modules = registry.create_transport_rpc_modules(module_config);
@@ -535,6 +689,7 @@ where
     /// # Example
     ///
     /// ```no_run
+    /// use reth_consensus::noop::NoopConsensus;
     /// use reth_evm::ConfigureEvm;
     /// use reth_evm_ethereum::execute::EthExecutorProvider;
     /// use reth_network_api::noop::NoopNetwork;
@@ -554,6 +709,7 @@ where
     ///         .with_events(TestCanonStateSubscriptions::default())
     ///         .with_evm_config(evm)
     ///         .with_block_executor(EthExecutorProvider::mainnet())
+    ///         .with_consensus(NoopConsensus::default())
     ///         .into_registry(Default::default(), Box::new(EthApi::with_spawner));
     ///
     ///     let eth_api = registry.eth_api();
@@ -563,11 +719,20 @@ where
         self,
         config: RpcModuleConfig,
         eth: DynEthApiBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events, EthApi>,
-    ) -> RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor>
+    ) -> RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
     where
         EthApi: EthApiTypes + 'static,
     {
-        let Self { provider, pool, network, executor, events, evm_config, block_executor } = self;
+        let Self {
+            provider,
+            pool,
+            network,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+        } = self;
         RpcRegistryInner::new(
             provider,
             pool,
@@ -578,6 +743,7 @@ where
             evm_config,
             eth,
             block_executor,
+            consensus,
         )
     }
 
@@ -593,7 +759,16 @@ where
     {
         let mut modules = TransportRpcModules::default();
 
-        let Self { provider, pool, network, executor, events, evm_config, block_executor } = self;
+        let Self {
+            provider,
+            pool,
+            network,
+            executor,
+            events,
+            evm_config,
+            block_executor,
+            consensus,
+        } = self;
 
         if !module_config.is_empty() {
             let TransportRpcModuleConfig { http, ws, ipc, config } = module_config.clone();
@@ -608,6 +783,7 @@ where
                 evm_config,
                 eth,
                 block_executor,
+                consensus,
             );
 
             modules.config = module_config;
@@ -620,9 +796,9 @@ where
     }
 }
 
-impl Default for RpcModuleBuilder<(), (), (), (), (), (), ()> {
+impl Default for RpcModuleBuilder<(), (), (), (), (), (), (), ()> {
     fn default() -> Self {
-        Self::new((), (), (), (), (), (), ())
+        Self::new((), (), (), (), (), (), (), ())
     }
 }
 
@@ -704,6 +880,7 @@ pub struct RpcRegistryInner<
     Events,
     EthApi: EthApiTypes,
     BlockExecutor,
+    Consensus,
 > {
     provider: Provider,
     pool: Pool,
@@ -711,6 +888,7 @@ pub struct RpcRegistryInner<
     executor: Tasks,
     events: Events,
     block_executor: BlockExecutor,
+    consensus: Consensus,
     /// Holds a all `eth_` namespace handlers
     eth: EthHandlers<Provider, Pool, Network, Events, EthApi>,
     /// to put trace calls behind semaphore
@@ -721,8 +899,8 @@ pub struct RpcRegistryInner<
 
 // === impl RpcRegistryInner ===
 
-impl<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor>
-    RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor>
+impl<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
+    RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
 where
     Provider: StateProviderFactory + BlockReader + EvmEnvProvider + Clone + Unpin + 'static,
     Pool: Send + Sync + Clone + 'static,
@@ -752,6 +930,7 @@ where
             EthApi,
         >,
         block_executor: BlockExecutor,
+        consensus: Consensus,
     ) -> Self
     where
         EvmConfig: ConfigureEvm<Header = Header>,
@@ -779,12 +958,13 @@ where
             blocking_pool_guard,
             events,
             block_executor,
+            consensus,
         }
     }
 }
 
-impl<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor>
-    RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor>
+impl<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
+    RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
 where
     EthApi: EthApiTypes,
 {
@@ -841,8 +1021,8 @@ where
     }
 }
 
-impl<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor>
-    RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor>
+impl<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
+    RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
 where
     Network: NetworkInfo + Clone + 'static,
     EthApi: EthApiTypes,
@@ -880,8 +1060,8 @@ where
     }
 }
 
-impl<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor>
-    RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor>
+impl<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
+    RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
 where
     Provider: FullRpcProvider + AccountReader + ChangeSetReader,
     Network: NetworkInfo + Peers + Clone + 'static,
@@ -982,19 +1162,29 @@ where
         self
     }
 
+    /// Register Flashbots namespace
+    pub fn register_flashbots(&mut self) -> &mut Self
+    where
+        Consensus: reth_consensus::Consensus + Clone + 'static,
+    {
+        let validationapi = self.validation_api();
+        self.modules.insert(RethRpcModule::Flashbots, validationapi.into_rpc().into());
+        self
+    }
+
     /// Instantiates `OtterscanApi`
     ///
     /// # Panics
     ///
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
-    pub fn otterscan_api(&self) -> OtterscanApi<EthApi> {
+    pub fn otterscan_api(&self) -> OtterscanApi<Provider, EthApi> {
         let eth_api = self.eth_api().clone();
-        OtterscanApi::new(eth_api)
+        OtterscanApi::new(self.provider.clone(), eth_api)
     }
 }
 
-impl<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor>
-    RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor>
+impl<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
+    RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
 where
     Provider: FullRpcProvider + AccountReader + ChangeSetReader,
     Network: NetworkInfo + Peers + Clone + 'static,
@@ -1066,10 +1256,23 @@ where
     pub fn reth_api(&self) -> RethApi<Provider> {
         RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
     }
+
+    /// Instantiates `ValidationApi`
+    pub fn validation_api(&self) -> ValidationApi<Provider, BlockExecutor>
+    where
+        Consensus: reth_consensus::Consensus + Clone + 'static,
+    {
+        ValidationApi::new(
+            self.provider.clone(),
+            Arc::new(self.consensus.clone()),
+            self.block_executor.clone(),
+            Box::new(self.executor.clone()),
+        )
+    }
 }
 
-impl<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor>
-    RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor>
+impl<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
+    RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi, BlockExecutor, Consensus>
 where
     Provider: FullRpcProvider + AccountReader + ChangeSetReader,
     Pool: TransactionPool + 'static,
@@ -1078,6 +1281,7 @@ where
     Events: CanonStateSubscriptions + Clone + 'static,
     EthApi: FullEthApiServer,
     BlockExecutor: BlockExecutorProvider,
+    Consensus: reth_consensus::Consensus + Clone + 'static,
 {
     /// Configures the auth module that includes the
     ///   * `engine_` namespace
@@ -1213,12 +1417,27 @@ where
                         )
                         .into_rpc()
                         .into(),
-                        RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
+                        RethRpcModule::Ots => {
+                            OtterscanApi::new(self.provider.clone(), eth_api.clone())
+                                .into_rpc()
+                                .into()
+                        }
                         RethRpcModule::Reth => {
                             RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
                                 .into_rpc()
                                 .into()
                         }
+                        RethRpcModule::Flashbots => ValidationApi::new(
+                            self.provider.clone(),
+                            Arc::new(self.consensus.clone()),
+                            self.block_executor.clone(),
+                            Box::new(self.executor.clone()),
+                        )
+                        .into_rpc()
+                        .into(),
+                        // the anvil methods are backed by the local miner and installed by the
+                        // node when running in dev mode
+                        RethRpcModule::Anvil => Methods::new(),
                     })
                     .clone()
             })
@@ -1821,6 +2040,28 @@ impl TransportRpcModules {
         Ok(())
     }
 
+    /// Merge the given [Methods] in the methods of all transports that have the given module
+    /// configured.
+    ///
+    /// Fails if any of the methods in other is present already.
+    pub fn merge_if_module_configured(
+        &mut self,
+        module: RethRpcModule,
+        other: impl Into<Methods>,
+    ) -> Result<(), RegisterMethodError> {
+        let other = other.into();
+        if self.config.http().is_some_and(|sel| sel.contains(&module)) {
+            self.merge_http(other.clone())?;
+        }
+        if self.config.ws().is_some_and(|sel| sel.contains(&module)) {
+            self.merge_ws(other.clone())?;
+        }
+        if self.config.ipc().is_some_and(|sel| sel.contains(&module)) {
+            self.merge_ipc(other)?;
+        }
+        Ok(())
+    }
+
     /// Removes the method with the given name from the configured http methods.
     ///
     /// Returns `true` if the method was found and removed, `false` otherwise.
@@ -431,28 +431,23 @@ where
     .err()
     .unwrap();
 
-    assert!(is_unimplemented(
-        OtterscanClient::<Transaction>::search_transactions_before(
-            client,
-            address,
-            block_number,
-            page_size,
-        )
-        .await
-        .err()
-        .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::<Transaction>::search_transactions_after(
-            client,
-            address,
-            block_number,
-            page_size,
-        )
-        .await
-        .err()
-        .unwrap()
-    ));
+    // the address transactions index is not enabled
+    OtterscanClient::<Transaction>::search_transactions_before(
+        client,
+        address,
+        block_number,
+        page_size,
+    )
+    .await
+    .unwrap_err();
+    OtterscanClient::<Transaction>::search_transactions_after(
+        client,
+        address,
+        block_number,
+        page_size,
+    )
+    .await
+    .unwrap_err();
     assert!(OtterscanClient::<Transaction>::get_transaction_by_sender_and_nonce(
         client, sender, nonce
     )
@@ -3,6 +3,7 @@ use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use alloy_rpc_types_engine::{ClientCode, ClientVersionV1};
 use reth_beacon_consensus::BeaconConsensusEngineHandle;
 use reth_chainspec::MAINNET;
+use reth_consensus::noop::NoopConsensus;
 use reth_ethereum_engine_primitives::{EthEngineTypes, EthereumEngineValidator};
 use reth_evm::execute::BasicBlockExecutorProvider;
 use reth_evm_ethereum::{execute::EthExecutionStrategyFactory, EthEvmConfig};
@@ -126,6 +127,7 @@ pub fn test_rpc_builder() -> RpcModuleBuilder<
     TestCanonStateSubscriptions,
     EthEvmConfig,
     BasicBlockExecutorProvider<EthExecutionStrategyFactory>,
+    NoopConsensus,
 > {
     RpcModuleBuilder::default()
         .with_provider(NoopProvider::default())
@@ -137,4 +139,5 @@ pub fn test_rpc_builder() -> RpcModuleBuilder<
         .with_block_executor(
             BasicBlockExecutorProvider::new(EthExecutionStrategyFactory::mainnet()),
         )
+        .with_consensus(NoopConsensus::default())
 }
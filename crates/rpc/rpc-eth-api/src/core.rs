@@ -11,7 +11,7 @@ use alloy_rpc_types::{
     BlockOverrides, Bundle, EIP1186AccountProofResponse, EthCallResponse, FeeHistory, Header,
     Index, StateContext, SyncStatus, Work,
 };
-use alloy_rpc_types_eth::transaction::TransactionRequest;
+use alloy_rpc_types_eth::{erc4337::ConditionalOptions, transaction::TransactionRequest};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::{BlockId, BlockNumberOrTag};
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
@@ -336,6 +336,15 @@ pub trait EthApi<T: RpcObject, B: RpcObject, R: RpcObject> {
     #[method(name = "sendRawTransaction")]
     async fn send_raw_transaction(&self, bytes: Bytes) -> RpcResult<B256>;
 
+    /// Sends signed transaction that may only be included in blocks that satisfy the given
+    /// conditions, returning its hash.
+    #[method(name = "sendRawTransactionConditional")]
+    async fn send_raw_transaction_conditional(
+        &self,
+        bytes: Bytes,
+        options: ConditionalOptions,
+    ) -> RpcResult<B256>;
+
     /// Returns an Ethereum specific signature with: sign(keccak256("\x19Ethereum Signed Message:\n"
     /// + len(message) + message))).
     #[method(name = "sign")]
@@ -773,6 +782,16 @@ where
         Ok(EthTransactions::send_raw_transaction(self, tx).await?)
     }
 
+    /// Handler for: `eth_sendRawTransactionConditional`
+    async fn send_raw_transaction_conditional(
+        &self,
+        tx: Bytes,
+        options: ConditionalOptions,
+    ) -> RpcResult<B256> {
+        trace!(target: "rpc::eth", ?tx, ?options, "Serving eth_sendRawTransactionConditional");
+        Ok(EthTransactions::send_raw_transaction_conditional(self, tx, options).await?)
+    }
+
     /// Handler for: `eth_sign`
     async fn sign(&self, address: Address, message: Bytes) -> RpcResult<Bytes> {
         trace!(target: "rpc::eth", ?address, ?message, "Serving eth_sign");
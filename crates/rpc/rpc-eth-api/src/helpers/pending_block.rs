@@ -248,11 +248,13 @@ pub trait LoadPendingBlock: EthApiTypes {
 
         let mut executed_txs = Vec::new();
         let mut senders = Vec::new();
-        let mut best_txs =
-            self.pool().best_transactions_with_attributes(BestTransactionsAttributes::new(
+        let mut best_txs = self.pool().best_transactions_with_attributes(
+            BestTransactionsAttributes::new(
                 base_fee,
                 block_env.get_blob_gasprice().map(|gasprice| gasprice as u64),
-            ));
+            )
+            .with_block(parent_hash, block_number, block_env.timestamp.to::<u64>()),
+        );
 
         let (withdrawals, withdrawals_root) = match origin {
             PendingBlockEnvOrigin::ActualPending(ref block) => {
@@ -7,7 +7,10 @@ use alloy_eips::eip2718::Encodable2718;
 use alloy_network::TransactionBuilder;
 use alloy_primitives::{Address, Bytes, TxHash, B256};
 use alloy_rpc_types::{BlockNumberOrTag, TransactionInfo};
-use alloy_rpc_types_eth::transaction::TransactionRequest;
+use alloy_rpc_types_eth::{
+    erc4337::{AccountStorage, ConditionalOptions},
+    transaction::TransactionRequest,
+};
 use futures::Future;
 use reth_primitives::{
     BlockId, Receipt, SealedBlockWithSenders, TransactionMeta, TransactionSigned,
@@ -18,7 +21,10 @@ use reth_rpc_eth_types::{
     EthApiError, EthStateCache, SignError, TransactionSource,
 };
 use reth_rpc_types_compat::transaction::{from_recovered, from_recovered_with_block_context};
-use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
+use reth_transaction_pool::{
+    conditional::{KnownAccountStorage, TransactionConditional, MAX_TRANSACTION_CONDITIONAL_COST},
+    PoolTransaction, TransactionOrigin, TransactionPool,
+};
 use std::sync::Arc;
 
 use crate::{FromEthApiError, FullEthApiTypes, IntoEthApiError, RpcReceipt, RpcTransaction};
@@ -344,6 +350,77 @@ pub trait EthTransactions: LoadTransaction {
         }
     }
 
+    /// Decodes and recovers the transaction and submits it to the pool with the given conditions.
+    ///
+    /// The conditions are checked against the latest block and state before submission.
+    ///
+    /// Returns the hash of the transaction.
+    fn send_raw_transaction_conditional(
+        &self,
+        tx: Bytes,
+        options: ConditionalOptions,
+    ) -> impl Future<Output = Result<B256, Self::Error>> + Send
+    where
+        Self: LoadState,
+    {
+        async move {
+            let conditional = transaction_conditional_from_options(options);
+            let cost = conditional.cost();
+            if cost > MAX_TRANSACTION_CONDITIONAL_COST {
+                return Err(Self::Error::from_eth_err(EthApiError::ConditionalTransactionRejected(
+                    format!("cost {cost} exceeds maximum {MAX_TRANSACTION_CONDITIONAL_COST}"),
+                )))
+            }
+
+            let recovered = recover_raw_transaction(tx)?;
+            let pool_transaction =
+                <Self::Pool as TransactionPool>::Transaction::from_pooled(recovered.into());
+
+            let conditional = self
+                .spawn_blocking_io(move |this| {
+                    let header = EthTransactions::provider(&this)
+                        .latest_header()
+                        .map_err(Self::Error::from_eth_err)?
+                        .ok_or(EthApiError::HeaderNotFound(BlockNumberOrTag::Latest.into()))?;
+                    if conditional
+                        .has_exceeded_block_attributes(header.number + 1, header.timestamp + 1)
+                    {
+                        return Err(Self::Error::from_eth_err(
+                            EthApiError::ConditionalTransactionRejected(
+                                "block number or timestamp bounds exceeded".to_string(),
+                            ),
+                        ))
+                    }
+
+                    let state = this.latest_state()?;
+                    if !conditional
+                        .matches_known_accounts(&state)
+                        .map_err(Self::Error::from_eth_err)?
+                    {
+                        return Err(Self::Error::from_eth_err(
+                            EthApiError::ConditionalTransactionRejected(
+                                "known accounts storage mismatch".to_string(),
+                            ),
+                        ))
+                    }
+                    Ok(conditional)
+                })
+                .await?;
+
+            // submit the transaction to the pool with a `Local` origin
+            let hash = LoadTransaction::pool(self)
+                .add_conditional_transaction(
+                    TransactionOrigin::Local,
+                    pool_transaction,
+                    conditional,
+                )
+                .await
+                .map_err(Self::Error::from_eth_err)?;
+
+            Ok(hash)
+        }
+    }
+
     /// Signs transaction with a matching signer, if any and submits the transaction to the pool.
     /// Returns the hash of the signed transaction.
     fn send_transaction(
@@ -574,3 +651,26 @@ pub trait LoadTransaction: SpawnBlocking + FullEthApiTypes {
         }
     }
 }
+
+/// Converts the options of `eth_sendRawTransactionConditional` into a [`TransactionConditional`].
+fn transaction_conditional_from_options(options: ConditionalOptions) -> TransactionConditional {
+    TransactionConditional {
+        known_accounts: options
+            .known_accounts
+            .into_iter()
+            .map(|(address, storage)| {
+                let storage = match storage {
+                    AccountStorage::RootHash(root) => KnownAccountStorage::StorageRoot(root),
+                    AccountStorage::Slots(slots) => KnownAccountStorage::Slots(
+                        slots.into_iter().map(|(slot, value)| (slot.into(), value)).collect(),
+                    ),
+                };
+                (address, storage)
+            })
+            .collect(),
+        block_number_min: options.block_number_min,
+        block_number_max: options.block_number_max,
+        timestamp_min: options.timestamp_min,
+        timestamp_max: options.timestamp_max,
+    }
+}
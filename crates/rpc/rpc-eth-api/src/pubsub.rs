@@ -1,13 +1,17 @@
 //! `eth_` RPC API for pubsub subscription.
 
 use alloy_json_rpc::RpcObject;
-use alloy_rpc_types::pubsub::{Params, SubscriptionKind};
+use alloy_rpc_types::pubsub::SubscriptionKind;
 use jsonrpsee::proc_macros::rpc;
+use reth_rpc_eth_types::SubscriptionParams;
 
 /// Ethereum pub-sub rpc interface.
 #[rpc(server, namespace = "eth")]
 pub trait EthPubSubApi<T: RpcObject> {
     /// Create an ethereum subscription for the given params
+    ///
+    /// `newPendingTransactions` subscriptions additionally accept a
+    /// [`PendingTransactionFilter`](reth_rpc_eth_types::PendingTransactionFilter).
     #[subscription(
         name = "subscribe" => "subscription",
         unsubscribe = "unsubscribe",
@@ -16,6 +20,6 @@ pub trait EthPubSubApi<T: RpcObject> {
     async fn subscribe(
         &self,
         kind: SubscriptionKind,
-        params: Option<Params>,
+        params: Option<SubscriptionParams>,
     ) -> jsonrpsee::core::SubscriptionResult;
 }
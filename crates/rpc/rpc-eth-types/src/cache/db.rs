@@ -97,6 +97,38 @@ impl reth_storage_api::StateProofProvider for StateProviderTraitObjWrapper<'_> {
     }
 }
 
+impl reth_storage_api::StateRangeProvider for StateProviderTraitObjWrapper<'_> {
+    fn account_range(
+        &self,
+        input: reth_trie::TrieInput,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> ProviderResult<reth_trie::range::AccountRange> {
+        self.0.account_range(input, start, limit, storage_limit)
+    }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<reth_trie::range::StorageRange> {
+        self.0.storage_range(address, hashed_storage, start, limit)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_address: B256,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<reth_trie::range::StorageRange> {
+        self.0.hashed_storage_range(hashed_address, hashed_storage, start, limit)
+    }
+}
+
 impl reth_storage_api::AccountReader for StateProviderTraitObjWrapper<'_> {
     fn basic_account(
         &self,
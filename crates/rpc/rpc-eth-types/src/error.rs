@@ -10,9 +10,12 @@ use reth_primitives::{revm_primitives::InvalidHeader, BlockId};
 use reth_rpc_server_types::result::{
     block_id_to_str, internal_rpc_err, invalid_params_rpc_err, rpc_err, rpc_error_with_code,
 };
-use reth_transaction_pool::error::{
-    Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
-    PoolError, PoolErrorKind, PoolTransactionError,
+use reth_transaction_pool::{
+    error::{
+        Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
+        PoolError, PoolErrorKind, PoolTransactionError,
+    },
+    AdmissionError,
 };
 use revm::primitives::{EVMError, ExecutionResult, HaltReason, InvalidTransaction, OutOfGasError};
 use revm_inspectors::tracing::MuxError;
@@ -94,6 +97,10 @@ pub enum EthApiError {
     /// Thrown when a requested transaction is not found
     #[error("transaction not found")]
     TransactionNotFound,
+    /// Thrown when the conditions of a conditional transaction are not met or too expensive to
+    /// check
+    #[error("conditional transaction rejected: {0}")]
+    ConditionalTransactionRejected(String),
     /// Some feature is unsupported
     #[error("unsupported")]
     Unsupported(&'static str),
@@ -181,6 +188,9 @@ impl From<EthApiError> for jsonrpsee_types::error::ErrorObject<'static> {
             EthApiError::EvmCustom(_) |
             EthApiError::EvmPrecompile(_) |
             EthApiError::InvalidRewardPercentiles => internal_rpc_err(error.to_string()),
+            err @ EthApiError::ConditionalTransactionRejected(_) => {
+                rpc_error_with_code(EthRpcErrorCode::TransactionRejected.code(), err.to_string())
+            }
             EthApiError::UnknownBlockOrTxIndex => {
                 rpc_error_with_code(EthRpcErrorCode::ResourceNotFound.code(), error.to_string())
             }
@@ -663,6 +673,9 @@ pub enum RpcPoolError {
     /// constraint (blob vs normal tx)
     #[error("address already reserved")]
     AddressAlreadyReserved,
+    /// Rejected by an admission policy of the pool
+    #[error(transparent)]
+    Admission(#[from] AdmissionError),
     /// Other unspecified error
     #[error(transparent)]
     Other(Box<dyn core::error::Error + Send + Sync>),
@@ -709,6 +722,7 @@ impl From<InvalidPoolTransactionError> for RpcPoolError {
             InvalidPoolTransactionError::Other(err) => Self::PoolTransactionError(err),
             InvalidPoolTransactionError::Eip4844(err) => Self::Eip4844(err),
             InvalidPoolTransactionError::Eip7702(err) => Self::Eip7702(err),
+            InvalidPoolTransactionError::Admission(err) => Self::Admission(err),
             InvalidPoolTransactionError::Overdraft { cost, balance } => {
                 Self::Invalid(RpcInvalidTransactionError::InsufficientFunds { cost, balance })
             }
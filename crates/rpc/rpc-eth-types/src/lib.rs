@@ -16,6 +16,7 @@ pub mod gas_oracle;
 pub mod id_provider;
 pub mod logs_utils;
 pub mod pending_block;
+pub mod pubsub;
 pub mod receipt;
 pub mod revm_utils;
 pub mod simulate;
@@ -37,5 +38,6 @@ pub use gas_oracle::{
 };
 pub use id_provider::EthSubscriptionIdProvider;
 pub use pending_block::{PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin};
+pub use pubsub::{PendingTransactionFilter, SubscriptionParams};
 pub use receipt::ReceiptBuilder;
 pub use transaction::TransactionSource;
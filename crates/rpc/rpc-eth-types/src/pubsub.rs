@@ -0,0 +1,225 @@
+//! Types for `eth_subscribe` subscriptions.
+
+use alloy_primitives::{Address, Selector, U8};
+use alloy_rpc_types::pubsub::Params;
+use reth_transaction_pool::{PoolTransaction, SubPool};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+use std::collections::HashSet;
+
+/// Parameters of an `eth_subscribe` call.
+///
+/// This extends the standard [`Params`] with a [`PendingTransactionFilter`] for
+/// `newPendingTransactions` subscriptions.
+///
+/// Params that are valid as both, like an empty object, are rejected as ambiguous.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum SubscriptionParams {
+    /// Standard subscription params.
+    Eth(Params),
+    /// Server-side filter for `newPendingTransactions` subscriptions.
+    PendingTransactions(Box<PendingTransactionFilter>),
+}
+
+impl<'de> Deserialize<'de> for SubscriptionParams {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        match (Params::deserialize(&value), PendingTransactionFilter::deserialize(&value)) {
+            (Ok(_), Ok(_)) => Err(D::Error::custom(
+                "ambiguous subscription params, valid as both logs and pending transaction filter",
+            )),
+            (Ok(params), Err(_)) => Ok(Self::Eth(params)),
+            (Err(_), Ok(filter)) => Ok(Self::PendingTransactions(Box::new(filter))),
+            (Err(_), Err(_)) => Err(D::Error::custom("invalid subscription params")),
+        }
+    }
+}
+
+impl From<Params> for SubscriptionParams {
+    fn from(params: Params) -> Self {
+        Self::Eth(params)
+    }
+}
+
+impl From<PendingTransactionFilter> for SubscriptionParams {
+    fn from(filter: PendingTransactionFilter) -> Self {
+        Self::PendingTransactions(Box::new(filter))
+    }
+}
+
+/// Filter for `newPendingTransactions` subscriptions that is applied before transactions are
+/// serialized.
+///
+/// All set criteria must match. Unset criteria match all transactions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct PendingTransactionFilter {
+    /// Yield full transaction objects instead of hashes.
+    pub full_transactions: bool,
+    /// The sub-pool to stream new transactions of, defaults to [`SubPool::Pending`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subpool: Option<SubPool>,
+    /// Only match transactions sent by one of these addresses.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<HashSet<Address>>,
+    /// Only match transactions calling one of these addresses.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<HashSet<Address>>,
+    /// Only match transactions whose calldata starts with one of these method selectors.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selectors: Option<HashSet<Selector>>,
+    /// Only match transactions with at least this max priority fee per gas, or gas price for
+    /// legacy transactions.
+    #[serde(with = "alloy_serde::quantity::opt", skip_serializing_if = "Option::is_none")]
+    pub min_priority_fee: Option<u128>,
+    /// Only match transactions of one of these [EIP-2718](https://eips.ethereum.org/EIPS/eip-2718)
+    /// types.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_types: Option<HashSet<U8>>,
+}
+
+impl PendingTransactionFilter {
+    /// Returns the sub-pool to stream new transactions of.
+    pub fn subpool(&self) -> SubPool {
+        self.subpool.unwrap_or(SubPool::Pending)
+    }
+
+    /// Returns `true` if the transaction matches all criteria of this filter.
+    pub fn matches<T: PoolTransaction>(&self, tx: &T) -> bool {
+        if self.from.as_ref().is_some_and(|from| !from.contains(&tx.sender())) {
+            return false
+        }
+        if let Some(to) = &self.to {
+            if !tx.to().is_some_and(|addr| to.contains(&addr)) {
+                return false
+            }
+        }
+        if let Some(selectors) = &self.selectors {
+            let matches = tx
+                .input()
+                .get(..4)
+                .is_some_and(|selector| selectors.contains(&Selector::from_slice(selector)));
+            if !matches {
+                return false
+            }
+        }
+        if self.min_priority_fee.is_some_and(|min| tx.priority_fee_or_price() < min) {
+            return false
+        }
+        if self.tx_types.as_ref().is_some_and(|types| !types.contains(&U8::from(tx.tx_type()))) {
+            return false
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, Bytes};
+    use reth_transaction_pool::test_utils::MockTransaction;
+
+    #[test]
+    fn deserialize_subscription_params() {
+        let params: SubscriptionParams = serde_json::from_str("true").unwrap();
+        assert_eq!(params, SubscriptionParams::Eth(Params::Bool(true)));
+
+        let params: SubscriptionParams =
+            serde_json::from_str(r#"{"address":"0x0000000000000000000000000000000000000001"}"#)
+                .unwrap();
+        assert!(matches!(params, SubscriptionParams::Eth(Params::Logs(_))));
+
+        // an empty object is both a logs and a pending transaction filter
+        assert!(serde_json::from_str::<SubscriptionParams>("{}").is_err());
+
+        let params: SubscriptionParams = serde_json::from_str(
+            r#"{
+                "fullTransactions": true,
+                "subpool": "queued",
+                "from": ["0x0000000000000000000000000000000000000001"],
+                "selectors": ["0xa9059cbb"],
+                "minPriorityFee": "0x3b9aca00",
+                "txTypes": ["0x2"]
+            }"#,
+        )
+        .unwrap();
+        let SubscriptionParams::PendingTransactions(filter) = params else {
+            panic!("expected pending transaction filter")
+        };
+        assert!(filter.full_transactions);
+        assert_eq!(filter.subpool(), SubPool::Queued);
+        assert_eq!(
+            filter.from,
+            Some(HashSet::from([address!("0000000000000000000000000000000000000001")]))
+        );
+        assert_eq!(
+            filter.selectors,
+            Some(HashSet::from([Selector::from([0xa9, 0x05, 0x9c, 0xbb])]))
+        );
+        assert_eq!(filter.min_priority_fee, Some(1_000_000_000));
+        assert_eq!(filter.tx_types, Some(HashSet::from([U8::from(2)])));
+    }
+
+    #[test]
+    fn pending_transaction_filter_matches() {
+        let tx = MockTransaction::eip1559()
+            .with_input(Bytes::from_static(&[0xa9, 0x05, 0x9c, 0xbb, 0x01]))
+            .with_priority_fee(10);
+        let sender = tx.get_sender();
+        let to = tx.to().unwrap();
+        let other = Address::random();
+
+        // an empty filter matches all transactions
+        assert!(PendingTransactionFilter::default().matches(&tx));
+
+        let filter =
+            PendingTransactionFilter { from: Some(HashSet::from([sender])), ..Default::default() };
+        assert!(filter.matches(&tx));
+        let filter =
+            PendingTransactionFilter { from: Some(HashSet::from([other])), ..Default::default() };
+        assert!(!filter.matches(&tx));
+
+        let filter =
+            PendingTransactionFilter { to: Some(HashSet::from([to])), ..Default::default() };
+        assert!(filter.matches(&tx));
+        let filter =
+            PendingTransactionFilter { to: Some(HashSet::from([other])), ..Default::default() };
+        assert!(!filter.matches(&tx));
+
+        let filter = PendingTransactionFilter {
+            selectors: Some(HashSet::from([Selector::from([0xa9, 0x05, 0x9c, 0xbb])])),
+            ..Default::default()
+        };
+        assert!(filter.matches(&tx));
+        // calldata shorter than a selector matches no selector
+        assert!(!filter.matches(&tx.clone().with_input(Bytes::from_static(&[0xa9, 0x05]))));
+        let filter = PendingTransactionFilter {
+            selectors: Some(HashSet::from([Selector::from([0x09, 0x5e, 0xa7, 0xb3])])),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&tx));
+
+        let filter = PendingTransactionFilter { min_priority_fee: Some(10), ..Default::default() };
+        assert!(filter.matches(&tx));
+        let filter = PendingTransactionFilter { min_priority_fee: Some(11), ..Default::default() };
+        assert!(!filter.matches(&tx));
+
+        let filter = PendingTransactionFilter {
+            tx_types: Some(HashSet::from([U8::from(2)])),
+            ..Default::default()
+        };
+        assert!(filter.matches(&tx));
+        assert!(!filter.matches(&MockTransaction::legacy()));
+
+        // all criteria must match
+        let filter = PendingTransactionFilter {
+            from: Some(HashSet::from([sender])),
+            min_priority_fee: Some(11),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&tx));
+    }
+}
@@ -98,6 +98,15 @@ impl RpcModuleSelection {
         }
     }
 
+    /// Returns true if the given module is part of the selection
+    pub fn contains(&self, module: &RethRpcModule) -> bool {
+        match self {
+            Self::All => true,
+            Self::Standard => Self::STANDARD_MODULES.contains(module),
+            Self::Selection(s) => s.contains(module),
+        }
+    }
+
     /// Returns an iterator over all configured [`RethRpcModule`]
     pub fn iter_selection(&self) -> Box<dyn Iterator<Item = RethRpcModule> + '_> {
         match self {
@@ -258,6 +267,10 @@ pub enum RethRpcModule {
     Reth,
     /// `ots_` module
     Ots,
+    /// `flashbots_` module
+    Flashbots,
+    /// `anvil_` module, only available on dev mode nodes
+    Anvil,
 }
 
 // === impl RethRpcModule ===
@@ -306,6 +319,8 @@ impl FromStr for RethRpcModule {
             "rpc" => Self::Rpc,
             "reth" => Self::Reth,
             "ots" => Self::Ots,
+            "flashbots" => Self::Flashbots,
+            "anvil" => Self::Anvil,
             _ => return Err(ParseError::VariantNotFound),
         })
     }
@@ -384,6 +399,17 @@ mod test {
         assert!(!non_empty_selection.is_empty());
     }
 
+    #[test]
+    fn test_rpc_module_selection_contains() {
+        let selection = RpcModuleSelection::from([RethRpcModule::Eth, RethRpcModule::Anvil]);
+        assert!(selection.contains(&RethRpcModule::Anvil));
+        assert!(!selection.contains(&RethRpcModule::Admin));
+
+        assert!(RpcModuleSelection::All.contains(&RethRpcModule::Anvil));
+        assert!(RpcModuleSelection::Standard.contains(&RethRpcModule::Net));
+        assert!(!RpcModuleSelection::Standard.contains(&RethRpcModule::Anvil));
+    }
+
     #[test]
     fn test_rpc_module_selection_iter_selection() {
         let all_modules = RpcModuleSelection::All;
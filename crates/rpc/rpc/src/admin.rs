@@ -1,4 +1,7 @@
-use std::sync::Arc;
+use std::{
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
 
 use alloy_genesis::ChainConfig;
 use alloy_rpc_types_admin::{
@@ -8,12 +11,20 @@ use alloy_rpc_types_admin::{
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, ForkCondition};
+use reth_db_common::snapshot::create_snapshot;
+use reth_db_models::SnapshotManifest;
 use reth_network_api::{NetworkInfo, Peers};
 use reth_network_peers::{id2pk, AnyNode, NodeRecord};
 use reth_network_types::PeerKind;
 use reth_primitives::EthereumHardfork;
-use reth_rpc_api::AdminApiServer;
-use reth_rpc_server_types::ToRpcResult;
+use reth_provider::{DatabaseProviderFactory, StaticFileProviderFactory};
+use reth_rpc_api::{AdminApiServer, AdminSnapshotApiServer};
+use reth_rpc_server_types::{
+    result::{internal_rpc_err, invalid_params_rpc_err},
+    ToRpcResult,
+};
+use reth_tasks::TaskSpawner;
+use tokio::sync::{oneshot, Mutex};
 
 /// `admin` API implementation.
 ///
@@ -192,3 +203,65 @@ impl<N, ChainSpec> std::fmt::Debug for AdminApi<N, ChainSpec> {
         f.debug_struct("AdminApi").finish_non_exhaustive()
     }
 }
+
+/// `admin` API implementation for taking snapshots of the database.
+///
+/// This type provides the functionality for handling `admin_snapshot` requests. Snapshots are only
+/// written to the configured snapshot directory, and only one snapshot is taken at a time.
+pub struct AdminSnapshot<Provider> {
+    /// The provider of the database and static files.
+    provider: Provider,
+    /// The directory that snapshots are written to.
+    snapshot_dir: PathBuf,
+    /// Held while a snapshot is being taken.
+    in_progress: Arc<Mutex<()>>,
+    /// The type that can spawn tasks which would otherwise block.
+    task_spawner: Box<dyn TaskSpawner>,
+}
+
+impl<Provider> AdminSnapshot<Provider> {
+    /// Creates a new instance of `AdminSnapshot` which writes snapshots to `snapshot_dir`.
+    pub fn new(
+        provider: Provider,
+        snapshot_dir: PathBuf,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        Self { provider, snapshot_dir, in_progress: Default::default(), task_spawner }
+    }
+}
+
+#[async_trait]
+impl<Provider> AdminSnapshotApiServer for AdminSnapshot<Provider>
+where
+    Provider: DatabaseProviderFactory + StaticFileProviderFactory + Clone + 'static,
+{
+    /// Handler for `admin_snapshot`
+    async fn snapshot(&self, name: String) -> RpcResult<SnapshotManifest> {
+        // The snapshot must be a direct child of the snapshot directory.
+        let mut components = Path::new(&name).components();
+        if !matches!((components.next(), components.next()), (Some(Component::Normal(_)), None)) {
+            return Err(invalid_params_rpc_err(format!("invalid snapshot name: {name}")))
+        }
+        let path = self.snapshot_dir.join(name);
+
+        let Ok(guard) = self.in_progress.clone().try_lock_owned() else {
+            return Err(internal_rpc_err("a snapshot is already being taken"))
+        };
+
+        let (tx, rx) = oneshot::channel();
+        let provider = self.provider.clone();
+        self.task_spawner.spawn_blocking(Box::pin(async move {
+            let _ = tx.send(create_snapshot(&provider, &path));
+            drop(guard);
+        }));
+        rx.await
+            .map_err(|_| internal_rpc_err("snapshot task was cancelled"))?
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+}
+
+impl<Provider> std::fmt::Debug for AdminSnapshot<Provider> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AdminSnapshot").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,1053 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    future::Future,
+    ops::RangeInclusive,
+    sync::Arc,
+    time::Duration,
+};
+
+use alloy_consensus::{TxEip4844Variant, TypedTransaction};
+use alloy_dyn_abi::TypedData;
+use alloy_primitives::{map::HashMap, Address, Bytes, Parity, B256, U256};
+use alloy_rpc_types::{Block, BlockTransactions, BlockTransactionsKind, TransactionRequest};
+use alloy_rpc_types_anvil::{
+    Forking, Metadata, MineOptions, NodeEnvironment, NodeForkConfig, NodeInfo,
+};
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use parking_lot::{Mutex, RwLock};
+use reth_chainspec::{EthChainSpec, EthereumHardfork, Hardforks, Head};
+use reth_engine_local::{LocalMinerHandle, MiningMode};
+use reth_evm::state_overlay::{AccountOverlay, BlockEnvOverlay, StateOverlay};
+use reth_primitives::{BlockNumberOrTag, SealedHeader, Signature, Transaction, TransactionSigned};
+use reth_provider::{
+    BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, HeaderProvider, ProviderResult,
+    StateProvider, StateProviderFactory, StorageChangeSetReader, TransactionVariant,
+};
+use reth_rpc_api::AnvilApiServer;
+use reth_rpc_eth_api::helpers::{signer::Result as SignResult, EthSigner};
+use reth_rpc_eth_types::{EthApiError, EthResult, SignError};
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_rpc_types_compat::block::from_block;
+use reth_tasks::TaskSpawner;
+use reth_transaction_pool::TransactionPool;
+use serde::{Deserialize, Serialize};
+use tokio::sync::oneshot;
+
+use crate::eth::EthTxBuilder;
+
+/// `anvil` API implementation for dev mode nodes.
+///
+/// Mining and time related methods are forwarded to the [`LocalMinerHandle`] of the local engine.
+///
+/// Methods that modify state (`anvil_setBalance`, `anvil_loadState`, ...) queue the changes in the
+/// [`StateOverlay`] of the node and mine a block that applies them, so they are visible right away,
+/// as with anvil. Since the chain can't be rewound, `anvil_revert` and `anvil_reset` restore the
+/// state of the targeted block in a new block instead.
+///
+/// Impersonated accounts are served by [`ImpersonatedAccounts`], which is registered as a signer of
+/// the `eth` namespace. Changing the chain id is not supported, it is fixed by the chain spec.
+pub struct AnvilApi<Provider, Pool> {
+    inner: Arc<AnvilApiInner<Provider, Pool>>,
+}
+
+// === impl AnvilApi ===
+
+impl<Provider, Pool> AnvilApi<Provider, Pool> {
+    /// Create a new instance of the [`AnvilApi`]
+    pub fn new(
+        provider: Provider,
+        pool: Pool,
+        miner: LocalMinerHandle,
+        state_overlay: StateOverlay,
+        impersonated: ImpersonatedAccounts,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        let inner = Arc::new(AnvilApiInner {
+            provider,
+            pool,
+            miner,
+            state_overlay,
+            impersonated,
+            task_spawner,
+            snapshots: Default::default(),
+        });
+        Self { inner }
+    }
+
+    /// The provider that can interact with the chain.
+    pub fn provider(&self) -> &Provider {
+        &self.inner.provider
+    }
+
+    /// The transaction pool of the node.
+    pub fn pool(&self) -> &Pool {
+        &self.inner.pool
+    }
+
+    /// Handle to the local miner.
+    pub fn miner(&self) -> &LocalMinerHandle {
+        &self.inner.miner
+    }
+
+    /// The out-of-band state changes applied by the local miner.
+    pub fn state_overlay(&self) -> &StateOverlay {
+        &self.inner.state_overlay
+    }
+
+    /// The accounts impersonated with `anvil_impersonateAccount`.
+    pub fn impersonated(&self) -> &ImpersonatedAccounts {
+        &self.inner.impersonated
+    }
+}
+
+impl<Provider, Pool> AnvilApi<Provider, Pool>
+where
+    Provider: BlockReaderIdExt
+        + HeaderProvider
+        + StateProviderFactory
+        + ChangeSetReader
+        + StorageChangeSetReader
+        + ChainSpecProvider<ChainSpec: EthChainSpec + Hardforks>
+        + 'static,
+    Pool: TransactionPool + Clone + 'static,
+{
+    /// Executes the future on a new blocking task.
+    async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
+    where
+        C: FnOnce(Self) -> F,
+        F: Future<Output = EthResult<R>> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let this = self.clone();
+        let f = c(this);
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let res = f.await;
+            let _ = tx.send(res);
+        }));
+        rx.await.map_err(|_| EthApiError::InternalEthError)?
+    }
+
+    /// Mines the given number of blocks and returns their hashes.
+    async fn mine(&self, blocks: u64, interval: Option<u64>) -> RpcResult<Vec<B256>> {
+        self.miner().mine(blocks, interval).await.map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Queues the given account changes and mines a block that applies them.
+    async fn apply_state_changes(
+        &self,
+        changes: impl IntoIterator<Item = (Address, AccountOverlay)>,
+    ) -> RpcResult<()> {
+        for (address, changes) in changes {
+            self.state_overlay().update(address, changes);
+        }
+        self.mine(1, None).await?;
+        Ok(())
+    }
+
+    /// Returns the latest header.
+    fn latest_header(&self) -> RpcResult<SealedHeader> {
+        Ok(self
+            .provider()
+            .sealed_header_by_number_or_tag(BlockNumberOrTag::Latest)
+            .map_err(EthApiError::from)?
+            .ok_or(EthApiError::HeaderNotFound(BlockNumberOrTag::Latest.into()))?)
+    }
+
+    /// Restores the state after the given block in a new block.
+    ///
+    /// Changes queued but not mined yet are discarded.
+    async fn restore_state(&self, block: SealedHeader) -> RpcResult<()> {
+        let changes = self
+            .on_blocking_task(|this| async move {
+                let provider = this.provider();
+                let latest = provider.best_block_number()?;
+                let touched = touched_state(provider, block.number + 1..=latest)?;
+                let target = provider.state_by_block_hash(block.hash())?;
+                let current = provider.latest()?;
+                Ok(state_diff(&*target, &*current, touched)?)
+            })
+            .await?;
+        self.state_overlay().set_pending(Default::default());
+        if changes.is_empty() {
+            return Ok(())
+        }
+        self.apply_state_changes(changes).await
+    }
+
+    /// Returns the full rpc block for the given hash.
+    fn rpc_block(&self, hash: B256) -> RpcResult<Block> {
+        let provider = self.provider();
+        let block = provider
+            .block_with_senders(hash.into(), TransactionVariant::WithHash)
+            .map_err(EthApiError::from)?
+            .ok_or(EthApiError::HeaderNotFound(hash.into()))?;
+        let total_difficulty = provider
+            .header_td_by_number(block.number)
+            .map_err(EthApiError::from)?
+            .unwrap_or_default();
+        let block = from_block::<EthTxBuilder>(
+            block,
+            total_difficulty,
+            BlockTransactionsKind::Full,
+            Some(hash),
+        )
+        .map_err(|err| internal_rpc_err(err.to_string()))?;
+
+        // the anvil namespace returns plain transactions without any additional fields
+        let transactions = match block.transactions {
+            BlockTransactions::Full(txs) => {
+                BlockTransactions::Full(txs.into_iter().map(|tx| tx.inner).collect())
+            }
+            BlockTransactions::Hashes(hashes) => BlockTransactions::Hashes(hashes),
+            BlockTransactions::Uncle => BlockTransactions::Uncle,
+        };
+        Ok(Block {
+            header: block.header,
+            uncles: block.uncles,
+            transactions,
+            size: block.size,
+            withdrawals: block.withdrawals,
+        })
+    }
+}
+
+#[async_trait]
+impl<Provider, Pool> AnvilApiServer for AnvilApi<Provider, Pool>
+where
+    Provider: BlockReaderIdExt
+        + HeaderProvider
+        + StateProviderFactory
+        + ChangeSetReader
+        + StorageChangeSetReader
+        + ChainSpecProvider<ChainSpec: EthChainSpec + Hardforks>
+        + 'static,
+    Pool: TransactionPool + Clone + 'static,
+{
+    /// Handler for `anvil_impersonateAccount`
+    async fn anvil_impersonate_account(&self, address: Address) -> RpcResult<()> {
+        self.impersonated().impersonate(address);
+        Ok(())
+    }
+
+    /// Handler for `anvil_stopImpersonatingAccount`
+    async fn anvil_stop_impersonating_account(&self, address: Address) -> RpcResult<()> {
+        self.impersonated().stop_impersonating(address);
+        Ok(())
+    }
+
+    /// Handler for `anvil_autoImpersonateAccount`
+    async fn anvil_auto_impersonate_account(&self, enabled: bool) -> RpcResult<()> {
+        self.impersonated().set_auto_impersonate(enabled);
+        Ok(())
+    }
+
+    /// Handler for `anvil_getAutomine`
+    async fn anvil_get_automine(&self) -> RpcResult<bool> {
+        self.miner().is_instant().await.map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_mine`
+    async fn anvil_mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        let blocks = blocks.map(|blocks| blocks.saturating_to()).unwrap_or(1);
+        let interval = interval.map(|interval| interval.saturating_to());
+        self.mine(blocks, interval).await?;
+        Ok(())
+    }
+
+    /// Handler for `anvil_setAutomine`
+    async fn anvil_set_automine(&self, enabled: bool) -> RpcResult<()> {
+        let mode =
+            if enabled { MiningMode::instant(self.pool().clone()) } else { MiningMode::Manual };
+        self.miner().set_mining_mode(mode).map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_setIntervalMining`
+    async fn anvil_set_interval_mining(&self, interval: u64) -> RpcResult<()> {
+        let mode = if interval == 0 {
+            MiningMode::Manual
+        } else {
+            MiningMode::interval(Duration::from_secs(interval))
+        };
+        self.miner().set_mining_mode(mode).map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_dropTransaction`
+    async fn anvil_drop_transaction(&self, tx_hash: B256) -> RpcResult<Option<B256>> {
+        Ok(self.pool().remove_transactions(vec![tx_hash]).first().map(|tx| *tx.hash()))
+    }
+
+    /// Handler for `anvil_reset`
+    ///
+    /// Restores the genesis state in a new block, forking is not supported.
+    async fn anvil_reset(&self, fork: Option<Forking>) -> RpcResult<()> {
+        if fork.is_some_and(|fork| fork.json_rpc_url.is_some()) {
+            return Err(EthApiError::Unsupported("anvil_reset with forking").into())
+        }
+        let genesis = self
+            .provider()
+            .sealed_header(0)
+            .map_err(EthApiError::from)?
+            .ok_or(EthApiError::HeaderNotFound(BlockNumberOrTag::Earliest.into()))?;
+        self.inner.snapshots.lock().entries.clear();
+        self.state_overlay().update_block_env(|env| *env = BlockEnvOverlay::default());
+        self.restore_state(genesis).await
+    }
+
+    async fn anvil_set_rpc_url(&self, _url: String) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setRpcUrl").into())
+    }
+
+    /// Handler for `anvil_setBalance`
+    async fn anvil_set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        let changes = AccountOverlay { balance: Some(balance), ..Default::default() };
+        self.apply_state_changes([(address, changes)]).await
+    }
+
+    /// Handler for `anvil_setCode`
+    async fn anvil_set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        ensure_deployable_code(&code)?;
+        let changes = AccountOverlay { code: Some(code), ..Default::default() };
+        self.apply_state_changes([(address, changes)]).await
+    }
+
+    /// Handler for `anvil_setNonce`
+    async fn anvil_set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        let nonce: u64 =
+            nonce.try_into().map_err(|_| invalid_params_rpc_err("nonce overflows u64"))?;
+        let changes = AccountOverlay { nonce: Some(nonce), ..Default::default() };
+        self.apply_state_changes([(address, changes)]).await
+    }
+
+    /// Handler for `anvil_setStorageAt`
+    async fn anvil_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: B256,
+    ) -> RpcResult<bool> {
+        let changes = AccountOverlay {
+            storage: HashMap::from_iter([(slot, U256::from_be_bytes(value.0))]),
+            ..Default::default()
+        };
+        self.apply_state_changes([(address, changes)]).await?;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_setCoinbase`
+    async fn anvil_set_coinbase(&self, address: Address) -> RpcResult<()> {
+        self.state_overlay().update_block_env(|env| env.coinbase = Some(address));
+        Ok(())
+    }
+
+    async fn anvil_set_chain_id(&self, _chain_id: u64) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setChainId").into())
+    }
+
+    async fn anvil_set_logging_enabled(&self, _enabled: bool) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setLoggingEnabled").into())
+    }
+
+    /// Handler for `anvil_setMinGasPrice`
+    async fn anvil_set_min_gas_price(&self, _gas_price: U256) -> RpcResult<()> {
+        let header = self.latest_header()?;
+        if self
+            .provider()
+            .chain_spec()
+            .is_fork_active_at_block(EthereumHardfork::London, header.number + 1)
+        {
+            // same as anvil, the minimum gas price is replaced by the base fee
+            return Err(invalid_params_rpc_err(
+                "anvil_setMinGasPrice is not supported when EIP-1559 is active",
+            ))
+        }
+        Err(EthApiError::Unsupported("anvil_setMinGasPrice").into())
+    }
+
+    /// Handler for `anvil_setNextBlockBaseFeePerGas`
+    async fn anvil_set_next_block_base_fee_per_gas(&self, base_fee: U256) -> RpcResult<()> {
+        let base_fee: u64 =
+            base_fee.try_into().map_err(|_| invalid_params_rpc_err("base fee overflows u64"))?;
+        self.state_overlay().update_block_env(|env| env.base_fee = Some(base_fee));
+        Ok(())
+    }
+
+    /// Handler for `anvil_setTime`
+    async fn anvil_set_time(&self, timestamp: u64) -> RpcResult<u64> {
+        self.miner().set_time(timestamp).map_err(|err| internal_rpc_err(err.to_string()))?;
+        Ok(timestamp)
+    }
+
+    /// Handler for `anvil_dumpState`
+    ///
+    /// Unlike anvil, the state is returned as uncompressed json.
+    async fn anvil_dump_state(&self) -> RpcResult<Bytes> {
+        let state = self
+            .on_blocking_task(|this| async move {
+                let provider = this.provider();
+                let latest = provider.best_block_number()?;
+                // every account and slot ever written shows up in a changeset, including the
+                // genesis allocations
+                let touched = touched_state(provider, 0..=latest)?;
+                let accounts = dump_accounts(&*provider.latest()?, touched)?;
+                Ok(SerializableState { best_block_number: Some(latest), accounts })
+            })
+            .await?;
+        let json = serde_json::to_vec(&state).map_err(|err| internal_rpc_err(err.to_string()))?;
+        Ok(json.into())
+    }
+
+    /// Handler for `anvil_loadState`
+    async fn anvil_load_state(&self, state: Bytes) -> RpcResult<bool> {
+        if state.starts_with(&[0x1f, 0x8b]) {
+            return Err(invalid_params_rpc_err("gzip compressed state is not supported"))
+        }
+        let state: SerializableState = serde_json::from_slice(&state)
+            .map_err(|err| invalid_params_rpc_err(format!("invalid state: {err}")))?;
+        let changes = state
+            .accounts
+            .into_iter()
+            .map(|(address, account)| {
+                ensure_deployable_code(&account.code)?;
+                Ok((address, account.into()))
+            })
+            .collect::<RpcResult<Vec<_>>>()?;
+        self.apply_state_changes(changes).await?;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_nodeInfo`
+    async fn anvil_node_info(&self) -> RpcResult<NodeInfo> {
+        let provider = self.provider();
+        let header = provider
+            .sealed_header_by_number_or_tag(BlockNumberOrTag::Latest)
+            .map_err(EthApiError::from)?
+            .ok_or(EthApiError::HeaderNotFound(BlockNumberOrTag::Latest.into()))?;
+        let total_difficulty = provider
+            .header_td_by_number(header.number)
+            .map_err(EthApiError::from)?
+            .unwrap_or_default();
+        let head = Head {
+            number: header.number,
+            hash: header.hash(),
+            difficulty: header.difficulty,
+            total_difficulty,
+            timestamp: header.timestamp,
+        };
+        let chain_spec = provider.chain_spec();
+        let hard_fork = chain_spec
+            .forks_iter()
+            .filter(|(_, condition)| condition.active_at_head(&head))
+            .last()
+            .map(|(fork, _)| fork.name().to_string())
+            .unwrap_or_default();
+        let base_fee = U256::from(header.base_fee_per_gas.unwrap_or_default());
+
+        Ok(NodeInfo {
+            current_block_number: header.number,
+            current_block_timestamp: header.timestamp,
+            current_block_hash: header.hash(),
+            hard_fork,
+            transaction_order: "fees".to_string(),
+            environment: NodeEnvironment {
+                base_fee,
+                chain_id: chain_spec.chain().id(),
+                gas_limit: U256::from(header.gas_limit),
+                gas_price: base_fee,
+            },
+            fork_config: NodeForkConfig::default(),
+        })
+    }
+
+    /// Handler for `anvil_metadata`
+    async fn anvil_metadata(&self) -> RpcResult<Metadata> {
+        let provider = self.provider();
+        let header = self.latest_header()?;
+        let snapshots = self
+            .inner
+            .snapshots
+            .lock()
+            .entries
+            .iter()
+            .map(|(id, snapshot)| (*id, (snapshot.block.number, snapshot.block.hash())))
+            .collect();
+
+        Ok(Metadata {
+            client_version: format!("reth/v{}", env!("CARGO_PKG_VERSION")),
+            chain_id: provider.chain_spec().chain().id(),
+            instance_id: B256::ZERO,
+            latest_block_number: header.number,
+            latest_block_hash: header.hash(),
+            forked_network: None,
+            snapshots,
+        })
+    }
+
+    /// Handler for `anvil_snapshot`
+    async fn anvil_snapshot(&self) -> RpcResult<U256> {
+        let block = self.latest_header()?;
+        let block_env = self.state_overlay().block_env();
+        let mut snapshots = self.inner.snapshots.lock();
+        let id = snapshots.next_id;
+        snapshots.next_id += U256::from(1);
+        snapshots.entries.insert(id, Snapshot { block, block_env });
+        Ok(id)
+    }
+
+    /// Handler for `anvil_revert`
+    ///
+    /// Removes the snapshot and all snapshots taken after it.
+    async fn anvil_revert(&self, id: U256) -> RpcResult<bool> {
+        let snapshot = {
+            let mut snapshots = self.inner.snapshots.lock();
+            let Some(snapshot) = snapshots.entries.remove(&id) else { return Ok(false) };
+            snapshots.entries.retain(|other, _| *other < id);
+            snapshot
+        };
+        self.state_overlay().update_block_env(|env| *env = snapshot.block_env);
+        self.restore_state(snapshot.block).await?;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_increaseTime`
+    async fn anvil_increase_time(&self, seconds: U256) -> RpcResult<i64> {
+        self.miner()
+            .increase_time(seconds.saturating_to())
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_setNextBlockTimestamp`
+    async fn anvil_set_next_block_timestamp(&self, seconds: u64) -> RpcResult<()> {
+        self.miner()
+            .set_next_block_timestamp(seconds)
+            .await
+            .map_err(|err| invalid_params_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_setBlockGasLimit`
+    async fn anvil_set_block_gas_limit(&self, gas_limit: U256) -> RpcResult<bool> {
+        let gas_limit: u64 =
+            gas_limit.try_into().map_err(|_| invalid_params_rpc_err("gas limit overflows u64"))?;
+        self.state_overlay().update_block_env(|env| env.gas_limit = Some(gas_limit));
+        Ok(true)
+    }
+
+    /// Handler for `anvil_setBlockTimestampInterval`
+    async fn anvil_set_block_timestamp_interval(&self, seconds: u64) -> RpcResult<()> {
+        self.miner()
+            .set_block_timestamp_interval(Some(seconds))
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))?;
+        Ok(())
+    }
+
+    /// Handler for `anvil_removeBlockTimestampInterval`
+    async fn anvil_remove_block_timestamp_interval(&self) -> RpcResult<bool> {
+        self.miner()
+            .set_block_timestamp_interval(None)
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_mine_detailed`
+    async fn anvil_mine_detailed(&self, opts: Option<MineOptions>) -> RpcResult<Vec<Block>> {
+        let (timestamp, blocks) = match opts.unwrap_or_default() {
+            MineOptions::Options { timestamp, blocks } => (timestamp, blocks.unwrap_or(1)),
+            MineOptions::Timestamp(timestamp) => (timestamp, 1),
+        };
+        if let Some(timestamp) = timestamp {
+            self.miner()
+                .set_next_block_timestamp(timestamp)
+                .await
+                .map_err(|err| invalid_params_rpc_err(err.to_string()))?;
+        }
+
+        let hashes = self.mine(blocks, None).await?;
+        hashes.into_iter().map(|hash| self.rpc_block(hash)).collect()
+    }
+
+    /// Handler for `anvil_enableTraces`
+    ///
+    /// Traces are always available through the `debug` and `trace` namespaces.
+    async fn anvil_enable_traces(&self) -> RpcResult<()> {
+        Ok(())
+    }
+
+    /// Handler for `anvil_removePoolTransactions`
+    async fn anvil_remove_pool_transactions(&self, address: Address) -> RpcResult<()> {
+        self.pool().remove_transactions_by_sender(address);
+        Ok(())
+    }
+}
+
+impl<Provider, Pool> std::fmt::Debug for AnvilApi<Provider, Pool> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnvilApi").finish_non_exhaustive()
+    }
+}
+
+impl<Provider, Pool> Clone for AnvilApi<Provider, Pool> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct AnvilApiInner<Provider, Pool> {
+    /// The provider that can interact with the chain.
+    provider: Provider,
+    /// The transaction pool of the node.
+    pool: Pool,
+    /// Handle to the local miner driving the dev chain.
+    miner: LocalMinerHandle,
+    /// Out-of-band state changes applied by the local miner.
+    state_overlay: StateOverlay,
+    /// Accounts impersonated with `anvil_impersonateAccount`.
+    impersonated: ImpersonatedAccounts,
+    /// The type that can spawn tasks which would otherwise block.
+    task_spawner: Box<dyn TaskSpawner>,
+    /// Snapshots taken by `anvil_snapshot`.
+    snapshots: Mutex<Snapshots>,
+}
+
+/// Accounts impersonated with `anvil_impersonateAccount`.
+///
+/// This is registered as a signer of the `eth` namespace, so `eth_sendTransaction` accepts
+/// transactions from impersonated accounts. They are signed with a placeholder signature derived
+/// from the sender and added to the pool with the impersonated sender, the local miner hands the
+/// blocks it builds to the engine together with their senders.
+///
+/// Methods that recover the sender from the signature of a mined transaction, e.g.
+/// `eth_getTransactionReceipt`, don't return the impersonated sender.
+///
+/// Cloning is cheap, all clones share the same accounts.
+#[derive(Debug, Clone, Default)]
+pub struct ImpersonatedAccounts {
+    inner: Arc<RwLock<ImpersonatedAccountsInner>>,
+}
+
+#[derive(Debug, Default)]
+struct ImpersonatedAccountsInner {
+    /// Accounts impersonated explicitly.
+    accounts: HashSet<Address>,
+    /// Whether all accounts are impersonated.
+    auto_impersonate: bool,
+}
+
+impl ImpersonatedAccounts {
+    /// Starts impersonating the given account.
+    pub fn impersonate(&self, address: Address) {
+        self.inner.write().accounts.insert(address);
+    }
+
+    /// Stops impersonating the given account.
+    pub fn stop_impersonating(&self, address: Address) {
+        self.inner.write().accounts.remove(&address);
+    }
+
+    /// Enables or disables the impersonation of all accounts.
+    pub fn set_auto_impersonate(&self, enabled: bool) {
+        self.inner.write().auto_impersonate = enabled;
+    }
+
+    /// Returns `true` if the given account is impersonated.
+    pub fn is_impersonated(&self, address: &Address) -> bool {
+        let inner = self.inner.read();
+        inner.auto_impersonate || inner.accounts.contains(address)
+    }
+}
+
+#[async_trait]
+impl EthSigner for ImpersonatedAccounts {
+    fn accounts(&self) -> Vec<Address> {
+        self.inner.read().accounts.iter().copied().collect()
+    }
+
+    fn is_signer_for(&self, addr: &Address) -> bool {
+        self.is_impersonated(addr)
+    }
+
+    async fn sign(&self, _address: Address, _message: &[u8]) -> SignResult<Signature> {
+        Err(SignError::CouldNotSign)
+    }
+
+    async fn sign_transaction(
+        &self,
+        request: TransactionRequest,
+        address: &Address,
+    ) -> SignResult<TransactionSigned> {
+        let tx = request.build_typed_tx().map_err(|_| SignError::InvalidTransactionRequest)?;
+        let transaction = match tx {
+            TypedTransaction::Legacy(tx) => Transaction::Legacy(tx),
+            TypedTransaction::Eip2930(tx) => Transaction::Eip2930(tx),
+            TypedTransaction::Eip1559(tx) => Transaction::Eip1559(tx),
+            TypedTransaction::Eip4844(tx) => Transaction::Eip4844(match tx {
+                TxEip4844Variant::TxEip4844(tx) => tx,
+                TxEip4844Variant::TxEip4844WithSidecar(tx) => tx.tx,
+            }),
+            TypedTransaction::Eip7702(tx) => Transaction::Eip7702(tx),
+        };
+        Ok(TransactionSigned::from_transaction_and_signature(
+            transaction,
+            impersonated_signature(address),
+        ))
+    }
+
+    fn sign_typed_data(&self, _address: Address, _payload: &TypedData) -> SignResult<Signature> {
+        Err(SignError::CouldNotSign)
+    }
+}
+
+/// Returns the placeholder signature of transactions sent by an impersonated account.
+///
+/// The signature is derived from the sender, so identical transactions of different impersonated
+/// accounts have different hashes.
+fn impersonated_signature(address: &Address) -> Signature {
+    Signature::new(U256::from_be_slice(address.as_slice()), U256::from(1), Parity::Parity(false))
+}
+
+/// Snapshots taken by `anvil_snapshot`, keyed by their id.
+#[derive(Debug, Default)]
+struct Snapshots {
+    next_id: U256,
+    entries: BTreeMap<U256, Snapshot>,
+}
+
+/// The chain at the time of an `anvil_snapshot` call.
+#[derive(Debug)]
+struct Snapshot {
+    /// The latest block.
+    block: SealedHeader,
+    /// The overrides of the block environment.
+    block_env: BlockEnvOverlay,
+}
+
+/// State as returned by `anvil_dumpState` and accepted by `anvil_loadState`.
+///
+/// This is a subset of the format used by anvil, other fields are ignored.
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct SerializableState {
+    /// The number of the block the state was dumped at.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    best_block_number: Option<u64>,
+    /// All accounts of the state.
+    accounts: BTreeMap<Address, SerializableAccountRecord>,
+}
+
+/// An account of a [`SerializableState`].
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct SerializableAccountRecord {
+    nonce: u64,
+    balance: U256,
+    code: Bytes,
+    #[serde(default)]
+    storage: BTreeMap<U256, U256>,
+}
+
+impl From<SerializableAccountRecord> for AccountOverlay {
+    fn from(account: SerializableAccountRecord) -> Self {
+        Self {
+            balance: Some(account.balance),
+            nonce: Some(account.nonce),
+            code: Some(account.code),
+            storage: account.storage.into_iter().collect(),
+        }
+    }
+}
+
+/// Rejects code starting with the `0xEF` byte, which can't be deployed since EIP-3541.
+fn ensure_deployable_code(code: &Bytes) -> RpcResult<()> {
+    if code.first() == Some(&0xEF) {
+        return Err(invalid_params_rpc_err("code starting with the 0xEF byte is not supported"))
+    }
+    Ok(())
+}
+
+/// Collects all accounts and storage slots changed by the blocks in the given range.
+fn touched_state<Provider>(
+    provider: &Provider,
+    range: RangeInclusive<u64>,
+) -> ProviderResult<BTreeMap<Address, BTreeSet<B256>>>
+where
+    Provider: ChangeSetReader + StorageChangeSetReader,
+{
+    let mut touched = BTreeMap::<Address, BTreeSet<B256>>::new();
+    for number in range {
+        for change in provider.account_block_changeset(number)? {
+            touched.entry(change.address).or_default();
+        }
+        for (key, entry) in provider.storage_changeset(number)? {
+            touched.entry(key.address()).or_default().insert(entry.key);
+        }
+    }
+    Ok(touched)
+}
+
+/// Returns the changes that bring the given accounts and slots from the `current` state back to
+/// the `target` state.
+fn state_diff(
+    target: &dyn StateProvider,
+    current: &dyn StateProvider,
+    touched: BTreeMap<Address, BTreeSet<B256>>,
+) -> ProviderResult<Vec<(Address, AccountOverlay)>> {
+    let mut changes = Vec::new();
+    for (address, slots) in touched {
+        let before = target.basic_account(address)?.unwrap_or_default();
+        let after = current.basic_account(address)?.unwrap_or_default();
+
+        let mut account = AccountOverlay::default();
+        if before.balance != after.balance {
+            account.balance = Some(before.balance);
+        }
+        if before.nonce != after.nonce {
+            account.nonce = Some(before.nonce);
+        }
+        if before.get_bytecode_hash() != after.get_bytecode_hash() {
+            account.code = Some(account_code(target, before.bytecode_hash)?);
+        }
+        for slot in slots {
+            let value = target.storage(address, slot)?.unwrap_or_default();
+            if value != current.storage(address, slot)?.unwrap_or_default() {
+                account.storage.insert(U256::from_be_bytes(slot.0), value);
+            }
+        }
+
+        if account != AccountOverlay::default() {
+            changes.push((address, account));
+        }
+    }
+    Ok(changes)
+}
+
+/// Returns the given accounts with all their non-zero slots in the given set.
+fn dump_accounts(
+    state: &dyn StateProvider,
+    touched: BTreeMap<Address, BTreeSet<B256>>,
+) -> ProviderResult<BTreeMap<Address, SerializableAccountRecord>> {
+    let mut accounts = BTreeMap::new();
+    for (address, slots) in touched {
+        let Some(account) = state.basic_account(address)? else { continue };
+        let mut storage = BTreeMap::new();
+        for slot in slots {
+            if let Some(value) = state.storage(address, slot)?.filter(|value| !value.is_zero()) {
+                storage.insert(U256::from_be_bytes(slot.0), value);
+            }
+        }
+        accounts.insert(
+            address,
+            SerializableAccountRecord {
+                nonce: account.nonce,
+                balance: account.balance,
+                code: account_code(state, account.bytecode_hash)?,
+                storage,
+            },
+        );
+    }
+    Ok(accounts)
+}
+
+/// Returns the code with the given hash, or empty code for accounts without code.
+fn account_code(state: &dyn StateProvider, code_hash: Option<B256>) -> ProviderResult<Bytes> {
+    let Some(code_hash) = code_hash else { return Ok(Bytes::new()) };
+    Ok(state.bytecode_by_hash(code_hash)?.map(|code| code.original_bytes()).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+
+    const CODE: Bytes = Bytes::from_static(&[0x60, 0x00]);
+
+    fn touched(
+        accounts: impl IntoIterator<Item = (Address, Vec<B256>)>,
+    ) -> BTreeMap<Address, BTreeSet<B256>> {
+        accounts
+            .into_iter()
+            .map(|(address, slots)| (address, slots.into_iter().collect()))
+            .collect()
+    }
+
+    #[test]
+    fn state_diff_restores_target() {
+        let unchanged = Address::with_last_byte(1);
+        let modified = Address::with_last_byte(2);
+        let created = Address::with_last_byte(3);
+        let slot = B256::with_last_byte(1);
+        let new_slot = B256::with_last_byte(2);
+
+        let target = MockEthProvider::default();
+        target.add_account(unchanged, ExtendedAccount::new(1, U256::from(1)));
+        target.add_account(
+            modified,
+            ExtendedAccount::new(1, U256::from(10))
+                .with_bytecode(CODE)
+                .extend_storage([(slot, U256::from(1))]),
+        );
+
+        let current = MockEthProvider::default();
+        current.add_account(unchanged, ExtendedAccount::new(1, U256::from(1)));
+        current.add_account(
+            modified,
+            ExtendedAccount::new(1, U256::from(20))
+                .extend_storage([(slot, U256::from(2)), (new_slot, U256::from(3))]),
+        );
+        current.add_account(created, ExtendedAccount::new(2, U256::from(5)));
+
+        let changes = state_diff(
+            &target,
+            &current,
+            touched([(unchanged, vec![]), (modified, vec![slot, new_slot]), (created, vec![])]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    modified,
+                    AccountOverlay {
+                        balance: Some(U256::from(10)),
+                        nonce: None,
+                        code: Some(CODE),
+                        storage: HashMap::from_iter([
+                            (U256::from(1), U256::from(1)),
+                            (U256::from(2), U256::ZERO),
+                        ]),
+                    }
+                ),
+                (
+                    created,
+                    AccountOverlay {
+                        balance: Some(U256::ZERO),
+                        nonce: Some(0),
+                        ..Default::default()
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn dump_and_load_state() {
+        let contract = Address::with_last_byte(1);
+        let eoa = Address::with_last_byte(2);
+        let slot = B256::with_last_byte(1);
+        let cleared_slot = B256::with_last_byte(2);
+
+        let state = MockEthProvider::default();
+        state.add_account(
+            contract,
+            ExtendedAccount::new(1, U256::ZERO)
+                .with_bytecode(CODE)
+                .extend_storage([(slot, U256::from(7)), (cleared_slot, U256::ZERO)]),
+        );
+        state.add_account(eoa, ExtendedAccount::new(3, U256::from(100)));
+
+        let accounts = dump_accounts(
+            &state,
+            touched([
+                (contract, vec![slot, cleared_slot]),
+                (eoa, vec![]),
+                (Address::with_last_byte(3), vec![]),
+            ]),
+        )
+        .unwrap();
+        let dump = SerializableState { best_block_number: Some(1), accounts };
+        let json = serde_json::to_vec(&dump).unwrap();
+        assert_eq!(serde_json::from_slice::<SerializableState>(&json).unwrap(), dump);
+
+        let changes = dump.accounts.into_iter().map(|(address, account)| (address, account.into()));
+        assert_eq!(
+            changes.collect::<Vec<(Address, AccountOverlay)>>(),
+            vec![
+                (
+                    contract,
+                    AccountOverlay {
+                        balance: Some(U256::ZERO),
+                        nonce: Some(1),
+                        code: Some(CODE),
+                        storage: HashMap::from_iter([(U256::from(1), U256::from(7))]),
+                    }
+                ),
+                (
+                    eoa,
+                    AccountOverlay {
+                        balance: Some(U256::from(100)),
+                        nonce: Some(3),
+                        code: Some(Bytes::new()),
+                        storage: Default::default(),
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_anvil_state() {
+        let json = r#"{
+            "block": { "number": "0x1" },
+            "accounts": {
+                "0x0000000000000000000000000000000000000001": {
+                    "nonce": 1,
+                    "balance": "0x10",
+                    "code": "0x6000",
+                    "storage": { "0x1": "0x2" }
+                }
+            },
+            "best_block_number": 5,
+            "blocks": [],
+            "transactions": []
+        }"#;
+        let state: SerializableState = serde_json::from_str(json).unwrap();
+        assert_eq!(state.best_block_number, Some(5));
+        assert_eq!(
+            state.accounts.get(&Address::with_last_byte(1)),
+            Some(&SerializableAccountRecord {
+                nonce: 1,
+                balance: U256::from(16),
+                code: CODE,
+                storage: BTreeMap::from([(U256::from(1), U256::from(2))]),
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_undeployable_code() {
+        assert!(ensure_deployable_code(&CODE).is_ok());
+        assert!(ensure_deployable_code(&Bytes::new()).is_ok());
+        assert!(ensure_deployable_code(&Bytes::from_static(&[0xef, 0x00, 0x01])).is_err());
+    }
+
+    #[tokio::test]
+    async fn impersonated_accounts() {
+        let alice = Address::with_last_byte(1);
+        let bob = Address::with_last_byte(2);
+        let impersonated = ImpersonatedAccounts::default();
+        assert!(!impersonated.is_signer_for(&alice));
+
+        impersonated.impersonate(alice);
+        assert!(impersonated.is_signer_for(&alice));
+        assert!(!impersonated.is_signer_for(&bob));
+        assert_eq!(impersonated.accounts(), vec![alice]);
+
+        impersonated.set_auto_impersonate(true);
+        assert!(impersonated.is_signer_for(&bob));
+        impersonated.set_auto_impersonate(false);
+        impersonated.stop_impersonating(alice);
+        assert!(!impersonated.is_signer_for(&alice));
+
+        // the same request sent by different accounts results in different transactions
+        let request = TransactionRequest::default()
+            .to(bob)
+            .nonce(0)
+            .gas_limit(21_000)
+            .max_fee_per_gas(1)
+            .max_priority_fee_per_gas(1);
+        let from_alice = impersonated.sign_transaction(request.clone(), &alice).await.unwrap();
+        let from_bob = impersonated.sign_transaction(request, &bob).await.unwrap();
+        assert_ne!(from_alice.hash(), from_bob.hash());
+        assert!(impersonated.sign(alice, b"message").await.is_err());
+    }
+}
@@ -1,5 +1,6 @@
+use alloy_consensus::constants::KECCAK_EMPTY;
 use alloy_eips::eip2718::Encodable2718;
-use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_primitives::{hex, Address, Bytes, B256, U256};
 use alloy_rlp::{Decodable, Encodable};
 use alloy_rpc_types::{
     state::EvmOverrides, Block as RpcBlock, BlockError, Bundle, StateContext, TransactionInfo,
@@ -14,18 +15,24 @@ use alloy_rpc_types_trace::geth::{
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::EthereumHardforks;
+use reth_errors::ProviderResult;
 use reth_evm::{
     execute::{BlockExecutorProvider, Executor},
     system_calls::SystemCaller,
     ConfigureEvmEnv,
 };
-use reth_primitives::{Block, BlockId, BlockNumberOrTag, TransactionSignedEcRecovered};
+use reth_primitives::{
+    Block, BlockId, BlockNumberOrTag, SealedHeader, TransactionSignedEcRecovered,
+};
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, HeaderProvider, StateProofProvider, StateProviderFactory,
-    TransactionVariant,
+    AccountReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, HeaderProvider,
+    StateProofProvider, StateProvider, StateProviderFactory, StateRangeProvider, StateRootProvider,
+    StorageChangeSetReader, TransactionVariant,
 };
 use reth_revm::database::StateProviderDatabase;
-use reth_rpc_api::DebugApiServer;
+use reth_rpc_api::{
+    AccountRangeResult, DebugApiServer, DumpAccount, StorageRangeEntry, StorageRangeResult,
+};
 use reth_rpc_eth_api::{
     helpers::{Call, EthApiSpec, EthTransactions, LoadState, TraceExt},
     EthApiTypes, FromEthApiError,
@@ -35,16 +42,28 @@ use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
 use reth_tasks::pool::BlockingTaskGuard;
 use reth_trie::{HashedPostState, HashedStorage};
 use revm::{
-    db::{CacheDB, State},
+    db::{states::bundle_state::BundleRetention, AccountState, CacheDB, State},
     primitives::{db::DatabaseCommit, BlockEnv, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg},
 };
 use revm_inspectors::tracing::{
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig, TransactionContext,
 };
 use revm_primitives::{keccak256, HashMap};
-use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
+/// The maximum number of accounts returned by a single `debug_accountRange` call.
+const ACCOUNT_RANGE_MAX_RESULTS: u64 = 256;
+
+/// The maximum number of storage slots returned by a single `debug_accountRange` call.
+const ACCOUNT_RANGE_MAX_STORAGE_SLOTS: usize = 10_000;
+
+/// The maximum number of blocks a `debug_getModifiedAccountsBy*` call can span.
+const MODIFIED_ACCOUNTS_MAX_BLOCKS: u64 = 1_024;
+
 /// `debug` API implementation.
 ///
 /// This type provides the functionality for handling `debug` related requests.
@@ -81,6 +100,8 @@ where
         + HeaderProvider
         + ChainSpecProvider<ChainSpec: EthereumHardforks>
         + StateProviderFactory
+        + ChangeSetReader
+        + StorageChangeSetReader
         + 'static,
     Eth: EthApiTypes + TraceExt + 'static,
     BlockExecutor: BlockExecutorProvider,
@@ -690,6 +711,367 @@ where
             .await
     }
 
+    /// Returns the addresses of all accounts whose nonce, balance, code hash or storage differ
+    /// between the state after block `start` and the state after block `end`.
+    ///
+    /// If `end` is not provided, the accounts modified by block `start` itself are returned.
+    pub async fn debug_get_modified_accounts(
+        &self,
+        start: BlockId,
+        end: Option<BlockId>,
+    ) -> Result<Vec<Address>, Eth::Error> {
+        let (start, end) = match end {
+            Some(end) => (self.sealed_header(start)?, self.sealed_header(end)?),
+            None => {
+                let end = self.sealed_header(start)?;
+                (self.sealed_header(end.parent_hash.into())?, end)
+            }
+        };
+        if start.number >= end.number {
+            return Err(EthApiError::InvalidParams(format!(
+                "start block height ({}) must be less than end block height ({})",
+                start.number, end.number
+            ))
+            .into())
+        }
+        if end.number - start.number > MODIFIED_ACCOUNTS_MAX_BLOCKS {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range ({}) exceeds the maximum of {MODIFIED_ACCOUNTS_MAX_BLOCKS} blocks",
+                end.number - start.number
+            ))
+            .into())
+        }
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_blocking_io(move |_| {
+                let provider = &this.inner.provider;
+                let modified = || -> ProviderResult<Vec<Address>> {
+                    // collect all accounts and storage slots touched in (start, end]
+                    let mut touched = BTreeMap::<Address, BTreeSet<B256>>::new();
+                    for number in start.number + 1..=end.number {
+                        for change in provider.account_block_changeset(number)? {
+                            touched.entry(change.address).or_default();
+                        }
+                        for (key, entry) in provider.storage_changeset(number)? {
+                            touched.entry(key.address()).or_default().insert(entry.key);
+                        }
+                    }
+
+                    // an account touched in between might have been reverted to its original
+                    // state, so compare the state at both ends
+                    let before = provider.state_by_block_hash(start.hash())?;
+                    let after = provider.state_by_block_hash(end.hash())?;
+                    let mut modified = Vec::new();
+                    for (address, slots) in touched {
+                        let mut changed =
+                            before.basic_account(address)? != after.basic_account(address)?;
+                        for slot in slots {
+                            if changed {
+                                break
+                            }
+                            changed = before.storage(address, slot)?.unwrap_or_default() !=
+                                after.storage(address, slot)?.unwrap_or_default();
+                        }
+                        if changed {
+                            modified.push(address);
+                        }
+                    }
+                    Ok(modified)
+                };
+                modified().map_err(Eth::Error::from_eth_err)
+            })
+            .await
+    }
+
+    /// Returns the preimage of the given hash, `None` if it's unknown.
+    ///
+    /// Reth doesn't record the preimages of hashed trie keys, but contract code, headers and
+    /// transactions are looked up by their hash.
+    pub async fn debug_preimage(&self, hash: B256) -> Result<Option<Bytes>, Eth::Error> {
+        let this = self.clone();
+        self.eth_api()
+            .spawn_blocking_io(move |_| {
+                let provider = &this.inner.provider;
+                let preimage = || -> ProviderResult<Option<Bytes>> {
+                    if let Some(code) = provider.latest()?.bytecode_by_hash(hash)? {
+                        return Ok(Some(code.original_bytes()))
+                    }
+                    if let Some(header) = provider.header(&hash)? {
+                        let mut res = Vec::new();
+                        header.encode(&mut res);
+                        return Ok(Some(res.into()))
+                    }
+                    Ok(provider.transaction_by_hash(hash)?.map(|tx| tx.encoded_2718().into()))
+                };
+                preimage().map_err(Eth::Error::from_eth_err)
+            })
+            .await
+    }
+
+    /// Returns a page of the storage of `address` as it was right before transaction `tx_index`
+    /// of the given block was executed.
+    ///
+    /// The page is ordered by the hash of the storage key and starts at `key_start`.
+    pub async fn debug_storage_range_at(
+        &self,
+        block_hash: B256,
+        tx_index: usize,
+        address: Address,
+        key_start: B256,
+        max_result: usize,
+    ) -> Result<StorageRangeResult, Eth::Error> {
+        let block = self
+            .eth_api()
+            .block_with_senders(block_hash.into())
+            .await?
+            .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+        let target_tx_hash = match block.body.transactions.get(tx_index) {
+            Some(tx) => Some(tx.hash()),
+            None if tx_index == 0 && block.body.transactions.is_empty() => None,
+            None => {
+                return Err(EthApiError::InvalidParams(format!(
+                    "transaction index {tx_index} out of range for block {block_hash}"
+                ))
+                .into())
+            }
+        };
+        let (cfg, block_env, _) = self.eth_api().evm_env_at(block_hash.into()).await?;
+        let parent_beacon_block_root = block.parent_beacon_block_root;
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash.into(), move |state| {
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+                // apply relevant system calls
+                SystemCaller::new(
+                    Call::evm_config(this.eth_api()).clone(),
+                    LoadState::provider(this.eth_api()).chain_spec(),
+                )
+                .pre_block_beacon_root_contract_call(
+                    &mut db,
+                    &cfg,
+                    &block_env,
+                    parent_beacon_block_root,
+                )
+                .map_err(|_| {
+                    EthApiError::EvmCustom(
+                        "failed to apply 4788 beacon root system call".to_string(),
+                    )
+                })?;
+
+                // replay all transactions prior to the targeted transaction
+                if let Some(target_tx_hash) = target_tx_hash {
+                    this.eth_api().replay_transactions_until(
+                        &mut db,
+                        cfg,
+                        block_env,
+                        block.transactions_with_sender(),
+                        target_tx_hash,
+                    )?;
+                }
+
+                // overlay the storage changed by the replayed transactions, their keys are the
+                // only preimages we know
+                let mut preimages = HashMap::<B256, B256>::default();
+                let mut hashed_storage = HashedStorage::default();
+                if let Some(account) = db.accounts.get(&address) {
+                    hashed_storage.wiped = matches!(
+                        account.account_state,
+                        AccountState::StorageCleared | AccountState::NotExisting
+                    );
+                    for (slot, value) in &account.storage {
+                        let slot = B256::from(*slot);
+                        let hashed_slot = keccak256(slot);
+                        preimages.insert(hashed_slot, slot);
+                        hashed_storage.storage.insert(hashed_slot, *value);
+                    }
+                }
+
+                let range = db
+                    .db
+                    .storage_range(address, hashed_storage, key_start, max_result)
+                    .map_err(Eth::Error::from_eth_err)?;
+                Ok(StorageRangeResult {
+                    storage: range
+                        .slots
+                        .into_iter()
+                        .map(|(hashed_slot, value)| {
+                            let key = preimages.get(&hashed_slot).copied();
+                            (hashed_slot, StorageRangeEntry { key, value: value.into() })
+                        })
+                        .collect(),
+                    next_key: range.next_key,
+                })
+            })
+            .await
+    }
+
+    /// Returns a page of the accounts in the state after the given block, ordered by hashed
+    /// address and starting at `start`.
+    ///
+    /// Reth doesn't store address preimages, so all accounts are keyed by `pre(<hashed
+    /// address>)`. If `incompletes` is not set, such accounts are skipped and the page is empty.
+    ///
+    /// The page ends early if the storage of its accounts exceeds
+    /// [`ACCOUNT_RANGE_MAX_STORAGE_SLOTS`].
+    pub async fn debug_account_range(
+        &self,
+        block: BlockNumberOrTag,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> Result<AccountRangeResult, Eth::Error> {
+        let header = self.sealed_header(block.into())?;
+        if !incompletes {
+            return Ok(AccountRangeResult { root: header.state_root, ..Default::default() })
+        }
+
+        // same semantics as geth, the start key is right aligned and cropped from the left
+        let start = B256::left_padding_from(&start[start.len().saturating_sub(32)..]);
+        let limit = if max_results == 0 || max_results > ACCOUNT_RANGE_MAX_RESULTS {
+            ACCOUNT_RANGE_MAX_RESULTS
+        } else {
+            max_results
+        } as usize;
+
+        let storage_limit = if nostorage { 0 } else { ACCOUNT_RANGE_MAX_STORAGE_SLOTS };
+
+        self.eth_api()
+            .spawn_with_state_at_block(header.hash().into(), move |state| {
+                let range = state
+                    .account_range(Default::default(), start, limit, storage_limit)
+                    .map_err(Eth::Error::from_eth_err)?;
+
+                let mut accounts = BTreeMap::new();
+                for entry in range.accounts {
+                    if entry.storage_next_key.is_some() {
+                        return Err(EthApiError::InvalidParams(format!(
+                            "storage of account {} exceeds the maximum of \
+                             {ACCOUNT_RANGE_MAX_STORAGE_SLOTS} slots, retry with nostorage",
+                            entry.hashed_address
+                        ))
+                        .into())
+                    }
+                    let code_hash = entry.account.get_bytecode_hash();
+                    let code = if nocode || code_hash == KECCAK_EMPTY {
+                        None
+                    } else {
+                        state
+                            .bytecode_by_hash(code_hash)
+                            .map_err(Eth::Error::from_eth_err)?
+                            .map(|code| code.original_bytes())
+                    };
+                    let storage = (!entry.storage.is_empty()).then(|| {
+                        entry
+                            .storage
+                            .into_iter()
+                            .map(|(slot, value)| {
+                                (slot, hex::encode(value.to_be_bytes_trimmed_vec()))
+                            })
+                            .collect()
+                    });
+                    accounts.insert(
+                        format!("pre({})", entry.hashed_address),
+                        DumpAccount {
+                            balance: entry.account.balance.to_string(),
+                            nonce: entry.account.nonce,
+                            root: entry.storage_root.into(),
+                            code_hash: code_hash.into(),
+                            code,
+                            storage,
+                            address: None,
+                            address_hash: entry.hashed_address.into(),
+                        },
+                    );
+                }
+
+                Ok(AccountRangeResult {
+                    root: header.state_root,
+                    accounts,
+                    next: range.next_key.map(Into::into),
+                })
+            })
+            .await
+    }
+
+    /// Re-executes the block with the given hash and returns the state root after each of its
+    /// transactions.
+    pub async fn debug_intermediate_roots(
+        &self,
+        block_hash: B256,
+    ) -> Result<Vec<B256>, Eth::Error> {
+        let block = self
+            .eth_api()
+            .block_with_senders(block_hash.into())
+            .await?
+            .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+        let (cfg, block_env, _) = self.eth_api().evm_env_at(block_hash.into()).await?;
+        let parent_beacon_block_root = block.parent_beacon_block_root;
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash.into(), move |state_provider| {
+                let mut db = State::builder()
+                    .with_database(StateProviderDatabase::new(&state_provider))
+                    .with_bundle_update()
+                    .build();
+
+                // apply relevant system calls
+                SystemCaller::new(
+                    Call::evm_config(this.eth_api()).clone(),
+                    LoadState::provider(this.eth_api()).chain_spec(),
+                )
+                .pre_block_beacon_root_contract_call(
+                    &mut db,
+                    &cfg,
+                    &block_env,
+                    parent_beacon_block_root,
+                )
+                .map_err(|_| {
+                    EthApiError::EvmCustom(
+                        "failed to apply 4788 beacon root system call".to_string(),
+                    )
+                })?;
+
+                let mut roots = Vec::with_capacity(block.body.transactions.len());
+                for (signer, tx) in block.transactions_with_sender() {
+                    let env = EnvWithHandlerCfg {
+                        env: Env::boxed(
+                            cfg.cfg_env.clone(),
+                            block_env.clone(),
+                            Call::evm_config(this.eth_api()).tx_env(tx, *signer),
+                        ),
+                        handler_cfg: cfg.handler_cfg,
+                    };
+                    let (res, _) = this.eth_api().transact(&mut db, env)?;
+                    db.commit(res.state);
+                    db.merge_transitions(BundleRetention::PlainState);
+
+                    let hashed_state = HashedPostState::from_bundle_state(&db.bundle_state.state);
+                    roots.push(
+                        state_provider
+                            .state_root(hashed_state)
+                            .map_err(Eth::Error::from_eth_err)?,
+                    );
+                }
+                Ok(roots)
+            })
+            .await
+    }
+
+    /// Returns the header of the given block.
+    fn sealed_header(&self, block_id: BlockId) -> Result<SealedHeader, Eth::Error> {
+        self.inner
+            .provider
+            .sealed_header_by_id(block_id)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or_else(|| EthApiError::HeaderNotFound(block_id).into())
+    }
+
     /// Executes the configured transaction with the environment on the given database.
     ///
     /// Returns the trace frame and the state that got updated after executing the transaction.
@@ -841,6 +1223,8 @@ where
         + HeaderProvider
         + ChainSpecProvider<ChainSpec: EthereumHardforks>
         + StateProviderFactory
+        + ChangeSetReader
+        + StorageChangeSetReader
         + 'static,
     Eth: EthApiSpec + EthTransactions + TraceExt + 'static,
     BlockExecutor: BlockExecutorProvider,
@@ -1013,16 +1397,27 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_accountRange`
     async fn debug_account_range(
         &self,
-        _block_number: BlockNumberOrTag,
-        _start: Bytes,
-        _max_results: u64,
-        _nocode: bool,
-        _nostorage: bool,
-        _incompletes: bool,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_number: BlockNumberOrTag,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> RpcResult<AccountRangeResult> {
+        Self::debug_account_range(
+            self,
+            block_number,
+            start,
+            max_results,
+            nocode,
+            nostorage,
+            incompletes,
+        )
+        .await
+        .map_err(Into::into)
     }
 
     async fn debug_block_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
@@ -1077,32 +1472,42 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_getModifiedAccountsByHash`
     async fn debug_get_modified_accounts_by_hash(
         &self,
-        _start_hash: B256,
-        _end_hash: B256,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_hash: B256,
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_get_modified_accounts(self, start_hash.into(), end_hash.map(Into::into))
+            .await
+            .map_err(Into::into)
     }
 
+    /// Handler for `debug_getModifiedAccountsByNumber`
     async fn debug_get_modified_accounts_by_number(
         &self,
-        _start_number: u64,
-        _end_number: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_number: u64,
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_get_modified_accounts(self, start_number.into(), end_number.map(Into::into))
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_go_trace(&self, _file: String, _seconds: u64) -> RpcResult<()> {
         Ok(())
     }
 
+    /// Handler for `debug_intermediateRoots`
     async fn debug_intermediate_roots(
         &self,
-        _block_hash: B256,
+        block_hash: B256,
         _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+    ) -> RpcResult<Vec<B256>> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_intermediate_roots(self, block_hash).await.map_err(Into::into)
     }
 
     async fn debug_mem_stats(&self) -> RpcResult<()> {
@@ -1113,8 +1518,12 @@ where
         Ok(())
     }
 
-    async fn debug_preimage(&self, _hash: B256) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_preimage`
+    async fn debug_preimage(&self, hash: B256) -> RpcResult<Bytes> {
+        Self::debug_preimage(self, hash)
+            .await
+            .map_err(Into::into)
+            .and_then(|preimage| preimage.ok_or_else(|| internal_rpc_err("unknown preimage")))
     }
 
     async fn debug_print_block(&self, _number: u64) -> RpcResult<()> {
@@ -1181,15 +1590,26 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_storageRangeAt`
     async fn debug_storage_range_at(
         &self,
-        _block_hash: B256,
-        _tx_idx: usize,
-        _contract_address: Address,
-        _key_start: B256,
-        _max_result: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> RpcResult<StorageRangeResult> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_storage_range_at(
+            self,
+            block_hash,
+            tx_idx,
+            contract_address,
+            key_start,
+            max_result as usize,
+        )
+        .await
+        .map_err(Into::into)
     }
 
     async fn debug_trace_bad_block(
@@ -13,21 +13,28 @@ use alloy_rpc_types::{
 use alloy_serde::WithOtherFields;
 use futures::StreamExt;
 use jsonrpsee::{
-    server::SubscriptionMessage, types::ErrorObject, PendingSubscriptionSink, SubscriptionSink,
+    core::server::TrySendError, server::SubscriptionMessage, types::ErrorObject,
+    PendingSubscriptionSink, SubscriptionSink,
 };
 use reth_network_api::NetworkInfo;
 use reth_provider::{BlockReader, CanonStateSubscriptions, EvmEnvProvider};
 use reth_rpc_eth_api::{pubsub::EthPubSubApiServer, FullEthApiTypes, RpcTransaction};
-use reth_rpc_eth_types::logs_utils;
+use reth_rpc_eth_types::{logs_utils, PendingTransactionFilter, SubscriptionParams};
 use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
 use reth_rpc_types_compat::transaction::from_recovered;
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
-use reth_transaction_pool::{NewTransactionEvent, TransactionPool};
+use reth_transaction_pool::{
+    NewSubpoolTransactionStream, NewTransactionEvent, TransactionPool, ValidPoolTransaction,
+};
 use serde::Serialize;
 use tokio_stream::{
     wrappers::{BroadcastStream, ReceiverStream},
     Stream,
 };
+use tracing::debug;
+
+/// Maximum number of consecutive items a lossy subscription may drop before it's closed.
+const MAX_CONSECUTIVE_DROPPED_ITEMS: usize = 1024;
 
 /// `Eth` pubsub RPC implementation.
 ///
@@ -85,7 +92,7 @@ where
         &self,
         pending: PendingSubscriptionSink,
         kind: SubscriptionKind,
-        params: Option<Params>,
+        params: Option<SubscriptionParams>,
     ) -> jsonrpsee::core::SubscriptionResult {
         let sink = pending.accept().await?;
         let pubsub = self.inner.clone();
@@ -102,7 +109,7 @@ async fn handle_accepted<Provider, Pool, Events, Network, Eth>(
     pubsub: Arc<EthPubSubInner<Provider, Pool, Events, Network>>,
     accepted_sink: SubscriptionSink,
     kind: SubscriptionKind,
-    params: Option<Params>,
+    params: Option<SubscriptionParams>,
 ) -> Result<(), ErrorObject<'static>>
 where
     Provider: BlockReader + EvmEnvProvider + Clone + 'static,
@@ -123,10 +130,13 @@ where
         SubscriptionKind::Logs => {
             // if no params are provided, used default filter params
             let filter = match params {
-                Some(Params::Logs(filter)) => FilteredParams::new(Some(*filter)),
-                Some(Params::Bool(_)) => {
-                    return Err(invalid_params_rpc_err("Invalid params for logs"))
+                Some(SubscriptionParams::Eth(Params::Logs(filter))) => {
+                    FilteredParams::new(Some(*filter))
                 }
+                Some(
+                    SubscriptionParams::Eth(Params::Bool(_)) |
+                    SubscriptionParams::PendingTransactions(_),
+                ) => return Err(invalid_params_rpc_err("Invalid params for logs")),
                 _ => FilteredParams::default(),
             };
             let stream = pubsub.log_stream(filter).map(|log| {
@@ -137,7 +147,28 @@ where
         SubscriptionKind::NewPendingTransactions => {
             if let Some(params) = params {
                 match params {
-                    Params::Bool(true) => {
+                    SubscriptionParams::PendingTransactions(filter) => {
+                        // filtered transactions, matched before they're serialized
+                        let full_transactions = filter.full_transactions;
+                        let stream = pubsub.filtered_pending_transaction_stream(*filter);
+                        if full_transactions {
+                            let stream = stream.map(|tx| {
+                                EthSubscriptionResult::FullTransaction(Box::new(from_recovered::<
+                                    Eth::TransactionCompat,
+                                >(
+                                    tx.to_recovered_transaction(),
+                                )))
+                            });
+                            return pipe_from_stream_lossy(accepted_sink, stream).await
+                        }
+                        let stream = stream.map(|tx| {
+                            EthSubscriptionResult::<WithOtherFields<Transaction>>::TransactionHash(
+                                *tx.hash(),
+                            )
+                        });
+                        return pipe_from_stream_lossy(accepted_sink, stream).await
+                    }
+                    SubscriptionParams::Eth(Params::Bool(true)) => {
                         // full transaction objects requested
                         let stream = pubsub.full_pending_transaction_stream().map(|tx| {
                             EthSubscriptionResult::FullTransaction(Box::new(from_recovered::<
@@ -148,10 +179,10 @@ where
                         });
                         return pipe_from_stream(accepted_sink, stream).await
                     }
-                    Params::Bool(false) | Params::None => {
+                    SubscriptionParams::Eth(Params::Bool(false) | Params::None) => {
                         // only hashes requested
                     }
-                    Params::Logs(_) => {
+                    SubscriptionParams::Eth(Params::Logs(_)) => {
                         return Err(invalid_params_rpc_err(
                             "Invalid params for newPendingTransactions",
                         ))
@@ -201,6 +232,37 @@ where
     }
 }
 
+/// Pipes all stream items to the subscription sink without waiting for a slow subscriber.
+///
+/// Items that don't fit into the subscription's buffer are dropped. The subscription is closed
+/// once more than [`MAX_CONSECUTIVE_DROPPED_ITEMS`] consecutive items were dropped.
+async fn pipe_from_stream_lossy<T, St>(
+    mut sink: SubscriptionSink,
+    mut stream: St,
+) -> Result<(), ErrorObject<'static>>
+where
+    St: Stream<Item = T> + Unpin,
+    T: Serialize,
+{
+    let mut dropped = 0;
+    while let Some(item) = stream.next().await {
+        let msg = SubscriptionMessage::from_json(&item).map_err(SubscriptionSerializeError::new)?;
+        match sink.try_send(msg) {
+            Ok(()) => dropped = 0,
+            Err(TrySendError::Full(_)) => {
+                dropped += 1;
+                if dropped > MAX_CONSECUTIVE_DROPPED_ITEMS {
+                    debug!(target: "rpc::eth", method = sink.method_name(), "closing subscription of slow subscriber");
+                    break
+                }
+            }
+            // connection dropped
+            Err(TrySendError::Closed(_)) => break,
+        }
+    }
+    Ok(())
+}
+
 /// Helper to convert a serde error into an [`ErrorObject`]
 #[derive(Debug, thiserror::Error)]
 #[error("Failed to serialize subscription item: {0}")]
@@ -219,7 +281,7 @@ impl From<SubscriptionSerializeError> for ErrorObject<'static> {
 }
 
 /// Pipes all stream items to the subscription sink.
-async fn pipe_from_stream<T, St>(
+pub(crate) async fn pipe_from_stream<T, St>(
     sink: SubscriptionSink,
     mut stream: St,
 ) -> Result<(), ErrorObject<'static>>
@@ -303,6 +365,20 @@ where
         ReceiverStream::new(self.pool.pending_transactions_listener())
     }
 
+    /// Returns a stream that yields all transactions added to the filter's sub-pool that match the
+    /// filter.
+    fn filtered_pending_transaction_stream(
+        &self,
+        filter: PendingTransactionFilter,
+    ) -> impl Stream<Item = Arc<ValidPoolTransaction<<Pool as TransactionPool>::Transaction>>> {
+        NewSubpoolTransactionStream::new(self.pool.new_transactions_listener(), filter.subpool())
+            .filter_map(move |event| {
+                futures::future::ready(
+                    filter.matches(&event.transaction.transaction).then_some(event.transaction),
+                )
+            })
+    }
+
     /// Returns a stream that yields all transactions emitted by the txpool.
     fn full_pending_transaction_stream(
         &self,
@@ -346,3 +422,60 @@ where
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::{core::SubscriptionResult, rpc_params, RpcModule};
+
+    /// Returns a module with a lossy subscription that yields the given items.
+    fn lossy_module<I>(items: I) -> RpcModule<()>
+    where
+        I: Iterator<Item = u64> + Clone + Send + Sync + 'static,
+    {
+        let mut module = RpcModule::new(());
+        module
+            .register_subscription(
+                "subscribe_items",
+                "s_items",
+                "unsubscribe_items",
+                move |_, pending, _, _| {
+                    let items = items.clone();
+                    async move {
+                        let sink = pending.accept().await?;
+                        pipe_from_stream_lossy(sink, futures::stream::iter(items)).await?;
+                        SubscriptionResult::Ok(())
+                    }
+                },
+            )
+            .unwrap();
+        module
+    }
+
+    /// Collects all items of the subscription until it's closed.
+    async fn collect_items(module: &RpcModule<()>, buf_size: usize) -> Vec<u64> {
+        let mut sub = module.subscribe("subscribe_items", rpc_params![], buf_size).await.unwrap();
+        let mut items = Vec::new();
+        while let Some(item) = sub.next::<u64>().await {
+            items.push(item.unwrap().0);
+        }
+        items
+    }
+
+    #[tokio::test]
+    async fn lossy_pipe_forwards_all_items_to_fast_subscriber() {
+        let module = lossy_module(0..10);
+        assert_eq!(collect_items(&module, 16).await, (0..10).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn lossy_pipe_closes_slow_subscription() {
+        // the stream never ends, so the subscription is only closed because items are dropped
+        let module = lossy_module(0..);
+        let items = collect_items(&module, 4).await;
+
+        // items that didn't fit into the buffer were dropped instead of waiting for the subscriber
+        assert!(items.len() <= 4);
+        assert_eq!(items, (0..items.len() as u64).collect::<Vec<_>>());
+    }
+}
@@ -1,6 +1,6 @@
 use alloy_consensus::Transaction;
 use alloy_network::{ReceiptResponse, TransactionResponse};
-use alloy_primitives::{Address, Bytes, TxHash, B256, U256};
+use alloy_primitives::{Address, BlockNumber, Bytes, TxHash, TxNumber, B256, U256};
 use alloy_rpc_types::{BlockTransactions, Header, TransactionReceipt};
 use alloy_rpc_types_trace::{
     otterscan::{
@@ -12,6 +12,7 @@ use alloy_rpc_types_trace::{
 use async_trait::async_trait;
 use jsonrpsee::{core::RpcResult, types::ErrorObjectOwned};
 use reth_primitives::{BlockId, BlockNumberOrTag};
+use reth_provider::{AddressTransactionsReader, BlockReader, StageCheckpointReader};
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
@@ -19,28 +20,38 @@ use reth_rpc_eth_api::{
 };
 use reth_rpc_eth_types::{utils::binary_search, EthApiError};
 use reth_rpc_server_types::result::internal_rpc_err;
+use reth_stages_types::StageId;
 use revm_inspectors::{
     tracing::{types::CallTraceNode, TracingInspectorConfig},
     transfer::{TransferInspector, TransferKind},
 };
 use revm_primitives::{ExecutionResult, SignedAuthorization};
+use std::ops::{Range, RangeInclusive};
 
 const API_LEVEL: u64 = 8;
 
+/// Maximum number of blocks past the address transactions index that are traced in order to find
+/// the transactions of an address.
+///
+/// The index is advanced by the engine once blocks are persisted, so only the blocks that are not
+/// persisted yet, or that were not indexed because the index lags behind, have to be traced.
+const MAX_TRACED_BLOCKS: u64 = 1_024;
+
 /// Otterscan API.
 #[derive(Debug)]
-pub struct OtterscanApi<Eth> {
+pub struct OtterscanApi<Provider, Eth> {
+    provider: Provider,
     eth: Eth,
 }
 
-impl<Eth> OtterscanApi<Eth> {
+impl<Provider, Eth> OtterscanApi<Provider, Eth> {
     /// Creates a new instance of `Otterscan`.
-    pub const fn new(eth: Eth) -> Self {
-        Self { eth }
+    pub const fn new(provider: Provider, eth: Eth) -> Self {
+        Self { provider, eth }
     }
 }
 
-impl<Eth> OtterscanApi<Eth>
+impl<Provider, Eth> OtterscanApi<Provider, Eth>
 where
     Eth: FullEthApiTypes,
 {
@@ -60,9 +71,261 @@ where
     }
 }
 
+impl<Provider, Eth> OtterscanApi<Provider, Eth>
+where
+    Provider: BlockReader + AddressTransactionsReader,
+{
+    /// Looks up the transactions `address` took part in within the given block range in the
+    /// address transactions index. See [`Self::find_address_transactions`].
+    fn indexed_address_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+        reverse: bool,
+    ) -> RpcResult<Vec<(BlockNumber, Vec<usize>)>> {
+        if range.is_empty() || limit == 0 {
+            return Ok(Vec::new())
+        }
+
+        let first_tx = self.block_tx_range(*range.start())?.start;
+        let next_tx = self.block_tx_range(*range.end())?.end;
+        if first_tx == next_tx {
+            return Ok(Vec::new())
+        }
+
+        let lookup = |range: RangeInclusive<TxNumber>, limit: usize| {
+            if reverse {
+                self.provider.address_transactions_rev(address, range, limit)
+            } else {
+                self.provider.address_transactions(address, range, limit)
+            }
+            .map_err(EthApiError::from)
+        };
+
+        let mut tx_numbers = lookup(first_tx..=next_tx - 1, limit)?;
+
+        // Complete the last block, so that it's not split across pages.
+        if let Some(&last) = tx_numbers.last().filter(|_| tx_numbers.len() == limit) {
+            let tx_range = self.block_tx_range(self.transaction_block(last)?)?;
+            let rest = if reverse {
+                (last > tx_range.start).then(|| tx_range.start..=last - 1)
+            } else {
+                (last + 1 < tx_range.end).then(|| last + 1..=tx_range.end - 1)
+            };
+            if let Some(rest) = rest {
+                tx_numbers.extend(lookup(rest, usize::MAX)?);
+            }
+        }
+
+        let mut blocks: Vec<(BlockNumber, Vec<usize>)> = Vec::new();
+        let mut current_range = 0..0;
+        for tx_number in tx_numbers {
+            if !current_range.contains(&tx_number) {
+                let block_number = self.transaction_block(tx_number)?;
+                current_range = self.block_tx_range(block_number)?;
+                blocks.push((block_number, Vec::new()));
+            }
+            if let Some((_, indices)) = blocks.last_mut() {
+                indices.push((tx_number - current_range.start) as usize);
+            }
+        }
+
+        Ok(blocks)
+    }
+
+    /// Returns the range of transaction numbers of the given block.
+    fn block_tx_range(&self, block_number: BlockNumber) -> RpcResult<Range<TxNumber>> {
+        Ok(self
+            .provider
+            .block_body_indices(block_number)
+            .map_err(EthApiError::from)?
+            .ok_or(EthApiError::HeaderNotFound(block_number.into()))?
+            .tx_num_range())
+    }
+
+    /// Returns the number of the block containing the given transaction.
+    fn transaction_block(&self, tx_number: TxNumber) -> RpcResult<BlockNumber> {
+        Ok(self
+            .provider
+            .transaction_block(tx_number)
+            .map_err(EthApiError::from)?
+            .ok_or(EthApiError::TransactionNotFound)?)
+    }
+}
+
+impl<Provider, Eth> OtterscanApi<Provider, Eth>
+where
+    Provider: BlockReader + StageCheckpointReader + AddressTransactionsReader,
+    Eth: EthApiServer<
+            RpcTransaction<Eth::NetworkTypes>,
+            RpcBlock<Eth::NetworkTypes>,
+            RpcReceipt<Eth::NetworkTypes>,
+        > + TraceExt
+        + 'static,
+{
+    /// Returns the blocks within `range` that contain transactions `address` took part in, along
+    /// with the indices of these transactions within the block.
+    ///
+    /// Blocks are visited in descending order if `reverse` is set, and in ascending order
+    /// otherwise, until at least `limit` transactions were found. The transactions of a block are
+    /// never split across pages, so more than `limit` transactions may be returned.
+    ///
+    /// Blocks covered by the [`StageId::IndexAddressTransactions`] checkpoint are looked up in the
+    /// address transactions index. More recent blocks, which were not indexed yet, are traced
+    /// instead, as long as there are at most [`MAX_TRACED_BLOCKS`] of them.
+    async fn find_address_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+        reverse: bool,
+    ) -> RpcResult<Vec<(BlockNumber, Vec<usize>)>> {
+        let indexed_until = self
+            .provider
+            .get_stage_checkpoint(StageId::IndexAddressTransactions)
+            .map_err(EthApiError::from)?
+            .ok_or_else(|| internal_rpc_err("address transactions index is not enabled"))?
+            .block_number;
+
+        let (start, end) = range.into_inner();
+        let indexed = start..=end.min(indexed_until);
+        let traced = indexed_until.checked_add(1).map_or(1..=0, |next| start.max(next)..=end);
+
+        let mut blocks = if reverse {
+            self.traced_address_transactions(address, traced.clone(), limit, reverse).await?
+        } else {
+            self.indexed_address_transactions(address, indexed.clone(), limit, reverse)?
+        };
+
+        let found = count_transactions(&blocks);
+        if found < limit {
+            let remaining = limit - found;
+            blocks.extend(if reverse {
+                self.indexed_address_transactions(address, indexed, remaining, reverse)?
+            } else {
+                self.traced_address_transactions(address, traced, remaining, reverse).await?
+            });
+        }
+
+        Ok(blocks)
+    }
+
+    /// Traces the given blocks in order to find the transactions `address` took part in, until
+    /// at least `limit` transactions were found. See [`Self::find_address_transactions`].
+    ///
+    /// Returns an error if the range spans more than [`MAX_TRACED_BLOCKS`] blocks.
+    async fn traced_address_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+        reverse: bool,
+    ) -> RpcResult<Vec<(BlockNumber, Vec<usize>)>> {
+        if range.is_empty() || limit == 0 {
+            return Ok(Vec::new())
+        }
+        let (start, end) = range.into_inner();
+        if end - start >= MAX_TRACED_BLOCKS {
+            return Err(internal_rpc_err(format!(
+                "{} blocks are not covered by the address transactions index, at most \
+                 {MAX_TRACED_BLOCKS} blocks can be traced",
+                end - start + 1
+            )))
+        }
+
+        let mut found = Vec::new();
+        let mut count = 0;
+        for offset in 0..=end - start {
+            if count >= limit {
+                break
+            }
+            let block_number = if reverse { end - offset } else { start + offset };
+
+            let indices = self
+                .eth
+                .trace_block_with(
+                    block_number.into(),
+                    None,
+                    TracingInspectorConfig::default_parity(),
+                    move |tx_info, inspector, _, _, _| {
+                        // The callee of a delegate call is recorded as the address of its trace,
+                        // and the contract it runs in as the caller.
+                        let took_part = inspector.traces().nodes().iter().any(|node| {
+                            node.trace.caller == address ||
+                                node.trace.address == address ||
+                                node.trace.selfdestruct_refund_target == Some(address)
+                        });
+                        Ok(tx_info.index.filter(|_| took_part))
+                    },
+                )
+                .await
+                .map_err(Into::into)?
+                .unwrap_or_default()
+                .into_iter()
+                .flatten()
+                .map(|index| index as usize)
+                .collect::<Vec<_>>();
+
+            if !indices.is_empty() {
+                count += indices.len();
+                found.push((block_number, indices));
+            }
+        }
+
+        Ok(found)
+    }
+
+    /// Fetches the given transactions of the given blocks along with their receipts, ordered from
+    /// the most recent to the oldest transaction, as expected by Otterscan.
+    async fn transactions_with_receipts(
+        &self,
+        mut blocks: Vec<(BlockNumber, Vec<usize>)>,
+        first_page: bool,
+        last_page: bool,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        blocks.sort_unstable_by(|(a, _), (b, _)| b.cmp(a));
+
+        let mut txs = Vec::with_capacity(count_transactions(&blocks));
+        let mut receipts = Vec::with_capacity(txs.capacity());
+        for (block_number, mut indices) in blocks {
+            indices.sort_unstable_by(|a, b| b.cmp(a));
+
+            let block_id = block_number.into();
+            let block = self.eth.block_by_number(block_id, true);
+            let block_id = block_id.into();
+            let block_receipts = self.eth.block_receipts(block_id);
+            let (block, block_receipts) = futures::try_join!(block, block_receipts)?;
+
+            let block = block.ok_or(EthApiError::HeaderNotFound(block_id))?;
+            let mut block_receipts =
+                block_receipts.ok_or(EthApiError::ReceiptsNotFound(block_id))?;
+            let BlockTransactions::Full(transactions) = block.transactions else {
+                return Err(internal_rpc_err("block is not full"));
+            };
+
+            let timestamp = Some(block.header.timestamp);
+            for index in indices {
+                let (Some(tx), Some(receipt)) =
+                    (transactions.get(index), block_receipts.get_mut(index))
+                else {
+                    return Err(EthApiError::TransactionNotFound.into())
+                };
+                let tx_ty = Eth::TransactionCompat::tx_type(tx);
+                receipts.push(ots_receipt(receipt.clone(), tx_ty, timestamp));
+                txs.push(tx.clone());
+            }
+        }
+
+        Ok(TransactionsWithReceipts { txs, receipts, first_page, last_page })
+    }
+}
+
 #[async_trait]
-impl<Eth> OtterscanServer<RpcTransaction<Eth::NetworkTypes>> for OtterscanApi<Eth>
+impl<Provider, Eth> OtterscanServer<RpcTransaction<Eth::NetworkTypes>>
+    for OtterscanApi<Provider, Eth>
 where
+    Provider: BlockReader + StageCheckpointReader + AddressTransactionsReader + 'static,
     Eth: EthApiServer<
             RpcTransaction<Eth::NetworkTypes>,
             RpcBlock<Eth::NetworkTypes>,
@@ -239,35 +502,7 @@ where
         let receipts = receipts
             .drain(page_start..page_end)
             .zip(transactions.iter().map(Eth::TransactionCompat::tx_type))
-            .map(|(receipt, tx_ty)| {
-                let inner = OtsReceipt {
-                    status: receipt.status(),
-                    cumulative_gas_used: receipt.cumulative_gas_used() as u64,
-                    logs: None,
-                    logs_bloom: None,
-                    r#type: tx_ty,
-                };
-
-                let receipt = TransactionReceipt {
-                    inner,
-                    transaction_hash: receipt.transaction_hash(),
-                    transaction_index: receipt.transaction_index(),
-                    block_hash: receipt.block_hash(),
-                    block_number: receipt.block_number(),
-                    gas_used: receipt.gas_used(),
-                    effective_gas_price: receipt.effective_gas_price(),
-                    blob_gas_used: receipt.blob_gas_used(),
-                    blob_gas_price: receipt.blob_gas_price(),
-                    from: receipt.from(),
-                    to: receipt.to(),
-                    contract_address: receipt.contract_address(),
-                    authorization_list: receipt
-                        .authorization_list()
-                        .map(<[SignedAuthorization]>::to_vec),
-                };
-
-                OtsTransactionReceipt { receipt, timestamp }
-            })
+            .map(|(receipt, tx_ty)| ots_receipt(receipt, tx_ty, timestamp))
             .collect();
 
         // use `transaction_count` to indicate the paginate information
@@ -279,21 +514,51 @@ where
     /// Handler for `searchTransactionsBefore`
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: u64,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: u64,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        let best = self.provider.best_block_number().map_err(EthApiError::from)?;
+        // block number 0 requests the most recent transactions
+        let is_first_page = block_number == 0;
+        let end = if is_first_page { best } else { (block_number - 1).min(best) };
+
+        let blocks = self.find_address_transactions(address, 0..=end, page_size, true).await?;
+        let is_last_page = match blocks.last() {
+            Some((oldest, _)) if count_transactions(&blocks) >= page_size && *oldest > 0 => {
+                self.find_address_transactions(address, 0..=oldest - 1, 1, true).await?.is_empty()
+            }
+            _ => true,
+        };
+
+        self.transactions_with_receipts(blocks, is_first_page, is_last_page).await
     }
 
     /// Handler for `searchTransactionsAfter`
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: u64,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: u64,
+        page_size: usize,
+    ) -> RpcResult<TransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        let best = self.provider.best_block_number().map_err(EthApiError::from)?;
+        // block number 0 requests the oldest transactions
+        let is_last_page = block_number == 0;
+        let Some(start) = block_number.checked_add(1) else {
+            return Err(EthApiError::InvalidParams("block number is too large".to_string()).into())
+        };
+
+        let blocks =
+            self.find_address_transactions(address, start..=best, page_size, false).await?;
+        let is_first_page = match blocks.last() {
+            Some((newest, _)) if count_transactions(&blocks) >= page_size && *newest < best => self
+                .find_address_transactions(address, newest + 1..=best, 1, false)
+                .await?
+                .is_empty(),
+            _ => true,
+        };
+
+        self.transactions_with_receipts(blocks, is_first_page, is_last_page).await
     }
 
     /// Handler for `getTransactionBySenderAndNonce`
@@ -374,3 +639,95 @@ where
         Ok(found)
     }
 }
+
+/// Returns the total number of transactions in the given blocks.
+fn count_transactions(blocks: &[(BlockNumber, Vec<usize>)]) -> usize {
+    blocks.iter().map(|(_, indices)| indices.len()).sum()
+}
+
+/// Converts a receipt into the trimmed down receipt format used by Otterscan.
+fn ots_receipt<R: ReceiptResponse>(
+    receipt: R,
+    tx_ty: u8,
+    timestamp: Option<u64>,
+) -> OtsTransactionReceipt {
+    let inner = OtsReceipt {
+        status: receipt.status(),
+        cumulative_gas_used: receipt.cumulative_gas_used() as u64,
+        logs: None,
+        logs_bloom: None,
+        r#type: tx_ty,
+    };
+
+    let receipt = TransactionReceipt {
+        inner,
+        transaction_hash: receipt.transaction_hash(),
+        transaction_index: receipt.transaction_index(),
+        block_hash: receipt.block_hash(),
+        block_number: receipt.block_number(),
+        gas_used: receipt.gas_used(),
+        effective_gas_price: receipt.effective_gas_price(),
+        blob_gas_used: receipt.blob_gas_used(),
+        blob_gas_price: receipt.blob_gas_price(),
+        from: receipt.from(),
+        to: receipt.to(),
+        contract_address: receipt.contract_address(),
+        authorization_list: receipt.authorization_list().map(<[SignedAuthorization]>::to_vec),
+    };
+
+    OtsTransactionReceipt { receipt, timestamp }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::{tables, BlockNumberList};
+    use reth_db_api::{models::ShardedKey, transaction::DbTxMut};
+    use reth_db_models::StoredBlockBodyIndices;
+    use reth_provider::{
+        test_utils::create_test_provider_factory, DBProvider, DatabaseProviderFactory,
+    };
+
+    #[test]
+    fn indexed_pages_dont_split_blocks() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+
+        // block 1 contains transactions 0..3, block 2 contains 3..5 and block 3 contains 5..8
+        let provider_rw = factory.database_provider_rw().unwrap();
+        let tx = provider_rw.tx_ref();
+        for (block_number, first_tx_num, tx_count) in [(0, 0, 0), (1, 0, 3), (2, 3, 2), (3, 5, 3)] {
+            tx.put::<tables::BlockBodyIndices>(
+                block_number,
+                StoredBlockBodyIndices { first_tx_num, tx_count },
+            )
+            .unwrap();
+            if tx_count > 0 {
+                tx.put::<tables::TransactionBlocks>(first_tx_num + tx_count - 1, block_number)
+                    .unwrap();
+            }
+        }
+        tx.put::<tables::AddressTransactions>(
+            ShardedKey::new(address, u64::MAX),
+            BlockNumberList::new_pre_sorted([0, 2, 3, 4, 6]),
+        )
+        .unwrap();
+        provider_rw.commit().unwrap();
+
+        let api = OtterscanApi::new(factory.provider().unwrap(), ());
+
+        // the page is filled in the middle of block 2, which is completed
+        let page = api.indexed_address_transactions(address, 0..=3, 2, true).unwrap();
+        assert_eq!(page, vec![(3, vec![1]), (2, vec![1, 0])]);
+        // the next page starts below the oldest block of the previous page
+        let page = api.indexed_address_transactions(address, 0..=1, 2, true).unwrap();
+        assert_eq!(page, vec![(1, vec![2, 0])]);
+
+        let page = api.indexed_address_transactions(address, 0..=3, 1, false).unwrap();
+        assert_eq!(page, vec![(1, vec![0, 2])]);
+        let page = api.indexed_address_transactions(address, 2..=3, 1, false).unwrap();
+        assert_eq!(page, vec![(2, vec![0, 1])]);
+        let page = api.indexed_address_transactions(address, 3..=3, 1, false).unwrap();
+        assert_eq!(page, vec![(3, vec![1])]);
+    }
+}
@@ -1,16 +1,23 @@
 use std::{collections::HashMap, future::Future, sync::Arc};
 
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{Address, B256, U256};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
+use reth_db_models::{StoredBlobSidecar, StoredBlockBlobSidecars};
 use reth_errors::RethResult;
 use reth_primitives::BlockId;
-use reth_provider::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
+use reth_provider::{
+    BlobSidecarsProvider, BlockNumReader, BlockReaderIdExt, ChangeSetReader, StateProviderFactory,
+};
 use reth_rpc_api::RethApiServer;
 use reth_rpc_eth_types::{EthApiError, EthResult};
 use reth_tasks::TaskSpawner;
 use tokio::sync::oneshot;
 
+/// The max number of blocks searched for a blob by `reth_getBlobSidecarByVersionedHash`, roughly a
+/// day of mainnet blocks.
+pub const MAX_VERSIONED_HASH_LOOKUP_BLOCKS: u64 = 8192;
+
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
@@ -35,7 +42,8 @@ impl<Provider> RethApi<Provider> {
 
 impl<Provider> RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider:
+        BlockReaderIdExt + ChangeSetReader + StateProviderFactory + BlobSidecarsProvider + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -83,12 +91,48 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns the retained blob sidecars of a block.
+    pub async fn blob_sidecars(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<Option<StoredBlockBlobSidecars>> {
+        self.on_blocking_task(|this| async move {
+            let Some(block_number) = this.provider().block_number_for_id(block_id)? else {
+                return Err(EthApiError::HeaderNotFound(block_id))
+            };
+            Ok(this
+                .provider()
+                .blob_sidecars_by_block(block_number)?
+                .filter(|sidecars| !sidecars.is_empty() || !sidecars.is_complete()))
+        })
+        .await
+    }
+
+    /// Returns the retained blob sidecar containing the blob with the given versioned hash.
+    ///
+    /// Only the most recent [`MAX_VERSIONED_HASH_LOOKUP_BLOCKS`] blocks are searched.
+    pub async fn blob_sidecar_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+    ) -> EthResult<Option<StoredBlobSidecar>> {
+        self.on_blocking_task(|this| async move {
+            let latest = this.provider().best_block_number()?;
+            let block_range = latest.saturating_sub(MAX_VERSIONED_HASH_LOOKUP_BLOCKS - 1)..=latest;
+            Ok(this
+                .provider()
+                .blob_sidecar_by_versioned_hash(versioned_hash, block_range)?
+                .map(|(_, sidecar)| sidecar))
+        })
+        .await
+    }
 }
 
 #[async_trait]
 impl<Provider> RethApiServer for RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider:
+        BlockReaderIdExt + ChangeSetReader + StateProviderFactory + BlobSidecarsProvider + 'static,
 {
     /// Handler for `reth_getBalanceChangesInBlock`
     async fn reth_get_balance_changes_in_block(
@@ -97,6 +141,22 @@ where
     ) -> RpcResult<HashMap<Address, U256>> {
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
+
+    /// Handler for `reth_getBlobSidecars`
+    async fn reth_get_blob_sidecars(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<StoredBlockBlobSidecars>> {
+        Ok(Self::blob_sidecars(self, block_id).await?)
+    }
+
+    /// Handler for `reth_getBlobSidecarByVersionedHash`
+    async fn reth_get_blob_sidecar_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+    ) -> RpcResult<Option<StoredBlobSidecar>> {
+        Ok(Self::blob_sidecar_by_versioned_hash(self, versioned_hash).await?)
+    }
 }
 
 impl<Provider> std::fmt::Debug for RethApi<Provider> {
@@ -1,19 +1,23 @@
 use std::{collections::BTreeMap, marker::PhantomData};
 
 use alloy_consensus::Transaction;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash};
 use alloy_rpc_types_txpool::{
     TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus,
 };
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult as Result;
+use jsonrpsee::{core::RpcResult as Result, PendingSubscriptionSink};
 use reth_primitives::TransactionSignedEcRecovered;
 use reth_rpc_api::TxPoolApiServer;
 use reth_rpc_eth_api::{FullEthApiTypes, RpcTransaction};
 use reth_rpc_types_compat::{transaction::from_recovered, TransactionCompat};
-use reth_transaction_pool::{AllPoolTransactions, PoolTransaction, TransactionPool};
+use reth_transaction_pool::{
+    AllPoolTransactions, PoolTransaction, TransactionHistory, TransactionPool,
+};
 use tracing::trace;
 
+use crate::eth::pubsub::pipe_from_stream;
+
 /// `txpool` API implementation.
 ///
 /// This type provides the functionality for handling `txpool` related requests.
@@ -145,6 +149,24 @@ where
         trace!(target: "rpc::eth", "Serving txpool_content");
         Ok(self.content())
     }
+
+    /// Handler for `txpool_getTransactionStatus`
+    async fn txpool_get_transaction_status(&self, hash: TxHash) -> Result<TransactionHistory> {
+        trace!(target: "rpc::eth", ?hash, "Serving txpool_getTransactionStatus");
+        Ok(self.pool.transaction_history(hash))
+    }
+
+    /// Handler for `txpool_subscribeTransactionEvents`
+    async fn txpool_subscribe_transaction_events(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        trace!(target: "rpc::eth", "Serving txpool_subscribeTransactionEvents");
+        let sink = pending.accept().await?;
+        let stream = self.pool.transaction_journal_listener();
+        pipe_from_stream(sink, stream).await?;
+        Ok(())
+    }
 }
 
 impl<Pool, Eth> std::fmt::Debug for TxPoolApi<Pool, Eth> {
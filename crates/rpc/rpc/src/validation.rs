@@ -0,0 +1,653 @@
+use std::{future::Future, sync::Arc};
+
+use alloy_consensus::Transaction;
+use alloy_eips::eip4844::kzg_to_versioned_hash;
+use alloy_primitives::{B256, U256};
+use alloy_rpc_types_beacon::relay::{
+    BidTrace, BuilderBlockValidationRequest, BuilderBlockValidationRequestV2,
+    BuilderBlockValidationRequestV3,
+};
+use alloy_rpc_types_engine::{BlobsBundleV1, ExecutionPayload, PayloadError};
+use async_trait::async_trait;
+use jsonrpsee::{core::RpcResult, types::ErrorObject};
+use reth_consensus::{Consensus, ConsensusError, PostExecutionInput};
+use reth_errors::{BlockExecutionError, ProviderError};
+use reth_evm::execute::{BlockExecutionOutput, BlockExecutorProvider, Executor};
+use reth_primitives::{BlockWithSenders, GotExpected, Receipt, SealedBlock, SealedHeader};
+use reth_provider::{HeaderProvider, StateProviderFactory, StateRootProvider};
+use reth_revm::database::StateProviderDatabase;
+use reth_rpc_api::BlockSubmissionValidationApiServer;
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_rpc_types_compat::engine::payload::try_into_sealed_block;
+use reth_tasks::TaskSpawner;
+use reth_trie::HashedPostState;
+use tokio::sync::oneshot;
+
+/// The bound divisor of the gas limit, used in update calculations.
+const GAS_LIMIT_BOUND_DIVISOR: u64 = 1024;
+
+/// `flashbots` block validation API implementation.
+///
+/// This type validates block submissions of builders by executing them on top of their parent
+/// block.
+pub struct ValidationApi<Provider, E> {
+    inner: Arc<ValidationApiInner<Provider, E>>,
+}
+
+// === impl ValidationApi ===
+
+impl<Provider, E> ValidationApi<Provider, E> {
+    /// Create a new instance of the [`ValidationApi`]
+    pub fn new(
+        provider: Provider,
+        consensus: Arc<dyn Consensus>,
+        executor_provider: E,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        let inner =
+            Arc::new(ValidationApiInner { provider, consensus, executor_provider, task_spawner });
+        Self { inner }
+    }
+
+    /// The provider that can interact with the chain.
+    pub fn provider(&self) -> &Provider {
+        &self.inner.provider
+    }
+}
+
+impl<Provider, E> ValidationApi<Provider, E>
+where
+    Provider: HeaderProvider + StateProviderFactory + Clone + 'static,
+    E: BlockExecutorProvider,
+{
+    /// Executes the future on a new blocking task.
+    async fn on_blocking_task<C, F, R>(&self, c: C) -> Result<R, ValidationApiError>
+    where
+        C: FnOnce(Self) -> F,
+        F: Future<Output = Result<R, ValidationApiError>> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let this = self.clone();
+        let f = c(this);
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let res = f.await;
+            let _ = tx.send(res);
+        }));
+        rx.await.map_err(|_| ValidationApiError::TaskDropped)?
+    }
+
+    /// Validates the given payload against the bid trace of the builder.
+    ///
+    /// The payload is converted into a block which is then validated against the bid, checked
+    /// for consensus rules and executed on top of its parent block. Finally, the proposer payment
+    /// and the resulting state root are verified.
+    ///
+    /// If a blobs bundle is given, its commitments must match the blob versioned hashes of the
+    /// block.
+    pub async fn validate_builder_submission(
+        &self,
+        payload: ExecutionPayload,
+        message: BidTrace,
+        registered_gas_limit: u64,
+        parent_beacon_block_root: Option<B256>,
+        withdrawals_root: Option<B256>,
+        blobs_bundle: Option<BlobsBundleV1>,
+    ) -> Result<(), ValidationApiError> {
+        self.on_blocking_task(|this| async move {
+            let block = try_into_sealed_block(payload, parent_beacon_block_root, None)?;
+
+            if let Some(blobs_bundle) = &blobs_bundle {
+                validate_blobs_bundle(&block, blobs_bundle)?;
+            }
+
+            if let Some(expected) = withdrawals_root {
+                let got = block.withdrawals_root.unwrap_or_default();
+                if got != expected {
+                    return Err(ValidationApiError::WithdrawalsRootMismatch(GotExpected {
+                        got,
+                        expected,
+                    }))
+                }
+            }
+
+            this.validate_message_against_block(block, message, registered_gas_limit)
+        })
+        .await
+    }
+
+    /// Validates the given block against the bid trace and executes it on top of its parent.
+    fn validate_message_against_block(
+        &self,
+        block: SealedBlock,
+        message: BidTrace,
+        registered_gas_limit: u64,
+    ) -> Result<(), ValidationApiError> {
+        compare_values(message.block_hash, block.hash(), ValidationApiError::BlockHashMismatch)?;
+        compare_values(
+            message.parent_hash,
+            block.parent_hash,
+            ValidationApiError::ParentHashMismatch,
+        )?;
+        compare_values(message.gas_limit, block.gas_limit, ValidationApiError::GasLimitMismatch)?;
+        compare_values(message.gas_used, block.gas_used, ValidationApiError::GasUsedMismatch)?;
+
+        let block = block
+            .try_seal_with_senders()
+            .map_err(|_| ValidationApiError::InvalidTransactionSignature)?;
+
+        let consensus = &self.inner.consensus;
+        consensus.validate_header(&block.header)?;
+        consensus.validate_block_pre_execution(&block)?;
+
+        let parent = self
+            .provider()
+            .sealed_header_by_hash(block.parent_hash)?
+            .ok_or(ValidationApiError::MissingParentBlock(block.parent_hash))?;
+        consensus.validate_header_against_parent(&block.header, &parent)?;
+        validate_gas_limit(registered_gas_limit, &parent, &block.header)?;
+
+        let state_provider = self.provider().history_by_block_hash(block.parent_hash)?;
+        let state_root = block.state_root;
+        let block = block.unseal();
+
+        let executor =
+            self.inner.executor_provider.executor(StateProviderDatabase::new(&state_provider));
+        // the total difficulty is irrelevant for post merge blocks, which builder submissions
+        // always are
+        let output = executor.execute((&block, U256::MAX).into())?;
+
+        consensus.validate_block_post_execution(
+            &block,
+            PostExecutionInput::new(&output.receipts, &output.requests),
+        )?;
+
+        ensure_payment(&block, &output, &message)?;
+
+        let hashed_state = HashedPostState::from_bundle_state(&output.state.state);
+        let got = state_provider.state_root(hashed_state)?;
+        compare_values(state_root, got, ValidationApiError::StateRootMismatch)?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Provider, E> BlockSubmissionValidationApiServer for ValidationApi<Provider, E>
+where
+    Provider: HeaderProvider + StateProviderFactory + Clone + 'static,
+    E: BlockExecutorProvider,
+{
+    /// Handler for `flashbots_validateBuilderSubmissionV1`
+    async fn validate_builder_submission_v1(
+        &self,
+        request: BuilderBlockValidationRequest,
+    ) -> RpcResult<()> {
+        let BuilderBlockValidationRequest { request, registered_gas_limit } = request;
+        Ok(self
+            .validate_builder_submission(
+                request.execution_payload.into(),
+                request.message,
+                registered_gas_limit,
+                None,
+                None,
+                None,
+            )
+            .await?)
+    }
+
+    /// Handler for `flashbots_validateBuilderSubmissionV2`
+    async fn validate_builder_submission_v2(
+        &self,
+        request: BuilderBlockValidationRequestV2,
+    ) -> RpcResult<()> {
+        let BuilderBlockValidationRequestV2 { request, registered_gas_limit, withdrawals_root } =
+            request;
+        Ok(self
+            .validate_builder_submission(
+                request.execution_payload.into(),
+                request.message,
+                registered_gas_limit,
+                None,
+                Some(withdrawals_root),
+                None,
+            )
+            .await?)
+    }
+
+    /// Handler for `flashbots_validateBuilderSubmissionV3`
+    async fn validate_builder_submission_v3(
+        &self,
+        request: BuilderBlockValidationRequestV3,
+    ) -> RpcResult<()> {
+        let BuilderBlockValidationRequestV3 {
+            request,
+            registered_gas_limit,
+            parent_beacon_block_root,
+        } = request;
+        Ok(self
+            .validate_builder_submission(
+                request.execution_payload.into(),
+                request.message,
+                registered_gas_limit,
+                Some(parent_beacon_block_root),
+                None,
+                Some(request.blobs_bundle),
+            )
+            .await?)
+    }
+}
+
+impl<Provider, E> std::fmt::Debug for ValidationApi<Provider, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ValidationApi").finish_non_exhaustive()
+    }
+}
+
+impl<Provider, E> Clone for ValidationApi<Provider, E> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+struct ValidationApiInner<Provider, E> {
+    /// The provider that can interact with the chain.
+    provider: Provider,
+    /// Consensus implementation used to validate the submitted blocks.
+    consensus: Arc<dyn Consensus>,
+    /// Provider of the executor that executes the submitted blocks.
+    executor_provider: E,
+    /// The type that can spawn tasks which would otherwise block.
+    task_spawner: Box<dyn TaskSpawner>,
+}
+
+/// Returns an error built from the given constructor if the values are not equal.
+fn compare_values<T: PartialEq>(
+    expected: T,
+    got: T,
+    err: impl FnOnce(GotExpected<T>) -> ValidationApiError,
+) -> Result<(), ValidationApiError> {
+    if got != expected {
+        return Err(err(GotExpected { got, expected }))
+    }
+    Ok(())
+}
+
+/// Ensures that the block gas limit moves towards the gas limit registered by the proposer as
+/// far as the gas limit bounds relative to the parent allow.
+fn validate_gas_limit(
+    registered_gas_limit: u64,
+    parent: &SealedHeader,
+    header: &SealedHeader,
+) -> Result<(), ValidationApiError> {
+    // the bounds collapse to the parent gas limit if it is below the bound divisor
+    let max_delta = (parent.gas_limit / GAS_LIMIT_BOUND_DIVISOR).saturating_sub(1);
+    let max_gas_limit = parent.gas_limit.saturating_add(max_delta);
+    let min_gas_limit = parent.gas_limit.saturating_sub(max_delta);
+    let best_gas_limit = if min_gas_limit > max_gas_limit {
+        parent.gas_limit
+    } else {
+        registered_gas_limit.clamp(min_gas_limit, max_gas_limit)
+    };
+
+    compare_values(best_gas_limit, header.gas_limit, ValidationApiError::GasLimitMismatch)
+}
+
+/// Ensures that the commitments of the blobs bundle match the blob versioned hashes of the block.
+fn validate_blobs_bundle(
+    block: &SealedBlock,
+    blobs_bundle: &BlobsBundleV1,
+) -> Result<(), ValidationApiError> {
+    if blobs_bundle.commitments.len() != blobs_bundle.proofs.len() ||
+        blobs_bundle.commitments.len() != blobs_bundle.blobs.len()
+    {
+        return Err(ValidationApiError::InvalidBlobsBundle)
+    }
+
+    let versioned_hashes = blobs_bundle
+        .commitments
+        .iter()
+        .map(|commitment| kzg_to_versioned_hash(commitment.as_slice()));
+    if !versioned_hashes.eq(block.blob_versioned_hashes_iter().copied()) {
+        return Err(ValidationApiError::InvalidBlobsBundle)
+    }
+
+    Ok(())
+}
+
+/// Ensures that the proposer fee recipient received the bid value.
+///
+/// The payment is either reflected in the balance change of the fee recipient, or made by the
+/// last transaction of the block, which must be a plain transfer of the bid value to the fee
+/// recipient.
+fn ensure_payment(
+    block: &BlockWithSenders,
+    output: &BlockExecutionOutput<Receipt>,
+    message: &BidTrace,
+) -> Result<(), ValidationApiError> {
+    let (mut balance_before, balance_after) =
+        if let Some(account) = output.state.state.get(&message.proposer_fee_recipient) {
+            let before = account.original_info.as_ref().map(|info| info.balance);
+            let after = account.info.as_ref().map(|info| info.balance);
+            (before.unwrap_or_default(), after.unwrap_or_default())
+        } else {
+            // the account is untouched, so its balance did not change
+            (U256::ZERO, U256::ZERO)
+        };
+
+    // withdrawals to the fee recipient do not count towards the payment
+    for withdrawal in block.body.withdrawals.iter().flatten() {
+        if withdrawal.address == message.proposer_fee_recipient {
+            balance_before = balance_before
+                .checked_add(withdrawal.amount_wei())
+                .ok_or(ValidationApiError::PaymentOverflow)?;
+        }
+    }
+
+    let expected_balance =
+        balance_before.checked_add(message.value).ok_or(ValidationApiError::PaymentOverflow)?;
+    if balance_after >= expected_balance {
+        return Ok(())
+    }
+
+    let (receipt, tx) = output
+        .receipts
+        .last()
+        .zip(block.body.transactions.last())
+        .ok_or(ValidationApiError::ProposerPayment)?;
+
+    if !receipt.success ||
+        tx.to() != Some(message.proposer_fee_recipient) ||
+        tx.value() != message.value ||
+        !tx.input().is_empty() ||
+        tx.effective_tip_per_gas(block.base_fee_per_gas.unwrap_or_default()).unwrap_or_default() !=
+            0
+    {
+        return Err(ValidationApiError::ProposerPayment)
+    }
+
+    Ok(())
+}
+
+/// Errors thrown by the [`ValidationApi`].
+#[derive(Debug, thiserror::Error)]
+pub enum ValidationApiError {
+    /// Thrown if the block hash does not match the bid.
+    #[error("block hash mismatch: {0}")]
+    BlockHashMismatch(GotExpected<B256>),
+    /// Thrown if the parent hash does not match the bid.
+    #[error("parent hash mismatch: {0}")]
+    ParentHashMismatch(GotExpected<B256>),
+    /// Thrown if the gas limit does not match the bid or the registered gas limit.
+    #[error("gas limit mismatch: {0}")]
+    GasLimitMismatch(GotExpected<u64>),
+    /// Thrown if the gas used does not match the bid.
+    #[error("gas used mismatch: {0}")]
+    GasUsedMismatch(GotExpected<u64>),
+    /// Thrown if the state root of the block does not match the executed state.
+    #[error("state root mismatch: {0}")]
+    StateRootMismatch(GotExpected<B256>),
+    /// Thrown if the withdrawals root does not match the request.
+    #[error("withdrawals root mismatch: {0}")]
+    WithdrawalsRootMismatch(GotExpected<B256>),
+    /// Thrown if the parent block is unknown.
+    #[error("parent block {0} not found")]
+    MissingParentBlock(B256),
+    /// Thrown if the proposer was not paid the bid value.
+    #[error("proposer payment not found")]
+    ProposerPayment,
+    /// Thrown if the bid value and the withdrawals overflow the fee recipient balance.
+    #[error("proposer payment overflows the fee recipient balance")]
+    PaymentOverflow,
+    /// Thrown if the blobs bundle does not match the blob transactions of the block.
+    #[error("blobs bundle does not match the blob versioned hashes of the block")]
+    InvalidBlobsBundle,
+    /// Thrown if a transaction signature could not be recovered.
+    #[error("could not recover transaction signer")]
+    InvalidTransactionSignature,
+    /// Thrown if the blocking validation task was dropped.
+    #[error("validation task dropped")]
+    TaskDropped,
+    /// Thrown if the payload can't be converted into a block.
+    #[error(transparent)]
+    Payload(#[from] PayloadError),
+    /// Thrown if the block violates consensus rules.
+    #[error(transparent)]
+    Consensus(#[from] ConsensusError),
+    /// Thrown if the block execution failed.
+    #[error(transparent)]
+    Execution(#[from] BlockExecutionError),
+    /// Thrown if the database lookups failed.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+impl From<ValidationApiError> for ErrorObject<'static> {
+    fn from(error: ValidationApiError) -> Self {
+        match error {
+            ValidationApiError::TaskDropped | ValidationApiError::Provider(_) => {
+                internal_rpc_err(error.to_string())
+            }
+            _ => invalid_params_rpc_err(error.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH};
+    use alloy_eips::{
+        eip4844::{Blob, Bytes48},
+        eip4895::Withdrawal,
+    };
+    use alloy_primitives::Address;
+    use alloy_rpc_types_beacon::relay::SignedBidSubmissionV3;
+    use jsonrpsee::types::{error::INVALID_PARAMS_CODE, ErrorObjectOwned};
+    use reth_consensus::noop::NoopConsensus;
+    use reth_evm_ethereum::execute::EthExecutorProvider;
+    use reth_primitives::{Block, BlockBody, Header, Withdrawals};
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_rpc_types_compat::engine::payload::block_to_payload_v3;
+    use reth_tasks::TokioTaskExecutor;
+
+    fn sealed_header(gas_limit: u64) -> SealedHeader {
+        SealedHeader::new(Header { gas_limit, ..Default::default() }, B256::ZERO)
+    }
+
+    #[test]
+    fn gas_limit_moves_towards_registered_limit() {
+        let parent = sealed_header(30_000_000);
+        let step = 30_000_000 / GAS_LIMIT_BOUND_DIVISOR - 1;
+
+        // registered limit within bounds must be matched exactly
+        assert!(validate_gas_limit(30_000_000, &parent, &sealed_header(30_000_000)).is_ok());
+        assert!(validate_gas_limit(30_000_000, &parent, &sealed_header(30_000_001)).is_err());
+
+        // registered limit out of bounds must be approached as far as possible
+        let up = sealed_header(30_000_000 + step);
+        assert!(validate_gas_limit(36_000_000, &parent, &up).is_ok());
+        let down = sealed_header(30_000_000 - step);
+        assert!(validate_gas_limit(0, &parent, &down).is_ok());
+        assert!(matches!(
+            validate_gas_limit(36_000_000, &parent, &parent),
+            Err(ValidationApiError::GasLimitMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn gas_limit_bounds_saturate() {
+        // bounds collapse to the parent gas limit below the bound divisor
+        for gas_limit in [0, 1, 1_000, GAS_LIMIT_BOUND_DIVISOR] {
+            let parent = sealed_header(gas_limit);
+            assert!(validate_gas_limit(u64::MAX, &parent, &parent).is_ok());
+            assert!(validate_gas_limit(0, &parent, &parent).is_ok());
+        }
+
+        let parent = sealed_header(u64::MAX);
+        assert!(validate_gas_limit(u64::MAX, &parent, &parent).is_ok());
+        let down = sealed_header(u64::MAX - (u64::MAX / GAS_LIMIT_BOUND_DIVISOR - 1));
+        assert!(validate_gas_limit(0, &parent, &down).is_ok());
+    }
+
+    fn api(
+        provider: MockEthProvider,
+    ) -> ValidationApi<MockEthProvider, impl BlockExecutorProvider> {
+        ValidationApi::new(
+            provider,
+            Arc::new(NoopConsensus::default()),
+            EthExecutorProvider::mainnet(),
+            Box::new(TokioTaskExecutor::default()),
+        )
+    }
+
+    /// Returns an empty cancun block on top of the given parent and a matching request.
+    fn submission(
+        parent: &SealedHeader,
+        gas_limit: u64,
+        registered_gas_limit: u64,
+    ) -> BuilderBlockValidationRequestV3 {
+        let parent_beacon_block_root = B256::with_last_byte(1);
+        let block = Block {
+            header: Header {
+                parent_hash: parent.hash(),
+                ommers_hash: EMPTY_OMMER_ROOT_HASH,
+                transactions_root: EMPTY_ROOT_HASH,
+                receipts_root: EMPTY_ROOT_HASH,
+                withdrawals_root: Some(EMPTY_ROOT_HASH),
+                number: parent.number + 1,
+                gas_limit,
+                timestamp: parent.timestamp + 12,
+                base_fee_per_gas: Some(7),
+                blob_gas_used: Some(0),
+                excess_blob_gas: Some(0),
+                parent_beacon_block_root: Some(parent_beacon_block_root),
+                ..Default::default()
+            },
+            body: BlockBody { withdrawals: Some(Default::default()), ..Default::default() },
+        }
+        .seal_slow();
+
+        BuilderBlockValidationRequestV3 {
+            request: SignedBidSubmissionV3 {
+                message: BidTrace {
+                    slot: 1,
+                    parent_hash: block.parent_hash,
+                    block_hash: block.hash(),
+                    builder_pubkey: Default::default(),
+                    proposer_pubkey: Default::default(),
+                    proposer_fee_recipient: Address::ZERO,
+                    gas_limit: block.gas_limit,
+                    gas_used: block.gas_used,
+                    value: U256::ZERO,
+                },
+                execution_payload: block_to_payload_v3(block),
+                blobs_bundle: Default::default(),
+                signature: Default::default(),
+            },
+            registered_gas_limit,
+            parent_beacon_block_root,
+        }
+    }
+
+    async fn rejection(
+        provider: MockEthProvider,
+        request: BuilderBlockValidationRequestV3,
+    ) -> ErrorObjectOwned {
+        let err = api(provider).validate_builder_submission_v3(request).await.unwrap_err();
+        assert_eq!(err.code(), INVALID_PARAMS_CODE);
+        err
+    }
+
+    #[tokio::test]
+    async fn validate_builder_submission_v3_rejections() {
+        let header = Header { gas_limit: 30_000_000, ..Default::default() };
+        let parent = SealedHeader::new(header.clone(), header.hash_slow());
+        let provider = MockEthProvider::default();
+        provider.add_header(parent.hash(), header);
+
+        // the payload does not hash to its block hash
+        let mut request = submission(&parent, 30_000_000, 30_000_000);
+        request.parent_beacon_block_root = B256::with_last_byte(2);
+        let err = rejection(provider.clone(), request).await;
+        assert!(err.message().starts_with("block hash mismatch"), "{err:?}");
+
+        let mut request = submission(&parent, 30_000_000, 30_000_000);
+        request.request.message.block_hash = B256::with_last_byte(2);
+        let err = rejection(provider.clone(), request).await;
+        assert!(err.message().starts_with("block hash mismatch"), "{err:?}");
+
+        let mut request = submission(&parent, 30_000_000, 30_000_000);
+        request.request.message.parent_hash = B256::with_last_byte(2);
+        let err = rejection(provider.clone(), request).await;
+        assert!(err.message().starts_with("parent hash mismatch"), "{err:?}");
+
+        let mut request = submission(&parent, 30_000_000, 30_000_000);
+        request.request.message.gas_limit = 29_000_000;
+        let err = rejection(provider.clone(), request).await;
+        assert!(err.message().starts_with("gas limit mismatch"), "{err:?}");
+
+        let mut request = submission(&parent, 30_000_000, 30_000_000);
+        request.request.message.gas_used = 21_000;
+        let err = rejection(provider.clone(), request).await;
+        assert!(err.message().starts_with("gas used mismatch"), "{err:?}");
+
+        // the parent block is unknown
+        let request = submission(&parent, 30_000_000, 30_000_000);
+        let err = rejection(MockEthProvider::default(), request).await;
+        assert_eq!(err.message(), format!("parent block {} not found", parent.hash()));
+
+        // the gas limit does not move towards the registered gas limit
+        let request = submission(&parent, 30_000_000, 36_000_000);
+        let err = rejection(provider.clone(), request).await;
+        assert!(err.message().starts_with("gas limit mismatch"), "{err:?}");
+
+        // the blobs bundle is inconsistent or has blobs the block doesn't commit to
+        let mut request = submission(&parent, 30_000_000, 30_000_000);
+        request.request.blobs_bundle.commitments.push(Bytes48::default());
+        let err = rejection(provider.clone(), request).await;
+        assert!(err.message().starts_with("blobs bundle does not match"), "{err:?}");
+
+        let mut request = submission(&parent, 30_000_000, 30_000_000);
+        request.request.blobs_bundle.commitments.push(Bytes48::default());
+        request.request.blobs_bundle.proofs.push(Bytes48::default());
+        request.request.blobs_bundle.blobs.push(Blob::default());
+        let err = rejection(provider, request).await;
+        assert!(err.message().starts_with("blobs bundle does not match"), "{err:?}");
+    }
+
+    #[test]
+    fn payment_overflow() {
+        let fee_recipient = Address::with_last_byte(1);
+        let block = BlockWithSenders {
+            block: Block {
+                body: BlockBody {
+                    withdrawals: Some(Withdrawals::new(vec![Withdrawal {
+                        address: fee_recipient,
+                        amount: 1,
+                        ..Default::default()
+                    }])),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            senders: Vec::new(),
+        };
+        let output = BlockExecutionOutput {
+            state: Default::default(),
+            receipts: Vec::new(),
+            requests: Default::default(),
+            gas_used: 0,
+        };
+        let message = BidTrace {
+            proposer_fee_recipient: fee_recipient,
+            value: U256::MAX,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            ensure_payment(&block, &output, &message),
+            Err(ValidationApiError::PaymentOverflow)
+        ));
+    }
+}
@@ -0,0 +1,435 @@
+use super::load_history_indices;
+use alloy_primitives::{Address, BlockNumber, Sealable, TxNumber, U256};
+use reth_chainspec::EthereumHardforks;
+use reth_config::config::{EtlConfig, IndexAddressTransactionsConfig};
+use reth_db::{tables, BlockNumberList};
+use reth_db_api::{models::ShardedKey, table::Decode, transaction::DbTxMut};
+use reth_etl::Collector;
+use reth_evm::{system_calls::SystemCaller, ConfigureEvm};
+use reth_execution_errors::{BlockExecutionError, BlockValidationError};
+use reth_primitives::{Header, SealedHeader};
+use reth_provider::{
+    BlockReader, ChainSpecProvider, DBProvider, HeaderProvider, HistoricalStateProviderRef,
+    HistoryWriter, ProviderError, StaticFileProviderFactory, TransactionVariant,
+};
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::CacheDB,
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome},
+    primitives::{BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, ResultAndState, TxKind},
+    Database, DatabaseCommit, EvmContext, Inspector,
+};
+use reth_stages_api::{
+    BlockErrorKind, ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId,
+    UnwindInput, UnwindOutput,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::RangeInclusive,
+};
+use tracing::info;
+
+/// Number of blocks after which the in-memory indices are moved to the [`Collector`].
+const DEFAULT_CACHE_THRESHOLD: u64 = 1_000;
+
+/// Stage indexing the transactions each address took part in, either as the sender, the recipient
+/// or as a participant of an internal call. For more information on the index take a look at
+/// [`tables::AddressTransactions`].
+///
+/// Internal call participants are found by re-executing every block on top of its parent state,
+/// so the stage has to run after
+/// [`IndexAccountHistoryStage`][crate::stages::IndexAccountHistoryStage]
+/// and [`IndexStorageHistoryStage`][crate::stages::IndexStorageHistoryStage], and requires the
+/// account and storage history of the indexed blocks to be available.
+#[derive(Debug)]
+pub struct IndexAddressTransactionsStage<E> {
+    /// The EVM configuration used to re-execute blocks.
+    evm_config: E,
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl<E> IndexAddressTransactionsStage<E> {
+    /// Create new instance of [`IndexAddressTransactionsStage`].
+    pub const fn new(
+        evm_config: E,
+        config: IndexAddressTransactionsConfig,
+        etl_config: EtlConfig,
+    ) -> Self {
+        Self { evm_config, commit_threshold: config.commit_threshold, etl_config }
+    }
+}
+
+impl<E> IndexAddressTransactionsStage<E>
+where
+    E: ConfigureEvm<Header = Header>,
+{
+    /// Re-executes the blocks in the given range, calling `on_participant` for every address that
+    /// took part in a transaction, along with the transaction number.
+    ///
+    /// An address may be reported more than once for the same transaction.
+    fn walk_participants<Provider>(
+        &self,
+        provider: &Provider,
+        range: RangeInclusive<BlockNumber>,
+        mut on_participant: impl FnMut(Address, TxNumber),
+    ) -> Result<(), StageError>
+    where
+        Provider: DBProvider
+            + BlockReader
+            + HeaderProvider
+            + StaticFileProviderFactory
+            + ChainSpecProvider<ChainSpec: EthereumHardforks>,
+    {
+        for block_number in range {
+            let Some(body) = provider.block_body_indices(block_number)? else {
+                return Err(ProviderError::BlockBodyIndicesNotFound(block_number).into())
+            };
+            if body.tx_count == 0 {
+                continue
+            }
+
+            let block = provider
+                .block_with_senders(block_number.into(), TransactionVariant::NoHash)?
+                .ok_or(ProviderError::HeaderNotFound(block_number.into()))?;
+            let total_difficulty = provider
+                .header_td_by_number(block_number)?
+                .ok_or(ProviderError::TotalDifficultyNotFound(block_number))?;
+
+            let mut cfg = CfgEnvWithHandlerCfg::new(Default::default(), Default::default());
+            let mut block_env = BlockEnv::default();
+            self.evm_config.fill_cfg_and_block_env(
+                &mut cfg,
+                &mut block_env,
+                &block.header,
+                total_difficulty,
+            );
+
+            // State at the beginning of the block, i.e. after its parent was executed.
+            let state = HistoricalStateProviderRef::new(
+                provider.tx_ref(),
+                block_number,
+                provider.static_file_provider(),
+            );
+            let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+            SystemCaller::new(self.evm_config.clone(), provider.chain_spec())
+                .pre_block_beacon_root_contract_call(
+                    &mut db,
+                    &cfg,
+                    &block_env,
+                    block.header.parent_beacon_block_root,
+                )
+                .map_err(|error| block_error(&block.header, error))?;
+
+            let mut evm = self.evm_config.evm_with_env_and_inspector(
+                db,
+                EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, Default::default()),
+                CallParticipantsInspector::default(),
+            );
+
+            for (tx_number, (sender, transaction)) in
+                (body.first_tx_num..).zip(block.transactions_with_sender())
+            {
+                on_participant(*sender, tx_number);
+                if let TxKind::Call(to) = transaction.kind() {
+                    on_participant(to, tx_number);
+                }
+
+                self.evm_config.fill_tx_env(evm.tx_mut(), transaction, *sender);
+                let ResultAndState { state, .. } = evm.transact().map_err(|error| {
+                    block_error(
+                        &block.header,
+                        BlockValidationError::EVM {
+                            hash: transaction.recalculate_hash(),
+                            error: Box::new(error.map_db_err(Into::into)),
+                        }
+                        .into(),
+                    )
+                })?;
+                evm.db_mut().commit(state);
+
+                for address in evm.context.external.take_participants() {
+                    on_participant(address, tx_number);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<E, Provider> Stage<Provider> for IndexAddressTransactionsStage<E>
+where
+    E: ConfigureEvm<Header = Header>,
+    Provider: DBProvider<Tx: DbTxMut>
+        + BlockReader
+        + HeaderProvider
+        + StaticFileProviderFactory
+        + ChainSpecProvider<ChainSpec: EthereumHardforks>
+        + HistoryWriter,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexAddressTransactions
+    }
+
+    /// Execute the stage.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+        let first_sync = input.checkpoint().block_number == 0;
+
+        // On first sync we clear the table since it's faster to rebuild from scratch.
+        if first_sync {
+            provider.tx_ref().clear::<tables::AddressTransactions>()?;
+        }
+
+        info!(target: "sync::stages::index_address_transactions::exec", ?first_sync, ?range, "Collecting indices");
+        let mut collector = Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+        let mut collect = |cache: &mut HashMap<Address, Vec<TxNumber>>| {
+            for (address, tx_numbers) in cache.drain() {
+                let last = *tx_numbers.last().expect("not empty");
+                collector.insert(
+                    ShardedKey::new(address, last),
+                    BlockNumberList::new_pre_sorted(tx_numbers),
+                )?;
+            }
+            Ok::<(), StageError>(())
+        };
+
+        // Blocks are walked in ascending order, so the transaction numbers of each address are
+        // sorted as long as duplicates of the same transaction are skipped.
+        let mut cache = HashMap::<Address, Vec<TxNumber>>::default();
+        for chunk_start in range.clone().step_by(DEFAULT_CACHE_THRESHOLD as usize) {
+            let chunk_end = (chunk_start + DEFAULT_CACHE_THRESHOLD - 1).min(*range.end());
+            self.walk_participants(provider, chunk_start..=chunk_end, |address, tx_number| {
+                let tx_numbers = cache.entry(address).or_default();
+                if tx_numbers.last() != Some(&tx_number) {
+                    tx_numbers.push(tx_number);
+                }
+            })?;
+            collect(&mut cache)?;
+        }
+
+        info!(target: "sync::stages::index_address_transactions::exec", "Loading indices into database");
+        load_history_indices::<_, tables::AddressTransactions, _>(
+            provider,
+            collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode_owned,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        let first_tx_number = provider
+            .block_body_indices(*range.start())?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(*range.start()))?
+            .first_tx_num;
+
+        let mut addresses = HashSet::new();
+        self.walk_participants(provider, range, |address, _| {
+            addresses.insert(address);
+        })?;
+        provider.unwind_address_transactions_indices(addresses, first_tx_number)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+fn block_error(header: &Header, error: BlockExecutionError) -> StageError {
+    let (header, seal) = header.clone().seal_slow().into_parts();
+    StageError::Block {
+        block: Box::new(SealedHeader::new(header, seal)),
+        error: BlockErrorKind::Execution(error),
+    }
+}
+
+/// An [`Inspector`] collecting the callers and callees of all calls, including the code addresses
+/// of delegate calls, the creators and addresses of all created contracts, and the beneficiaries of
+/// all self-destructs.
+#[derive(Debug, Default)]
+struct CallParticipantsInspector {
+    participants: HashSet<Address>,
+}
+
+impl CallParticipantsInspector {
+    /// Returns the participants collected so far, resetting the inspector.
+    fn take_participants(&mut self) -> HashSet<Address> {
+        std::mem::take(&mut self.participants)
+    }
+}
+
+impl<DB: Database> Inspector<DB> for CallParticipantsInspector {
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.participants.insert(inputs.caller);
+        self.participants.insert(inputs.target_address);
+        // the account whose code is run by a `DELEGATECALL` or `CALLCODE`
+        self.participants.insert(inputs.bytecode_address);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.participants.insert(inputs.caller);
+        if let Some(address) = outcome.address {
+            self.participants.insert(address);
+        }
+        outcome
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, _value: U256) {
+        self.participants.insert(contract);
+        self.participants.insert(target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, hex_literal::hex, keccak256, Bytes};
+    use alloy_rlp::Decodable;
+    use reth_chainspec::ChainSpecBuilder;
+    use reth_db_api::{cursor::DbCursorRO, transaction::DbTx};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::{Account, Bytecode, SealedBlock, StaticFileSegment};
+    use reth_provider::{
+        providers::StaticFileWriter, test_utils::create_test_provider_factory,
+        AddressTransactionsReader, DatabaseProviderFactory,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn index_and_unwind_call_participants() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        // Block 1 contains a single transaction from `sender` to `contract`.
+        let mut genesis_rlp = hex!("f901faf901f5a00000000000000000000000000000000000000000000000000000000000000000a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa045571b40ae66ca7480791bbb2887286e4e4c4b1b298b191c889d6959023a32eda056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000808502540be400808000a00000000000000000000000000000000000000000000000000000000000000000880000000000000000c0c0").as_slice();
+        let genesis = SealedBlock::decode(&mut genesis_rlp).unwrap();
+        let mut block_rlp = hex!("f90262f901f9a075c371ba45999d87f4542326910a11af515897aebce5265d3f6acd1f1161f82fa01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa098f2dcd87c8ae4083e7017a05456c14eea4b1db2032126e27b3b1563d57d7cc0a08151d548273f6683169524b66ca9fe338b9ce42bc3540046c828fd939ae23bcba03f4e5c2ec5b2170b711d97ee755c160457bb58d8daa338e835ec02ae6860bbabb901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000018502540be40082a8798203e800a00000000000000000000000000000000000000000000000000000000000000000880000000000000000f863f861800a8405f5e10094100000000000000000000000000000000000000080801ba07e09e26678ed4fac08a249ebe8ed680bf9051a5e14ad223e4b2b9d26e0208f37a05f6e3f188e3e6eab7d7d3b6568f5eac7d687b08d307d3154ccd8c87b4630509bc0").as_slice();
+        let block = SealedBlock::decode(&mut block_rlp).unwrap();
+        provider.insert_historical_block(genesis.try_seal_with_senders().unwrap()).unwrap();
+        provider.insert_historical_block(block.try_seal_with_senders().unwrap()).unwrap();
+        provider
+            .static_file_provider()
+            .latest_writer(StaticFileSegment::Headers)
+            .unwrap()
+            .commit()
+            .unwrap();
+        provider.commit().unwrap();
+
+        let sender = address!("a94f5374fce5edbc8e2a8697c15331677e6ebf0b");
+        let contract = address!("1000000000000000000000000000000000000000");
+        let callee = address!("2000000000000000000000000000000000000000");
+        let library = address!("3000000000000000000000000000000000000000");
+
+        // `contract` calls `callee` and delegates to `library`:
+        // CALL(gas, callee, 0, 0, 0, 0, 0) DELEGATECALL(gas, library, 0, 0, 0, 0)
+        let code = Bytes::from_static(&hex!(
+            "600060006000600060007320000000000000000000000000000000000000005af150"
+            "60006000600060007330000000000000000000000000000000000000005af400"
+        ));
+        let code_hash = keccak256(&code);
+        let provider = factory.provider_rw().unwrap();
+        let db_tx = provider.tx_ref();
+        db_tx
+            .put::<tables::PlainAccountState>(
+                contract,
+                Account { nonce: 0, balance: U256::ZERO, bytecode_hash: Some(code_hash) },
+            )
+            .unwrap();
+        db_tx
+            .put::<tables::PlainAccountState>(
+                sender,
+                Account {
+                    nonce: 0,
+                    balance: U256::from(0x3635c9adc5dea00000u128),
+                    bytecode_hash: None,
+                },
+            )
+            .unwrap();
+        db_tx.put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code)).unwrap();
+        // Both accounts were created in the genesis block.
+        for address in [sender, contract] {
+            db_tx
+                .put::<tables::AccountsHistory>(
+                    ShardedKey::last(address),
+                    BlockNumberList::new_pre_sorted([0]),
+                )
+                .unwrap();
+        }
+        provider.commit().unwrap();
+
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().berlin_activated().build());
+        let mut stage = IndexAddressTransactionsStage::new(
+            EthEvmConfig::new(chain_spec),
+            IndexAddressTransactionsConfig::default(),
+            EtlConfig::default(),
+        );
+
+        let provider = factory.database_provider_rw().unwrap();
+        let output =
+            stage.execute(&provider, ExecInput { target: Some(1), checkpoint: None }).unwrap();
+        assert_eq!(output, ExecOutput { checkpoint: StageCheckpoint::new(1), done: true });
+        provider.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        for address in [sender, contract, callee, library] {
+            assert_eq!(provider.address_transactions(address, 0..=u64::MAX, 10).unwrap(), vec![0]);
+            assert_eq!(
+                provider.address_transactions_rev(address, 0..=u64::MAX, 10).unwrap(),
+                vec![0]
+            );
+        }
+        assert!(provider
+            .address_transactions(Address::random(), 0..=u64::MAX, 10)
+            .unwrap()
+            .is_empty());
+        drop(provider);
+
+        let provider = factory.database_provider_rw().unwrap();
+        let output = stage
+            .unwind(
+                &provider,
+                UnwindInput { checkpoint: StageCheckpoint::new(1), unwind_to: 0, bad_block: None },
+            )
+            .unwrap();
+        assert_eq!(output, UnwindOutput { checkpoint: StageCheckpoint::new(0) });
+        assert_eq!(
+            provider
+                .tx_ref()
+                .cursor_read::<tables::AddressTransactions>()
+                .unwrap()
+                .first()
+                .unwrap(),
+            None
+        );
+    }
+}
@@ -5,16 +5,18 @@ use reth_db::tables;
 use reth_db_api::transaction::{DbTx, DbTxMut};
 use reth_primitives::{GotExpected, SealedHeader};
 use reth_provider::{
-    DBProvider, HeaderProvider, ProviderError, StageCheckpointReader, StageCheckpointWriter,
-    StatsReader, TrieWriter,
+    ChangeSetReader, DBProvider, HeaderProvider, ProviderError, StageCheckpointReader,
+    StageCheckpointWriter, StatsReader, StorageChangeSetReader, TrieWriter,
 };
 use reth_stages_api::{
     BlockErrorKind, EntitiesCheckpoint, ExecInput, ExecOutput, MerkleCheckpoint, Stage,
     StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
 };
-use reth_trie::{IntermediateStateRootState, StateRoot, StateRootProgress, StoredSubNode};
-use reth_trie_db::DatabaseStateRoot;
-use std::fmt::Debug;
+use reth_trie::{
+    updates::TrieUpdates, IntermediateStateRootState, StateRoot, StateRootProgress, StoredSubNode,
+};
+use reth_trie_db::{DatabaseStateRoot, PrefixSetLoader};
+use std::{fmt::Debug, ops::RangeInclusive};
 use tracing::*;
 
 // TODO: automate the process outlined below so the user can just send in a debugging package
@@ -137,7 +139,9 @@ where
         + StatsReader
         + HeaderProvider
         + StageCheckpointReader
-        + StageCheckpointWriter,
+        + StageCheckpointWriter
+        + ChangeSetReader
+        + StorageChangeSetReader,
 {
     /// Return the id of the stage
     fn id(&self) -> StageId {
@@ -249,12 +253,10 @@ where
             }
         } else {
             debug!(target: "sync::stages::merkle::exec", current = ?current_block_number, target = ?to_block, "Updating trie");
-            let (root, updates) =
-                StateRoot::incremental_root_with_updates(provider.tx_ref(), range)
-                    .map_err(|e| {
-                        error!(target: "sync::stages::merkle", %e, ?current_block_number, ?to_block, "Incremental state root failed! {INVALID_STATE_ROOT_ERROR_MESSAGE}");
-                        StageError::Fatal(Box::new(e))
-                    })?;
+            let (root, updates) = incremental_root_with_updates(provider, range).map_err(|e| {
+                error!(target: "sync::stages::merkle", %e, ?current_block_number, ?to_block, "Incremental state root failed! {INVALID_STATE_ROOT_ERROR_MESSAGE}");
+                e
+            })?;
 
             provider.write_trie_updates(&updates)?;
 
@@ -324,8 +326,7 @@ where
         if range.is_empty() {
             info!(target: "sync::stages::merkle::unwind", "Nothing to unwind");
         } else {
-            let (block_root, updates) = StateRoot::incremental_root_with_updates(tx, range)
-                .map_err(|e| StageError::Fatal(Box::new(e)))?;
+            let (block_root, updates) = incremental_root_with_updates(provider, range)?;
 
             // Validate the calculated state root
             let target = provider
@@ -347,6 +348,31 @@ where
     }
 }
 
+/// Calculates the state root and trie updates incrementally for the changes of the given block
+/// range.
+///
+/// Changesets are read through the provider, so blocks whose changesets were already moved to
+/// static files are included.
+fn incremental_root_with_updates<Provider>(
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+) -> Result<(B256, TrieUpdates), StageError>
+where
+    Provider: DBProvider + ChangeSetReader + StorageChangeSetReader,
+{
+    debug!(target: "sync::stages::merkle", ?range, "Incremental state root");
+    let accounts = provider.account_changesets_range(range.clone())?;
+    let storages = provider.storage_changesets_range(range)?;
+    let prefix_sets = PrefixSetLoader::new(provider.tx_ref()).load_changesets(
+        accounts.into_iter().map(|(_, change)| change.address),
+        storages.into_iter().map(|(block_address, entry)| (block_address.address(), entry.key)),
+    )?;
+    StateRoot::from_tx(provider.tx_ref())
+        .with_prefix_sets(prefix_sets)
+        .root_with_updates()
+        .map_err(|e| StageError::Fatal(Box::new(e)))
+}
+
 /// Check that the computed state root matches the root in the expected header.
 #[inline]
 fn validate_state_root(
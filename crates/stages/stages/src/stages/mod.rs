@@ -12,6 +12,8 @@ mod hashing_storage;
 mod headers;
 /// Index history of account changes
 mod index_account_history;
+/// Index transactions of addresses
+mod index_address_transactions;
 /// Index history of storage changes
 mod index_storage_history;
 /// Stage for computing state root.
@@ -19,6 +21,8 @@ mod merkle;
 mod prune;
 /// The sender recovery stage.
 mod sender_recovery;
+/// The snap state stage.
+mod snap_state;
 /// The transaction lookup stage
 mod tx_lookup;
 
@@ -29,10 +33,12 @@ pub use hashing_account::*;
 pub use hashing_storage::*;
 pub use headers::*;
 pub use index_account_history::*;
+pub use index_address_transactions::*;
 pub use index_storage_history::*;
 pub use merkle::*;
 pub use prune::*;
 pub use sender_recovery::*;
+pub use snap_state::*;
 pub use tx_lookup::*;
 
 mod utils;
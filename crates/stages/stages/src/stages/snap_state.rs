@@ -0,0 +1,1714 @@
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_primitives::{
+    keccak256,
+    map::{HashMap, HashSet},
+    BlockNumber, Bytes, B256, U256,
+};
+use alloy_rlp::Decodable;
+use alloy_trie::{
+    nodes::{encode_path_leaf, TrieNode},
+    proof::verify_proof,
+};
+use futures_util::FutureExt;
+use reth_config::config::SnapStateConfig;
+use reth_db::tables;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
+use reth_network_p2p::{
+    error::PeerRequestResult,
+    snap::client::{
+        AccountRange, GetAccountRange, GetByteCodes, GetStorageRanges, GetTrieNodes, SlimAccount,
+        SnapClient, StorageData, StorageRanges, TriePath,
+    },
+};
+use reth_network_peers::WithPeerId;
+use reth_primitives::{Account, Bytecode, StorageEntry};
+use reth_provider::{
+    DBProvider, HeaderProvider, ProviderError, StageCheckpointReader, StageCheckpointWriter,
+    TrieWriter,
+};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use reth_trie::{
+    prefix_set::TriePrefixSetsMut, root::storage_root, BranchNodeCompact, Nibbles, StateRoot,
+    StorageRoot, StoredNibbles, StoredNibblesSubKey, TrieAccount, EMPTY_ROOT_HASH,
+};
+use reth_trie_db::{DatabaseStateRoot, DatabaseStorageRoot};
+use std::{
+    collections::VecDeque,
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tracing::*;
+
+/// The delay before a failed request is retried, doubled with every failed attempt.
+const RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// The number of failed attempts after which the state of the pivot is considered unavailable.
+const MAX_REQUEST_ATTEMPTS: u32 = 8;
+
+/// The maximum number of accounts of a single storage ranges request.
+const MAX_STORAGE_ACCOUNTS_PER_REQUEST: usize = 128;
+
+/// The maximum number of bytecodes of a single bytecodes request.
+const MAX_BYTECODES_PER_REQUEST: usize = 128;
+
+/// The maximum number of trie nodes of a single trie nodes request.
+const MAX_TRIE_NODES_PER_REQUEST: usize = 128;
+
+/// The number of healing rounds against the same pivot after which the download starts over.
+const MAX_HEAL_ROUNDS: usize = 4;
+
+/// The stages that are advanced to the pivot block once its state was downloaded, since they
+/// build the hashed state and the trie up to the pivot otherwise.
+const PIVOT_STAGES: [StageId; 3] =
+    [StageId::AccountHashing, StageId::StorageHashing, StageId::MerkleExecute];
+
+/// The snap state stage downloads the hashed state of a pivot block from peers supporting the
+/// `snap/1` protocol instead of building it from the executed blocks. Unlike a full snap sync, it
+/// doesn't skip the execution of the blocks up to the pivot.
+///
+/// The accounts are downloaded in ranges of their hashes and written to
+/// [`tables::HashedAccounts`], along with their storage slots to [`tables::HashedStorages`] and
+/// their bytecodes to [`tables::Bytecodes`]. The edges of each account range are verified against
+/// the proof of the response, the storage of each account against the storage root of the account.
+///
+/// Peers only serve the state of recent blocks. If the state of the pivot isn't served anymore,
+/// the pivot is moved closer to the sync target and the download continues, keeping the state
+/// downloaded so far. Once all accounts are downloaded, the trie is built from the hashed state.
+/// If its root doesn't match the state root of the pivot, the trie is healed: the trie nodes that
+/// differ from the local trie are downloaded top-down, and the hashed state below them is
+/// replaced with their leaves.
+///
+/// Afterwards, the checkpoints of the hashing and merkle stages are set to the pivot block, so
+/// that they only process the blocks after it. The `snap` protocol only identifies accounts and
+/// storage slots by their hashes, so the plain state tables can't be populated from the downloaded
+/// state and all blocks are still executed. The stage only runs on a node that hasn't executed any
+/// blocks yet.
+pub struct SnapStateStage<C> {
+    /// The client used to request state from `snap` peers.
+    client: C,
+    /// The stage configuration.
+    config: SnapStateConfig,
+    /// The progress of the download, loaded on the first execution.
+    progress: Option<SnapStateProgress>,
+    /// The healing of the downloaded state, if it doesn't match the pivot.
+    heal: Option<SnapHeal>,
+    /// Whether the pivot has to be moved before the download continues.
+    stale: bool,
+    /// The download that is currently in progress.
+    download: Option<SnapDownloadFut>,
+    /// The finished download that is not yet written to the database.
+    buffer: Option<Result<SnapDownload, PivotUnavailable>>,
+}
+
+impl<C> SnapStateStage<C> {
+    /// Create new instance of [`SnapStateStage`].
+    pub const fn new(client: C, config: SnapStateConfig) -> Self {
+        Self {
+            client,
+            config,
+            progress: None,
+            heal: None,
+            stale: false,
+            download: None,
+            buffer: None,
+        }
+    }
+
+    /// Resets the download, e.g. if the pivot block was unwound.
+    fn reset(&mut self) {
+        self.progress = None;
+        self.heal = None;
+        self.stale = false;
+        self.download = None;
+        self.buffer = None;
+    }
+
+    /// Moves the pivot to the sync target, since peers stopped serving the state of the pivot.
+    ///
+    /// The state downloaded so far is kept, it's healed against the new pivot once all accounts
+    /// are downloaded. If the sync target didn't advance, the stage finishes without progress, so
+    /// that the pipeline picks up a new target first.
+    fn repivot<Provider>(
+        &mut self,
+        provider: &Provider,
+        input: ExecInput,
+        progress: SnapStateProgress,
+    ) -> Result<ExecOutput, StageError>
+    where
+        Provider: HeaderProvider + StageCheckpointWriter,
+    {
+        self.heal = self.heal.take().map(SnapHeal::interrupted);
+
+        let pivot = input.target().saturating_sub(self.config.pivot_distance);
+        if pivot <= progress.pivot {
+            info!(target: "sync::stages::snap_state", pivot = progress.pivot, "Pivot state is unavailable, waiting for a new sync target");
+            self.stale = true;
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let header = provider
+            .sealed_header(pivot)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(pivot.into()))?;
+        info!(target: "sync::stages::snap_state", previous = progress.pivot, pivot, state_root = ?header.state_root, "Moving the pivot");
+        let progress = SnapStateProgress { pivot, state_root: header.state_root, ..progress };
+        progress.save(provider)?;
+        self.progress = Some(progress);
+        self.stale = false;
+        Ok(ExecOutput { checkpoint: input.checkpoint(), done: false })
+    }
+}
+
+impl<C: fmt::Debug> fmt::Debug for SnapStateStage<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnapStateStage")
+            .field("client", &self.client)
+            .field("config", &self.config)
+            .field("progress", &self.progress)
+            .field("heal", &self.heal)
+            .field("stale", &self.stale)
+            .field("buffer", &self.buffer)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C, Provider> Stage<Provider> for SnapStateStage<C>
+where
+    C: SnapClient + Clone + 'static,
+    Provider: DBProvider<Tx: DbTxMut>
+        + HeaderProvider
+        + TrieWriter
+        + StageCheckpointReader
+        + StageCheckpointWriter,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::SnapState
+    }
+
+    fn poll_execute_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+        input: ExecInput,
+    ) -> Poll<Result<(), StageError>> {
+        if input.target_reached() || self.buffer.is_some() || self.stale {
+            return Poll::Ready(Ok(()))
+        }
+
+        // The download is started once the pivot was picked by the stage execution.
+        let Some(SnapStateProgress { state_root, next, .. }) = self.progress else {
+            return Poll::Ready(Ok(()))
+        };
+
+        let mut download: SnapDownloadFut = match self.download.take() {
+            Some(download) => download,
+            None => {
+                let client = self.client.clone();
+                let config = self.config;
+                if let Some(start) = next {
+                    Box::pin(
+                        download_batch(client, state_root, start, config)
+                            .map(|batch| batch.map(SnapDownload::State)),
+                    )
+                } else if let Some(heal) = self.heal.as_mut().filter(|heal| heal.has_pending()) {
+                    let (nodes, codes) = heal.take_pending(config.commit_threshold as usize);
+                    Box::pin(
+                        download_heal_batch(client, state_root, nodes, codes, config)
+                            .map(|batch| batch.map(SnapDownload::Heal)),
+                    )
+                } else {
+                    return Poll::Ready(Ok(()))
+                }
+            }
+        };
+        match download.poll_unpin(cx) {
+            Poll::Ready(result) => {
+                self.buffer = Some(result);
+                Poll::Ready(Ok(()))
+            }
+            Poll::Pending => {
+                self.download = Some(download);
+                Poll::Pending
+            }
+        }
+    }
+
+    /// Write the downloaded state and verify it once the download is complete.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let progress = match self.progress {
+            Some(progress) => progress,
+            None => {
+                // The state of a node that already executed blocks is kept.
+                let executed = provider
+                    .get_stage_checkpoint(StageId::Execution)?
+                    .unwrap_or_default()
+                    .block_number;
+                if input.checkpoint().block_number > 0 || executed > 0 {
+                    return Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
+                }
+
+                match SnapStateProgress::load(provider)? {
+                    Some(progress) => progress,
+                    None => {
+                        let pivot = input.target().saturating_sub(self.config.pivot_distance);
+                        if pivot == 0 {
+                            return Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
+                        }
+                        let header = provider
+                            .sealed_header(pivot)?
+                            .ok_or_else(|| ProviderError::HeaderNotFound(pivot.into()))?;
+                        info!(target: "sync::stages::snap_state", pivot, state_root = ?header.state_root, "Starting the pivot state download");
+
+                        // The downloaded state replaces the genesis state.
+                        clear_state(provider.tx_ref())?;
+                        let progress = SnapStateProgress {
+                            pivot,
+                            state_root: header.state_root,
+                            next: Some(B256::ZERO),
+                        };
+                        progress.save(provider)?;
+                        progress
+                    }
+                }
+            }
+        };
+        self.progress = Some(progress);
+
+        if self.stale {
+            return self.repivot(provider, input, progress)
+        }
+
+        match self.buffer.take() {
+            Some(Err(PivotUnavailable)) => return self.repivot(provider, input, progress),
+            Some(Ok(SnapDownload::State(batch))) => {
+                let next = batch.next;
+                batch.write(provider.tx_ref())?;
+
+                let progress = SnapStateProgress { next, ..progress };
+                progress.save(provider)?;
+                self.progress = Some(progress);
+
+                debug!(target: "sync::stages::snap_state", pivot = progress.pivot, ?next, "Wrote snap state batch");
+                return Ok(ExecOutput { checkpoint: input.checkpoint(), done: false })
+            }
+            Some(Ok(SnapDownload::Heal(batch))) => {
+                if let Some(heal) = &mut self.heal {
+                    heal.write(provider.tx_ref(), batch)?;
+                    debug!(target: "sync::stages::snap_state", pivot = progress.pivot, pending = heal.pending.len(), "Wrote healed trie nodes");
+                }
+                return Ok(ExecOutput { checkpoint: input.checkpoint(), done: false })
+            }
+            None => {}
+        }
+
+        // The download starts once the progress is known.
+        if progress.next.is_some() || self.heal.as_ref().is_some_and(SnapHeal::has_pending) {
+            return Ok(ExecOutput { checkpoint: input.checkpoint(), done: false })
+        }
+
+        // All accounts are downloaded, build the trie and check it against the pivot. After
+        // healing, only the parts of the trie that changed are rebuilt.
+        let (root, updates, rounds) = match self.heal.take() {
+            Some(heal) => {
+                let (root, updates) = StateRoot::from_tx(provider.tx_ref())
+                    .with_prefix_sets(heal.prefix_sets.freeze())
+                    .root_with_updates()
+                    .map_err(|e| StageError::Fatal(Box::new(e)))?;
+                (root, updates, heal.round)
+            }
+            None => {
+                info!(target: "sync::stages::snap_state", pivot = progress.pivot, "Building the trie of the downloaded state");
+                provider.tx_ref().clear::<tables::AccountsTrie>()?;
+                provider.tx_ref().clear::<tables::StoragesTrie>()?;
+                let (root, updates) = StateRoot::from_tx(provider.tx_ref())
+                    .root_with_updates()
+                    .map_err(|e| StageError::Fatal(Box::new(e)))?;
+                (root, updates, 0)
+            }
+        };
+        provider.write_trie_updates(&updates)?;
+
+        if root != progress.state_root {
+            if rounds >= MAX_HEAL_ROUNDS {
+                warn!(target: "sync::stages::snap_state", pivot = progress.pivot, ?root, state_root = ?progress.state_root, "Failed to heal the downloaded state, starting over");
+                clear_state(provider.tx_ref())?;
+                SnapStateProgress::clear(provider)?;
+                self.reset();
+                return Ok(ExecOutput { checkpoint: input.checkpoint(), done: false })
+            }
+
+            info!(target: "sync::stages::snap_state", pivot = progress.pivot, ?root, state_root = ?progress.state_root, "Healing the downloaded state");
+            self.heal = Some(SnapHeal::new(progress.state_root, rounds + 1));
+            return Ok(ExecOutput { checkpoint: input.checkpoint(), done: false })
+        }
+
+        for stage_id in PIVOT_STAGES {
+            provider.save_stage_checkpoint(stage_id, StageCheckpoint::new(progress.pivot))?;
+        }
+        SnapStateProgress::clear(provider)?;
+        self.reset();
+
+        info!(target: "sync::stages::snap_state", pivot = progress.pivot, "Finished the pivot state download");
+        Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        // A download in progress is restarted if its pivot is unwound.
+        if let Some(progress) = SnapStateProgress::load(provider)? {
+            if input.unwind_to < progress.pivot {
+                clear_state(provider.tx_ref())?;
+                SnapStateProgress::clear(provider)?;
+                self.reset();
+            }
+        }
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+    }
+}
+
+/// Clears the hashed state and the trie.
+fn clear_state<TX: DbTxMut>(tx: &TX) -> Result<(), StageError> {
+    tx.clear::<tables::HashedAccounts>()?;
+    tx.clear::<tables::HashedStorages>()?;
+    tx.clear::<tables::AccountsTrie>()?;
+    tx.clear::<tables::StoragesTrie>()?;
+    Ok(())
+}
+
+/// The progress of the pivot state download, persisted as the stage checkpoint progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SnapStateProgress {
+    /// The block the state is downloaded at.
+    pivot: BlockNumber,
+    /// The state root of the pivot block.
+    state_root: B256,
+    /// The hash of the next account to download, `None` if all accounts are downloaded.
+    next: Option<B256>,
+}
+
+impl SnapStateProgress {
+    /// Loads the progress, if a download is in progress.
+    fn load<Provider>(provider: &Provider) -> Result<Option<Self>, StageError>
+    where
+        Provider: StageCheckpointReader + HeaderProvider,
+    {
+        let buf = provider.get_stage_checkpoint_progress(StageId::SnapState)?.unwrap_or_default();
+        if buf.len() < 8 {
+            return Ok(None)
+        }
+
+        let pivot = BlockNumber::from_be_bytes(buf[..8].try_into().expect("8 bytes"));
+        let next = (buf.len() >= 40).then(|| B256::from_slice(&buf[8..40]));
+        let state_root = provider
+            .header_by_number(pivot)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(pivot.into()))?
+            .state_root;
+        Ok(Some(Self { pivot, state_root, next }))
+    }
+
+    /// Saves the progress.
+    fn save(&self, provider: &impl StageCheckpointWriter) -> Result<(), StageError> {
+        let mut buf = self.pivot.to_be_bytes().to_vec();
+        if let Some(next) = self.next {
+            buf.extend_from_slice(next.as_slice());
+        }
+        Ok(provider.save_stage_checkpoint_progress(StageId::SnapState, buf)?)
+    }
+
+    /// Clears the progress.
+    fn clear(provider: &impl StageCheckpointWriter) -> Result<(), StageError> {
+        Ok(provider.save_stage_checkpoint_progress(StageId::SnapState, vec![])?)
+    }
+}
+
+/// A pending download of the stage.
+type SnapDownloadFut =
+    Pin<Box<dyn Future<Output = Result<SnapDownload, PivotUnavailable>> + Send + Sync>>;
+
+/// A verified download, ready to be written to the database.
+#[derive(Debug)]
+enum SnapDownload {
+    /// The next batch of the state.
+    State(SnapStateBatch),
+    /// Trie nodes and bytecodes that are missing from the downloaded state.
+    Heal(SnapHealBatch),
+}
+
+/// Peers repeatedly failed to serve the state of the pivot, it's likely no longer available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PivotUnavailable;
+
+/// A verified batch of the state, ordered by account hash.
+#[derive(Debug, Default)]
+struct SnapStateBatch {
+    /// The accounts by hash.
+    accounts: Vec<(B256, Account)>,
+    /// The storage slots of the accounts by hash.
+    storages: Vec<(B256, Vec<StorageEntry>)>,
+    /// The bytecodes of the accounts by hash.
+    bytecodes: Vec<(B256, Bytes)>,
+    /// The hash of the next account to download, `None` if this is the last batch.
+    next: Option<B256>,
+}
+
+impl SnapStateBatch {
+    /// Writes the batch to the database.
+    fn write<TX: DbTxMut + DbTx>(self, tx: &TX) -> Result<(), StageError> {
+        for (hashed_address, account) in self.accounts {
+            tx.put::<tables::HashedAccounts>(hashed_address, account)?;
+        }
+        for (hashed_address, entries) in self.storages {
+            for entry in entries {
+                tx.put::<tables::HashedStorages>(hashed_address, entry)?;
+            }
+        }
+        for (code_hash, code) in self.bytecodes {
+            tx.put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code))?;
+        }
+        Ok(())
+    }
+}
+
+/// A trie node that differs from the local trie.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MissingTrieNode {
+    /// The hashed address of the account if the node belongs to its storage trie.
+    hashed_address: Option<B256>,
+    /// The path of the node.
+    path: Nibbles,
+    /// The hash of the node.
+    hash: B256,
+}
+
+impl MissingTrieNode {
+    /// Returns the path of the node as it's requested from peers.
+    fn trie_path(&self) -> TriePath {
+        let path = Bytes::from(encode_path_leaf(&self.path, false).to_vec());
+        match self.hashed_address {
+            Some(hashed_address) => TriePath(vec![hashed_address.into(), path]),
+            None => TriePath(vec![path]),
+        }
+    }
+}
+
+/// Verified trie nodes and bytecodes downloaded to heal the state.
+#[derive(Debug, Default)]
+struct SnapHealBatch {
+    /// The trie nodes along with their encoding.
+    nodes: Vec<(MissingTrieNode, Bytes)>,
+    /// The bytecodes by hash.
+    bytecodes: Vec<(B256, Bytes)>,
+}
+
+/// The healing of the downloaded state against the state root of the pivot.
+///
+/// The trie is walked top-down, starting at the root. Nodes whose hash matches the hash of the
+/// local node at the same path are skipped along with their children, all other nodes are
+/// downloaded. The hashed state below a downloaded node that isn't part of it is deleted, the
+/// leaves of the node are written.
+#[derive(Debug, Default)]
+struct SnapHeal {
+    /// The trie nodes to download.
+    pending: Vec<MissingTrieNode>,
+    /// The hashes of the bytecodes to download.
+    codes: HashSet<B256>,
+    /// The keys of the hashed state changed by the healing, used to update the trie.
+    prefix_sets: TriePrefixSetsMut,
+    /// The healing round against the current pivot.
+    round: usize,
+}
+
+impl SnapHeal {
+    /// Starts a healing round against the given state root.
+    fn new(state_root: B256, round: usize) -> Self {
+        let root =
+            MissingTrieNode { hashed_address: None, path: Nibbles::default(), hash: state_root };
+        Self { pending: vec![root], round, ..Default::default() }
+    }
+
+    /// Stops the healing, keeping the changes that are still to be applied to the trie.
+    fn interrupted(self) -> Self {
+        Self { prefix_sets: self.prefix_sets, ..Default::default() }
+    }
+
+    /// Returns `true` if there are trie nodes or bytecodes left to download.
+    fn has_pending(&self) -> bool {
+        !self.pending.is_empty() || !self.codes.is_empty()
+    }
+
+    /// Takes up to `limit` trie nodes and all bytecodes to download.
+    ///
+    /// The most recently discovered nodes are downloaded first, so that the pending nodes don't
+    /// grow with the width of the trie.
+    fn take_pending(&mut self, limit: usize) -> (Vec<MissingTrieNode>, HashSet<B256>) {
+        let nodes = self.pending.split_off(self.pending.len().saturating_sub(limit.max(1)));
+        (nodes, std::mem::take(&mut self.codes))
+    }
+
+    /// Writes the downloaded bytecodes and heals the state below the downloaded nodes.
+    fn write<TX: DbTxMut + DbTx>(
+        &mut self,
+        tx: &TX,
+        batch: SnapHealBatch,
+    ) -> Result<(), StageError> {
+        for (code_hash, code) in batch.bytecodes {
+            tx.put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code))?;
+        }
+        for (node, encoded) in batch.nodes {
+            self.heal_node(tx, node.hashed_address, node.path, decode_trie_node(&encoded)?)?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the local state below the given path with the state of the node.
+    fn heal_node<TX: DbTxMut + DbTx>(
+        &mut self,
+        tx: &TX,
+        hashed_address: Option<B256>,
+        path: Nibbles,
+        node: TrieNode,
+    ) -> Result<(), StageError> {
+        match node {
+            TrieNode::Branch(branch) => {
+                let local = local_branch_node(tx, hashed_address, &path)?;
+                for (nibble, child) in branch.as_ref().children() {
+                    let mut child_path = path.clone();
+                    child_path.push(nibble);
+
+                    let Some(child) = child else {
+                        self.delete_state(tx, hashed_address, &child_path, None)?;
+                        continue
+                    };
+                    match child.as_hash() {
+                        Some(hash) => {
+                            let local_hash = local
+                                .as_ref()
+                                .filter(|local| local.hash_mask.is_bit_set(nibble))
+                                .map(|local| local.hash_for_nibble(nibble));
+                            if local_hash != Some(hash) {
+                                self.pending.push(MissingTrieNode {
+                                    hashed_address,
+                                    path: child_path,
+                                    hash,
+                                });
+                            }
+                        }
+                        // embedded nodes are healed right away
+                        None => self.heal_node(
+                            tx,
+                            hashed_address,
+                            child_path,
+                            decode_trie_node(child.as_slice())?,
+                        )?,
+                    }
+                }
+            }
+            TrieNode::Extension(extension) => {
+                let child_path = path.join(&extension.key);
+                self.delete_state(tx, hashed_address, &path, Some(&child_path))?;
+                match extension.child.as_hash() {
+                    Some(hash) => self.pending.push(MissingTrieNode {
+                        hashed_address,
+                        path: child_path,
+                        hash,
+                    }),
+                    None => self.heal_node(
+                        tx,
+                        hashed_address,
+                        child_path,
+                        decode_trie_node(extension.child.as_slice())?,
+                    )?,
+                }
+            }
+            TrieNode::Leaf(leaf) => {
+                let key = path.join(&leaf.key);
+                if key.len() != 64 {
+                    return Err(StageError::Fatal(Box::new(SnapVerificationError::InvalidTrieNode)))
+                }
+                self.delete_state(tx, hashed_address, &path, Some(&key))?;
+
+                let key = B256::from_slice(&key.pack());
+                match hashed_address {
+                    Some(hashed_address) => {
+                        self.heal_storage_slot(tx, hashed_address, key, &leaf.value)?
+                    }
+                    None => self.heal_account(tx, key, &leaf.value)?,
+                }
+            }
+            TrieNode::EmptyRoot => self.delete_state(tx, hashed_address, &path, None)?,
+        }
+        Ok(())
+    }
+
+    /// Writes an account leaf, scheduling the healing of its storage and the download of its
+    /// bytecode if they're missing.
+    fn heal_account<TX: DbTxMut + DbTx>(
+        &mut self,
+        tx: &TX,
+        hashed_address: B256,
+        value: &[u8],
+    ) -> Result<(), StageError> {
+        let account = TrieAccount::decode(&mut &value[..])
+            .map_err(|_| StageError::Fatal(Box::new(SnapVerificationError::InvalidTrieNode)))?;
+        let bytecode_hash = (account.code_hash != KECCAK_EMPTY).then_some(account.code_hash);
+        tx.put::<tables::HashedAccounts>(
+            hashed_address,
+            Account { nonce: account.nonce, balance: account.balance, bytecode_hash },
+        )?;
+        self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+
+        if let Some(code_hash) = bytecode_hash {
+            if tx.get::<tables::Bytecodes>(code_hash)?.is_none() {
+                self.codes.insert(code_hash);
+            }
+        }
+
+        // The storage trie of the account is still the one the local trie was built from.
+        let storage_root = StorageRoot::from_tx_hashed(tx, hashed_address)
+            .root()
+            .map_err(|e| StageError::Fatal(Box::new(e)))?;
+        if storage_root != account.storage_root {
+            if account.storage_root == EMPTY_ROOT_HASH {
+                self.delete_state(tx, Some(hashed_address), &Nibbles::default(), None)?;
+            } else {
+                self.pending.push(MissingTrieNode {
+                    hashed_address: Some(hashed_address),
+                    path: Nibbles::default(),
+                    hash: account.storage_root,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a storage leaf.
+    fn heal_storage_slot<TX: DbTxMut + DbTx>(
+        &mut self,
+        tx: &TX,
+        hashed_address: B256,
+        key: B256,
+        value: &[u8],
+    ) -> Result<(), StageError> {
+        let value = U256::decode(&mut &value[..])
+            .map_err(|_| StageError::Fatal(Box::new(SnapVerificationError::InvalidTrieNode)))?;
+
+        let mut cursor = tx.cursor_dup_write::<tables::HashedStorages>()?;
+        if cursor.seek_by_key_subkey(hashed_address, key)?.is_some_and(|entry| entry.key == key) {
+            cursor.delete_current()?;
+        }
+        if !value.is_zero() {
+            cursor.upsert(hashed_address, StorageEntry::new(key, value))?;
+        }
+        self.storage_changed(hashed_address, Nibbles::unpack(key));
+        Ok(())
+    }
+
+    /// Deletes the hashed state below the given path, except for the state below `keep`.
+    ///
+    /// The state of the account trie is deleted if `hashed_address` is `None`, otherwise the
+    /// storage of the account.
+    fn delete_state<TX: DbTxMut + DbTx>(
+        &mut self,
+        tx: &TX,
+        hashed_address: Option<B256>,
+        path: &Nibbles,
+        keep: Option<&Nibbles>,
+    ) -> Result<(), StageError> {
+        let start = first_key_with_prefix(path);
+        match hashed_address {
+            None => {
+                let mut cursor = tx.cursor_write::<tables::HashedAccounts>()?;
+                let mut walker = cursor.walk(Some(start))?;
+                while let Some((hashed_address, _)) = walker.next().transpose()? {
+                    let key = Nibbles::unpack(hashed_address);
+                    if !key.starts_with(path) {
+                        break
+                    }
+                    if keep.is_some_and(|keep| key.starts_with(keep)) {
+                        continue
+                    }
+
+                    walker.delete_current()?;
+                    let mut storage = tx.cursor_dup_write::<tables::HashedStorages>()?;
+                    if storage.seek_exact(hashed_address)?.is_some() {
+                        storage.delete_current_duplicates()?;
+                    }
+                    self.prefix_sets.account_prefix_set.insert(key);
+                    self.prefix_sets.destroyed_accounts.insert(hashed_address);
+                }
+            }
+            Some(hashed_address) => {
+                let mut cursor = tx.cursor_dup_write::<tables::HashedStorages>()?;
+                let mut entry = cursor.seek_by_key_subkey(hashed_address, start)?;
+                while let Some(slot) = entry {
+                    let key = Nibbles::unpack(slot.key);
+                    if !key.starts_with(path) {
+                        break
+                    }
+                    if !keep.is_some_and(|keep| key.starts_with(keep)) {
+                        cursor.delete_current()?;
+                        self.storage_changed(hashed_address, key);
+                    }
+                    entry = cursor
+                        .next_dup()?
+                        .filter(|(key, _)| *key == hashed_address)
+                        .map(|(_, slot)| slot);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Marks a storage slot of the account as changed.
+    fn storage_changed(&mut self, hashed_address: B256, key: Nibbles) {
+        self.prefix_sets.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+        self.prefix_sets.storage_prefix_sets.entry(hashed_address).or_default().insert(key);
+    }
+}
+
+/// Returns the local branch node at the given path of the state trie, or of the storage trie of
+/// the given account.
+fn local_branch_node<TX: DbTx>(
+    tx: &TX,
+    hashed_address: Option<B256>,
+    path: &Nibbles,
+) -> Result<Option<BranchNodeCompact>, DatabaseError> {
+    match hashed_address {
+        None => tx.get::<tables::AccountsTrie>(StoredNibbles(path.clone())),
+        Some(hashed_address) => Ok(tx
+            .cursor_dup_read::<tables::StoragesTrie>()?
+            .seek_by_key_subkey(hashed_address, StoredNibblesSubKey(path.clone()))?
+            .filter(|entry| entry.nibbles.0 == *path)
+            .map(|entry| entry.node)),
+    }
+}
+
+/// Returns the first hash starting with the given nibbles.
+fn first_key_with_prefix(prefix: &Nibbles) -> B256 {
+    let mut key = B256::ZERO;
+    let packed = prefix.pack();
+    key[..packed.len()].copy_from_slice(&packed);
+    key
+}
+
+/// Decodes a trie node whose hash was verified.
+fn decode_trie_node(encoded: &[u8]) -> Result<TrieNode, StageError> {
+    TrieNode::decode(&mut &encoded[..])
+        .map_err(|_| StageError::Fatal(Box::new(SnapVerificationError::InvalidTrieNode)))
+}
+
+/// Counts the failed attempts of a download, giving up after [`MAX_REQUEST_ATTEMPTS`].
+#[derive(Debug, Default)]
+struct Attempts(u32);
+
+impl Attempts {
+    /// Records a failed attempt and waits before the next one, backing off exponentially.
+    async fn failed(&mut self) -> Result<(), PivotUnavailable> {
+        self.0 += 1;
+        if self.0 >= MAX_REQUEST_ATTEMPTS {
+            return Err(PivotUnavailable)
+        }
+        tokio::time::sleep(RETRY_DELAY * 2u32.pow(self.0.min(4) - 1)).await;
+        Ok(())
+    }
+}
+
+/// Downloads the next batch of the state, starting at the account with the given hash.
+///
+/// Failed requests and invalid responses are retried, until the state of the pivot is considered
+/// unavailable.
+async fn download_batch<C: SnapClient>(
+    client: C,
+    state_root: B256,
+    start: B256,
+    config: SnapStateConfig,
+) -> Result<SnapStateBatch, PivotUnavailable> {
+    let mut accounts = Vec::new();
+    let mut next = Some(start);
+    while let Some(start) = next {
+        if accounts.len() as u64 >= config.commit_threshold {
+            break
+        }
+
+        let range = download_account_range(&client, state_root, start, config).await?;
+        let Some((last, _)) = range.last() else {
+            next = None;
+            break
+        };
+        next = increment_hash(*last);
+        accounts.extend(range);
+    }
+
+    let with_storage = accounts
+        .iter()
+        .filter(|(_, (_, storage_root))| *storage_root != EMPTY_ROOT_HASH)
+        .map(|(hashed_address, (_, storage_root))| (*hashed_address, *storage_root))
+        .collect();
+    let storages = download_storages(&client, state_root, with_storage, config).await?;
+
+    let code_hashes =
+        accounts.iter().filter_map(|(_, (account, _))| account.bytecode_hash).collect();
+    let bytecodes = download_bytecodes(&client, code_hashes, config).await?;
+
+    Ok(SnapStateBatch {
+        accounts: accounts.into_iter().map(|(hash, (account, _))| (hash, account)).collect(),
+        storages,
+        bytecodes,
+        next,
+    })
+}
+
+/// Downloads the given trie nodes and bytecodes to heal the state.
+async fn download_heal_batch<C: SnapClient>(
+    client: C,
+    state_root: B256,
+    nodes: Vec<MissingTrieNode>,
+    code_hashes: HashSet<B256>,
+    config: SnapStateConfig,
+) -> Result<SnapHealBatch, PivotUnavailable> {
+    let nodes = download_trie_nodes(&client, state_root, nodes, config).await?;
+    let bytecodes = download_bytecodes(&client, code_hashes, config).await?;
+    Ok(SnapHealBatch { nodes, bytecodes })
+}
+
+/// Accounts along with their storage roots.
+type AccountsWithStorageRoot = Vec<(B256, (Account, B256))>;
+
+/// Downloads the next verified range of accounts, starting at the given hash.
+///
+/// Returns an empty range if there are no more accounts.
+async fn download_account_range<C: SnapClient>(
+    client: &C,
+    state_root: B256,
+    start: B256,
+    config: SnapStateConfig,
+) -> Result<AccountsWithStorageRoot, PivotUnavailable> {
+    let mut attempts = Attempts::default();
+    loop {
+        let request = GetAccountRange {
+            request_id: 0,
+            root_hash: state_root,
+            starting_hash: start,
+            limit_hash: B256::repeat_byte(0xff),
+            response_bytes: config.response_bytes,
+        };
+        let Some((peer_id, range)) =
+            send_request(|| client.get_account_range(request.clone())).await.map(WithPeerId::split)
+        else {
+            attempts.failed().await?;
+            continue
+        };
+
+        // An empty response without proof means the peer doesn't have the state.
+        if range.accounts.is_empty() && range.proof.is_empty() {
+            attempts.failed().await?;
+            continue
+        }
+
+        if let Err(err) = verify_account_range(state_root, start, &range) {
+            debug!(target: "sync::stages::snap_state", ?peer_id, %err, "Invalid account range");
+            client.report_bad_message(peer_id);
+            attempts.failed().await?;
+            continue
+        }
+
+        return Ok(range
+            .accounts
+            .into_iter()
+            .map(|data| {
+                let account = Account {
+                    nonce: data.body.nonce,
+                    balance: data.body.balance,
+                    bytecode_hash: (data.body.code_hash != KECCAK_EMPTY)
+                        .then_some(data.body.code_hash),
+                };
+                (data.hash, (account, data.body.storage_root))
+            })
+            .collect())
+    }
+}
+
+/// Downloads the verified storage slots of the given accounts.
+async fn download_storages<C: SnapClient>(
+    client: &C,
+    state_root: B256,
+    accounts: VecDeque<(B256, B256)>,
+    config: SnapStateConfig,
+) -> Result<Vec<(B256, Vec<StorageEntry>)>, PivotUnavailable> {
+    let mut storages = Vec::with_capacity(accounts.len());
+    let mut pending = accounts;
+    let mut attempts = Attempts::default();
+
+    while !pending.is_empty() {
+        let account_hashes = pending
+            .iter()
+            .take(MAX_STORAGE_ACCOUNTS_PER_REQUEST)
+            .map(|(hashed_address, _)| *hashed_address)
+            .collect();
+        let request = GetStorageRanges {
+            request_id: 0,
+            root_hash: state_root,
+            account_hashes,
+            starting_hash: Bytes::new(),
+            limit_hash: Bytes::new(),
+            response_bytes: config.response_bytes,
+        };
+        let Some((peer_id, ranges)) = send_request(|| client.get_storage_ranges(request.clone()))
+            .await
+            .map(WithPeerId::split)
+        else {
+            attempts.failed().await?;
+            continue
+        };
+        if ranges.slots.is_empty() {
+            attempts.failed().await?;
+            continue
+        }
+
+        match verify_storage_ranges(&pending, &ranges) {
+            Ok(verified) => {
+                let num_verified = verified.len();
+                storages.extend(verified);
+                pending.drain(..num_verified);
+                attempts = Attempts::default();
+            }
+            Err(err) => {
+                debug!(target: "sync::stages::snap_state", ?peer_id, %err, "Invalid storage ranges");
+                client.report_bad_message(peer_id);
+                attempts.failed().await?;
+                continue
+            }
+        }
+
+        // The last storage is incomplete if the response is proven, it's downloaded on its own.
+        if !ranges.proof.is_empty() {
+            let (hashed_address, storage_root) =
+                pending.pop_front().expect("more slots than accounts");
+            let entries = download_large_storage(
+                client,
+                state_root,
+                hashed_address,
+                storage_root,
+                ranges.slots.into_iter().last().unwrap_or_default(),
+                config,
+            )
+            .await?;
+            storages.push((hashed_address, entries));
+        }
+    }
+
+    Ok(storages)
+}
+
+/// Downloads the remaining storage slots of an account whose storage exceeds a single response.
+async fn download_large_storage<C: SnapClient>(
+    client: &C,
+    state_root: B256,
+    hashed_address: B256,
+    storage_root: B256,
+    slots: Vec<StorageData>,
+    config: SnapStateConfig,
+) -> Result<Vec<StorageEntry>, PivotUnavailable> {
+    let mut entries = storage_entries(&slots).unwrap_or_default();
+    let mut attempts = Attempts::default();
+
+    loop {
+        // the storage is complete if the last possible slot was received
+        let start = match entries.last() {
+            Some(entry) => increment_hash(entry.key),
+            None => Some(B256::ZERO),
+        };
+
+        if let Some(start) = start {
+            let request = GetStorageRanges {
+                request_id: 0,
+                root_hash: state_root,
+                account_hashes: vec![hashed_address],
+                starting_hash: Bytes::copy_from_slice(start.as_slice()),
+                limit_hash: Bytes::new(),
+                response_bytes: config.response_bytes,
+            };
+            let Some((peer_id, ranges)) =
+                send_request(|| client.get_storage_ranges(request.clone()))
+                    .await
+                    .map(WithPeerId::split)
+            else {
+                attempts.failed().await?;
+                continue
+            };
+            let Some(slots) = ranges.slots.into_iter().next().filter(|slots| !slots.is_empty())
+            else {
+                attempts.failed().await?;
+                continue
+            };
+            match storage_entries(&slots) {
+                Ok(slots) => entries.extend(slots),
+                Err(err) => {
+                    debug!(target: "sync::stages::snap_state", ?peer_id, %err, "Invalid storage range");
+                    client.report_bad_message(peer_id);
+                    attempts.failed().await?;
+                    continue
+                }
+            }
+            if !ranges.proof.is_empty() {
+                continue
+            }
+        }
+
+        if storage_root_of(&entries) == storage_root {
+            return Ok(entries)
+        }
+        // The slots were received from different peers, so a mismatch can't be attributed to one
+        // of them.
+        debug!(target: "sync::stages::snap_state", ?hashed_address, "Invalid large storage, retrying");
+        entries.clear();
+        attempts.failed().await?;
+    }
+}
+
+/// Downloads the given bytecodes.
+async fn download_bytecodes<C: SnapClient>(
+    client: &C,
+    code_hashes: HashSet<B256>,
+    config: SnapStateConfig,
+) -> Result<Vec<(B256, Bytes)>, PivotUnavailable> {
+    let mut bytecodes = Vec::with_capacity(code_hashes.len());
+    let mut pending = code_hashes;
+    let mut attempts = Attempts::default();
+
+    while !pending.is_empty() {
+        let request = GetByteCodes {
+            request_id: 0,
+            hashes: pending.iter().take(MAX_BYTECODES_PER_REQUEST).copied().collect(),
+            response_bytes: config.response_bytes,
+        };
+        let Some((peer_id, response)) =
+            send_request(|| client.get_byte_codes(request.clone())).await.map(WithPeerId::split)
+        else {
+            attempts.failed().await?;
+            continue
+        };
+
+        let mut received = 0;
+        for code in response.codes {
+            let code_hash = keccak256(&code);
+            if pending.remove(&code_hash) {
+                bytecodes.push((code_hash, code));
+                received += 1;
+            } else {
+                debug!(target: "sync::stages::snap_state", ?peer_id, ?code_hash, "Unrequested bytecode");
+                client.report_bad_message(peer_id);
+            }
+        }
+        if received == 0 {
+            attempts.failed().await?;
+        } else {
+            attempts = Attempts::default();
+        }
+    }
+
+    Ok(bytecodes)
+}
+
+/// Downloads the given trie nodes, verified against their hashes.
+async fn download_trie_nodes<C: SnapClient>(
+    client: &C,
+    state_root: B256,
+    nodes: Vec<MissingTrieNode>,
+    config: SnapStateConfig,
+) -> Result<Vec<(MissingTrieNode, Bytes)>, PivotUnavailable> {
+    let mut downloaded = Vec::with_capacity(nodes.len());
+    let mut pending = VecDeque::from(nodes);
+    let mut attempts = Attempts::default();
+
+    while !pending.is_empty() {
+        let request = GetTrieNodes {
+            request_id: 0,
+            root_hash: state_root,
+            paths: pending
+                .iter()
+                .take(MAX_TRIE_NODES_PER_REQUEST)
+                .map(|node| node.trie_path())
+                .collect(),
+            response_bytes: config.response_bytes,
+        };
+        let Some((peer_id, response)) =
+            send_request(|| client.get_trie_nodes(request.clone())).await.map(WithPeerId::split)
+        else {
+            attempts.failed().await?;
+            continue
+        };
+
+        // The response may be cut short, but the nodes must be in request order.
+        if response.nodes.len() > request.paths.len() ||
+            response
+                .nodes
+                .iter()
+                .zip(&pending)
+                .any(|(node, missing)| keccak256(node) != missing.hash)
+        {
+            debug!(target: "sync::stages::snap_state", ?peer_id, "Invalid trie nodes");
+            client.report_bad_message(peer_id);
+            attempts.failed().await?;
+            continue
+        }
+        if response.nodes.is_empty() {
+            attempts.failed().await?;
+            continue
+        }
+
+        attempts = Attempts::default();
+        downloaded.extend(pending.drain(..response.nodes.len()).zip(response.nodes));
+    }
+
+    Ok(downloaded)
+}
+
+/// Sends a request, logging the error if it failed.
+async fn send_request<T, F>(request: impl FnOnce() -> F) -> Option<WithPeerId<T>>
+where
+    F: Future<Output = PeerRequestResult<T>>,
+{
+    match request().await {
+        Ok(response) => Some(response),
+        Err(err) => {
+            trace!(target: "sync::stages::snap_state", %err, "Snap request failed");
+            None
+        }
+    }
+}
+
+/// Verifies an account range against the state root.
+///
+/// The accounts must be ordered and not precede the requested starting hash. A range without proof
+/// must contain all accounts of the trie, otherwise the starting hash and the last account are
+/// verified against the proof. The accounts in between are verified when the trie is built from
+/// the downloaded state.
+fn verify_account_range(
+    state_root: B256,
+    start: B256,
+    range: &AccountRange,
+) -> Result<(), SnapVerificationError> {
+    if range.accounts.first().is_some_and(|first| first.hash < start) ||
+        range.accounts.windows(2).any(|pair| pair[0].hash >= pair[1].hash)
+    {
+        return Err(SnapVerificationError::Unordered)
+    }
+
+    if range.proof.is_empty() {
+        let root = reth_trie::root::state_root(
+            range.accounts.iter().map(|data| (data.hash, trie_account(&data.body))),
+        );
+        return if start == B256::ZERO && root == state_root {
+            Ok(())
+        } else {
+            Err(SnapVerificationError::RootMismatch)
+        }
+    }
+
+    let proof = range.proof.iter().map(|node| (keccak256(node), node)).collect::<HashMap<_, _>>();
+    // the proof covers the starting hash, which may not exist, and the last account
+    let start_value = range
+        .accounts
+        .first()
+        .filter(|first| first.hash == start)
+        .map(|first| alloy_rlp::encode(trie_account(&first.body)));
+    verify_edge(state_root, start, start_value, &proof)?;
+    if let Some(last) = range.accounts.last() {
+        let last_value = alloy_rlp::encode(trie_account(&last.body));
+        verify_edge(state_root, last.hash, Some(last_value), &proof)?;
+    }
+    Ok(())
+}
+
+/// Returns the account as it's stored in the trie.
+const fn trie_account(body: &SlimAccount) -> TrieAccount {
+    TrieAccount {
+        nonce: body.nonce,
+        balance: body.balance,
+        storage_root: body.storage_root,
+        code_hash: body.code_hash,
+    }
+}
+
+/// Verifies the value of a key against the proof nodes, which aren't ordered.
+fn verify_edge(
+    root: B256,
+    key: B256,
+    expected: Option<Vec<u8>>,
+    proof: &HashMap<B256, &Bytes>,
+) -> Result<(), SnapVerificationError> {
+    let key = Nibbles::unpack(key);
+
+    // Collect the nodes along the path of the key, embedded nodes are resolved by the verification.
+    let mut path = Vec::new();
+    let mut walked = 0;
+    let mut next = Some(root);
+    while let Some(node) = next.take().and_then(|hash| proof.get(&hash)) {
+        path.push(*node);
+        let child = match TrieNode::decode(&mut &node[..])
+            .map_err(|_| SnapVerificationError::InvalidProof)?
+        {
+            TrieNode::Branch(branch) => {
+                let Some(nibble) = key.get(walked) else { break };
+                walked += 1;
+                branch.state_mask.is_bit_set(*nibble).then(|| {
+                    let index = (0..*nibble).filter(|i| branch.state_mask.is_bit_set(*i)).count();
+                    branch.stack[index].clone()
+                })
+            }
+            TrieNode::Extension(extension) => {
+                walked += extension.key.len();
+                Some(extension.child)
+            }
+            TrieNode::Leaf(_) | TrieNode::EmptyRoot => None,
+        };
+        next = child.and_then(|child| child.as_hash());
+    }
+
+    verify_proof(root, key, expected, path).map_err(|_| SnapVerificationError::InvalidProof)
+}
+
+/// Verifies the storage ranges of the given accounts, returning the verified complete storages.
+///
+/// If the response is proven, the last storage is incomplete and not verified.
+fn verify_storage_ranges(
+    accounts: &VecDeque<(B256, B256)>,
+    ranges: &StorageRanges,
+) -> Result<Vec<(B256, Vec<StorageEntry>)>, SnapVerificationError> {
+    if ranges.slots.len() > accounts.len().min(MAX_STORAGE_ACCOUNTS_PER_REQUEST) {
+        return Err(SnapVerificationError::Unrequested)
+    }
+
+    let num_complete = ranges.slots.len() - usize::from(!ranges.proof.is_empty());
+    let mut verified = Vec::with_capacity(num_complete);
+    for ((hashed_address, storage_root), slots) in
+        accounts.iter().zip(&ranges.slots).take(num_complete)
+    {
+        let entries = storage_entries(slots)?;
+        if storage_root_of(&entries) != *storage_root {
+            return Err(SnapVerificationError::RootMismatch)
+        }
+        verified.push((*hashed_address, entries));
+    }
+    Ok(verified)
+}
+
+/// Decodes the given storage slots, which must be ordered by hash.
+fn storage_entries(slots: &[StorageData]) -> Result<Vec<StorageEntry>, SnapVerificationError> {
+    if slots.windows(2).any(|pair| pair[0].hash >= pair[1].hash) {
+        return Err(SnapVerificationError::Unordered)
+    }
+    slots
+        .iter()
+        .map(|slot| {
+            let value = slot.value().map_err(|_| SnapVerificationError::InvalidValue)?;
+            Ok(StorageEntry::new(slot.hash, value))
+        })
+        .collect()
+}
+
+/// Computes the storage root of the given ordered storage slots.
+fn storage_root_of(entries: &[StorageEntry]) -> B256 {
+    storage_root(entries.iter().filter(|entry| !entry.value.is_zero()).map(|e| (e.key, e.value)))
+}
+
+/// Returns the hash following the given one, or `None` if it's the last possible hash.
+fn increment_hash(hash: B256) -> Option<B256> {
+    let (next, overflow) = U256::from_be_bytes(hash.0).overflowing_add(U256::from(1));
+    (!overflow).then(|| B256::from(next.to_be_bytes()))
+}
+
+/// Errors of the verification of `snap` responses.
+#[derive(Debug, thiserror::Error)]
+enum SnapVerificationError {
+    /// The entries of the response are not ordered by hash.
+    #[error("entries are not ordered")]
+    Unordered,
+    /// The entries don't match the expected root.
+    #[error("root mismatch")]
+    RootMismatch,
+    /// The proof doesn't prove the entries.
+    #[error("invalid proof")]
+    InvalidProof,
+    /// The response contains unrequested entries.
+    #[error("unrequested entries")]
+    Unrequested,
+    /// A storage value can't be decoded.
+    #[error("invalid storage value")]
+    InvalidValue,
+    /// A trie node can't be decoded or doesn't fit into the trie.
+    #[error("invalid trie node")]
+    InvalidTrieNode,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestStageDB;
+    use alloy_primitives::Sealable;
+    use alloy_trie::{proof::ProofRetainer, HashBuilder};
+    use reth_network_p2p::{
+        download::DownloadClient,
+        snap::client::{AccountData, ByteCodes, SnapFut, TrieNodes},
+    };
+    use reth_network_peers::PeerId;
+    use reth_primitives::SealedHeader;
+    use reth_provider::DatabaseProviderFactory;
+    use reth_stages_api::StageExt;
+    use reth_testing_utils::generators::{self, random_header_range};
+    use std::collections::BTreeMap;
+
+    /// A client serving a fixed state, at most two accounts per response. The state of other roots
+    /// isn't served.
+    #[derive(Debug, Clone, Default)]
+    struct TestSnapClient {
+        accounts: BTreeMap<B256, (SlimAccount, BTreeMap<B256, U256>)>,
+        codes: Vec<Bytes>,
+    }
+
+    impl TestSnapClient {
+        fn state_root(&self) -> B256 {
+            self.account_range(B256::ZERO).0
+        }
+
+        /// Returns the state root along with the response to an account range request.
+        fn account_range(&self, start: B256) -> (B256, AccountRange) {
+            let accounts = self
+                .accounts
+                .range(start..)
+                .take(2)
+                .map(|(hash, (body, _))| AccountData { hash: *hash, body: *body })
+                .collect::<Vec<_>>();
+
+            let mut targets = vec![Nibbles::unpack(start)];
+            targets.extend(accounts.last().map(|last| Nibbles::unpack(last.hash)));
+            let mut hb = HashBuilder::default().with_proof_retainer(ProofRetainer::new(targets));
+            for (hash, (body, _)) in &self.accounts {
+                hb.add_leaf(Nibbles::unpack(hash), &alloy_rlp::encode(trie_account(body)));
+            }
+            let root = hb.root();
+            let proof = hb.take_proof_nodes().into_nodes_sorted().into_iter().map(|(_, node)| node);
+            (root, AccountRange { request_id: 0, accounts, proof: proof.collect() })
+        }
+
+        /// Returns the nodes of the state trie and of the storage tries by their requested path.
+        fn trie_nodes(&self) -> HashMap<Vec<Bytes>, Bytes> {
+            let mut nodes = HashMap::default();
+            let targets = self.accounts.keys().map(Nibbles::unpack).collect();
+            let mut hb = HashBuilder::default().with_proof_retainer(ProofRetainer::new(targets));
+            for (hash, (body, storage)) in &self.accounts {
+                hb.add_leaf(Nibbles::unpack(hash), &alloy_rlp::encode(trie_account(body)));
+
+                let targets = storage.keys().map(Nibbles::unpack).collect();
+                let mut storage_hb =
+                    HashBuilder::default().with_proof_retainer(ProofRetainer::new(targets));
+                for (slot, value) in storage {
+                    storage_hb
+                        .add_leaf(Nibbles::unpack(slot), &alloy_rlp::encode_fixed_size(value));
+                }
+                storage_hb.root();
+                for (path, node) in storage_hb.take_proof_nodes().into_nodes_sorted() {
+                    let node_path =
+                        MissingTrieNode { hashed_address: Some(*hash), path, hash: B256::ZERO };
+                    nodes.insert(node_path.trie_path().0, node);
+                }
+            }
+            hb.root();
+            for (path, node) in hb.take_proof_nodes().into_nodes_sorted() {
+                let node_path = MissingTrieNode { hashed_address: None, path, hash: B256::ZERO };
+                nodes.insert(node_path.trie_path().0, node);
+            }
+            nodes
+        }
+    }
+
+    impl DownloadClient for TestSnapClient {
+        fn report_bad_message(&self, _peer_id: PeerId) {
+            panic!("unexpected bad message")
+        }
+
+        fn num_connected_peers(&self) -> usize {
+            1
+        }
+    }
+
+    impl SnapClient for TestSnapClient {
+        fn get_account_range(&self, request: GetAccountRange) -> SnapFut<AccountRange> {
+            let (root, mut range) = self.account_range(request.starting_hash);
+            if root != request.root_hash {
+                range = AccountRange::default();
+            }
+            Box::pin(std::future::ready(Ok(WithPeerId::new(PeerId::ZERO, range))))
+        }
+
+        fn get_storage_ranges(&self, request: GetStorageRanges) -> SnapFut<StorageRanges> {
+            if request.root_hash != self.state_root() {
+                let ranges = StorageRanges::default();
+                return Box::pin(std::future::ready(Ok(WithPeerId::new(PeerId::ZERO, ranges))))
+            }
+
+            let slots = request
+                .account_hashes
+                .iter()
+                .map(|hash| {
+                    self.accounts[hash]
+                        .1
+                        .iter()
+                        .map(|(slot, value)| StorageData::new(*slot, *value))
+                        .collect()
+                })
+                .collect();
+            let ranges = StorageRanges { request_id: 0, slots, proof: vec![] };
+            Box::pin(std::future::ready(Ok(WithPeerId::new(PeerId::ZERO, ranges))))
+        }
+
+        fn get_byte_codes(&self, request: GetByteCodes) -> SnapFut<ByteCodes> {
+            let codes = self
+                .codes
+                .iter()
+                .filter(|code| request.hashes.contains(&keccak256(code)))
+                .cloned()
+                .collect();
+            let codes = ByteCodes { request_id: 0, codes };
+            Box::pin(std::future::ready(Ok(WithPeerId::new(PeerId::ZERO, codes))))
+        }
+
+        fn get_trie_nodes(&self, request: GetTrieNodes) -> SnapFut<TrieNodes> {
+            let mut nodes = Vec::new();
+            if request.root_hash == self.state_root() {
+                let trie_nodes = self.trie_nodes();
+                nodes = request
+                    .paths
+                    .iter()
+                    .map_while(|path| trie_nodes.get(&path.0).cloned())
+                    .collect();
+            }
+            let nodes = TrieNodes { request_id: 0, nodes };
+            Box::pin(std::future::ready(Ok(WithPeerId::new(PeerId::ZERO, nodes))))
+        }
+    }
+
+    fn test_client() -> TestSnapClient {
+        let code = Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0xf3]);
+        let storage = BTreeMap::from([
+            (B256::with_last_byte(1), U256::from(1)),
+            (B256::with_last_byte(2), U256::from(2)),
+        ]);
+        let contract = SlimAccount {
+            nonce: 1,
+            balance: U256::ZERO,
+            storage_root: storage_root(storage.clone()),
+            code_hash: keccak256(&code),
+        };
+
+        let mut client = TestSnapClient { codes: vec![code], ..Default::default() };
+        client.accounts.insert(B256::repeat_byte(0x90), (contract, storage));
+        for i in 1..=4u8 {
+            let eoa = SlimAccount { nonce: i as u64, balance: U256::from(i), ..Default::default() };
+            client.accounts.insert(B256::repeat_byte(i * 0x20), (eoa, BTreeMap::new()));
+        }
+        client
+    }
+
+    async fn execute_to_completion<Provider>(
+        stage: &mut impl Stage<Provider>,
+        provider: &Provider,
+        mut input: ExecInput,
+    ) -> StageCheckpoint {
+        loop {
+            stage.execute_ready(input).await.unwrap();
+            let output = stage.execute(provider, input).unwrap();
+            input.checkpoint = Some(output.checkpoint);
+            if output.done {
+                return output.checkpoint
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn download_pivot_state() {
+        let db = TestStageDB::default();
+        let client = test_client();
+
+        let mut rng = generators::rng();
+        let mut headers = random_header_range(&mut rng, 0..11, B256::ZERO);
+        let mut pivot = headers[8].clone().unseal();
+        pivot.state_root = client.state_root();
+        let (pivot, seal) = pivot.seal_slow().into_parts();
+        headers[8] = SealedHeader::new(pivot, seal);
+        db.insert_headers(headers.iter()).unwrap();
+
+        let config = SnapStateConfig {
+            enabled: true,
+            pivot_distance: 2,
+            commit_threshold: 2,
+            ..Default::default()
+        };
+        let mut stage = SnapStateStage::new(client.clone(), config);
+        let provider = db.factory.database_provider_rw().unwrap();
+        let checkpoint = execute_to_completion(
+            &mut stage,
+            &provider,
+            ExecInput { target: Some(10), checkpoint: None },
+        )
+        .await;
+        assert_eq!(checkpoint, StageCheckpoint::new(10));
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::AccountHashing).unwrap(),
+            Some(StageCheckpoint::new(8))
+        );
+        assert_eq!(provider.get_stage_checkpoint(StageId::Execution).unwrap(), None);
+        assert_eq!(
+            provider.get_stage_checkpoint_progress(StageId::SnapState).unwrap(),
+            Some(vec![])
+        );
+
+        let tx = provider.tx_ref();
+        let accounts = tx
+            .cursor_read::<tables::HashedAccounts>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .map(|entry| entry.unwrap().0)
+            .collect::<Vec<_>>();
+        assert_eq!(accounts, client.accounts.keys().copied().collect::<Vec<_>>());
+        let mut storage = tx.cursor_dup_read::<tables::HashedStorages>().unwrap();
+        assert_eq!(storage.walk(None).unwrap().count(), 2);
+        assert!(tx.get::<tables::Bytecodes>(keccak256(&client.codes[0])).unwrap().is_some());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn heal_state_after_pivot_moved() {
+        let db = TestStageDB::default();
+        let client = test_client();
+
+        // The state changes after the first batch of two accounts is downloaded.
+        let mut moved = client.clone();
+        moved.accounts.remove(&B256::repeat_byte(0x40));
+        let eoa = SlimAccount { nonce: 7, balance: U256::from(7), ..Default::default() };
+        moved.accounts.insert(B256::repeat_byte(0x10), (eoa, BTreeMap::new()));
+        let storage = BTreeMap::from([
+            (B256::with_last_byte(1), U256::from(5)),
+            (B256::with_last_byte(7), U256::from(6)),
+        ]);
+        let (account, slots) = moved.accounts.get_mut(&B256::repeat_byte(0x20)).unwrap();
+        account.balance += U256::from(1);
+        account.storage_root = storage_root(storage.clone());
+        *slots = storage;
+
+        let mut rng = generators::rng();
+        let mut headers = random_header_range(&mut rng, 0..13, B256::ZERO);
+        for (number, state_root) in [(8, client.state_root()), (10, moved.state_root())] {
+            let mut header = headers[number].clone().unseal();
+            header.state_root = state_root;
+            let (header, seal) = header.seal_slow().into_parts();
+            headers[number] = SealedHeader::new(header, seal);
+        }
+        db.insert_headers(headers.iter()).unwrap();
+
+        let config = SnapStateConfig {
+            enabled: true,
+            pivot_distance: 2,
+            commit_threshold: 2,
+            ..Default::default()
+        };
+        let provider = db.factory.database_provider_rw().unwrap();
+        let input = ExecInput { target: Some(10), checkpoint: None };
+
+        let mut stage = SnapStateStage::new(client, config);
+        for _ in 0..2 {
+            stage.execute_ready(input).await.unwrap();
+            assert!(!stage.execute(&provider, input).unwrap().done);
+        }
+
+        // Peers stopped serving the pivot state, the pivot can't move before the target does.
+        let mut stage = SnapStateStage::new(moved.clone(), config);
+        let checkpoint = execute_to_completion(&mut stage, &provider, input).await;
+        assert_eq!(checkpoint, StageCheckpoint::new(0));
+
+        let input = ExecInput { target: Some(12), checkpoint: Some(checkpoint) };
+        let checkpoint = execute_to_completion(&mut stage, &provider, input).await;
+        assert_eq!(checkpoint, StageCheckpoint::new(12));
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::AccountHashing).unwrap(),
+            Some(StageCheckpoint::new(10))
+        );
+
+        let tx = provider.tx_ref();
+        let accounts = tx
+            .cursor_read::<tables::HashedAccounts>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .map(|entry| entry.unwrap())
+            .collect::<Vec<_>>();
+        let expected = moved
+            .accounts
+            .iter()
+            .map(|(hash, (body, _))| {
+                let bytecode_hash = (body.code_hash != KECCAK_EMPTY).then_some(body.code_hash);
+                (*hash, Account { nonce: body.nonce, balance: body.balance, bytecode_hash })
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(accounts, expected);
+
+        let storages = tx
+            .cursor_dup_read::<tables::HashedStorages>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .map(|entry| {
+                let (hashed_address, entry) = entry.unwrap();
+                (hashed_address, entry.key, entry.value)
+            })
+            .collect::<Vec<_>>();
+        let expected = moved
+            .accounts
+            .iter()
+            .flat_map(|(hash, (_, storage))| {
+                storage.iter().map(|(slot, value)| (*hash, *slot, *value))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(storages, expected);
+    }
+
+    #[test]
+    fn reject_tampered_account_range() {
+        let client = test_client();
+        let state_root = client.state_root();
+        let start = B256::repeat_byte(0x30);
+
+        let (_, mut range) = client.account_range(start);
+        assert!(verify_account_range(state_root, start, &range).is_ok());
+
+        range.accounts[1].body.balance += U256::from(1);
+        assert!(matches!(
+            verify_account_range(state_root, start, &range),
+            Err(SnapVerificationError::InvalidProof)
+        ));
+
+        range.accounts.swap(0, 1);
+        assert!(matches!(
+            verify_account_range(state_root, start, &range),
+            Err(SnapVerificationError::Unordered)
+        ));
+    }
+}
@@ -20,6 +20,13 @@ pub enum StageId {
     TransactionLookup,
     IndexStorageHistory,
     IndexAccountHistory,
+    /// Optional stage indexing the transactions each address took part in. Not part of
+    /// [`StageId::ALL`], since it's only run if explicitly enabled.
+    IndexAddressTransactions,
+    /// Optional stage downloading the hashed state at a pivot block instead of hashing and
+    /// merkleizing the blocks up to it. Not part of [`StageId::ALL`], since it's only run if
+    /// explicitly enabled.
+    SnapState,
     Prune,
     Finish,
     /// Other custom stage with a provided string identifier.
@@ -75,6 +82,8 @@ impl StageId {
             Self::TransactionLookup => "TransactionLookup",
             Self::IndexAccountHistory => "IndexAccountHistory",
             Self::IndexStorageHistory => "IndexStorageHistory",
+            Self::IndexAddressTransactions => "IndexAddressTransactions",
+            Self::SnapState => "SnapState",
             Self::Prune => "Prune",
             Self::Finish => "Finish",
             Self::Other(s) => s,
@@ -119,6 +128,8 @@ mod tests {
         assert_eq!(StageId::MerkleExecute.to_string(), "MerkleExecute");
         assert_eq!(StageId::IndexAccountHistory.to_string(), "IndexAccountHistory");
         assert_eq!(StageId::IndexStorageHistory.to_string(), "IndexStorageHistory");
+        assert_eq!(StageId::IndexAddressTransactions.to_string(), "IndexAddressTransactions");
+        assert_eq!(StageId::SnapState.to_string(), "SnapState");
         assert_eq!(StageId::TransactionLookup.to_string(), "TransactionLookup");
         assert_eq!(StageId::Finish.to_string(), "Finish");
 
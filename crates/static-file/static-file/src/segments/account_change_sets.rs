@@ -0,0 +1,57 @@
+use crate::segments::Segment;
+use alloy_primitives::BlockNumber;
+use reth_db::tables;
+use reth_db_api::{cursor::DbCursorRO, models::StoredBlockAccountChangeSet, transaction::DbTx};
+use reth_provider::{
+    providers::{StaticFileProvider, StaticFileWriter},
+    DBProvider,
+};
+use reth_static_file_types::StaticFileSegment;
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeInclusive;
+
+/// Static File segment responsible for [`StaticFileSegment::AccountChangeSets`] part of data.
+#[derive(Debug, Default)]
+pub struct AccountChangeSets;
+
+impl<Provider: DBProvider> Segment<Provider> for AccountChangeSets {
+    fn segment(&self) -> StaticFileSegment {
+        StaticFileSegment::AccountChangeSets
+    }
+
+    fn copy_to_static_files(
+        &self,
+        provider: Provider,
+        static_file_provider: StaticFileProvider,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let mut static_file_writer = static_file_provider
+            .get_writer(*block_range.start(), StaticFileSegment::AccountChangeSets)?;
+
+        let mut changesets_cursor = provider.tx_ref().cursor_read::<tables::AccountChangeSets>()?;
+        let changesets_walker = changesets_cursor.walk_range(block_range.clone())?;
+
+        // Every block gets a row, even if it didn't change any account, so the static file stays
+        // indexed by block number.
+        let mut next_block = *block_range.start();
+        let mut change_set = StoredBlockAccountChangeSet::default();
+        for entry in changesets_walker {
+            let (block, change) = entry?;
+            while next_block < block {
+                let _static_file_block = static_file_writer
+                    .append_account_change_set(next_block, &std::mem::take(&mut change_set))?;
+                debug_assert_eq!(_static_file_block, next_block);
+                next_block += 1;
+            }
+            change_set.changes.push(change);
+        }
+
+        for block in next_block..=*block_range.end() {
+            let _static_file_block = static_file_writer
+                .append_account_change_set(block, &std::mem::take(&mut change_set))?;
+            debug_assert_eq!(_static_file_block, block);
+        }
+
+        Ok(())
+    }
+}
@@ -37,6 +37,17 @@ impl<Provider> StaticFileProducer<Provider> {
     pub fn new(provider: Provider, prune_modes: PruneModes) -> Self {
         Self(Arc::new(Mutex::new(StaticFileProducerInner::new(provider, prune_modes))))
     }
+
+    /// Sets whether account and storage changesets are moved to static files. Disabled by
+    /// default.
+    pub fn with_change_sets(self, account_change_sets: bool, storage_change_sets: bool) -> Self {
+        {
+            let mut inner = self.0.lock();
+            inner.account_change_sets = account_change_sets;
+            inner.storage_change_sets = storage_change_sets;
+        }
+        self
+    }
 }
 
 impl<Provider> Clone for StaticFileProducer<Provider> {
@@ -63,6 +74,10 @@ pub struct StaticFileProducerInner<Provider> {
     /// needed in [`StaticFileProducerInner`] to prevent attempting to move prunable data to static
     /// files. See [`StaticFileProducerInner::get_static_file_targets`].
     prune_modes: PruneModes,
+    /// Whether account changesets are moved to static files.
+    account_change_sets: bool,
+    /// Whether storage changesets are moved to static files.
+    storage_change_sets: bool,
     event_sender: EventSender<StaticFileProducerEvent>,
 }
 
@@ -72,12 +87,18 @@ pub struct StaticFileTargets {
     headers: Option<RangeInclusive<BlockNumber>>,
     receipts: Option<RangeInclusive<BlockNumber>>,
     transactions: Option<RangeInclusive<BlockNumber>>,
+    account_change_sets: Option<RangeInclusive<BlockNumber>>,
+    storage_change_sets: Option<RangeInclusive<BlockNumber>>,
 }
 
 impl StaticFileTargets {
     /// Returns `true` if any of the targets are [Some].
     pub const fn any(&self) -> bool {
-        self.headers.is_some() || self.receipts.is_some() || self.transactions.is_some()
+        self.headers.is_some() ||
+            self.receipts.is_some() ||
+            self.transactions.is_some() ||
+            self.account_change_sets.is_some() ||
+            self.storage_change_sets.is_some()
     }
 
     // Returns `true` if all targets are either [`None`] or has beginning of the range equal to the
@@ -87,6 +108,8 @@ impl StaticFileTargets {
             (self.headers.as_ref(), static_files.headers),
             (self.receipts.as_ref(), static_files.receipts),
             (self.transactions.as_ref(), static_files.transactions),
+            (self.account_change_sets.as_ref(), static_files.account_change_sets),
+            (self.storage_change_sets.as_ref(), static_files.storage_change_sets),
         ]
         .iter()
         .all(|(target_block_range, highest_static_fileted_block)| {
@@ -102,7 +125,13 @@ impl StaticFileTargets {
 
 impl<Provider> StaticFileProducerInner<Provider> {
     fn new(provider: Provider, prune_modes: PruneModes) -> Self {
-        Self { provider, prune_modes, event_sender: Default::default() }
+        Self {
+            provider,
+            prune_modes,
+            account_change_sets: false,
+            storage_change_sets: false,
+            event_sender: Default::default(),
+        }
     }
 }
 
@@ -162,6 +191,12 @@ where
         if let Some(block_range) = targets.receipts.clone() {
             segments.push((Box::new(segments::Receipts), block_range));
         }
+        if let Some(block_range) = targets.account_change_sets.clone() {
+            segments.push((Box::new(segments::AccountChangeSets), block_range));
+        }
+        if let Some(block_range) = targets.storage_change_sets.clone() {
+            segments.push((Box::new(segments::StorageChangeSets), block_range));
+        }
 
         segments.par_iter().try_for_each(|(segment, block_range)| -> ProviderResult<()> {
             debug!(target: "static_file", segment = %segment.segment(), ?block_range, "StaticFileProducer segment");
@@ -200,15 +235,25 @@ where
     /// Returns highest block numbers for all static file segments.
     pub fn copy_to_static_files(&self) -> ProviderResult<HighestStaticFiles> {
         let provider = self.provider.database_provider_ro()?;
-        let stages_checkpoints = [StageId::Headers, StageId::Execution, StageId::Bodies]
-            .into_iter()
-            .map(|stage| provider.get_stage_checkpoint(stage).map(|c| c.map(|c| c.block_number)))
-            .collect::<Result<Vec<_>, _>>()?;
+        let stages_checkpoints = [
+            StageId::Headers,
+            StageId::Execution,
+            StageId::Bodies,
+            StageId::IndexAccountHistory,
+            StageId::IndexStorageHistory,
+        ]
+        .into_iter()
+        .map(|stage| provider.get_stage_checkpoint(stage).map(|c| c.map(|c| c.block_number)))
+        .collect::<Result<Vec<_>, _>>()?;
 
         let highest_static_files = HighestStaticFiles {
             headers: stages_checkpoints[0],
             receipts: stages_checkpoints[1],
             transactions: stages_checkpoints[2],
+            blob_sidecars: None,
+            // Changesets are needed in the database until history indices are built from them
+            account_change_sets: stages_checkpoints[3],
+            storage_change_sets: stages_checkpoints[4],
         };
         let targets = self.get_static_file_targets(highest_static_files)?;
         self.run(targets)?;
@@ -248,6 +293,32 @@ where
                     finalized_block_number,
                 )
             }),
+            // StaticFile changesets only if enabled and history is not pruned according to the
+            // user configuration
+            account_change_sets: if self.account_change_sets &&
+                self.prune_modes.account_history.is_none()
+            {
+                finalized_block_numbers.account_change_sets.and_then(|finalized_block_number| {
+                    self.get_static_file_target(
+                        highest_static_files.account_change_sets,
+                        finalized_block_number,
+                    )
+                })
+            } else {
+                None
+            },
+            storage_change_sets: if self.storage_change_sets &&
+                self.prune_modes.storage_history.is_none()
+            {
+                finalized_block_numbers.storage_change_sets.and_then(|finalized_block_number| {
+                    self.get_static_file_target(
+                        highest_static_files.storage_change_sets,
+                        finalized_block_number,
+                    )
+                })
+            } else {
+                None
+            },
         };
 
         trace!(
@@ -277,12 +348,18 @@ mod tests {
     use crate::static_file_producer::{
         StaticFileProducer, StaticFileProducerInner, StaticFileTargets,
     };
-    use alloy_primitives::{B256, U256};
+    use alloy_primitives::{Address, B256, U256};
     use assert_matches::assert_matches;
-    use reth_db_api::{database::Database, transaction::DbTx};
+    use reth_db::tables;
+    use reth_db_api::{
+        database::Database,
+        models::AccountBeforeTx,
+        transaction::{DbTx, DbTxMut},
+    };
+    use reth_primitives::StorageEntry;
     use reth_provider::{
-        providers::StaticFileWriter, test_utils::MockNodeTypesWithDB, ProviderError,
-        ProviderFactory, StaticFileProviderFactory,
+        providers::StaticFileWriter, test_utils::MockNodeTypesWithDB, ChangeSetReader,
+        ProviderError, ProviderFactory, StaticFileProviderFactory, StorageChangeSetReader,
     };
     use reth_prune_types::PruneModes;
     use reth_stages::test_utils::{StorageKind, TestStageDB};
@@ -317,6 +394,16 @@ mod tests {
             TestStageDB::insert_header(None, &tx, &block.header, U256::ZERO)
                 .expect("insert block header");
         }
+        tx.put::<tables::AccountChangeSets>(
+            1,
+            AccountBeforeTx { address: Address::with_last_byte(1), info: None },
+        )
+        .expect("insert account changeset");
+        tx.put::<tables::StorageChangeSets>(
+            (1, Address::with_last_byte(1)).into(),
+            StorageEntry { key: B256::with_last_byte(2), value: U256::from(3) },
+        )
+        .expect("insert storage changeset");
         tx.commit().expect("commit tx");
 
         let mut receipts = Vec::new();
@@ -337,13 +424,18 @@ mod tests {
         let (provider_factory, _temp_static_files_dir) = setup();
 
         let static_file_producer =
-            StaticFileProducerInner::new(provider_factory.clone(), PruneModes::default());
+            StaticFileProducer::new(provider_factory.clone(), PruneModes::default())
+                .with_change_sets(true, true);
+        let static_file_producer = static_file_producer.lock();
 
         let targets = static_file_producer
             .get_static_file_targets(HighestStaticFiles {
                 headers: Some(1),
                 receipts: Some(1),
                 transactions: Some(1),
+                blob_sidecars: None,
+                account_change_sets: Some(1),
+                storage_change_sets: Some(1),
             })
             .expect("get static file targets");
         assert_eq!(
@@ -351,13 +443,35 @@ mod tests {
             StaticFileTargets {
                 headers: Some(0..=1),
                 receipts: Some(0..=1),
-                transactions: Some(0..=1)
+                transactions: Some(0..=1),
+                account_change_sets: Some(0..=1),
+                storage_change_sets: Some(0..=1)
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
         assert_eq!(
             provider_factory.static_file_provider().get_highest_static_files(),
-            HighestStaticFiles { headers: Some(1), receipts: Some(1), transactions: Some(1) }
+            HighestStaticFiles {
+                headers: Some(1),
+                receipts: Some(1),
+                transactions: Some(1),
+                blob_sidecars: None,
+                account_change_sets: Some(1),
+                storage_change_sets: Some(1)
+            }
+        );
+        let static_file_provider = provider_factory.static_file_provider();
+        assert_eq!(static_file_provider.account_block_changeset(0).unwrap(), vec![]);
+        assert_eq!(
+            static_file_provider.account_block_changeset(1).unwrap(),
+            vec![AccountBeforeTx { address: Address::with_last_byte(1), info: None }]
+        );
+        assert_eq!(
+            static_file_provider.storage_changeset(1).unwrap(),
+            vec![(
+                (1, Address::with_last_byte(1)).into(),
+                StorageEntry { key: B256::with_last_byte(2), value: U256::from(3) }
+            )]
         );
 
         let targets = static_file_producer
@@ -365,6 +479,9 @@ mod tests {
                 headers: Some(3),
                 receipts: Some(3),
                 transactions: Some(3),
+                blob_sidecars: None,
+                account_change_sets: Some(3),
+                storage_change_sets: Some(3),
             })
             .expect("get static file targets");
         assert_eq!(
@@ -372,13 +489,22 @@ mod tests {
             StaticFileTargets {
                 headers: Some(2..=3),
                 receipts: Some(2..=3),
-                transactions: Some(2..=3)
+                transactions: Some(2..=3),
+                account_change_sets: Some(2..=3),
+                storage_change_sets: Some(2..=3)
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
         assert_eq!(
             provider_factory.static_file_provider().get_highest_static_files(),
-            HighestStaticFiles { headers: Some(3), receipts: Some(3), transactions: Some(3) }
+            HighestStaticFiles {
+                headers: Some(3),
+                receipts: Some(3),
+                transactions: Some(3),
+                blob_sidecars: None,
+                account_change_sets: Some(3),
+                storage_change_sets: Some(3)
+            }
         );
 
         let targets = static_file_producer
@@ -386,6 +512,9 @@ mod tests {
                 headers: Some(4),
                 receipts: Some(4),
                 transactions: Some(4),
+                blob_sidecars: None,
+                account_change_sets: None,
+                storage_change_sets: None,
             })
             .expect("get static file targets");
         assert_eq!(
@@ -393,7 +522,9 @@ mod tests {
             StaticFileTargets {
                 headers: Some(4..=4),
                 receipts: Some(4..=4),
-                transactions: Some(4..=4)
+                transactions: Some(4..=4),
+                account_change_sets: None,
+                storage_change_sets: None
             }
         );
         assert_matches!(
@@ -402,10 +533,38 @@ mod tests {
         );
         assert_eq!(
             provider_factory.static_file_provider().get_highest_static_files(),
-            HighestStaticFiles { headers: Some(3), receipts: Some(3), transactions: Some(3) }
+            HighestStaticFiles {
+                headers: Some(3),
+                receipts: Some(3),
+                transactions: Some(3),
+                blob_sidecars: None,
+                account_change_sets: Some(3),
+                storage_change_sets: Some(3)
+            }
         );
     }
 
+    #[test]
+    fn change_sets_disabled_by_default() {
+        let (provider_factory, _temp_static_files_dir) = setup();
+
+        let static_file_producer =
+            StaticFileProducerInner::new(provider_factory, PruneModes::default());
+
+        let targets = static_file_producer
+            .get_static_file_targets(HighestStaticFiles {
+                headers: Some(1),
+                receipts: Some(1),
+                transactions: Some(1),
+                blob_sidecars: None,
+                account_change_sets: Some(1),
+                storage_change_sets: Some(1),
+            })
+            .expect("get static file targets");
+        assert_eq!(targets.account_change_sets, None);
+        assert_eq!(targets.storage_change_sets, None);
+    }
+
     /// Tests that a cloneable [`StaticFileProducer`] type is not susceptible to any race condition.
     #[test]
     fn only_one() {
@@ -430,6 +589,9 @@ mod tests {
                         headers: Some(1),
                         receipts: Some(1),
                         transactions: Some(1),
+                        blob_sidecars: None,
+                        account_change_sets: None,
+                        storage_change_sets: None,
                     })
                     .expect("get static file targets");
                 assert_matches!(locked_producer.run(targets.clone()), Ok(_));
@@ -30,6 +30,18 @@ pub struct HighestStaticFiles {
     /// Highest static file block of transactions, inclusive.
     /// If [`None`], no static file is available.
     pub transactions: Option<BlockNumber>,
+    /// Highest static file block of blob sidecars, inclusive.
+    /// If [`None`], no static file is available.
+    ///
+    /// Blob sidecars are not moved from the database, so they're not taken into account by
+    /// [`Self::min`] and [`Self::max`].
+    pub blob_sidecars: Option<BlockNumber>,
+    /// Highest static file block of account changesets, inclusive.
+    /// If [`None`], no static file is available.
+    pub account_change_sets: Option<BlockNumber>,
+    /// Highest static file block of storage changesets, inclusive.
+    /// If [`None`], no static file is available.
+    pub storage_change_sets: Option<BlockNumber>,
 }
 
 impl HighestStaticFiles {
@@ -39,6 +51,9 @@ impl HighestStaticFiles {
             StaticFileSegment::Headers => self.headers,
             StaticFileSegment::Transactions => self.transactions,
             StaticFileSegment::Receipts => self.receipts,
+            StaticFileSegment::BlobSidecars => self.blob_sidecars,
+            StaticFileSegment::AccountChangeSets => self.account_change_sets,
+            StaticFileSegment::StorageChangeSets => self.storage_change_sets,
         }
     }
 
@@ -48,17 +63,38 @@ impl HighestStaticFiles {
             StaticFileSegment::Headers => &mut self.headers,
             StaticFileSegment::Transactions => &mut self.transactions,
             StaticFileSegment::Receipts => &mut self.receipts,
+            StaticFileSegment::BlobSidecars => &mut self.blob_sidecars,
+            StaticFileSegment::AccountChangeSets => &mut self.account_change_sets,
+            StaticFileSegment::StorageChangeSets => &mut self.storage_change_sets,
         }
     }
 
-    /// Returns the minimum block of all segments.
+    /// Returns the minimum block of all data segments.
     pub fn min(&self) -> Option<u64> {
-        [self.headers, self.transactions, self.receipts].iter().filter_map(|&option| option).min()
+        [
+            self.headers,
+            self.transactions,
+            self.receipts,
+            self.account_change_sets,
+            self.storage_change_sets,
+        ]
+        .iter()
+        .filter_map(|&option| option)
+        .min()
     }
 
-    /// Returns the maximum block of all segments.
+    /// Returns the maximum block of all data segments.
     pub fn max(&self) -> Option<u64> {
-        [self.headers, self.transactions, self.receipts].iter().filter_map(|&option| option).max()
+        [
+            self.headers,
+            self.transactions,
+            self.receipts,
+            self.account_change_sets,
+            self.storage_change_sets,
+        ]
+        .iter()
+        .filter_map(|&option| option)
+        .max()
     }
 }
 
@@ -78,8 +114,14 @@ mod tests {
 
     #[test]
     fn test_highest_static_files_highest() {
-        let files =
-            HighestStaticFiles { headers: Some(100), receipts: Some(200), transactions: None };
+        let files = HighestStaticFiles {
+            headers: Some(100),
+            receipts: Some(200),
+            transactions: None,
+            blob_sidecars: None,
+            account_change_sets: None,
+            storage_change_sets: None,
+        };
 
         // Test for headers segment
         assert_eq!(files.highest(StaticFileSegment::Headers), Some(100));
@@ -110,8 +152,14 @@ mod tests {
 
     #[test]
     fn test_highest_static_files_min() {
-        let files =
-            HighestStaticFiles { headers: Some(300), receipts: Some(100), transactions: None };
+        let files = HighestStaticFiles {
+            headers: Some(300),
+            receipts: Some(100),
+            transactions: None,
+            blob_sidecars: None,
+            account_change_sets: None,
+            storage_change_sets: None,
+        };
 
         // Minimum value among the available segments
         assert_eq!(files.min(), Some(100));
@@ -123,8 +171,14 @@ mod tests {
 
     #[test]
     fn test_highest_static_files_max() {
-        let files =
-            HighestStaticFiles { headers: Some(300), receipts: Some(100), transactions: Some(500) };
+        let files = HighestStaticFiles {
+            headers: Some(300),
+            receipts: Some(100),
+            transactions: Some(500),
+            blob_sidecars: None,
+            account_change_sets: None,
+            storage_change_sets: None,
+        };
 
         // Maximum value among the available segments
         assert_eq!(files.max(), Some(500));
@@ -34,6 +34,16 @@ pub enum StaticFileSegment {
     #[strum(serialize = "receipts")]
     /// Static File segment responsible for the `Receipts` table.
     Receipts,
+    #[strum(serialize = "blob-sidecars")]
+    /// Static File segment responsible for the blob sidecars of canonical blocks. It's not backed
+    /// by a database table, and only exists if blob sidecar retention is enabled.
+    BlobSidecars,
+    #[strum(serialize = "account-change-sets")]
+    /// Static File segment responsible for the `AccountChangeSets` table.
+    AccountChangeSets,
+    #[strum(serialize = "storage-change-sets")]
+    /// Static File segment responsible for the `StorageChangeSets` table.
+    StorageChangeSets,
 }
 
 impl StaticFileSegment {
@@ -43,6 +53,9 @@ impl StaticFileSegment {
             Self::Headers => "headers",
             Self::Transactions => "transactions",
             Self::Receipts => "receipts",
+            Self::BlobSidecars => "blob-sidecars",
+            Self::AccountChangeSets => "account-change-sets",
+            Self::StorageChangeSets => "storage-change-sets",
         }
     }
 
@@ -55,7 +68,11 @@ impl StaticFileSegment {
     pub const fn columns(&self) -> usize {
         match self {
             Self::Headers => 3,
-            Self::Transactions | Self::Receipts => 1,
+            Self::BlobSidecars => 2,
+            Self::Transactions |
+            Self::Receipts |
+            Self::AccountChangeSets |
+            Self::StorageChangeSets => 1,
         }
     }
 
@@ -123,11 +140,31 @@ impl StaticFileSegment {
         matches!(self, Self::Receipts)
     }
 
+    /// Returns `true` if the segment is `StaticFileSegment::BlobSidecars`.
+    pub const fn is_blob_sidecars(&self) -> bool {
+        matches!(self, Self::BlobSidecars)
+    }
+
+    /// Returns `true` if the segment is `StaticFileSegment::AccountChangeSets` or
+    /// `StaticFileSegment::StorageChangeSets`.
+    pub const fn is_change_sets(&self) -> bool {
+        matches!(self, Self::AccountChangeSets | Self::StorageChangeSets)
+    }
+
     /// Returns `true` if the segment is `StaticFileSegment::Receipts` or
     /// `StaticFileSegment::Transactions`.
     pub const fn is_tx_based(&self) -> bool {
         matches!(self, Self::Receipts | Self::Transactions)
     }
+
+    /// Returns `true` if the segment is `StaticFileSegment::Headers`,
+    /// `StaticFileSegment::BlobSidecars` or one of the changeset segments.
+    pub const fn is_block_based(&self) -> bool {
+        matches!(
+            self,
+            Self::Headers | Self::BlobSidecars | Self::AccountChangeSets | Self::StorageChangeSets
+        )
+    }
 }
 
 /// A segment header that contains information common to all segments. Used for storage.
@@ -229,7 +266,10 @@ impl SegmentHeader {
     /// Increments tx end range depending on segment
     pub fn increment_tx(&mut self) {
         match self.segment {
-            StaticFileSegment::Headers => (),
+            StaticFileSegment::Headers |
+            StaticFileSegment::BlobSidecars |
+            StaticFileSegment::AccountChangeSets |
+            StaticFileSegment::StorageChangeSets => (),
             StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
                 if let Some(tx_range) = &mut self.tx_range {
                     tx_range.end += 1;
@@ -243,7 +283,10 @@ impl SegmentHeader {
     /// Removes `num` elements from end of tx or block range.
     pub fn prune(&mut self, num: u64) {
         match self.segment {
-            StaticFileSegment::Headers => {
+            StaticFileSegment::Headers |
+            StaticFileSegment::BlobSidecars |
+            StaticFileSegment::AccountChangeSets |
+            StaticFileSegment::StorageChangeSets => {
                 if let Some(range) = &mut self.block_range {
                     if num > range.end - range.start {
                         self.block_range = None;
@@ -287,7 +330,10 @@ impl SegmentHeader {
     /// Returns the row offset which depends on whether the segment is block or transaction based.
     pub fn start(&self) -> Option<u64> {
         match self.segment {
-            StaticFileSegment::Headers => self.block_start(),
+            StaticFileSegment::Headers |
+            StaticFileSegment::BlobSidecars |
+            StaticFileSegment::AccountChangeSets |
+            StaticFileSegment::StorageChangeSets => self.block_start(),
             StaticFileSegment::Transactions | StaticFileSegment::Receipts => self.tx_start(),
         }
     }
@@ -365,6 +411,24 @@ mod tests {
                 "static_file_transactions_1123233_11223233",
                 None,
             ),
+            (
+                StaticFileSegment::BlobSidecars,
+                19_500_000..=19_999_999,
+                "static_file_blob-sidecars_19500000_19999999",
+                None,
+            ),
+            (
+                StaticFileSegment::AccountChangeSets,
+                0..=499_999,
+                "static_file_account-change-sets_0_499999",
+                None,
+            ),
+            (
+                StaticFileSegment::StorageChangeSets,
+                0..=499_999,
+                "static_file_storage-change-sets_0_499999",
+                None,
+            ),
             (
                 StaticFileSegment::Headers,
                 2..=30,
@@ -24,7 +24,9 @@ pub mod storage_sharded_key;
 pub use accounts::*;
 pub use blocks::*;
 pub use reth_db_models::{
-    AccountBeforeTx, ClientVersion, StoredBlockBodyIndices, StoredBlockWithdrawals,
+    AccountBeforeTx, ClientVersion, StorageBeforeTx, StoredBlobSidecar,
+    StoredBlockAccountChangeSet, StoredBlockBlobSidecars, StoredBlockBlobVersionedHashes,
+    StoredBlockBodyIndices, StoredBlockStorageChangeSet, StoredBlockWithdrawals,
 };
 pub use sharded_key::ShardedKey;
 
@@ -223,6 +225,9 @@ impl_compression_for_compact!(
     StoredBlockBodyIndices,
     StoredBlockOmmers,
     StoredBlockWithdrawals,
+    StoredBlockBlobVersionedHashes,
+    StoredBlockAccountChangeSet,
+    StoredBlockStorageChangeSet,
     Bytecode,
     AccountBeforeTx,
     TransactionSignedNoHash,
@@ -234,6 +239,20 @@ impl_compression_for_compact!(
     GenesisAccount
 );
 
+impl Compress for StoredBlockBlobSidecars {
+    type Compressed = Vec<u8>;
+
+    fn compress_to_buf<B: bytes::BufMut + AsMut<[u8]>>(self, buf: &mut B) {
+        alloy_rlp::Encodable::encode(&self, buf)
+    }
+}
+
+impl Decompress for StoredBlockBlobSidecars {
+    fn decompress(mut value: &[u8]) -> Result<Self, DatabaseError> {
+        alloy_rlp::Decodable::decode(&mut value).map_err(|_| DatabaseError::Decode)
+    }
+}
+
 macro_rules! impl_compression_fixed_compact {
     ($($name:tt),+) => {
         $(
@@ -9,6 +9,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 pub mod init;
+pub mod snapshot;
 
 mod db_tool;
 pub use db_tool::*;
@@ -0,0 +1,509 @@
+//! Consistent snapshots of the database and static files of a node.
+
+use alloy_primitives::{BlockNumber, B256};
+use reth_db::{
+    create_db,
+    lockfile::{StorageLock, LOCKFILE_NAME},
+    mdbx::DatabaseArguments,
+    static_file::iter_static_files,
+    tables,
+    version::DB_VERSION,
+    DatabaseEnv, TableViewer, Tables,
+};
+use reth_db_api::{
+    database::Database,
+    models::ClientVersion,
+    table::{DupSort, Table, TableImporter},
+    transaction::DbTx,
+    DatabaseError,
+};
+use reth_db_models::snapshot::{
+    SnapshotFile, SnapshotManifest, SNAPSHOT_MANIFEST_FILE_NAME, SNAPSHOT_MANIFEST_VERSION,
+};
+use reth_fs_util::FsPathError;
+use reth_nippy_jar::NippyJar;
+use reth_primitives::{static_file::SegmentHeader, GotExpected};
+use reth_provider::{
+    providers::StaticFileProvider, BlockHashReader, DBProvider, DatabaseProviderFactory,
+    ProviderError, StaticFileProviderFactory,
+};
+use reth_stages_types::StageId;
+use sha2::{Digest, Sha256};
+use std::{
+    fs::File,
+    io::Read,
+    path::{Component, Path, PathBuf},
+};
+use tracing::{debug, info};
+
+/// Name of the database directory in a snapshot.
+pub const SNAPSHOT_DB_DIR: &str = "db";
+
+/// Name of the static files directory in a snapshot.
+pub const SNAPSHOT_STATIC_FILES_DIR: &str = "static_files";
+
+/// Name of the MDBX lock file, which is not part of a snapshot.
+const MDBX_LOCK_FILE_NAME: &str = "mdbx.lck";
+
+/// Step by which the snapshot database file grows. It's smaller than the default of the node, so
+/// that the snapshot doesn't contain gigabytes of preallocated space.
+const SNAPSHOT_DB_GROWTH_STEP: usize = 64 * 1024 * 1024;
+
+/// Size of the buffer used to read files when computing their checksums.
+const CHECKSUM_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Snapshot creation and restoration error type.
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotError {
+    /// The destination directory exists and is not empty.
+    #[error("destination directory {0:?} is not empty")]
+    DestinationNotEmpty(PathBuf),
+    /// The hash of the snapshot block couldn't be found.
+    #[error("hash of block {0} not found")]
+    MissingBlockHash(BlockNumber),
+    /// The database of the snapshot couldn't be created.
+    #[error("failed to create snapshot database: {0}")]
+    CreateDatabase(eyre::Report),
+    /// The manifest has an unsupported format version.
+    #[error("unsupported snapshot manifest version: {0}")]
+    UnsupportedManifestVersion(GotExpected<u64>),
+    /// The snapshot was taken from a database with a different version.
+    #[error("snapshot database version mismatch: {0}")]
+    DatabaseVersionMismatch(GotExpected<u64>),
+    /// A file listed in the manifest has a path outside of the snapshot directories.
+    #[error("invalid snapshot file path: {0}")]
+    InvalidFilePath(String),
+    /// A file listed in the manifest is missing.
+    #[error("snapshot file {0} is missing")]
+    MissingFile(String),
+    /// A file listed in the manifest has an unexpected size.
+    #[error("snapshot file {path} size mismatch: {size}")]
+    SizeMismatch {
+        /// Path of the file, relative to the snapshot directory.
+        path: String,
+        /// Actual and expected size.
+        size: GotExpected<u64>,
+    },
+    /// A file listed in the manifest has an unexpected checksum.
+    #[error("snapshot file {path} checksum mismatch: {checksum}")]
+    ChecksumMismatch {
+        /// Path of the file, relative to the snapshot directory.
+        path: String,
+        /// Actual and expected checksum.
+        checksum: GotExpected<B256>,
+    },
+    /// Provider error.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// Filesystem error.
+    #[error(transparent)]
+    FsPath(#[from] FsPathError),
+}
+
+impl From<DatabaseError> for SnapshotError {
+    fn from(error: DatabaseError) -> Self {
+        Self::Provider(ProviderError::Database(error))
+    }
+}
+
+/// Creates a consistent snapshot of the database and static files in the `dest` directory, which
+/// must either not exist or be empty.
+///
+/// The database is copied within a single read transaction, so it's safe to take the snapshot
+/// while the node is running. The transaction is closed before the static files are copied up to
+/// its highest block. They may contain data past it, which is pruned by the consistency check on
+/// the first start of a node from the snapshot.
+///
+/// The snapshot contains a manifest with the block number and hash of the snapshot, and the
+/// checksums of all of its files.
+pub fn create_snapshot<PF>(factory: &PF, dest: &Path) -> Result<SnapshotManifest, SnapshotError>
+where
+    PF: DatabaseProviderFactory + StaticFileProviderFactory,
+{
+    ensure_empty_dir(dest)?;
+
+    // The transaction pins the database state of the snapshot until the database is copied.
+    let provider = factory.database_provider_ro()?.disable_long_read_transaction_safety();
+    let tx = provider.tx_ref();
+    let static_file_provider = factory.static_file_provider();
+
+    let block_number = tx
+        .get::<tables::StageCheckpoints>(StageId::Finish.to_string())?
+        .unwrap_or_default()
+        .block_number;
+    let block_hash = match tx.get::<tables::CanonicalHeaders>(block_number)? {
+        Some(hash) => hash,
+        None => static_file_provider
+            .block_hash(block_number)?
+            .ok_or(SnapshotError::MissingBlockHash(block_number))?,
+    };
+    info!(target: "reth::snapshot", block_number, %block_hash, ?dest, "Creating snapshot");
+
+    copy_database(tx, &dest.join(SNAPSHOT_DB_DIR))?;
+    drop(provider);
+
+    // Static files are only appended to past the snapshot block, so they can be copied after the
+    // transaction is closed.
+    copy_static_files(&static_file_provider, &dest.join(SNAPSHOT_STATIC_FILES_DIR), block_number)?;
+
+    let manifest = SnapshotManifest {
+        version: SNAPSHOT_MANIFEST_VERSION,
+        db_version: DB_VERSION,
+        block_number,
+        block_hash,
+        files: snapshot_files(dest)?,
+    };
+    reth_fs_util::write_json_file(&dest.join(SNAPSHOT_MANIFEST_FILE_NAME), &manifest)?;
+
+    info!(target: "reth::snapshot", block_number, files = manifest.files.len(), "Created snapshot");
+
+    Ok(manifest)
+}
+
+/// Reads the manifest of the snapshot in `dir`, and verifies the sizes and checksums of all files
+/// listed in it.
+pub fn verify_snapshot(dir: &Path) -> Result<SnapshotManifest, SnapshotError> {
+    let manifest: SnapshotManifest =
+        reth_fs_util::read_json_file(&dir.join(SNAPSHOT_MANIFEST_FILE_NAME))?;
+
+    if manifest.version != SNAPSHOT_MANIFEST_VERSION {
+        return Err(SnapshotError::UnsupportedManifestVersion(GotExpected {
+            got: manifest.version,
+            expected: SNAPSHOT_MANIFEST_VERSION,
+        }))
+    }
+    if manifest.db_version != DB_VERSION {
+        return Err(SnapshotError::DatabaseVersionMismatch(GotExpected {
+            got: manifest.db_version,
+            expected: DB_VERSION,
+        }))
+    }
+
+    for file in &manifest.files {
+        debug!(target: "reth::snapshot", path = %file.path, "Verifying snapshot file");
+
+        validate_file_path(&file.path)?;
+        let path = dir.join(&file.path);
+        if !path.is_file() {
+            return Err(SnapshotError::MissingFile(file.path.clone()))
+        }
+
+        let size = reth_fs_util::metadata(&path)?.len();
+        if size != file.size {
+            return Err(SnapshotError::SizeMismatch {
+                path: file.path.clone(),
+                size: GotExpected { got: size, expected: file.size },
+            })
+        }
+
+        let checksum = file_checksum(&path)?;
+        if checksum != file.checksum {
+            return Err(SnapshotError::ChecksumMismatch {
+                path: file.path.clone(),
+                checksum: GotExpected { got: checksum, expected: file.checksum },
+            })
+        }
+    }
+
+    Ok(manifest)
+}
+
+/// Verifies the snapshot in `dir` and restores it to the given database and static files
+/// directories, which must either not exist or be empty.
+///
+/// If `force` is set, the existing contents of the directories are deleted instead, but only after
+/// the snapshot was verified. The storage locks of both directories are held while restoring, so
+/// a running node is never overwritten.
+pub fn restore_snapshot(
+    dir: &Path,
+    db_path: &Path,
+    static_files_path: &Path,
+    force: bool,
+) -> Result<SnapshotManifest, SnapshotError> {
+    let manifest = verify_snapshot(dir)?;
+    info!(target: "reth::snapshot", block_number = manifest.block_number, block_hash = %manifest.block_hash, "Verified snapshot");
+
+    let _db_lock = StorageLock::try_acquire(db_path).map_err(ProviderError::from)?;
+    let _static_files_lock =
+        StorageLock::try_acquire(static_files_path).map_err(ProviderError::from)?;
+    prepare_restore_dir(db_path, force)?;
+    prepare_restore_dir(static_files_path, force)?;
+
+    for file in &manifest.files {
+        let target = match file.path.split_once('/') {
+            Some((SNAPSHOT_DB_DIR, name)) => db_path.join(name),
+            Some((SNAPSHOT_STATIC_FILES_DIR, name)) => static_files_path.join(name),
+            _ => return Err(SnapshotError::InvalidFilePath(file.path.clone())),
+        };
+        reth_fs_util::copy(dir.join(&file.path), target)?;
+    }
+
+    info!(target: "reth::snapshot", block_number = manifest.block_number, "Restored snapshot");
+
+    Ok(manifest)
+}
+
+/// Copies the static files with data up to `block_number` into `dest`.
+///
+/// All jars are copied rather than linked, since older jars are still modified by unwinds and
+/// pruning.
+fn copy_static_files(
+    provider: &StaticFileProvider,
+    dest: &Path,
+    block_number: BlockNumber,
+) -> Result<(), SnapshotError> {
+    reth_fs_util::create_dir_all(dest)?;
+
+    let static_files = iter_static_files(provider.directory())
+        .map_err(|err| ProviderError::NippyJar(err.to_string()))?;
+
+    for (segment, ranges) in static_files {
+        for (block_range, _) in ranges.iter().filter(|(range, _)| range.start() <= block_number) {
+            let fixed_range = provider.find_fixed_range(block_range.start());
+            let jar = NippyJar::<SegmentHeader>::load(
+                &provider.directory().join(segment.filename(&fixed_range)),
+            )
+            .map_err(|err| ProviderError::NippyJar(err.to_string()))?;
+            debug!(target: "reth::snapshot", %segment, ?block_range, "Copying static file");
+
+            // The configuration is copied first, so that a jar which is appended to in the
+            // meantime is never shorter than its configuration, and gets healed on startup.
+            let paths =
+                [jar.config_path(), jar.index_path(), jar.offsets_path(), jar.data_path().into()];
+            for path in paths.into_iter().filter(|path| path.exists()) {
+                let target = dest.join(path.file_name().expect("jar file has a name"));
+                reth_fs_util::copy(&path, target)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Copies all tables visible to the transaction into a new database at `path`.
+fn copy_database<TX: DbTx>(tx: &TX, path: &Path) -> Result<(), SnapshotError> {
+    let db = create_db(
+        path,
+        DatabaseArguments::new(ClientVersion::default())
+            .with_growth_step(Some(SNAPSHOT_DB_GROWTH_STEP)),
+    )
+    .map_err(SnapshotError::CreateDatabase)?;
+    db.create_tables()?;
+
+    for table in Tables::ALL {
+        debug!(target: "reth::snapshot", %table, "Copying table");
+        table.view(&CopyTableViewer { source: tx, destination: &db })?;
+    }
+
+    Ok(())
+}
+
+/// Copies the contents of a table from the source transaction into the destination database.
+struct CopyTableViewer<'a, TX> {
+    source: &'a TX,
+    destination: &'a DatabaseEnv,
+}
+
+impl<TX: DbTx> TableViewer<()> for CopyTableViewer<'_, TX> {
+    type Error = DatabaseError;
+
+    fn view<T: Table>(&self) -> Result<(), Self::Error> {
+        self.destination.update(|tx| tx.import_table::<T, _>(self.source))?
+    }
+
+    fn view_dupsort<T: DupSort>(&self) -> Result<(), Self::Error> {
+        self.destination.update(|tx| tx.import_dupsort::<T, _>(self.source))?
+    }
+}
+
+/// Returns all files of the snapshot in `dir`, sorted by path.
+fn snapshot_files(dir: &Path) -> Result<Vec<SnapshotFile>, SnapshotError> {
+    let mut files = Vec::new();
+
+    for subdir in [SNAPSHOT_DB_DIR, SNAPSHOT_STATIC_FILES_DIR] {
+        let subdir_path = dir.join(subdir);
+        for entry in reth_fs_util::read_dir(&subdir_path)? {
+            let entry = entry.map_err(|err| FsPathError::read_dir(err, &subdir_path))?;
+            let path = entry.path();
+            if !path.is_file() || entry.file_name() == MDBX_LOCK_FILE_NAME {
+                continue
+            }
+
+            files.push(SnapshotFile {
+                path: format!("{subdir}/{}", entry.file_name().to_string_lossy()),
+                size: reth_fs_util::metadata(&path)?.len(),
+                checksum: file_checksum(&path)?,
+            });
+        }
+    }
+    files.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(files)
+}
+
+/// Returns the SHA-256 checksum of the file contents.
+fn file_checksum(path: &Path) -> Result<B256, FsPathError> {
+    let mut file = File::open(path).map_err(|err| FsPathError::open(err, path))?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0; CHECKSUM_BUFFER_SIZE];
+    loop {
+        let read = file.read(&mut buf).map_err(|err| FsPathError::read(err, path))?;
+        if read == 0 {
+            break
+        }
+        hasher.update(&buf[..read]);
+    }
+    Ok(B256::new(hasher.finalize().into()))
+}
+
+/// Ensures that the manifest file path points into one of the snapshot directories.
+fn validate_file_path(path: &str) -> Result<(), SnapshotError> {
+    let mut components = Path::new(path).components();
+    let valid = matches!(
+        components.next(),
+        Some(Component::Normal(dir)) if dir == SNAPSHOT_DB_DIR || dir == SNAPSHOT_STATIC_FILES_DIR
+    ) && matches!(components.next(), Some(Component::Normal(_))) &&
+        components.next().is_none();
+
+    if valid {
+        Ok(())
+    } else {
+        Err(SnapshotError::InvalidFilePath(path.to_string()))
+    }
+}
+
+/// Ensures that the directory contains nothing but its storage lock file. If `force` is set, all
+/// other entries are deleted instead.
+fn prepare_restore_dir(path: &Path, force: bool) -> Result<(), SnapshotError> {
+    for entry in reth_fs_util::read_dir(path)? {
+        let entry = entry.map_err(|err| FsPathError::read_dir(err, path))?;
+        if entry.file_name() == LOCKFILE_NAME {
+            continue
+        }
+        if !force {
+            return Err(SnapshotError::DestinationNotEmpty(path.to_path_buf()))
+        }
+
+        let entry_path = entry.path();
+        if entry_path.is_dir() {
+            reth_fs_util::remove_dir_all(&entry_path)?;
+        } else {
+            reth_fs_util::remove_file(&entry_path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Creates the directory if it doesn't exist, and ensures that it's empty otherwise.
+fn ensure_empty_dir(path: &Path) -> Result<(), SnapshotError> {
+    if path.exists() && reth_fs_util::read_dir(path)?.next().is_some() {
+        return Err(SnapshotError::DestinationNotEmpty(path.to_path_buf()))
+    }
+    reth_fs_util::create_dir_all(path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::init::init_genesis;
+    use alloy_consensus::constants::MAINNET_GENESIS_HASH;
+    use assert_matches::assert_matches;
+    use reth_chainspec::MAINNET;
+    use reth_provider::test_utils::create_test_provider_factory_with_chain_spec;
+
+    #[test]
+    fn snapshot_roundtrip() {
+        let factory = create_test_provider_factory_with_chain_spec(MAINNET.clone());
+        init_genesis(&factory).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let snapshot_dir = dir.path().join("snapshot");
+        let manifest = create_snapshot(&factory, &snapshot_dir).unwrap();
+        assert_eq!(manifest.block_number, 0);
+        assert_eq!(manifest.block_hash, MAINNET_GENESIS_HASH);
+        assert!(manifest.files.iter().any(|file| file.path == "db/mdbx.dat"));
+        assert!(manifest.files.iter().any(|file| file.path.starts_with("static_files/")));
+
+        // the destination must be empty
+        assert_matches!(
+            create_snapshot(&factory, &snapshot_dir),
+            Err(SnapshotError::DestinationNotEmpty(_))
+        );
+
+        assert_eq!(verify_snapshot(&snapshot_dir).unwrap(), manifest);
+
+        let (db_path, static_files_path) = (dir.path().join("db"), dir.path().join("static_files"));
+        assert_eq!(
+            restore_snapshot(&snapshot_dir, &db_path, &static_files_path, false).unwrap(),
+            manifest
+        );
+        for file in &manifest.files {
+            let (_, name) = file.path.split_once('/').unwrap();
+            assert!(db_path.join(name).is_file() || static_files_path.join(name).is_file());
+        }
+
+        // restoring into a populated datadir fails, unless forced
+        assert_matches!(
+            restore_snapshot(&snapshot_dir, &db_path, &static_files_path, false),
+            Err(SnapshotError::DestinationNotEmpty(_))
+        );
+        assert_eq!(
+            restore_snapshot(&snapshot_dir, &db_path, &static_files_path, true).unwrap(),
+            manifest
+        );
+    }
+
+    #[test]
+    fn forced_restore_keeps_datadir_of_invalid_snapshot() {
+        let factory = create_test_provider_factory_with_chain_spec(MAINNET.clone());
+        init_genesis(&factory).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let snapshot_dir = dir.path().join("snapshot");
+        let manifest = create_snapshot(&factory, &snapshot_dir).unwrap();
+
+        let (db_path, static_files_path) = (dir.path().join("db"), dir.path().join("static_files"));
+        reth_fs_util::create_dir_all(&db_path).unwrap();
+        std::fs::write(db_path.join("mdbx.dat"), b"existing").unwrap();
+
+        let file = manifest.files.first().unwrap();
+        std::fs::remove_file(snapshot_dir.join(&file.path)).unwrap();
+
+        assert_matches!(
+            restore_snapshot(&snapshot_dir, &db_path, &static_files_path, true),
+            Err(SnapshotError::MissingFile(path)) if path == file.path
+        );
+        assert_eq!(std::fs::read(db_path.join("mdbx.dat")).unwrap(), b"existing");
+    }
+
+    #[test]
+    fn verify_snapshot_detects_corruption() {
+        let factory = create_test_provider_factory_with_chain_spec(MAINNET.clone());
+        init_genesis(&factory).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = create_snapshot(&factory, dir.path()).unwrap();
+
+        let file =
+            manifest.files.iter().find(|file| file.path.starts_with("static_files/")).unwrap();
+        let mut contents = std::fs::read(dir.path().join(&file.path)).unwrap();
+        contents[0] ^= 0xff;
+        std::fs::write(dir.path().join(&file.path), contents).unwrap();
+
+        assert_matches!(
+            verify_snapshot(dir.path()),
+            Err(SnapshotError::ChecksumMismatch { path, .. }) if path == file.path
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_file_paths() {
+        assert!(validate_file_path("db/mdbx.dat").is_ok());
+        assert!(validate_file_path("static_files/static_file_headers_0_499999").is_ok());
+        assert!(validate_file_path("../mdbx.dat").is_err());
+        assert!(validate_file_path("db/../../mdbx.dat").is_err());
+        assert!(validate_file_path("/etc/passwd").is_err());
+        assert!(validate_file_path("other/mdbx.dat").is_err());
+    }
+}
@@ -1,5 +1,5 @@
 use reth_codecs::{add_arbitrary_tests, Compact};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use alloy_primitives::{bytes::Buf, Address};
 use reth_primitives::Account;
@@ -7,7 +7,7 @@ use reth_primitives::Account;
 /// Account as it is saved in the database.
 ///
 /// [`Address`] is the subkey.
-#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize)]
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
 #[add_arbitrary_tests(compact)]
 pub struct AccountBeforeTx {
@@ -0,0 +1,114 @@
+//! Blob sidecar models.
+
+use alloy_primitives::B256;
+use alloy_rlp::{RlpDecodable, RlpEncodable};
+use reth_codecs::{add_arbitrary_tests, Compact};
+use reth_primitives::BlobTransactionSidecar;
+use serde::{Deserialize, Serialize};
+
+/// The blob sidecar of a transaction included in a block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, RlpEncodable, RlpDecodable)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredBlobSidecar {
+    /// The index of the transaction in the block.
+    pub tx_index: u64,
+    /// The blob sidecar of the transaction.
+    pub sidecar: BlobTransactionSidecar,
+}
+
+/// The storage representation of the blob sidecars of a block.
+///
+/// Only contains the sidecars which were available to the node when the block was retained, the
+/// blob transactions whose sidecars were missing are listed in `missing`.
+///
+/// Stored RLP encoded, since blobs are incompressible.
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, RlpEncodable, RlpDecodable,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredBlockBlobSidecars {
+    /// The blob sidecars, ordered by transaction index.
+    pub sidecars: Vec<StoredBlobSidecar>,
+    /// The indices of the blob transactions whose sidecars were not available, in ascending
+    /// order.
+    pub missing: Vec<u64>,
+}
+
+impl StoredBlockBlobSidecars {
+    /// Returns `true` if there are no sidecars.
+    pub fn is_empty(&self) -> bool {
+        self.sidecars.is_empty()
+    }
+
+    /// Returns `true` if the sidecars of all blob transactions of the block were retained.
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    /// Returns the versioned hashes of all blobs, ordered by transaction index.
+    pub fn versioned_hashes(&self) -> impl Iterator<Item = B256> + '_ {
+        self.sidecars.iter().flat_map(|sidecar| sidecar.sidecar.versioned_hashes())
+    }
+
+    /// Returns the sidecar containing the blob with the given versioned hash.
+    pub fn find_by_versioned_hash(&self, versioned_hash: &B256) -> Option<&StoredBlobSidecar> {
+        self.sidecars
+            .iter()
+            .find(|sidecar| sidecar.sidecar.versioned_hashes().any(|hash| hash == *versioned_hash))
+    }
+}
+
+/// The storage representation of the versioned hashes of all blobs in a block.
+///
+/// Allows looking up blobs by versioned hash without decoding the sidecars.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Compact)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct StoredBlockBlobVersionedHashes {
+    /// The versioned hashes, ordered by transaction index.
+    pub hashes: Vec<B256>,
+}
+
+impl From<&StoredBlockBlobSidecars> for StoredBlockBlobVersionedHashes {
+    fn from(sidecars: &StoredBlockBlobSidecars) -> Self {
+        Self { hashes: sidecars.versioned_hashes().collect() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::FixedBytes;
+    use alloy_rlp::{Decodable, Encodable};
+
+    #[test]
+    fn block_blob_sidecars_roundtrip() {
+        let sidecar = BlobTransactionSidecar {
+            blobs: vec![FixedBytes::repeat_byte(1), FixedBytes::repeat_byte(2)],
+            commitments: vec![FixedBytes::repeat_byte(3), FixedBytes::repeat_byte(4)],
+            proofs: vec![FixedBytes::repeat_byte(5), FixedBytes::repeat_byte(6)],
+        };
+        let sidecars = StoredBlockBlobSidecars {
+            sidecars: vec![StoredBlobSidecar { tx_index: 3, sidecar: sidecar.clone() }],
+            missing: vec![5],
+        };
+        assert!(!sidecars.is_complete());
+
+        let mut buf = Vec::new();
+        sidecars.encode(&mut buf);
+        assert_eq!(StoredBlockBlobSidecars::decode(&mut buf.as_slice()).unwrap(), sidecars);
+        assert!(StoredBlockBlobSidecars::decode(&mut &buf[..buf.len() - 1]).is_err());
+
+        let versioned_hash = sidecar.versioned_hash_for_blob(1).unwrap();
+        assert_eq!(sidecars.find_by_versioned_hash(&versioned_hash).unwrap().tx_index, 3);
+        assert_eq!(
+            StoredBlockBlobVersionedHashes::from(&sidecars).hashes,
+            sidecar.versioned_hashes().collect::<Vec<_>>()
+        );
+
+        let mut buf = Vec::new();
+        StoredBlockBlobSidecars::default().encode(&mut buf);
+        let empty = StoredBlockBlobSidecars::decode(&mut buf.as_slice()).unwrap();
+        assert!(empty.is_empty() && empty.is_complete());
+    }
+}
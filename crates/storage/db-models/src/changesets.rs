@@ -0,0 +1,136 @@
+//! Changeset models.
+
+use crate::AccountBeforeTx;
+use alloy_primitives::{Address, B256, U256};
+use reth_codecs::{add_arbitrary_tests, Compact};
+use reth_primitives::StorageEntry;
+use serde::{Deserialize, Serialize};
+
+/// Storage slot as it was before a block changed it.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Compact)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct StorageBeforeTx {
+    /// Address of the account owning the storage slot.
+    pub address: Address,
+    /// Storage key.
+    pub key: B256,
+    /// Storage value before the block.
+    pub value: U256,
+}
+
+impl StorageBeforeTx {
+    /// Creates a new [`StorageBeforeTx`] from the address and the storage entry of a changeset.
+    pub const fn new(address: Address, entry: StorageEntry) -> Self {
+        Self { address, key: entry.key, value: entry.value }
+    }
+
+    /// Returns the storage entry of the changeset.
+    pub const fn entry(&self) -> StorageEntry {
+        StorageEntry { key: self.key, value: self.value }
+    }
+}
+
+/// The storage representation of the account changeset of a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Compact)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct StoredBlockAccountChangeSet {
+    /// The account changes, ordered by address.
+    pub changes: Vec<AccountBeforeTx>,
+}
+
+impl StoredBlockAccountChangeSet {
+    /// Returns `true` if the block didn't change any account.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the account state before the block for the given address, if the block changed it.
+    pub fn get(&self, address: Address) -> Option<&AccountBeforeTx> {
+        self.changes
+            .binary_search_by(|change| change.address.cmp(&address))
+            .ok()
+            .map(|index| &self.changes[index])
+    }
+}
+
+/// The storage representation of the storage changeset of a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Compact)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(compact)]
+pub struct StoredBlockStorageChangeSet {
+    /// The storage changes, ordered by address and storage key.
+    pub changes: Vec<StorageBeforeTx>,
+}
+
+impl StoredBlockStorageChangeSet {
+    /// Returns `true` if the block didn't change any storage slot.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the storage slot before the block for the given address and key, if the block
+    /// changed it.
+    pub fn get(&self, address: Address, key: B256) -> Option<&StorageBeforeTx> {
+        self.changes
+            .binary_search_by(|change| (change.address, change.key).cmp(&(address, key)))
+            .ok()
+            .map(|index| &self.changes[index])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Account;
+
+    #[test]
+    fn block_changesets_lookup() {
+        let accounts = StoredBlockAccountChangeSet {
+            changes: vec![
+                AccountBeforeTx { address: Address::with_last_byte(1), info: None },
+                AccountBeforeTx {
+                    address: Address::with_last_byte(2),
+                    info: Some(Account { nonce: 1, ..Default::default() }),
+                },
+            ],
+        };
+
+        let mut buf = Vec::new();
+        let len = accounts.to_compact(&mut buf);
+        let decoded = StoredBlockAccountChangeSet::from_compact(&buf, len).0;
+        assert_eq!(decoded, accounts);
+        assert_eq!(decoded.get(Address::with_last_byte(2)), Some(&accounts.changes[1]));
+        assert_eq!(decoded.get(Address::with_last_byte(3)), None);
+
+        let storages = StoredBlockStorageChangeSet {
+            changes: vec![
+                StorageBeforeTx {
+                    address: Address::with_last_byte(1),
+                    key: B256::with_last_byte(2),
+                    value: U256::from(3),
+                },
+                StorageBeforeTx {
+                    address: Address::with_last_byte(2),
+                    key: B256::with_last_byte(1),
+                    value: U256::ZERO,
+                },
+            ],
+        };
+
+        let mut buf = Vec::new();
+        let len = storages.to_compact(&mut buf);
+        let decoded = StoredBlockStorageChangeSet::from_compact(&buf, len).0;
+        assert_eq!(decoded, storages);
+        assert_eq!(
+            decoded.get(Address::with_last_byte(2), B256::with_last_byte(1)),
+            Some(&storages.changes[1])
+        );
+        assert_eq!(decoded.get(Address::with_last_byte(1), B256::with_last_byte(1)), None);
+
+        let mut buf = Vec::new();
+        let len = StoredBlockAccountChangeSet::default().to_compact(&mut buf);
+        assert!(StoredBlockAccountChangeSet::from_compact(&buf, len).0.is_empty());
+    }
+}
@@ -4,10 +4,22 @@
 pub mod accounts;
 pub use accounts::AccountBeforeTx;
 
+/// Blob sidecars
+pub mod blobs;
+pub use blobs::{StoredBlobSidecar, StoredBlockBlobSidecars, StoredBlockBlobVersionedHashes};
+
 /// Blocks
 pub mod blocks;
 pub use blocks::{StoredBlockBodyIndices, StoredBlockWithdrawals};
 
+/// Changesets
+pub mod changesets;
+pub use changesets::{StorageBeforeTx, StoredBlockAccountChangeSet, StoredBlockStorageChangeSet};
+
 /// Client Version
 pub mod client_version;
 pub use client_version::ClientVersion;
+
+/// Snapshots
+pub mod snapshot;
+pub use snapshot::{SnapshotFile, SnapshotManifest};
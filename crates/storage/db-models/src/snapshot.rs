@@ -0,0 +1,38 @@
+//! Database snapshot models.
+
+use alloy_primitives::{BlockNumber, B256};
+use serde::{Deserialize, Serialize};
+
+/// Name of the manifest file in the root of a snapshot directory.
+pub const SNAPSHOT_MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Version of the [`SnapshotManifest`] format.
+pub const SNAPSHOT_MANIFEST_VERSION: u64 = 1;
+
+/// Describes a consistent snapshot of the database and the static files of a node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotManifest {
+    /// Version of the manifest format.
+    pub version: u64,
+    /// Version of the database the snapshot was taken from.
+    pub db_version: u64,
+    /// Number of the highest block fully synced in the snapshot.
+    pub block_number: BlockNumber,
+    /// Hash of the highest block fully synced in the snapshot.
+    pub block_hash: B256,
+    /// All files of the snapshot, sorted by path.
+    pub files: Vec<SnapshotFile>,
+}
+
+/// A single file of a [`SnapshotManifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotFile {
+    /// Path of the file, relative to the snapshot directory.
+    pub path: String,
+    /// Size of the file in bytes.
+    pub size: u64,
+    /// SHA-256 checksum of the file contents.
+    pub checksum: B256,
+}
@@ -89,6 +89,8 @@ pub struct DatabaseArguments {
     ///
     /// This flag affects only at environment opening but can't be changed after.
     exclusive: Option<bool>,
+    /// Step in bytes by which the database file grows. If [None], the default value is used.
+    growth_step: Option<usize>,
 }
 
 impl DatabaseArguments {
@@ -99,6 +101,7 @@ impl DatabaseArguments {
             log_level: None,
             max_read_transaction_duration: None,
             exclusive: None,
+            growth_step: None,
         }
     }
 
@@ -123,6 +126,12 @@ impl DatabaseArguments {
         self
     }
 
+    /// Set the step in bytes by which the database file grows.
+    pub const fn with_growth_step(mut self, growth_step: Option<usize>) -> Self {
+        self.growth_step = growth_step;
+        self
+    }
+
     /// Returns the client version if any.
     pub const fn client_version(&self) -> &ClientVersion {
         &self.client_version
@@ -281,8 +290,8 @@ impl DatabaseEnv {
         inner_env.set_geometry(Geometry {
             // Maximum database size of 4 terabytes
             size: Some(0..(4 * TERABYTE)),
-            // We grow the database in increments of 4 gigabytes
-            growth_step: Some(4 * GIGABYTE as isize),
+            // We grow the database in increments of 4 gigabytes by default
+            growth_step: Some(args.growth_step.unwrap_or(4 * GIGABYTE) as isize),
             // The database never shrinks
             shrink_threshold: Some(0),
             page_size: Some(PageSize::Set(default_page_size())),
@@ -1,2 +1,7 @@
 #[cfg(feature = "mdbx")]
 pub(crate) mod mdbx;
+#[cfg(feature = "redb")]
+pub(crate) mod redb;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,508 @@
+//! Cursor wrapper for redb.
+
+use super::{
+    dup_key, dup_key_prefix, dup_prefix, prefix_successor, redb_error, split_dup_key,
+    tx::{remove_prefix, RawEntry, RawRange, SharedTx},
+    Error, TransactionKind, RO, RW,
+};
+use crate::{tables::utils::decoder, DatabaseError};
+use reth_db_api::{
+    common::{PairResult, ValueOnlyResult},
+    cursor::{
+        DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW, DupWalker, RangeWalker,
+        ReverseWalker, Walker,
+    },
+    table::{Compress, DupSort, Encode, Table, TableRow},
+};
+use reth_storage_errors::db::{DatabaseErrorInfo, DatabaseWriteError, DatabaseWriteOperation};
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+    sync::Arc,
+};
+
+/// Read only Cursor.
+pub type CursorRO<T> = Cursor<RO, T>;
+/// Read write cursor.
+pub type CursorRW<T> = Cursor<RW, T>;
+
+/// Position of a [`Cursor`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Position {
+    /// Cursor was not positioned yet.
+    Unset,
+    /// Cursor is at the entry with the given raw key.
+    At(Vec<u8>),
+    /// Entry with the given raw key was deleted, the cursor is at the entry following it.
+    Deleted(Vec<u8>),
+    /// A seek found no entry, the cursor is past the end of the table.
+    Eof,
+}
+
+/// Cursor wrapper to access KV items.
+///
+/// redb tables can't outlive a single operation of the transaction (see [`SharedTx`]), so the
+/// cursor remembers the raw key it is positioned at and every movement is a range query relative
+/// to it.
+#[derive(Debug)]
+pub struct Cursor<K: TransactionKind, T: Table> {
+    /// Transaction the cursor was opened in.
+    tx: Arc<SharedTx>,
+    /// Current position of the cursor.
+    position: Position,
+    /// Phantom data to enforce encoding/decoding.
+    _dbi: PhantomData<(K, T)>,
+}
+
+impl<K: TransactionKind, T: Table> Cursor<K, T> {
+    pub(crate) fn new(tx: Arc<SharedTx>) -> Self {
+        Self { tx, position: Position::Unset, _dbi: PhantomData }
+    }
+
+    /// Returns the first entry in the given range.
+    fn first_in(&self, range: RawRange<'_>) -> Result<Option<RawEntry>, DatabaseError> {
+        self.tx.read(T::NAME, |table| table.first_raw(range))
+    }
+
+    /// Returns the last entry in the given range.
+    fn last_in(&self, range: RawRange<'_>) -> Result<Option<RawEntry>, DatabaseError> {
+        self.tx.read(T::NAME, |table| table.last_raw(range))
+    }
+
+    /// Returns the entry the cursor is at.
+    fn current_entry(&self) -> Result<Option<RawEntry>, DatabaseError> {
+        match &self.position {
+            Position::At(raw) => self.first_in((Bound::Included(raw.as_slice()), Bound::Unbounded)),
+            Position::Deleted(raw) => {
+                self.first_in((Bound::Excluded(raw.as_slice()), Bound::Unbounded))
+            }
+            Position::Unset | Position::Eof => Ok(None),
+        }
+    }
+
+    /// Returns the raw key to seek to for the given encoded key.
+    fn seek_key(&self, key: &[u8]) -> Result<Vec<u8>, DatabaseError> {
+        if T::DUPSORT {
+            dup_prefix(key).map_err(|e| DatabaseError::Read(e.into()))
+        } else {
+            Ok(key.to_vec())
+        }
+    }
+
+    /// Returns the first entry with a raw key greater than or equal to the given encoded key.
+    fn seek_raw(&mut self, key: &[u8]) -> PairResult<T> {
+        let key = self.seek_key(key)?;
+        let entry = self.first_in((Bound::Included(key.as_slice()), Bound::Unbounded))?;
+        self.seek_to(entry)
+    }
+
+    /// Moves the cursor to the given entry. If there is none, the cursor is moved past the end of
+    /// the table.
+    fn seek_to(&mut self, entry: Option<RawEntry>) -> PairResult<T> {
+        let Some(entry) = entry else {
+            self.position = Position::Eof;
+            return Ok(None)
+        };
+        self.position = Position::At(entry.0.clone());
+        self.decode(entry).map(Some)
+    }
+
+    /// Moves the cursor to the given entry. If there is none, the cursor stays where it is.
+    fn step_to(&mut self, entry: Option<RawEntry>) -> PairResult<T> {
+        let Some(entry) = entry else { return Ok(None) };
+        self.position = Position::At(entry.0.clone());
+        self.decode(entry).map(Some)
+    }
+
+    /// Decodes a raw entry of the table.
+    fn decode(&self, (key, value): RawEntry) -> Result<TableRow<T>, DatabaseError> {
+        if T::DUPSORT {
+            let (key, value) = split_dup_key(&key);
+            decoder::<T>((Cow::Borrowed(key), Cow::Borrowed(value)))
+        } else {
+            decoder::<T>((Cow::Owned(key), Cow::Owned(value)))
+        }
+    }
+}
+
+impl<K: TransactionKind, T: Table> DbCursorRO<T> for Cursor<K, T> {
+    fn first(&mut self) -> PairResult<T> {
+        let entry = self.first_in((Bound::Unbounded, Bound::Unbounded))?;
+        self.seek_to(entry)
+    }
+
+    fn seek_exact(&mut self, key: <T as Table>::Key) -> PairResult<T> {
+        let key = self.seek_key(key.encode().as_ref())?;
+        let entry = self.first_in((Bound::Included(key.as_slice()), Bound::Unbounded))?;
+        let exact = entry.as_ref().is_some_and(|(raw, _)| {
+            if T::DUPSORT {
+                raw.starts_with(&key)
+            } else {
+                *raw == key
+            }
+        });
+
+        // Like MDBX, the cursor is left at the next entry if the key doesn't exist.
+        let row = self.seek_to(entry)?;
+        Ok(row.filter(|_| exact))
+    }
+
+    fn seek(&mut self, key: <T as Table>::Key) -> PairResult<T> {
+        self.seek_raw(key.encode().as_ref())
+    }
+
+    fn next(&mut self) -> PairResult<T> {
+        let entry = match &self.position {
+            Position::Unset => return self.first(),
+            Position::At(raw) | Position::Deleted(raw) => {
+                self.first_in((Bound::Excluded(raw.as_slice()), Bound::Unbounded))?
+            }
+            Position::Eof => return Ok(None),
+        };
+        self.step_to(entry)
+    }
+
+    fn prev(&mut self) -> PairResult<T> {
+        let entry = match &self.position {
+            Position::Unset | Position::Eof => return self.last(),
+            Position::At(raw) | Position::Deleted(raw) => {
+                self.last_in((Bound::Unbounded, Bound::Excluded(raw.as_slice())))?
+            }
+        };
+        self.step_to(entry)
+    }
+
+    fn last(&mut self) -> PairResult<T> {
+        let entry = self.last_in((Bound::Unbounded, Bound::Unbounded))?;
+        self.seek_to(entry)
+    }
+
+    fn current(&mut self) -> PairResult<T> {
+        self.current_entry()?.map(|entry| self.decode(entry)).transpose()
+    }
+
+    fn walk(&mut self, start_key: Option<T::Key>) -> Result<Walker<'_, T, Self>, DatabaseError> {
+        let start = if let Some(start_key) = start_key {
+            self.seek_raw(start_key.encode().as_ref()).transpose()
+        } else {
+            self.first().transpose()
+        };
+
+        Ok(Walker::new(self, start))
+    }
+
+    fn walk_range(
+        &mut self,
+        range: impl RangeBounds<T::Key>,
+    ) -> Result<RangeWalker<'_, T, Self>, DatabaseError> {
+        let start = match range.start_bound().cloned() {
+            Bound::Included(key) => self.seek_raw(key.encode().as_ref()),
+            Bound::Excluded(_key) => {
+                unreachable!("Rust doesn't allow for Bound::Excluded in starting bounds");
+            }
+            Bound::Unbounded => self.first(),
+        };
+        Ok(RangeWalker::new(self, start.transpose(), range.end_bound().cloned()))
+    }
+
+    fn walk_back(
+        &mut self,
+        start_key: Option<T::Key>,
+    ) -> Result<ReverseWalker<'_, T, Self>, DatabaseError> {
+        let start = if let Some(start_key) = start_key {
+            self.seek_raw(start_key.encode().as_ref())
+        } else {
+            self.last()
+        }
+        .transpose();
+
+        Ok(ReverseWalker::new(self, start))
+    }
+}
+
+impl<K: TransactionKind, T: DupSort> DbDupCursorRO<T> for Cursor<K, T> {
+    /// Returns the next `(key, value)` pair of a DUPSORT table.
+    fn next_dup(&mut self) -> PairResult<T> {
+        let entry = match &self.position {
+            Position::Unset => return self.first(),
+            Position::At(raw) | Position::Deleted(raw) if T::DUPSORT => {
+                let end = prefix_successor(dup_key_prefix(raw));
+                self.first_in((
+                    Bound::Excluded(raw.as_slice()),
+                    end.as_deref().map_or(Bound::Unbounded, Bound::Excluded),
+                ))?
+            }
+            _ => return Ok(None),
+        };
+        self.step_to(entry)
+    }
+
+    /// Returns the next `(key, value)` pair skipping the duplicates.
+    fn next_no_dup(&mut self) -> PairResult<T> {
+        let entry = match &self.position {
+            Position::Unset => return self.first(),
+            Position::At(raw) | Position::Deleted(raw) if T::DUPSORT => {
+                match prefix_successor(dup_key_prefix(raw)) {
+                    Some(start) => {
+                        self.first_in((Bound::Included(start.as_slice()), Bound::Unbounded))?
+                    }
+                    None => None,
+                }
+            }
+            Position::At(_) | Position::Deleted(_) => return self.next(),
+            Position::Eof => return Ok(None),
+        };
+        self.step_to(entry)
+    }
+
+    /// Returns the next `value` of a duplicate `key`.
+    fn next_dup_val(&mut self) -> ValueOnlyResult<T> {
+        Ok(self.next_dup()?.map(|(_, value)| value))
+    }
+
+    fn seek_by_key_subkey(
+        &mut self,
+        key: <T as Table>::Key,
+        subkey: <T as DupSort>::SubKey,
+    ) -> ValueOnlyResult<T> {
+        let prefix = self.seek_key(key.encode().as_ref())?;
+        let end = prefix_successor(&prefix);
+
+        let mut start = prefix.clone();
+        start.extend_from_slice(subkey.encode().as_ref());
+
+        let entry = self.first_in((
+            Bound::Included(start.as_slice()),
+            end.as_deref().map_or(Bound::Unbounded, Bound::Excluded),
+        ))?;
+        Ok(self.seek_to(entry)?.map(|(_, value)| value))
+    }
+
+    /// Depending on its arguments, returns an iterator starting at:
+    /// - Some(key), Some(subkey): a `key` item whose data is >= than `subkey`
+    /// - Some(key), None: first item of a specified `key`
+    /// - None, Some(subkey): like first case, but in the first key
+    /// - None, None: first item in the table of a DUPSORT table.
+    fn walk_dup(
+        &mut self,
+        key: Option<T::Key>,
+        subkey: Option<T::SubKey>,
+    ) -> Result<DupWalker<'_, T, Self>, DatabaseError> {
+        let start = match (key, subkey) {
+            (Some(key), Some(subkey)) => {
+                self.seek_by_key_subkey(key.clone(), subkey)?.map(|value| Ok((key, value)))
+            }
+            (Some(key), None) => {
+                let prefix = self.seek_key(key.encode().as_ref())?;
+                let entry = self
+                    .first_in((Bound::Included(prefix.as_slice()), Bound::Unbounded))?
+                    .filter(|(raw, _)| raw.starts_with(&prefix));
+                self.seek_to(entry).transpose()
+            }
+            (None, Some(subkey)) => {
+                if let Some((key, _)) = self.first()? {
+                    self.seek_by_key_subkey(key.clone(), subkey)?.map(|value| Ok((key, value)))
+                } else {
+                    Some(Err(DatabaseError::Read(Error::NotFound.into())))
+                }
+            }
+            (None, None) => self.first().transpose(),
+        };
+
+        Ok(DupWalker::<'_, T, Self> { cursor: self, start })
+    }
+}
+
+impl<T: Table> Cursor<RW, T> {
+    /// Returns the raw entry to write for the given encoded key and compressed value.
+    fn raw_entry(
+        &self,
+        key: &[u8],
+        value: &[u8],
+        operation: DatabaseWriteOperation,
+    ) -> Result<RawEntry, DatabaseError> {
+        if T::DUPSORT {
+            let raw_key =
+                dup_key(key, value).map_err(|e| write_error::<T>(e.into(), operation, key))?;
+            Ok((raw_key, Vec::new()))
+        } else {
+            Ok((key.to_vec(), value.to_vec()))
+        }
+    }
+
+    /// Writes the raw entry and moves the cursor to it.
+    fn write_raw(
+        &mut self,
+        (raw_key, raw_value): RawEntry,
+        operation: DatabaseWriteOperation,
+        key: &[u8],
+    ) -> Result<(), DatabaseError> {
+        self.tx.write(T::NAME, |table| {
+            table
+                .insert(raw_key.as_slice(), raw_value.as_slice())
+                .map_err(|e| write_error::<T>(redb_error(e), operation, key))?;
+            Ok(())
+        })?;
+        self.position = Position::At(raw_key);
+        Ok(())
+    }
+
+    /// Fails the write, leaving the cursor at the given raw key.
+    fn write_conflict(
+        &mut self,
+        raw_key: Vec<u8>,
+        error: Error,
+        operation: DatabaseWriteOperation,
+        key: &[u8],
+    ) -> Result<(), DatabaseError> {
+        self.position = Position::At(raw_key);
+        Err(write_error::<T>(error.into(), operation, key))
+    }
+}
+
+impl<T: Table> DbCursorRW<T> for Cursor<RW, T> {
+    /// Database operation that will update an existing row if a specified value already
+    /// exists in a table, and insert a new row if the specified value doesn't already exist
+    ///
+    /// For a DUPSORT table, `upsert` will not actually update-or-insert. If the key already exists,
+    /// it will append the value to the subkey, even if the subkeys are the same. So if you want
+    /// to properly upsert, you'll need to `seek_exact` & `delete_current` if the key+subkey was
+    /// found, before calling `upsert`.
+    fn upsert(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let key = key.encode();
+        let value = value.compress();
+        let operation = DatabaseWriteOperation::CursorUpsert;
+
+        let entry = self.raw_entry(key.as_ref(), value.as_ref(), operation)?;
+        self.write_raw(entry, operation, key.as_ref())
+    }
+
+    fn insert(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let key = key.encode();
+        let value = value.compress();
+        let operation = DatabaseWriteOperation::CursorInsert;
+
+        let entry = self.raw_entry(key.as_ref(), value.as_ref(), operation)?;
+        // In a DUPSORT table, the insert fails if the key has any duplicates.
+        let existing = if T::DUPSORT {
+            let prefix = dup_key_prefix(&entry.0);
+            let end = prefix_successor(prefix);
+            self.first_in((
+                Bound::Included(prefix),
+                end.as_deref().map_or(Bound::Unbounded, Bound::Excluded),
+            ))?
+        } else {
+            self.first_in((
+                Bound::Included(entry.0.as_slice()),
+                Bound::Included(entry.0.as_slice()),
+            ))?
+        };
+        if let Some((existing, _)) = existing {
+            return self.write_conflict(existing, Error::KeyExist, operation, key.as_ref())
+        }
+
+        self.write_raw(entry, operation, key.as_ref())
+    }
+
+    /// Appends the data to the end of the table. Consequently, the append operation
+    /// will fail if the inserted key is less than the last table key
+    fn append(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let key = key.encode();
+        let value = value.compress();
+        let operation = DatabaseWriteOperation::CursorAppend;
+
+        let entry = self.raw_entry(key.as_ref(), value.as_ref(), operation)?;
+        if let Some((last, _)) = self.last_in((Bound::Unbounded, Bound::Unbounded))? {
+            let ordering = if T::DUPSORT {
+                dup_key_prefix(&entry.0).cmp(dup_key_prefix(&last))
+            } else {
+                entry.0.cmp(&last)
+            };
+            match ordering {
+                Ordering::Greater => {}
+                // Like MDBX, appending to the last key of a DUPSORT table adds a duplicate.
+                Ordering::Equal if T::DUPSORT => {}
+                Ordering::Equal => {
+                    return self.write_conflict(last, Error::KeyExist, operation, key.as_ref())
+                }
+                Ordering::Less => {
+                    return self.write_conflict(last, Error::KeyMismatch, operation, key.as_ref())
+                }
+            }
+        }
+
+        self.write_raw(entry, operation, key.as_ref())
+    }
+
+    fn delete_current(&mut self) -> Result<(), DatabaseError> {
+        let Some((raw, _)) = self.current_entry()? else {
+            return Err(DatabaseError::Delete(Error::NotFound.into()))
+        };
+
+        self.tx.write(T::NAME, |table| {
+            table.remove(raw.as_slice()).map_err(|e| DatabaseError::Delete(redb_error(e)))?;
+            Ok(())
+        })?;
+        self.position = Position::Deleted(raw);
+        Ok(())
+    }
+}
+
+impl<T: DupSort> DbDupCursorRW<T> for Cursor<RW, T> {
+    fn delete_current_duplicates(&mut self) -> Result<(), DatabaseError> {
+        if !T::DUPSORT {
+            return self.delete_current()
+        }
+
+        let Some((raw, _)) = self.current_entry()? else {
+            return Err(DatabaseError::Delete(Error::NotFound.into()))
+        };
+
+        let prefix = dup_key_prefix(&raw);
+        let last = self.tx.write(T::NAME, |table| remove_prefix(table, prefix))?;
+        self.position = Position::Deleted(last.unwrap_or(raw));
+        Ok(())
+    }
+
+    fn append_dup(&mut self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        if !T::DUPSORT {
+            return self.append(key, value)
+        }
+
+        let key = key.encode();
+        let value = value.compress();
+        let operation = DatabaseWriteOperation::CursorAppendDup;
+
+        let entry = self.raw_entry(key.as_ref(), value.as_ref(), operation)?;
+        let prefix = dup_key_prefix(&entry.0);
+        let end = prefix_successor(prefix);
+        let last = self.last_in((
+            Bound::Included(prefix),
+            end.as_deref().map_or(Bound::Unbounded, Bound::Excluded),
+        ))?;
+        if let Some((last, _)) = last {
+            match entry.0.cmp(&last) {
+                Ordering::Greater => {}
+                Ordering::Equal => {
+                    return self.write_conflict(last, Error::KeyExist, operation, key.as_ref())
+                }
+                Ordering::Less => {
+                    return self.write_conflict(last, Error::KeyMismatch, operation, key.as_ref())
+                }
+            }
+        }
+
+        self.write_raw(entry, operation, key.as_ref())
+    }
+}
+
+/// Returns a write error of the table `T`.
+fn write_error<T: Table>(
+    info: DatabaseErrorInfo,
+    operation: DatabaseWriteOperation,
+    key: &[u8],
+) -> DatabaseError {
+    DatabaseWriteError { info, operation, table_name: T::NAME, key: key.to_vec() }.into()
+}
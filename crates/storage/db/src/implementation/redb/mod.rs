@@ -0,0 +1,325 @@
+//! Module that interacts with [redb](https://www.redb.org).
+//!
+//! redb has no notion of `DUPSORT` tables, so every table is a plain `&[u8] -> &[u8]` B-tree and
+//! duplicates are emulated on top of it: an entry of a `DUPSORT` table is stored as the key
+//! `[len(key)] ++ key ++ value` with an empty value. Entries of the same key are therefore
+//! adjacent and ordered by their value bytes, which matches MDBX as long as the encoded keys of a
+//! table have a fixed length. This holds for all `DUPSORT` tables of reth.
+
+use crate::{
+    lockfile::StorageLock,
+    tables::{self, Tables},
+    DatabaseError,
+};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW},
+    database::Database,
+    database_metrics::{DatabaseMetadata, DatabaseMetadataValue, DatabaseMetrics},
+    models::ClientVersion,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_storage_errors::db::DatabaseErrorInfo;
+use std::{
+    fmt,
+    path::Path,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tx::Tx;
+
+pub mod cursor;
+pub mod tx;
+
+/// Name of the redb database file inside the database directory.
+pub const REDB_FILE_NAME: &str = "reth.redb";
+
+/// Raw definition of a reth table in redb.
+pub(crate) type RawTableDefinition<'a> = redb::TableDefinition<'a, &'static [u8], &'static [u8]>;
+
+/// Returns the raw definition of the table with the given name.
+pub(crate) const fn table_definition(name: &str) -> RawTableDefinition<'_> {
+    redb::TableDefinition::new(name)
+}
+
+/// Returns the prefix shared by all raw keys of the duplicates of `key`.
+pub(crate) fn dup_prefix(key: &[u8]) -> Result<Vec<u8>, Error> {
+    let len = u8::try_from(key.len()).map_err(|_| Error::KeyTooLong(key.len()))?;
+    let mut prefix = Vec::with_capacity(1 + key.len());
+    prefix.push(len);
+    prefix.extend_from_slice(key);
+    Ok(prefix)
+}
+
+/// Returns the raw key of a duplicate `(key, value)` entry.
+pub(crate) fn dup_key(key: &[u8], value: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut raw = dup_prefix(key)?;
+    raw.extend_from_slice(value);
+    Ok(raw)
+}
+
+/// Splits the raw key of a duplicate entry into its key and value.
+pub(crate) fn split_dup_key(raw: &[u8]) -> (&[u8], &[u8]) {
+    let len = raw[0] as usize;
+    raw[1..].split_at(len)
+}
+
+/// Returns the prefix of the raw key of a duplicate entry, i.e. the raw key without the value.
+pub(crate) fn dup_key_prefix(raw: &[u8]) -> &[u8] {
+    &raw[..1 + raw[0] as usize]
+}
+
+/// Returns the smallest key that is greater than all keys starting with `prefix`, or [None] if
+/// there is no such key.
+pub(crate) fn prefix_successor(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut successor = prefix.to_vec();
+    while let Some(last) = successor.pop() {
+        if last < u8::MAX {
+            successor.push(last + 1);
+            return Some(successor)
+        }
+    }
+    None
+}
+
+/// Errors of the redb backend.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// Error returned by redb.
+    #[error(transparent)]
+    Redb(#[from] redb::Error),
+    /// Key/data pair already exists.
+    #[error("key/data pair already exists")]
+    KeyExist,
+    /// No matching key/data pair found.
+    #[error("no matching key/data pair found")]
+    NotFound,
+    /// Key is not greater than the last key of the table or of its duplicates when appending.
+    #[error("the given key is not greater than the last key")]
+    KeyMismatch,
+    /// Encoded key of a `DUPSORT` table is longer than 255 bytes.
+    #[error("encoded key of {0} bytes is too long for a dupsort table")]
+    KeyTooLong(usize),
+    /// Transaction was already committed or aborted.
+    #[error("transaction is already closed")]
+    TransactionClosed,
+}
+
+impl Error {
+    /// Wraps any redb error.
+    pub(crate) fn redb(error: impl Into<redb::Error>) -> Self {
+        Self::Redb(error.into())
+    }
+
+    /// Returns the code of the error.
+    ///
+    /// Codes are specific to the redb backend, they don't overlap with MDBX error codes.
+    pub const fn code(&self) -> i32 {
+        match self {
+            Self::Redb(_) => 1,
+            Self::KeyExist => 2,
+            Self::NotFound => 3,
+            Self::KeyMismatch => 4,
+            Self::KeyTooLong(_) => 5,
+            Self::TransactionClosed => 6,
+        }
+    }
+}
+
+impl From<Error> for DatabaseErrorInfo {
+    fn from(error: Error) -> Self {
+        Self { message: error.to_string(), code: error.code() }
+    }
+}
+
+/// Converts any redb error into [`DatabaseErrorInfo`].
+pub(crate) fn redb_error(error: impl Into<redb::Error>) -> DatabaseErrorInfo {
+    Error::redb(error).into()
+}
+
+/// Arguments for database initialization.
+#[derive(Clone, Debug, Default)]
+pub struct DatabaseArguments {
+    /// Client version that accesses the database.
+    client_version: ClientVersion,
+    /// Size of the redb page cache in bytes. If [None], the default value is used.
+    cache_size: Option<usize>,
+}
+
+impl DatabaseArguments {
+    /// Create new database arguments with given client version.
+    pub const fn new(client_version: ClientVersion) -> Self {
+        Self { client_version, cache_size: None }
+    }
+
+    /// Set the size of the page cache in bytes.
+    pub const fn with_cache_size(mut self, cache_size: Option<usize>) -> Self {
+        self.cache_size = cache_size;
+        self
+    }
+
+    /// Returns the client version if any.
+    pub const fn client_version(&self) -> &ClientVersion {
+        &self.client_version
+    }
+}
+
+/// Wrapper for the redb database.
+#[derive(Clone)]
+pub struct RedbDatabase {
+    /// redb database.
+    inner: Arc<redb::Database>,
+    /// Write lock of the database directory.
+    _lock_file: Arc<StorageLock>,
+}
+
+impl fmt::Debug for RedbDatabase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedbDatabase").finish_non_exhaustive()
+    }
+}
+
+impl Database for RedbDatabase {
+    type TX = Tx<RO>;
+    type TXMut = Tx<RW>;
+
+    fn tx(&self) -> Result<Self::TX, DatabaseError> {
+        self.inner.begin_read().map(Tx::new_read).map_err(|e| DatabaseError::InitTx(redb_error(e)))
+    }
+
+    fn tx_mut(&self) -> Result<Self::TXMut, DatabaseError> {
+        self.inner
+            .begin_write()
+            .map(Tx::new_write)
+            .map_err(|e| DatabaseError::InitTx(redb_error(e)))
+    }
+}
+
+impl DatabaseMetrics for RedbDatabase {}
+
+impl DatabaseMetadata for RedbDatabase {
+    fn metadata(&self) -> DatabaseMetadataValue {
+        DatabaseMetadataValue::new(None)
+    }
+}
+
+impl RedbDatabase {
+    /// Opens the database in the specified directory, creating the database file if it doesn't
+    /// exist.
+    ///
+    /// redb databases can only be opened by a single process, so the database is always opened in
+    /// read-write mode. It does not create the tables, for that call
+    /// [`RedbDatabase::create_tables`].
+    pub fn open(path: &Path, args: DatabaseArguments) -> Result<Self, DatabaseError> {
+        let lock_file =
+            StorageLock::try_acquire(path).map_err(|err| DatabaseError::Other(err.to_string()))?;
+
+        let mut builder = redb::Builder::new();
+        if let Some(cache_size) = args.cache_size {
+            builder.set_cache_size(cache_size);
+        }
+        let inner = builder
+            .create(path.join(REDB_FILE_NAME))
+            .map_err(|e| DatabaseError::Open(redb_error(e)))?;
+
+        Ok(Self { inner: Arc::new(inner), _lock_file: Arc::new(lock_file) })
+    }
+
+    /// Creates all the tables defined in [`Tables`], if necessary.
+    pub fn create_tables(&self) -> Result<(), DatabaseError> {
+        let tx = self.inner.begin_write().map_err(|e| DatabaseError::InitTx(redb_error(e)))?;
+
+        for table in Tables::ALL {
+            tx.open_table(table_definition(table.name()))
+                .map_err(|e| DatabaseError::CreateTable(redb_error(e)))?;
+        }
+
+        tx.commit().map_err(|e| DatabaseError::Commit(redb_error(e)))?;
+
+        Ok(())
+    }
+
+    /// Records version that accesses the database with write privileges.
+    pub fn record_client_version(&self, version: ClientVersion) -> Result<(), DatabaseError> {
+        if version.is_empty() {
+            return Ok(())
+        }
+
+        let tx = self.tx_mut()?;
+        let mut version_cursor = tx.cursor_write::<tables::VersionHistory>()?;
+
+        let last_version = version_cursor.last()?.map(|(_, v)| v);
+        if Some(&version) != last_version.as_ref() {
+            version_cursor.upsert(
+                SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+                version,
+            )?;
+            tx.commit()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Marker trait for the kind of a redb transaction.
+pub trait TransactionKind: fmt::Debug + Send + Sync + 'static {}
+
+/// Marker type for read-only transactions.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct RO;
+
+/// Marker type for read-write transactions.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct RW;
+
+impl TransactionKind for RO {}
+impl TransactionKind for RW {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tables::PlainAccountState, test_utils::*};
+    use alloy_primitives::Address;
+    use reth_primitives::Account;
+    use tempfile::TempDir;
+
+    #[test]
+    fn db_reopen() {
+        let path = TempDir::new().expect(ERROR_TEMPDIR);
+        let args = DatabaseArguments::new(ClientVersion::default());
+        let key = Address::with_last_byte(1);
+        let value = Account { nonce: 1, ..Default::default() };
+
+        {
+            let db = crate::redb::init_db(path.path(), args.clone()).expect(ERROR_DB_CREATION);
+            db.update(|tx| tx.put::<PlainAccountState>(key, value).unwrap()).unwrap();
+        }
+
+        let db = crate::redb::open_db(path.path(), args).expect(ERROR_DB_OPEN);
+        let result = db.view(|tx| tx.get::<PlainAccountState>(key).unwrap()).unwrap();
+        assert_eq!(result, Some(value));
+    }
+
+    #[test]
+    fn write_conflict_has_redb_error_code() {
+        let db = create_test_redb_rw_db();
+        let tx = db.tx_mut().unwrap();
+        let mut cursor = tx.cursor_write::<PlainAccountState>().unwrap();
+        let key = Address::with_last_byte(1);
+
+        cursor.insert(key, Account::default()).unwrap();
+        let Err(DatabaseError::Write(err)) = cursor.insert(key, Account::default()) else {
+            panic!("expected a write error")
+        };
+        assert_eq!(err.info, DatabaseErrorInfo::from(Error::KeyExist));
+        assert_eq!(err.info.code, 2);
+    }
+
+    #[test]
+    fn prefix_successor_skips_max_bytes() {
+        assert_eq!(prefix_successor(&[1, 2]), Some(vec![1, 3]));
+        assert_eq!(prefix_successor(&[1, 0xff]), Some(vec![2]));
+        assert_eq!(prefix_successor(&[0xff, 0xff]), None);
+    }
+}
@@ -0,0 +1,339 @@
+//! Transaction wrapper for redb.
+
+use super::{
+    cursor::Cursor, dup_key, dup_prefix, prefix_successor, redb_error, table_definition, Error,
+    TransactionKind, RO, RW,
+};
+use crate::{tables::utils::decode_one, DatabaseError};
+use parking_lot::Mutex;
+use redb::{ReadableTable, ReadableTableMetadata, StorageError};
+use reth_db_api::{
+    table::{Compress, DupSort, Encode, Table, TableImporter},
+    transaction::{DbTx, DbTxMut},
+};
+use reth_storage_errors::db::{DatabaseErrorInfo, DatabaseWriteError, DatabaseWriteOperation};
+use std::{borrow::Cow, fmt, marker::PhantomData, ops::Bound, sync::Arc};
+
+/// Raw `(key, value)` entry of a redb table.
+pub(crate) type RawEntry = (Vec<u8>, Vec<u8>);
+
+/// Raw bounds of a range of keys.
+pub(crate) type RawRange<'a> = (Bound<&'a [u8]>, Bound<&'a [u8]>);
+
+/// redb table opened in a write transaction.
+pub(crate) type RawTableMut<'txn> = redb::Table<'txn, &'static [u8], &'static [u8]>;
+
+/// Read access to a redb table, regardless of the kind of transaction it was opened in.
+pub(crate) trait TableReader {
+    /// Returns the value of the given key.
+    fn get_raw(&self, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError>;
+
+    /// Returns the first entry in the given range.
+    fn first_raw(&self, range: RawRange<'_>) -> Result<Option<RawEntry>, StorageError>;
+
+    /// Returns the last entry in the given range.
+    fn last_raw(&self, range: RawRange<'_>) -> Result<Option<RawEntry>, StorageError>;
+
+    /// Returns the number of entries in the table.
+    fn len_raw(&self) -> Result<u64, StorageError>;
+}
+
+impl<T> TableReader for T
+where
+    T: ReadableTable<&'static [u8], &'static [u8]> + ReadableTableMetadata,
+{
+    fn get_raw(&self, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
+        Ok(self.get(key)?.map(|value| value.value().to_vec()))
+    }
+
+    fn first_raw(&self, range: RawRange<'_>) -> Result<Option<RawEntry>, StorageError> {
+        self.range::<&[u8]>(range)?
+            .next()
+            .map(|entry| entry.map(|(k, v)| (k.value().to_vec(), v.value().to_vec())))
+            .transpose()
+    }
+
+    fn last_raw(&self, range: RawRange<'_>) -> Result<Option<RawEntry>, StorageError> {
+        self.range::<&[u8]>(range)?
+            .next_back()
+            .map(|entry| entry.map(|(k, v)| (k.value().to_vec(), v.value().to_vec())))
+            .transpose()
+    }
+
+    fn len_raw(&self) -> Result<u64, StorageError> {
+        ReadableTableMetadata::len(self)
+    }
+}
+
+/// Underlying redb transaction.
+enum Transaction {
+    Read(redb::ReadTransaction),
+    Write(redb::WriteTransaction),
+}
+
+/// redb transaction shared between a [`Tx`] and the cursors opened from it.
+///
+/// Tables are opened for the duration of a single operation, because redb tables borrow the
+/// transaction they were opened in. The transaction is [None] once committed or aborted.
+pub(crate) struct SharedTx {
+    inner: Mutex<Option<Transaction>>,
+}
+
+impl fmt::Debug for SharedTx {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.inner.lock().as_ref() {
+            Some(Transaction::Read(_)) => "read",
+            Some(Transaction::Write(_)) => "write",
+            None => "closed",
+        };
+        f.debug_struct("SharedTx").field("kind", &kind).finish()
+    }
+}
+
+impl SharedTx {
+    /// Executes `f` on the table with the given name.
+    pub(crate) fn read<R>(
+        &self,
+        table: &str,
+        f: impl FnOnce(&dyn TableReader) -> Result<R, StorageError>,
+    ) -> Result<R, DatabaseError> {
+        let inner = self.inner.lock();
+        let result = match inner.as_ref() {
+            Some(Transaction::Read(tx)) => f(&tx
+                .open_table(table_definition(table))
+                .map_err(|e| DatabaseError::Open(redb_error(e)))?),
+            Some(Transaction::Write(tx)) => f(&tx
+                .open_table(table_definition(table))
+                .map_err(|e| DatabaseError::Open(redb_error(e)))?),
+            None => return Err(DatabaseError::Read(Error::TransactionClosed.into())),
+        };
+        result.map_err(|e| DatabaseError::Read(redb_error(e)))
+    }
+
+    /// Executes `f` on the table with the given name, opened for writing.
+    pub(crate) fn write<R>(
+        &self,
+        table: &str,
+        f: impl FnOnce(&mut RawTableMut<'_>) -> Result<R, DatabaseError>,
+    ) -> Result<R, DatabaseError> {
+        let inner = self.inner.lock();
+        let Some(Transaction::Write(tx)) = inner.as_ref() else {
+            return Err(DatabaseError::Open(Error::TransactionClosed.into()))
+        };
+        let mut table = tx
+            .open_table(table_definition(table))
+            .map_err(|e| DatabaseError::Open(redb_error(e)))?;
+        f(&mut table)
+    }
+
+    /// Removes all entries of the table with the given name.
+    fn clear(&self, table: &str) -> Result<(), DatabaseError> {
+        let inner = self.inner.lock();
+        let Some(Transaction::Write(tx)) = inner.as_ref() else {
+            return Err(DatabaseError::Delete(Error::TransactionClosed.into()))
+        };
+        tx.delete_table(table_definition(table))
+            .map_err(|e| DatabaseError::Delete(redb_error(e)))?;
+        // Recreate the table, reads of missing tables fail.
+        tx.open_table(table_definition(table)).map_err(|e| DatabaseError::Open(redb_error(e)))?;
+        Ok(())
+    }
+
+    /// Takes the transaction out, closing it for all cursors.
+    fn take(&self) -> Option<Transaction> {
+        self.inner.lock().take()
+    }
+}
+
+/// Wrapper for the redb transaction.
+#[derive(Debug)]
+pub struct Tx<K: TransactionKind> {
+    /// redb transaction, shared with the cursors.
+    inner: Arc<SharedTx>,
+    /// Phantom data to enforce the transaction kind.
+    _kind: PhantomData<K>,
+}
+
+impl Tx<RO> {
+    /// Creates new `Tx` object with a read-only transaction.
+    pub(crate) fn new_read(tx: redb::ReadTransaction) -> Self {
+        Self::new_inner(Transaction::Read(tx))
+    }
+}
+
+impl Tx<RW> {
+    /// Creates new `Tx` object with a read-write transaction.
+    pub(crate) fn new_write(tx: redb::WriteTransaction) -> Self {
+        Self::new_inner(Transaction::Write(tx))
+    }
+}
+
+impl<K: TransactionKind> Tx<K> {
+    fn new_inner(tx: Transaction) -> Self {
+        Self { inner: Arc::new(SharedTx { inner: Mutex::new(Some(tx)) }), _kind: PhantomData }
+    }
+
+    /// Creates a cursor to iterate over a table items.
+    pub fn new_cursor<T: Table>(&self) -> Result<Cursor<K, T>, DatabaseError> {
+        Ok(Cursor::new(self.inner.clone()))
+    }
+}
+
+impl TableImporter for Tx<RW> {}
+
+impl<K: TransactionKind> DbTx for Tx<K> {
+    type Cursor<T: Table> = Cursor<K, T>;
+    type DupCursor<T: DupSort> = Cursor<K, T>;
+
+    fn get<T: Table>(&self, key: T::Key) -> Result<Option<<T as Table>::Value>, DatabaseError> {
+        let key = key.encode();
+        let value = if T::DUPSORT {
+            // Returns the first duplicate of the key.
+            let prefix = dup_prefix(key.as_ref()).map_err(|e| DatabaseError::Read(e.into()))?;
+            let end = prefix_successor(&prefix);
+            self.inner
+                .read(T::NAME, |table| {
+                    table.first_raw((
+                        Bound::Included(prefix.as_slice()),
+                        end.as_deref().map_or(Bound::Unbounded, Bound::Excluded),
+                    ))
+                })?
+                .map(|(raw, _)| raw[prefix.len()..].to_vec())
+        } else {
+            self.inner.read(T::NAME, |table| table.get_raw(key.as_ref()))?
+        };
+
+        value.map(|value| decode_one::<T>(Cow::Owned(value))).transpose()
+    }
+
+    fn commit(self) -> Result<bool, DatabaseError> {
+        match self.inner.take() {
+            Some(Transaction::Write(tx)) => {
+                tx.commit().map_err(|e| DatabaseError::Commit(redb_error(e)))?
+            }
+            Some(Transaction::Read(tx)) => drop(tx),
+            None => return Err(DatabaseError::Commit(Error::TransactionClosed.into())),
+        }
+        Ok(true)
+    }
+
+    fn abort(self) {
+        if let Some(Transaction::Write(tx)) = self.inner.take() {
+            let _ = tx.abort();
+        }
+    }
+
+    // Iterate over read only values in database.
+    fn cursor_read<T: Table>(&self) -> Result<Self::Cursor<T>, DatabaseError> {
+        self.new_cursor()
+    }
+
+    /// Iterate over read only values in database.
+    fn cursor_dup_read<T: DupSort>(&self) -> Result<Self::DupCursor<T>, DatabaseError> {
+        self.new_cursor()
+    }
+
+    /// Returns number of entries in the table.
+    fn entries<T: Table>(&self) -> Result<usize, DatabaseError> {
+        self.inner.read(T::NAME, |table| table.len_raw()).map(|len| len as usize).map_err(|e| {
+            match e {
+                DatabaseError::Read(info) => DatabaseError::Stats(info),
+                e => e,
+            }
+        })
+    }
+
+    /// redb transactions have no timeout, so this is a no-op.
+    fn disable_long_read_transaction_safety(&mut self) {}
+}
+
+impl DbTxMut for Tx<RW> {
+    type CursorMut<T: Table> = Cursor<RW, T>;
+    type DupCursorMut<T: DupSort> = Cursor<RW, T>;
+
+    fn put<T: Table>(&self, key: T::Key, value: T::Value) -> Result<(), DatabaseError> {
+        let key = key.encode();
+        let value = value.compress();
+        let write_error = |info: DatabaseErrorInfo| DatabaseWriteError {
+            info,
+            operation: DatabaseWriteOperation::Put,
+            table_name: T::NAME,
+            key: key.as_ref().to_vec(),
+        };
+
+        let (raw_key, raw_value) = if T::DUPSORT {
+            (dup_key(key.as_ref(), value.as_ref()).map_err(|e| write_error(e.into()))?, Vec::new())
+        } else {
+            (key.as_ref().to_vec(), value.as_ref().to_vec())
+        };
+
+        self.inner.write(T::NAME, |table| {
+            table
+                .insert(raw_key.as_slice(), raw_value.as_slice())
+                .map_err(|e| write_error(redb_error(e)))?;
+            Ok(())
+        })
+    }
+
+    fn delete<T: Table>(
+        &self,
+        key: T::Key,
+        value: Option<T::Value>,
+    ) -> Result<bool, DatabaseError> {
+        let key = key.encode();
+
+        if !T::DUPSORT {
+            return self.inner.write(T::NAME, |table| {
+                Ok(table
+                    .remove(key.as_ref())
+                    .map_err(|e| DatabaseError::Delete(redb_error(e)))?
+                    .is_some())
+            });
+        }
+
+        // Without a value, all duplicates of the key are deleted.
+        let Some(value) = value else {
+            let prefix = dup_prefix(key.as_ref()).map_err(|e| DatabaseError::Delete(e.into()))?;
+            return self.inner.write(T::NAME, |table| Ok(remove_prefix(table, &prefix)?.is_some()))
+        };
+
+        let raw_key = dup_key(key.as_ref(), value.compress().as_ref())
+            .map_err(|e| DatabaseError::Delete(e.into()))?;
+        self.inner.write(T::NAME, |table| {
+            Ok(table
+                .remove(raw_key.as_slice())
+                .map_err(|e| DatabaseError::Delete(redb_error(e)))?
+                .is_some())
+        })
+    }
+
+    fn clear<T: Table>(&self) -> Result<(), DatabaseError> {
+        self.inner.clear(T::NAME)
+    }
+
+    fn cursor_write<T: Table>(&self) -> Result<Self::CursorMut<T>, DatabaseError> {
+        self.new_cursor()
+    }
+
+    fn cursor_dup_write<T: DupSort>(&self) -> Result<Self::DupCursorMut<T>, DatabaseError> {
+        self.new_cursor()
+    }
+}
+
+/// Removes all entries whose key starts with `prefix`, returning the last removed key.
+pub(crate) fn remove_prefix(
+    table: &mut RawTableMut<'_>,
+    prefix: &[u8],
+) -> Result<Option<Vec<u8>>, DatabaseError> {
+    let end = prefix_successor(prefix);
+    let range = (Bound::Included(prefix), end.as_deref().map_or(Bound::Unbounded, Bound::Excluded));
+
+    let mut last = None;
+    while let Some((raw, _)) =
+        table.first_raw(range).map_err(|e| DatabaseError::Delete(redb_error(e)))?
+    {
+        table.remove(raw.as_slice()).map_err(|e| DatabaseError::Delete(redb_error(e)))?;
+        last = Some(raw);
+    }
+    Ok(last)
+}
@@ -0,0 +1,631 @@
+//! Tests that run against every database backend.
+
+use crate::{
+    tables::{
+        AccountChangeSets, AccountsHistory, CanonicalHeaders, Headers, PlainAccountState,
+        PlainStorageState,
+    },
+    DatabaseError,
+};
+use alloy_primitives::{Address, B256, U256};
+use assert_matches::assert_matches;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW, ReverseWalker, Walker},
+    database::Database,
+    models::{AccountBeforeTx, ShardedKey},
+    table::{Encode, Table},
+    transaction::{DbTx, DbTxMut},
+};
+use reth_primitives::{Account, Header, StorageEntry};
+use reth_primitives_traits::IntegerList;
+use reth_storage_errors::db::DatabaseWriteOperation;
+
+/// Generates a test of every given function for each enabled backend.
+macro_rules! db_tests {
+    ($($name:ident),* $(,)?) => {
+        #[cfg(feature = "mdbx")]
+        mod mdbx {
+            $(
+                #[test]
+                fn $name() {
+                    super::$name(crate::test_utils::create_test_rw_db())
+                }
+            )*
+        }
+
+        #[cfg(feature = "redb")]
+        mod redb {
+            $(
+                #[test]
+                fn $name() {
+                    super::$name(crate::test_utils::create_test_redb_rw_db())
+                }
+            )*
+        }
+    };
+}
+
+db_tests!(
+    db_manual_put_get,
+    db_abort,
+    db_delete_and_clear,
+    db_dup_cursor_delete_first,
+    db_dup_cursor_delete_current_duplicates,
+    db_dup_cursor_next_dup_and_no_dup,
+    db_cursor_walk_range,
+    db_cursor_walk_range_on_dup_table,
+    db_walker,
+    db_walk_back,
+    db_cursor_seek_exact_or_previous_key,
+    db_cursor_insert,
+    db_cursor_insert_dup,
+    db_cursor_delete_current_non_existent,
+    db_cursor_append_failure,
+    db_cursor_upsert,
+    db_cursor_dupsort_append,
+    dup_value_with_same_subkey,
+    db_sharded_key,
+);
+
+const ERROR_PUT: &str = "Not able to insert value into table.";
+const ERROR_APPEND: &str = "Not able to append the value to the table.";
+const ERROR_UPSERT: &str = "Not able to upsert the value to the table.";
+const ERROR_GET: &str = "Not able to get value from table.";
+const ERROR_DEL: &str = "Not able to delete from table.";
+const ERROR_COMMIT: &str = "Not able to commit transaction.";
+const ERROR_RETURN_VALUE: &str = "Mismatching result.";
+const ERROR_INIT_TX: &str = "Failed to create a database transaction.";
+
+/// Asserts that the result is an error of the given write operation on `key` of `T`.
+///
+/// The error info isn't compared, because error codes are specific to each backend.
+fn assert_write_error<T: Table>(
+    result: Result<(), DatabaseError>,
+    operation: DatabaseWriteOperation,
+    key: T::Key,
+) {
+    let key: Vec<u8> = key.encode().into();
+    assert_matches!(
+        result,
+        Err(DatabaseError::Write(err))
+            if err.operation == operation && err.table_name == T::NAME && err.key == key
+    );
+}
+
+fn db_manual_put_get<DB: Database>(db: DB) {
+    let value = Header::default();
+    let key = 1u64;
+
+    // PUT
+    let tx = db.tx_mut().expect(ERROR_INIT_TX);
+    tx.put::<Headers>(key, value.clone()).expect(ERROR_PUT);
+    tx.commit().expect(ERROR_COMMIT);
+
+    // GET
+    let tx = db.tx().expect(ERROR_INIT_TX);
+    let result = tx.get::<Headers>(key).expect(ERROR_GET);
+    assert_eq!(result.expect(ERROR_RETURN_VALUE), value);
+    assert_eq!(tx.entries::<Headers>(), Ok(1));
+    tx.commit().expect(ERROR_COMMIT);
+}
+
+fn db_abort<DB: Database>(db: DB) {
+    let tx = db.tx_mut().expect(ERROR_INIT_TX);
+    tx.put::<CanonicalHeaders>(0, B256::ZERO).expect(ERROR_PUT);
+    tx.abort();
+
+    let tx = db.tx().expect(ERROR_INIT_TX);
+    assert_eq!(tx.get::<CanonicalHeaders>(0), Ok(None));
+}
+
+fn db_delete_and_clear<DB: Database>(db: DB) {
+    let key = Address::with_last_byte(1);
+    let entry_0 = StorageEntry { key: B256::with_last_byte(1), value: U256::from(0) };
+    let entry_1 = StorageEntry { key: B256::with_last_byte(2), value: U256::from(1) };
+
+    let tx = db.tx_mut().expect(ERROR_INIT_TX);
+    tx.put::<PlainStorageState>(key, entry_0).expect(ERROR_PUT);
+    tx.put::<PlainStorageState>(key, entry_1).expect(ERROR_PUT);
+    tx.put::<CanonicalHeaders>(0, B256::ZERO).expect(ERROR_PUT);
+    assert_eq!(tx.entries::<PlainStorageState>(), Ok(2));
+
+    // Delete a single duplicate, then all remaining duplicates of the key.
+    assert_eq!(tx.delete::<PlainStorageState>(key, Some(entry_0)), Ok(true));
+    assert_eq!(tx.delete::<PlainStorageState>(key, Some(entry_0)), Ok(false));
+    assert_eq!(tx.get::<PlainStorageState>(key), Ok(Some(entry_1)));
+    assert_eq!(tx.delete::<PlainStorageState>(key, None), Ok(true));
+    assert_eq!(tx.get::<PlainStorageState>(key), Ok(None));
+
+    tx.clear::<CanonicalHeaders>().expect(ERROR_DEL);
+    assert_eq!(tx.entries::<CanonicalHeaders>(), Ok(0));
+    tx.put::<CanonicalHeaders>(1, B256::ZERO).expect(ERROR_PUT);
+    tx.commit().expect(ERROR_COMMIT);
+
+    let tx = db.tx().expect(ERROR_INIT_TX);
+    assert_eq!(tx.get::<CanonicalHeaders>(0), Ok(None));
+    assert_eq!(tx.get::<CanonicalHeaders>(1), Ok(Some(B256::ZERO)));
+}
+
+fn db_dup_cursor_delete_first<DB: Database>(db: DB) {
+    let tx = db.tx_mut().expect(ERROR_INIT_TX);
+
+    let mut dup_cursor = tx.cursor_dup_write::<PlainStorageState>().unwrap();
+
+    let entry_0 = StorageEntry { key: B256::with_last_byte(1), value: U256::from(0) };
+    let entry_1 = StorageEntry { key: B256::with_last_byte(1), value: U256::from(1) };
+
+    dup_cursor.upsert(Address::with_last_byte(1), entry_0).expect(ERROR_UPSERT);
+    dup_cursor.upsert(Address::with_last_byte(1), entry_1).expect(ERROR_UPSERT);
+
+    assert_eq!(
+        dup_cursor.walk(None).unwrap().collect::<Result<Vec<_>, _>>(),
+        Ok(vec![(Address::with_last_byte(1), entry_0), (Address::with_last_byte(1), entry_1),])
+    );
+
+    let mut walker = dup_cursor.walk(None).unwrap();
+    walker.delete_current().expect(ERROR_DEL);
+
+    assert_eq!(walker.next(), Some(Ok((Address::with_last_byte(1), entry_1))));
+
+    // Check the tx view - it correctly holds entry_1
+    assert_eq!(
+        tx.cursor_dup_read::<PlainStorageState>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>(),
+        Ok(vec![(Address::with_last_byte(1), entry_1)])
+    );
+
+    // Check the remainder of walker
+    assert_eq!(walker.next(), None);
+}
+
+fn db_dup_cursor_delete_current_duplicates<DB: Database>(db: DB) {
+    let tx = db.tx_mut().expect(ERROR_INIT_TX);
+
+    let key1 = Address::with_last_byte(1);
+    let key2 = Address::with_last_byte(2);
+    let entry_0 = StorageEntry { key: B256::with_last_byte(1), value: U256::from(0) };
+    let entry_1 = StorageEntry { key: B256::with_last_byte(2), value: U256::from(1) };
+
+    let mut dup_cursor = tx.cursor_dup_write::<PlainStorageState>().unwrap();
+    dup_cursor.upsert(key1, entry_0).expect(ERROR_UPSERT);
+    dup_cursor.upsert(key1, entry_1).expect(ERROR_UPSERT);
+    dup_cursor.upsert(key2, entry_0).expect(ERROR_UPSERT);
+
+    assert_eq!(dup_cursor.seek_exact(key1), Ok(Some((key1, entry_0))));
+    dup_cursor.delete_current_duplicates().expect(ERROR_DEL);
+
+    assert_eq!(dup_cursor.seek_exact(key1), Ok(None));
+    assert_eq!(
+        dup_cursor.walk(None).unwrap().collect::<Result<Vec<_>, _>>(),
+        Ok(vec![(key2, entry_0)])
+    );
+}
+
+fn db_dup_cursor_next_dup_and_no_dup<DB: Database>(db: DB) {
+    let tx = db.tx_mut().expect(ERROR_INIT_TX);
+
+    let key1 = Address::with_last_byte(1);
+    let key2 = Address::with_last_byte(2);
+    let entry_0 = StorageEntry { key: B256::with_last_byte(1), value: U256::from(0) };
+    let entry_1 = StorageEntry { key: B256::with_last_byte(2), value: U256::from(1) };
+
+    let mut dup_cursor = tx.cursor_dup_write::<PlainStorageState>().unwrap();
+    dup_cursor.upsert(key1, entry_0).expect(ERROR_UPSERT);
+    dup_cursor.upsert(key1, entry_1).expect(ERROR_UPSERT);
+    dup_cursor.upsert(key2, entry_1).expect(ERROR_UPSERT);
+
+    assert_eq!(dup_cursor.first(), Ok(Some((key1, entry_0))));
+    assert_eq!(dup_cursor.next_dup(), Ok(Some((key1, entry_1))));
+    // No more duplicates, the cursor doesn't move.
+    assert_eq!(dup_cursor.next_dup(), Ok(None));
+    assert_eq!(dup_cursor.current(), Ok(Some((key1, entry_1))));
+
+    assert_eq!(dup_cursor.first(), Ok(Some((key1, entry_0))));
+    assert_eq!(dup_cursor.next_no_dup(), Ok(Some((key2, entry_1))));
+    assert_eq!(dup_cursor.next_no_dup(), Ok(None));
+
+    assert_eq!(
+        dup_cursor.walk_dup(Some(key2), None).unwrap().collect::<Result<Vec<_>, _>>(),
+        Ok(vec![(key2, entry_1)])
+    );
+}
+
+fn db_cursor_walk_range<DB: Database>(db: DB) {
+    // PUT (0, 0), (1, 0), (2, 0), (3, 0)
+    let tx = db.tx_mut().expect(ERROR_INIT_TX);
+    vec![0, 1, 2, 3]
+        .into_iter()
+        .try_for_each(|key| tx.put::<CanonicalHeaders>(key, B256::ZERO))
+        .expect(ERROR_PUT);
+    tx.commit().expect(ERROR_COMMIT);
+
+    let tx = db.tx().expect(ERROR_INIT_TX);
+    let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+
+    // [1, 3)
+    let mut walker = cursor.walk_range(1..3).unwrap();
+    assert_eq!(walker.next(), Some(Ok((1, B256::ZERO))));
+    assert_eq!(walker.next(), Some(Ok((2, B256::ZERO))));
+    assert_eq!(walker.next(), None);
+    // next() returns None after walker is done
+    assert_eq!(walker.next(), None);
+
+    // [1, ∞)
+    let mut walker = cursor.walk_range(1..).unwrap();
+    assert_eq!(walker.next(), Some(Ok((1, B256::ZERO))));
+    assert_eq!(walker.next(), Some(Ok((2, B256::ZERO))));
+    assert_eq!(walker.next(), Some(Ok((3, B256::ZERO))));
+    // next() returns None after walker is done
+    assert_eq!(walker.next(), None);
+
+    // (∞, 3)
+    let mut walker = cursor.walk_range(..3).unwrap();
+    assert_eq!(walker.next(), Some(Ok((0, B256::ZERO))));
+    assert_eq!(walker.next(), Some(Ok((1, B256::ZERO))));
+    assert_eq!(walker.next(), Some(Ok((2, B256::ZERO))));
+    // next() returns None after walker is done
+    assert_eq!(walker.next(), None);
+}
+
+fn db_cursor_walk_range_on_dup_table<DB: Database>(db: DB) {
+    let address0 = Address::ZERO;
+    let address1 = Address::with_last_byte(1);
+    let address2 = Address::with_last_byte(2);
+
+    let tx = db.tx_mut().expect(ERROR_INIT_TX);
+    for block in 0..3 {
+        for address in [address0, address1, address2] {
+            tx.put::<AccountChangeSets>(block, AccountBeforeTx { address, info: None })
+                .expect(ERROR_PUT);
+        }
+    }
+    tx.commit().expect(ERROR_COMMIT);
+
+    let tx = db.tx().expect(ERROR_INIT_TX);
+    let mut cursor = tx.cursor_read::<AccountChangeSets>().unwrap();
+
+    let entries = cursor.walk_range(..).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(entries.len(), 9);
+
+    let mut walker = cursor.walk_range(1..=1).unwrap();
+    assert_eq!(walker.next(), Some(Ok((1, AccountBeforeTx { address: address0, info: None }))));
+    assert_eq!(walker.next(), Some(Ok((1, AccountBeforeTx { address: address1, info: None }))));
+    assert_eq!(walker.next(), Some(Ok((1, AccountBeforeTx { address: address2, info: None }))));
+    assert_eq!(walker.next(), None);
+}
+
+fn db_walker<DB: Database>(db: DB) {
+    // PUT (0, 0), (1, 0), (3, 0)
+    let tx = db.tx_mut().expect(ERROR_INIT_TX);
+    vec![0, 1, 3]
+        .into_iter()
+        .try_for_each(|key| tx.put::<CanonicalHeaders>(key, B256::ZERO))
+        .expect(ERROR_PUT);
+    tx.commit().expect(ERROR_COMMIT);
+
+    let tx = db.tx().expect(ERROR_INIT_TX);
+    let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+
+    let mut walker = Walker::new(&mut cursor, None);
+
+    assert_eq!(walker.next(), Some(Ok((0, B256::ZERO))));
+    assert_eq!(walker.next(), Some(Ok((1, B256::ZERO))));
+    assert_eq!(walker.next(), Some(Ok((3, B256::ZERO))));
+    assert_eq!(walker.next(), None);
+
+    // transform to ReverseWalker
+    let mut reverse_walker = walker.rev();
+    assert_eq!(reverse_walker.next(), Some(Ok((3, B256::ZERO))));
+    assert_eq!(reverse_walker.next(), Some(Ok((1, B256::ZERO))));
+    assert_eq!(reverse_walker.next(), Some(Ok((0, B256::ZERO))));
+    assert_eq!(reverse_walker.next(), None);
+
+    // transform back to Walker
+    let mut walker = reverse_walker.forward();
+    assert_eq!(walker.next(), Some(Ok((0, B256::ZERO))));
+    assert_eq!(walker.next(), Some(Ok((1, B256::ZERO))));
+    assert_eq!(walker.next(), Some(Ok((3, B256::ZERO))));
+    assert_eq!(walker.next(), None);
+}
+
+fn db_walk_back<DB: Database>(db: DB) {
+    // PUT (0, 0), (1, 0), (3, 0)
+    let tx = db.tx_mut().expect(ERROR_INIT_TX);
+    vec![0, 1, 3]
+        .into_iter()
+        .try_for_each(|key| tx.put::<CanonicalHeaders>(key, B256::ZERO))
+        .expect(ERROR_PUT);
+    tx.commit().expect(ERROR_COMMIT);
+
+    let tx = db.tx().expect(ERROR_INIT_TX);
+    let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+
+    let mut reverse_walker = cursor.walk_back(Some(1)).unwrap();
+    assert_eq!(reverse_walker.next(), Some(Ok((1, B256::ZERO))));
+    assert_eq!(reverse_walker.next(), Some(Ok((0, B256::ZERO))));
+    assert_eq!(reverse_walker.next(), None);
+
+    let mut reverse_walker = cursor.walk_back(Some(4)).unwrap();
+    assert_eq!(reverse_walker.next(), Some(Ok((3, B256::ZERO))));
+    assert_eq!(reverse_walker.next(), Some(Ok((1, B256::ZERO))));
+    assert_eq!(reverse_walker.next(), Some(Ok((0, B256::ZERO))));
+    assert_eq!(reverse_walker.next(), None);
+
+    let mut reverse_walker = ReverseWalker::new(&mut cursor, None);
+    assert_eq!(reverse_walker.next(), Some(Ok((3, B256::ZERO))));
+    assert_eq!(reverse_walker.next(), Some(Ok((1, B256::ZERO))));
+    assert_eq!(reverse_walker.next(), Some(Ok((0, B256::ZERO))));
+    assert_eq!(reverse_walker.next(), None);
+}
+
+fn db_cursor_seek_exact_or_previous_key<DB: Database>(db: DB) {
+    // PUT
+    let tx = db.tx_mut().expect(ERROR_INIT_TX);
+    vec![0, 1, 3]
+        .into_iter()
+        .try_for_each(|key| tx.put::<CanonicalHeaders>(key, B256::ZERO))
+        .expect(ERROR_PUT);
+    tx.commit().expect(ERROR_COMMIT);
+
+    // Cursor
+    let missing_key = 2;
+    let tx = db.tx().expect(ERROR_INIT_TX);
+    let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+    assert_eq!(cursor.current(), Ok(None));
+
+    // Seek exact
+    let exact = cursor.seek_exact(missing_key).unwrap();
+    assert_eq!(exact, None);
+    assert_eq!(cursor.current(), Ok(Some((missing_key + 1, B256::ZERO))));
+    assert_eq!(cursor.prev(), Ok(Some((missing_key - 1, B256::ZERO))));
+    assert_eq!(cursor.prev(), Ok(Some((missing_key - 2, B256::ZERO))));
+}
+
+fn db_cursor_insert<DB: Database>(db: DB) {
+    // PUT
+    let tx = db.tx_mut().expect(ERROR_INIT_TX);
+    vec![0, 1, 3, 4, 5]
+        .into_iter()
+        .try_for_each(|key| tx.put::<CanonicalHeaders>(key, B256::ZERO))
+        .expect(ERROR_PUT);
+    tx.commit().expect(ERROR_COMMIT);
+
+    let key_to_insert = 2;
+    let tx = db.tx_mut().expect(ERROR_INIT_TX);
+    let mut cursor = tx.cursor_write::<CanonicalHeaders>().unwrap();
+
+    // INSERT
+    assert_eq!(cursor.insert(key_to_insert, B256::ZERO), Ok(()));
+    assert_eq!(cursor.current(), Ok(Some((key_to_insert, B256::ZERO))));
+
+    // INSERT (failure)
+    assert_write_error::<CanonicalHeaders>(
+        cursor.insert(key_to_insert, B256::ZERO),
+        DatabaseWriteOperation::CursorInsert,
+        key_to_insert,
+    );
+    assert_eq!(cursor.current(), Ok(Some((key_to_insert, B256::ZERO))));
+
+    tx.commit().expect(ERROR_COMMIT);
+
+    // Confirm the result
+    let tx = db.tx().expect(ERROR_INIT_TX);
+    let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+    let res = cursor.walk(None).unwrap().map(|res| res.unwrap().0).collect::<Vec<_>>();
+    assert_eq!(res, vec![0, 1, 2, 3, 4, 5]);
+    tx.commit().expect(ERROR_COMMIT);
+}
+
+fn db_cursor_insert_dup<DB: Database>(db: DB) {
+    let tx = db.tx_mut().expect(ERROR_INIT_TX);
+
+    let mut dup_cursor = tx.cursor_dup_write::<PlainStorageState>().unwrap();
+    let key = Address::random();
+    let subkey1 = B256::random();
+    let subkey2 = B256::random();
+
+    let entry1 = StorageEntry { key: subkey1, value: U256::ZERO };
+    assert!(dup_cursor.insert(key, entry1).is_ok());
+
+    // Can't insert
+    let entry2 = StorageEntry { key: subkey2, value: U256::ZERO };
+    assert!(dup_cursor.insert(key, entry2).is_err());
+}
+
+fn db_cursor_delete_current_non_existent<DB: Database>(db: DB) {
+    let tx = db.tx_mut().expect(ERROR_INIT_TX);
+
+    let key1 = Address::with_last_byte(1);
+    let key2 = Address::with_last_byte(2);
+    let key3 = Address::with_last_byte(3);
+    let mut cursor = tx.cursor_write::<PlainAccountState>().unwrap();
+
+    assert!(cursor.insert(key1, Account::default()).is_ok());
+    assert!(cursor.insert(key2, Account::default()).is_ok());
+    assert!(cursor.insert(key3, Account::default()).is_ok());
+
+    // Seek & delete key2
+    cursor.seek_exact(key2).unwrap();
+    assert_eq!(cursor.delete_current(), Ok(()));
+    assert_eq!(cursor.seek_exact(key2), Ok(None));
+
+    // Seek & delete key2 again
+    assert_eq!(cursor.seek_exact(key2), Ok(None));
+    assert_eq!(cursor.delete_current(), Ok(()));
+    // Assert that key1 is still there
+    assert_eq!(cursor.seek_exact(key1), Ok(Some((key1, Account::default()))));
+    // Assert that key3 was deleted
+    assert_eq!(cursor.seek_exact(key3), Ok(None));
+}
+
+fn db_cursor_append_failure<DB: Database>(db: DB) {
+    // PUT
+    let tx = db.tx_mut().expect(ERROR_INIT_TX);
+    vec![0, 1, 3, 4, 5]
+        .into_iter()
+        .try_for_each(|key| tx.put::<CanonicalHeaders>(key, B256::ZERO))
+        .expect(ERROR_PUT);
+    tx.commit().expect(ERROR_COMMIT);
+
+    // APPEND
+    let key_to_append = 2;
+    let tx = db.tx_mut().expect(ERROR_INIT_TX);
+    let mut cursor = tx.cursor_write::<CanonicalHeaders>().unwrap();
+    assert_write_error::<CanonicalHeaders>(
+        cursor.append(key_to_append, B256::ZERO),
+        DatabaseWriteOperation::CursorAppend,
+        key_to_append,
+    );
+    assert_eq!(cursor.current(), Ok(Some((5, B256::ZERO)))); // the end of table
+    assert_eq!(cursor.append(6, B256::ZERO), Ok(()));
+    tx.commit().expect(ERROR_COMMIT);
+
+    // Confirm the result
+    let tx = db.tx().expect(ERROR_INIT_TX);
+    let mut cursor = tx.cursor_read::<CanonicalHeaders>().unwrap();
+    let res = cursor.walk(None).unwrap().map(|res| res.unwrap().0).collect::<Vec<_>>();
+    assert_eq!(res, vec![0, 1, 3, 4, 5, 6]);
+    tx.commit().expect(ERROR_COMMIT);
+}
+
+fn db_cursor_upsert<DB: Database>(db: DB) {
+    let tx = db.tx_mut().expect(ERROR_INIT_TX);
+
+    let mut cursor = tx.cursor_write::<PlainAccountState>().unwrap();
+    let key = Address::random();
+
+    let account = Account::default();
+    cursor.upsert(key, account).expect(ERROR_UPSERT);
+    assert_eq!(cursor.seek_exact(key), Ok(Some((key, account))));
+
+    let account = Account { nonce: 1, ..Default::default() };
+    cursor.upsert(key, account).expect(ERROR_UPSERT);
+    assert_eq!(cursor.seek_exact(key), Ok(Some((key, account))));
+
+    let mut dup_cursor = tx.cursor_dup_write::<PlainStorageState>().unwrap();
+    let subkey = B256::random();
+
+    let value = U256::from(1);
+    let entry1 = StorageEntry { key: subkey, value };
+    dup_cursor.upsert(key, entry1).expect(ERROR_UPSERT);
+    assert_eq!(dup_cursor.seek_by_key_subkey(key, subkey), Ok(Some(entry1)));
+
+    let value = U256::from(2);
+    let entry2 = StorageEntry { key: subkey, value };
+    dup_cursor.upsert(key, entry2).expect(ERROR_UPSERT);
+    assert_eq!(dup_cursor.seek_by_key_subkey(key, subkey), Ok(Some(entry1)));
+    assert_eq!(dup_cursor.next_dup_val(), Ok(Some(entry2)));
+}
+
+fn db_cursor_dupsort_append<DB: Database>(db: DB) {
+    let transition_id = 2;
+
+    let tx = db.tx_mut().expect(ERROR_INIT_TX);
+    let mut cursor = tx.cursor_write::<AccountChangeSets>().unwrap();
+    vec![0, 1, 3, 4, 5]
+        .into_iter()
+        .try_for_each(|val| {
+            cursor.append(
+                transition_id,
+                AccountBeforeTx { address: Address::with_last_byte(val), info: None },
+            )
+        })
+        .expect(ERROR_APPEND);
+    tx.commit().expect(ERROR_COMMIT);
+
+    // APPEND DUP & APPEND
+    let subkey_to_append = 2;
+    let tx = db.tx_mut().expect(ERROR_INIT_TX);
+    let mut cursor = tx.cursor_write::<AccountChangeSets>().unwrap();
+    assert_write_error::<AccountChangeSets>(
+        cursor.append_dup(
+            transition_id,
+            AccountBeforeTx { address: Address::with_last_byte(subkey_to_append), info: None },
+        ),
+        DatabaseWriteOperation::CursorAppendDup,
+        transition_id,
+    );
+    assert_write_error::<AccountChangeSets>(
+        cursor.append(
+            transition_id - 1,
+            AccountBeforeTx { address: Address::with_last_byte(subkey_to_append), info: None },
+        ),
+        DatabaseWriteOperation::CursorAppend,
+        transition_id - 1,
+    );
+    assert_eq!(
+        cursor.append(
+            transition_id,
+            AccountBeforeTx { address: Address::with_last_byte(subkey_to_append), info: None }
+        ),
+        Ok(())
+    );
+}
+
+fn dup_value_with_same_subkey<DB: Database>(db: DB) {
+    let key1 = Address::new([0x11; 20]);
+    let key2 = Address::new([0x22; 20]);
+
+    // PUT key1 (0,1)
+    let value01 = StorageEntry { key: B256::with_last_byte(0), value: U256::from(1) };
+    db.update(|tx| tx.put::<PlainStorageState>(key1, value01).expect(ERROR_PUT)).unwrap();
+
+    // PUT key1 (0,0)
+    let value00 = StorageEntry::default();
+    db.update(|tx| tx.put::<PlainStorageState>(key1, value00).expect(ERROR_PUT)).unwrap();
+
+    // PUT key2 (2,2)
+    let value22 = StorageEntry { key: B256::with_last_byte(2), value: U256::from(2) };
+    db.update(|tx| tx.put::<PlainStorageState>(key2, value22).expect(ERROR_PUT)).unwrap();
+
+    // Iterate with walk
+    {
+        let tx = db.tx().expect(ERROR_INIT_TX);
+        let mut cursor = tx.cursor_dup_read::<PlainStorageState>().unwrap();
+        let first = cursor.first().unwrap().unwrap();
+        let mut walker = cursor.walk(Some(first.0)).unwrap();
+
+        // NOTE: Both values are present
+        assert_eq!(Some(Ok((key1, value00))), walker.next());
+        assert_eq!(Some(Ok((key1, value01))), walker.next());
+        assert_eq!(Some(Ok((key2, value22))), walker.next());
+    }
+
+    // seek_by_key_subkey
+    {
+        let tx = db.tx().expect(ERROR_INIT_TX);
+        let mut cursor = tx.cursor_dup_read::<PlainStorageState>().unwrap();
+
+        // NOTE: There are two values with same SubKey but only first one is shown
+        assert_eq!(Ok(Some(value00)), cursor.seek_by_key_subkey(key1, value00.key));
+        // key1 but value is greater than the one in the DB
+        assert_eq!(Ok(None), cursor.seek_by_key_subkey(key1, value22.key));
+    }
+}
+
+fn db_sharded_key<DB: Database>(db: DB) {
+    let real_key = Address::with_last_byte(1);
+
+    for i in 1..5 {
+        let key = ShardedKey::new(real_key, i * 100);
+        let list = IntegerList::new_pre_sorted([i * 100u64]);
+
+        db.update(|tx| tx.put::<AccountsHistory>(key.clone(), list.clone()).expect("")).unwrap();
+    }
+
+    let tx = db.tx().expect(ERROR_INIT_TX);
+    let mut cursor = tx.cursor_read::<AccountsHistory>().unwrap();
+
+    // Seek value with non existing key.
+    let mut walker = cursor.walk(Some(ShardedKey::new(real_key, 150))).unwrap();
+    let (key, _) = walker.next().unwrap().unwrap();
+    assert_eq!(ShardedKey::new(real_key, 200), key);
+
+    // Seek greatest index, the cursor is past the end of the table.
+    let _unknown = cursor.seek_exact(ShardedKey::new(real_key, u64::MAX)).unwrap();
+    let (key, _) = cursor.prev().unwrap().unwrap();
+    assert_eq!(ShardedKey::new(real_key, 400), key);
+}
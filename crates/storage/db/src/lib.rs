@@ -21,16 +21,18 @@ pub mod lockfile;
 mod metrics;
 pub mod static_file;
 pub mod tables;
-#[cfg(feature = "mdbx")]
+#[cfg(any(feature = "mdbx", feature = "redb"))]
 mod utils;
 pub mod version;
 
 #[cfg(feature = "mdbx")]
 pub mod mdbx;
+#[cfg(feature = "redb")]
+pub mod redb;
 
 pub use reth_storage_errors::db::{DatabaseError, DatabaseWriteOperation};
 pub use tables::*;
-#[cfg(feature = "mdbx")]
+#[cfg(any(feature = "mdbx", feature = "redb"))]
 pub use utils::is_database_empty;
 
 #[cfg(feature = "mdbx")]
@@ -201,6 +203,21 @@ pub mod test_utils {
         Arc::new(TempDatabase::new(db, path))
     }
 
+    /// Create read/write redb database for testing
+    #[cfg(feature = "redb")]
+    pub fn create_test_redb_rw_db() -> Arc<TempDatabase<crate::redb::RedbDatabase>> {
+        let path = tempdir_path();
+        let emsg = format!("{ERROR_DB_CREATION}: {path:?}");
+
+        let db = crate::redb::init_db(
+            &path,
+            crate::redb::DatabaseArguments::new(ClientVersion::default()),
+        )
+        .expect(&emsg);
+
+        Arc::new(TempDatabase::new(db, path))
+    }
+
     /// Create read only database for testing
     pub fn create_test_ro_db() -> Arc<TempDatabase<DatabaseEnv>> {
         let args = DatabaseArguments::new(ClientVersion::default())
@@ -12,7 +12,7 @@ use std::{
 use sysinfo::{ProcessRefreshKind, RefreshKind, System};
 
 /// File lock name.
-const LOCKFILE_NAME: &str = "lock";
+pub const LOCKFILE_NAME: &str = "lock";
 
 /// A file lock for a storage directory to ensure exclusive read-write access across different
 /// processes.
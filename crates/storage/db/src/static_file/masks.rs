@@ -1,11 +1,19 @@
-use super::{ReceiptMask, TransactionMask};
+use super::{
+    AccountChangeSetsMask, BlobSidecarsMask, ReceiptMask, StorageChangeSetsMask, TransactionMask,
+};
 use crate::{
     add_static_file_mask,
     static_file::mask::{ColumnSelectorOne, ColumnSelectorTwo, HeaderMask},
     HeaderTerminalDifficulties, RawValue, Receipts, Transactions,
 };
 use alloy_primitives::BlockHash;
-use reth_db_api::table::Table;
+use reth_db_api::{
+    models::{
+        StoredBlockAccountChangeSet, StoredBlockBlobSidecars, StoredBlockBlobVersionedHashes,
+        StoredBlockStorageChangeSet,
+    },
+    table::Table,
+};
 use reth_primitives::Header;
 
 // HEADER MASKS
@@ -21,3 +29,11 @@ add_static_file_mask!(ReceiptMask, <Receipts as Table>::Value, 0b1);
 // TRANSACTION MASKS
 add_static_file_mask!(TransactionMask, <Transactions as Table>::Value, 0b1);
 add_static_file_mask!(TransactionMask, RawValue<<Transactions as Table>::Value>, 0b1);
+
+// BLOB SIDECAR MASKS
+add_static_file_mask!(BlobSidecarsMask, StoredBlockBlobSidecars, 0b01);
+add_static_file_mask!(BlobSidecarsMask, StoredBlockBlobVersionedHashes, 0b10);
+
+// CHANGESET MASKS
+add_static_file_mask!(AccountChangeSetsMask, StoredBlockAccountChangeSet, 0b1);
+add_static_file_mask!(StorageChangeSetsMask, StoredBlockStorageChangeSet, 0b1);
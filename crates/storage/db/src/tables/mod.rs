@@ -16,7 +16,7 @@ pub mod codecs;
 mod raw;
 pub use raw::{RawDupSort, RawKey, RawTable, RawValue, TableRawRow};
 
-#[cfg(feature = "mdbx")]
+#[cfg(any(feature = "mdbx", feature = "redb"))]
 pub(crate) mod utils;
 
 use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256};
@@ -129,6 +129,7 @@ macro_rules! tables {
 
             impl reth_db_api::table::Table for $name {
                 const NAME: &'static str = table_names::$name;
+                const DUPSORT: bool = tables!(@bool $($subkey)?);
 
                 type Key = $key;
                 type Value = $value;
@@ -357,6 +358,15 @@ tables! {
     /// Code example can be found in `reth_provider::HistoricalStateProviderRef`
     table StoragesHistory<Key = StorageShardedKey, Value = BlockNumberList>;
 
+    /// Stores pointers to the transactions an address took part in, either as the sender, the
+    /// recipient or as a participant of one of the internal calls of the transaction.
+    ///
+    /// Sharded the same way as [`AccountsHistory`], except that the lists contain transaction
+    /// numbers instead of block numbers. The last shard of an address is keyed with `u64::MAX`.
+    ///
+    /// This table is only populated if the optional `IndexAddressTransactionsStage` is enabled.
+    table AddressTransactions<Key = ShardedKey<Address>, Value = BlockNumberList>;
+
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
     /// or changed balance,nonce.
@@ -12,6 +12,9 @@ pub use zstd::{bulk::Decompressor, dict::DecoderDictionary};
 
 type RawDictionary = Vec<u8>;
 
+/// Minimum size in bytes of the sample data of a column to train a dictionary on.
+const MIN_TRAINING_DATA_SIZE: usize = 1024;
+
 /// Represents the state of a Zstandard compression operation.
 #[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ZstdState {
@@ -71,7 +74,7 @@ impl Zstd {
     }
 
     /// If using dictionaries, creates a list of [`Compressor`].
-    pub fn compressors(&self) -> Result<Option<Vec<Compressor<'_>>>, NippyJarError> {
+    pub fn compressors(&self) -> Result<Option<Vec<Compressor<'static>>>, NippyJarError> {
         match self.state {
             ZstdState::PendingDictionary => Err(NippyJarError::CompressorNotReady),
             ZstdState::Ready => {
@@ -81,7 +84,7 @@ impl Zstd {
 
                 if let Some(dictionaries) = &self.dictionaries {
                     debug!(target: "nippy-jar", count=?dictionaries.len(), "Generating ZSTD compressor dictionaries.");
-                    return Ok(Some(dictionaries.compressors()?))
+                    return Ok(Some(dictionaries.compressors(self.level)?))
                 }
                 Ok(None)
             }
@@ -189,7 +192,6 @@ impl Compression for Zstd {
         matches!(self.state, ZstdState::Ready)
     }
 
-    #[cfg(test)]
     /// If using it with dictionaries, prepares a dictionary for each column.
     fn prepare_compression(
         &mut self,
@@ -214,7 +216,7 @@ impl Compression for Zstd {
         }
 
         let mut dictionaries = Vec::with_capacity(columns.len());
-        for column in columns {
+        for (index, column) in columns.into_iter().enumerate() {
             // ZSTD requires all training data to be continuous in memory, alongside the size of
             // each entry
             let mut sizes = vec![];
@@ -226,6 +228,14 @@ impl Compression for Zstd {
                 })
                 .collect();
 
+            // zstd's trainer can crash instead of failing on a handful of bytes.
+            if data.len() < MIN_TRAINING_DATA_SIZE {
+                return Err(NippyJarError::NotEnoughTrainingData {
+                    column: index,
+                    size: data.len(),
+                })
+            }
+
             dictionaries.push(zstd::dict::from_continuous(&data, &sizes, self.max_dict_size)?);
         }
 
@@ -277,7 +287,6 @@ impl std::fmt::Debug for ZstdDictionaries<'_> {
 }
 
 impl ZstdDictionaries<'_> {
-    #[cfg(test)]
     /// Creates [`ZstdDictionaries`].
     pub(crate) fn new(raw: Vec<RawDictionary>) -> Self {
         Self(raw.into_iter().map(ZstdDictionary::Raw).collect())
@@ -285,11 +294,7 @@ impl ZstdDictionaries<'_> {
 
     /// Loads a list [`RawDictionary`] into a list of [`ZstdDictionary::Loaded`].
     pub(crate) fn load(raw: Vec<RawDictionary>) -> Self {
-        Self(
-            raw.into_iter()
-                .map(|dict| ZstdDictionary::Loaded(DecoderDictionary::copy(&dict)))
-                .collect(),
-        )
+        Self(raw.into_iter().map(ZstdDictionary::load).collect())
     }
 
     /// Creates a list of decompressors from a list of [`ZstdDictionary::Loaded`].
@@ -304,33 +309,67 @@ impl ZstdDictionaries<'_> {
             .collect::<Result<Vec<_>, _>>()?)
     }
 
-    /// Creates a list of compressors from a list of [`ZstdDictionary::Raw`].
-    pub(crate) fn compressors(&self) -> Result<Vec<Compressor<'_>>, NippyJarError> {
+    /// Creates a list of compressors from a list of [`ZstdDictionary`].
+    pub(crate) fn compressors(
+        &self,
+        level: i32,
+    ) -> Result<Vec<Compressor<'static>>, NippyJarError> {
         Ok(self
             .iter()
-            .flat_map(|dict| {
-                dict.raw()
-                    .ok_or(NippyJarError::CompressorNotAllowed)
-                    .map(|dict| Compressor::with_dictionary(0, dict))
-            })
+            .map(|dict| Compressor::with_dictionary(level, dict.raw()))
             .collect::<Result<Vec<_>, _>>()?)
     }
 }
 
-/// A Zstd dictionary. It's created and serialized with [`ZstdDictionary::Raw`], and deserialized as
-/// [`ZstdDictionary::Loaded`].
+/// List of per-column [`Compressor`]s, created from freshly trained dictionaries.
+pub(crate) struct ZstdCompressors(Vec<Compressor<'static>>);
+
+impl std::fmt::Debug for ZstdCompressors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZstdCompressors").field("num", &self.0.len()).finish_non_exhaustive()
+    }
+}
+
+impl ZstdCompressors {
+    /// Creates [`ZstdCompressors`] if `zstd` uses dictionaries.
+    pub(crate) fn new(zstd: &Zstd) -> Option<Self> {
+        zstd.compressors().ok().flatten().map(Self)
+    }
+
+    /// Appends the compressed `src` value of `column` to `dest`.
+    ///
+    /// Returns number of bytes written to `dest`.
+    pub(crate) fn compress_to(
+        &mut self,
+        column: usize,
+        src: &[u8],
+        dest: &mut Vec<u8>,
+    ) -> Result<usize, NippyJarError> {
+        let compressed = self.0[column].compress(src)?;
+        dest.extend_from_slice(&compressed);
+        Ok(compressed.len())
+    }
+}
+
+/// A Zstd dictionary. It's created with [`ZstdDictionary::Raw`], and deserialized as
+/// [`ZstdDictionary::Loaded`], which keeps the raw dictionary so that a jar loaded from disk can
+/// still compress new rows and write its configuration back.
 pub(crate) enum ZstdDictionary<'a> {
-    #[allow(dead_code)]
     Raw(RawDictionary),
-    Loaded(DecoderDictionary<'a>),
+    Loaded(RawDictionary, DecoderDictionary<'a>),
 }
 
 impl ZstdDictionary<'_> {
-    /// Returns a reference to the expected `RawDictionary`
-    pub(crate) const fn raw(&self) -> Option<&RawDictionary> {
+    /// Prepares the raw dictionary for decompression.
+    fn load(raw: RawDictionary) -> Self {
+        let dict = DecoderDictionary::copy(&raw);
+        Self::Loaded(raw, dict)
+    }
+
+    /// Returns a reference to the `RawDictionary`
+    pub(crate) const fn raw(&self) -> &RawDictionary {
         match self {
-            ZstdDictionary::Raw(dict) => Some(dict),
-            ZstdDictionary::Loaded(_) => None,
+            ZstdDictionary::Raw(dict) | ZstdDictionary::Loaded(dict, _) => dict,
         }
     }
 
@@ -338,7 +377,7 @@ impl ZstdDictionary<'_> {
     pub(crate) const fn loaded(&self) -> Option<&DecoderDictionary<'_>> {
         match self {
             ZstdDictionary::Raw(_) => None,
-            ZstdDictionary::Loaded(dict) => Some(dict),
+            ZstdDictionary::Loaded(_, dict) => Some(dict),
         }
     }
 }
@@ -348,8 +387,7 @@ impl<'de> Deserialize<'de> for ZstdDictionary<'_> {
     where
         D: Deserializer<'de>,
     {
-        let dict = RawDictionary::deserialize(deserializer)?;
-        Ok(Self::Loaded(DecoderDictionary::copy(&dict)))
+        Ok(Self::load(RawDictionary::deserialize(deserializer)?))
     }
 }
 
@@ -358,19 +396,13 @@ impl Serialize for ZstdDictionary<'_> {
     where
         S: Serializer,
     {
-        match self {
-            ZstdDictionary::Raw(r) => r.serialize(serializer),
-            ZstdDictionary::Loaded(_) => unreachable!(),
-        }
+        self.raw().serialize(serializer)
     }
 }
 
 #[cfg(test)]
 impl PartialEq for ZstdDictionary<'_> {
     fn eq(&self, other: &Self) -> bool {
-        if let (Self::Raw(a), Self::Raw(b)) = (self, &other) {
-            return a == b
-        }
-        unimplemented!("`DecoderDictionary` can't be compared. So comparison should be done after decompressing a value.");
+        self.raw() == other.raw()
     }
 }
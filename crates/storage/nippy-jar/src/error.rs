@@ -40,6 +40,14 @@ pub enum NippyJarError {
     #[error("number of columns does not match: {0} != {1}")]
     ColumnLenMismatch(usize, usize),
 
+    /// The number of rows does not match the expected length.
+    #[error("number of rows does not match: {0} != {1}")]
+    RowLenMismatch(usize, usize),
+
+    /// The values of a row do not match the expected ones.
+    #[error("values of row {0} do not match")]
+    RowMismatch(usize),
+
     /// An unexpected missing value was encountered at a specific row and column.
     #[error("unexpected missing value: row:col {0}:{1}")]
     UnexpectedMissingValue(u64, u64),
@@ -77,6 +85,15 @@ pub enum NippyJarError {
     #[error("it's not possible to generate a compressor after loading a dictionary.")]
     CompressorNotAllowed,
 
+    /// Not enough sample data to train a dictionary for a column.
+    #[error("not enough data to train a dictionary for column {column}: {size} bytes")]
+    NotEnoughTrainingData {
+        /// The column index.
+        column: usize,
+        /// Total size of the column samples in bytes.
+        size: usize,
+    },
+
     /// The number of offsets is smaller than the requested prune size.
     #[error("number of offsets ({0}) is smaller than prune request ({1}).")]
     InvalidPruning(u64, u64),
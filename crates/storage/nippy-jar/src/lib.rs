@@ -29,9 +29,7 @@ use tracing::*;
 
 /// Compression algorithms supported by `NippyJar`.
 pub mod compression;
-#[cfg(test)]
-use compression::Compression;
-use compression::Compressors;
+use compression::{Compression, Compressors};
 
 /// empty enum for backwards compatibility
 #[derive(Debug, Serialize, Deserialize)]
@@ -63,6 +61,11 @@ const INDEX_FILE_EXTENSION: &str = "idx";
 const OFFSETS_FILE_EXTENSION: &str = "off";
 /// The file extension used for configuration files.
 pub const CONFIG_FILE_EXTENSION: &str = "conf";
+/// The file extension used for marker files of in-progress jar replacements.
+const REPLACE_MARKER_EXTENSION: &str = "replace";
+/// Amount of sample data per column used to train a zstd dictionary, as a multiple of the maximum
+/// dictionary size.
+const DICTIONARY_SAMPLE_RATIO: usize = 100;
 
 /// A [`RefRow`] is a list of column value slices pointing to either an internal buffer or a
 /// memory-mapped file.
@@ -172,6 +175,12 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self
     }
 
+    /// Sets the compression algorithm, replacing any previously configured one.
+    pub fn with_compressor(mut self, compressor: Option<Compressors>) -> Self {
+        self.compressor = compressor;
+        self
+    }
+
     /// Gets a reference to the user header.
     pub const fn user_header(&self) -> &H {
         &self.user_header
@@ -246,6 +255,21 @@ impl<H: NippyJarHeader> NippyJar<H> {
         Ok(())
     }
 
+    /// Replaces the files of this jar with the files of `new_jar`, e.g. a jar returned by
+    /// [`NippyJar::recompress`].
+    ///
+    /// A marker file pointing to `new_jar` is written next to this jar before any file is moved
+    /// and removed once all of them are. If the process stops in between, this jar is left with a
+    /// mix of old and new files until [`recover_replacements`] completes the replacement.
+    pub fn replace_with(&self, new_jar: &Self) -> Result<(), NippyJarError> {
+        let marker = self.data_path().with_extension(REPLACE_MARKER_EXTENSION);
+        reth_fs_util::atomic_write_file(&marker, |file| {
+            bincode::serialize_into(file, new_jar.data_path())
+        })?;
+
+        complete_replacement(&marker, new_jar.data_path(), self.data_path())
+    }
+
     /// Returns a [`DataReader`] of the data and offset file
     pub fn open_data_reader(&self) -> Result<DataReader, NippyJarError> {
         DataReader::new(self.data_path())
@@ -259,7 +283,6 @@ impl<H: NippyJarHeader> NippyJar<H> {
     }
 }
 
-#[cfg(test)]
 impl<H: NippyJarHeader> NippyJar<H> {
     /// If required, prepares any compression algorithm to an early pass of the data.
     pub fn prepare_compression(
@@ -273,7 +296,93 @@ impl<H: NippyJarHeader> NippyJar<H> {
         }
         Ok(())
     }
+}
+
+impl<H: NippyJarHeader + Clone> NippyJar<H> {
+    /// Re-encodes all rows of this jar into a new jar at `path`, using `compressor`.
+    ///
+    /// If `compressor` is zstd with dictionaries, a dictionary is trained per column on a sample of
+    /// its values first. Row count and offsets of the new jar are checked by [`NippyJarChecker`],
+    /// and every value of the new jar is decompressed and compared to the original before it's
+    /// returned.
+    pub fn recompress(
+        &self,
+        path: &Path,
+        compressor: Option<Compressors>,
+    ) -> Result<Self, NippyJarError> {
+        if path.exists() {
+            return Err(NippyJarError::Custom(format!("{} already exists", path.display())))
+        }
+
+        let mut jar =
+            Self::new(self.columns, path, self.user_header.clone()).with_compressor(compressor);
+
+        if let Some(Compressors::Zstd(zstd)) = &jar.compressor {
+            if !zstd.is_ready() {
+                let samples = self
+                    .column_samples(zstd.max_dict_size.saturating_mul(DICTIONARY_SAMPLE_RATIO))?;
+                jar.prepare_compression(samples)?;
+            }
+        }
+
+        debug!(target: "nippy-jar", from=?self.data_path(), to=?path, "Recompressing jar.");
+
+        let mut writer = NippyJarWriter::new(jar)?;
+        let mut cursor = NippyJarCursor::new(self)?;
+        while let Some(row) = cursor.next_row()? {
+            for value in row {
+                writer.append_column(Some(Ok(value)))?;
+            }
+        }
+        writer.commit()?;
+
+        let mut checker = NippyJarChecker::new(writer.into_jar());
+        checker.check_consistency()?;
+        if checker.jar.rows != self.rows {
+            return Err(NippyJarError::RowLenMismatch(self.rows, checker.jar.rows))
+        }
+
+        // Values are read back from the jar as it was written to disk.
+        let jar = Self::load(path)?;
+        let mut cursor = NippyJarCursor::new(self)?;
+        let mut new_cursor = NippyJarCursor::new(&jar)?;
+        let mut row_index = 0;
+        while let Some(row) = cursor.next_row()? {
+            if new_cursor.next_row()?.as_ref() != Some(&row) {
+                return Err(NippyJarError::RowMismatch(row_index))
+            }
+            row_index += 1;
+        }
+        drop(new_cursor);
+
+        Ok(jar)
+    }
+
+    /// Returns up to `max_size` bytes of values per column, to be used as training data.
+    fn column_samples(&self, max_size: usize) -> Result<Vec<Vec<Vec<u8>>>, NippyJarError> {
+        let mut samples = vec![Vec::new(); self.columns];
+        let mut sizes = vec![0; self.columns];
+
+        let mut cursor = NippyJarCursor::new(self)?;
+        while let Some(row) = cursor.next_row()? {
+            if sizes.iter().all(|size| *size >= max_size) {
+                break
+            }
+
+            for (column, value) in row.into_iter().enumerate() {
+                if sizes[column] < max_size {
+                    sizes[column] += value.len();
+                    samples[column].push(value.to_vec());
+                }
+            }
+        }
+
+        Ok(samples)
+    }
+}
 
+#[cfg(test)]
+impl<H: NippyJarHeader> NippyJar<H> {
     /// Writes all data and configuration to a file and the offset index to another.
     pub fn freeze(
         self,
@@ -317,6 +426,67 @@ impl<H: NippyJarHeader> NippyJar<H> {
     }
 }
 
+/// Completes the replacements of jars in `dir` that were started by [`NippyJar::replace_with`]
+/// but interrupted, returning the data paths of the replaced jars.
+///
+/// Must not be called while any jar in `dir` is being replaced.
+pub fn recover_replacements(dir: &Path) -> Result<Vec<PathBuf>, NippyJarError> {
+    let mut replaced = Vec::new();
+    if !dir.exists() {
+        return Ok(replaced)
+    }
+
+    for entry in reth_fs_util::read_dir(dir)? {
+        let marker = entry?.path();
+        if marker.extension() != Some(REPLACE_MARKER_EXTENSION.as_ref()) {
+            continue
+        }
+
+        let source: PathBuf = bincode::deserialize(&reth_fs_util::read(&marker)?)?;
+        let target = marker.with_extension("");
+        warn!(target: "nippy-jar", ?source, ?target, "Completing interrupted replacement of jar.");
+
+        complete_replacement(&marker, &source, &target)?;
+        replaced.push(target);
+    }
+
+    Ok(replaced)
+}
+
+/// Moves the files of the jar at `source` over the files of the jar at `target` and removes the
+/// replacement `marker`. Files already moved by an earlier attempt are skipped.
+///
+/// All files of `source` must have been synced to disk before `marker` was written.
+fn complete_replacement(marker: &Path, source: &Path, target: &Path) -> Result<(), NippyJarError> {
+    for extension in [None, Some(OFFSETS_FILE_EXTENSION), Some(CONFIG_FILE_EXTENSION)] {
+        let (from, to) = match extension {
+            Some(extension) => (source.with_extension(extension), target.with_extension(extension)),
+            None => (source.to_path_buf(), target.to_path_buf()),
+        };
+        if from.exists() {
+            reth_fs_util::rename(from, to)?;
+        }
+    }
+
+    // The marker may only go away once the renames are durable.
+    if let Some(dir) = target.parent() {
+        sync_dir(dir)?;
+    }
+    reth_fs_util::remove_file(marker)?;
+
+    Ok(())
+}
+
+/// Flushes the entries of `dir` to disk.
+fn sync_dir(dir: &Path) -> Result<(), NippyJarError> {
+    let mut options = std::fs::OpenOptions::new();
+    options.read(true);
+    #[cfg(windows)]
+    options.write(true).custom_flags(0x02000000); // FILE_FLAG_BACKUP_SEMANTICS
+    options.open(dir)?.sync_all()?;
+    Ok(())
+}
+
 /// Manages the reading of static file data using memory-mapped files.
 ///
 /// Holds file and mmap descriptors of the data and offsets files of a `static_file`.
@@ -420,7 +590,6 @@ impl DataReader {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use compression::Compression;
     use rand::{rngs::SmallRng, seq::SliceRandom, RngCore, SeedableRng};
     use std::{fs::OpenOptions, io::Read};
 
@@ -499,6 +668,12 @@ mod tests {
                 zstd.prepare_compression(vec![col1.clone(), col2.clone(), col2.clone()]),
                 Err(NippyJarError::ColumnLenMismatch(columns, 3)) if columns == num_columns
             ));
+
+            // Too little data to train a dictionary on.
+            assert!(matches!(
+                zstd.prepare_compression(vec![vec![vec![1u8]; 10], col2.clone()]),
+                Err(NippyJarError::NotEnoughTrainingData { column: 0, size: 10 })
+            ));
         }
 
         // If ZSTD is enabled, do not write to the file unless the column dictionaries have been
@@ -628,6 +803,135 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_recompress() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let num_columns = 2;
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        let nippy = NippyJar::new_without_header(num_columns, file_path.path())
+            .with_lz4()
+            .freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows)
+            .unwrap();
+
+        // Don't overwrite an existing jar
+        assert!(matches!(nippy.recompress(file_path.path(), None), Err(NippyJarError::Custom(_))));
+
+        let compressor = Compressors::Zstd(compression::Zstd::new(true, 5000, num_columns));
+        let recompressed_path = dir.path().join("recompressed");
+        let recompressed = nippy.recompress(&recompressed_path, Some(compressor)).unwrap();
+        assert_eq!(recompressed.rows(), nippy.rows());
+        assert_eq!(recompressed.max_row_size, nippy.max_row_size);
+
+        let loaded_nippy = NippyJar::load_without_header(&recompressed_path).unwrap();
+        if let Some(Compressors::Zstd(zstd)) = loaded_nippy.compressor() {
+            assert!(zstd.use_dict);
+            assert_eq!(zstd.dictionaries.as_ref().map(|dict| dict.len()), Some(num_columns));
+
+            let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+
+            // Iterate over compressed values and compare
+            let mut row_index = 0usize;
+            while let Some(row) = cursor.next_row().unwrap() {
+                assert_eq!(
+                    (row[0], row[1]),
+                    (col1[row_index].as_slice(), col2[row_index].as_slice())
+                );
+                row_index += 1;
+            }
+            assert_eq!(row_index, col1.len());
+        } else {
+            panic!("Expected Zstd compressor")
+        }
+
+        // A recompressed jar can still be truncated and appended to
+        let mut writer =
+            NippyJarWriter::new(NippyJar::load_without_header(&recompressed_path).unwrap())
+                .unwrap();
+        writer.prune_rows(1).unwrap();
+        writer
+            .append_rows(
+                vec![
+                    clone_with_result(&col1[..1].to_vec()),
+                    clone_with_result(&col2[..1].to_vec()),
+                ],
+                1,
+            )
+            .unwrap();
+        writer.commit().unwrap();
+
+        let loaded_nippy = NippyJar::load_without_header(&recompressed_path).unwrap();
+        assert_eq!(loaded_nippy.rows(), col1.len());
+        let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+        let last_row = cursor.row_by_number(col1.len() - 1).unwrap().unwrap();
+        assert_eq!((last_row[0], last_row[1]), (col1[0].as_slice(), col2[0].as_slice()));
+        let row = cursor.row_by_number(col1.len() - 2).unwrap().unwrap();
+        assert_eq!(
+            (row[0], row[1]),
+            (col1[col1.len() - 2].as_slice(), col2[col2.len() - 2].as_slice())
+        );
+    }
+
+    #[test]
+    fn test_replace_with() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let num_columns = 2;
+        let dir = tempfile::tempdir().unwrap();
+        let tmp_dir = dir.path().join("tmp");
+        reth_fs_util::create_dir_all(&tmp_dir).unwrap();
+
+        let assert_rows = |jar: &NippyJar| {
+            let mut cursor = NippyJarCursor::new(jar).unwrap();
+            let mut row_index = 0usize;
+            while let Some(row) = cursor.next_row().unwrap() {
+                assert_eq!(
+                    (row[0], row[1]),
+                    (col1[row_index].as_slice(), col2[row_index].as_slice())
+                );
+                row_index += 1;
+            }
+            assert_eq!(row_index, col1.len());
+        };
+
+        for interrupted in [false, true] {
+            let path = dir.path().join(format!("jar_{interrupted}"));
+            let nippy = NippyJar::new_without_header(num_columns, &path)
+                .with_lz4()
+                .freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows)
+                .unwrap();
+            let compressor = Compressors::Zstd(compression::Zstd::new(true, 5000, num_columns));
+            let recompressed = nippy
+                .recompress(&tmp_dir.join(format!("jar_{interrupted}")), Some(compressor))
+                .unwrap();
+
+            if interrupted {
+                // Stop right after the data file was moved, leaving the old offsets and config
+                // next to the new data.
+                let marker = path.with_extension(REPLACE_MARKER_EXTENSION);
+                reth_fs_util::atomic_write_file(&marker, |file| {
+                    bincode::serialize_into(file, recompressed.data_path())
+                })
+                .unwrap();
+                reth_fs_util::rename(recompressed.data_path(), nippy.data_path()).unwrap();
+
+                assert_eq!(recover_replacements(dir.path()).unwrap(), vec![path.clone()]);
+                assert!(!marker.exists());
+            } else {
+                nippy.replace_with(&recompressed).unwrap();
+            }
+
+            let loaded_nippy = NippyJar::load_without_header(&path).unwrap();
+            assert!(matches!(loaded_nippy.compressor(), Some(Compressors::Zstd(_))));
+            assert_rows(&loaded_nippy);
+        }
+
+        assert!(recover_replacements(dir.path()).unwrap().is_empty());
+        assert_eq!(reth_fs_util::read_dir(&tmp_dir).unwrap().count(), 0);
+    }
+
     /// Tests `NippyJar` with everything enabled.
     #[test]
     fn test_full_nippy_jar() {
@@ -1,6 +1,6 @@
 use crate::{
-    compression::Compression, ColumnResult, NippyJar, NippyJarChecker, NippyJarError,
-    NippyJarHeader,
+    compression::{Compression, Compressors, ZstdCompressors},
+    ColumnResult, NippyJar, NippyJarChecker, NippyJarError, NippyJarHeader,
 };
 use std::{
     fs::{File, OpenOptions},
@@ -33,6 +33,9 @@ pub struct NippyJarWriter<H: NippyJarHeader = ()> {
     data_file: BufWriter<File>,
     /// File handle to where the offsets are stored.
     offsets_file: BufWriter<File>,
+    /// Per-column compressors, if the jar uses freshly trained zstd dictionaries. Otherwise,
+    /// values are compressed with the jar compressor.
+    column_compressors: Option<ZstdCompressors>,
     /// Temporary buffer to reuse when compressing data.
     tmp_buf: Vec<u8>,
     /// Used to find the maximum uncompressed size of a row in a jar.
@@ -70,10 +73,16 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
             (jar, data_file.expect("qed"), offsets_file.expect("qed"))
         };
 
+        let column_compressors = match jar.compressor() {
+            Some(Compressors::Zstd(zstd)) if zstd.use_dict => ZstdCompressors::new(zstd),
+            _ => None,
+        };
+
         let mut writer = Self {
             jar,
             data_file,
             offsets_file,
+            column_compressors,
             tmp_buf: Vec::with_capacity(1_000_000),
             uncompressed_row_size: 0,
             offsets: Vec::with_capacity(1_000_000),
@@ -227,7 +236,12 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
     /// Writes column to data file. If it's the last column of the row, call `finalize_row()`
     fn write_column(&mut self, value: &[u8]) -> Result<usize, NippyJarError> {
         self.uncompressed_row_size += value.len();
-        let len = if let Some(compression) = &self.jar.compressor {
+        let len = if let Some(compressors) = &mut self.column_compressors {
+            let before = self.tmp_buf.len();
+            let len = compressors.compress_to(self.column, value, &mut self.tmp_buf)?;
+            self.data_file.write_all(&self.tmp_buf[before..before + len])?;
+            len
+        } else if let Some(compression) = &self.jar.compressor {
             let before = self.tmp_buf.len();
             let len = compression.compress_to(value, &mut self.tmp_buf)?;
             self.data_file.write_all(&self.tmp_buf[before..before + len])?;
@@ -1,11 +1,12 @@
 use crate::{
-    providers::StaticFileProvider, AccountReader, BlockHashReader, BlockIdReader, BlockNumReader,
-    BlockReader, BlockReaderIdExt, BlockSource, CanonChainTracker, CanonStateNotifications,
-    CanonStateSubscriptions, ChainSpecProvider, ChainStateBlockReader, ChangeSetReader,
-    DatabaseProviderFactory, DatabaseProviderRO, EvmEnvProvider, HeaderProvider, ProviderError,
-    ProviderFactory, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
-    StageCheckpointReader, StateProviderBox, StateProviderFactory, StateReader,
-    StaticFileProviderFactory, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    providers::StaticFileProvider, AccountReader, AddressTransactionsReader, BlobSidecarsProvider,
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource,
+    CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions, ChainSpecProvider,
+    ChainStateBlockReader, ChangeSetReader, DatabaseProviderFactory, DatabaseProviderRO,
+    EvmEnvProvider, HeaderProvider, ProviderError, ProviderFactory, PruneCheckpointReader,
+    ReceiptProvider, ReceiptProviderIdExt, StageCheckpointReader, StateProviderBox,
+    StateProviderFactory, StateReader, StaticFileProviderFactory, TransactionVariant,
+    TransactionsProvider, WithdrawalsProvider,
 };
 use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag, HashOrNumber};
 use alloy_primitives::{Address, BlockHash, BlockNumber, Sealable, TxHash, TxNumber, B256, U256};
@@ -16,7 +17,9 @@ use reth_chain_state::{
 };
 use reth_chainspec::{ChainInfo, EthereumHardforks};
 use reth_db::models::BlockNumberAddress;
-use reth_db_api::models::{AccountBeforeTx, StoredBlockBodyIndices};
+use reth_db_api::models::{
+    AccountBeforeTx, StoredBlobSidecar, StoredBlockBlobSidecars, StoredBlockBodyIndices,
+};
 use reth_evm::ConfigureEvmEnv;
 use reth_execution_types::{BundleStateInit, ExecutionOutcome, RevertsInit};
 use reth_node_types::NodeTypesWithDB;
@@ -1200,6 +1203,23 @@ impl<N: ProviderNodeTypes> WithdrawalsProvider for BlockchainProvider2<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> BlobSidecarsProvider for BlockchainProvider2<N> {
+    fn blob_sidecars_by_block(
+        &self,
+        number: BlockNumber,
+    ) -> ProviderResult<Option<StoredBlockBlobSidecars>> {
+        self.database.blob_sidecars_by_block(number)
+    }
+
+    fn blob_sidecar_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<(BlockNumber, StoredBlobSidecar)>> {
+        self.database.blob_sidecar_by_versioned_hash(versioned_hash, block_range)
+    }
+}
+
 impl<N: ProviderNodeTypes> StageCheckpointReader for BlockchainProvider2<N> {
     fn get_stage_checkpoint(&self, id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
         self.database.provider()?.get_stage_checkpoint(id)
@@ -1658,6 +1678,27 @@ impl<N: ProviderNodeTypes> ChangeSetReader for BlockchainProvider2<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> AddressTransactionsReader for BlockchainProvider2<N> {
+    fn address_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        // The index is only maintained for persisted blocks.
+        self.database.provider()?.address_transactions(address, range, limit)
+    }
+
+    fn address_transactions_rev(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.database.provider()?.address_transactions_rev(address, range, limit)
+    }
+}
+
 impl<N: ProviderNodeTypes> AccountReader for BlockchainProvider2<N> {
     /// Get basic account information.
     fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
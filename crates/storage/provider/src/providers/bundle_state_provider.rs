@@ -2,14 +2,17 @@ use crate::{
     AccountReader, BlockHashReader, ExecutionDataProvider, StateProvider, StateRootProvider,
 };
 use alloy_primitives::{
+    keccak256,
     map::{HashMap, HashSet},
     Address, BlockNumber, Bytes, B256,
 };
 use reth_primitives::{Account, Bytecode};
-use reth_storage_api::{StateProofProvider, StorageRootProvider};
+use reth_storage_api::{StateProofProvider, StateRangeProvider, StorageRootProvider};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
-    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof, TrieInput,
+    range::{AccountRange, StorageRange},
+    updates::TrieUpdates,
+    AccountProof, HashedPostState, HashedStorage, MultiProof, TrieInput,
 };
 
 /// A state provider that resolves to data from either a wrapped [`crate::ExecutionOutcome`]
@@ -175,6 +178,52 @@ impl<SP: StateProvider, EDP: ExecutionDataProvider> StateProofProvider
     }
 }
 
+impl<SP: StateProvider, EDP: ExecutionDataProvider> StateRangeProvider
+    for BundleStateProvider<SP, EDP>
+{
+    fn account_range(
+        &self,
+        mut input: TrieInput,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> ProviderResult<AccountRange> {
+        let bundle_state = self.block_execution_data_provider.execution_outcome().state();
+        input.prepend(HashedPostState::from_bundle_state(&bundle_state.state));
+        self.state_provider.account_range(input, start, limit, storage_limit)
+    }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        let mut storage = self.get_hashed_storage(address);
+        storage.extend(&hashed_storage);
+        self.state_provider.storage_range(address, storage, start, limit)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_address: B256,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        let bundle_state = self.block_execution_data_provider.execution_outcome().state();
+        let mut storage = bundle_state
+            .state
+            .keys()
+            .find(|address| keccak256(address) == hashed_address)
+            .map(|address| self.get_hashed_storage(*address))
+            .unwrap_or_default();
+        storage.extend(&hashed_storage);
+        self.state_provider.hashed_storage_range(hashed_address, storage, start, limit)
+    }
+}
+
 impl<SP: StateProvider, EDP: ExecutionDataProvider> StateProvider for BundleStateProvider<SP, EDP> {
     fn storage(
         &self,
@@ -2,11 +2,10 @@ use crate::{BlockNumReader, DatabaseProviderFactory, HeaderProvider};
 use alloy_primitives::B256;
 use reth_errors::ProviderError;
 use reth_primitives::GotExpected;
-use reth_storage_api::{BlockReader, DBProvider};
+use reth_storage_api::{BlockReader, ChangeSetReader, StorageChangeSetReader};
 use reth_storage_errors::provider::ProviderResult;
 
 use reth_trie::HashedPostState;
-use reth_trie_db::DatabaseHashedPostState;
 
 pub use reth_storage_errors::provider::ConsistentViewError;
 
@@ -49,17 +48,30 @@ where
     }
 
     /// Retrieve revert hashed state down to the given block hash.
-    pub fn revert_state(&self, block_hash: B256) -> ProviderResult<HashedPostState> {
+    ///
+    /// Changesets are read through the provider, so reverts below the highest changeset static
+    /// file are served from static files.
+    pub fn revert_state(&self, block_hash: B256) -> ProviderResult<HashedPostState>
+    where
+        Factory::Provider: ChangeSetReader + StorageChangeSetReader,
+    {
         let provider = self.provider_ro()?;
         let block_number = provider
             .block_number(block_hash)?
             .ok_or(ProviderError::BlockHashNotFound(block_hash))?;
-        if block_number == provider.best_block_number()? &&
-            block_number == provider.last_block_number()?
-        {
+        let last_block_number = provider.last_block_number()?;
+        if block_number == provider.best_block_number()? && block_number == last_block_number {
             Ok(HashedPostState::default())
         } else {
-            Ok(HashedPostState::from_reverts(provider.tx_ref(), block_number + 1)?)
+            let range = block_number + 1..=last_block_number;
+            let accounts = provider.account_changesets_range(range.clone())?;
+            let storages = provider.storage_changesets_range(range)?;
+            Ok(HashedPostState::from_plain_reverts(
+                accounts.into_iter().map(|(_, change)| (change.address, change.info)),
+                storages.into_iter().map(|(block_address, entry)| {
+                    (block_address.address(), entry.key, entry.value)
+                }),
+            ))
         }
     }
 
@@ -2,17 +2,20 @@ use crate::{
     providers::{state::latest::LatestStateProvider, StaticFileProvider},
     to_range,
     traits::{BlockSource, ReceiptProvider},
-    BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory,
-    EvmEnvProvider, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, ProviderError,
-    PruneCheckpointReader, StageCheckpointReader, StateProviderBox, StaticFileProviderFactory,
-    TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    BlobSidecarsProvider, BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider,
+    DatabaseProviderFactory, EvmEnvProvider, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
+    ProviderError, PruneCheckpointReader, StageCheckpointReader, StateProviderBox,
+    StaticFileProviderFactory, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256};
 use core::fmt;
 use reth_chainspec::{ChainInfo, EthereumHardforks};
 use reth_db::{init_db, mdbx::DatabaseArguments, DatabaseEnv};
-use reth_db_api::{database::Database, models::StoredBlockBodyIndices};
+use reth_db_api::{
+    database::Database,
+    models::{StoredBlobSidecar, StoredBlockBlobSidecars, StoredBlockBodyIndices},
+};
 use reth_errors::{RethError, RethResult};
 use reth_evm::ConfigureEvmEnv;
 use reth_node_types::NodeTypesWithDB;
@@ -519,6 +522,23 @@ impl<N: ProviderNodeTypes> WithdrawalsProvider for ProviderFactory<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> BlobSidecarsProvider for ProviderFactory<N> {
+    fn blob_sidecars_by_block(
+        &self,
+        number: BlockNumber,
+    ) -> ProviderResult<Option<StoredBlockBlobSidecars>> {
+        self.static_file_provider.blob_sidecars_by_block(number)
+    }
+
+    fn blob_sidecar_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<(BlockNumber, StoredBlobSidecar)>> {
+        self.static_file_provider.blob_sidecar_by_versioned_hash(versioned_hash, block_range)
+    }
+}
+
 impl<N: ProviderNodeTypes> StageCheckpointReader for ProviderFactory<N> {
     fn get_stage_checkpoint(&self, id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
         self.provider()?.get_stage_checkpoint(id)
@@ -3,7 +3,8 @@ use crate::{
     providers::{database::metrics, static_file::StaticFileWriter, StaticFileProvider},
     to_range,
     traits::{
-        AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
+        AccountExtReader, AddressTransactionsReader, BlockSource, ChangeSetReader, ReceiptProvider,
+        StageCheckpointWriter,
     },
     writer::UnifiedStorageWriter,
     AccountReader, BlockExecutionReader, BlockExecutionWriter, BlockHashReader, BlockNumReader,
@@ -830,6 +831,114 @@ impl<TX: DbTx, Spec: Send + Sync> DatabaseProvider<TX, Spec> {
         Ok(blocks)
     }
 
+    /// Returns the account changesets of the given block range.
+    ///
+    /// Changesets of blocks that were moved to static files are read from there, the rest from the
+    /// [`AccountChangeSets`][tables::AccountChangeSets] table.
+    fn changesets_of_accounts(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        let range = to_range(range);
+        let mut changesets = Vec::new();
+
+        let mut db_start = range.start;
+        if let Some(highest) = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::AccountChangeSets)
+        {
+            for block_number in range.start..range.end.min(highest + 1) {
+                if let Some(change_set) =
+                    self.static_file_provider.account_change_set(block_number)?
+                {
+                    changesets.extend(
+                        change_set.changes.into_iter().map(|change| (block_number, change)),
+                    );
+                }
+            }
+            db_start = db_start.max(highest + 1);
+        }
+
+        if db_start < range.end {
+            for entry in self
+                .tx
+                .cursor_read::<tables::AccountChangeSets>()?
+                .walk_range(db_start..range.end)?
+            {
+                changesets.push(entry?);
+            }
+        }
+
+        Ok(changesets)
+    }
+
+    /// Returns the storage changesets of the given block range.
+    ///
+    /// Changesets of blocks that were moved to static files are read from there, the rest from the
+    /// [`StorageChangeSets`][tables::StorageChangeSets] table.
+    fn changesets_of_storages(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        let range = to_range(range);
+        let mut changesets = Vec::new();
+
+        let mut db_start = range.start;
+        if let Some(highest) = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets)
+        {
+            for block_number in range.start..range.end.min(highest + 1) {
+                if let Some(change_set) =
+                    self.static_file_provider.storage_change_set(block_number)?
+                {
+                    changesets.extend(change_set.changes.into_iter().map(|change| {
+                        (BlockNumberAddress((block_number, change.address)), change.entry())
+                    }));
+                }
+            }
+            db_start = db_start.max(highest + 1);
+        }
+
+        if db_start < range.end {
+            let db_range = BlockNumberAddress((db_start, Address::ZERO))..
+                BlockNumberAddress((range.end, Address::ZERO));
+            for entry in
+                self.tx.cursor_dup_read::<tables::StorageChangeSets>()?.walk_range(db_range)?
+            {
+                changesets.push(entry?);
+            }
+        }
+
+        Ok(changesets)
+    }
+
+    /// Queues the removal of the account and storage changesets of all blocks starting at `block`
+    /// from static files. The removal is committed together with the static file writers.
+    fn unwind_static_file_change_sets(&self, block: BlockNumber) -> ProviderResult<()> {
+        if let Some(highest) = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::AccountChangeSets)
+            .filter(|highest| *highest >= block)
+        {
+            self.static_file_provider
+                .latest_writer(StaticFileSegment::AccountChangeSets)?
+                .prune_account_change_sets(highest - block + 1)?;
+        }
+
+        if let Some(highest) = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets)
+            .filter(|highest| *highest >= block)
+        {
+            self.static_file_provider
+                .latest_writer(StaticFileSegment::StorageChangeSets)?
+                .prune_storage_change_sets(highest - block + 1)?;
+        }
+
+        Ok(())
+    }
+
     /// Return the last N blocks of state, recreating the [`ExecutionOutcome`].
     ///
     /// 1. Iterate over the [`BlockBodyIndices`][tables::BlockBodyIndices] table to get all the
@@ -873,10 +982,8 @@ impl<TX: DbTx, Spec: Send + Sync> DatabaseProvider<TX, Spec> {
             return Ok(None)
         };
 
-        let storage_range = BlockNumberAddress::range(range.clone());
-
-        let storage_changeset = self.get::<tables::StorageChangeSets>(storage_range)?;
-        let account_changeset = self.get::<tables::AccountChangeSets>(range)?;
+        let storage_changeset = self.changesets_of_storages(range.clone())?;
+        let account_changeset = self.changesets_of_accounts(range)?;
 
         // This is not working for blocks that are not at tip. as plain state is not the last
         // state of end range. We should rename the functions or add support to access
@@ -1376,13 +1483,11 @@ impl<TX: DbTx, Spec: Send + Sync> AccountExtReader for DatabaseProvider<TX, Spec
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<BTreeSet<Address>> {
-        self.tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(_, account_before)| account_before.address).map_err(Into::into)
-            })
-            .collect()
+        Ok(self
+            .changesets_of_accounts(range)?
+            .into_iter()
+            .map(|(_, account_before)| account_before.address)
+            .collect())
     }
 
     fn basic_accounts(
@@ -1400,16 +1505,13 @@ impl<TX: DbTx, Spec: Send + Sync> AccountExtReader for DatabaseProvider<TX, Spec
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::AccountChangeSets>()?;
-
-        let account_transitions = changeset_cursor.walk_range(range)?.try_fold(
+        let account_transitions = self.changesets_of_accounts(range)?.into_iter().fold(
             BTreeMap::new(),
-            |mut accounts: BTreeMap<Address, Vec<u64>>, entry| -> ProviderResult<_> {
-                let (index, account) = entry?;
+            |mut accounts: BTreeMap<Address, Vec<u64>>, (index, account)| {
                 accounts.entry(account.address).or_default().push(index);
-                Ok(accounts)
+                accounts
             },
-        )?;
+        );
 
         Ok(account_transitions)
     }
@@ -1420,13 +1522,14 @@ impl<TX: DbTx, Spec: Send + Sync> StorageChangeSetReader for DatabaseProvider<TX
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
-        let range = block_number..=block_number;
-        let storage_range = BlockNumberAddress::range(range);
-        self.tx
-            .cursor_dup_read::<tables::StorageChangeSets>()?
-            .walk_range(storage_range)?
-            .map(|result| -> ProviderResult<_> { Ok(result?) })
-            .collect()
+        self.changesets_of_storages(block_number..=block_number)
+    }
+
+    fn storage_changesets_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        self.changesets_of_storages(range)
     }
 }
 
@@ -1435,15 +1538,94 @@ impl<TX: DbTx, Spec: Send + Sync> ChangeSetReader for DatabaseProvider<TX, Spec>
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
-        let range = block_number..=block_number;
-        self.tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|result| -> ProviderResult<_> {
-                let (_, account_before) = result?;
-                Ok(account_before)
-            })
-            .collect()
+        Ok(self
+            .changesets_of_accounts(block_number..=block_number)?
+            .into_iter()
+            .map(|(_, account_before)| account_before)
+            .collect())
+    }
+
+    fn account_changesets_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        self.changesets_of_accounts(range)
+    }
+}
+
+impl<TX: DbTx, Spec: Send + Sync> AddressTransactionsReader for DatabaseProvider<TX, Spec> {
+    fn address_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        let mut tx_numbers = Vec::new();
+        if limit == 0 || range.is_empty() {
+            return Ok(tx_numbers)
+        }
+
+        // The first shard that can contain the start of the range is the first one with a highest
+        // transaction number greater than or equal to it.
+        let mut cursor = self.tx.cursor_read::<tables::AddressTransactions>()?;
+        let mut entry = cursor.seek(ShardedKey::new(address, *range.start()))?;
+        while let Some((sharded_key, list)) = entry {
+            if sharded_key.key != address {
+                break
+            }
+
+            for tx_number in list.iter().skip_while(|tx_number| tx_number < range.start()) {
+                if tx_number > *range.end() {
+                    return Ok(tx_numbers)
+                }
+
+                tx_numbers.push(tx_number);
+                if tx_numbers.len() == limit {
+                    return Ok(tx_numbers)
+                }
+            }
+
+            entry = cursor.next()?;
+        }
+
+        Ok(tx_numbers)
+    }
+
+    fn address_transactions_rev(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        let mut tx_numbers = Vec::new();
+        if limit == 0 || range.is_empty() {
+            return Ok(tx_numbers)
+        }
+
+        // The last shard of an address is keyed with `u64::MAX`, so the shard containing the end of
+        // the range always exists if the address has been indexed at all.
+        let mut cursor = self.tx.cursor_read::<tables::AddressTransactions>()?;
+        let mut entry = cursor.seek(ShardedKey::new(address, *range.end()))?;
+        while let Some((sharded_key, list)) = entry {
+            if sharded_key.key != address {
+                break
+            }
+
+            for tx_number in list.iter().rev().skip_while(|tx_number| tx_number > range.end()) {
+                if tx_number < *range.start() {
+                    return Ok(tx_numbers)
+                }
+
+                tx_numbers.push(tx_number);
+                if tx_numbers.len() == limit {
+                    return Ok(tx_numbers)
+                }
+            }
+
+            entry = cursor.prev()?;
+        }
+
+        Ok(tx_numbers)
     }
 }
 
@@ -2286,36 +2468,35 @@ impl<TX: DbTx, Spec: Send + Sync> StorageReader for DatabaseProvider<TX, Spec> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, BTreeSet<B256>>> {
-        self.tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(BlockNumberAddress::range(range))?
+        Ok(self
+            .changesets_of_storages(range)?
+            .into_iter()
             // fold all storages and save its old state so we can remove it from HashedStorage
             // it is needed as it is dup table.
-            .try_fold(BTreeMap::new(), |mut accounts: BTreeMap<Address, BTreeSet<B256>>, entry| {
-                let (BlockNumberAddress((_, address)), storage_entry) = entry?;
-                accounts.entry(address).or_default().insert(storage_entry.key);
-                Ok(accounts)
-            })
+            .fold(
+                BTreeMap::new(),
+                |mut accounts: BTreeMap<Address, BTreeSet<B256>>,
+                 (BlockNumberAddress((_, address)), storage_entry)| {
+                    accounts.entry(address).or_default().insert(storage_entry.key);
+                    accounts
+                },
+            ))
     }
 
     fn changed_storages_and_blocks_with_range(
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<(Address, B256), Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::StorageChangeSets>()?;
-
-        let storage_changeset_lists =
-            changeset_cursor.walk_range(BlockNumberAddress::range(range))?.try_fold(
-                BTreeMap::new(),
-                |mut storages: BTreeMap<(Address, B256), Vec<u64>>, entry| -> ProviderResult<_> {
-                    let (index, storage) = entry?;
-                    storages
-                        .entry((index.address(), storage.key))
-                        .or_default()
-                        .push(index.block_number());
-                    Ok(storages)
-                },
-            )?;
+        let storage_changeset_lists = self.changesets_of_storages(range)?.into_iter().fold(
+            BTreeMap::new(),
+            |mut storages: BTreeMap<(Address, B256), Vec<u64>>, (index, storage)| {
+                storages
+                    .entry((index.address(), storage.key))
+                    .or_default()
+                    .push(index.block_number());
+                storages
+            },
+        );
 
         Ok(storage_changeset_lists)
     }
@@ -2525,10 +2706,11 @@ impl<TX: DbTxMut + DbTx, Spec: Send + Sync> StateChangeWriter for DatabaseProvid
         let to_transaction_num =
             block_bodies.last().expect("already checked if there are blocks").1.last_tx_num();
 
-        let storage_range = BlockNumberAddress::range(range.clone());
-
-        let storage_changeset = self.take::<tables::StorageChangeSets>(storage_range)?;
-        let account_changeset = self.take::<tables::AccountChangeSets>(range)?;
+        let storage_changeset = self.changesets_of_storages(range.clone())?;
+        let account_changeset = self.changesets_of_accounts(range.clone())?;
+        self.remove::<tables::StorageChangeSets>(BlockNumberAddress::range(range.clone()))?;
+        self.remove::<tables::AccountChangeSets>(range.clone())?;
+        self.unwind_static_file_change_sets(*range.start())?;
 
         // This is not working for blocks that are not at tip. as plain state is not the last
         // state of end range. We should rename the functions or add support to access
@@ -2618,10 +2800,11 @@ impl<TX: DbTxMut + DbTx, Spec: Send + Sync> StateChangeWriter for DatabaseProvid
         let to_transaction_num =
             block_bodies.last().expect("already checked if there are blocks").1.last_tx_num();
 
-        let storage_range = BlockNumberAddress::range(range.clone());
-
-        let storage_changeset = self.take::<tables::StorageChangeSets>(storage_range)?;
-        let account_changeset = self.take::<tables::AccountChangeSets>(range)?;
+        let storage_changeset = self.changesets_of_storages(range.clone())?;
+        let account_changeset = self.changesets_of_accounts(range.clone())?;
+        self.remove::<tables::StorageChangeSets>(BlockNumberAddress::range(range.clone()))?;
+        self.remove::<tables::AccountChangeSets>(range.clone())?;
+        self.unwind_static_file_change_sets(*range.start())?;
 
         // This is not working for blocks that are not at tip. as plain state is not the last
         // state of end range. We should rename the functions or add support to access
@@ -2794,12 +2977,9 @@ impl<TX: DbTxMut + DbTx, Spec: Send + Sync> HashingWriter for DatabaseProvider<T
         // Note that collecting and then reversing the order is necessary to ensure that the
         // changes are applied in the correct order.
         let hashed_accounts = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| entry.map(|(_, e)| (keccak256(e.address), e.info)))
-            .collect::<Result<Vec<_>, _>>()?
+            .changesets_of_accounts(range)?
             .into_iter()
+            .map(|(_, e)| (keccak256(e.address), e.info))
             .rev()
             .collect::<BTreeMap<_, _>>();
 
@@ -2838,15 +3018,13 @@ impl<TX: DbTxMut + DbTx, Spec: Send + Sync> HashingWriter for DatabaseProvider<T
         range: Range<BlockNumberAddress>,
     ) -> ProviderResult<HashMap<B256, BTreeSet<B256>>> {
         // Aggregate all block changesets and make list of accounts that have been changed.
-        let mut changesets = self.tx.cursor_read::<tables::StorageChangeSets>()?;
-        let mut hashed_storages = changesets
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(BlockNumberAddress((_, address)), storage_entry)| {
-                    (keccak256(address), keccak256(storage_entry.key), storage_entry.value)
-                })
+        let mut hashed_storages = self
+            .changesets_of_storages(range.start.block_number()..range.end.block_number())?
+            .into_iter()
+            .map(|(BlockNumberAddress((_, address)), storage_entry)| {
+                (keccak256(address), keccak256(storage_entry.key), storage_entry.value)
             })
-            .collect::<Result<Vec<_>, _>>()?;
+            .collect::<Vec<_>>();
         hashed_storages.sort_by_key(|(ha, hk, _)| (*ha, *hk));
 
         // Apply values to HashedState, and remove the account if it's None.
@@ -2997,11 +3175,10 @@ impl<TX: DbTxMut + DbTx, Spec: Send + Sync> HistoryWriter for DatabaseProvider<T
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<usize> {
         let mut last_indices = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| entry.map(|(index, account)| (account.address, index)))
-            .collect::<Result<Vec<_>, _>>()?;
+            .changesets_of_accounts(range)?
+            .into_iter()
+            .map(|(index, account)| (account.address, index))
+            .collect::<Vec<_>>();
         last_indices.sort_by_key(|(a, _)| *a);
 
         // Unwind the account history index.
@@ -3043,13 +3220,10 @@ impl<TX: DbTxMut + DbTx, Spec: Send + Sync> HistoryWriter for DatabaseProvider<T
         range: Range<BlockNumberAddress>,
     ) -> ProviderResult<usize> {
         let mut storage_changesets = self
-            .tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(BlockNumberAddress((bn, address)), storage)| (address, storage.key, bn))
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+            .changesets_of_storages(range.start.block_number()..range.end.block_number())?
+            .into_iter()
+            .map(|(BlockNumberAddress((bn, address)), storage)| (address, storage.key, bn))
+            .collect::<Vec<_>>();
         storage_changesets.sort_by_key(|(address, key, _)| (*address, *key));
 
         let mut cursor = self.tx.cursor_write::<tables::StoragesHistory>()?;
@@ -3090,6 +3264,37 @@ impl<TX: DbTxMut + DbTx, Spec: Send + Sync> HistoryWriter for DatabaseProvider<T
         )
     }
 
+    fn unwind_address_transactions_indices(
+        &self,
+        addresses: impl IntoIterator<Item = Address>,
+        tx_number: TxNumber,
+    ) -> ProviderResult<usize> {
+        let mut addresses = addresses.into_iter().collect::<Vec<_>>();
+        addresses.sort_unstable();
+        addresses.dedup();
+
+        let mut cursor = self.tx.cursor_write::<tables::AddressTransactions>()?;
+        for &address in &addresses {
+            let partial_shard = unwind_history_shards::<_, tables::AddressTransactions, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                tx_number,
+                |sharded_key| sharded_key.key == address,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(addresses.len())
+    }
+
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()> {
         // account history stage
         {
@@ -1,11 +1,12 @@
 use crate::{
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BlockSource, BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotifications,
-    CanonStateSubscriptions, ChainSpecProvider, ChainStateBlockReader, ChangeSetReader,
-    DatabaseProviderFactory, EvmEnvProvider, FullExecutionDataProvider, HeaderProvider,
-    ProviderError, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
-    StageCheckpointReader, StateProviderBox, StateProviderFactory, StaticFileProviderFactory,
-    TransactionVariant, TransactionsProvider, TreeViewer, WithdrawalsProvider,
+    AccountReader, AddressTransactionsReader, BlobSidecarsProvider, BlockHashReader, BlockIdReader,
+    BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource, BlockchainTreePendingStateProvider,
+    CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions, ChainSpecProvider,
+    ChainStateBlockReader, ChangeSetReader, DatabaseProviderFactory, EvmEnvProvider,
+    FullExecutionDataProvider, HeaderProvider, ProviderError, PruneCheckpointReader,
+    ReceiptProvider, ReceiptProviderIdExt, StageCheckpointReader, StateProviderBox,
+    StateProviderFactory, StaticFileProviderFactory, StorageChangeSetReader, TransactionVariant,
+    TransactionsProvider, TreeViewer, WithdrawalsProvider,
 };
 use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag};
 use alloy_primitives::{Address, BlockHash, BlockNumber, Sealable, TxHash, TxNumber, B256, U256};
@@ -16,13 +17,16 @@ use reth_blockchain_tree_api::{
 };
 use reth_chain_state::{ChainInfoTracker, ForkChoiceNotifications, ForkChoiceSubscriptions};
 use reth_chainspec::{ChainInfo, EthereumHardforks};
-use reth_db_api::models::{AccountBeforeTx, StoredBlockBodyIndices};
+use reth_db_api::models::{
+    AccountBeforeTx, BlockNumberAddress, StoredBlobSidecar, StoredBlockBlobSidecars,
+    StoredBlockBodyIndices,
+};
 use reth_evm::ConfigureEvmEnv;
 use reth_node_types::NodeTypesWithDB;
 use reth_primitives::{
     Account, Block, BlockWithSenders, Header, Receipt, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, TransactionMeta, TransactionSigned, TransactionSignedNoHash, Withdrawal,
-    Withdrawals,
+    SealedHeader, StorageEntry, TransactionMeta, TransactionSigned, TransactionSignedNoHash,
+    Withdrawal, Withdrawals,
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
@@ -504,6 +508,23 @@ impl<N: ProviderNodeTypes> WithdrawalsProvider for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> BlobSidecarsProvider for BlockchainProvider<N> {
+    fn blob_sidecars_by_block(
+        &self,
+        number: BlockNumber,
+    ) -> ProviderResult<Option<StoredBlockBlobSidecars>> {
+        self.database.blob_sidecars_by_block(number)
+    }
+
+    fn blob_sidecar_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<(BlockNumber, StoredBlobSidecar)>> {
+        self.database.blob_sidecar_by_versioned_hash(versioned_hash, block_range)
+    }
+}
+
 impl<N: ProviderNodeTypes> StageCheckpointReader for BlockchainProvider<N> {
     fn get_stage_checkpoint(&self, id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
         self.database.provider()?.get_stage_checkpoint(id)
@@ -928,6 +949,35 @@ impl<N: ProviderNodeTypes> ChangeSetReader for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> StorageChangeSetReader for BlockchainProvider<N> {
+    fn storage_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        self.database.provider()?.storage_changeset(block_number)
+    }
+}
+
+impl<N: ProviderNodeTypes> AddressTransactionsReader for BlockchainProvider<N> {
+    fn address_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.database.provider()?.address_transactions(address, range, limit)
+    }
+
+    fn address_transactions_rev(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.database.provider()?.address_transactions_rev(address, range, limit)
+    }
+}
+
 impl<N: ProviderNodeTypes> AccountReader for BlockchainProvider<N> {
     /// Get basic account information.
     fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
@@ -3,8 +3,9 @@ use crate::{
     AccountReader, BlockHashReader, ProviderError, StateProvider, StateRootProvider,
 };
 use alloy_primitives::{
+    keccak256,
     map::{HashMap, HashSet},
-    Address, BlockNumber, Bytes, StorageKey, StorageValue, B256,
+    Address, BlockNumber, Bytes, StorageKey, StorageValue, B256, U256,
 };
 use reth_db::{tables, BlockNumberList};
 use reth_db_api::{
@@ -14,19 +15,20 @@ use reth_db_api::{
     transaction::DbTx,
 };
 use reth_primitives::{constants::EPOCH_SLOTS, Account, Bytecode, StaticFileSegment};
-use reth_storage_api::{StateProofProvider, StorageRootProvider};
+use reth_storage_api::{StateProofProvider, StateRangeProvider, StorageRootProvider};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
     proof::{Proof, StorageProof},
+    range::{AccountRange, StateRange, StorageRange},
     updates::TrieUpdates,
     witness::TrieWitness,
     AccountProof, HashedPostState, HashedStorage, MultiProof, StateRoot, StorageRoot, TrieInput,
 };
 use reth_trie_db::{
-    DatabaseHashedPostState, DatabaseHashedStorage, DatabaseProof, DatabaseStateRoot,
-    DatabaseStorageProof, DatabaseStorageRoot, DatabaseTrieWitness,
+    DatabaseHashedPostState, DatabaseHashedStorage, DatabaseProof, DatabaseStateRange,
+    DatabaseStateRoot, DatabaseStorageProof, DatabaseStorageRoot, DatabaseTrieWitness,
 };
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::OnceLock};
 
 /// State provider for a given block number which takes a tx reference.
 ///
@@ -39,6 +41,8 @@ use std::fmt::Debug;
 /// - [`tables::StoragesHistory`]
 /// - [`tables::AccountChangeSets`]
 /// - [`tables::StorageChangeSets`]
+///
+/// Changesets of blocks that were moved to static files are read from there instead.
 #[derive(Debug)]
 pub struct HistoricalStateProviderRef<'b, TX: DbTx> {
     /// Transaction
@@ -49,6 +53,8 @@ pub struct HistoricalStateProviderRef<'b, TX: DbTx> {
     lowest_available_blocks: LowestAvailableBlocks,
     /// Static File provider
     static_file_provider: StaticFileProvider,
+    /// Revert state of the provider, kept across calls if set.
+    revert_state_cache: Option<&'b OnceLock<HashedPostState>>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -66,7 +72,13 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
         block_number: BlockNumber,
         static_file_provider: StaticFileProvider,
     ) -> Self {
-        Self { tx, block_number, lowest_available_blocks: Default::default(), static_file_provider }
+        Self {
+            tx,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            static_file_provider,
+            revert_state_cache: None,
+        }
     }
 
     /// Create new `StateProvider` for historical block number and lowest block numbers at which
@@ -77,7 +89,19 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
         lowest_available_blocks: LowestAvailableBlocks,
         static_file_provider: StaticFileProvider,
     ) -> Self {
-        Self { tx, block_number, lowest_available_blocks, static_file_provider }
+        Self {
+            tx,
+            block_number,
+            lowest_available_blocks,
+            static_file_provider,
+            revert_state_cache: None,
+        }
+    }
+
+    /// Keep the revert state in the given cell, so that it's only built once across calls.
+    const fn with_revert_state_cache(mut self, cache: &'b OnceLock<HashedPostState>) -> Self {
+        self.revert_state_cache = Some(cache);
+        self
     }
 
     /// Lookup an account in the `AccountsHistory` table
@@ -131,6 +155,10 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
 
     /// Retrieve revert hashed state for this history provider.
     fn revert_state(&self) -> ProviderResult<HashedPostState> {
+        if let Some(reverts) = self.revert_state_cache.and_then(OnceLock::get) {
+            return Ok(reverts.clone())
+        }
+
         if !self.lowest_available_blocks.is_account_history_available(self.block_number) ||
             !self.lowest_available_blocks.is_storage_history_available(self.block_number)
         {
@@ -145,7 +173,23 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
             );
         }
 
-        Ok(HashedPostState::from_reverts(self.tx, self.block_number)?)
+        let mut reverts = HashedPostState::from_reverts(self.tx, self.block_number)?;
+        // Changesets in static files belong to older blocks, so their values take precedence.
+        reverts.extend(self.static_file_revert_state(None)?);
+        match self.revert_state_cache {
+            Some(cache) => Ok(cache.get_or_init(|| reverts).clone()),
+            None => Ok(reverts),
+        }
+    }
+
+    /// Retrieve revert hashed storage for this history provider and target hashed address.
+    ///
+    /// Storage reverts are keyed by plain address, so the whole revert state is needed.
+    fn revert_hashed_storage(&self, hashed_address: B256) -> ProviderResult<HashedStorage> {
+        if let Some(reverts) = self.revert_state_cache.and_then(OnceLock::get) {
+            return Ok(reverts.storages.get(&hashed_address).cloned().unwrap_or_default())
+        }
+        Ok(self.revert_state()?.storages.remove(&hashed_address).unwrap_or_default())
     }
 
     /// Retrieve revert hashed storage for this history provider and target address.
@@ -162,7 +206,82 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
             );
         }
 
-        Ok(HashedStorage::from_reverts(self.tx, address, self.block_number)?)
+        let mut reverts = HashedStorage::from_reverts(self.tx, address, self.block_number)?;
+        // Changesets in static files belong to older blocks, so their values take precedence.
+        if let Some(storage) =
+            self.static_file_revert_state(Some(address))?.storages.remove(&keccak256(address))
+        {
+            reverts.extend(&storage);
+        }
+        Ok(reverts)
+    }
+
+    /// Retrieve revert hashed state from the changesets that were moved to static files, starting
+    /// at this provider's block number. If `address` is set, only its storage is collected.
+    fn static_file_revert_state(
+        &self,
+        address: Option<Address>,
+    ) -> ProviderResult<HashedPostState> {
+        let mut accounts = HashMap::<Address, Option<Account>>::default();
+        if address.is_none() {
+            if let Some(highest) = self
+                .static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::AccountChangeSets)
+            {
+                for block_number in self.block_number..=highest {
+                    let Some(change_set) =
+                        self.static_file_provider.account_change_set(block_number)?
+                    else {
+                        continue
+                    };
+                    for change in change_set.changes {
+                        accounts.entry(change.address).or_insert(change.info);
+                    }
+                }
+            }
+        }
+
+        let mut storages = HashMap::<Address, HashMap<B256, U256>>::default();
+        if let Some(highest) = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets)
+        {
+            for block_number in self.block_number..=highest {
+                let Some(change_set) =
+                    self.static_file_provider.storage_change_set(block_number)?
+                else {
+                    continue
+                };
+                for change in change_set.changes {
+                    if address.map_or(true, |address| address == change.address) {
+                        storages
+                            .entry(change.address)
+                            .or_default()
+                            .entry(change.key)
+                            .or_insert(change.value);
+                    }
+                }
+            }
+        }
+
+        Ok(HashedPostState {
+            accounts: accounts
+                .into_iter()
+                .map(|(address, info)| (keccak256(address), info))
+                .collect(),
+            storages: storages
+                .into_iter()
+                .map(|(address, storage)| {
+                    (
+                        keccak256(address),
+                        HashedStorage::from_iter(
+                            false,
+                            storage.into_iter().map(|(slot, value)| (keccak256(slot), value)),
+                        ),
+                    )
+                })
+                .collect(),
+        })
     }
 
     fn history_info<T, K>(
@@ -253,15 +372,28 @@ impl<TX: DbTx> AccountReader for HistoricalStateProviderRef<'_, TX> {
         match self.account_history_lookup(address)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(self
-                .tx
-                .cursor_dup_read::<tables::AccountChangeSets>()?
-                .seek_by_key_subkey(changeset_block_number, address)?
-                .filter(|acc| acc.address == address)
+                .static_file_provider
+                .get_with_static_file_or_database(
+                    StaticFileSegment::AccountChangeSets,
+                    changeset_block_number,
+                    |static_file| {
+                        Ok(static_file
+                            .account_change_set(changeset_block_number)?
+                            .and_then(|change_set| change_set.get(address).map(|acc| acc.info)))
+                    },
+                    || {
+                        Ok(self
+                            .tx
+                            .cursor_dup_read::<tables::AccountChangeSets>()?
+                            .seek_by_key_subkey(changeset_block_number, address)?
+                            .filter(|acc| acc.address == address)
+                            .map(|acc| acc.info))
+                    },
+                )?
                 .ok_or(ProviderError::AccountChangesetNotFound {
                     block_number: changeset_block_number,
                     address,
-                })?
-                .info),
+                })?),
             HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => {
                 Ok(self.tx.get::<tables::PlainAccountState>(address)?)
             }
@@ -396,6 +528,52 @@ impl<TX: DbTx> StateProofProvider for HistoricalStateProviderRef<'_, TX> {
     }
 }
 
+impl<TX: DbTx> StateRangeProvider for HistoricalStateProviderRef<'_, TX> {
+    fn account_range(
+        &self,
+        mut input: TrieInput,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> ProviderResult<AccountRange> {
+        input.prepend(self.revert_state()?);
+        StateRange::overlay_account_range(self.tx, input, start, limit, storage_limit)
+            .map_err(|err| ProviderError::Database(err.into()))
+    }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        let mut revert_storage = self.revert_storage(address)?;
+        revert_storage.extend(&hashed_storage);
+        StateRange::overlay_storage_range(self.tx, address, revert_storage, start, limit)
+            .map_err(Into::into)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_address: B256,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        let mut revert_storage = self.revert_hashed_storage(hashed_address)?;
+        revert_storage.extend(&hashed_storage);
+        StateRange::overlay_hashed_storage_range(
+            self.tx,
+            hashed_address,
+            revert_storage,
+            start,
+            limit,
+        )
+        .map_err(Into::into)
+    }
+}
+
 impl<TX: DbTx> StateProvider for HistoricalStateProviderRef<'_, TX> {
     /// Get storage.
     fn storage(
@@ -406,16 +584,34 @@ impl<TX: DbTx> StateProvider for HistoricalStateProviderRef<'_, TX> {
         match self.storage_history_lookup(address, storage_key)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
-                self.tx
-                    .cursor_dup_read::<tables::StorageChangeSets>()?
-                    .seek_by_key_subkey((changeset_block_number, address).into(), storage_key)?
-                    .filter(|entry| entry.key == storage_key)
+                self.static_file_provider
+                    .get_with_static_file_or_database(
+                        StaticFileSegment::StorageChangeSets,
+                        changeset_block_number,
+                        |static_file| {
+                            Ok(static_file.storage_change_set(changeset_block_number)?.and_then(
+                                |change_set| {
+                                    change_set.get(address, storage_key).map(|entry| entry.value)
+                                },
+                            ))
+                        },
+                        || {
+                            Ok(self
+                                .tx
+                                .cursor_dup_read::<tables::StorageChangeSets>()?
+                                .seek_by_key_subkey(
+                                    (changeset_block_number, address).into(),
+                                    storage_key,
+                                )?
+                                .filter(|entry| entry.key == storage_key)
+                                .map(|entry| entry.value))
+                        },
+                    )?
                     .ok_or_else(|| ProviderError::StorageChangesetNotFound {
                         block_number: changeset_block_number,
                         address,
                         storage_key: Box::new(storage_key),
-                    })?
-                    .value,
+                    })?,
             )),
             HistoryInfo::InPlainState | HistoryInfo::MaybeInPlainState => Ok(self
                 .tx
@@ -445,6 +641,11 @@ pub struct HistoricalStateProvider<TX: DbTx> {
     lowest_available_blocks: LowestAvailableBlocks,
     /// Static File provider
     static_file_provider: StaticFileProvider,
+    /// Revert state of the provider, built on first use.
+    ///
+    /// The state of the transaction doesn't change, so it's shared by all calls, e.g. by the
+    /// pages of a range request.
+    revert_state: OnceLock<HashedPostState>,
 }
 
 impl<TX: DbTx> HistoricalStateProvider<TX> {
@@ -454,7 +655,13 @@ impl<TX: DbTx> HistoricalStateProvider<TX> {
         block_number: BlockNumber,
         static_file_provider: StaticFileProvider,
     ) -> Self {
-        Self { tx, block_number, lowest_available_blocks: Default::default(), static_file_provider }
+        Self {
+            tx,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            static_file_provider,
+            revert_state: OnceLock::new(),
+        }
     }
 
     /// Set the lowest block number at which the account history is available.
@@ -484,6 +691,7 @@ impl<TX: DbTx> HistoricalStateProvider<TX> {
             self.lowest_available_blocks,
             self.static_file_provider.clone(),
         )
+        .with_revert_state_cache(&self.revert_state)
     }
 }
 
@@ -521,7 +729,10 @@ impl LowestAvailableBlocks {
 #[cfg(test)]
 mod tests {
     use crate::{
-        providers::state::historical::{HistoryInfo, LowestAvailableBlocks},
+        providers::{
+            state::historical::{HistoryInfo, LowestAvailableBlocks},
+            StaticFileWriter,
+        },
         test_utils::create_test_provider_factory,
         AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, StateProvider,
         StaticFileProviderFactory,
@@ -529,10 +740,13 @@ mod tests {
     use alloy_primitives::{address, b256, Address, B256, U256};
     use reth_db::{tables, BlockNumberList};
     use reth_db_api::{
-        models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
+        models::{
+            storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey, StorageBeforeTx,
+            StoredBlockAccountChangeSet, StoredBlockStorageChangeSet,
+        },
         transaction::{DbTx, DbTxMut},
     };
-    use reth_primitives::{Account, StorageEntry};
+    use reth_primitives::{Account, StaticFileSegment, StorageEntry};
     use reth_storage_errors::provider::ProviderError;
 
     const ADDRESS: Address = address!("0000000000000000000000000000000000000001");
@@ -776,6 +990,76 @@ mod tests {
         );
     }
 
+    #[test]
+    fn history_provider_get_from_static_files() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+        let static_file_provider = factory.static_file_provider();
+
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey { key: ADDRESS, highest_block_number: u64::MAX },
+            BlockNumberList::new([1, 3]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::StoragesHistory>(
+            StorageShardedKey {
+                address: ADDRESS,
+                sharded_key: ShardedKey { key: STORAGE, highest_block_number: u64::MAX },
+            },
+            BlockNumberList::new([1, 3]).unwrap(),
+        )
+        .unwrap();
+
+        let acc_at1 = Account { nonce: 1, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at3 = Account { nonce: 3, balance: U256::ZERO, bytecode_hash: None };
+        let entry_at1 = StorageEntry { key: STORAGE, value: U256::from(1) };
+        let entry_at3 = StorageEntry { key: STORAGE, value: U256::from(3) };
+
+        // changesets of blocks 0 and 1 are in static files, block 3 is in the database
+        let mut writer =
+            static_file_provider.latest_writer(StaticFileSegment::AccountChangeSets).unwrap();
+        writer.append_account_change_set(0, &StoredBlockAccountChangeSet::default()).unwrap();
+        writer
+            .append_account_change_set(
+                1,
+                &StoredBlockAccountChangeSet {
+                    changes: vec![AccountBeforeTx { address: ADDRESS, info: Some(acc_at1) }],
+                },
+            )
+            .unwrap();
+        writer.commit().unwrap();
+        drop(writer);
+
+        let mut writer =
+            static_file_provider.latest_writer(StaticFileSegment::StorageChangeSets).unwrap();
+        writer.append_storage_change_set(0, &StoredBlockStorageChangeSet::default()).unwrap();
+        writer
+            .append_storage_change_set(
+                1,
+                &StoredBlockStorageChangeSet {
+                    changes: vec![StorageBeforeTx::new(ADDRESS, entry_at1)],
+                },
+            )
+            .unwrap();
+        writer.commit().unwrap();
+        drop(writer);
+
+        tx.put::<tables::AccountChangeSets>(
+            3,
+            AccountBeforeTx { address: ADDRESS, info: Some(acc_at3) },
+        )
+        .unwrap();
+        tx.put::<tables::StorageChangeSets>((3, ADDRESS).into(), entry_at3).unwrap();
+
+        let provider = HistoricalStateProviderRef::new(&tx, 1, static_file_provider.clone());
+        assert_eq!(provider.basic_account(ADDRESS), Ok(Some(acc_at1)));
+        assert_eq!(provider.storage(ADDRESS, STORAGE), Ok(Some(entry_at1.value)));
+
+        let provider = HistoricalStateProviderRef::new(&tx, 2, static_file_provider);
+        assert_eq!(provider.basic_account(ADDRESS), Ok(Some(acc_at3)));
+        assert_eq!(provider.storage(ADDRESS, STORAGE), Ok(Some(entry_at3.value)));
+    }
+
     #[test]
     fn history_provider_unavailable() {
         let factory = create_test_provider_factory();
@@ -12,17 +12,18 @@ use reth_db_api::{
     transaction::DbTx,
 };
 use reth_primitives::{Account, Bytecode, StaticFileSegment};
-use reth_storage_api::{StateProofProvider, StorageRootProvider};
+use reth_storage_api::{StateProofProvider, StateRangeProvider, StorageRootProvider};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::{
     proof::{Proof, StorageProof},
+    range::{AccountRange, StateRange, StorageRange},
     updates::TrieUpdates,
     witness::TrieWitness,
     AccountProof, HashedPostState, HashedStorage, MultiProof, StateRoot, StorageRoot, TrieInput,
 };
 use reth_trie_db::{
-    DatabaseProof, DatabaseStateRoot, DatabaseStorageProof, DatabaseStorageRoot,
-    DatabaseTrieWitness,
+    DatabaseProof, DatabaseStateRange, DatabaseStateRoot, DatabaseStorageProof,
+    DatabaseStorageRoot, DatabaseTrieWitness,
 };
 
 /// State provider over latest state that takes tx reference.
@@ -161,6 +162,47 @@ impl<TX: DbTx> StateProofProvider for LatestStateProviderRef<'_, TX> {
     }
 }
 
+impl<TX: DbTx> StateRangeProvider for LatestStateProviderRef<'_, TX> {
+    fn account_range(
+        &self,
+        input: TrieInput,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> ProviderResult<AccountRange> {
+        StateRange::overlay_account_range(self.tx, input, start, limit, storage_limit)
+            .map_err(|err| ProviderError::Database(err.into()))
+    }
+
+    fn storage_range(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        StateRange::overlay_storage_range(self.tx, address, hashed_storage, start, limit)
+            .map_err(Into::into)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_address: B256,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange> {
+        StateRange::overlay_hashed_storage_range(
+            self.tx,
+            hashed_address,
+            hashed_storage,
+            start,
+            limit,
+        )
+        .map_err(Into::into)
+    }
+}
+
 impl<TX: DbTx> StateProvider for LatestStateProviderRef<'_, TX> {
     /// Get storage.
     fn storage(
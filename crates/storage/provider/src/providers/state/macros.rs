@@ -56,6 +56,11 @@ macro_rules! delegate_provider_impls {
                 fn multiproof(&self, input: reth_trie::TrieInput, targets: alloy_primitives::map::HashMap<alloy_primitives::B256, alloy_primitives::map::HashSet<alloy_primitives::B256>>) -> reth_storage_errors::provider::ProviderResult<reth_trie::MultiProof>;
                 fn witness(&self, input: reth_trie::TrieInput, target: reth_trie::HashedPostState) -> reth_storage_errors::provider::ProviderResult<alloy_primitives::map::HashMap<alloy_primitives::B256, alloy_primitives::Bytes>>;
             }
+            StateRangeProvider $(where [$($generics)*])? {
+                fn account_range(&self, input: reth_trie::TrieInput, start: alloy_primitives::B256, limit: usize, storage_limit: usize) -> reth_storage_errors::provider::ProviderResult<reth_trie::range::AccountRange>;
+                fn storage_range(&self, address: alloy_primitives::Address, hashed_storage: reth_trie::HashedStorage, start: alloy_primitives::B256, limit: usize) -> reth_storage_errors::provider::ProviderResult<reth_trie::range::StorageRange>;
+                fn hashed_storage_range(&self, hashed_address: alloy_primitives::B256, hashed_storage: reth_trie::HashedStorage, start: alloy_primitives::B256, limit: usize) -> reth_storage_errors::provider::ProviderResult<reth_trie::range::StorageRange>;
+            }
         );
     }
 }
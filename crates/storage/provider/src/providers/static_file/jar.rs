@@ -3,20 +3,28 @@ use super::{
     LoadedJarRef,
 };
 use crate::{
-    to_range, BlockHashReader, BlockNumReader, HeaderProvider, ReceiptProvider,
-    TransactionsProvider,
+    to_range, BlobSidecarsProvider, BlockHashReader, BlockNumReader, ChangeSetReader,
+    HeaderProvider, ReceiptProvider, StorageChangeSetReader, TransactionsProvider,
 };
 use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256};
 use reth_chainspec::ChainInfo;
-use reth_db::static_file::{HeaderMask, ReceiptMask, StaticFileCursor, TransactionMask};
-use reth_db_api::models::CompactU256;
+use reth_db::static_file::{
+    AccountChangeSetsMask, BlobSidecarsMask, HeaderMask, ReceiptMask, StaticFileCursor,
+    StorageChangeSetsMask, TransactionMask,
+};
+use reth_db_api::models::{
+    AccountBeforeTx, BlockNumberAddress, CompactU256, StoredBlobSidecar,
+    StoredBlockAccountChangeSet, StoredBlockBlobSidecars, StoredBlockBlobVersionedHashes,
+    StoredBlockStorageChangeSet,
+};
 use reth_primitives::{
-    Header, Receipt, SealedHeader, TransactionMeta, TransactionSigned, TransactionSignedNoHash,
+    Header, Receipt, SealedHeader, StorageEntry, TransactionMeta, TransactionSigned,
+    TransactionSignedNoHash,
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use std::{
-    ops::{Deref, RangeBounds},
+    ops::{Deref, RangeBounds, RangeInclusive},
     sync::Arc,
 };
 
@@ -75,6 +83,90 @@ impl<'a> StaticFileJarProvider<'a> {
     }
 }
 
+impl BlobSidecarsProvider for StaticFileJarProvider<'_> {
+    fn blob_sidecars_by_block(
+        &self,
+        number: BlockNumber,
+    ) -> ProviderResult<Option<StoredBlockBlobSidecars>> {
+        self.cursor()?.get_one::<BlobSidecarsMask<StoredBlockBlobSidecars>>(number.into())
+    }
+
+    fn blob_sidecar_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<(BlockNumber, StoredBlobSidecar)>> {
+        let Some(jar_range) = self.user_header().block_range().copied() else { return Ok(None) };
+        let start = jar_range.start().max(*block_range.start());
+        let end = jar_range.end().min(*block_range.end());
+
+        // It's more probable that the request is for a recent blob, so we iterate the blocks in
+        // reverse.
+        let mut cursor = self.cursor()?;
+        for number in (start..=end).rev() {
+            let contains_blob = cursor
+                .get_one::<BlobSidecarsMask<StoredBlockBlobVersionedHashes>>(number.into())?
+                .is_some_and(|versioned_hashes| versioned_hashes.hashes.contains(&versioned_hash));
+            if contains_blob {
+                return Ok(cursor
+                    .get_one::<BlobSidecarsMask<StoredBlockBlobSidecars>>(number.into())?
+                    .and_then(|sidecars| sidecars.find_by_versioned_hash(&versioned_hash).cloned())
+                    .map(|sidecar| (number, sidecar)))
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl StaticFileJarProvider<'_> {
+    /// Returns the account changeset of a block.
+    pub fn account_change_set(
+        &self,
+        number: BlockNumber,
+    ) -> ProviderResult<Option<StoredBlockAccountChangeSet>> {
+        self.cursor()?.get_one::<AccountChangeSetsMask<StoredBlockAccountChangeSet>>(number.into())
+    }
+
+    /// Returns the storage changeset of a block.
+    pub fn storage_change_set(
+        &self,
+        number: BlockNumber,
+    ) -> ProviderResult<Option<StoredBlockStorageChangeSet>> {
+        self.cursor()?.get_one::<StorageChangeSetsMask<StoredBlockStorageChangeSet>>(number.into())
+    }
+}
+
+impl ChangeSetReader for StaticFileJarProvider<'_> {
+    fn account_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<AccountBeforeTx>> {
+        Ok(self
+            .account_change_set(block_number)?
+            .map(|change_set| change_set.changes)
+            .unwrap_or_default())
+    }
+}
+
+impl StorageChangeSetReader for StaticFileJarProvider<'_> {
+    fn storage_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        Ok(self
+            .storage_change_set(block_number)?
+            .map(|change_set| {
+                change_set
+                    .changes
+                    .into_iter()
+                    .map(|change| ((block_number, change.address).into(), change.entry()))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+}
+
 impl HeaderProvider for StaticFileJarProvider<'_> {
     fn header(&self, block_hash: &BlockHash) -> ProviderResult<Option<Header>> {
         Ok(self
@@ -3,9 +3,10 @@ use super::{
     StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
 };
 use crate::{
-    to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, HeaderProvider,
-    ReceiptProvider, StageCheckpointReader, StatsReader, TransactionVariant, TransactionsProvider,
-    TransactionsProviderExt, WithdrawalsProvider,
+    to_range, BlobSidecarsProvider, BlobSidecarsWriter, BlockHashReader, BlockNumReader,
+    BlockReader, BlockSource, ChangeSetReader, HeaderProvider, ReceiptProvider,
+    StageCheckpointReader, StatsReader, StorageChangeSetReader, TransactionVariant,
+    TransactionsProvider, TransactionsProviderExt, WithdrawalsProvider,
 };
 use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::{keccak256, Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256};
@@ -20,20 +21,25 @@ use reth_db::{
 };
 use reth_db_api::{
     cursor::DbCursorRO,
-    models::{CompactU256, StoredBlockBodyIndices},
+    models::{
+        AccountBeforeTx, BlockNumberAddress, CompactU256, StoredBlobSidecar,
+        StoredBlockAccountChangeSet, StoredBlockBlobSidecars, StoredBlockBodyIndices,
+        StoredBlockStorageChangeSet,
+    },
     table::Table,
     transaction::DbTx,
 };
-use reth_nippy_jar::{NippyJar, NippyJarChecker, CONFIG_FILE_EXTENSION};
+use reth_nippy_jar::{recover_replacements, NippyJar, NippyJarChecker, CONFIG_FILE_EXTENSION};
 use reth_primitives::{
     static_file::{
         find_fixed_range, HighestStaticFiles, SegmentHeader, SegmentRangeInclusive,
         DEFAULT_BLOCKS_PER_STATIC_FILE,
     },
     Block, BlockWithSenders, Header, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader,
-    StaticFileSegment, TransactionMeta, TransactionSigned, TransactionSignedNoHash, Withdrawal,
-    Withdrawals,
+    StaticFileSegment, StorageEntry, TransactionMeta, TransactionSigned, TransactionSignedNoHash,
+    Withdrawal, Withdrawals,
 };
+use reth_prune_types::PruneSegment;
 use reth_stages_types::{PipelineTarget, StageId};
 use reth_storage_api::DBProvider;
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
@@ -204,6 +210,8 @@ pub struct StaticFileProviderInner {
     map: DashMap<(BlockNumber, StaticFileSegment), LoadedJar>,
     /// Max static file block for each segment
     static_files_max_block: RwLock<HashMap<StaticFileSegment, u64>>,
+    /// Start block of the lowest static file for each segment
+    static_files_min_block: RwLock<HashMap<StaticFileSegment, u64>>,
     /// Available static file block ranges on disk indexed by max transactions.
     static_files_tx_index: RwLock<SegmentRanges>,
     /// Directory where `static_files` are located
@@ -223,7 +231,11 @@ impl StaticFileProviderInner {
     /// Creates a new [`StaticFileProviderInner`].
     fn new(path: impl AsRef<Path>, access: StaticFileAccess) -> ProviderResult<Self> {
         let _lock_file = if access.is_read_write() {
-            StorageLock::try_acquire(path.as_ref())?.into()
+            let lock_file = StorageLock::try_acquire(path.as_ref())?;
+            // A recompression of static files may have been interrupted while swapping files.
+            recover_replacements(path.as_ref())
+                .map_err(|err| ProviderError::NippyJar(err.to_string()))?;
+            lock_file.into()
         } else {
             None
         };
@@ -232,6 +244,7 @@ impl StaticFileProviderInner {
             map: Default::default(),
             writers: Default::default(),
             static_files_max_block: Default::default(),
+            static_files_min_block: Default::default(),
             static_files_tx_index: Default::default(),
             path: path.as_ref().to_path_buf(),
             metrics: None,
@@ -416,6 +429,46 @@ impl StaticFileProvider {
         Ok(())
     }
 
+    /// Given a segment, it deletes all static files whose block range ends before `block`, starting
+    /// from the lowest one. The highest static file of the segment is never deleted.
+    ///
+    /// Returns the number of deleted rows and the highest deleted block, if any.
+    ///
+    /// CAUTION: destructive. Deletes files on disk.
+    pub fn delete_jars_below(
+        &self,
+        segment: StaticFileSegment,
+        block: BlockNumber,
+    ) -> ProviderResult<(u64, Option<BlockNumber>)> {
+        let (Some(lowest_block), Some(highest_block)) = (
+            self.get_lowest_static_file_block(segment),
+            self.get_highest_static_file_block(segment),
+        ) else {
+            return Ok((0, None))
+        };
+        let highest_range = self.find_fixed_range(highest_block);
+
+        let mut deleted_rows = 0;
+        let mut deleted_block = None;
+        let mut range = self.find_fixed_range(lowest_block);
+        while range.end() < block && range.end() < highest_range.start() {
+            let jar = if let Some((_, jar)) = self.map.remove(&(range.end(), segment)) {
+                jar.jar
+            } else {
+                NippyJar::<SegmentHeader>::load(&self.path.join(segment.filename(&range)))
+                    .map_err(|e| ProviderError::NippyJar(e.to_string()))?
+            };
+            deleted_rows += jar.rows() as u64;
+            jar.delete().map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+
+            deleted_block = Some(range.end());
+            self.static_files_min_block.write().insert(segment, range.end() + 1);
+            range = self.find_fixed_range(range.end() + 1);
+        }
+
+        Ok((deleted_rows, deleted_block))
+    }
+
     /// Given a segment and block range it returns a cached
     /// [`StaticFileJarProvider`]. TODO(joshie): we should check the size and pop N if there's too
     /// many.
@@ -455,6 +508,7 @@ impl StaticFileProvider {
             .read()
             .get(&segment)
             .filter(|max| **max >= block)
+            .filter(|_| self.get_lowest_static_file_block(segment).map_or(true, |min| min <= block))
             .map(|_| self.find_fixed_range(block))
     }
 
@@ -497,6 +551,7 @@ impl StaticFileProvider {
         segment_max_block: Option<BlockNumber>,
     ) -> ProviderResult<()> {
         let mut max_block = self.static_files_max_block.write();
+        let mut min_block = self.static_files_min_block.write();
         let mut tx_index = self.static_files_tx_index.write();
 
         match segment_max_block {
@@ -505,6 +560,10 @@ impl StaticFileProvider {
                 max_block.insert(segment, segment_max_block);
                 let fixed_range = self.find_fixed_range(segment_max_block);
 
+                // Update the min block for the segment, in case this is its first static file
+                let min = min_block.entry(segment).or_insert_with(|| fixed_range.start());
+                *min = (*min).min(fixed_range.start());
+
                 let jar = NippyJar::<SegmentHeader>::load(
                     &self.path.join(segment.filename(&fixed_range)),
                 )
@@ -559,6 +618,7 @@ impl StaticFileProvider {
             None => {
                 tx_index.remove(&segment);
                 max_block.remove(&segment);
+                min_block.remove(&segment);
             }
         };
 
@@ -568,15 +628,20 @@ impl StaticFileProvider {
     /// Initializes the inner transaction and block index
     pub fn initialize_index(&self) -> ProviderResult<()> {
         let mut max_block = self.static_files_max_block.write();
+        let mut min_block = self.static_files_min_block.write();
         let mut tx_index = self.static_files_tx_index.write();
 
         max_block.clear();
+        min_block.clear();
         tx_index.clear();
 
         for (segment, ranges) in
             iter_static_files(&self.path).map_err(|e| ProviderError::NippyJar(e.to_string()))?
         {
-            // Update last block for each segment
+            // Update first and last block for each segment
+            if let Some((block_range, _)) = ranges.first() {
+                min_block.insert(segment, self.find_fixed_range(block_range.start()).start());
+            }
             if let Some((block_range, _)) = ranges.last() {
                 max_block.insert(segment, block_range.end());
             }
@@ -674,6 +739,24 @@ impl StaticFileProvider {
 
             let initial_highest_block = self.get_highest_static_file_block(segment);
 
+            if segment.is_blob_sidecars() {
+                // Blob sidecars are optional and not backed by the database.
+                if initial_highest_block.is_some() {
+                    self.ensure_blob_sidecars_invariants(provider)?;
+                }
+                continue
+            }
+
+            if segment.is_change_sets() {
+                // Changesets are only moved to static files by archive nodes.
+                if initial_highest_block.is_some() {
+                    if let Some(unwind) = self.ensure_change_sets_invariants(provider, segment)? {
+                        update_unwind_target(unwind);
+                    }
+                }
+                continue
+            }
+
             //  File consistency is broken if:
             //
             // * appending data was interrupted before a config commit, then data file will be
@@ -742,6 +825,9 @@ impl StaticFileProvider {
             }
 
             if let Some(unwind) = match segment {
+                StaticFileSegment::BlobSidecars |
+                StaticFileSegment::AccountChangeSets |
+                StaticFileSegment::StorageChangeSets => unreachable!("handled above"),
                 StaticFileSegment::Headers => self.ensure_invariants::<_, tables::Headers>(
                     provider,
                     segment,
@@ -769,6 +855,142 @@ impl StaticFileProvider {
         Ok(unwind_target.map(PipelineTarget::Unwind))
     }
 
+    /// Heals any file level inconsistency of the blob sidecars segment, and removes the blob
+    /// sidecars of blocks above the highest block in the database.
+    fn ensure_blob_sidecars_invariants<Provider>(&self, provider: &Provider) -> ProviderResult<()>
+    where
+        Provider: BlockReader,
+    {
+        let segment = StaticFileSegment::BlobSidecars;
+        if self.access.is_read_only() {
+            return self.check_segment_consistency(segment)
+        }
+
+        // Fetching the writer will attempt to heal any file level inconsistency.
+        let mut writer = self.latest_writer(segment)?;
+
+        let last_block = provider.last_block_number()?;
+        if let Some(highest_block) =
+            self.get_highest_static_file_block(segment).filter(|block| *block > last_block)
+        {
+            info!(
+                target: "reth::providers::static_file",
+                from = highest_block,
+                to = last_block,
+                ?segment,
+                "Unwinding static file segment."
+            );
+            writer.prune_blob_sidecars(highest_block - last_block)?;
+            writer.commit()?;
+        }
+
+        Ok(())
+    }
+
+    /// Heals any file level inconsistency of a changeset segment, and checks its highest block
+    /// against the database.
+    ///
+    /// Blocks don't necessarily change the state, so unlike [`Self::ensure_invariants`], continuity
+    /// with the database table can't be checked by its keys. Instead:
+    /// * if database rows above the highest static file block were pruned, static file data was
+    ///   lost, and the unwind target is returned.
+    /// * if the [`StageId::Execution`] checkpoint is behind, the extra static file rows are
+    ///   removed.
+    fn ensure_change_sets_invariants<Provider>(
+        &self,
+        provider: &Provider,
+        segment: StaticFileSegment,
+    ) -> ProviderResult<Option<BlockNumber>>
+    where
+        Provider: DBProvider + StageCheckpointReader,
+    {
+        let initial_highest_block = self.get_highest_static_file_block(segment);
+        if self.access.is_read_only() {
+            self.check_segment_consistency(segment)?;
+        } else {
+            // Fetching the writer will attempt to heal any file level inconsistency.
+            self.latest_writer(segment)?;
+        }
+
+        let highest_block = self.get_highest_static_file_block(segment);
+        if initial_highest_block != highest_block {
+            info!(
+                target: "reth::providers::static_file",
+                ?initial_highest_block,
+                unwind_target = highest_block,
+                ?segment,
+                "Setting unwind target."
+            );
+            return Ok(Some(highest_block.unwrap_or_default()))
+        }
+        let highest_block = highest_block.unwrap_or_default();
+
+        let (prune_segment, db_last_block) = if segment == StaticFileSegment::AccountChangeSets {
+            (
+                PruneSegment::AccountChangeSets,
+                provider
+                    .tx_ref()
+                    .cursor_read::<tables::AccountChangeSets>()?
+                    .last()?
+                    .map(|(key, _)| key),
+            )
+        } else {
+            (
+                PruneSegment::StorageChangeSets,
+                provider
+                    .tx_ref()
+                    .cursor_read::<tables::StorageChangeSets>()?
+                    .last()?
+                    .map(|(key, _)| key.block_number()),
+            )
+        };
+
+        // If the database rows were pruned past the static files, then we have most likely lost
+        // static file data and need to unwind so we can load it again.
+        let highest_pruned_block = provider
+            .tx_ref()
+            .get::<tables::PruneCheckpoints>(prune_segment)?
+            .and_then(|checkpoint| checkpoint.block_number);
+        if highest_pruned_block.is_some_and(|block| block > highest_block) {
+            info!(
+                target: "reth::providers::static_file",
+                ?highest_pruned_block,
+                unwind_target = highest_block,
+                ?segment,
+                "Setting unwind target."
+            );
+            return Ok(Some(highest_block))
+        }
+
+        if db_last_block.is_some_and(|block| block > highest_block) {
+            return Ok(None)
+        }
+
+        // If the checkpoint is behind, then we failed to do a database commit **but committed** to
+        // static files on unwinding the execution stage. All we need to do is to prune the extra
+        // static file rows.
+        let checkpoint_block_number =
+            provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+        if checkpoint_block_number < highest_block {
+            info!(
+                target: "reth::providers",
+                ?segment,
+                from = highest_block,
+                to = checkpoint_block_number,
+                "Unwinding static file segment."
+            );
+            let mut writer = self.latest_writer(segment)?;
+            if segment == StaticFileSegment::AccountChangeSets {
+                writer.prune_account_change_sets(highest_block - checkpoint_block_number)?;
+            } else {
+                writer.prune_storage_change_sets(highest_block - checkpoint_block_number)?;
+            }
+            writer.commit()?;
+        }
+
+        Ok(None)
+    }
+
     /// Checks consistency of the latest static file segment and throws an error if at fault.
     /// Read-only.
     pub fn check_segment_consistency(&self, segment: StaticFileSegment) -> ProviderResult<()> {
@@ -846,6 +1068,10 @@ impl StaticFileProvider {
                 StaticFileSegment::Headers => StageId::Headers,
                 StaticFileSegment::Transactions => StageId::Bodies,
                 StaticFileSegment::Receipts => StageId::Execution,
+                StaticFileSegment::BlobSidecars => StageId::Finish,
+                StaticFileSegment::AccountChangeSets | StaticFileSegment::StorageChangeSets => {
+                    StageId::Execution
+                }
             })?
             .unwrap_or_default()
             .block_number;
@@ -897,6 +1123,13 @@ impl StaticFileProvider {
         self.static_files_max_block.read().get(&segment).copied()
     }
 
+    /// Gets the start block of the lowest static file if it exists for a static file segment.
+    ///
+    /// If there is nothing on disk for the given segment, this will return [`None`].
+    pub fn get_lowest_static_file_block(&self, segment: StaticFileSegment) -> Option<BlockNumber> {
+        self.static_files_min_block.read().get(&segment).copied()
+    }
+
     /// Gets the highest static file transaction.
     ///
     /// If there is nothing on disk for the given segment, this will return [`None`].
@@ -913,6 +1146,11 @@ impl StaticFileProvider {
             headers: self.get_highest_static_file_block(StaticFileSegment::Headers),
             receipts: self.get_highest_static_file_block(StaticFileSegment::Receipts),
             transactions: self.get_highest_static_file_block(StaticFileSegment::Transactions),
+            blob_sidecars: self.get_highest_static_file_block(StaticFileSegment::BlobSidecars),
+            account_change_sets: self
+                .get_highest_static_file_block(StaticFileSegment::AccountChangeSets),
+            storage_change_sets: self
+                .get_highest_static_file_block(StaticFileSegment::StorageChangeSets),
         }
     }
 
@@ -924,8 +1162,9 @@ impl StaticFileProvider {
         func: impl Fn(StaticFileJarProvider<'_>) -> ProviderResult<Option<T>>,
     ) -> ProviderResult<Option<T>> {
         if let Some(highest_block) = self.get_highest_static_file_block(segment) {
+            let lowest_block = self.get_lowest_static_file_block(segment).unwrap_or_default();
             let mut range = self.find_fixed_range(highest_block);
-            while range.end() > 0 {
+            while range.end() > 0 && range.start() >= lowest_block {
                 if let Some(res) = func(self.get_or_create_jar_provider(segment, &range)?)? {
                     return Ok(Some(res))
                 }
@@ -956,7 +1195,10 @@ impl StaticFileProvider {
         P: FnMut(&T) -> bool,
     {
         let get_provider = |start: u64| match segment {
-            StaticFileSegment::Headers => {
+            StaticFileSegment::Headers |
+            StaticFileSegment::BlobSidecars |
+            StaticFileSegment::AccountChangeSets |
+            StaticFileSegment::StorageChangeSets => {
                 self.get_segment_provider_from_block(segment, start, None)
             }
             StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
@@ -994,7 +1236,7 @@ impl StaticFileProvider {
                                 "Could not find block or tx number on a range request"
                             );
 
-                            let err = if segment.is_headers() {
+                            let err = if segment.is_block_based() {
                                 ProviderError::MissingStaticFileBlock(segment, number)
                             } else {
                                 ProviderError::MissingStaticFileTx(segment, number)
@@ -1032,7 +1274,10 @@ impl StaticFileProvider {
         T: std::fmt::Debug,
     {
         let get_provider = move |start: u64| match segment {
-            StaticFileSegment::Headers => {
+            StaticFileSegment::Headers |
+            StaticFileSegment::BlobSidecars |
+            StaticFileSegment::AccountChangeSets |
+            StaticFileSegment::StorageChangeSets => {
                 self.get_segment_provider_from_block(segment, start, None)
             }
             StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
@@ -1084,7 +1329,10 @@ impl StaticFileProvider {
     {
         // If there is, check the maximum block or transaction number of the segment.
         let static_file_upper_bound = match segment {
-            StaticFileSegment::Headers => self.get_highest_static_file_block(segment),
+            StaticFileSegment::Headers |
+            StaticFileSegment::BlobSidecars |
+            StaticFileSegment::AccountChangeSets |
+            StaticFileSegment::StorageChangeSets => self.get_highest_static_file_block(segment),
             StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
                 self.get_highest_static_file_tx(segment)
             }
@@ -1126,7 +1374,10 @@ impl StaticFileProvider {
 
         // If there is, check the maximum block or transaction number of the segment.
         if let Some(static_file_upper_bound) = match segment {
-            StaticFileSegment::Headers => self.get_highest_static_file_block(segment),
+            StaticFileSegment::Headers |
+            StaticFileSegment::BlobSidecars |
+            StaticFileSegment::AccountChangeSets |
+            StaticFileSegment::StorageChangeSets => self.get_highest_static_file_block(segment),
             StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
                 self.get_highest_static_file_tx(segment)
             }
@@ -1642,6 +1893,141 @@ impl WithdrawalsProvider for StaticFileProvider {
     }
 }
 
+impl BlobSidecarsProvider for StaticFileProvider {
+    fn blob_sidecars_by_block(
+        &self,
+        number: BlockNumber,
+    ) -> ProviderResult<Option<StoredBlockBlobSidecars>> {
+        self.get_segment_provider_from_block(StaticFileSegment::BlobSidecars, number, None)
+            .and_then(|provider| provider.blob_sidecars_by_block(number))
+            .or_else(|err| {
+                if let ProviderError::MissingStaticFileBlock(_, _) = err {
+                    Ok(None)
+                } else {
+                    Err(err)
+                }
+            })
+    }
+
+    fn blob_sidecar_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<(BlockNumber, StoredBlobSidecar)>> {
+        let segment = StaticFileSegment::BlobSidecars;
+        let Some(highest_block) = self.get_highest_static_file_block(segment) else {
+            return Ok(None)
+        };
+        let lowest_block = self.get_lowest_static_file_block(segment).unwrap_or_default();
+        let start = lowest_block.max(*block_range.start());
+        let mut end = highest_block.min(*block_range.end());
+
+        // Only the static files overlapping the range are searched, most recent first.
+        while start <= end {
+            let fixed_range = self.find_fixed_range(end);
+            let jar_provider = self.get_or_create_jar_provider(segment, &fixed_range)?;
+            let jar_range = fixed_range.start().max(start)..=end;
+            if let Some(res) = jar_provider.blob_sidecar_by_versioned_hash(versioned_hash, jar_range)?
+            {
+                return Ok(Some(res))
+            }
+            if fixed_range.start() <= start {
+                break
+            }
+            end = fixed_range.start() - 1;
+        }
+
+        Ok(None)
+    }
+}
+
+impl BlobSidecarsWriter for StaticFileProvider {
+    fn write_blob_sidecars(
+        &self,
+        blocks: Vec<(BlockNumber, StoredBlockBlobSidecars)>,
+    ) -> ProviderResult<()> {
+        let Some((first_block, _)) = blocks.first() else { return Ok(()) };
+        let segment = StaticFileSegment::BlobSidecars;
+
+        let mut writer = self.get_writer(*first_block, segment)?;
+
+        // Replace any retained blob sidecars of reorged blocks.
+        if let Some(highest_block) =
+            self.get_highest_static_file_block(segment).filter(|block| block >= first_block)
+        {
+            writer.prune_blob_sidecars(highest_block - first_block + 1)?;
+            writer.commit()?;
+        }
+
+        for (number, sidecars) in blocks {
+            writer.append_blob_sidecars(number, sidecars)?;
+        }
+        writer.commit()
+    }
+}
+
+impl StaticFileProvider {
+    /// Returns the account changeset of a block, if it has been moved to static files.
+    pub fn account_change_set(
+        &self,
+        number: BlockNumber,
+    ) -> ProviderResult<Option<StoredBlockAccountChangeSet>> {
+        self.get_segment_provider_from_block(StaticFileSegment::AccountChangeSets, number, None)
+            .and_then(|provider| provider.account_change_set(number))
+            .or_else(|err| {
+                if let ProviderError::MissingStaticFileBlock(_, _) = err {
+                    Ok(None)
+                } else {
+                    Err(err)
+                }
+            })
+    }
+
+    /// Returns the storage changeset of a block, if it has been moved to static files.
+    pub fn storage_change_set(
+        &self,
+        number: BlockNumber,
+    ) -> ProviderResult<Option<StoredBlockStorageChangeSet>> {
+        self.get_segment_provider_from_block(StaticFileSegment::StorageChangeSets, number, None)
+            .and_then(|provider| provider.storage_change_set(number))
+            .or_else(|err| {
+                if let ProviderError::MissingStaticFileBlock(_, _) = err {
+                    Ok(None)
+                } else {
+                    Err(err)
+                }
+            })
+    }
+}
+
+impl ChangeSetReader for StaticFileProvider {
+    fn account_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<AccountBeforeTx>> {
+        self.get_segment_provider_from_block(
+            StaticFileSegment::AccountChangeSets,
+            block_number,
+            None,
+        )?
+        .account_block_changeset(block_number)
+    }
+}
+
+impl StorageChangeSetReader for StaticFileProvider {
+    fn storage_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        self.get_segment_provider_from_block(
+            StaticFileSegment::StorageChangeSets,
+            block_number,
+            None,
+        )?
+        .storage_changeset(block_number)
+    }
+}
+
 impl StatsReader for StaticFileProvider {
     fn count_entries<T: Table>(&self) -> ProviderResult<usize> {
         match T::NAME {
@@ -57,18 +57,24 @@ mod tests {
     use super::*;
     use crate::{test_utils::create_test_provider_factory, HeaderProvider};
     use alloy_consensus::Transaction;
+    use alloy_eips::eip4844::{Blob, Bytes48};
     use alloy_primitives::{BlockHash, TxNumber, B256, U256};
     use rand::seq::SliceRandom;
     use reth_db::{
         test_utils::create_test_static_files_dir, CanonicalHeaders, HeaderNumbers,
         HeaderTerminalDifficulties, Headers,
     };
-    use reth_db_api::transaction::DbTxMut;
+    use reth_db_api::{
+        models::{StoredBlobSidecar, StoredBlockBlobSidecars},
+        transaction::DbTxMut,
+    };
     use reth_primitives::{
         static_file::{find_fixed_range, SegmentRangeInclusive, DEFAULT_BLOCKS_PER_STATIC_FILE},
-        Header, Receipt, TransactionSignedNoHash,
+        BlobTransactionSidecar, Header, Receipt, TransactionSignedNoHash,
+    };
+    use reth_storage_api::{
+        BlobSidecarsProvider, BlobSidecarsWriter, ReceiptProvider, TransactionsProvider,
     };
-    use reth_storage_api::{ReceiptProvider, TransactionsProvider};
     use reth_testing_utils::generators::{self, random_header_range};
     use std::{fmt::Debug, fs, ops::Range, path::Path};
 
@@ -547,4 +553,92 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_blob_sidecars() {
+        let (static_dir, _) = create_test_static_files_dir();
+        let segment = StaticFileSegment::BlobSidecars;
+        let blocks_per_file = 10;
+
+        let sf_rw = StaticFileProvider::read_write(&static_dir)
+            .expect("Failed to create static file provider")
+            .with_custom_blocks_per_file(blocks_per_file);
+
+        let block_sidecars = |tx_index: u64, byte: u8| StoredBlockBlobSidecars {
+            sidecars: vec![StoredBlobSidecar {
+                tx_index,
+                sidecar: BlobTransactionSidecar {
+                    blobs: vec![Blob::repeat_byte(byte)],
+                    commitments: vec![Bytes48::repeat_byte(byte)],
+                    proofs: vec![Bytes48::repeat_byte(byte)],
+                },
+            }],
+            missing: Vec::new(),
+        };
+        let first = block_sidecars(1, 1);
+        let first_hash = first.versioned_hashes().next().unwrap();
+
+        // The segment starts at the file range of the first retained block
+        sf_rw
+            .write_blob_sidecars(vec![
+                (25, StoredBlockBlobSidecars::default()),
+                (26, first.clone()),
+                (27, StoredBlockBlobSidecars::default()),
+            ])
+            .unwrap();
+        assert_eq!(sf_rw.get_lowest_static_file_block(segment), Some(20));
+        assert_eq!(sf_rw.get_highest_static_file_block(segment), Some(27));
+        assert_eq!(sf_rw.blob_sidecars_by_block(26).unwrap(), Some(first.clone()));
+        assert_eq!(
+            sf_rw.blob_sidecar_by_versioned_hash(first_hash, 0..=u64::MAX).unwrap(),
+            Some((26, first.sidecars[0].clone()))
+        );
+        // Blocks outside of the range are not searched
+        assert_eq!(sf_rw.blob_sidecar_by_versioned_hash(first_hash, 27..=30).unwrap(), None);
+
+        // Reorged blocks are replaced
+        let second = block_sidecars(0, 2);
+        sf_rw
+            .write_blob_sidecars(vec![
+                (26, StoredBlockBlobSidecars::default()),
+                (27, second.clone()),
+            ])
+            .unwrap();
+        assert_eq!(sf_rw.blob_sidecar_by_versioned_hash(first_hash, 0..=u64::MAX).unwrap(), None);
+        assert_eq!(sf_rw.blob_sidecars_by_block(27).unwrap(), Some(second.clone()));
+
+        // Gaps across files are filled with empty rows
+        sf_rw.write_blob_sidecars(vec![(42, first.clone())]).unwrap();
+        assert_eq!(sf_rw.get_highest_static_file_block(segment), Some(42));
+        assert_eq!(
+            sf_rw.blob_sidecars_by_block(35).unwrap(),
+            Some(StoredBlockBlobSidecars::default())
+        );
+        assert_eq!(
+            sf_rw.blob_sidecar_by_versioned_hash(first_hash, 20..=42).unwrap(),
+            Some((42, first.sidecars[0].clone()))
+        );
+        assert_eq!(
+            sf_rw
+                .blob_sidecar_by_versioned_hash(second.versioned_hashes().next().unwrap(), 27..=42)
+                .unwrap(),
+            Some((27, second.sidecars[0].clone()))
+        );
+
+        // Only whole files below the given block are deleted
+        assert_eq!(sf_rw.delete_jars_below(segment, 35).unwrap(), (10, Some(29)));
+        assert_eq!(sf_rw.get_lowest_static_file_block(segment), Some(30));
+        assert_eq!(sf_rw.blob_sidecars_by_block(27).unwrap(), None);
+        assert_eq!(
+            sf_rw
+                .blob_sidecar_by_versioned_hash(second.versioned_hashes().next().unwrap(), 0..=42)
+                .unwrap(),
+            None
+        );
+
+        // The highest file is never deleted
+        assert_eq!(sf_rw.delete_jars_below(segment, 100).unwrap(), (10, Some(39)));
+        assert_eq!(sf_rw.get_lowest_static_file_block(segment), Some(40));
+        assert_eq!(sf_rw.blob_sidecars_by_block(42).unwrap(), Some(first));
+    }
 }
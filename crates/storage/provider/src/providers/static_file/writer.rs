@@ -5,7 +5,13 @@ use crate::providers::static_file::metrics::StaticFileProviderOperation;
 use alloy_primitives::{BlockHash, BlockNumber, TxNumber, U256};
 use parking_lot::{lock_api::RwLockWriteGuard, RawRwLock, RwLock};
 use reth_codecs::Compact;
-use reth_db_api::models::CompactU256;
+use reth_db_api::{
+    models::{
+        CompactU256, StoredBlockAccountChangeSet, StoredBlockBlobSidecars,
+        StoredBlockBlobVersionedHashes, StoredBlockStorageChangeSet,
+    },
+    table::Compress,
+};
 use reth_nippy_jar::{NippyJar, NippyJarError, NippyJarWriter};
 use reth_primitives::{
     static_file::{SegmentHeader, SegmentRangeInclusive},
@@ -29,6 +35,9 @@ pub(crate) struct StaticFileWriters {
     headers: RwLock<Option<StaticFileProviderRW>>,
     transactions: RwLock<Option<StaticFileProviderRW>>,
     receipts: RwLock<Option<StaticFileProviderRW>>,
+    blob_sidecars: RwLock<Option<StaticFileProviderRW>>,
+    account_change_sets: RwLock<Option<StaticFileProviderRW>>,
+    storage_change_sets: RwLock<Option<StaticFileProviderRW>>,
 }
 
 impl StaticFileWriters {
@@ -41,6 +50,9 @@ impl StaticFileWriters {
             StaticFileSegment::Headers => self.headers.write(),
             StaticFileSegment::Transactions => self.transactions.write(),
             StaticFileSegment::Receipts => self.receipts.write(),
+            StaticFileSegment::BlobSidecars => self.blob_sidecars.write(),
+            StaticFileSegment::AccountChangeSets => self.account_change_sets.write(),
+            StaticFileSegment::StorageChangeSets => self.storage_change_sets.write(),
         };
 
         if write_guard.is_none() {
@@ -51,7 +63,14 @@ impl StaticFileWriters {
     }
 
     pub(crate) fn commit(&self) -> ProviderResult<()> {
-        for writer_lock in [&self.headers, &self.transactions, &self.receipts] {
+        for writer_lock in [
+            &self.headers,
+            &self.transactions,
+            &self.receipts,
+            &self.blob_sidecars,
+            &self.account_change_sets,
+            &self.storage_change_sets,
+        ] {
             let mut writer = writer_lock.write();
             if let Some(writer) = writer.as_mut() {
                 writer.commit()?;
@@ -187,7 +206,7 @@ impl StaticFileProviderRW {
     /// [`NippyJarWriter`] for more on healing.
     fn ensure_end_range_consistency(&mut self) -> ProviderResult<()> {
         // If we have lost rows (in this run or previous), we need to update the [SegmentHeader].
-        let expected_rows = if self.user_header().segment().is_headers() {
+        let expected_rows = if self.user_header().segment().is_block_based() {
             self.user_header().block_len().unwrap_or_default()
         } else {
             self.user_header().tx_len().unwrap_or_default()
@@ -217,6 +236,10 @@ impl StaticFileProviderRW {
                 StaticFileSegment::Receipts => {
                     self.prune_receipt_data(to_delete, last_block_number.expect("should exist"))?
                 }
+                StaticFileSegment::BlobSidecars => self.prune_blob_sidecar_data(to_delete)?,
+                StaticFileSegment::AccountChangeSets | StaticFileSegment::StorageChangeSets => {
+                    self.prune_change_set_data(to_delete)?
+                }
             }
         }
 
@@ -289,6 +312,10 @@ impl StaticFileProviderRW {
         //
         // If that expected block start is 0, then it means that there's no actual block data, and
         // there's no block data in static files.
+        //
+        // Segments which don't start at block 0 (eg. `BlobSidecars`) might not have a previous
+        // static file either.
+        let segment = self.writer.user_header().segment();
         let segment_max_block = self
             .writer
             .user_header()
@@ -296,11 +323,25 @@ impl StaticFileProviderRW {
             .as_ref()
             .map(|block_range| block_range.end())
             .or_else(|| {
-                (self.writer.user_header().expected_block_start() > 0)
-                    .then(|| self.writer.user_header().expected_block_start() - 1)
+                let expected_block_start = self.writer.user_header().expected_block_start();
+                self.has_previous_static_file(segment, expected_block_start)
+                    .then(|| expected_block_start - 1)
             });
 
-        self.reader().update_index(self.writer.user_header().segment(), segment_max_block)
+        self.reader().update_index(segment, segment_max_block)
+    }
+
+    /// Returns `true` if there is a static file of the segment before the one starting at
+    /// `block_start`.
+    fn has_previous_static_file(
+        &self,
+        segment: StaticFileSegment,
+        block_start: BlockNumber,
+    ) -> bool {
+        block_start > 0 &&
+            self.reader()
+                .get_lowest_static_file_block(segment)
+                .is_some_and(|lowest_block| lowest_block < block_start)
     }
 
     /// Allows to increment the [`SegmentHeader`] end block. It will commit the current static file,
@@ -352,15 +393,7 @@ impl StaticFileProviderRW {
     /// Verifies if the incoming block number matches the next expected block number
     /// for a static file. This ensures data continuity when adding new blocks.
     fn check_next_block_number(&self, expected_block_number: u64) -> ProviderResult<()> {
-        // The next static file block number can be found by checking the one after block_end.
-        // However if it's a new file that hasn't been added any data, its block range will actually
-        // be None. In that case, the next block will be found on `expected_block_start`.
-        let next_static_file_block = self
-            .writer
-            .user_header()
-            .block_end()
-            .map(|b| b + 1)
-            .unwrap_or_else(|| self.writer.user_header().expected_block_start());
+        let next_static_file_block = self.next_block_number();
 
         if expected_block_number != next_static_file_block {
             return Err(ProviderError::UnexpectedStaticFileBlockNumber(
@@ -372,6 +405,18 @@ impl StaticFileProviderRW {
         Ok(())
     }
 
+    /// Returns the next block number expected by the static file.
+    pub fn next_block_number(&self) -> BlockNumber {
+        // The next static file block number can be found by checking the one after block_end.
+        // However if it's a new file that hasn't been added any data, its block range will actually
+        // be None. In that case, the next block will be found on `expected_block_start`.
+        self.writer
+            .user_header()
+            .block_end()
+            .map(|b| b + 1)
+            .unwrap_or_else(|| self.writer.user_header().expected_block_start())
+    }
+
     /// Truncates a number of rows from disk. It deletes and loads an older static file if block
     /// goes beyond the start of the current block range.
     ///
@@ -384,7 +429,10 @@ impl StaticFileProviderRW {
         let segment = self.writer.user_header().segment();
         while remaining_rows > 0 {
             let len = match segment {
-                StaticFileSegment::Headers => {
+                StaticFileSegment::Headers |
+                StaticFileSegment::BlobSidecars |
+                StaticFileSegment::AccountChangeSets |
+                StaticFileSegment::StorageChangeSets => {
                     self.writer.user_header().block_len().unwrap_or_default()
                 }
                 StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
@@ -398,12 +446,12 @@ impl StaticFileProviderRW {
                 let block_start = self.writer.user_header().expected_block_start();
 
                 // We only delete the file if it's NOT the first static file AND:
-                // * it's a Header segment  OR
+                // * it's a block-based segment  OR
                 // * it's a tx-based segment AND `last_block` is lower than the first block of this
                 //   file's block range. Otherwise, having no rows simply means that this block
                 //   range has no transactions, but the file should remain.
-                if block_start != 0 &&
-                    (segment.is_headers() || last_block.is_some_and(|b| b < block_start))
+                if self.has_previous_static_file(segment, block_start) &&
+                    (segment.is_block_based() || last_block.is_some_and(|b| b < block_start))
                 {
                     self.delete_current_and_open_previous()?;
                 } else {
@@ -481,6 +529,17 @@ impl StaticFileProviderRW {
         Ok(())
     }
 
+    /// Appends a column value which is encoded with [`Compress`] instead of [`Compact`].
+    fn append_compressed_column<T: Compress>(&mut self, column: T) -> ProviderResult<()> {
+        self.buf.clear();
+        column.compress_to_buf(&mut self.buf);
+
+        self.writer
+            .append_column(Some(Ok(&self.buf)))
+            .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+        Ok(())
+    }
+
     /// Appends to tx number-based static file.
     ///
     /// Returns the current [`TxNumber`] as seen in the static file.
@@ -534,6 +593,109 @@ impl StaticFileProviderRW {
         Ok(block_number)
     }
 
+    /// Appends the blob sidecars of a block to static file.
+    ///
+    /// Blob sidecars are not retained for every block, so any gap between the last block of the
+    /// static file and `block_number` is filled with empty rows.
+    ///
+    /// It **CALLS** `increment_block()` since the number of rows is equal to the number of
+    /// blocks.
+    ///
+    /// Returns the current [`BlockNumber`] as seen in the static file.
+    pub fn append_blob_sidecars(
+        &mut self,
+        block_number: BlockNumber,
+        sidecars: StoredBlockBlobSidecars,
+    ) -> ProviderResult<BlockNumber> {
+        let start = Instant::now();
+        self.ensure_no_queued_prune()?;
+
+        debug_assert!(self.writer.user_header().segment() == StaticFileSegment::BlobSidecars);
+
+        let next_block_number = self.next_block_number();
+        if block_number < next_block_number {
+            return Err(ProviderError::UnexpectedStaticFileBlockNumber(
+                StaticFileSegment::BlobSidecars,
+                block_number,
+                next_block_number,
+            ))
+        }
+
+        for number in next_block_number..block_number {
+            self.increment_block(number)?;
+            self.append_compressed_column(StoredBlockBlobSidecars::default())?;
+            self.append_column(StoredBlockBlobVersionedHashes::default())?;
+        }
+
+        let block_number = self.increment_block(block_number)?;
+        let versioned_hashes = StoredBlockBlobVersionedHashes::from(&sidecars);
+        self.append_compressed_column(sidecars)?;
+        self.append_column(versioned_hashes)?;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_segment_operation(
+                StaticFileSegment::BlobSidecars,
+                StaticFileProviderOperation::Append,
+                Some(start.elapsed()),
+            );
+        }
+
+        Ok(block_number)
+    }
+
+    /// Appends the account changeset of a block to static file.
+    ///
+    /// It **CALLS** `increment_block()` since the number of changesets is equal to the number of
+    /// blocks.
+    ///
+    /// Returns the current [`BlockNumber`] as seen in the static file.
+    pub fn append_account_change_set(
+        &mut self,
+        block_number: BlockNumber,
+        change_set: &StoredBlockAccountChangeSet,
+    ) -> ProviderResult<BlockNumber> {
+        debug_assert!(self.writer.user_header().segment() == StaticFileSegment::AccountChangeSets);
+        self.append_change_set(block_number, change_set)
+    }
+
+    /// Appends the storage changeset of a block to static file.
+    ///
+    /// It **CALLS** `increment_block()` since the number of changesets is equal to the number of
+    /// blocks.
+    ///
+    /// Returns the current [`BlockNumber`] as seen in the static file.
+    pub fn append_storage_change_set(
+        &mut self,
+        block_number: BlockNumber,
+        change_set: &StoredBlockStorageChangeSet,
+    ) -> ProviderResult<BlockNumber> {
+        debug_assert!(self.writer.user_header().segment() == StaticFileSegment::StorageChangeSets);
+        self.append_change_set(block_number, change_set)
+    }
+
+    /// Appends a changeset row to a changeset static file.
+    fn append_change_set<T: Compact>(
+        &mut self,
+        block_number: BlockNumber,
+        change_set: T,
+    ) -> ProviderResult<BlockNumber> {
+        let start = Instant::now();
+        self.ensure_no_queued_prune()?;
+
+        let block_number = self.increment_block(block_number)?;
+        self.append_column(change_set)?;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_segment_operation(
+                self.writer.user_header().segment(),
+                StaticFileProviderOperation::Append,
+                Some(start.elapsed()),
+            );
+        }
+
+        Ok(block_number)
+    }
+
     /// Appends transaction to static file.
     ///
     /// It **DOES NOT CALL** `increment_block()`, it should be handled elsewhere. There might be
@@ -661,6 +823,24 @@ impl StaticFileProviderRW {
         self.queue_prune(to_delete, None)
     }
 
+    /// Adds an instruction to prune the blob sidecars of `to_delete` blocks during commit.
+    pub fn prune_blob_sidecars(&mut self, to_delete: u64) -> ProviderResult<()> {
+        debug_assert_eq!(self.writer.user_header().segment(), StaticFileSegment::BlobSidecars);
+        self.queue_prune(to_delete, None)
+    }
+
+    /// Adds an instruction to prune the account changesets of `to_delete` blocks during commit.
+    pub fn prune_account_change_sets(&mut self, to_delete: u64) -> ProviderResult<()> {
+        debug_assert_eq!(self.writer.user_header().segment(), StaticFileSegment::AccountChangeSets);
+        self.queue_prune(to_delete, None)
+    }
+
+    /// Adds an instruction to prune the storage changesets of `to_delete` blocks during commit.
+    pub fn prune_storage_change_sets(&mut self, to_delete: u64) -> ProviderResult<()> {
+        debug_assert_eq!(self.writer.user_header().segment(), StaticFileSegment::StorageChangeSets);
+        self.queue_prune(to_delete, None)
+    }
+
     /// Adds an instruction to prune `to_delete` elements during commit.
     ///
     /// Note: `last_block` refers to the block the unwinds ends at if dealing with transaction-based
@@ -750,6 +930,45 @@ impl StaticFileProviderRW {
         Ok(())
     }
 
+    /// Prunes the blob sidecars of the last `to_delete` blocks from the data file.
+    fn prune_blob_sidecar_data(&mut self, to_delete: u64) -> ProviderResult<()> {
+        let start = Instant::now();
+
+        debug_assert!(self.writer.user_header().segment() == StaticFileSegment::BlobSidecars);
+
+        self.truncate(to_delete, None)?;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_segment_operation(
+                StaticFileSegment::BlobSidecars,
+                StaticFileProviderOperation::Prune,
+                Some(start.elapsed()),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Prunes the changesets of the last `to_delete` blocks from the data file.
+    fn prune_change_set_data(&mut self, to_delete: u64) -> ProviderResult<()> {
+        let start = Instant::now();
+
+        let segment = self.writer.user_header().segment();
+        debug_assert!(segment.is_change_sets());
+
+        self.truncate(to_delete, None)?;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_segment_operation(
+                segment,
+                StaticFileProviderOperation::Prune,
+                Some(start.elapsed()),
+            );
+        }
+
+        Ok(())
+    }
+
     fn reader(&self) -> StaticFileProvider {
         Self::upgrade_provider_to_strong_reference(&self.reader)
     }
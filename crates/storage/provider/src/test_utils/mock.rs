@@ -1,10 +1,10 @@
 use crate::{
     traits::{BlockSource, ReceiptProvider},
-    AccountReader, BlockExecutionReader, BlockHashReader, BlockIdReader, BlockNumReader,
-    BlockReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, DatabaseProvider,
-    EvmEnvProvider, HeaderProvider, ReceiptProviderIdExt, StateProvider, StateProviderBox,
-    StateProviderFactory, StateReader, StateRootProvider, TransactionVariant, TransactionsProvider,
-    WithdrawalsProvider,
+    AccountReader, AddressTransactionsReader, BlobSidecarsProvider, BlockExecutionReader,
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
+    ChainSpecProvider, ChangeSetReader, DatabaseProvider, EvmEnvProvider, HeaderProvider,
+    ReceiptProviderIdExt, StateProvider, StateProviderBox, StateProviderFactory, StateReader,
+    StateRootProvider, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 use alloy_consensus::constants::EMPTY_ROOT_HASH;
 use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumberOrTag};
@@ -17,17 +17,21 @@ use alloy_primitives::{
 use parking_lot::Mutex;
 use reth_chainspec::{ChainInfo, ChainSpec};
 use reth_db::mock::{DatabaseMock, TxMock};
-use reth_db_api::models::{AccountBeforeTx, StoredBlockBodyIndices};
+use reth_db_api::models::{
+    AccountBeforeTx, BlockNumberAddress, StoredBlobSidecar, StoredBlockBlobSidecars,
+    StoredBlockBodyIndices,
+};
 use reth_evm::ConfigureEvmEnv;
 use reth_execution_types::{Chain, ExecutionOutcome};
 use reth_primitives::{
     Account, Block, BlockWithSenders, Bytecode, GotExpected, Header, Receipt, SealedBlock,
-    SealedBlockWithSenders, SealedHeader, TransactionMeta, TransactionSigned,
+    SealedBlockWithSenders, SealedHeader, StorageEntry, TransactionMeta, TransactionSigned,
     TransactionSignedNoHash, Withdrawal, Withdrawals,
 };
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    DatabaseProviderFactory, StageCheckpointReader, StateProofProvider, StorageRootProvider,
+    DatabaseProviderFactory, StageCheckpointReader, StateProofProvider, StateRangeProvider,
+    StorageChangeSetReader, StorageRootProvider,
 };
 use reth_storage_errors::provider::{ConsistentViewError, ProviderError, ProviderResult};
 use reth_trie::{
@@ -669,6 +673,38 @@ impl StateProofProvider for MockEthProvider {
     }
 }
 
+impl StateRangeProvider for MockEthProvider {
+    fn account_range(
+        &self,
+        _input: TrieInput,
+        _start: B256,
+        _limit: usize,
+        _storage_limit: usize,
+    ) -> ProviderResult<reth_trie::range::AccountRange> {
+        Ok(Default::default())
+    }
+
+    fn storage_range(
+        &self,
+        _address: Address,
+        _hashed_storage: HashedStorage,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<reth_trie::range::StorageRange> {
+        Ok(Default::default())
+    }
+
+    fn hashed_storage_range(
+        &self,
+        _hashed_address: B256,
+        _hashed_storage: HashedStorage,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<reth_trie::range::StorageRange> {
+        Ok(Default::default())
+    }
+}
+
 impl StateProvider for MockEthProvider {
     fn storage(
         &self,
@@ -809,6 +845,23 @@ impl WithdrawalsProvider for MockEthProvider {
     }
 }
 
+impl BlobSidecarsProvider for MockEthProvider {
+    fn blob_sidecars_by_block(
+        &self,
+        _number: BlockNumber,
+    ) -> ProviderResult<Option<StoredBlockBlobSidecars>> {
+        Ok(None)
+    }
+
+    fn blob_sidecar_by_versioned_hash(
+        &self,
+        _versioned_hash: B256,
+        _block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<(BlockNumber, StoredBlobSidecar)>> {
+        Ok(None)
+    }
+}
+
 impl ChangeSetReader for MockEthProvider {
     fn account_block_changeset(
         &self,
@@ -818,6 +871,35 @@ impl ChangeSetReader for MockEthProvider {
     }
 }
 
+impl StorageChangeSetReader for MockEthProvider {
+    fn storage_changeset(
+        &self,
+        _block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        Ok(Vec::default())
+    }
+}
+
+impl AddressTransactionsReader for MockEthProvider {
+    fn address_transactions(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<TxNumber>,
+        _limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn address_transactions_rev(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<TxNumber>,
+        _limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        Ok(Vec::default())
+    }
+}
+
 impl BlockExecutionReader for MockEthProvider {
     fn get_block_and_execution_range(
         &self,
@@ -1,10 +1,7 @@
 use crate::{providers::StaticFileProvider, HashingWriter, ProviderFactory, TrieWriter};
 use alloy_primitives::B256;
 use reth_chainspec::{ChainSpec, MAINNET};
-use reth_db::{
-    test_utils::{create_test_rw_db, create_test_static_files_dir, TempDatabase},
-    DatabaseEnv,
-};
+use reth_db::test_utils::{create_test_static_files_dir, TempDatabase};
 use reth_errors::ProviderResult;
 use reth_node_types::{NodeTypesWithDB, NodeTypesWithDBAdapter};
 use reth_primitives::{Account, StorageEntry};
@@ -27,8 +24,16 @@ pub type MockNodeTypes = reth_node_types::AnyNodeTypesWithEngine<
     reth_chainspec::ChainSpec,
 >;
 
+/// Database backing the test provider factories.
+#[cfg(not(feature = "redb"))]
+pub type TestDatabase = reth_db::DatabaseEnv;
+
+/// Database backing the test provider factories.
+#[cfg(feature = "redb")]
+pub type TestDatabase = reth_db::redb::RedbDatabase;
+
 /// Mock [`reth_node_types::NodeTypesWithDB`] for testing.
-pub type MockNodeTypesWithDB<DB = TempDatabase<DatabaseEnv>> =
+pub type MockNodeTypesWithDB<DB = TempDatabase<TestDatabase>> =
     NodeTypesWithDBAdapter<MockNodeTypes, Arc<DB>>;
 
 /// Creates test provider factory with mainnet chain spec.
@@ -41,7 +46,10 @@ pub fn create_test_provider_factory_with_chain_spec(
     chain_spec: Arc<ChainSpec>,
 ) -> ProviderFactory<MockNodeTypesWithDB> {
     let (static_dir, _) = create_test_static_files_dir();
-    let db = create_test_rw_db();
+    #[cfg(not(feature = "redb"))]
+    let db = reth_db::test_utils::create_test_rw_db();
+    #[cfg(feature = "redb")]
+    let db = reth_db::test_utils::create_test_redb_rw_db();
     ProviderFactory::new(
         db,
         chain_spec,
@@ -14,17 +14,22 @@ use reth_chain_state::{
     ForkChoiceSubscriptions,
 };
 use reth_chainspec::{ChainInfo, ChainSpec, MAINNET};
-use reth_db_api::models::{AccountBeforeTx, StoredBlockBodyIndices};
+use reth_db_api::models::{
+    AccountBeforeTx, BlockNumberAddress, StoredBlobSidecar, StoredBlockBlobSidecars,
+    StoredBlockBodyIndices,
+};
 use reth_errors::ProviderError;
 use reth_evm::ConfigureEvmEnv;
 use reth_primitives::{
     Account, Block, BlockWithSenders, Bytecode, Header, Receipt, SealedBlock,
-    SealedBlockWithSenders, SealedHeader, TransactionMeta, TransactionSigned,
+    SealedBlockWithSenders, SealedHeader, StorageEntry, TransactionMeta, TransactionSigned,
     TransactionSignedNoHash, Withdrawal, Withdrawals,
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
-use reth_storage_api::{StateProofProvider, StorageRootProvider};
+use reth_storage_api::{
+    StateProofProvider, StateRangeProvider, StorageChangeSetReader, StorageRootProvider,
+};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
     updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof, TrieInput,
@@ -35,11 +40,12 @@ use tokio::sync::{broadcast, watch};
 use crate::{
     providers::StaticFileProvider,
     traits::{BlockSource, ReceiptProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider, PruneCheckpointReader,
-    ReceiptProviderIdExt, StageCheckpointReader, StateProvider, StateProviderBox,
-    StateProviderFactory, StateRootProvider, StaticFileProviderFactory, TransactionVariant,
-    TransactionsProvider, WithdrawalsProvider,
+    AccountReader, AddressTransactionsReader, BlobSidecarsProvider, BlockHashReader, BlockIdReader,
+    BlockNumReader, BlockReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader,
+    EvmEnvProvider, HeaderProvider, PruneCheckpointReader, ReceiptProviderIdExt,
+    StageCheckpointReader, StateProvider, StateProviderBox, StateProviderFactory,
+    StateRootProvider, StaticFileProviderFactory, TransactionVariant, TransactionsProvider,
+    WithdrawalsProvider,
 };
 
 /// Supports various api interfaces for testing purposes.
@@ -324,6 +330,35 @@ impl ChangeSetReader for NoopProvider {
     }
 }
 
+impl StorageChangeSetReader for NoopProvider {
+    fn storage_changeset(
+        &self,
+        _block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        Ok(Vec::default())
+    }
+}
+
+impl AddressTransactionsReader for NoopProvider {
+    fn address_transactions(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<TxNumber>,
+        _limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn address_transactions_rev(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<TxNumber>,
+        _limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        Ok(Vec::default())
+    }
+}
+
 impl StateRootProvider for NoopProvider {
     fn state_root(&self, _state: HashedPostState) -> ProviderResult<B256> {
         Ok(B256::default())
@@ -394,6 +429,38 @@ impl StateProofProvider for NoopProvider {
     }
 }
 
+impl StateRangeProvider for NoopProvider {
+    fn account_range(
+        &self,
+        _input: TrieInput,
+        _start: B256,
+        _limit: usize,
+        _storage_limit: usize,
+    ) -> ProviderResult<reth_trie::range::AccountRange> {
+        Ok(Default::default())
+    }
+
+    fn storage_range(
+        &self,
+        _address: Address,
+        _hashed_storage: HashedStorage,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<reth_trie::range::StorageRange> {
+        Ok(Default::default())
+    }
+
+    fn hashed_storage_range(
+        &self,
+        _hashed_address: B256,
+        _hashed_storage: HashedStorage,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<reth_trie::range::StorageRange> {
+        Ok(Default::default())
+    }
+}
+
 impl StateProvider for NoopProvider {
     fn storage(
         &self,
@@ -539,6 +606,23 @@ impl WithdrawalsProvider for NoopProvider {
     }
 }
 
+impl BlobSidecarsProvider for NoopProvider {
+    fn blob_sidecars_by_block(
+        &self,
+        _number: BlockNumber,
+    ) -> ProviderResult<Option<StoredBlockBlobSidecars>> {
+        Ok(None)
+    }
+
+    fn blob_sidecar_by_versioned_hash(
+        &self,
+        _versioned_hash: B256,
+        _block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<(BlockNumber, StoredBlobSidecar)>> {
+        Ok(None)
+    }
+}
+
 impl PruneCheckpointReader for NoopProvider {
     fn get_prune_checkpoint(
         &self,
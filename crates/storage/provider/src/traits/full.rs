@@ -1,9 +1,10 @@
 //! Helper provider traits to encapsulate all provider traits for simplicity.
 
 use crate::{
-    AccountReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    EvmEnvProvider, HeaderProvider, StageCheckpointReader, StateProviderFactory,
-    StaticFileProviderFactory, TransactionsProvider,
+    AccountReader, AddressTransactionsReader, BlobSidecarsProvider, BlockReaderIdExt,
+    ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory, EvmEnvProvider, HeaderProvider,
+    PruneCheckpointReader, StageCheckpointReader, StateProviderFactory, StaticFileProviderFactory,
+    StorageChangeSetReader, TransactionsProvider,
 };
 use reth_chain_state::{CanonStateSubscriptions, ForkChoiceSubscriptions};
 use reth_chainspec::EthereumHardforks;
@@ -22,6 +23,10 @@ pub trait FullProvider<N: NodeTypesWithDB>:
     + CanonStateSubscriptions
     + ForkChoiceSubscriptions
     + StageCheckpointReader
+    + PruneCheckpointReader
+    + AddressTransactionsReader
+    + StorageChangeSetReader
+    + BlobSidecarsProvider
     + Clone
     + Unpin
     + 'static
@@ -40,6 +45,10 @@ impl<T, N: NodeTypesWithDB> FullProvider<N> for T where
         + CanonStateSubscriptions
         + ForkChoiceSubscriptions
         + StageCheckpointReader
+        + PruneCheckpointReader
+        + AddressTransactionsReader
+        + StorageChangeSetReader
+        + BlobSidecarsProvider
         + Clone
         + Unpin
         + 'static
@@ -56,6 +65,11 @@ pub trait FullRpcProvider:
     + HeaderProvider
     + TransactionsProvider
     + StageCheckpointReader
+    + PruneCheckpointReader
+    + AddressTransactionsReader
+    + ChangeSetReader
+    + StorageChangeSetReader
+    + BlobSidecarsProvider
     + Clone
     + Unpin
     + 'static
@@ -70,6 +84,11 @@ impl<T> FullRpcProvider for T where
         + HeaderProvider
         + TransactionsProvider
         + StageCheckpointReader
+        + PruneCheckpointReader
+        + AddressTransactionsReader
+        + ChangeSetReader
+        + StorageChangeSetReader
+        + BlobSidecarsProvider
         + Clone
         + Unpin
         + 'static
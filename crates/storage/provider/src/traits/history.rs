@@ -1,4 +1,4 @@
-use alloy_primitives::{Address, BlockNumber, B256};
+use alloy_primitives::{Address, BlockNumber, TxNumber, B256};
 use auto_impl::auto_impl;
 use reth_db_api::models::BlockNumberAddress;
 use reth_storage_errors::provider::ProviderResult;
@@ -35,6 +35,16 @@ pub trait HistoryWriter: Send + Sync {
         storage_transitions: impl IntoIterator<Item = ((Address, B256), impl IntoIterator<Item = u64>)>,
     ) -> ProviderResult<()>;
 
+    /// Unwind and clear the address transactions indices of the given addresses, removing all
+    /// transaction numbers greater than or equal to `tx_number`.
+    ///
+    /// Returns number of addresses unwound.
+    fn unwind_address_transactions_indices(
+        &self,
+        addresses: impl IntoIterator<Item = Address>,
+        tx_number: TxNumber,
+    ) -> ProviderResult<usize>;
+
     /// Read account/storage changesets and update account/storage history indices.
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
 }
@@ -1,4 +1,4 @@
-use alloy_primitives::{Address, BlockNumber};
+use alloy_primitives::{Address, BlockNumber, TxNumber};
 use auto_impl::auto_impl;
 use reth_db_models::AccountBeforeTx;
 use reth_primitives::Account;
@@ -53,4 +53,43 @@ pub trait ChangeSetReader: Send + Sync {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>>;
+
+    /// Returns the account changesets of all blocks in the given range, in ascending order of
+    /// their block number.
+    fn account_changesets_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        let mut changesets = Vec::new();
+        for block_number in range {
+            changesets.extend(
+                self.account_block_changeset(block_number)?
+                    .into_iter()
+                    .map(|change| (block_number, change)),
+            );
+        }
+        Ok(changesets)
+    }
+}
+
+/// Reader for the index of transactions each address took part in.
+#[auto_impl(&, Arc, Box)]
+pub trait AddressTransactionsReader: Send + Sync {
+    /// Returns at most `limit` numbers of transactions within `range` that `address` took part
+    /// in, in ascending order.
+    fn address_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>>;
+
+    /// Returns at most `limit` numbers of transactions within `range` that `address` took part
+    /// in, in descending order.
+    fn address_transactions_rev(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>>;
 }
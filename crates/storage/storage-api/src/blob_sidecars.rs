@@ -0,0 +1,39 @@
+use alloy_primitives::{BlockNumber, B256};
+use reth_db_models::{StoredBlobSidecar, StoredBlockBlobSidecars};
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeInclusive;
+
+///  Client trait for fetching the retained blob sidecars of canonical blocks.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait BlobSidecarsProvider: Send + Sync {
+    /// Get the retained blob sidecars of a block by number.
+    ///
+    /// Returns `None` if the blob sidecars of the block are not retained.
+    fn blob_sidecars_by_block(
+        &self,
+        number: BlockNumber,
+    ) -> ProviderResult<Option<StoredBlockBlobSidecars>>;
+
+    /// Get the retained blob sidecar that contains the blob with the given versioned hash,
+    /// alongside the number of the block that includes its transaction.
+    ///
+    /// Only the blocks in `block_range` are searched, most recent first.
+    fn blob_sidecar_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+        block_range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<(BlockNumber, StoredBlobSidecar)>>;
+}
+
+///  Client trait for retaining the blob sidecars of canonical blocks.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait BlobSidecarsWriter: Send + Sync {
+    /// Retains the blob sidecars of consecutive canonical blocks.
+    ///
+    /// Any previously retained blob sidecars of the first block and above are replaced, which
+    /// allows handling reorgs.
+    fn write_blob_sidecars(
+        &self,
+        blocks: Vec<(BlockNumber, StoredBlockBlobSidecars)>,
+    ) -> ProviderResult<()>;
+}
@@ -13,6 +13,9 @@ pub use reth_storage_errors as errors;
 mod account;
 pub use account::*;
 
+mod blob_sidecars;
+pub use blob_sidecars::*;
+
 mod block;
 pub use block::*;
 
@@ -1,3 +1,4 @@
+use alloy_primitives::BlockNumber;
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_storage_errors::provider::ProviderResult;
 
@@ -12,6 +13,24 @@ pub trait PruneCheckpointReader: Send + Sync {
 
     /// Fetch all the prune checkpoints.
     fn get_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>>;
+
+    /// Returns the earliest block whose header, body and receipts weren't pruned.
+    fn earliest_available_block(&self) -> ProviderResult<BlockNumber> {
+        let mut earliest = 0;
+        for segment in [
+            PruneSegment::Headers,
+            PruneSegment::Transactions,
+            PruneSegment::Receipts,
+            PruneSegment::ContractLogs,
+        ] {
+            if let Some(block_number) =
+                self.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number)
+            {
+                earliest = earliest.max(block_number + 1);
+            }
+        }
+        Ok(earliest)
+    }
 }
 
 /// The trait for updating prune checkpoint related data.
@@ -40,4 +40,17 @@ pub trait StorageChangeSetReader: Send + Sync {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>>;
+
+    /// Returns the storage changesets of all blocks in the given range, in ascending order of
+    /// their block number.
+    fn storage_changesets_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        let mut changesets = Vec::new();
+        for block_number in range {
+            changesets.extend(self.storage_changeset(block_number)?);
+        }
+        Ok(changesets)
+    }
 }
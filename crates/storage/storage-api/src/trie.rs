@@ -4,8 +4,9 @@ use alloy_primitives::{
 };
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
-    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof, StorageProof,
-    TrieInput,
+    range::{AccountRange, StorageRange},
+    updates::TrieUpdates,
+    AccountProof, HashedPostState, HashedStorage, MultiProof, StorageProof, TrieInput,
 };
 
 /// A type that can compute the state root of a given post state.
@@ -85,3 +86,40 @@ pub trait StateProofProvider: Send + Sync {
         target: HashedPostState,
     ) -> ProviderResult<HashMap<B256, Bytes>>;
 }
+
+/// A type that can page through the hashed state on top of a given post state.
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait StateRangeProvider: Send + Sync {
+    /// Returns up to `limit` accounts ordered by hashed address, starting at the hashed address
+    /// `start`, of the state represented by [`TrieInput`] on top of the current state.
+    ///
+    /// Up to `storage_limit` storage slots of the returned accounts are collected as well, see
+    /// [`StateRange::account_range`](reth_trie::range::StateRange::account_range).
+    fn account_range(
+        &self,
+        input: TrieInput,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> ProviderResult<AccountRange>;
+
+    /// Returns up to `limit` storage slots of the target account ordered by hashed slot, starting
+    /// at the hashed slot `start`, with `HashedStorage` applied on top of the current state.
+    fn storage_range(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange>;
+
+    /// Same as [`StateRangeProvider::storage_range`], but for the account with the given hashed
+    /// address.
+    fn hashed_storage_range(
+        &self,
+        hashed_address: B256,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<StorageRange>;
+}
@@ -0,0 +1,583 @@
+//! Admission policies that decide whether a transaction may enter the pool.
+//!
+//! Admission policies are checked before a transaction is handed to the
+//! [`TransactionValidator`](crate::TransactionValidator). Unlike validation, they don't depend on
+//! the state of the chain but on rules configured by the node operator, for example sender or
+//! recipient deny-lists and rate limits.
+//!
+//! A rejected transaction is reported as
+//! [`InvalidPoolTransactionError::Admission`](crate::error::InvalidPoolTransactionError::Admission).
+//!
+//! Quotas, like rate limits, are consumed by the check and released again if the validator
+//! rejects the transaction.
+
+use crate::{metrics::AdmissionMetrics, PeerId, PoolTransaction, TransactionOrigin};
+use alloy_primitives::Address;
+use parking_lot::Mutex;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+/// Maximum number of tracked keys of a [`RateLimiter`] before expired windows are pruned.
+const RATE_LIMITER_PRUNE_THRESHOLD: usize = 10_000;
+
+/// Information about the pool's current view of the sender of a transaction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AdmissionContext {
+    /// The peer the transaction was received from, if it was received over the network.
+    pub peer: Option<PeerId>,
+    /// Number of transactions of the sender that are already in the pool.
+    ///
+    /// A transaction with the same nonce, which the new transaction would replace, is not counted.
+    pub sender_transactions: usize,
+    /// Combined size of the input data of the sender's transactions in the pool.
+    ///
+    /// A transaction with the same nonce, which the new transaction would replace, is not counted.
+    pub sender_calldata: usize,
+}
+
+/// A rule that decides whether a transaction is admitted into the pool.
+///
+/// Policies are invoked for every transaction before it is validated.
+pub trait AdmissionPolicy<T: PoolTransaction>: fmt::Debug + Send + Sync {
+    /// Returns an error if the transaction must not enter the pool.
+    fn check(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+        context: &AdmissionContext,
+    ) -> Result<(), AdmissionError>;
+
+    /// Releases the quotas consumed by [`AdmissionPolicy::check`] for a transaction of `sender`
+    /// that was admitted, but then rejected by the validator.
+    fn release(&self, origin: TransactionOrigin, sender: Address, context: &AdmissionContext) {
+        let _ = (origin, sender, context);
+    }
+}
+
+/// Reasons for rejecting a transaction by an [`AdmissionPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AdmissionError {
+    /// The sender of the transaction is not allowed.
+    #[error("sender {0} is not allowed")]
+    SenderNotAllowed(Address),
+    /// The recipient of the transaction is not allowed.
+    #[error("recipient {0} is not allowed")]
+    RecipientNotAllowed(Address),
+    /// The sender submitted too many transactions.
+    #[error("sender {0} exceeded the rate limit")]
+    SenderRateLimited(Address),
+    /// Too many transactions of this origin were submitted.
+    #[error("rate limit for {0:?} transactions exceeded")]
+    OriginRateLimited(TransactionOrigin),
+    /// The transaction would exceed the calldata quota of its sender.
+    #[error("pending calldata of sender {sender} would exceed {max} bytes")]
+    CalldataLimitExceeded {
+        /// The sender of the transaction.
+        sender: Address,
+        /// The configured maximum.
+        max: usize,
+    },
+    /// Rejected by a custom policy.
+    #[error("{0}")]
+    Other(String),
+}
+
+/// Limits the number of transactions within a time window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RateLimit {
+    /// Maximum number of transactions per window.
+    pub max_transactions: u32,
+    /// Length of the window.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub window: Duration,
+}
+
+impl RateLimit {
+    /// Creates a new rate limit.
+    pub const fn new(max_transactions: u32, window: Duration) -> Self {
+        Self { max_transactions, window }
+    }
+}
+
+/// Rate limits by [`TransactionOrigin`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct OriginRateLimits {
+    /// Rate limit for [`TransactionOrigin::Local`] transactions.
+    pub local: Option<RateLimit>,
+    /// Rate limit for [`TransactionOrigin::External`] transactions of each peer.
+    pub external: Option<RateLimit>,
+    /// Rate limit for [`TransactionOrigin::Private`] transactions.
+    pub private: Option<RateLimit>,
+}
+
+/// Configuration of the built-in admission policies.
+///
+/// Empty lists and `None` limits disable the corresponding policy.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct AdmissionConfig {
+    /// Senders whose transactions are rejected.
+    pub deny_senders: Vec<Address>,
+    /// If not empty, only transactions of these senders are accepted.
+    pub allow_senders: Vec<Address>,
+    /// Rejects transactions that call one of these addresses.
+    pub deny_recipients: Vec<Address>,
+    /// If not empty, only transactions calling one of these addresses or creating a contract are
+    /// accepted.
+    pub allow_recipients: Vec<Address>,
+    /// Rate limit for every sender.
+    pub sender_rate_limit: Option<RateLimit>,
+    /// Rate limits for every origin.
+    pub origin_rate_limits: OriginRateLimits,
+    /// Maximum combined input data size in bytes of the transactions of a sender in the pool.
+    pub max_sender_calldata: Option<usize>,
+}
+
+/// Rejects transactions based on their sender.
+#[derive(Debug, Clone, Default)]
+pub struct SenderFilter {
+    allow: HashSet<Address>,
+    deny: HashSet<Address>,
+}
+
+impl SenderFilter {
+    /// Creates a new filter.
+    ///
+    /// If `allow` is empty, all senders that are not in `deny` are accepted.
+    pub const fn new(allow: HashSet<Address>, deny: HashSet<Address>) -> Self {
+        Self { allow, deny }
+    }
+}
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for SenderFilter {
+    fn check(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: &T,
+        _context: &AdmissionContext,
+    ) -> Result<(), AdmissionError> {
+        let sender = transaction.sender();
+        if self.deny.contains(&sender) || (!self.allow.is_empty() && !self.allow.contains(&sender))
+        {
+            return Err(AdmissionError::SenderNotAllowed(sender))
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions based on their `to` address.
+///
+/// Contract creations are not affected.
+#[derive(Debug, Clone, Default)]
+pub struct RecipientFilter {
+    allow: HashSet<Address>,
+    deny: HashSet<Address>,
+}
+
+impl RecipientFilter {
+    /// Creates a new filter.
+    ///
+    /// If `allow` is empty, all recipients that are not in `deny` are accepted.
+    pub const fn new(allow: HashSet<Address>, deny: HashSet<Address>) -> Self {
+        Self { allow, deny }
+    }
+}
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for RecipientFilter {
+    fn check(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: &T,
+        _context: &AdmissionContext,
+    ) -> Result<(), AdmissionError> {
+        let Some(to) = transaction.to() else { return Ok(()) };
+        if self.deny.contains(&to) || (!self.allow.is_empty() && !self.allow.contains(&to)) {
+            return Err(AdmissionError::RecipientNotAllowed(to))
+        }
+        Ok(())
+    }
+}
+
+/// Fixed window rate limiter keyed by `K`.
+#[derive(Debug)]
+pub struct RateLimiter<K> {
+    limit: RateLimit,
+    windows: Mutex<HashMap<K, (Instant, u32)>>,
+}
+
+impl<K: Hash + Eq> RateLimiter<K> {
+    /// Creates a new rate limiter.
+    pub fn new(limit: RateLimit) -> Self {
+        Self { limit, windows: Default::default() }
+    }
+
+    /// Records a transaction for the key and returns `false` if the limit is exceeded.
+    pub fn try_acquire(&self, key: K) -> bool {
+        let now = Instant::now();
+        let mut windows = self.windows.lock();
+        if windows.len() >= RATE_LIMITER_PRUNE_THRESHOLD {
+            windows.retain(|_, (start, _)| now.duration_since(*start) < self.limit.window);
+        }
+
+        let (start, count) = windows.entry(key).or_insert((now, 0));
+        if now.duration_since(*start) >= self.limit.window {
+            *start = now;
+            *count = 0;
+        }
+        if *count >= self.limit.max_transactions {
+            return false
+        }
+        *count += 1;
+        true
+    }
+
+    /// Gives back a transaction recorded for the key in the current window.
+    pub fn release(&self, key: &K) {
+        let now = Instant::now();
+        if let Some((start, count)) = self.windows.lock().get_mut(key) {
+            if now.duration_since(*start) < self.limit.window {
+                *count = count.saturating_sub(1);
+            }
+        }
+    }
+}
+
+/// Limits the number of transactions a sender can submit.
+#[derive(Debug)]
+pub struct SenderRateLimit(RateLimiter<Address>);
+
+impl SenderRateLimit {
+    /// Creates a new sender rate limit.
+    pub fn new(limit: RateLimit) -> Self {
+        Self(RateLimiter::new(limit))
+    }
+}
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for SenderRateLimit {
+    fn check(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: &T,
+        _context: &AdmissionContext,
+    ) -> Result<(), AdmissionError> {
+        let sender = transaction.sender();
+        if !self.0.try_acquire(sender) {
+            return Err(AdmissionError::SenderRateLimited(sender))
+        }
+        Ok(())
+    }
+
+    fn release(&self, _origin: TransactionOrigin, sender: Address, _context: &AdmissionContext) {
+        self.0.release(&sender);
+    }
+}
+
+/// Limits the number of transactions per [`TransactionOrigin`].
+///
+/// Transactions received over the network are limited per peer, see [`AdmissionContext::peer`].
+#[derive(Debug)]
+pub struct OriginRateLimit {
+    limits: OriginRateLimits,
+    limiter: [Option<RateLimiter<Option<PeerId>>>; 3],
+}
+
+impl OriginRateLimit {
+    /// Creates a new origin rate limit.
+    pub fn new(limits: OriginRateLimits) -> Self {
+        Self {
+            limits,
+            limiter: [
+                limits.local.map(RateLimiter::new),
+                limits.external.map(RateLimiter::new),
+                limits.private.map(RateLimiter::new),
+            ],
+        }
+    }
+
+    /// Returns the configured limits.
+    pub const fn limits(&self) -> &OriginRateLimits {
+        &self.limits
+    }
+
+    /// Returns the limiter of the origin, if it is limited.
+    fn limiter(&self, origin: TransactionOrigin) -> Option<&RateLimiter<Option<PeerId>>> {
+        let idx = match origin {
+            TransactionOrigin::Local => 0,
+            TransactionOrigin::External => 1,
+            TransactionOrigin::Private => 2,
+        };
+        self.limiter[idx].as_ref()
+    }
+}
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for OriginRateLimit {
+    fn check(
+        &self,
+        origin: TransactionOrigin,
+        _transaction: &T,
+        context: &AdmissionContext,
+    ) -> Result<(), AdmissionError> {
+        if let Some(limiter) = self.limiter(origin) {
+            if !limiter.try_acquire(context.peer) {
+                return Err(AdmissionError::OriginRateLimited(origin))
+            }
+        }
+        Ok(())
+    }
+
+    fn release(&self, origin: TransactionOrigin, _sender: Address, context: &AdmissionContext) {
+        if let Some(limiter) = self.limiter(origin) {
+            limiter.release(&context.peer);
+        }
+    }
+}
+
+/// Limits the combined input data size of a sender's transactions in the pool.
+#[derive(Debug, Clone, Copy)]
+pub struct SenderCalldataLimit {
+    max: usize,
+}
+
+impl SenderCalldataLimit {
+    /// Creates a new limit of `max` bytes per sender.
+    pub const fn new(max: usize) -> Self {
+        Self { max }
+    }
+}
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for SenderCalldataLimit {
+    fn check(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: &T,
+        context: &AdmissionContext,
+    ) -> Result<(), AdmissionError> {
+        if context.sender_calldata + transaction.input().len() > self.max {
+            return Err(AdmissionError::CalldataLimitExceeded {
+                sender: transaction.sender(),
+                max: self.max,
+            })
+        }
+        Ok(())
+    }
+}
+
+/// A list of [`AdmissionPolicy`]s that must all accept a transaction.
+pub struct AdmissionPolicies<T> {
+    policies: Vec<Box<dyn AdmissionPolicy<T>>>,
+    metrics: AdmissionMetrics,
+}
+
+impl<T: PoolTransaction> AdmissionPolicies<T> {
+    /// Creates the built-in policies enabled in the config.
+    pub fn from_config(config: &AdmissionConfig) -> Self {
+        let mut policies = Self::default();
+        if !config.allow_senders.is_empty() || !config.deny_senders.is_empty() {
+            policies.push(SenderFilter::new(
+                config.allow_senders.iter().copied().collect(),
+                config.deny_senders.iter().copied().collect(),
+            ));
+        }
+        if !config.allow_recipients.is_empty() || !config.deny_recipients.is_empty() {
+            policies.push(RecipientFilter::new(
+                config.allow_recipients.iter().copied().collect(),
+                config.deny_recipients.iter().copied().collect(),
+            ));
+        }
+        if let Some(limit) = config.sender_rate_limit {
+            policies.push(SenderRateLimit::new(limit));
+        }
+        if config.origin_rate_limits != OriginRateLimits::default() {
+            policies.push(OriginRateLimit::new(config.origin_rate_limits));
+        }
+        if let Some(max) = config.max_sender_calldata {
+            policies.push(SenderCalldataLimit::new(max));
+        }
+        policies
+    }
+
+    /// Adds a policy.
+    pub fn push(&mut self, policy: impl AdmissionPolicy<T> + 'static) {
+        self.policies.push(Box::new(policy));
+    }
+
+    /// Returns `true` if there are no policies.
+    pub fn is_empty(&self) -> bool {
+        self.policies.is_empty()
+    }
+
+    /// Returns the number of policies.
+    pub fn len(&self) -> usize {
+        self.policies.len()
+    }
+}
+
+impl<T> Default for AdmissionPolicies<T> {
+    fn default() -> Self {
+        Self { policies: Vec::new(), metrics: Default::default() }
+    }
+}
+
+impl<T> fmt::Debug for AdmissionPolicies<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdmissionPolicies").field("policies", &self.policies).finish()
+    }
+}
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for AdmissionPolicies<T> {
+    fn check(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+        context: &AdmissionContext,
+    ) -> Result<(), AdmissionError> {
+        for policy in &self.policies {
+            if let Err(err) = policy.check(origin, transaction, context) {
+                self.metrics.record(&err);
+                return Err(err)
+            }
+        }
+        Ok(())
+    }
+
+    fn release(&self, origin: TransactionOrigin, sender: Address, context: &AdmissionContext) {
+        for policy in &self.policies {
+            policy.release(origin, sender, context);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+    use alloy_primitives::{Bytes, TxKind};
+
+    fn eip1559_to(kind: TxKind) -> MockTransaction {
+        let mut tx = MockTransaction::eip1559();
+        if let MockTransaction::Eip1559 { to, .. } = &mut tx {
+            *to = kind;
+        }
+        tx
+    }
+
+    #[test]
+    fn sender_filter() {
+        let tx = MockTransaction::eip1559();
+        let ctx = AdmissionContext::default();
+        let origin = TransactionOrigin::External;
+
+        let deny = SenderFilter::new(HashSet::new(), HashSet::from([tx.sender()]));
+        assert_eq!(
+            deny.check(origin, &tx, &ctx),
+            Err(AdmissionError::SenderNotAllowed(tx.sender()))
+        );
+
+        let allow = SenderFilter::new(HashSet::from([tx.sender()]), HashSet::new());
+        assert!(allow.check(origin, &tx, &ctx).is_ok());
+        let other = MockTransaction::eip1559().with_sender(Address::random());
+        assert!(allow.check(origin, &other, &ctx).is_err());
+    }
+
+    #[test]
+    fn recipient_filter_ignores_create() {
+        let to = Address::random();
+        let filter = RecipientFilter::new(HashSet::from([to]), HashSet::new());
+        let ctx = AdmissionContext::default();
+        let origin = TransactionOrigin::External;
+
+        assert!(filter.check(origin, &eip1559_to(to.into()), &ctx).is_ok());
+        assert_eq!(
+            filter.check(origin, &eip1559_to(TxKind::Call(Address::ZERO)), &ctx),
+            Err(AdmissionError::RecipientNotAllowed(Address::ZERO))
+        );
+        assert!(filter.check(origin, &eip1559_to(TxKind::Create), &ctx).is_ok());
+    }
+
+    #[test]
+    fn sender_rate_limit() {
+        let policy = SenderRateLimit::new(RateLimit::new(2, Duration::from_secs(60)));
+        let ctx = AdmissionContext::default();
+        let origin = TransactionOrigin::External;
+        let tx = MockTransaction::eip1559();
+
+        assert!(policy.check(origin, &tx, &ctx).is_ok());
+        assert!(policy.check(origin, &tx.next(), &ctx).is_ok());
+        assert_eq!(
+            policy.check(origin, &tx.next().next(), &ctx),
+            Err(AdmissionError::SenderRateLimited(tx.sender()))
+        );
+        // other senders are not affected
+        assert!(policy.check(origin, &MockTransaction::eip1559(), &ctx).is_ok());
+
+        // a released transaction doesn't count
+        AdmissionPolicy::<MockTransaction>::release(&policy, origin, tx.sender(), &ctx);
+        assert!(policy.check(origin, &tx.next().next(), &ctx).is_ok());
+    }
+
+    #[test]
+    fn origin_rate_limit() {
+        let policy = OriginRateLimit::new(OriginRateLimits {
+            external: Some(RateLimit::new(1, Duration::from_secs(60))),
+            ..Default::default()
+        });
+        let ctx = AdmissionContext::default();
+        let tx = MockTransaction::eip1559();
+
+        assert!(policy.check(TransactionOrigin::External, &tx, &ctx).is_ok());
+        assert_eq!(
+            policy.check(TransactionOrigin::External, &tx, &ctx),
+            Err(AdmissionError::OriginRateLimited(TransactionOrigin::External))
+        );
+        assert!(policy.check(TransactionOrigin::Local, &tx, &ctx).is_ok());
+    }
+
+    #[test]
+    fn origin_rate_limit_per_peer() {
+        let policy = OriginRateLimit::new(OriginRateLimits {
+            external: Some(RateLimit::new(1, Duration::from_secs(60))),
+            ..Default::default()
+        });
+        let origin = TransactionOrigin::External;
+        let tx = MockTransaction::eip1559();
+        let peer = AdmissionContext { peer: Some(PeerId::random()), ..Default::default() };
+        let other = AdmissionContext { peer: Some(PeerId::random()), ..Default::default() };
+
+        assert!(policy.check(origin, &tx, &peer).is_ok());
+        assert!(policy.check(origin, &tx, &peer).is_err());
+        // the quota of other peers is not affected
+        assert!(policy.check(origin, &tx, &other).is_ok());
+
+        AdmissionPolicy::<MockTransaction>::release(&policy, origin, tx.sender(), &peer);
+        assert!(policy.check(origin, &tx, &peer).is_ok());
+    }
+
+    #[test]
+    fn policies_from_config() {
+        let tx = MockTransaction::eip1559().with_input(Bytes::from_static(&[1; 32]));
+        let config = AdmissionConfig {
+            deny_senders: vec![Address::random()],
+            max_sender_calldata: Some(32),
+            ..Default::default()
+        };
+        let policies = AdmissionPolicies::<MockTransaction>::from_config(&config);
+        assert_eq!(policies.len(), 2);
+
+        let origin = TransactionOrigin::External;
+        assert!(policies.check(origin, &tx, &AdmissionContext::default()).is_ok());
+        let ctx =
+            AdmissionContext { sender_transactions: 1, sender_calldata: 1, ..Default::default() };
+        assert_eq!(
+            policies.check(origin, &tx, &ctx),
+            Err(AdmissionError::CalldataLimitExceeded { sender: tx.sender(), max: 32 })
+        );
+    }
+}
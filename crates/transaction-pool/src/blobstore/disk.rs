@@ -1,16 +1,19 @@
 //! A simple diskstore for blobs
 
-use crate::blobstore::{BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize};
+use crate::blobstore::{
+    blobs_and_proofs_v1, blobs_and_proofs_v2, find_versioned_hashes, BlobAndProofV2, BlobStore,
+    BlobStoreCleanupStat, BlobStoreError, BlobStoreSize, BlobTransactionSidecarVariant,
+};
 use alloy_eips::eip4844::BlobAndProofV1;
 use alloy_primitives::{TxHash, B256};
 use alloy_rlp::{Decodable, Encodable};
 use parking_lot::{Mutex, RwLock};
-use reth_primitives::BlobTransactionSidecar;
+use reth_primitives::kzg::KzgSettings;
 use schnellru::{ByLength, LruMap};
 use std::{collections::HashSet, fmt, fs, io, path::PathBuf, sync::Arc};
 use tracing::{debug, trace};
 
-/// How many [`BlobTransactionSidecar`] to cache in memory.
+/// How many [`BlobTransactionSidecarVariant`] to cache in memory.
 pub const DEFAULT_MAX_CACHED_BLOBS: u32 = 100;
 
 /// A blob store that stores blob data on disk.
@@ -30,12 +33,20 @@ impl DiskFileBlobStore {
         opts: DiskFileBlobStoreConfig,
     ) -> Result<Self, DiskFileBlobStoreError> {
         let blob_dir = blob_dir.into();
-        let DiskFileBlobStoreConfig { max_cached_entries, .. } = opts;
+        let DiskFileBlobStoreConfig { max_cached_entries, open } = opts;
         let inner = DiskFileBlobStoreInner::new(blob_dir, max_cached_entries);
 
         // initialize the blob store
-        inner.delete_all()?;
-        inner.create_blob_dir()?;
+        match open {
+            OpenDiskFileBlobStore::Clear => {
+                inner.delete_all()?;
+                inner.create_blob_dir()?;
+            }
+            OpenDiskFileBlobStore::ReIndex => {
+                inner.create_blob_dir()?;
+                inner.reindex()?;
+            }
+        }
 
         Ok(Self { inner: Arc::new(inner) })
     }
@@ -52,11 +63,14 @@ impl DiskFileBlobStore {
 }
 
 impl BlobStore for DiskFileBlobStore {
-    fn insert(&self, tx: B256, data: BlobTransactionSidecar) -> Result<(), BlobStoreError> {
+    fn insert(&self, tx: B256, data: BlobTransactionSidecarVariant) -> Result<(), BlobStoreError> {
         self.inner.insert_one(tx, data)
     }
 
-    fn insert_all(&self, txs: Vec<(B256, BlobTransactionSidecar)>) -> Result<(), BlobStoreError> {
+    fn insert_all(
+        &self,
+        txs: Vec<(B256, BlobTransactionSidecarVariant)>,
+    ) -> Result<(), BlobStoreError> {
         if txs.is_empty() {
             return Ok(())
         }
@@ -76,6 +90,13 @@ impl BlobStore for DiskFileBlobStore {
         Ok(())
     }
 
+    fn delete_unclaimed(&self, is_claimed: &dyn Fn(&B256) -> bool) -> Result<(), BlobStoreError> {
+        let reindexed = std::mem::take(&mut *self.inner.reindexed.lock());
+        let unclaimed = reindexed.into_iter().filter(|tx| !is_claimed(tx)).collect::<Vec<_>>();
+        debug!(target:"txpool::blob", num_blobs=%unclaimed.len(), "Deleting unclaimed blobs");
+        self.delete_all(unclaimed)
+    }
+
     fn cleanup(&self) -> BlobStoreCleanupStat {
         let txs_to_delete = {
             let mut txs_to_delete = self.inner.txs_to_delete.write();
@@ -104,7 +125,7 @@ impl BlobStore for DiskFileBlobStore {
         stat
     }
 
-    fn get(&self, tx: B256) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
+    fn get(&self, tx: B256) -> Result<Option<BlobTransactionSidecarVariant>, BlobStoreError> {
         self.inner.get_one(tx)
     }
 
@@ -115,14 +136,17 @@ impl BlobStore for DiskFileBlobStore {
     fn get_all(
         &self,
         txs: Vec<B256>,
-    ) -> Result<Vec<(B256, BlobTransactionSidecar)>, BlobStoreError> {
+    ) -> Result<Vec<(B256, BlobTransactionSidecarVariant)>, BlobStoreError> {
         if txs.is_empty() {
             return Ok(Vec::new())
         }
         self.inner.get_all(txs)
     }
 
-    fn get_exact(&self, txs: Vec<B256>) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError> {
+    fn get_exact(
+        &self,
+        txs: Vec<B256>,
+    ) -> Result<Vec<BlobTransactionSidecarVariant>, BlobStoreError> {
         if txs.is_empty() {
             return Ok(Vec::new())
         }
@@ -132,26 +156,34 @@ impl BlobStore for DiskFileBlobStore {
     fn get_by_versioned_hashes(
         &self,
         versioned_hashes: &[B256],
+        settings: &KzgSettings,
     ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError> {
-        let mut result = vec![None; versioned_hashes.len()];
-        for (_tx_hash, blob_sidecar) in self.inner.blob_cache.lock().iter() {
-            for (i, blob_versioned_hash) in blob_sidecar.versioned_hashes().enumerate() {
-                for (j, target_versioned_hash) in versioned_hashes.iter().enumerate() {
-                    if blob_versioned_hash == *target_versioned_hash {
-                        result[j].get_or_insert_with(|| BlobAndProofV1 {
-                            blob: Box::new(blob_sidecar.blobs[i]),
-                            proof: blob_sidecar.proofs[i],
-                        });
-                    }
-                }
-            }
+        let matches = {
+            let cache = self.inner.blob_cache.lock();
+            find_versioned_hashes(
+                cache.iter().map(|(_, blob_sidecar)| blob_sidecar),
+                versioned_hashes,
+            )
+        };
+        // proofs of sidecars with cell proofs are computed after releasing the lock
+        blobs_and_proofs_v1(matches, versioned_hashes.len(), settings)
+    }
 
-            // Return early if all blobs are found.
-            if result.iter().all(|blob| blob.is_some()) {
-                break;
-            }
-        }
-        Ok(result)
+    fn get_by_versioned_hashes_v2(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV2>>, BlobStoreError> {
+        let matches = {
+            let cache = self.inner.blob_cache.lock();
+            find_versioned_hashes(
+                cache
+                    .iter()
+                    .map(|(_, blob_sidecar)| blob_sidecar)
+                    .filter(|blob_sidecar| blob_sidecar.is_eip7594()),
+                versioned_hashes,
+            )
+        };
+        Ok(blobs_and_proofs_v2(matches, versioned_hashes.len()))
     }
 
     fn data_size_hint(&self) -> Option<usize> {
@@ -165,10 +197,12 @@ impl BlobStore for DiskFileBlobStore {
 
 struct DiskFileBlobStoreInner {
     blob_dir: PathBuf,
-    blob_cache: Mutex<LruMap<TxHash, BlobTransactionSidecar, ByLength>>,
+    blob_cache: Mutex<LruMap<TxHash, BlobTransactionSidecarVariant, ByLength>>,
     size_tracker: BlobStoreSize,
     file_lock: RwLock<()>,
     txs_to_delete: RwLock<HashSet<B256>>,
+    /// Blobs that were found on disk when the store was reindexed.
+    reindexed: Mutex<HashSet<B256>>,
 }
 
 impl DiskFileBlobStoreInner {
@@ -180,6 +214,7 @@ impl DiskFileBlobStoreInner {
             size_tracker: Default::default(),
             file_lock: Default::default(),
             txs_to_delete: Default::default(),
+            reindexed: Default::default(),
         }
     }
 
@@ -190,6 +225,34 @@ impl DiskFileBlobStoreInner {
             .map_err(|e| DiskFileBlobStoreError::Open(self.blob_dir.clone(), e))
     }
 
+    /// Recomputes the size tracker from the blob files that already exist on disk.
+    ///
+    /// The blobs are remembered, so that the ones that are not claimed by a transaction again can
+    /// be deleted, see [`BlobStore::delete_unclaimed`].
+    fn reindex(&self) -> Result<(), DiskFileBlobStoreError> {
+        let entries = fs::read_dir(&self.blob_dir)
+            .map_err(|e| DiskFileBlobStoreError::Open(self.blob_dir.clone(), e))?;
+        let mut size = 0;
+        let mut len = 0;
+        let mut reindexed = self.reindexed.lock();
+        for entry in entries {
+            let entry =
+                entry.map_err(|e| DiskFileBlobStoreError::Open(self.blob_dir.clone(), e))?;
+            let Ok(metadata) = entry.metadata() else { continue };
+            if metadata.is_file() {
+                size += metadata.len() as usize;
+                len += 1;
+                if let Some(tx) = entry.file_name().to_str().and_then(|name| name.parse().ok()) {
+                    reindexed.insert(tx);
+                }
+            }
+        }
+        self.size_tracker.add_size(size);
+        self.size_tracker.update_len(len);
+        debug!(target:"txpool::blob", blob_dir = ?self.blob_dir, len, size, "Reindexed blob store");
+        Ok(())
+    }
+
     /// Deletes the entire blob store.
     fn delete_all(&self) -> Result<(), DiskFileBlobStoreError> {
         match fs::remove_dir_all(&self.blob_dir) {
@@ -203,8 +266,12 @@ impl DiskFileBlobStoreInner {
     }
 
     /// Ensures blob is in the blob cache and written to the disk.
-    fn insert_one(&self, tx: B256, data: BlobTransactionSidecar) -> Result<(), BlobStoreError> {
-        let mut buf = Vec::with_capacity(data.fields_len());
+    fn insert_one(
+        &self,
+        tx: B256,
+        data: BlobTransactionSidecarVariant,
+    ) -> Result<(), BlobStoreError> {
+        let mut buf = Vec::with_capacity(data.length());
         data.encode(&mut buf);
         self.blob_cache.lock().insert(tx, data);
         let size = self.write_one_encoded(tx, &buf)?;
@@ -215,11 +282,14 @@ impl DiskFileBlobStoreInner {
     }
 
     /// Ensures blobs are in the blob cache and written to the disk.
-    fn insert_many(&self, txs: Vec<(B256, BlobTransactionSidecar)>) -> Result<(), BlobStoreError> {
+    fn insert_many(
+        &self,
+        txs: Vec<(B256, BlobTransactionSidecarVariant)>,
+    ) -> Result<(), BlobStoreError> {
         let raw = txs
             .iter()
             .map(|(tx, data)| {
-                let mut buf = Vec::with_capacity(data.fields_len());
+                let mut buf = Vec::with_capacity(data.length());
                 data.encode(&mut buf);
                 (self.blob_disk_file(*tx), buf)
             })
@@ -279,7 +349,7 @@ impl DiskFileBlobStoreInner {
     }
 
     /// Retrieves the blob for the given transaction hash from the blob cache or disk.
-    fn get_one(&self, tx: B256) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
+    fn get_one(&self, tx: B256) -> Result<Option<BlobTransactionSidecarVariant>, BlobStoreError> {
         if let Some(blob) = self.blob_cache.lock().get(&tx) {
             return Ok(Some(blob.clone()))
         }
@@ -298,7 +368,7 @@ impl DiskFileBlobStoreInner {
 
     /// Retrieves the blob data for the given transaction hash.
     #[inline]
-    fn read_one(&self, tx: B256) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
+    fn read_one(&self, tx: B256) -> Result<Option<BlobTransactionSidecarVariant>, BlobStoreError> {
         let path = self.blob_disk_file(tx);
         let data = {
             let _lock = self.file_lock.read();
@@ -312,17 +382,17 @@ impl DiskFileBlobStoreInner {
                 }
             }
         };
-        BlobTransactionSidecar::decode(&mut data.as_slice())
+        BlobTransactionSidecarVariant::decode(&mut data.as_slice())
             .map(Some)
             .map_err(BlobStoreError::DecodeError)
     }
 
     /// Returns decoded blobs read from disk.
-    fn read_many_decoded(&self, txs: Vec<TxHash>) -> Vec<(TxHash, BlobTransactionSidecar)> {
+    fn read_many_decoded(&self, txs: Vec<TxHash>) -> Vec<(TxHash, BlobTransactionSidecarVariant)> {
         self.read_many_raw(txs)
             .into_iter()
             .filter_map(|(tx, data)| {
-                BlobTransactionSidecar::decode(&mut data.as_slice())
+                BlobTransactionSidecarVariant::decode(&mut data.as_slice())
                     .map(|sidecar| (tx, sidecar))
                     .ok()
             })
@@ -375,7 +445,7 @@ impl DiskFileBlobStoreInner {
     fn get_all(
         &self,
         txs: Vec<B256>,
-    ) -> Result<Vec<(B256, BlobTransactionSidecar)>, BlobStoreError> {
+    ) -> Result<Vec<(B256, BlobTransactionSidecarVariant)>, BlobStoreError> {
         let mut res = Vec::with_capacity(txs.len());
         let mut cache_miss = Vec::new();
         {
@@ -408,7 +478,10 @@ impl DiskFileBlobStoreInner {
     ///
     /// Returns an error if there are any missing blobs.
     #[inline]
-    fn get_exact(&self, txs: Vec<B256>) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError> {
+    fn get_exact(
+        &self,
+        txs: Vec<B256>,
+    ) -> Result<Vec<BlobTransactionSidecarVariant>, BlobStoreError> {
         txs.into_iter()
             .map(|tx| self.get_one(tx)?.ok_or(BlobStoreError::MissingSidecar(tx)))
             .collect()
@@ -421,6 +494,7 @@ impl fmt::Debug for DiskFileBlobStoreInner {
             .field("blob_dir", &self.blob_dir)
             .field("cached_blobs", &self.blob_cache.try_lock().map(|lock| lock.len()))
             .field("txs_to_delete", &self.txs_to_delete.try_read())
+            .field("reindexed", &self.reindexed.try_lock().map(|lock| lock.len()))
             .finish()
     }
 }
@@ -473,6 +547,12 @@ impl DiskFileBlobStoreConfig {
         self.max_cached_entries = max_cached_entries;
         self
     }
+
+    /// Set how the blob store should be opened.
+    pub const fn with_open(mut self, open: OpenDiskFileBlobStore) -> Self {
+        self.open = open;
+        self
+    }
 }
 
 /// How to open a disk file blob store.
@@ -482,12 +562,17 @@ pub enum OpenDiskFileBlobStore {
     #[default]
     Clear,
     /// Keep the existing blob store and index
+    ///
+    /// Blobs that are not claimed by a transaction again must be deleted with
+    /// [`BlobStore::delete_unclaimed`].
     ReIndex,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::blobstore::BlobTransactionSidecarEip7594;
+    use reth_primitives::BlobTransactionSidecar;
     use std::sync::atomic::Ordering;
 
     fn tmp_store() -> (DiskFileBlobStore, tempfile::TempDir) {
@@ -496,13 +581,18 @@ mod tests {
         (store, dir)
     }
 
-    fn rng_blobs(num: usize) -> Vec<(TxHash, BlobTransactionSidecar)> {
+    fn rng_blobs(num: usize) -> Vec<(TxHash, BlobTransactionSidecarVariant)> {
         let mut rng = rand::thread_rng();
         (0..num)
-            .map(|_| {
+            .map(|i| {
                 let tx = TxHash::random_with(&mut rng);
-                let blob =
-                    BlobTransactionSidecar { blobs: vec![], commitments: vec![], proofs: vec![] };
+                // store both sidecar versions
+                let blob = if i % 2 == 0 {
+                    BlobTransactionSidecar { blobs: vec![], commitments: vec![], proofs: vec![] }
+                        .into()
+                } else {
+                    BlobTransactionSidecarEip7594::default().into()
+                };
                 (tx, blob)
             })
             .collect()
@@ -542,4 +632,57 @@ mod tests {
         assert_eq!(store.data_size_hint(), Some(0));
         assert_eq!(store.inner.size_tracker.num_blobs.load(Ordering::Relaxed), 0);
     }
+
+    #[test]
+    fn disk_reopen_reindex() {
+        let (store, dir) = tmp_store();
+
+        let blobs = rng_blobs(5);
+        store.insert_all(blobs.clone()).unwrap();
+        let size = store.data_size_hint();
+        drop(store);
+
+        let store = DiskFileBlobStore::open(
+            dir.path(),
+            DiskFileBlobStoreConfig::default().with_open(OpenDiskFileBlobStore::ReIndex),
+        )
+        .unwrap();
+        assert_eq!(store.blobs_len(), 5);
+        assert_eq!(store.data_size_hint(), size);
+        for (tx, blob) in &blobs {
+            assert_eq!(store.get(*tx).unwrap().unwrap(), *blob);
+        }
+
+        let store = DiskFileBlobStore::open(dir.path(), Default::default()).unwrap();
+        assert_eq!(store.blobs_len(), 0);
+        assert!(store.get(blobs[0].0).unwrap().is_none());
+    }
+
+    #[test]
+    fn delete_unclaimed_after_reindex() {
+        let (store, dir) = tmp_store();
+        let blobs = rng_blobs(5);
+        store.insert_all(blobs.clone()).unwrap();
+        drop(store);
+
+        let store = DiskFileBlobStore::open(
+            dir.path(),
+            DiskFileBlobStoreConfig::default().with_open(OpenDiskFileBlobStore::ReIndex),
+        )
+        .unwrap();
+        let claimed = blobs.iter().take(2).map(|(tx, _)| *tx).collect::<HashSet<_>>();
+        store.delete_unclaimed(&|tx| claimed.contains(tx)).unwrap();
+        let stat = store.cleanup();
+        assert_eq!(stat.delete_succeed, 3);
+        assert_eq!(store.blobs_len(), 2);
+        for (tx, _) in &blobs {
+            assert_eq!(store.contains(*tx).unwrap(), claimed.contains(tx));
+        }
+
+        // blobs inserted after reindexing are not affected
+        let (tx, blob) = rng_blobs(1).remove(0);
+        store.insert(tx, blob).unwrap();
+        store.delete_unclaimed(&|_| false).unwrap();
+        assert!(store.inner.txs_to_delete.read().is_empty());
+    }
 }
@@ -1,9 +1,11 @@
 use crate::blobstore::{
-    BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize, BlobTransactionSidecar,
+    blobs_and_proofs_v1, blobs_and_proofs_v2, find_versioned_hashes, BlobAndProofV2, BlobStore,
+    BlobStoreCleanupStat, BlobStoreError, BlobStoreSize, BlobTransactionSidecarVariant,
 };
 use alloy_eips::eip4844::BlobAndProofV1;
 use alloy_primitives::B256;
 use parking_lot::RwLock;
+use reth_primitives::kzg::KzgSettings;
 use std::{collections::HashMap, sync::Arc};
 
 /// An in-memory blob store.
@@ -15,7 +17,7 @@ pub struct InMemoryBlobStore {
 #[derive(Debug, Default)]
 struct InMemoryBlobStoreInner {
     /// Storage for all blob data.
-    store: RwLock<HashMap<B256, BlobTransactionSidecar>>,
+    store: RwLock<HashMap<B256, BlobTransactionSidecarVariant>>,
     size_tracker: BlobStoreSize,
 }
 
@@ -26,14 +28,17 @@ impl PartialEq for InMemoryBlobStoreInner {
 }
 
 impl BlobStore for InMemoryBlobStore {
-    fn insert(&self, tx: B256, data: BlobTransactionSidecar) -> Result<(), BlobStoreError> {
+    fn insert(&self, tx: B256, data: BlobTransactionSidecarVariant) -> Result<(), BlobStoreError> {
         let mut store = self.inner.store.write();
         self.inner.size_tracker.add_size(insert_size(&mut store, tx, data));
         self.inner.size_tracker.update_len(store.len());
         Ok(())
     }
 
-    fn insert_all(&self, txs: Vec<(B256, BlobTransactionSidecar)>) -> Result<(), BlobStoreError> {
+    fn insert_all(
+        &self,
+        txs: Vec<(B256, BlobTransactionSidecarVariant)>,
+    ) -> Result<(), BlobStoreError> {
         if txs.is_empty() {
             return Ok(())
         }
@@ -75,7 +80,7 @@ impl BlobStore for InMemoryBlobStore {
     }
 
     // Retrieves the decoded blob data for the given transaction hash.
-    fn get(&self, tx: B256) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
+    fn get(&self, tx: B256) -> Result<Option<BlobTransactionSidecarVariant>, BlobStoreError> {
         Ok(self.inner.store.read().get(&tx).cloned())
     }
 
@@ -86,12 +91,15 @@ impl BlobStore for InMemoryBlobStore {
     fn get_all(
         &self,
         txs: Vec<B256>,
-    ) -> Result<Vec<(B256, BlobTransactionSidecar)>, BlobStoreError> {
+    ) -> Result<Vec<(B256, BlobTransactionSidecarVariant)>, BlobStoreError> {
         let store = self.inner.store.read();
         Ok(txs.into_iter().filter_map(|tx| store.get(&tx).map(|item| (tx, item.clone()))).collect())
     }
 
-    fn get_exact(&self, txs: Vec<B256>) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError> {
+    fn get_exact(
+        &self,
+        txs: Vec<B256>,
+    ) -> Result<Vec<BlobTransactionSidecarVariant>, BlobStoreError> {
         let store = self.inner.store.read();
         txs.into_iter()
             .map(|tx| store.get(&tx).cloned().ok_or_else(|| BlobStoreError::MissingSidecar(tx)))
@@ -101,26 +109,28 @@ impl BlobStore for InMemoryBlobStore {
     fn get_by_versioned_hashes(
         &self,
         versioned_hashes: &[B256],
+        settings: &KzgSettings,
     ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError> {
-        let mut result = vec![None; versioned_hashes.len()];
-        for (_tx_hash, blob_sidecar) in self.inner.store.read().iter() {
-            for (i, blob_versioned_hash) in blob_sidecar.versioned_hashes().enumerate() {
-                for (j, target_versioned_hash) in versioned_hashes.iter().enumerate() {
-                    if blob_versioned_hash == *target_versioned_hash {
-                        result[j].get_or_insert_with(|| BlobAndProofV1 {
-                            blob: Box::new(blob_sidecar.blobs[i]),
-                            proof: blob_sidecar.proofs[i],
-                        });
-                    }
-                }
-            }
-
-            // Return early if all blobs are found.
-            if result.iter().all(|blob| blob.is_some()) {
-                break;
-            }
-        }
-        Ok(result)
+        let matches = {
+            let store = self.inner.store.read();
+            find_versioned_hashes(store.values(), versioned_hashes)
+        };
+        // proofs of sidecars with cell proofs are computed after releasing the lock
+        blobs_and_proofs_v1(matches, versioned_hashes.len(), settings)
+    }
+
+    fn get_by_versioned_hashes_v2(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV2>>, BlobStoreError> {
+        let matches = {
+            let store = self.inner.store.read();
+            find_versioned_hashes(
+                store.values().filter(|blob_sidecar| blob_sidecar.is_eip7594()),
+                versioned_hashes,
+            )
+        };
+        Ok(blobs_and_proofs_v2(matches, versioned_hashes.len()))
     }
 
     fn data_size_hint(&self) -> Option<usize> {
@@ -134,7 +144,7 @@ impl BlobStore for InMemoryBlobStore {
 
 /// Removes the given blob from the store and returns the size of the blob that was removed.
 #[inline]
-fn remove_size(store: &mut HashMap<B256, BlobTransactionSidecar>, tx: &B256) -> usize {
+fn remove_size(store: &mut HashMap<B256, BlobTransactionSidecarVariant>, tx: &B256) -> usize {
     store.remove(tx).map(|rem| rem.size()).unwrap_or_default()
 }
 
@@ -143,9 +153,9 @@ fn remove_size(store: &mut HashMap<B256, BlobTransactionSidecar>, tx: &B256) ->
 /// We don't need to handle the size updates for replacements because transactions are unique.
 #[inline]
 fn insert_size(
-    store: &mut HashMap<B256, BlobTransactionSidecar>,
+    store: &mut HashMap<B256, BlobTransactionSidecarVariant>,
     tx: B256,
-    blob: BlobTransactionSidecar,
+    blob: BlobTransactionSidecarVariant,
 ) -> usize {
     let add = blob.size();
     store.insert(tx, blob);
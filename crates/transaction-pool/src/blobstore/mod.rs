@@ -5,7 +5,11 @@ use alloy_primitives::B256;
 pub use disk::{DiskFileBlobStore, DiskFileBlobStoreConfig, OpenDiskFileBlobStore};
 pub use mem::InMemoryBlobStore;
 pub use noop::NoopBlobStore;
-use reth_primitives::BlobTransactionSidecar;
+use reth_primitives::{kzg::KzgSettings, BlobTransactionSidecar, BlobTransactionValidationError};
+pub use sidecar::{
+    BlobAndProofV2, BlobTransactionSidecarEip7594, BlobTransactionSidecarVariant,
+    CELLS_PER_EXT_BLOB, EIP7594_WRAPPER_VERSION,
+};
 use std::{
     fmt,
     sync::atomic::{AtomicUsize, Ordering},
@@ -15,6 +19,7 @@ pub use tracker::{BlobStoreCanonTracker, BlobStoreUpdates};
 pub mod disk;
 mod mem;
 mod noop;
+mod sidecar;
 mod tracker;
 
 /// A blob store that can be used to store blob data of EIP4844 transactions.
@@ -22,13 +27,19 @@ mod tracker;
 /// This type is responsible for keeping track of blob data until it is no longer needed (after
 /// finalization).
 ///
+/// Sidecars are stored as they were inserted, either with per-blob or per-cell KZG proofs, see
+/// [`BlobTransactionSidecarVariant`].
+///
 /// Note: this is Clone because it is expected to be wrapped in an Arc.
 pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
     /// Inserts the blob sidecar into the store
-    fn insert(&self, tx: B256, data: BlobTransactionSidecar) -> Result<(), BlobStoreError>;
+    fn insert(&self, tx: B256, data: BlobTransactionSidecarVariant) -> Result<(), BlobStoreError>;
 
     /// Inserts multiple blob sidecars into the store
-    fn insert_all(&self, txs: Vec<(B256, BlobTransactionSidecar)>) -> Result<(), BlobStoreError>;
+    fn insert_all(
+        &self,
+        txs: Vec<(B256, BlobTransactionSidecarVariant)>,
+    ) -> Result<(), BlobStoreError>;
 
     /// Deletes the blob sidecar from the store
     fn delete(&self, tx: B256) -> Result<(), BlobStoreError>;
@@ -43,8 +54,19 @@ pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
     /// the store uses deferred cleanup: [`DiskFileBlobStore`]
     fn cleanup(&self) -> BlobStoreCleanupStat;
 
+    /// Marks the blobs that the store kept from a previous run for deletion, unless `is_claimed`
+    /// returns `true` for their transaction hash.
+    ///
+    /// This is intended to be called once the transactions of the previous run were restored, see
+    /// [`OpenDiskFileBlobStore::ReIndex`]. Stores that don't keep blobs across runs don't need to
+    /// do anything.
+    fn delete_unclaimed(&self, is_claimed: &dyn Fn(&B256) -> bool) -> Result<(), BlobStoreError> {
+        let _ = is_claimed;
+        Ok(())
+    }
+
     /// Retrieves the decoded blob data for the given transaction hash.
-    fn get(&self, tx: B256) -> Result<Option<BlobTransactionSidecar>, BlobStoreError>;
+    fn get(&self, tx: B256) -> Result<Option<BlobTransactionSidecarVariant>, BlobStoreError>;
 
     /// Checks if the given transaction hash is in the blob store.
     fn contains(&self, tx: B256) -> Result<bool, BlobStoreError>;
@@ -58,20 +80,34 @@ pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
     fn get_all(
         &self,
         txs: Vec<B256>,
-    ) -> Result<Vec<(B256, BlobTransactionSidecar)>, BlobStoreError>;
+    ) -> Result<Vec<(B256, BlobTransactionSidecarVariant)>, BlobStoreError>;
 
-    /// Returns the exact [`BlobTransactionSidecar`] for the given transaction hashes in the exact
-    /// order they were requested.
+    /// Returns the exact [`BlobTransactionSidecarVariant`] for the given transaction hashes in the
+    /// exact order they were requested.
     ///
     /// Returns an error if any of the blobs are not found in the blob store.
-    fn get_exact(&self, txs: Vec<B256>) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError>;
+    fn get_exact(
+        &self,
+        txs: Vec<B256>,
+    ) -> Result<Vec<BlobTransactionSidecarVariant>, BlobStoreError>;
 
-    /// Return the [`BlobTransactionSidecar`]s for a list of blob versioned hashes.
+    /// Return the blobs and their KZG proofs for a list of blob versioned hashes.
+    ///
+    /// The blob proofs of sidecars with cell proofs are computed on demand with the given settings.
     fn get_by_versioned_hashes(
         &self,
         versioned_hashes: &[B256],
+        settings: &KzgSettings,
     ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError>;
 
+    /// Return the blobs and their cell proofs for a list of blob versioned hashes.
+    ///
+    /// Only blobs of sidecars with cell proofs are returned.
+    fn get_by_versioned_hashes_v2(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV2>>, BlobStoreError>;
+
     /// Data size of all transactions in the blob store.
     fn data_size_hint(&self) -> Option<usize>;
 
@@ -88,11 +124,85 @@ pub enum BlobStoreError {
     /// Failed to decode the stored blob data.
     #[error("failed to decode blob data: {0}")]
     DecodeError(#[from] alloy_rlp::Error),
+    /// Failed to convert the stored blob data to another sidecar version.
+    #[error("failed to convert blob sidecar: {0}")]
+    Conversion(BlobTransactionValidationError),
     /// Other implementation specific error.
     #[error(transparent)]
     Other(Box<dyn core::error::Error + Send + Sync>),
 }
 
+/// Converts a stored sidecar into an EIP-4844 sidecar, computing the blob proofs of sidecars with
+/// cell proofs.
+pub(crate) fn into_eip4844_sidecar(
+    sidecar: BlobTransactionSidecarVariant,
+    settings: &KzgSettings,
+) -> Result<BlobTransactionSidecar, BlobStoreError> {
+    sidecar.try_into_eip4844(settings).map_err(BlobStoreError::Conversion)
+}
+
+/// Finds the sidecars containing the given versioned hashes.
+///
+/// Returns clones of the matching sidecars, each with the `(blob index, versioned hash index)`
+/// pairs it contains, so that proofs can be derived after releasing the lock guarding `sidecars`.
+pub(crate) fn find_versioned_hashes<'a>(
+    sidecars: impl IntoIterator<Item = &'a BlobTransactionSidecarVariant>,
+    versioned_hashes: &[B256],
+) -> Vec<(BlobTransactionSidecarVariant, Vec<(usize, usize)>)> {
+    let mut found = vec![false; versioned_hashes.len()];
+    let mut matches = Vec::new();
+    for blob_sidecar in sidecars {
+        let mut indices = Vec::new();
+        for (i, blob_versioned_hash) in blob_sidecar.versioned_hashes().enumerate() {
+            for (j, target_versioned_hash) in versioned_hashes.iter().enumerate() {
+                if blob_versioned_hash == *target_versioned_hash && !found[j] {
+                    found[j] = true;
+                    indices.push((i, j));
+                }
+            }
+        }
+        if !indices.is_empty() {
+            matches.push((blob_sidecar.clone(), indices));
+        }
+
+        // Return early if all blobs are found.
+        if found.iter().all(|found| *found) {
+            break
+        }
+    }
+    matches
+}
+
+/// Returns the blobs and their KZG proofs for the sidecars found by [`find_versioned_hashes`].
+pub(crate) fn blobs_and_proofs_v1(
+    matches: Vec<(BlobTransactionSidecarVariant, Vec<(usize, usize)>)>,
+    len: usize,
+    settings: &KzgSettings,
+) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError> {
+    let mut result = vec![None; len];
+    for (blob_sidecar, indices) in matches {
+        for (i, j) in indices {
+            result[j] =
+                blob_sidecar.blob_and_proof_v1(i, settings).map_err(BlobStoreError::Conversion)?;
+        }
+    }
+    Ok(result)
+}
+
+/// Returns the blobs and their cell proofs for the sidecars found by [`find_versioned_hashes`].
+pub(crate) fn blobs_and_proofs_v2(
+    matches: Vec<(BlobTransactionSidecarVariant, Vec<(usize, usize)>)>,
+    len: usize,
+) -> Vec<Option<BlobAndProofV2>> {
+    let mut result = vec![None; len];
+    for (blob_sidecar, indices) in matches {
+        for (i, j) in indices {
+            result[j] = blob_sidecar.blob_and_proofs_v2(i);
+        }
+    }
+    result
+}
+
 /// Keeps track of the size of the blob store.
 #[derive(Debug, Default)]
 pub(crate) struct BlobStoreSize {
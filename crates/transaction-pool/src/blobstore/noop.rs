@@ -1,6 +1,9 @@
-use crate::blobstore::{BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobTransactionSidecar};
+use crate::blobstore::{
+    BlobAndProofV2, BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobTransactionSidecarVariant,
+};
 use alloy_eips::eip4844::BlobAndProofV1;
 use alloy_primitives::B256;
+use reth_primitives::kzg::KzgSettings;
 
 /// A blobstore implementation that does nothing
 #[derive(Clone, Copy, Debug, PartialOrd, PartialEq, Eq, Default)]
@@ -8,11 +11,18 @@ use alloy_primitives::B256;
 pub struct NoopBlobStore;
 
 impl BlobStore for NoopBlobStore {
-    fn insert(&self, _tx: B256, _data: BlobTransactionSidecar) -> Result<(), BlobStoreError> {
+    fn insert(
+        &self,
+        _tx: B256,
+        _data: BlobTransactionSidecarVariant,
+    ) -> Result<(), BlobStoreError> {
         Ok(())
     }
 
-    fn insert_all(&self, _txs: Vec<(B256, BlobTransactionSidecar)>) -> Result<(), BlobStoreError> {
+    fn insert_all(
+        &self,
+        _txs: Vec<(B256, BlobTransactionSidecarVariant)>,
+    ) -> Result<(), BlobStoreError> {
         Ok(())
     }
 
@@ -28,7 +38,7 @@ impl BlobStore for NoopBlobStore {
         BlobStoreCleanupStat::default()
     }
 
-    fn get(&self, _tx: B256) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
+    fn get(&self, _tx: B256) -> Result<Option<BlobTransactionSidecarVariant>, BlobStoreError> {
         Ok(None)
     }
 
@@ -39,11 +49,14 @@ impl BlobStore for NoopBlobStore {
     fn get_all(
         &self,
         _txs: Vec<B256>,
-    ) -> Result<Vec<(B256, BlobTransactionSidecar)>, BlobStoreError> {
+    ) -> Result<Vec<(B256, BlobTransactionSidecarVariant)>, BlobStoreError> {
         Ok(vec![])
     }
 
-    fn get_exact(&self, txs: Vec<B256>) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError> {
+    fn get_exact(
+        &self,
+        txs: Vec<B256>,
+    ) -> Result<Vec<BlobTransactionSidecarVariant>, BlobStoreError> {
         if txs.is_empty() {
             return Ok(vec![])
         }
@@ -53,10 +66,18 @@ impl BlobStore for NoopBlobStore {
     fn get_by_versioned_hashes(
         &self,
         versioned_hashes: &[B256],
+        _settings: &KzgSettings,
     ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError> {
         Ok(vec![None; versioned_hashes.len()])
     }
 
+    fn get_by_versioned_hashes_v2(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV2>>, BlobStoreError> {
+        Ok(vec![None; versioned_hashes.len()])
+    }
+
     fn data_size_hint(&self) -> Option<usize> {
         Some(0)
     }
@@ -0,0 +1,480 @@
+//! Blob sidecars with per-blob (EIP-4844) or per-cell (EIP-7594) KZG proofs.
+
+use alloy_eips::eip4844::{
+    kzg_to_versioned_hash, Blob, BlobAndProofV1, Bytes48, BYTES_PER_BLOB, BYTES_PER_COMMITMENT,
+    BYTES_PER_PROOF,
+};
+use alloy_primitives::B256;
+use alloy_rlp::{Decodable, Encodable};
+use reth_primitives::{
+    kzg::{self, KzgSettings},
+    BlobTransactionSidecar, BlobTransactionValidationError,
+};
+use rust_eth_kzg::DASContext;
+use std::sync::LazyLock;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The number of cells of an extended blob, see [EIP-7594](https://eips.ethereum.org/EIPS/eip-7594).
+pub const CELLS_PER_EXT_BLOB: usize = 128;
+
+/// The version byte that precedes the encoding of a [`BlobTransactionSidecarEip7594`].
+pub const EIP7594_WRAPPER_VERSION: u8 = 1;
+
+/// The context used to compute cell proofs.
+///
+/// This uses the trusted setup of the Ethereum KZG ceremony, custom [`KzgSettings`] only apply to
+/// blob commitments and blob proofs.
+static DAS_CONTEXT: LazyLock<DASContext> = LazyLock::new(DASContext::default);
+
+/// A blob sidecar with one KZG proof per cell of each extended blob, as introduced by
+/// [EIP-7594](https://eips.ethereum.org/EIPS/eip-7594).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlobTransactionSidecarEip7594 {
+    /// The blob data.
+    pub blobs: Vec<Blob>,
+    /// The blob commitments.
+    pub commitments: Vec<Bytes48>,
+    /// The cell proofs, [`CELLS_PER_EXT_BLOB`] consecutive proofs per blob.
+    pub cell_proofs: Vec<Bytes48>,
+}
+
+impl BlobTransactionSidecarEip7594 {
+    /// Constructs a new sidecar from a set of blobs, commitments, and cell proofs.
+    pub const fn new(
+        blobs: Vec<Blob>,
+        commitments: Vec<Bytes48>,
+        cell_proofs: Vec<Bytes48>,
+    ) -> Self {
+        Self { blobs, commitments, cell_proofs }
+    }
+
+    /// Converts an EIP-4844 sidecar into a sidecar with cell proofs by computing the cell proofs
+    /// of each blob.
+    ///
+    /// This expects a sidecar that was already validated.
+    pub fn try_from_eip4844(
+        sidecar: BlobTransactionSidecar,
+    ) -> Result<Self, BlobTransactionValidationError> {
+        let mut cell_proofs = Vec::with_capacity(sidecar.blobs.len() * CELLS_PER_EXT_BLOB);
+        for blob in &sidecar.blobs {
+            cell_proofs.extend(compute_cell_proofs(blob)?);
+        }
+        Ok(Self::new(sidecar.blobs, sidecar.commitments, cell_proofs))
+    }
+
+    /// Returns an iterator over the versioned hashes of the commitments.
+    pub fn versioned_hashes(&self) -> impl Iterator<Item = B256> + '_ {
+        self.commitments.iter().map(|c| kzg_to_versioned_hash(c.as_slice()))
+    }
+
+    /// Returns the cell proofs of the blob at the given index.
+    pub fn cell_proofs(&self, blob_index: usize) -> Option<&[Bytes48]> {
+        let start = blob_index.checked_mul(CELLS_PER_EXT_BLOB)?;
+        self.cell_proofs.get(start..start + CELLS_PER_EXT_BLOB)
+    }
+
+    /// Verifies that the sidecar is well-formed and matches the given versioned hashes.
+    ///
+    /// This checks the number of commitments and cell proofs, the versioned hashes, that every
+    /// commitment is the commitment of its blob and that the cell proofs are the proofs of the
+    /// cells of the extended blob.
+    ///
+    /// Cell proofs are deterministic, so they're verified by recomputing them from the blob.
+    pub fn validate(
+        &self,
+        blob_versioned_hashes: &[B256],
+        settings: &KzgSettings,
+    ) -> Result<(), BlobTransactionValidationError> {
+        if blob_versioned_hashes.len() != self.commitments.len() ||
+            self.blobs.len() != self.commitments.len()
+        {
+            return Err(kzg::Error::MismatchLength(format!(
+                "There are {} versioned commitment hashes, {} commitments and {} blobs",
+                blob_versioned_hashes.len(),
+                self.commitments.len(),
+                self.blobs.len()
+            ))
+            .into())
+        }
+        if self.cell_proofs.len() != self.blobs.len() * CELLS_PER_EXT_BLOB {
+            return Err(kzg::Error::MismatchLength(format!(
+                "There are {} cell proofs for {} blobs",
+                self.cell_proofs.len(),
+                self.blobs.len()
+            ))
+            .into())
+        }
+
+        for (versioned_hash, commitment) in blob_versioned_hashes.iter().zip(&self.commitments) {
+            let expected = kzg_to_versioned_hash(commitment.as_slice());
+            if *versioned_hash != expected {
+                return Err(BlobTransactionValidationError::WrongVersionedHash {
+                    have: *versioned_hash,
+                    expected,
+                })
+            }
+        }
+
+        for (blob, commitment) in self.blobs.iter().zip(&self.commitments) {
+            let computed =
+                kzg::KzgCommitment::blob_to_kzg_commitment(&to_kzg_blob(blob), settings)?;
+            if computed.to_bytes().as_slice() != commitment.as_slice() {
+                return Err(BlobTransactionValidationError::InvalidProof)
+            }
+        }
+
+        for (blob, cell_proofs) in
+            self.blobs.iter().zip(self.cell_proofs.chunks_exact(CELLS_PER_EXT_BLOB))
+        {
+            if compute_cell_proofs(blob)? != cell_proofs {
+                return Err(BlobTransactionValidationError::InvalidProof)
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Computes the KZG proof of the blob at the given index.
+    pub fn compute_blob_proof(
+        &self,
+        blob_index: usize,
+        settings: &KzgSettings,
+    ) -> Result<Bytes48, BlobTransactionValidationError> {
+        let (Some(blob), Some(commitment)) =
+            (self.blobs.get(blob_index), self.commitments.get(blob_index))
+        else {
+            return Err(BlobTransactionValidationError::MissingSidecar)
+        };
+        let proof = kzg::KzgProof::compute_blob_kzg_proof(
+            &to_kzg_blob(blob),
+            &kzg::Bytes48::from(commitment.0),
+            settings,
+        )?;
+        Ok(Bytes48::from(*proof.to_bytes()))
+    }
+
+    /// Converts the sidecar into an EIP-4844 sidecar by computing the KZG proof of each blob.
+    pub fn try_into_eip4844(
+        self,
+        settings: &KzgSettings,
+    ) -> Result<BlobTransactionSidecar, BlobTransactionValidationError> {
+        let proofs = (0..self.blobs.len())
+            .map(|i| self.compute_blob_proof(i, settings))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(BlobTransactionSidecar::new(self.blobs, self.commitments, proofs))
+    }
+
+    /// Calculates a size heuristic for the in-memory size of the sidecar.
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.blobs.len() * BYTES_PER_BLOB +
+            self.commitments.len() * BYTES_PER_COMMITMENT +
+            self.cell_proofs.len() * BYTES_PER_PROOF
+    }
+
+    /// Outputs the RLP length of the sidecar fields, without a RLP header.
+    pub fn fields_len(&self) -> usize {
+        self.blobs.length() + self.commitments.length() + self.cell_proofs.length()
+    }
+}
+
+impl Encodable for BlobTransactionSidecarEip7594 {
+    /// Encodes the sidecar fields as RLP bytes, without a RLP header.
+    fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+        self.blobs.encode(out);
+        self.commitments.encode(out);
+        self.cell_proofs.encode(out);
+    }
+
+    fn length(&self) -> usize {
+        self.fields_len()
+    }
+}
+
+impl Decodable for BlobTransactionSidecarEip7594 {
+    /// Decodes the sidecar fields from RLP bytes, without a RLP header.
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Ok(Self {
+            blobs: Decodable::decode(buf)?,
+            commitments: Decodable::decode(buf)?,
+            cell_proofs: Decodable::decode(buf)?,
+        })
+    }
+}
+
+/// A blob sidecar with either per-blob or per-cell KZG proofs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlobTransactionSidecarVariant {
+    /// A sidecar with one KZG proof per blob.
+    Eip4844(BlobTransactionSidecar),
+    /// A sidecar with one KZG proof per cell.
+    Eip7594(BlobTransactionSidecarEip7594),
+}
+
+impl BlobTransactionSidecarVariant {
+    /// Returns `true` if this is a sidecar with cell proofs.
+    pub const fn is_eip7594(&self) -> bool {
+        matches!(self, Self::Eip7594(_))
+    }
+
+    /// Returns the EIP-4844 sidecar if this is one.
+    pub const fn as_eip4844(&self) -> Option<&BlobTransactionSidecar> {
+        match self {
+            Self::Eip4844(sidecar) => Some(sidecar),
+            Self::Eip7594(_) => None,
+        }
+    }
+
+    /// Returns the EIP-7594 sidecar if this is one.
+    pub const fn as_eip7594(&self) -> Option<&BlobTransactionSidecarEip7594> {
+        match self {
+            Self::Eip4844(_) => None,
+            Self::Eip7594(sidecar) => Some(sidecar),
+        }
+    }
+
+    /// Returns the blobs of the sidecar.
+    pub fn blobs(&self) -> &[Blob] {
+        match self {
+            Self::Eip4844(sidecar) => &sidecar.blobs,
+            Self::Eip7594(sidecar) => &sidecar.blobs,
+        }
+    }
+
+    /// Returns an iterator over the versioned hashes of the commitments.
+    pub fn versioned_hashes(&self) -> impl Iterator<Item = B256> + '_ {
+        let commitments = match self {
+            Self::Eip4844(sidecar) => &sidecar.commitments,
+            Self::Eip7594(sidecar) => &sidecar.commitments,
+        };
+        commitments.iter().map(|c| kzg_to_versioned_hash(c.as_slice()))
+    }
+
+    /// Verifies the sidecar against the given versioned hashes.
+    pub fn validate(
+        &self,
+        blob_versioned_hashes: &[B256],
+        settings: &KzgSettings,
+    ) -> Result<(), BlobTransactionValidationError> {
+        match self {
+            Self::Eip4844(sidecar) => sidecar.validate(blob_versioned_hashes, settings),
+            Self::Eip7594(sidecar) => sidecar.validate(blob_versioned_hashes, settings),
+        }
+    }
+
+    /// Returns the blob at the given index with its KZG proof.
+    ///
+    /// The proof is computed if this is a sidecar with cell proofs.
+    pub fn blob_and_proof_v1(
+        &self,
+        blob_index: usize,
+        settings: &KzgSettings,
+    ) -> Result<Option<BlobAndProofV1>, BlobTransactionValidationError> {
+        let Some(blob) = self.blobs().get(blob_index) else { return Ok(None) };
+        let proof = match self {
+            Self::Eip4844(sidecar) => sidecar.proofs[blob_index],
+            Self::Eip7594(sidecar) => sidecar.compute_blob_proof(blob_index, settings)?,
+        };
+        Ok(Some(BlobAndProofV1 { blob: Box::new(*blob), proof }))
+    }
+
+    /// Returns the blob at the given index with its cell proofs.
+    ///
+    /// Returns `None` for sidecars without cell proofs.
+    pub fn blob_and_proofs_v2(&self, blob_index: usize) -> Option<BlobAndProofV2> {
+        let sidecar = self.as_eip7594()?;
+        Some(BlobAndProofV2 {
+            blob: Box::new(*sidecar.blobs.get(blob_index)?),
+            proofs: sidecar.cell_proofs(blob_index)?.to_vec(),
+        })
+    }
+
+    /// Converts the sidecar into a sidecar with cell proofs, computing the cell proofs if
+    /// necessary.
+    pub fn try_into_eip7594(
+        self,
+    ) -> Result<BlobTransactionSidecarEip7594, BlobTransactionValidationError> {
+        match self {
+            Self::Eip4844(sidecar) => BlobTransactionSidecarEip7594::try_from_eip4844(sidecar),
+            Self::Eip7594(sidecar) => Ok(sidecar),
+        }
+    }
+
+    /// Converts the sidecar into an EIP-4844 sidecar, computing the blob proofs if necessary.
+    pub fn try_into_eip4844(
+        self,
+        settings: &KzgSettings,
+    ) -> Result<BlobTransactionSidecar, BlobTransactionValidationError> {
+        match self {
+            Self::Eip4844(sidecar) => Ok(sidecar),
+            Self::Eip7594(sidecar) => sidecar.try_into_eip4844(settings),
+        }
+    }
+
+    /// Calculates a size heuristic for the in-memory size of the sidecar.
+    #[inline]
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Eip4844(sidecar) => sidecar.size(),
+            Self::Eip7594(sidecar) => sidecar.size(),
+        }
+    }
+}
+
+impl Encodable for BlobTransactionSidecarVariant {
+    /// Encodes EIP-4844 sidecars as before and EIP-7594 sidecars prefixed with
+    /// [`EIP7594_WRAPPER_VERSION`], so that existing encodings remain decodable.
+    fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+        match self {
+            Self::Eip4844(sidecar) => sidecar.encode(out),
+            Self::Eip7594(sidecar) => {
+                out.put_u8(EIP7594_WRAPPER_VERSION);
+                sidecar.encode(out);
+            }
+        }
+    }
+
+    fn length(&self) -> usize {
+        match self {
+            Self::Eip4844(sidecar) => sidecar.length(),
+            Self::Eip7594(sidecar) => 1 + sidecar.length(),
+        }
+    }
+}
+
+impl Decodable for BlobTransactionSidecarVariant {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        match buf.first() {
+            Some(&EIP7594_WRAPPER_VERSION) => {
+                *buf = &buf[1..];
+                BlobTransactionSidecarEip7594::decode(buf).map(Self::Eip7594)
+            }
+            // EIP-4844 sidecars start with the RLP list of blobs
+            Some(_) => BlobTransactionSidecar::decode(buf).map(Self::Eip4844),
+            None => Err(alloy_rlp::Error::InputTooShort),
+        }
+    }
+}
+
+impl From<BlobTransactionSidecar> for BlobTransactionSidecarVariant {
+    fn from(sidecar: BlobTransactionSidecar) -> Self {
+        Self::Eip4844(sidecar)
+    }
+}
+
+impl From<BlobTransactionSidecarEip7594> for BlobTransactionSidecarVariant {
+    fn from(sidecar: BlobTransactionSidecarEip7594) -> Self {
+        Self::Eip7594(sidecar)
+    }
+}
+
+/// Blob type returned in responses to `engine_getBlobsV2`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BlobAndProofV2 {
+    /// The blob data.
+    pub blob: Box<Blob>,
+    /// The cell proofs of the blob.
+    pub proofs: Vec<Bytes48>,
+}
+
+/// Converts the blob into the [`kzg::Blob`] type.
+fn to_kzg_blob(blob: &Blob) -> kzg::Blob {
+    kzg::Blob::from(blob.0)
+}
+
+/// Computes the [`CELLS_PER_EXT_BLOB`] cell proofs of the blob.
+fn compute_cell_proofs(blob: &Blob) -> Result<Vec<Bytes48>, BlobTransactionValidationError> {
+    let (_cells, proofs) = DAS_CONTEXT
+        .compute_cells_and_kzg_proofs(&blob.0)
+        .map_err(|_| BlobTransactionValidationError::InvalidProof)?;
+    Ok(proofs.into_iter().map(Bytes48::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::kzg::BYTES_PER_FIELD_ELEMENT;
+    use revm::primitives::EnvKzgSettings;
+
+    fn eip7594_sidecar() -> BlobTransactionSidecarEip7594 {
+        let mut blob = Blob::default();
+        // keep every field element below the modulus
+        for chunk in blob.chunks_mut(BYTES_PER_FIELD_ELEMENT) {
+            chunk[1..].copy_from_slice(&[1; BYTES_PER_FIELD_ELEMENT - 1]);
+        }
+        let settings = EnvKzgSettings::Default;
+        let commitment =
+            kzg::KzgCommitment::blob_to_kzg_commitment(&to_kzg_blob(&blob), settings.get())
+                .unwrap();
+        let cell_proofs = compute_cell_proofs(&blob).unwrap();
+        BlobTransactionSidecarEip7594::new(
+            vec![blob],
+            vec![Bytes48::from(*commitment.to_bytes())],
+            cell_proofs,
+        )
+    }
+
+    #[test]
+    fn variant_encoding_roundtrip() {
+        let legacy = BlobTransactionSidecarVariant::Eip4844(BlobTransactionSidecar::default());
+        let mut buf = Vec::new();
+        legacy.encode(&mut buf);
+        assert_eq!(buf.len(), legacy.length());
+        assert_eq!(BlobTransactionSidecarVariant::decode(&mut buf.as_slice()).unwrap(), legacy);
+
+        // stored EIP-4844 sidecars remain decodable
+        let mut old = Vec::new();
+        BlobTransactionSidecar::default().encode(&mut old);
+        assert_eq!(old, buf);
+
+        let sidecar = BlobTransactionSidecarVariant::Eip7594(eip7594_sidecar());
+        let mut buf = Vec::new();
+        sidecar.encode(&mut buf);
+        assert_eq!(buf[0], EIP7594_WRAPPER_VERSION);
+        assert_eq!(buf.len(), sidecar.length());
+        assert_eq!(BlobTransactionSidecarVariant::decode(&mut buf.as_slice()).unwrap(), sidecar);
+    }
+
+    #[test]
+    fn validate_and_convert_eip7594() {
+        let settings = EnvKzgSettings::Default;
+        let sidecar = eip7594_sidecar();
+        let hashes = sidecar.versioned_hashes().collect::<Vec<_>>();
+        sidecar.validate(&hashes, settings.get()).unwrap();
+
+        let mut missing_proofs = sidecar.clone();
+        missing_proofs.cell_proofs.pop();
+        assert!(missing_proofs.validate(&hashes, settings.get()).is_err());
+
+        let mut swapped_proofs = sidecar.clone();
+        swapped_proofs.cell_proofs.swap(0, 1);
+        assert!(matches!(
+            swapped_proofs.validate(&hashes, settings.get()),
+            Err(BlobTransactionValidationError::InvalidProof)
+        ));
+
+        let mut zero_proof = sidecar.clone();
+        zero_proof.cell_proofs[CELLS_PER_EXT_BLOB - 1] = Bytes48::default();
+        assert!(matches!(
+            zero_proof.validate(&hashes, settings.get()),
+            Err(BlobTransactionValidationError::InvalidProof)
+        ));
+
+        let variant = BlobTransactionSidecarVariant::from(sidecar.clone());
+        assert_eq!(variant.blob_and_proofs_v2(0).unwrap().proofs.len(), CELLS_PER_EXT_BLOB);
+
+        let converted = sidecar.clone().try_into_eip4844(settings.get()).unwrap();
+        converted.validate(&hashes, settings.get()).unwrap();
+        assert_eq!(
+            variant.blob_and_proof_v1(0, settings.get()).unwrap().unwrap().proof,
+            converted.proofs[0]
+        );
+
+        // converting back recomputes the same cell proofs
+        let variant = BlobTransactionSidecarVariant::from(converted);
+        assert_eq!(variant.try_into_eip7594().unwrap(), sidecar);
+    }
+}
@@ -0,0 +1,180 @@
+//! Conditions of transactions submitted via `eth_sendRawTransactionConditional`.
+
+use alloy_primitives::{Address, BlockNumber, B256};
+use reth_storage_api::{errors::provider::ProviderResult, StateProvider};
+use reth_trie::HashedStorage;
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+
+/// The maximum number of storage roots and slots a [`TransactionConditional`] may require to be
+/// checked.
+pub const MAX_TRANSACTION_CONDITIONAL_COST: usize = 1000;
+
+/// The expected storage of an account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KnownAccountStorage {
+    /// The expected storage root of the account.
+    StorageRoot(B256),
+    /// The expected values of individual storage slots.
+    Slots(HashMap<B256, B256>),
+}
+
+/// Conditions that must hold for a transaction to be included in a block.
+///
+/// The block number and timestamp bounds are inclusive. The known accounts are checked against the
+/// state of the parent block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransactionConditional {
+    /// The expected storage of accounts.
+    pub known_accounts: HashMap<Address, KnownAccountStorage>,
+    /// The minimum block number the transaction can be included in.
+    pub block_number_min: Option<BlockNumber>,
+    /// The maximum block number the transaction can be included in.
+    pub block_number_max: Option<BlockNumber>,
+    /// The minimum timestamp of the block the transaction can be included in.
+    pub timestamp_min: Option<u64>,
+    /// The maximum timestamp of the block the transaction can be included in.
+    pub timestamp_max: Option<u64>,
+}
+
+impl TransactionConditional {
+    /// Returns the number of storage roots and slots that need to be checked.
+    pub fn cost(&self) -> usize {
+        self.known_accounts
+            .values()
+            .map(|storage| match storage {
+                KnownAccountStorage::StorageRoot(_) => 1,
+                KnownAccountStorage::Slots(slots) => slots.len(),
+            })
+            .sum()
+    }
+
+    /// Returns `true` if the transaction can be included in a block with the given number and
+    /// timestamp.
+    pub fn matches_block_attributes(&self, block_number: BlockNumber, timestamp: u64) -> bool {
+        self.block_number_min.map_or(true, |min| block_number >= min) &&
+            self.block_number_max.map_or(true, |max| block_number <= max) &&
+            self.timestamp_min.map_or(true, |min| timestamp >= min) &&
+            self.timestamp_max.map_or(true, |max| timestamp <= max)
+    }
+
+    /// Returns `true` if the transaction can't be included in a block with the given number and
+    /// timestamp or any later block.
+    pub fn has_exceeded_block_attributes(&self, block_number: BlockNumber, timestamp: u64) -> bool {
+        self.block_number_max.map_or(false, |max| block_number > max) ||
+            self.timestamp_max.map_or(false, |max| timestamp > max)
+    }
+
+    /// Returns `true` if the storage of all known accounts matches the given state.
+    pub fn matches_known_accounts<S: StateProvider + ?Sized>(
+        &self,
+        state: &S,
+    ) -> ProviderResult<bool> {
+        self.matches_changed_known_accounts(state, None, &mut HashMap::new())
+    }
+
+    /// Returns `true` if the storage of all known accounts that are in the `changed` set matches
+    /// the given state.
+    ///
+    /// If `changed` is `None`, all known accounts are checked. Storage roots are looked up in and
+    /// added to `storage_roots`, so they're only computed once for all conditionals checked
+    /// against the same state.
+    pub fn matches_changed_known_accounts<S: StateProvider + ?Sized>(
+        &self,
+        state: &S,
+        changed: Option<&HashSet<Address>>,
+        storage_roots: &mut HashMap<Address, B256>,
+    ) -> ProviderResult<bool> {
+        for (address, expected) in &self.known_accounts {
+            if changed.is_some_and(|changed| !changed.contains(address)) {
+                continue
+            }
+            match expected {
+                KnownAccountStorage::StorageRoot(root) => {
+                    let current = match storage_roots.entry(*address) {
+                        Entry::Occupied(entry) => *entry.get(),
+                        Entry::Vacant(entry) => {
+                            *entry.insert(state.storage_root(*address, HashedStorage::default())?)
+                        }
+                    };
+                    if current != *root {
+                        return Ok(false)
+                    }
+                }
+                KnownAccountStorage::Slots(slots) => {
+                    for (slot, value) in slots {
+                        let current = state.storage(*address, *slot)?.unwrap_or_default();
+                        if B256::from(current) != *value {
+                            return Ok(false)
+                        }
+                    }
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// Returns `true` if any of the known accounts is in the given set.
+    pub fn has_known_account_in(&self, accounts: &HashSet<Address>) -> bool {
+        self.known_accounts.keys().any(|address| accounts.contains(address))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::test_utils::MockEthProvider;
+
+    #[test]
+    fn block_attributes() {
+        let conditional = TransactionConditional {
+            block_number_min: Some(10),
+            block_number_max: Some(20),
+            timestamp_max: Some(1000),
+            ..Default::default()
+        };
+
+        assert!(!conditional.matches_block_attributes(9, 0));
+        assert!(conditional.matches_block_attributes(10, 0));
+        assert!(conditional.matches_block_attributes(20, 1000));
+        assert!(!conditional.matches_block_attributes(21, 1000));
+        assert!(!conditional.matches_block_attributes(20, 1001));
+
+        assert!(!conditional.has_exceeded_block_attributes(9, 0));
+        assert!(!conditional.has_exceeded_block_attributes(20, 1000));
+        assert!(conditional.has_exceeded_block_attributes(21, 0));
+        assert!(conditional.has_exceeded_block_attributes(15, 1001));
+    }
+
+    #[test]
+    fn changed_known_accounts() {
+        let state = MockEthProvider::default();
+        let address = Address::with_last_byte(1);
+        let conditional = TransactionConditional {
+            known_accounts: HashMap::from([(
+                address,
+                KnownAccountStorage::StorageRoot(B256::with_last_byte(1)),
+            )]),
+            ..Default::default()
+        };
+
+        assert!(!conditional.matches_known_accounts(&state).unwrap());
+
+        // unchanged accounts are not checked
+        let mut storage_roots = HashMap::new();
+        assert!(conditional
+            .matches_changed_known_accounts(&state, Some(&HashSet::new()), &mut storage_roots)
+            .unwrap());
+        assert!(storage_roots.is_empty());
+
+        // storage roots are computed once
+        let changed = HashSet::from([address]);
+        assert!(!conditional
+            .matches_changed_known_accounts(&state, Some(&changed), &mut storage_roots)
+            .unwrap());
+        assert_eq!(storage_roots.len(), 1);
+        storage_roots.insert(address, B256::with_last_byte(1));
+        assert!(conditional
+            .matches_changed_known_accounts(&state, Some(&changed), &mut storage_roots)
+            .unwrap());
+    }
+}
@@ -1,5 +1,8 @@
 use crate::{
-    pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
+    admission::AdmissionConfig,
+    pool::{
+        TransactionJournalConfig, NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE,
+    },
     PoolSize, TransactionOrigin,
 };
 use alloy_consensus::constants::EIP4844_TX_TYPE_ID;
@@ -10,6 +13,12 @@ use std::{collections::HashSet, ops::Mul};
 /// Guarantees max transactions for one sender, compatible with geth/erigon
 pub const TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER: usize = 16;
 
+/// Max number of blob transactions one sender can have in the blob pool.
+///
+/// Lower than [`TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER`], since blob transactions are much larger than
+/// regular transactions.
+pub const TXPOOL_MAX_BLOB_ACCOUNT_SLOTS_PER_SENDER: usize = 8;
+
 /// The default maximum allowed number of transactions in the given subpool.
 pub const TXPOOL_SUBPOOL_MAX_TXS_DEFAULT: usize = 10_000;
 
@@ -42,6 +51,8 @@ pub struct PoolConfig {
     pub max_account_slots: usize,
     /// Price bump (in %) for the transaction pool underpriced check.
     pub price_bumps: PriceBumpConfig,
+    /// Additional policies that only apply to blob transactions.
+    pub blob_pool: BlobPoolConfig,
     /// Minimum base fee required by the protocol.
     pub minimal_protocol_basefee: u64,
     /// The max gas limit for transactions in the pool
@@ -53,6 +64,10 @@ pub struct PoolConfig {
     pub pending_tx_listener_buffer_size: usize,
     /// Bound on number of new transactions from `reth_network::TransactionsManager` to buffer.
     pub new_tx_listener_buffer_size: usize,
+    /// Built-in admission policies that are checked before transactions are validated.
+    pub admission: AdmissionConfig,
+    /// Bounds of the in-memory journal of transaction lifecycle events.
+    pub journal: TransactionJournalConfig,
 }
 
 impl PoolConfig {
@@ -75,11 +90,14 @@ impl Default for PoolConfig {
             blob_limit: Default::default(),
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
             price_bumps: Default::default(),
+            blob_pool: Default::default(),
             minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE,
             gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
             local_transactions_config: Default::default(),
             pending_tx_listener_buffer_size: PENDING_TX_LISTENER_BUFFER_SIZE,
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
+            admission: Default::default(),
+            journal: Default::default(),
         }
     }
 }
@@ -154,6 +172,30 @@ impl Default for PriceBumpConfig {
     }
 }
 
+/// Policies that only apply to EIP-4844 blob transactions.
+///
+/// These are enforced in addition to the general rules of the pool, blob transactions are also
+/// always gapless and mutually exclusive with non-blob transactions of the same sender.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct BlobPoolConfig {
+    /// Max number of blob transactions a single non-local sender can have in the pool.
+    ///
+    /// This only has an effect if it is lower than [`PoolConfig::max_account_slots`].
+    pub max_account_slots: usize,
+    /// Max number of (logarithmic) fee jumps the fees must decrease by before a blob
+    /// transaction becomes executable, see [`fee_delta`](crate::pool::fee_delta).
+    ///
+    /// New blob transactions that are further away from the current fees are rejected and parked
+    /// blob transactions that fall behind are evicted on new blocks. `None` disables this.
+    pub max_fee_jumps: Option<u64>,
+}
+
+impl Default for BlobPoolConfig {
+    fn default() -> Self {
+        Self { max_account_slots: TXPOOL_MAX_BLOB_ACCOUNT_SLOTS_PER_SENDER, max_fee_jumps: None }
+    }
+}
+
 /// Configuration options for the locally received transactions:
 /// [`TransactionOrigin::Local`](TransactionOrigin)
 #[derive(Debug, Clone, Eq, PartialEq)]
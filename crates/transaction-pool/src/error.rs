@@ -1,5 +1,6 @@
 //! Transaction pool errors
 
+use crate::admission::AdmissionError;
 use alloy_primitives::{Address, TxHash, U256};
 use reth_primitives::{BlobTransactionValidationError, InvalidTransactionError};
 
@@ -170,6 +171,14 @@ pub enum Eip4844PoolTransactionError {
     /// would introduce gap in the nonce sequence.
     #[error("nonce too high")]
     Eip4844NonceGap,
+    /// Thrown if the sender already occupies all of its blob transaction slots in the pool.
+    ///
+    /// See [`BlobPoolConfig::max_account_slots`](crate::BlobPoolConfig::max_account_slots).
+    #[error("sender {0} exceeded blob transaction capacity")]
+    ExceededSenderBlobCapacity(Address),
+    /// Thrown if a blob sidecar with cell proofs arrives while EIP-7594 sidecars aren't accepted.
+    #[error("EIP-7594 blob sidecars are not supported")]
+    UnexpectedEip7594Sidecar,
 }
 
 /// Represents all errors that can happen when validating transactions for the pool for EIP-7702
@@ -226,6 +235,9 @@ pub enum InvalidPoolTransactionError {
     /// invocation.
     #[error("intrinsic gas too low")]
     IntrinsicGasTooLow,
+    /// Rejected by an [`AdmissionPolicy`](crate::admission::AdmissionPolicy) of the pool.
+    #[error(transparent)]
+    Admission(#[from] AdmissionError),
 }
 
 // === impl InvalidPoolTransactionError ===
@@ -283,6 +295,10 @@ impl InvalidPoolTransactionError {
             }
             Self::IntrinsicGasTooLow => true,
             Self::Overdraft { .. } => false,
+            Self::Admission(_) => {
+                // local setting
+                false
+            }
             Self::Other(err) => err.is_bad_transaction(),
             Self::Eip4844(eip4844_err) => {
                 match eip4844_err {
@@ -300,6 +316,10 @@ impl InvalidPoolTransactionError {
                         // is only thrown for valid(good) blob transactions
                         false
                     }
+                    Eip4844PoolTransactionError::ExceededSenderBlobCapacity(_) => {
+                        // local setting
+                        false
+                    }
                     Eip4844PoolTransactionError::NoEip4844Blobs => {
                         // this is a malformed transaction and should not be sent over the network
                         true
@@ -308,6 +328,10 @@ impl InvalidPoolTransactionError {
                         // this is a malformed transaction and should not be sent over the network
                         true
                     }
+                    Eip4844PoolTransactionError::UnexpectedEip7594Sidecar => {
+                        // depends on the local fork configuration
+                        false
+                    }
                 }
             }
             Self::Eip7702(eip7702_err) => match eip7702_err {
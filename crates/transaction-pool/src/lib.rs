@@ -150,9 +150,9 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
-use crate::{identifier::TransactionId, pool::PoolInner};
+use crate::{blobstore::into_eip4844_sidecar, identifier::TransactionId, pool::PoolInner};
 use alloy_eips::eip4844::BlobAndProofV1;
-use alloy_primitives::{Address, TxHash, B256, U256};
+use alloy_primitives::{Address, BlockHash, TxHash, B256, U256};
 use aquamarine as _;
 use reth_eth_wire_types::HandleMempoolData;
 use reth_execution_types::ChangedAccount;
@@ -163,18 +163,28 @@ use tokio::sync::mpsc::Receiver;
 use tracing::{instrument, trace};
 
 pub use crate::{
-    blobstore::{BlobStore, BlobStoreError},
+    admission::{AdmissionConfig, AdmissionError, AdmissionPolicy},
+    blobstore::{
+        BlobAndProofV2, BlobStore, BlobStoreError, BlobTransactionSidecarEip7594,
+        BlobTransactionSidecarVariant,
+    },
+    conditional::TransactionConditional,
     config::{
-        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-        DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, REPLACE_BLOB_PRICE_BUMP,
-        TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
-        TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+        BlobPoolConfig, LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit,
+        DEFAULT_PRICE_BUMP, DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, REPLACE_BLOB_PRICE_BUMP,
+        TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_MAX_BLOB_ACCOUNT_SLOTS_PER_SENDER,
+        TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
     error::PoolResult,
-    ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
+    ordering::{
+        ArrivalTimeOrdering, BuiltinOrdering, CalldataTipOrdering, CoinbaseTipOrdering, Priority,
+        TransactionOrdering, TransactionOrderingKind,
+    },
     pool::{
-        blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, FullTransactionEvent,
-        TransactionEvent, TransactionEvents,
+        blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, DiscardReason,
+        FullTransactionEvent, TransactionEvent, TransactionEvents, TransactionHistory,
+        TransactionJournalConfig, TransactionJournalEntry, TransactionJournalEvent,
+        TransactionJournalEvents, TransactionStatus,
     },
     traits::*,
     validate::{
@@ -183,6 +193,8 @@ pub use crate::{
     },
 };
 
+pub mod admission;
+pub mod conditional;
 pub mod error;
 pub mod maintain;
 pub mod metrics;
@@ -203,7 +215,7 @@ pub mod test_utils;
 /// Type alias for default ethereum transaction pool
 pub type EthTransactionPool<Client, S> = Pool<
     TransactionValidationTaskExecutor<EthTransactionValidator<Client, EthPooledTransaction>>,
-    CoinbaseTipOrdering<EthPooledTransaction>,
+    BuiltinOrdering<EthPooledTransaction>,
     S,
 >;
 
@@ -237,27 +249,53 @@ where
         self.inner().config()
     }
 
+    /// Adds an [`AdmissionPolicy`] that every new transaction must pass before it is validated.
+    ///
+    /// This is checked in addition to the policies configured in [`PoolConfig::admission`].
+    pub fn add_admission_policy(&self, policy: impl AdmissionPolicy<V::Transaction> + 'static) {
+        self.pool.add_admission_policy(policy)
+    }
+
     /// Returns future that validates all transactions in the given iterator.
     ///
     /// This returns the validated transactions in the iterator's order.
     async fn validate_all(
         &self,
         origin: TransactionOrigin,
+        peer: Option<PeerId>,
         transactions: impl IntoIterator<Item = V::Transaction>,
     ) -> Vec<(TxHash, TransactionValidationOutcome<V::Transaction>)> {
-        futures_util::future::join_all(transactions.into_iter().map(|tx| self.validate(origin, tx)))
-            .await
+        futures_util::future::join_all(
+            transactions.into_iter().map(|tx| self.validate(origin, peer, tx)),
+        )
+        .await
     }
 
     /// Validates the given transaction
+    ///
+    /// The quotas the transaction consumed in the admission policies are released if it turns out
+    /// to be invalid.
     async fn validate(
         &self,
         origin: TransactionOrigin,
+        peer: Option<PeerId>,
         transaction: V::Transaction,
     ) -> (TxHash, TransactionValidationOutcome<V::Transaction>) {
         let hash = *transaction.hash();
+        let sender = transaction.sender();
+
+        let context = match self.pool.check_admission(origin, peer, &transaction) {
+            Ok(context) => context,
+            Err(err) => {
+                trace!(target: "txpool", %hash, %err, "transaction rejected by admission policy");
+                return (hash, TransactionValidationOutcome::Invalid(transaction, err.into()))
+            }
+        };
 
         let outcome = self.pool.validator().validate_transaction(origin, transaction).await;
+        if !outcome.is_valid() {
+            self.pool.release_admission(origin, sender, &context);
+        }
 
         (hash, outcome)
     }
@@ -284,7 +322,10 @@ where
     S: BlobStore,
 {
     /// Returns a new [`Pool`] that uses the default [`TransactionValidationTaskExecutor`] when
-    /// validating [`EthPooledTransaction`]s and ords via [`CoinbaseTipOrdering`]
+    /// validating [`EthPooledTransaction`]s and orders them via the default [`BuiltinOrdering`],
+    /// which is [`CoinbaseTipOrdering`].
+    ///
+    /// See [`Pool::new`] for using one of the other [`BuiltinOrdering`]s.
     ///
     /// # Example
     ///
@@ -316,7 +357,7 @@ where
         blob_store: S,
         config: PoolConfig,
     ) -> Self {
-        Self::new(validator, CoinbaseTipOrdering::default(), blob_store, config)
+        Self::new(validator, BuiltinOrdering::default(), blob_store, config)
     }
 }
 
@@ -343,7 +384,7 @@ where
         origin: TransactionOrigin,
         transaction: Self::Transaction,
     ) -> PoolResult<TransactionEvents> {
-        let (_, tx) = self.validate(origin, transaction).await;
+        let (_, tx) = self.validate(origin, None, transaction).await;
         self.pool.add_transaction_and_subscribe(origin, tx)
     }
 
@@ -352,11 +393,21 @@ where
         origin: TransactionOrigin,
         transaction: Self::Transaction,
     ) -> PoolResult<TxHash> {
-        let (_, tx) = self.validate(origin, transaction).await;
+        let (_, tx) = self.validate(origin, None, transaction).await;
         let mut results = self.pool.add_transactions(origin, std::iter::once(tx));
         results.pop().expect("result length is the same as the input")
     }
 
+    async fn add_conditional_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+        conditional: TransactionConditional,
+    ) -> PoolResult<TxHash> {
+        let (_, tx) = self.validate(origin, None, transaction).await;
+        self.pool.add_conditional_transaction(origin, tx, conditional)
+    }
+
     async fn add_transactions(
         &self,
         origin: TransactionOrigin,
@@ -365,7 +416,21 @@ where
         if transactions.is_empty() {
             return Vec::new()
         }
-        let validated = self.validate_all(origin, transactions).await;
+        let validated = self.validate_all(origin, None, transactions).await;
+
+        self.pool.add_transactions(origin, validated.into_iter().map(|(_, tx)| tx))
+    }
+
+    async fn add_external_transactions_from_peer(
+        &self,
+        peer_id: PeerId,
+        transactions: Vec<Self::Transaction>,
+    ) -> Vec<PoolResult<TxHash>> {
+        if transactions.is_empty() {
+            return Vec::new()
+        }
+        let origin = TransactionOrigin::External;
+        let validated = self.validate_all(origin, Some(peer_id), transactions).await;
 
         self.pool.add_transactions(origin, validated.into_iter().map(|(_, tx)| tx))
     }
@@ -378,6 +443,14 @@ where
         self.pool.add_all_transactions_event_listener()
     }
 
+    fn transaction_journal_listener(&self) -> TransactionJournalEvents {
+        self.pool.add_transaction_journal_listener()
+    }
+
+    fn transaction_history(&self, tx_hash: TxHash) -> TransactionHistory {
+        self.pool.transaction_history(tx_hash)
+    }
+
     fn pending_transactions_listener_for(&self, kind: TransactionListenerKind) -> Receiver<TxHash> {
         self.pool.add_pending_listener(kind)
     }
@@ -535,6 +608,10 @@ where
         self.pool.get_transactions_by_origin(origin)
     }
 
+    fn get_conditional_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        self.pool.conditional_transactions()
+    }
+
     /// Returns all pending transactions filtered by [`TransactionOrigin`]
     fn get_pending_transactions_by_origin(
         &self,
@@ -548,28 +625,53 @@ where
     }
 
     fn get_blob(&self, tx_hash: TxHash) -> Result<Option<BlobTransactionSidecar>, BlobStoreError> {
-        self.pool.blob_store().get(tx_hash)
+        let kzg_settings = self.pool.kzg_settings();
+        self.pool
+            .blob_store()
+            .get(tx_hash)?
+            .map(|sidecar| into_eip4844_sidecar(sidecar, kzg_settings.get()))
+            .transpose()
     }
 
     fn get_all_blobs(
         &self,
         tx_hashes: Vec<TxHash>,
     ) -> Result<Vec<(TxHash, BlobTransactionSidecar)>, BlobStoreError> {
-        self.pool.blob_store().get_all(tx_hashes)
+        let kzg_settings = self.pool.kzg_settings();
+        self.pool
+            .blob_store()
+            .get_all(tx_hashes)?
+            .into_iter()
+            .map(|(tx, sidecar)| Ok((tx, into_eip4844_sidecar(sidecar, kzg_settings.get())?)))
+            .collect()
     }
 
     fn get_all_blobs_exact(
         &self,
         tx_hashes: Vec<TxHash>,
     ) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError> {
-        self.pool.blob_store().get_exact(tx_hashes)
+        let kzg_settings = self.pool.kzg_settings();
+        self.pool
+            .blob_store()
+            .get_exact(tx_hashes)?
+            .into_iter()
+            .map(|sidecar| into_eip4844_sidecar(sidecar, kzg_settings.get()))
+            .collect()
     }
 
     fn get_blobs_for_versioned_hashes(
         &self,
         versioned_hashes: &[B256],
     ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError> {
-        self.pool.blob_store().get_by_versioned_hashes(versioned_hashes)
+        let kzg_settings = self.pool.kzg_settings();
+        self.pool.blob_store().get_by_versioned_hashes(versioned_hashes, kzg_settings.get())
+    }
+
+    fn get_blobs_for_versioned_hashes_v2(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV2>>, BlobStoreError> {
+        self.pool.blob_store().get_by_versioned_hashes_v2(versioned_hashes)
     }
 }
 
@@ -605,6 +707,14 @@ where
     fn cleanup_blobs(&self) {
         self.pool.cleanup_blobs()
     }
+
+    fn delete_unclaimed_blobs(&self) {
+        self.pool.delete_unclaimed_blobs()
+    }
+
+    fn on_conditional_transactions_checked(&self, block_hash: BlockHash) {
+        self.pool.on_conditional_transactions_checked(block_hash)
+    }
 }
 
 impl<V, T: TransactionOrdering, S> Clone for Pool<V, T, S> {
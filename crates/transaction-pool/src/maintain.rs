@@ -2,33 +2,38 @@
 
 use crate::{
     blobstore::{BlobStoreCanonTracker, BlobStoreUpdates},
-    error::PoolError,
+    error::{PoolError, PoolErrorKind},
     metrics::MaintainPoolMetrics,
     traits::{CanonicalStateUpdate, TransactionPool, TransactionPoolExt},
-    BlockInfo, PoolTransaction,
+    BlockInfo, PoolTransaction, TransactionOrigin, ValidPoolTransaction,
 };
 use alloy_primitives::{Address, BlockHash, BlockNumber, Sealable};
+use alloy_rlp::{RlpDecodable, RlpEncodable};
 use futures_util::{
     future::{BoxFuture, Fuse, FusedFuture},
     FutureExt, Stream, StreamExt,
 };
 use reth_chain_state::CanonStateNotification;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec};
-use reth_execution_types::ChangedAccount;
+use reth_db_models::{StoredBlobSidecar, StoredBlockBlobSidecars};
+use reth_execution_types::{ChangedAccount, ExecutionOutcome};
 use reth_fs_util::FsPathError;
 use reth_primitives::{
     BlockNumberOrTag, PooledTransactionsElementEcRecovered, SealedHeader, TransactionSigned,
     TransactionSignedEcRecovered,
 };
-use reth_storage_api::{errors::provider::ProviderError, BlockReaderIdExt, StateProviderFactory};
+use reth_storage_api::{
+    errors::provider::ProviderError, BlobSidecarsWriter, BlockReaderIdExt, StateProviderFactory,
+};
 use reth_tasks::TaskSpawner;
 use std::{
     borrow::Borrow,
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     hash::{Hash, Hasher},
     path::{Path, PathBuf},
+    time::Duration,
 };
-use tokio::sync::oneshot;
+use tokio::{sync::oneshot, time::MissedTickBehavior};
 use tracing::{debug, error, info, trace, warn};
 
 /// Additional settings for maintaining the transaction pool
@@ -65,6 +70,40 @@ impl LocalTransactionBackupConfig {
     }
 }
 
+/// Settings for the transaction pool snapshot task
+#[derive(Debug, Clone)]
+pub struct TransactionPoolSnapshotConfig {
+    /// Path to the snapshot file
+    pub path: Option<PathBuf>,
+    /// Interval at which the snapshot is written while the node is running
+    ///
+    /// Default: 60s
+    pub interval: Duration,
+    /// Maximum number of transactions that are revalidated at once when the snapshot is restored
+    ///
+    /// Default: 1000
+    pub restore_batch_size: usize,
+}
+
+impl TransactionPoolSnapshotConfig {
+    /// Creates a config that writes the pool snapshot to the given path
+    pub fn with_path(path: PathBuf) -> Self {
+        Self { path: Some(path), ..Default::default() }
+    }
+
+    /// Sets the interval at which the snapshot is written
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+}
+
+impl Default for TransactionPoolSnapshotConfig {
+    fn default() -> Self {
+        Self { path: None, interval: Duration::from_secs(60), restore_batch_size: 1000 }
+    }
+}
+
 /// Returns a spawnable future for maintaining the state of the transaction pool.
 pub fn maintain_transaction_pool_future<Client, P, St, Tasks>(
     client: Client,
@@ -133,6 +172,9 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
     // keeps track of the state of the pool wrt to blocks
     let mut maintained_state = MaintainedPoolState::InSync;
 
+    // the block the known accounts of conditional transactions were last checked at
+    let mut conditionals_checked_at = None;
+
     // the future that reloads accounts from state
     let mut reload_accounts_fut = Fuse::terminated();
 
@@ -353,6 +395,16 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     mined_transactions: new_blocks.transaction_hashes().collect(),
                 };
                 pool.on_canonical_state_change(update);
+                remove_invalid_conditional_transactions(
+                    &client,
+                    &pool,
+                    new_tip.hash(),
+                    old_blocks.tip().hash(),
+                    changed_storage_accounts(old_state)
+                        .chain(changed_storage_accounts(new_state))
+                        .collect(),
+                    &mut conditionals_checked_at,
+                );
 
                 // all transactions that were mined in the old chain but not in the new chain need
                 // to be re-injected
@@ -435,6 +487,14 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     mined_transactions,
                 };
                 pool.on_canonical_state_change(update);
+                remove_invalid_conditional_transactions(
+                    &client,
+                    &pool,
+                    tip.hash(),
+                    first_block.parent_hash,
+                    changed_storage_accounts(state).collect(),
+                    &mut conditionals_checked_at,
+                );
 
                 // keep track of mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&blocks);
@@ -545,6 +605,85 @@ where
     Ok(res)
 }
 
+/// Removes all conditional transactions whose known accounts no longer match the state at the
+/// given block, together with their descendants.
+///
+/// If the known accounts were last checked at `parent`, only the known accounts in `changed` are
+/// checked again. Storage roots are computed at most once per account.
+fn remove_invalid_conditional_transactions<Client, P>(
+    client: &Client,
+    pool: &P,
+    at: BlockHash,
+    parent: BlockHash,
+    changed: HashSet<Address>,
+    checked_at: &mut Option<BlockHash>,
+) where
+    Client: StateProviderFactory,
+    P: TransactionPoolExt,
+{
+    let changed = (*checked_at == Some(parent)).then_some(changed);
+    let conditional_txs = pool
+        .get_conditional_transactions()
+        .into_iter()
+        .filter(|tx| {
+            tx.conditional.as_ref().is_some_and(|conditional| match &changed {
+                Some(changed) => conditional.has_known_account_in(changed),
+                None => !conditional.known_accounts.is_empty(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if !conditional_txs.is_empty() {
+        let state = match client.history_by_block_hash(at) {
+            Ok(state) => state,
+            Err(err) => {
+                debug!(target: "txpool", %err, "failed to load state for conditional transactions");
+                *checked_at = None;
+                return
+            }
+        };
+
+        let mut storage_roots = HashMap::default();
+        let mut invalid = Vec::new();
+        for tx in conditional_txs {
+            let Some(conditional) = tx.conditional.as_ref() else { continue };
+            match conditional.matches_changed_known_accounts(
+                &state,
+                changed.as_ref(),
+                &mut storage_roots,
+            ) {
+                Ok(true) => {}
+                Ok(false) => invalid.push(*tx.hash()),
+                Err(err) => {
+                    debug!(target: "txpool", %err, "failed to check conditional transactions");
+                    *checked_at = None;
+                    return
+                }
+            }
+        }
+
+        if !invalid.is_empty() {
+            debug!(target: "txpool", num_txs = invalid.len(), "removing conditional transactions with outdated known accounts");
+            // descendants can't be executed without the removed transactions
+            let removed = pool.remove_transactions_and_descendants(invalid);
+            pool.delete_blobs(
+                removed.into_iter().filter(|tx| tx.is_eip4844()).map(|tx| *tx.hash()).collect(),
+            );
+        }
+    }
+
+    *checked_at = Some(at);
+    pool.on_conditional_transactions_checked(at);
+}
+
+/// Returns the accounts whose storage changed in the given execution outcome.
+fn changed_storage_accounts(state: &ExecutionOutcome) -> impl Iterator<Item = Address> + '_ {
+    state
+        .bundle_accounts_iter()
+        .filter(|(_, account)| !account.storage.is_empty() || account.was_destroyed())
+        .map(|(address, _)| address)
+}
+
 /// Loads transactions from a file, decodes them from the RLP format, and inserts them
 /// into the transaction pool on node boot up.
 /// The file is removed after the transactions have been successfully processed.
@@ -633,6 +772,240 @@ pub enum TransactionsBackupError {
     Pool(#[from] PoolError),
 }
 
+/// Version of the transaction pool snapshot format.
+const POOL_SNAPSHOT_VERSION: u8 = 1;
+
+/// A snapshot of all transactions in the pool.
+///
+/// Blob sidecars are not part of the snapshot, they are referenced by transaction hash and read
+/// from the blob store when the snapshot is restored.
+#[derive(Debug, Default, RlpEncodable, RlpDecodable)]
+struct PoolSnapshot {
+    version: u8,
+    transactions: Vec<PoolSnapshotEntry>,
+}
+
+/// A single transaction of a [`PoolSnapshot`].
+#[derive(Debug, RlpEncodable, RlpDecodable)]
+struct PoolSnapshotEntry {
+    origin: u8,
+    transaction: TransactionSigned,
+}
+
+impl PoolSnapshotEntry {
+    fn new<T: PoolTransaction<Consensus: Into<TransactionSignedEcRecovered>>>(
+        tx: &ValidPoolTransaction<T>,
+    ) -> Self {
+        let origin = match tx.origin {
+            TransactionOrigin::Local => 0,
+            TransactionOrigin::External => 1,
+            TransactionOrigin::Private => 2,
+        };
+        let recovered: TransactionSignedEcRecovered =
+            tx.transaction.clone().into_consensus().into();
+        Self { origin, transaction: recovered.into_signed() }
+    }
+
+    const fn origin(&self) -> Option<TransactionOrigin> {
+        match self.origin {
+            0 => Some(TransactionOrigin::Local),
+            1 => Some(TransactionOrigin::External),
+            2 => Some(TransactionOrigin::Private),
+            _ => None,
+        }
+    }
+}
+
+/// Writes all transactions of the pool to the snapshot file.
+///
+/// The snapshot is written to a temporary file first and then moved into place, so that a crash
+/// while writing never leaves a partially written snapshot behind.
+fn save_transaction_pool_snapshot<P>(pool: &P, file_path: &Path) -> Result<usize, FsPathError>
+where
+    P: TransactionPool,
+{
+    let mut transactions = pool.get_local_transactions();
+    transactions.extend(pool.get_private_transactions());
+    transactions.extend(pool.get_external_transactions());
+    // restore in nonce order so that sender's transactions don't end up queued
+    transactions.sort_unstable_by_key(|tx| (tx.sender(), tx.nonce()));
+
+    let snapshot = PoolSnapshot {
+        version: POOL_SNAPSHOT_VERSION,
+        transactions: transactions.iter().map(|tx| PoolSnapshotEntry::new(tx)).collect(),
+    };
+    let num_txs = snapshot.transactions.len();
+
+    let mut buf = Vec::new();
+    alloy_rlp::Encodable::encode(&snapshot, &mut buf);
+
+    if let Some(parent) = file_path.parent() {
+        reth_fs_util::create_dir_all(parent)?;
+    }
+    let tmp_path = file_path.with_extension("tmp");
+    reth_fs_util::write(&tmp_path, buf)?;
+    reth_fs_util::rename(&tmp_path, file_path)?;
+
+    Ok(num_txs)
+}
+
+/// Writes the transaction pool snapshot on a blocking task, since the whole pool is encoded and
+/// written to disk.
+async fn write_transaction_pool_snapshot<P>(pool: P, file_path: PathBuf)
+where
+    P: TransactionPool + 'static,
+{
+    let path = file_path.clone();
+    match tokio::task::spawn_blocking(move || save_transaction_pool_snapshot(&pool, &path)).await {
+        Ok(Ok(num_txs)) => {
+            debug!(target: "txpool", snapshot_file=?file_path, num_txs, "Wrote transaction pool snapshot")
+        }
+        Ok(Err(err)) => {
+            warn!(target: "txpool", %err, snapshot_file=?file_path, "Failed to write transaction pool snapshot")
+        }
+        Err(err) => {
+            warn!(target: "txpool", %err, snapshot_file=?file_path, "Transaction pool snapshot task failed")
+        }
+    }
+}
+
+/// Loads the transaction pool snapshot and reinserts its transactions into the pool.
+///
+/// Transactions are revalidated in batches of `batch_size` against the current state. Blob
+/// transactions are reassembled with their sidecars from the blob store, blobs of transactions
+/// that are rejected are deleted from the blob store.
+async fn restore_transaction_pool_snapshot<P>(
+    pool: P,
+    file_path: &Path,
+    batch_size: usize,
+) -> Result<(), TransactionsBackupError>
+where
+    P: TransactionPoolExt,
+{
+    if !file_path.exists() {
+        return Ok(())
+    }
+
+    debug!(target: "txpool", snapshot_file =?file_path, "Restoring transaction pool snapshot");
+    let data = reth_fs_util::read(file_path)?;
+    let snapshot: PoolSnapshot = alloy_rlp::Decodable::decode(&mut data.as_slice())?;
+    if snapshot.version != POOL_SNAPSHOT_VERSION {
+        return Err(alloy_rlp::Error::Custom("unsupported transaction pool snapshot version").into())
+    }
+
+    let mut restored = 0;
+    let mut rejected_blobs = Vec::new();
+    for chunk in snapshot.transactions.chunks(batch_size.max(1)) {
+        let mut batches: Vec<(TransactionOrigin, Vec<P::Transaction>)> = Vec::new();
+        for entry in chunk {
+            let Some(origin) = entry.origin() else { continue };
+            let Some(recovered) = entry.transaction.clone().try_ecrecovered() else { continue };
+
+            let transaction = if recovered.is_eip4844() {
+                let hash = TransactionSigned::hash(&recovered);
+                let Ok(Some(sidecar)) = pool.get_blob(hash) else {
+                    trace!(target: "txpool", %hash, "missing blob sidecar for snapshot transaction");
+                    continue
+                };
+                let Ok(pooled) = PooledTransactionsElementEcRecovered::try_from_blob_transaction(
+                    recovered, sidecar,
+                ) else {
+                    continue
+                };
+                <P::Transaction as PoolTransaction>::from_pooled(pooled.into())
+            } else {
+                let Ok(tx) =
+                    <P::Transaction as PoolTransaction>::try_from_consensus(recovered.into())
+                else {
+                    continue
+                };
+                tx
+            };
+
+            match batches.last_mut() {
+                Some((batch_origin, batch)) if *batch_origin == origin => batch.push(transaction),
+                _ => batches.push((origin, vec![transaction])),
+            }
+        }
+
+        for (origin, batch) in batches {
+            let blob_hashes = batch
+                .iter()
+                .filter(|tx| tx.is_eip4844())
+                .map(|tx| *tx.hash())
+                .collect::<HashSet<_>>();
+            for res in pool.add_transactions(origin, batch).await {
+                match res {
+                    Ok(_) => restored += 1,
+                    Err(err)
+                        if blob_hashes.contains(&err.hash) &&
+                            !matches!(err.kind, PoolErrorKind::AlreadyImported) =>
+                    {
+                        rejected_blobs.push(err.hash)
+                    }
+                    Err(_) => {}
+                }
+            }
+        }
+    }
+
+    if !rejected_blobs.is_empty() {
+        pool.delete_blobs(rejected_blobs);
+    }
+
+    info!(target: "txpool", snapshot_file =?file_path, total=%snapshot.transactions.len(), restored, "Restored transaction pool snapshot");
+    reth_fs_util::remove_file(file_path)?;
+    Ok(())
+}
+
+/// Task which persists a snapshot of the whole transaction pool.
+///
+/// On startup the snapshot is restored and every transaction is revalidated before it is added
+/// back to the pool. Blobs that were kept for the snapshot, but whose transactions were not
+/// restored, are deleted afterwards. While running, the snapshot is written at the configured
+/// interval and once more when the node shuts down.
+pub async fn snapshot_transaction_pool_task<P>(
+    shutdown: reth_tasks::shutdown::GracefulShutdown,
+    pool: P,
+    config: TransactionPoolSnapshotConfig,
+) where
+    P: TransactionPoolExt + Clone + 'static,
+{
+    let Some(snapshot_path) = config.path else {
+        // nothing to do
+        return
+    };
+
+    if let Err(err) =
+        restore_transaction_pool_snapshot(pool.clone(), &snapshot_path, config.restore_batch_size)
+            .await
+    {
+        error!(target: "txpool", "{}", err)
+    }
+    pool.delete_unclaimed_blobs();
+
+    let mut interval = tokio::time::interval_at(
+        tokio::time::Instant::now() + config.interval,
+        config.interval.max(Duration::from_secs(1)),
+    );
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let mut shutdown = std::pin::pin!(shutdown);
+    let graceful_guard = loop {
+        tokio::select! {
+            guard = &mut shutdown => break guard,
+            _ = interval.tick() => {
+                write_transaction_pool_snapshot(pool.clone(), snapshot_path.clone()).await
+            }
+        }
+    };
+
+    // write the final snapshot to disk
+    write_transaction_pool_snapshot(pool, snapshot_path).await;
+
+    drop(graceful_guard)
+}
+
 /// Task which manages saving local transactions to the persistent file in case of shutdown.
 /// Reloads the transactions from the file on the boot up and inserts them into the pool.
 pub async fn backup_local_transactions_task<P>(
@@ -659,6 +1032,70 @@ pub async fn backup_local_transactions_task<P>(
     drop(graceful_guard)
 }
 
+/// Task which retains the blob sidecars of canonical blocks.
+///
+/// The sidecars of blob transactions included in a block are only kept in the blob store until
+/// the block is finalized. This copies them to the given [`BlobSidecarsWriter`] as soon as the
+/// block is committed. On reorgs, the sidecars of the new chain replace the retained ones.
+///
+/// Blob transactions whose sidecars are no longer in the blob store are recorded as missing, so
+/// that incomplete blocks can be told apart.
+pub async fn retain_blob_sidecars_task<P, W, St>(pool: P, writer: W, mut events: St)
+where
+    P: TransactionPool,
+    W: BlobSidecarsWriter,
+    St: Stream<Item = CanonStateNotification> + Send + Unpin,
+{
+    while let Some(event) = events.next().await {
+        let chain = event.committed();
+        let mut blocks = Vec::with_capacity(chain.len());
+        for (number, block) in chain.blocks() {
+            let blob_txs = block
+                .body
+                .transactions()
+                .enumerate()
+                .filter(|(_, tx)| tx.is_eip4844())
+                .map(|(idx, tx)| (tx.hash(), idx as u64))
+                .collect::<HashMap<_, _>>();
+
+            let mut sidecars = if blob_txs.is_empty() {
+                Vec::new()
+            } else {
+                match pool.get_all_blobs(blob_txs.keys().copied().collect()) {
+                    Ok(sidecars) => sidecars
+                        .into_iter()
+                        .map(|(tx_hash, sidecar)| StoredBlobSidecar {
+                            tx_index: blob_txs[&tx_hash],
+                            sidecar,
+                        })
+                        .collect(),
+                    Err(err) => {
+                        warn!(target: "txpool", %err, block=number, "Failed to get blob sidecars of block");
+                        Vec::new()
+                    }
+                }
+            };
+            sidecars.sort_unstable_by_key(|sidecar| sidecar.tx_index);
+
+            let mut missing = blob_txs
+                .into_values()
+                .filter(|tx_index| {
+                    sidecars.binary_search_by_key(tx_index, |sidecar| sidecar.tx_index).is_err()
+                })
+                .collect::<Vec<_>>();
+            if !missing.is_empty() {
+                debug!(target: "txpool", block=number, missing=missing.len(), "Missing blob sidecars of block");
+                missing.sort_unstable();
+            }
+            blocks.push((*number, StoredBlockBlobSidecars { sidecars, missing }));
+        }
+
+        if let Err(err) = writer.write_blob_sidecars(blocks) {
+            error!(target: "txpool", %err, "Failed to retain blob sidecars");
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -733,6 +1170,59 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_transaction_pool_snapshot_roundtrip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let snapshot_path = temp_dir.path().join("txpool-snapshot.rlp");
+        let tx_bytes = hex!("02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507");
+        let tx = PooledTransactionsElement::decode_2718(&mut &tx_bytes[..]).unwrap();
+        let transaction: EthPooledTransaction = tx.try_into_ecrecovered().unwrap().into();
+        let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
+
+        let new_pool = || {
+            let provider = MockEthProvider::default();
+            provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
+            let blob_store = InMemoryBlobStore::default();
+            let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+                .build(provider, blob_store.clone());
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default())
+        };
+        let config = TransactionPoolSnapshotConfig::with_path(snapshot_path.clone());
+
+        // write the snapshot on shutdown
+        let txpool = new_pool();
+        txpool.add_transaction(TransactionOrigin::External, transaction.clone()).await.unwrap();
+
+        let manager = TaskManager::new(tokio::runtime::Handle::current());
+        let task_config = config.clone();
+        let task_pool = txpool.clone();
+        manager.executor().spawn_critical_with_graceful_shutdown_signal("test task", |shutdown| {
+            snapshot_transaction_pool_task(shutdown, task_pool, task_config)
+        });
+        manager.graceful_shutdown();
+
+        let data = fs::read(&snapshot_path).unwrap();
+        let snapshot: PoolSnapshot = alloy_rlp::Decodable::decode(&mut data.as_slice()).unwrap();
+        assert_eq!(snapshot.version, POOL_SNAPSHOT_VERSION);
+        assert_eq!(snapshot.transactions.len(), 1);
+
+        // restore the snapshot into an empty pool
+        let txpool = new_pool();
+        restore_transaction_pool_snapshot(
+            txpool.clone(),
+            &snapshot_path,
+            config.restore_batch_size,
+        )
+        .await
+        .unwrap();
+
+        let restored = txpool.get(transaction.hash()).expect("transaction restored");
+        assert_eq!(restored.origin, TransactionOrigin::External);
+        assert!(!snapshot_path.exists());
+
+        temp_dir.close().unwrap();
+    }
+
     #[test]
     fn test_update_with_higher_finalized_block() {
         let mut tracker = FinalizedBlockTracker::new(Some(10));
@@ -1,5 +1,6 @@
 //! Transaction pool metrics.
 
+use crate::admission::AdmissionError;
 use reth_metrics::{
     metrics::{Counter, Gauge},
     Metrics,
@@ -67,6 +68,40 @@ pub struct BlobStoreMetrics {
     pub(crate) blobstore_entries: Gauge,
 }
 
+/// Transaction pool admission policy metrics
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool.admission")]
+pub struct AdmissionMetrics {
+    /// Number of transactions rejected because of their sender
+    pub(crate) rejected_sender: Counter,
+    /// Number of transactions rejected because of their recipient
+    pub(crate) rejected_recipient: Counter,
+    /// Number of transactions rejected by a sender rate limit
+    pub(crate) rejected_sender_rate_limit: Counter,
+    /// Number of transactions rejected by an origin rate limit
+    pub(crate) rejected_origin_rate_limit: Counter,
+    /// Number of transactions rejected by the sender calldata limit
+    pub(crate) rejected_calldata_limit: Counter,
+    /// Number of transactions rejected by custom policies
+    pub(crate) rejected_other: Counter,
+}
+
+impl AdmissionMetrics {
+    /// Increments the counter of the rejection reason.
+    pub(crate) fn record(&self, err: &AdmissionError) {
+        match err {
+            AdmissionError::SenderNotAllowed(_) => self.rejected_sender.increment(1),
+            AdmissionError::RecipientNotAllowed(_) => self.rejected_recipient.increment(1),
+            AdmissionError::SenderRateLimited(_) => self.rejected_sender_rate_limit.increment(1),
+            AdmissionError::OriginRateLimited(_) => self.rejected_origin_rate_limit.increment(1),
+            AdmissionError::CalldataLimitExceeded { .. } => {
+                self.rejected_calldata_limit.increment(1)
+            }
+            AdmissionError::Other(_) => self.rejected_other.increment(1),
+        }
+    }
+}
+
 /// Transaction pool maintenance metrics
 #[derive(Metrics)]
 #[metrics(scope = "transaction_pool")]
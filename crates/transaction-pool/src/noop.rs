@@ -4,8 +4,10 @@
 //! to be generic over it.
 
 use crate::{
-    blobstore::BlobStoreError,
+    blobstore::{BlobAndProofV2, BlobStoreError},
+    conditional::TransactionConditional,
     error::PoolError,
+    pool::{TransactionHistory, TransactionJournalEvents, TransactionStatus},
     traits::{
         BestTransactionsAttributes, GetPooledTransactionLimit, NewBlobSidecar,
         TransactionListenerKind,
@@ -66,6 +68,16 @@ impl TransactionPool for NoopTransactionPool {
         Err(PoolError::other(hash, Box::new(NoopInsertError::new(transaction))))
     }
 
+    async fn add_conditional_transaction(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: Self::Transaction,
+        _conditional: TransactionConditional,
+    ) -> PoolResult<TxHash> {
+        let hash = *transaction.hash();
+        Err(PoolError::other(hash, Box::new(NoopInsertError::new(transaction))))
+    }
+
     async fn add_transactions(
         &self,
         _origin: TransactionOrigin,
@@ -88,6 +100,14 @@ impl TransactionPool for NoopTransactionPool {
         AllTransactionsEvents::new(mpsc::channel(1).1)
     }
 
+    fn transaction_journal_listener(&self) -> TransactionJournalEvents {
+        TransactionJournalEvents::new(mpsc::channel(1).1)
+    }
+
+    fn transaction_history(&self, tx_hash: TxHash) -> TransactionHistory {
+        TransactionHistory { hash: tx_hash, status: TransactionStatus::Unknown, events: Vec::new() }
+    }
+
     fn pending_transactions_listener_for(
         &self,
         _kind: TransactionListenerKind,
@@ -250,6 +270,10 @@ impl TransactionPool for NoopTransactionPool {
         vec![]
     }
 
+    fn get_conditional_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        vec![]
+    }
+
     fn get_pending_transactions_by_origin(
         &self,
         _origin: TransactionOrigin,
@@ -288,6 +312,13 @@ impl TransactionPool for NoopTransactionPool {
     ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError> {
         Ok(vec![None; versioned_hashes.len()])
     }
+
+    fn get_blobs_for_versioned_hashes_v2(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV2>>, BlobStoreError> {
+        Ok(vec![None; versioned_hashes.len()])
+    }
 }
 
 /// A [`TransactionValidator`] that does nothing.
@@ -1,6 +1,6 @@
-use crate::traits::PoolTransaction;
+use crate::{traits::PoolTransaction, ValidPoolTransaction};
 use alloy_primitives::U256;
-use std::{fmt, marker::PhantomData};
+use std::{fmt, marker::PhantomData, str::FromStr, time::Instant};
 
 /// Priority of the transaction that can be missing.
 ///
@@ -39,6 +39,19 @@ pub trait TransactionOrdering: Send + Sync + 'static {
         transaction: &Self::Transaction,
         base_fee: u64,
     ) -> Priority<Self::PriorityValue>;
+
+    /// Returns the priority score for the given validated transaction.
+    ///
+    /// This is what the pool uses to order its transactions. By default this is the
+    /// [`TransactionOrdering::priority`] of the transaction, orderings that depend on pool
+    /// metadata, like the time the transaction was added, can override it.
+    fn valid_transaction_priority(
+        &self,
+        transaction: &ValidPoolTransaction<Self::Transaction>,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        self.priority(&transaction.transaction, base_fee)
+    }
 }
 
 /// Default ordering for the pool.
@@ -79,3 +92,286 @@ impl<T> Clone for CoinbaseTipOrdering<T> {
         Self::default()
     }
 }
+
+/// Ordering by effective tip per gas and calldata byte.
+///
+/// The coinbase tip is divided by the size of the transaction's calldata, which favors
+/// transactions that are cheap to post to a data availability layer. Transactions without calldata
+/// are treated as having a single byte of calldata.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct CalldataTipOrdering<T>(PhantomData<T>);
+
+impl<T> TransactionOrdering for CalldataTipOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = U256;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        transaction
+            .effective_tip_per_gas(base_fee)
+            .map(|tip| {
+                // scale the tip so that small tips are not truncated to zero
+                (U256::from(tip) << 64) / U256::from(transaction.input().len().max(1))
+            })
+            .into()
+    }
+}
+
+impl<T> Default for CalldataTipOrdering<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<T> Clone for CalldataTipOrdering<T> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// First-come-first-served ordering.
+///
+/// Transactions are ordered by the time they were added to the pool, regardless of their tip, the
+/// earlier a transaction was added the higher its priority. Transactions that can't pay the base
+/// fee still have no priority.
+#[derive(Debug, Clone)]
+pub struct ArrivalTimeOrdering<T> {
+    /// The reference point for arrival times.
+    epoch: Instant,
+    _marker: PhantomData<T>,
+}
+
+impl<T> TransactionOrdering for ArrivalTimeOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = U256;
+    type Transaction = T;
+
+    /// Returns the priority of the transaction as if it arrived now, since the arrival time is
+    /// only known for validated transactions.
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        self.arrival_priority(transaction, Instant::now(), base_fee)
+    }
+
+    fn valid_transaction_priority(
+        &self,
+        transaction: &ValidPoolTransaction<Self::Transaction>,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        self.arrival_priority(&transaction.transaction, transaction.timestamp, base_fee)
+    }
+}
+
+impl<T: PoolTransaction> ArrivalTimeOrdering<T> {
+    /// Returns the priority of a transaction that arrived at the given time.
+    fn arrival_priority(&self, transaction: &T, arrival: Instant, base_fee: u64) -> Priority<U256> {
+        transaction
+            .effective_tip_per_gas(base_fee)
+            .map(|_| {
+                let arrival = arrival.saturating_duration_since(self.epoch);
+                U256::from(u128::MAX - arrival.as_nanos())
+            })
+            .into()
+    }
+}
+
+impl<T> Default for ArrivalTimeOrdering<T> {
+    fn default() -> Self {
+        Self { epoch: Instant::now(), _marker: Default::default() }
+    }
+}
+
+/// The built-in [`TransactionOrdering`]s that can be selected at runtime.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransactionOrderingKind {
+    /// [`CoinbaseTipOrdering`]
+    #[default]
+    CoinbaseTip,
+    /// [`CalldataTipOrdering`]
+    CalldataTip,
+    /// [`ArrivalTimeOrdering`]
+    ArrivalTime,
+}
+
+impl TransactionOrderingKind {
+    /// Returns the name of the ordering.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::CoinbaseTip => "coinbase-tip",
+            Self::CalldataTip => "calldata-tip",
+            Self::ArrivalTime => "arrival-time",
+        }
+    }
+}
+
+impl fmt::Display for TransactionOrderingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TransactionOrderingKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "coinbase-tip" => Ok(Self::CoinbaseTip),
+            "calldata-tip" => Ok(Self::CalldataTip),
+            "arrival-time" => Ok(Self::ArrivalTime),
+            _ => Err(format!(
+                "unknown transaction ordering: {s}, expected one of coinbase-tip, calldata-tip, arrival-time"
+            )),
+        }
+    }
+}
+
+/// A [`TransactionOrdering`] that is one of the built-in orderings, selected at runtime.
+///
+/// Defaults to [`CoinbaseTipOrdering`].
+#[derive(Debug, Clone)]
+pub enum BuiltinOrdering<T> {
+    /// Order by coinbase tip.
+    CoinbaseTip(CoinbaseTipOrdering<T>),
+    /// Order by coinbase tip per calldata byte.
+    CalldataTip(CalldataTipOrdering<T>),
+    /// Order by arrival time.
+    ArrivalTime(ArrivalTimeOrdering<T>),
+}
+
+impl<T> BuiltinOrdering<T> {
+    /// Creates the ordering of the given kind.
+    pub fn new(kind: TransactionOrderingKind) -> Self {
+        match kind {
+            TransactionOrderingKind::CoinbaseTip => Self::CoinbaseTip(Default::default()),
+            TransactionOrderingKind::CalldataTip => Self::CalldataTip(Default::default()),
+            TransactionOrderingKind::ArrivalTime => Self::ArrivalTime(Default::default()),
+        }
+    }
+
+    /// Returns the kind of the ordering.
+    pub const fn kind(&self) -> TransactionOrderingKind {
+        match self {
+            Self::CoinbaseTip(_) => TransactionOrderingKind::CoinbaseTip,
+            Self::CalldataTip(_) => TransactionOrderingKind::CalldataTip,
+            Self::ArrivalTime(_) => TransactionOrderingKind::ArrivalTime,
+        }
+    }
+}
+
+impl<T> TransactionOrdering for BuiltinOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = U256;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        match self {
+            Self::CoinbaseTip(ordering) => ordering.priority(transaction, base_fee),
+            Self::CalldataTip(ordering) => ordering.priority(transaction, base_fee),
+            Self::ArrivalTime(ordering) => ordering.priority(transaction, base_fee),
+        }
+    }
+
+    fn valid_transaction_priority(
+        &self,
+        transaction: &ValidPoolTransaction<Self::Transaction>,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        match self {
+            Self::CoinbaseTip(ordering) => {
+                ordering.valid_transaction_priority(transaction, base_fee)
+            }
+            Self::CalldataTip(ordering) => {
+                ordering.valid_transaction_priority(transaction, base_fee)
+            }
+            Self::ArrivalTime(ordering) => {
+                ordering.valid_transaction_priority(transaction, base_fee)
+            }
+        }
+    }
+}
+
+impl<T> Default for BuiltinOrdering<T> {
+    fn default() -> Self {
+        Self::new(TransactionOrderingKind::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{MockTransaction, MockTransactionFactory};
+    use alloy_primitives::Bytes;
+    use std::time::Duration;
+
+    #[test]
+    fn parse_ordering_kind() {
+        for kind in [
+            TransactionOrderingKind::CoinbaseTip,
+            TransactionOrderingKind::CalldataTip,
+            TransactionOrderingKind::ArrivalTime,
+        ] {
+            assert_eq!(kind.to_string().parse::<TransactionOrderingKind>(), Ok(kind));
+            assert_eq!(BuiltinOrdering::<MockTransaction>::new(kind).kind(), kind);
+        }
+        assert!("tip".parse::<TransactionOrderingKind>().is_err());
+    }
+
+    #[test]
+    fn calldata_tip_priority() {
+        let ordering = CalldataTipOrdering::default();
+        let small = MockTransaction::eip1559()
+            .with_max_fee(10)
+            .with_priority_fee(5)
+            .with_input(Bytes::from(vec![1; 10]));
+        let large = small.clone().with_input(Bytes::from(vec![1; 100]));
+        let empty = small.clone().with_input(Bytes::new());
+
+        assert!(ordering.priority(&small, 0) > ordering.priority(&large, 0));
+        assert!(ordering.priority(&empty, 0) > ordering.priority(&small, 0));
+        assert_eq!(ordering.priority(&small, 11), Priority::None);
+    }
+
+    #[test]
+    fn arrival_time_priority() {
+        let ordering = ArrivalTimeOrdering {
+            epoch: Instant::now() - Duration::from_secs(10),
+            _marker: PhantomData,
+        };
+        let mut f = MockTransactionFactory::default();
+        let mut early = f.validated(MockTransaction::eip1559().with_max_fee(10));
+        let mut late =
+            f.validated(MockTransaction::eip1559().with_max_fee(10).with_priority_fee(5));
+        early.timestamp = ordering.epoch + Duration::from_secs(1);
+        late.timestamp = ordering.epoch + Duration::from_secs(2);
+
+        assert!(
+            ordering.valid_transaction_priority(&early, 0) >
+                ordering.valid_transaction_priority(&late, 0)
+        );
+        assert_eq!(ordering.valid_transaction_priority(&early, 11), Priority::None);
+
+        // transactions that are not in the pool yet rank behind the ones that are
+        assert!(
+            ordering.valid_transaction_priority(&late, 0) > ordering.priority(&late.transaction, 0)
+        );
+        assert_eq!(ordering.priority(&late.transaction, 11), Priority::None);
+    }
+}
@@ -265,9 +265,10 @@ mod tests {
     use crate::{
         pool::pending::PendingPool,
         test_utils::{MockOrdering, MockTransaction, MockTransactionFactory},
-        Priority,
+        ArrivalTimeOrdering, CalldataTipOrdering, Priority,
     };
-    use alloy_primitives::U256;
+    use alloy_primitives::{Bytes, U256};
+    use std::time::{Duration, Instant};
 
     #[test]
     fn test_best_iter() {
@@ -623,4 +624,44 @@ mod tests {
             assert_eq!(tx.nonce() % 2, 0);
         }
     }
+
+    #[test]
+    fn test_best_calldata_tip_ordering() {
+        let mut pool = PendingPool::new(CalldataTipOrdering::default());
+        let mut f = MockTransactionFactory::default();
+
+        // (priority fee, calldata size)
+        let txs = [(10, 100), (5, 10), (20, 1000)].map(|(priority_fee, input_len)| {
+            MockTransaction::eip1559()
+                .with_max_fee(100)
+                .with_priority_fee(priority_fee)
+                .with_input(Bytes::from(vec![1; input_len]))
+        });
+        for tx in &txs {
+            pool.add_transaction(Arc::new(f.validated(tx.clone())), 0);
+        }
+
+        let best = pool.best().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, vec![*txs[1].get_hash(), *txs[0].get_hash(), *txs[2].get_hash()]);
+    }
+
+    #[test]
+    fn test_best_arrival_time_ordering() {
+        let mut pool = PendingPool::new(ArrivalTimeOrdering::default());
+        let mut f = MockTransactionFactory::default();
+
+        // higher tips arrive later
+        let now = Instant::now();
+        let mut hashes = Vec::new();
+        for i in 0..5u64 {
+            let tx = MockTransaction::eip1559().with_max_fee(100).with_priority_fee(i as u128 + 1);
+            hashes.push(*tx.get_hash());
+            let mut valid_tx = f.validated(tx);
+            valid_tx.timestamp = now + Duration::from_secs(i);
+            pool.add_transaction(Arc::new(valid_tx), 0);
+        }
+
+        let best = pool.best().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, hashes);
+    }
 }
@@ -212,6 +212,30 @@ impl<T: PoolTransaction> BlobTransactions<T> {
         removed
     }
 
+    /// Removes all transactions whose fee caps are more than `max_fee_jumps` fee jumps below the
+    /// given [`PendingFees`], see [`blob_tx_fee_jumps`].
+    ///
+    /// Note: this does not remove the descendants of the removed transactions.
+    pub(crate) fn truncate_by_fee_jumps(
+        &mut self,
+        pending_fees: &PendingFees,
+        max_fee_jumps: u64,
+    ) -> Vec<Arc<ValidPoolTransaction<T>>> {
+        let to_remove = self
+            .by_id
+            .iter()
+            .filter(|(_, tx)| blob_tx_fee_jumps(&tx.transaction, pending_fees) > max_fee_jumps)
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+
+        let mut removed = Vec::with_capacity(to_remove.len());
+        for id in to_remove {
+            removed.push(self.remove_transaction(&id).expect("transaction exists"));
+        }
+
+        removed
+    }
+
     /// Removes transactions until the pool satisfies its [`SubPoolLimit`].
     ///
     /// This is done by removing transactions according to their ordering in the pool, defined by
@@ -404,6 +428,20 @@ pub fn blob_tx_priority(
     delta_blob_fee.min(delta_priority_fee).min(0)
 }
 
+/// Returns how many (logarithmic) fee jumps the current fees need to decrease until the blob
+/// transaction's fee caps are satisfied, or `0` if the caps already satisfy the given fees.
+///
+/// This takes both the blob fee and the base fee into account, see [`fee_delta`].
+pub(crate) fn blob_tx_fee_jumps<T: PoolTransaction>(
+    transaction: &ValidPoolTransaction<T>,
+    pending_fees: &PendingFees,
+) -> u64 {
+    let delta_blob_fee =
+        fee_delta(transaction.max_fee_per_blob_gas().unwrap_or_default(), pending_fees.blob_fee);
+    let delta_base_fee = fee_delta(transaction.max_fee_per_gas(), pending_fees.base_fee as u128);
+    delta_blob_fee.min(delta_base_fee).min(0).unsigned_abs()
+}
+
 /// A struct used to determine the ordering for a specific blob transaction in the pool. This uses
 /// a `priority` value to determine the ordering, and uses the `submission_id` to break ties.
 ///
@@ -0,0 +1,289 @@
+//! A bounded in-memory journal of transaction lifecycle events.
+
+use crate::{pool::state::SubPool, traits::PropagateKind};
+use alloy_primitives::{TxHash, B256};
+use futures_util::Stream;
+use std::{
+    collections::{HashMap, VecDeque},
+    pin::Pin,
+    task::{Context, Poll},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::mpsc::{error::TrySendError, Receiver, Sender};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The size of the channel used to stream journal entries to subscribers.
+const TX_JOURNAL_CHANNEL_SIZE: usize = 1024;
+
+/// Default number of events the journal keeps per transaction.
+pub const DEFAULT_TXPOOL_JOURNAL_MAX_EVENTS_PER_TRANSACTION: usize = 32;
+
+/// Configuration for the transaction lifecycle journal.
+///
+/// The journal is disabled by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionJournalConfig {
+    /// Maximum number of transactions to keep history for.
+    ///
+    /// The history of the least recently added transaction is dropped first. `0` disables the
+    /// journal.
+    pub max_transactions: usize,
+    /// Maximum number of events to keep per transaction.
+    ///
+    /// The first event of a transaction is always kept.
+    pub max_events_per_transaction: usize,
+}
+
+impl TransactionJournalConfig {
+    /// Returns a config with the journal disabled.
+    pub const fn disabled() -> Self {
+        Self { max_transactions: 0, max_events_per_transaction: 0 }
+    }
+
+    /// Returns `true` if the journal is enabled.
+    pub const fn is_enabled(&self) -> bool {
+        self.max_transactions > 0 && self.max_events_per_transaction > 0
+    }
+}
+
+impl Default for TransactionJournalConfig {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+/// Why a transaction was discarded from the pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum DiscardReason {
+    /// The transaction failed validation.
+    Invalid(String),
+    /// The transaction was evicted to enforce the pool's size limits.
+    PoolLimits,
+    /// The transaction's nonce became outdated or it can no longer be executed after a state
+    /// change.
+    Outdated,
+    /// The transaction was explicitly removed from the pool.
+    Removed,
+}
+
+/// A lifecycle event of a transaction recorded in the journal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "camelCase"))]
+pub enum TransactionJournalEvent {
+    /// The transaction was added to the given sub-pool.
+    Added {
+        /// The sub-pool the transaction was added to.
+        subpool: SubPool,
+    },
+    /// The transaction was promoted to the pending sub-pool.
+    Promoted,
+    /// The transaction was replaced by another transaction with the same sender and nonce.
+    Replaced {
+        /// The hash of the replacement transaction.
+        #[cfg_attr(feature = "serde", serde(rename = "replacedBy"))]
+        replaced_by: TxHash,
+    },
+    /// The transaction was discarded from the pool.
+    Discarded {
+        /// Why the transaction was discarded.
+        reason: DiscardReason,
+    },
+    /// The transaction was propagated to peers.
+    Propagated {
+        /// The number of peers the full transaction was sent to.
+        full: usize,
+        /// The number of peers only the transaction hash was announced to.
+        hashes: usize,
+    },
+    /// The transaction was included in a block.
+    Mined {
+        /// The hash of the block that includes the transaction.
+        #[cfg_attr(feature = "serde", serde(rename = "blockHash"))]
+        block_hash: B256,
+    },
+}
+
+impl TransactionJournalEvent {
+    /// Returns a [`TransactionJournalEvent::Propagated`] event for the given peers.
+    pub fn propagated(peers: &[PropagateKind]) -> Self {
+        let full = peers.iter().filter(|kind| matches!(kind, PropagateKind::Full(_))).count();
+        Self::Propagated { full, hashes: peers.len() - full }
+    }
+
+    /// Returns `true` if no more events are expected for the transaction after this event.
+    pub const fn is_final(&self) -> bool {
+        matches!(self, Self::Replaced { .. } | Self::Discarded { .. } | Self::Mined { .. })
+    }
+}
+
+/// A single entry of the transaction journal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TransactionJournalEntry {
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// Unix timestamp in milliseconds when the event was recorded.
+    pub timestamp: u64,
+    /// The recorded event.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub event: TransactionJournalEvent,
+}
+
+/// The current status of a transaction, derived from the pool and the journal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum TransactionStatus {
+    /// The transaction is in the pool, in the given sub-pool.
+    InPool(SubPool),
+    /// The transaction was included in a block.
+    Mined,
+    /// The transaction was replaced by another transaction.
+    Replaced,
+    /// The transaction was discarded from the pool.
+    Discarded,
+    /// The transaction is not in the pool and its history is unknown.
+    Unknown,
+}
+
+/// The status of a transaction together with its recorded history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TransactionHistory {
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// The current status of the transaction.
+    pub status: TransactionStatus,
+    /// All recorded events of the transaction, oldest first.
+    pub events: Vec<TransactionJournalEntry>,
+}
+
+/// A Stream that receives all [`TransactionJournalEntry`]s recorded by the journal.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct TransactionJournalEvents {
+    events: Receiver<TransactionJournalEntry>,
+}
+
+impl TransactionJournalEvents {
+    /// Create a new instance of this stream.
+    pub const fn new(events: Receiver<TransactionJournalEntry>) -> Self {
+        Self { events }
+    }
+}
+
+impl Stream for TransactionJournalEvents {
+    type Item = TransactionJournalEntry;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().events.poll_recv(cx)
+    }
+}
+
+/// Bounded in-memory history of transaction lifecycle events.
+#[derive(Debug, Default)]
+pub(crate) struct TransactionJournal {
+    config: TransactionJournalConfig,
+    /// Recorded events by transaction hash.
+    entries: HashMap<TxHash, Vec<TransactionJournalEntry>>,
+    /// Transaction hashes in the order they were first recorded.
+    order: VecDeque<TxHash>,
+    /// Subscribers for all recorded entries.
+    senders: Vec<Sender<TransactionJournalEntry>>,
+}
+
+impl TransactionJournal {
+    /// Creates a new journal with the given config.
+    pub(crate) fn new(config: TransactionJournalConfig) -> Self {
+        Self { config, ..Default::default() }
+    }
+
+    /// Records the event for the given transaction and forwards it to all subscribers.
+    pub(crate) fn record(&mut self, hash: TxHash, event: TransactionJournalEvent) {
+        if !self.config.is_enabled() {
+            return
+        }
+
+        let entry = TransactionJournalEntry { hash, timestamp: unix_timestamp_millis(), event };
+        self.senders.retain(|sender| match sender.try_send(entry.clone()) {
+            Ok(_) | Err(TrySendError::Full(_)) => true,
+            Err(TrySendError::Closed(_)) => false,
+        });
+
+        let events = self.entries.entry(hash).or_insert_with(|| {
+            self.order.push_back(hash);
+            Vec::new()
+        });
+        if events.len() >= self.config.max_events_per_transaction {
+            // keep the first event, which tells how the transaction entered the pool
+            let first = usize::from(self.config.max_events_per_transaction > 1);
+            events.remove(first);
+        }
+        events.push(entry);
+
+        while self.order.len() > self.config.max_transactions {
+            if let Some(evicted) = self.order.pop_front() {
+                self.entries.remove(&evicted);
+            }
+        }
+    }
+
+    /// Returns all recorded events for the given transaction, oldest first.
+    pub(crate) fn get(&self, hash: &TxHash) -> Option<&[TransactionJournalEntry]> {
+        self.entries.get(hash).map(Vec::as_slice)
+    }
+
+    /// Creates a new subscription for all recorded entries.
+    pub(crate) fn subscribe(&mut self) -> TransactionJournalEvents {
+        let (tx, rx) = tokio::sync::mpsc::channel(TX_JOURNAL_CHANNEL_SIZE);
+        self.senders.push(tx);
+        TransactionJournalEvents::new(rx)
+    }
+}
+
+fn unix_timestamp_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn journal_is_bounded() {
+        let mut journal = TransactionJournal::new(TransactionJournalConfig {
+            max_transactions: 2,
+            max_events_per_transaction: 2,
+        });
+        let (a, b, c) = (TxHash::random(), TxHash::random(), TxHash::random());
+
+        journal.record(a, TransactionJournalEvent::Added { subpool: SubPool::Queued });
+        journal.record(a, TransactionJournalEvent::Promoted);
+        journal.record(a, TransactionJournalEvent::Mined { block_hash: B256::ZERO });
+        let events = journal.get(&a).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event, TransactionJournalEvent::Added { subpool: SubPool::Queued });
+        assert_eq!(events[1].event, TransactionJournalEvent::Mined { block_hash: B256::ZERO });
+
+        journal.record(b, TransactionJournalEvent::Added { subpool: SubPool::Pending });
+        journal.record(c, TransactionJournalEvent::Added { subpool: SubPool::Pending });
+        assert!(journal.get(&a).is_none());
+        assert!(journal.get(&b).is_some());
+        assert!(journal.get(&c).is_some());
+    }
+
+    #[test]
+    fn disabled_journal_records_nothing() {
+        let mut journal = TransactionJournal::new(TransactionJournalConfig::default());
+        let hash = TxHash::random();
+        journal.record(hash, TransactionJournalEvent::Promoted);
+        assert!(journal.get(&hash).is_none());
+    }
+}
@@ -1,7 +1,14 @@
 //! Listeners for the transaction-pool
 
 use crate::{
-    pool::events::{FullTransactionEvent, TransactionEvent},
+    pool::{
+        events::{FullTransactionEvent, TransactionEvent},
+        journal::{
+            DiscardReason, TransactionJournal, TransactionJournalConfig, TransactionJournalEntry,
+            TransactionJournalEvent, TransactionJournalEvents,
+        },
+        state::SubPool,
+    },
     traits::PropagateKind,
     PoolTransaction, ValidPoolTransaction,
 };
@@ -78,18 +85,36 @@ pub(crate) struct PoolEventBroadcast<T: PoolTransaction> {
     all_events_broadcaster: AllPoolEventsBroadcaster<T>,
     /// All listeners for events for a certain transaction hash.
     broadcasters_by_hash: HashMap<TxHash, PoolEventBroadcaster>,
+    /// Bounded history of recent transaction events.
+    journal: TransactionJournal,
 }
 
 impl<T: PoolTransaction> Default for PoolEventBroadcast<T> {
     fn default() -> Self {
+        Self::new(TransactionJournalConfig::default())
+    }
+}
+
+impl<T: PoolTransaction> PoolEventBroadcast<T> {
+    /// Creates a new instance with a journal configured by the given config.
+    pub(crate) fn new(journal: TransactionJournalConfig) -> Self {
         Self {
             all_events_broadcaster: AllPoolEventsBroadcaster::default(),
             broadcasters_by_hash: HashMap::default(),
+            journal: TransactionJournal::new(journal),
         }
     }
-}
 
-impl<T: PoolTransaction> PoolEventBroadcast<T> {
+    /// Returns all journal entries recorded for the given transaction, oldest first.
+    pub(crate) fn journal_entries(&self, tx_hash: &TxHash) -> Option<&[TransactionJournalEntry]> {
+        self.journal.get(tx_hash)
+    }
+
+    /// Create a new subscription for all journal entries.
+    pub(crate) fn subscribe_journal(&mut self) -> TransactionJournalEvents {
+        self.journal.subscribe()
+    }
+
     /// Calls the broadcast callback with the `PoolEventBroadcaster` that belongs to the hash.
     fn broadcast_event(
         &mut self,
@@ -134,6 +159,7 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
 
     /// Notify listeners about a transaction that was added to the pending queue.
     pub(crate) fn pending(&mut self, tx: &TxHash, replaced: Option<Arc<ValidPoolTransaction<T>>>) {
+        self.journal.record(*tx, TransactionJournalEvent::Added { subpool: SubPool::Pending });
         self.broadcast_event(tx, TransactionEvent::Pending, FullTransactionEvent::Pending(*tx));
 
         if let Some(replaced) = replaced {
@@ -142,8 +168,15 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
         }
     }
 
+    /// Notify listeners about a transaction that was promoted to the pending queue.
+    pub(crate) fn promoted(&mut self, tx: &TxHash) {
+        self.journal.record(*tx, TransactionJournalEvent::Promoted);
+        self.broadcast_event(tx, TransactionEvent::Pending, FullTransactionEvent::Pending(*tx));
+    }
+
     /// Notify listeners about a transaction that was replaced.
     pub(crate) fn replaced(&mut self, tx: Arc<ValidPoolTransaction<T>>, replaced_by: TxHash) {
+        self.journal.record(*tx.hash(), TransactionJournalEvent::Replaced { replaced_by });
         let transaction = Arc::clone(&tx);
         self.broadcast_event(
             tx.hash(),
@@ -153,12 +186,16 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
     }
 
     /// Notify listeners about a transaction that was added to the queued pool.
-    pub(crate) fn queued(&mut self, tx: &TxHash) {
+    ///
+    /// The given sub-pool is the parked sub-pool the transaction was added to.
+    pub(crate) fn queued(&mut self, tx: &TxHash, subpool: SubPool) {
+        self.journal.record(*tx, TransactionJournalEvent::Added { subpool });
         self.broadcast_event(tx, TransactionEvent::Queued, FullTransactionEvent::Queued(*tx));
     }
 
     /// Notify listeners about a transaction that was propagated.
     pub(crate) fn propagated(&mut self, tx: &TxHash, peers: Vec<PropagateKind>) {
+        self.journal.record(*tx, TransactionJournalEvent::propagated(&peers));
         let peers = Arc::new(peers);
         self.broadcast_event(
             tx,
@@ -168,12 +205,14 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
     }
 
     /// Notify listeners about a transaction that was discarded.
-    pub(crate) fn discarded(&mut self, tx: &TxHash) {
+    pub(crate) fn discarded(&mut self, tx: &TxHash, reason: DiscardReason) {
+        self.journal.record(*tx, TransactionJournalEvent::Discarded { reason });
         self.broadcast_event(tx, TransactionEvent::Discarded, FullTransactionEvent::Discarded(*tx));
     }
 
     /// Notify listeners that the transaction was mined
     pub(crate) fn mined(&mut self, tx: &TxHash, block_hash: B256) {
+        self.journal.record(*tx, TransactionJournalEvent::Mined { block_hash });
         self.broadcast_event(
             tx,
             TransactionEvent::Mined(block_hash),
@@ -66,6 +66,8 @@
 //!    category (2.) and become pending.
 
 use crate::{
+    admission::{AdmissionContext, AdmissionError, AdmissionPolicies, AdmissionPolicy},
+    conditional::TransactionConditional,
     error::{PoolError, PoolErrorKind, PoolResult},
     identifier::{SenderId, SenderIdentifiers, TransactionId},
     pool::{
@@ -74,22 +76,22 @@ use crate::{
         txpool::{SenderInfo, TxPool},
     },
     traits::{
-        AllPoolTransactions, BestTransactionsAttributes, BlockInfo, NewTransactionEvent, PoolSize,
-        PoolTransaction, PropagatedTransactions, TransactionOrigin,
+        AllPoolTransactions, BestTransactionsAttributes, BlockInfo, NewTransactionEvent, PeerId,
+        PoolSize, PoolTransaction, PropagatedTransactions, TransactionOrigin,
     },
     validate::{TransactionValidationOutcome, ValidPoolTransaction},
     CanonicalStateUpdate, PoolConfig, TransactionOrdering, TransactionValidator,
 };
-use alloy_primitives::{Address, TxHash, B256};
+use alloy_primitives::{Address, BlockHash, TxHash, B256};
 use best::BestTransactions;
 use parking_lot::{Mutex, RwLock, RwLockReadGuard};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_execution_types::ChangedAccount;
 
 use reth_primitives::{
-    BlobTransaction, BlobTransactionSidecar, PooledTransactionsElement, TransactionSigned,
-    TransactionSignedEcRecovered,
+    BlobTransaction, PooledTransactionsElement, TransactionSigned, TransactionSignedEcRecovered,
 };
+use revm::primitives::EnvKzgSettings;
 use std::{
     collections::{HashMap, HashSet},
     fmt,
@@ -99,8 +101,9 @@ use std::{
 use tokio::sync::mpsc;
 use tracing::{debug, trace, warn};
 mod events;
+mod journal;
 use crate::{
-    blobstore::BlobStore,
+    blobstore::{into_eip4844_sidecar, BlobStore, BlobTransactionSidecarVariant},
     metrics::BlobStoreMetrics,
     pool::txpool::UpdateOutcome,
     traits::{GetPooledTransactionLimit, NewBlobSidecar, TransactionListenerKind},
@@ -109,6 +112,11 @@ use crate::{
 pub use best::BestTransactionFilter;
 pub use blob::{blob_tx_priority, fee_delta};
 pub use events::{FullTransactionEvent, TransactionEvent};
+pub use journal::{
+    DiscardReason, TransactionHistory, TransactionJournalConfig, TransactionJournalEntry,
+    TransactionJournalEvent, TransactionJournalEvents, TransactionStatus,
+    DEFAULT_TXPOOL_JOURNAL_MAX_EVENTS_PER_TRANSACTION,
+};
 pub use listener::{AllTransactionsEvents, TransactionEvents};
 pub use parked::{BasefeeOrd, ParkedOrd, ParkedPool, QueuedOrd};
 pub use pending::PendingPool;
@@ -155,6 +163,8 @@ where
     blob_transaction_sidecar_listener: Mutex<Vec<BlobTransactionSidecarListener>>,
     /// Metrics for the blob store
     blob_store_metrics: BlobStoreMetrics,
+    /// Policies that are checked before transactions are validated.
+    admission_policies: RwLock<AdmissionPolicies<T::Transaction>>,
 }
 
 // === impl PoolInner ===
@@ -170,22 +180,78 @@ where
         Self {
             identifiers: Default::default(),
             validator,
-            event_listener: Default::default(),
+            event_listener: RwLock::new(PoolEventBroadcast::new(config.journal)),
             pool: RwLock::new(TxPool::new(ordering, config.clone())),
             pending_transaction_listener: Default::default(),
             transaction_listener: Default::default(),
             blob_transaction_sidecar_listener: Default::default(),
+            admission_policies: RwLock::new(AdmissionPolicies::from_config(&config.admission)),
             config,
             blob_store,
             blob_store_metrics: Default::default(),
         }
     }
 
+    /// Adds an admission policy that is checked before transactions are validated.
+    pub(crate) fn add_admission_policy(
+        &self,
+        policy: impl AdmissionPolicy<T::Transaction> + 'static,
+    ) {
+        self.admission_policies.write().push(policy);
+    }
+
+    /// Checks the transaction against all admission policies.
+    ///
+    /// Returns the context the transaction was checked with.
+    pub(crate) fn check_admission(
+        &self,
+        origin: TransactionOrigin,
+        peer: Option<PeerId>,
+        transaction: &T::Transaction,
+    ) -> Result<AdmissionContext, AdmissionError> {
+        let mut context = AdmissionContext { peer, ..Default::default() };
+        let policies = self.admission_policies.read();
+        if policies.is_empty() {
+            return Ok(context)
+        }
+
+        if let Some(sender_id) = self.identifiers.read().sender_id(&transaction.sender()) {
+            for tx in self.get_pool_data().all().txs_iter(sender_id).map(|(_, tx)| &tx.transaction)
+            {
+                // a transaction with the same nonce would be replaced
+                if tx.nonce() == transaction.nonce() {
+                    continue
+                }
+                context.sender_transactions += 1;
+                context.sender_calldata += tx.transaction.input().len();
+            }
+        }
+
+        policies.check(origin, transaction, &context)?;
+        Ok(context)
+    }
+
+    /// Releases the quotas a transaction of the sender consumed in the admission policies, after
+    /// it was rejected by the validator.
+    pub(crate) fn release_admission(
+        &self,
+        origin: TransactionOrigin,
+        sender: Address,
+        context: &AdmissionContext,
+    ) {
+        self.admission_policies.read().release(origin, sender, context);
+    }
+
     /// Returns the configured blob store.
     pub(crate) const fn blob_store(&self) -> &S {
         &self.blob_store
     }
 
+    /// Returns the KZG settings of the validator, used to derive the proofs of stored sidecars.
+    pub(crate) fn kzg_settings(&self) -> EnvKzgSettings {
+        self.validator.kzg_settings()
+    }
+
     /// Returns stats about the size of the pool.
     pub(crate) fn size(&self) -> PoolSize {
         self.get_pool_data().size()
@@ -200,6 +266,11 @@ where
         self.pool.write().set_block_info(info)
     }
 
+    /// Marks the known accounts of all conditional transactions as checked at the given block.
+    pub(crate) fn on_conditional_transactions_checked(&self, block_hash: BlockHash) {
+        self.pool.write().set_known_accounts_checked_at(block_hash)
+    }
+
     /// Returns the internal [`SenderId`] for this address
     pub(crate) fn get_sender_id(&self, addr: Address) -> SenderId {
         self.identifiers.write().sender_id_or_create(addr)
@@ -282,6 +353,33 @@ where
         self.event_listener.write().subscribe_all()
     }
 
+    /// Adds a listener for all entries recorded by the transaction journal.
+    pub(crate) fn add_transaction_journal_listener(&self) -> TransactionJournalEvents {
+        self.event_listener.write().subscribe_journal()
+    }
+
+    /// Returns the current status and the recorded history of the given transaction.
+    pub(crate) fn transaction_history(&self, tx_hash: TxHash) -> TransactionHistory {
+        let subpool = self.get_pool_data().all().subpool(&tx_hash);
+        let events = self
+            .event_listener
+            .read()
+            .journal_entries(&tx_hash)
+            .map(<[_]>::to_vec)
+            .unwrap_or_default();
+
+        let status = match subpool {
+            Some(subpool) => TransactionStatus::InPool(subpool),
+            None => match events.last().map(|entry| &entry.event) {
+                Some(TransactionJournalEvent::Mined { .. }) => TransactionStatus::Mined,
+                Some(TransactionJournalEvent::Replaced { .. }) => TransactionStatus::Replaced,
+                Some(TransactionJournalEvent::Discarded { .. }) => TransactionStatus::Discarded,
+                _ => TransactionStatus::Unknown,
+            },
+        };
+        TransactionHistory { hash: tx_hash, status, events }
+    }
+
     /// Returns a read lock to the pool's data.
     pub(crate) fn get_pool_data(&self) -> RwLockReadGuard<'_, TxPool<T>> {
         self.pool.read()
@@ -306,7 +404,12 @@ where
     ///
     /// Caution: this assumes the given transaction is eip-4844
     fn get_blob_transaction(&self, transaction: TransactionSigned) -> Option<BlobTransaction> {
-        if let Ok(Some(sidecar)) = self.blob_store.get(transaction.hash()) {
+        let kzg_settings = self.kzg_settings();
+        if let Ok(Some(sidecar)) = self
+            .blob_store
+            .get(transaction.hash())
+            .and_then(|s| s.map(|s| into_eip4844_sidecar(s, kzg_settings.get())).transpose())
+        {
             if let Ok(blob) = BlobTransaction::try_from_signed(transaction, sidecar) {
                 return Some(blob)
             }
@@ -406,6 +509,46 @@ where
 
         // notify listeners about updates
         self.notify_on_new_state(outcome);
+
+        // evict conditional transactions that can no longer be included in the next block
+        self.remove_expired_conditional_transactions(
+            block_info.last_seen_block_number + 1,
+            new_tip.timestamp + 1,
+        );
+    }
+
+    /// Returns all transactions that were submitted with a [`TransactionConditional`].
+    pub(crate) fn conditional_transactions(
+        &self,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.get_pool_data()
+            .all()
+            .transactions_iter()
+            .filter(|tx| tx.conditional.is_some())
+            .collect()
+    }
+
+    /// Removes all conditional transactions whose block number or timestamp bounds are exceeded
+    /// by the given block.
+    fn remove_expired_conditional_transactions(&self, block_number: u64, timestamp: u64) {
+        let expired = self
+            .get_pool_data()
+            .all()
+            .transactions_iter()
+            .filter(|tx| {
+                tx.conditional.as_ref().is_some_and(|conditional| {
+                    conditional.has_exceeded_block_attributes(block_number, timestamp)
+                })
+            })
+            .map(|tx| *tx.hash())
+            .collect::<Vec<_>>();
+        if expired.is_empty() {
+            return
+        }
+
+        debug!(target: "txpool", num_txs = expired.len(), "removing expired conditional transactions");
+        let removed = self.remove_transactions_and_descendants(expired);
+        self.delete_discarded_blobs(removed.iter());
     }
 
     /// Performs account updates on the pool.
@@ -417,8 +560,8 @@ where
             self.pool.write().update_accounts(changed_senders);
         let mut listener = self.event_listener.write();
 
-        promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
-        discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
+        promoted.iter().for_each(|tx| listener.promoted(tx.hash()));
+        discarded.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::Outdated));
 
         // This deletes outdated blob txs from the blob store, based on the account's nonce. This is
         // called during txpool maintenance when the pool drifted.
@@ -433,6 +576,7 @@ where
         &self,
         origin: TransactionOrigin,
         tx: TransactionValidationOutcome<T::Transaction>,
+        conditional: Option<Box<TransactionConditional>>,
     ) -> PoolResult<TxHash> {
         match tx {
             TransactionValidationOutcome::Valid {
@@ -459,9 +603,12 @@ where
                 let tx = ValidPoolTransaction {
                     transaction,
                     transaction_id,
-                    propagate,
+                    // conditional transactions are not propagated because peers would include them
+                    // unconditionally
+                    propagate: propagate && conditional.is_none(),
                     timestamp: Instant::now(),
                     origin,
+                    conditional,
                 };
 
                 let added = self.pool.write().add_transaction(tx, balance, state_nonce)?;
@@ -500,12 +647,12 @@ where
             }
             TransactionValidationOutcome::Invalid(tx, err) => {
                 let mut listener = self.event_listener.write();
-                listener.discarded(tx.hash());
+                listener.discarded(tx.hash(), DiscardReason::Invalid(err.to_string()));
                 Err(PoolError::new(*tx.hash(), err))
             }
             TransactionValidationOutcome::Error(tx_hash, err) => {
                 let mut listener = self.event_listener.write();
-                listener.discarded(&tx_hash);
+                listener.discarded(&tx_hash, DiscardReason::Invalid(err.to_string()));
                 Err(PoolError::other(tx_hash, err))
             }
         }
@@ -531,8 +678,41 @@ where
         origin: TransactionOrigin,
         transactions: impl IntoIterator<Item = TransactionValidationOutcome<T::Transaction>>,
     ) -> Vec<PoolResult<TxHash>> {
-        let mut added =
-            transactions.into_iter().map(|tx| self.add_transaction(origin, tx)).collect::<Vec<_>>();
+        self.add_transactions_with_conditionals(
+            origin,
+            transactions.into_iter().map(|tx| (tx, None)),
+        )
+    }
+
+    /// Adds a validated transaction that may only be included in blocks that satisfy the given
+    /// conditions.
+    pub(crate) fn add_conditional_transaction(
+        &self,
+        origin: TransactionOrigin,
+        tx: TransactionValidationOutcome<T::Transaction>,
+        conditional: TransactionConditional,
+    ) -> PoolResult<TxHash> {
+        let mut results = self.add_transactions_with_conditionals(
+            origin,
+            std::iter::once((tx, Some(Box::new(conditional)))),
+        );
+        results.pop().expect("result length is the same as the input")
+    }
+
+    fn add_transactions_with_conditionals(
+        &self,
+        origin: TransactionOrigin,
+        transactions: impl IntoIterator<
+            Item = (
+                TransactionValidationOutcome<T::Transaction>,
+                Option<Box<TransactionConditional>>,
+            ),
+        >,
+    ) -> Vec<PoolResult<TxHash>> {
+        let mut added = transactions
+            .into_iter()
+            .map(|(tx, conditional)| self.add_transaction(origin, tx, conditional))
+            .collect::<Vec<_>>();
 
         // If at least one transaction was added successfully, then we enforce the pool size limits.
         let discarded =
@@ -544,7 +724,7 @@ where
 
         {
             let mut listener = self.event_listener.write();
-            discarded.iter().for_each(|tx| listener.discarded(tx));
+            discarded.iter().for_each(|tx| listener.discarded(tx, DiscardReason::PoolLimits));
         }
 
         // It may happen that a newly added transaction is immediately discarded, so we need to
@@ -592,7 +772,7 @@ where
     }
 
     /// Notify all listeners about a blob sidecar for a newly inserted blob (eip4844) transaction.
-    fn on_new_blob_sidecar(&self, tx_hash: &TxHash, sidecar: &BlobTransactionSidecar) {
+    fn on_new_blob_sidecar(&self, tx_hash: &TxHash, sidecar: &BlobTransactionSidecarVariant) {
         let mut sidecar_listeners = self.blob_transaction_sidecar_listener.lock();
         if sidecar_listeners.is_empty() {
             return
@@ -639,8 +819,8 @@ where
         let mut listener = self.event_listener.write();
 
         mined.iter().for_each(|tx| listener.mined(tx, block_hash));
-        promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
-        discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
+        promoted.iter().for_each(|tx| listener.promoted(tx.hash()));
+        discarded.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::Outdated));
     }
 
     /// Fire events for the newly added transaction if there are any.
@@ -652,11 +832,13 @@ where
                 let AddedPendingTransaction { transaction, promoted, discarded, replaced } = tx;
 
                 listener.pending(transaction.hash(), replaced.clone());
-                promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
-                discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
+                promoted.iter().for_each(|tx| listener.promoted(tx.hash()));
+                discarded
+                    .iter()
+                    .for_each(|tx| listener.discarded(tx.hash(), DiscardReason::Outdated));
             }
-            AddedTransaction::Parked { transaction, replaced, .. } => {
-                listener.queued(transaction.hash());
+            AddedTransaction::Parked { transaction, replaced, subpool } => {
+                listener.queued(transaction.hash(), *subpool);
                 if let Some(replaced) = replaced {
                     listener.replaced(replaced.clone(), *transaction.hash());
                 }
@@ -710,7 +892,7 @@ where
 
         let mut listener = self.event_listener.write();
 
-        removed.iter().for_each(|tx| listener.discarded(tx.hash()));
+        removed.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::Removed));
 
         removed
     }
@@ -728,7 +910,7 @@ where
 
         let mut listener = self.event_listener.write();
 
-        removed.iter().for_each(|tx| listener.discarded(tx.hash()));
+        removed.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::Removed));
 
         removed
     }
@@ -742,7 +924,7 @@ where
 
         let mut listener = self.event_listener.write();
 
-        removed.iter().for_each(|tx| listener.discarded(tx.hash()));
+        removed.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::Removed));
 
         removed
     }
@@ -866,7 +1048,7 @@ where
     }
 
     /// Inserts a blob transaction into the blob store
-    fn insert_blob(&self, hash: TxHash, blob: BlobTransactionSidecar) {
+    fn insert_blob(&self, hash: TxHash, blob: BlobTransactionSidecarVariant) {
         debug!(target: "txpool", "[{:?}] storing blob sidecar", hash);
         if let Err(err) = self.blob_store.insert(hash, blob) {
             warn!(target: "txpool", %err, "[{:?}] failed to insert blob", hash);
@@ -885,6 +1067,15 @@ where
         let _ = self.blob_store.delete_all(txs);
     }
 
+    /// Deletes the blobs the blob store kept from a previous run, unless their transaction is in
+    /// the pool.
+    pub(crate) fn delete_unclaimed_blobs(&self) {
+        let pool = self.get_pool_data();
+        if let Err(err) = self.blob_store.delete_unclaimed(&|tx| pool.contains(tx)) {
+            warn!(target: "txpool", %err, "failed to delete unclaimed blobs");
+        }
+    }
+
     /// Cleans up the blob store
     pub(crate) fn cleanup_blobs(&self) {
         let stat = self.blob_store.cleanup();
@@ -1214,12 +1405,152 @@ impl<T: PoolTransaction> OnNewCanonicalStateOutcome<T> {
 mod tests {
     use crate::{
         blobstore::{BlobStore, InMemoryBlobStore},
+        conditional::{KnownAccountStorage, TransactionConditional},
+        pool::{
+            state::SubPool, DiscardReason, TransactionJournalConfig, TransactionJournalEvent,
+            TransactionStatus, DEFAULT_TXPOOL_JOURNAL_MAX_EVENTS_PER_TRANSACTION,
+        },
         test_utils::{MockTransaction, TestPoolBuilder},
         validate::ValidTransaction,
-        BlockInfo, PoolConfig, SubPoolLimit, TransactionOrigin, TransactionValidationOutcome, U256,
+        BestTransactionsAttributes, BlockInfo, PoolConfig, PoolTransaction, SubPoolLimit,
+        TransactionOrigin, TransactionValidationOutcome, U256,
     };
+    use alloy_primitives::{Address, TxHash, B256};
     use reth_primitives::{kzg::Blob, transaction::generate_blob_sidecar};
-    use std::{fs, path::PathBuf};
+    use std::{collections::HashMap, fs, path::PathBuf};
+
+    const fn valid_outcome(tx: MockTransaction) -> TransactionValidationOutcome<MockTransaction> {
+        TransactionValidationOutcome::Valid {
+            balance: U256::MAX,
+            state_nonce: 0,
+            transaction: ValidTransaction::Valid(tx),
+            propagate: true,
+        }
+    }
+
+    #[test]
+    fn test_conditional_transactions() {
+        let test_pool = &TestPoolBuilder::default().pool;
+
+        let conditional =
+            TransactionConditional { block_number_max: Some(5), ..Default::default() };
+        let conditional_hash = test_pool
+            .add_conditional_transaction(
+                TransactionOrigin::Local,
+                valid_outcome(MockTransaction::eip1559()),
+                conditional,
+            )
+            .unwrap();
+        let hash = test_pool
+            .add_transactions(
+                TransactionOrigin::Local,
+                std::iter::once(valid_outcome(MockTransaction::eip1559())),
+            )
+            .pop()
+            .unwrap()
+            .unwrap();
+
+        let conditional_txs = test_pool.conditional_transactions();
+        assert_eq!(conditional_txs.len(), 1);
+        assert_eq!(*conditional_txs[0].hash(), conditional_hash);
+        assert!(!conditional_txs[0].propagate);
+
+        // the conditional transaction can be included in block 5 but not in block 6
+        let best = test_pool
+            .best_transactions_with_attributes(
+                BestTransactionsAttributes::new(0, None).with_block(B256::ZERO, 5, 0),
+            )
+            .map(|tx| *tx.hash())
+            .collect::<Vec<_>>();
+        assert_eq!(best.len(), 2);
+        let best = test_pool
+            .best_transactions_with_attributes(
+                BestTransactionsAttributes::new(0, None).with_block(B256::ZERO, 6, 0),
+            )
+            .map(|tx| *tx.hash())
+            .collect::<Vec<_>>();
+        assert_eq!(best, vec![hash]);
+
+        test_pool.remove_expired_conditional_transactions(5, 0);
+        assert!(test_pool.get(&conditional_hash).is_some());
+        test_pool.remove_expired_conditional_transactions(6, 0);
+        assert!(test_pool.get(&conditional_hash).is_none());
+        assert!(test_pool.get(&hash).is_some());
+    }
+
+    #[test]
+    fn test_conditional_transactions_with_known_accounts() {
+        let test_pool = &TestPoolBuilder::default().pool;
+
+        let conditional = TransactionConditional {
+            known_accounts: HashMap::from([(
+                Address::ZERO,
+                KnownAccountStorage::StorageRoot(B256::ZERO),
+            )]),
+            ..Default::default()
+        };
+        test_pool
+            .add_conditional_transaction(
+                TransactionOrigin::Local,
+                valid_outcome(MockTransaction::eip1559()),
+                conditional,
+            )
+            .unwrap();
+
+        let parent = B256::with_last_byte(1);
+        let best_at = |parent_hash| {
+            test_pool
+                .best_transactions_with_attributes(
+                    BestTransactionsAttributes::new(0, None).with_block(parent_hash, 1, 0),
+                )
+                .count()
+        };
+
+        // the known accounts haven't been checked against the parent yet
+        assert_eq!(best_at(parent), 0);
+
+        test_pool.on_conditional_transactions_checked(parent);
+        assert_eq!(best_at(parent), 1);
+        assert_eq!(best_at(B256::ZERO), 0);
+    }
+
+    #[test]
+    fn test_transaction_history() {
+        let journal = TransactionJournalConfig {
+            max_transactions: 16,
+            max_events_per_transaction: DEFAULT_TXPOOL_JOURNAL_MAX_EVENTS_PER_TRANSACTION,
+        };
+        let test_pool =
+            &TestPoolBuilder::default().with_config(PoolConfig { journal, ..Default::default() }).pool;
+
+        let tx = MockTransaction::eip1559();
+        let hash = *PoolTransaction::hash(&tx);
+        test_pool
+            .add_transactions(TransactionOrigin::External, std::iter::once(valid_outcome(tx)))
+            .pop()
+            .unwrap()
+            .unwrap();
+
+        let history = test_pool.transaction_history(hash);
+        assert_eq!(history.status, TransactionStatus::InPool(SubPool::Pending));
+        assert_eq!(history.events.len(), 1);
+        assert_eq!(
+            history.events[0].event,
+            TransactionJournalEvent::Added { subpool: SubPool::Pending }
+        );
+
+        test_pool.remove_transactions(vec![hash]);
+        let history = test_pool.transaction_history(hash);
+        assert_eq!(history.status, TransactionStatus::Discarded);
+        assert_eq!(
+            history.events.last().unwrap().event,
+            TransactionJournalEvent::Discarded { reason: DiscardReason::Removed }
+        );
+
+        let unknown = test_pool.transaction_history(TxHash::random());
+        assert_eq!(unknown.status, TransactionStatus::Unknown);
+        assert!(unknown.events.is_empty());
+    }
 
     #[test]
     fn test_discard_blobs_on_blob_tx_eviction() {
@@ -1268,7 +1599,7 @@ mod tests {
 
             // Insert the sidecar into the blob store if the current index is within the blob limit.
             if n < blob_limit.max_txs {
-                blob_store.insert(tx.get_hash(), sidecar.clone()).unwrap();
+                blob_store.insert(tx.get_hash(), sidecar.clone().into()).unwrap();
             }
 
             // Add the transaction to the pool with external origin and valid outcome.
@@ -1280,10 +1611,11 @@ mod tests {
                         state_nonce: 0,
                         transaction: ValidTransaction::ValidWithSidecar {
                             transaction: tx,
-                            sidecar: sidecar.clone(),
+                            sidecar: sidecar.clone().into(),
                         },
                         propagate: true,
                     },
+                    None,
                 )
                 .unwrap();
 
@@ -144,7 +144,7 @@ impl<T: TransactionOrdering> PendingPool<T> {
         for tx in unlocked {
             submission_id += 1;
             debug_assert!(!best.all.contains_key(tx.id()), "transaction already included");
-            let priority = self.ordering.priority(&tx.transaction, base_fee);
+            let priority = self.ordering.valid_transaction_priority(&tx, base_fee);
             let tx_id = *tx.id();
             let transaction = PendingTransaction { submission_id, transaction: tx, priority };
             if best.ancestor(&tx_id).is_none() {
@@ -240,7 +240,7 @@ impl<T: TransactionOrdering> PendingPool<T> {
                 }
             } else {
                 // Re-insert the transaction with new priority.
-                tx.priority = self.ordering.priority(&tx.transaction.transaction, base_fee);
+                tx.priority = self.ordering.valid_transaction_priority(&tx.transaction, base_fee);
 
                 self.size_of += tx.transaction.size();
                 self.update_independents_and_highest_nonces(&tx);
@@ -298,7 +298,7 @@ impl<T: TransactionOrdering> PendingPool<T> {
         let tx_id = *tx.id();
 
         let submission_id = self.next_id();
-        let priority = self.ordering.priority(&tx.transaction, base_fee);
+        let priority = self.ordering.valid_transaction_priority(&tx, base_fee);
         let tx = PendingTransaction { submission_id, transaction: tx, priority };
 
         self.update_independents_and_highest_nonces(&tx);
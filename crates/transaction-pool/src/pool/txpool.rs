@@ -1,13 +1,16 @@
 //! The internal transaction pool implementation.
 
 use crate::{
-    config::{LocalTransactionConfig, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER},
+    config::{
+        LocalTransactionConfig, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+        TXPOOL_MAX_BLOB_ACCOUNT_SLOTS_PER_SENDER,
+    },
     error::{Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError, PoolErrorKind},
     identifier::{SenderId, TransactionId},
     metrics::{AllTransactionsMetrics, TxPoolMetrics},
     pool::{
-        best::BestTransactions,
-        blob::BlobTransactions,
+        best::{BestTransactionFilter, BestTransactions},
+        blob::{blob_tx_fee_jumps, BlobTransactions},
         parked::{BasefeeOrd, ParkedPool, QueuedOrd},
         pending::PendingPool,
         state::{SubPool, TxState},
@@ -22,7 +25,7 @@ use alloy_consensus::constants::{
     EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, EIP7702_TX_TYPE_ID,
     LEGACY_TX_TYPE_ID,
 };
-use alloy_primitives::{Address, TxHash, B256};
+use alloy_primitives::{Address, BlockHash, TxHash, B256};
 use reth_primitives::constants::{
     eip4844::BLOB_TX_MIN_BLOB_GASPRICE, ETHEREUM_BLOCK_GAS_LIMIT, MIN_PROTOCOL_BASE_FEE,
 };
@@ -75,6 +78,9 @@ pub struct TxPool<T: TransactionOrdering> {
     all_transactions: AllTransactions<T::Transaction>,
     /// Transaction pool metrics
     metrics: TxPoolMetrics,
+    /// The block whose state the known accounts of conditional transactions were last checked
+    /// against.
+    known_accounts_checked_at: Option<BlockHash>,
 }
 
 // === impl TxPool ===
@@ -91,6 +97,7 @@ impl<T: TransactionOrdering> TxPool<T> {
             all_transactions: AllTransactions::new(&config),
             config,
             metrics: Default::default(),
+            known_accounts_checked_at: None,
         }
     }
 
@@ -293,6 +300,12 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.pending_pool.best()
     }
 
+    /// Sets the block whose state the known accounts of conditional transactions were last checked
+    /// against.
+    pub(crate) fn set_known_accounts_checked_at(&mut self, block_hash: BlockHash) {
+        self.known_accounts_checked_at = Some(block_hash);
+    }
+
     /// Returns an iterator that yields transactions that are ready to be included in the block with
     /// the given base fee and optional blob fee.
     ///
@@ -303,6 +316,32 @@ impl<T: TransactionOrdering> TxPool<T> {
         &self,
         best_transactions_attributes: BestTransactionsAttributes,
     ) -> Box<dyn crate::traits::BestTransactions<Item = Arc<ValidPoolTransaction<T::Transaction>>>>
+    {
+        let best = self.best_transactions_with_fees(best_transactions_attributes);
+        match best_transactions_attributes.block {
+            Some((parent_hash, block_number, timestamp)) => {
+                // skip conditional transactions that can't be included in this block
+                let known_accounts_checked = self.known_accounts_checked_at == Some(parent_hash);
+                Box::new(BestTransactionFilter::new(
+                    best,
+                    move |tx: &Arc<ValidPoolTransaction<_>>| {
+                        tx.conditional.as_ref().map_or(true, |conditional| {
+                            conditional.matches_block_attributes(block_number, timestamp) &&
+                                (known_accounts_checked || conditional.known_accounts.is_empty())
+                        })
+                    },
+                ))
+            }
+            None => best,
+        }
+    }
+
+    /// Returns the best transactions that satisfy the base fee and blob fee of the given
+    /// attributes.
+    fn best_transactions_with_fees(
+        &self,
+        best_transactions_attributes: BestTransactionsAttributes,
+    ) -> Box<dyn crate::traits::BestTransactions<Item = Arc<ValidPoolTransaction<T::Transaction>>>>
     {
         // First we need to check if the given base fee is different than what's currently being
         // tracked
@@ -460,7 +499,10 @@ impl<T: TransactionOrdering> TxPool<T> {
             }
         }
 
-        let UpdateOutcome { promoted, discarded } = self.update_accounts(changed_senders);
+        let UpdateOutcome { promoted, mut discarded } = self.update_accounts(changed_senders);
+
+        // the fees may have moved away from parked blob transactions
+        discarded.extend(self.evict_distant_blob_transactions());
 
         self.update_transaction_type_metrics();
         self.metrics.performed_state_updates.increment(1);
@@ -596,6 +638,17 @@ impl<T: TransactionOrdering> TxPool<T> {
                             PoolErrorKind::SpammerExceededCapacity(transaction.sender()),
                         ))
                     }
+                    InsertErr::ExceededSenderBlobTransactionsCapacity { transaction } => {
+                        Err(PoolError::new(
+                            *transaction.hash(),
+                            PoolErrorKind::InvalidTransaction(
+                                Eip4844PoolTransactionError::ExceededSenderBlobCapacity(
+                                    transaction.sender(),
+                                )
+                                .into(),
+                            ),
+                        ))
+                    }
                     InsertErr::TxGasLimitMoreThanAvailableBlockGas {
                         transaction,
                         block_gas_limit,
@@ -615,6 +668,10 @@ impl<T: TransactionOrdering> TxPool<T> {
                             Eip4844PoolTransactionError::Eip4844NonceGap.into(),
                         ),
                     )),
+                    InsertErr::BlobTxExceedsMaxFeeJumps { transaction } => Err(PoolError::new(
+                        *transaction.hash(),
+                        PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::Underpriced),
+                    )),
                     InsertErr::Overdraft { transaction } => Err(PoolError::new(
                         *transaction.hash(),
                         PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::Overdraft {
@@ -681,13 +738,29 @@ impl<T: TransactionOrdering> TxPool<T> {
     /// Removes and returns all matching transactions from the pool.
     ///
     /// Note: this does not advance any descendants of the removed transactions and does not apply
-    /// any additional updates.
+    /// any additional updates. The only exception are blob transactions: because blob transactions
+    /// are always gapless, the descendants of a removed blob transaction are removed as well.
     pub(crate) fn remove_transactions(
         &mut self,
         hashes: Vec<TxHash>,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        let txs =
-            hashes.into_iter().filter_map(|hash| self.remove_transaction_by_hash(&hash)).collect();
+        let mut txs = Vec::new();
+        for hash in hashes {
+            let Some(tx) = self.all_transactions.by_hash.get(&hash) else { continue };
+            if tx.is_eip4844() {
+                // remove the descendants first, starting with the highest nonce, so the sub-pools
+                // never see a nonce gap
+                let descendants = self
+                    .all_transactions
+                    .descendant_txs_exclusive(tx.id())
+                    .map(|(id, _)| *id)
+                    .collect::<Vec<_>>();
+                for id in descendants.into_iter().rev() {
+                    txs.extend(self.remove_transaction(&id));
+                }
+            }
+            txs.extend(self.remove_transaction_by_hash(&hash));
+        }
         self.update_size_metrics();
         txs
     }
@@ -936,6 +1009,37 @@ impl<T: TransactionOrdering> TxPool<T> {
         removed
     }
 
+    /// Evicts all parked blob transactions (and their descendants) whose fee caps are further
+    /// away from the current fees than the configured
+    /// [`BlobPoolConfig::max_fee_jumps`](crate::BlobPoolConfig::max_fee_jumps).
+    ///
+    /// This returns all transactions that were removed from the entire pool.
+    fn evict_distant_blob_transactions(
+        &mut self,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let Some(max_fee_jumps) = self.config.blob_pool.max_fee_jumps else { return Vec::new() };
+
+        let evicted = self
+            .blob_pool
+            .truncate_by_fee_jumps(&self.all_transactions.pending_fees, max_fee_jumps);
+
+        // 1. remove all evicted transactions from the total set
+        let mut removed = Vec::with_capacity(evicted.len());
+        for tx in evicted {
+            self.all_transactions.remove_transaction(tx.id());
+            trace!(target: "txpool", hash=%tx.hash(), "Evicted distant blob transaction");
+            removed.push(tx);
+        }
+
+        // 2. keep the blob pool gapless by removing the descendants that are still in the pool
+        let evicted_ids = removed.iter().map(|tx| *tx.id()).collect::<Vec<_>>();
+        for id in evicted_ids {
+            self.remove_descendants(&id, &mut removed);
+        }
+
+        removed
+    }
+
     /// Number of transactions in the entire pool
     pub(crate) fn len(&self) -> usize {
         self.all_transactions.len()
@@ -1017,6 +1121,10 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     block_gas_limit: u64,
     /// Max number of executable transaction slots guaranteed per account
     max_account_slots: usize,
+    /// Max number of blob transactions per account
+    max_blob_account_slots: usize,
+    /// Max number of fee jumps new blob transactions may be away from the current fees
+    max_blob_fee_jumps: Option<u64>,
     /// _All_ transactions identified by their hash.
     by_hash: HashMap<TxHash, Arc<ValidPoolTransaction<T>>>,
     /// _All_ transaction in the pool sorted by their sender and nonce pair.
@@ -1042,6 +1150,8 @@ impl<T: PoolTransaction> AllTransactions<T> {
     fn new(config: &PoolConfig) -> Self {
         Self {
             max_account_slots: config.max_account_slots,
+            max_blob_account_slots: config.blob_pool.max_account_slots,
+            max_blob_fee_jumps: config.blob_pool.max_fee_jumps,
             price_bumps: config.price_bumps,
             local_transactions_config: config.local_transactions_config.clone(),
             minimal_protocol_basefee: config.minimal_protocol_basefee,
@@ -1073,6 +1183,12 @@ impl<T: PoolTransaction> AllTransactions<T> {
         self.txs.get(id)
     }
 
+    /// Returns the sub-pool of the transaction with the given hash, if it is in the pool.
+    pub(crate) fn subpool(&self, tx_hash: &TxHash) -> Option<SubPool> {
+        let id = self.by_hash.get(tx_hash)?.transaction_id;
+        self.txs.get(&id).map(|tx| tx.subpool)
+    }
+
     /// Increments the transaction counter for the sender
     pub(crate) fn tx_inc(&mut self, sender: SenderId) {
         let count = self.tx_counter.entry(sender).or_default();
@@ -1417,7 +1533,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
     ///
     /// This will enforce all additional rules in the context of this pool, such as:
     ///   - Spam protection: reject new non-local transaction from a sender that exhausted its slot
-    ///     capacity.
+    ///     capacity, blob transactions are additionally limited by the blob slot capacity.
     ///   - Gas limit: reject transactions if they exceed a block's maximum gas.
     ///   - Ensures transaction types are not conflicting for the sender: blob vs normal
     ///     transactions are mutually exclusive for the same sender.
@@ -1433,6 +1549,17 @@ impl<T: PoolTransaction> AllTransactions<T> {
                     transaction: Arc::new(transaction),
                 })
             }
+            // blob and non-blob transactions are mutually exclusive, so all of the sender's
+            // transactions are blob transactions if this is one. Replacements don't take up an
+            // additional slot.
+            if transaction.is_eip4844() &&
+                current_txs >= self.max_blob_account_slots &&
+                !self.txs.contains_key(&transaction.transaction_id)
+            {
+                return Err(InsertErr::ExceededSenderBlobTransactionsCapacity {
+                    transaction: Arc::new(transaction),
+                })
+            }
         }
         if transaction.gas_limit() > self.block_gas_limit {
             return Err(InsertErr::TxGasLimitMoreThanAvailableBlockGas {
@@ -1451,6 +1578,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
     }
 
     /// Enforces additional constraints for blob transactions before attempting to insert:
+    ///    - new blob transactions must not be too many fee jumps away from the current fees
     ///    - new blob transactions must not have any nonce gaps
     ///    - blob transactions cannot go into overdraft
     ///    - replacement blob transaction with a higher fee must not shift an already propagated
@@ -1461,6 +1589,14 @@ impl<T: PoolTransaction> AllTransactions<T> {
         on_chain_balance: U256,
         ancestor: Option<TransactionId>,
     ) -> Result<ValidPoolTransaction<T>, InsertErr<T>> {
+        if let Some(max_fee_jumps) = self.max_blob_fee_jumps {
+            if blob_tx_fee_jumps(&new_blob_tx, &self.pending_fees) > max_fee_jumps {
+                return Err(InsertErr::BlobTxExceedsMaxFeeJumps {
+                    transaction: Arc::new(new_blob_tx),
+                })
+            }
+        }
+
         if let Some(ancestor) = ancestor {
             let Some(ancestor_tx) = self.txs.get(&ancestor) else {
                 // ancestor tx is missing, so we can't insert the new blob
@@ -1808,6 +1944,8 @@ impl<T: PoolTransaction> Default for AllTransactions<T> {
     fn default() -> Self {
         Self {
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+            max_blob_account_slots: TXPOOL_MAX_BLOB_ACCOUNT_SLOTS_PER_SENDER,
+            max_blob_fee_jumps: None,
             minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE,
             block_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
             by_hash: Default::default(),
@@ -1852,6 +1990,9 @@ pub(crate) enum InsertErr<T: PoolTransaction> {
     },
     /// Attempted to insert a blob transaction with a nonce gap
     BlobTxHasNonceGap { transaction: Arc<ValidPoolTransaction<T>> },
+    /// Attempted to insert a blob transaction whose fee caps are too many fee jumps below the
+    /// current fees
+    BlobTxExceedsMaxFeeJumps { transaction: Arc<ValidPoolTransaction<T>> },
     /// Attempted to insert a transaction that would overdraft the sender's balance at the time of
     /// insertion.
     Overdraft { transaction: Arc<ValidPoolTransaction<T>> },
@@ -1863,6 +2004,9 @@ pub(crate) enum InsertErr<T: PoolTransaction> {
     ///
     /// The sender can be considered a spammer at this point.
     ExceededSenderTransactionsCapacity { transaction: Arc<ValidPoolTransaction<T>> },
+    /// Attempted to insert a blob transaction that would exceed the sender's blob transaction
+    /// slots.
+    ExceededSenderBlobTransactionsCapacity { transaction: Arc<ValidPoolTransaction<T>> },
     /// Transaction gas limit exceeds block's gas limit
     TxGasLimitMoreThanAvailableBlockGas {
         transaction: Arc<ValidPoolTransaction<T>>,
@@ -1957,7 +2101,7 @@ mod tests {
     use crate::{
         test_utils::{MockOrdering, MockTransaction, MockTransactionFactory, MockTransactionSet},
         traits::TransactionOrigin,
-        SubPoolLimit,
+        BlobPoolConfig, SubPoolLimit,
     };
 
     #[test]
@@ -2680,6 +2824,126 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn rejects_blob_spammer() {
+        let on_chain_balance = U256::MAX;
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let config = PoolConfig {
+            blob_pool: BlobPoolConfig { max_account_slots: 2, ..Default::default() },
+            ..Default::default()
+        };
+        let mut pool = AllTransactions::new(&config);
+
+        let first = MockTransaction::eip4844();
+        let second = first.next();
+        pool.insert_tx(f.validated(first), on_chain_balance, on_chain_nonce).unwrap();
+        pool.insert_tx(f.validated(second.clone()), on_chain_balance, on_chain_nonce).unwrap();
+
+        let err = pool
+            .insert_tx(f.validated(second.next()), on_chain_balance, on_chain_nonce)
+            .unwrap_err();
+        assert!(matches!(err, InsertErr::ExceededSenderBlobTransactionsCapacity { .. }));
+
+        // replacing an existing blob transaction doesn't take up an additional slot
+        let replacement = second
+            .clone()
+            .with_max_fee(second.get_max_fee().unwrap() * 3)
+            .with_priority_fee(second.get_priority_fee().unwrap() * 3)
+            .inc_blob_fee_by(second.max_fee_per_blob_gas().unwrap() * 2)
+            .rng_hash();
+        let InsertOk { replaced_tx, .. } =
+            pool.insert_tx(f.validated(replacement), on_chain_balance, on_chain_nonce).unwrap();
+        assert!(replaced_tx.is_some());
+    }
+
+    #[test]
+    fn default_config_limits_blob_transactions() {
+        let on_chain_balance = U256::MAX;
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = AllTransactions::new(&PoolConfig::default());
+
+        let mut tx = MockTransaction::eip4844();
+        for _ in 0..TXPOOL_MAX_BLOB_ACCOUNT_SLOTS_PER_SENDER {
+            pool.insert_tx(f.validated(tx.clone()), on_chain_balance, on_chain_nonce).unwrap();
+            tx = tx.next();
+        }
+        let err = pool.insert_tx(f.validated(tx), on_chain_balance, on_chain_nonce).unwrap_err();
+        assert!(matches!(err, InsertErr::ExceededSenderBlobTransactionsCapacity { .. }));
+    }
+
+    #[test]
+    fn rejects_blob_tx_exceeding_max_fee_jumps() {
+        let on_chain_balance = U256::MAX;
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let config = PoolConfig {
+            blob_pool: BlobPoolConfig { max_fee_jumps: Some(4), ..Default::default() },
+            ..Default::default()
+        };
+        let mut pool = AllTransactions::new(&config);
+        pool.pending_fees.blob_fee = 1 << 40;
+
+        let tx = MockTransaction::eip4844();
+        let err = pool.insert_tx(f.validated(tx), on_chain_balance, on_chain_nonce).unwrap_err();
+        assert!(matches!(err, InsertErr::BlobTxExceedsMaxFeeJumps { .. }));
+
+        let tx = MockTransaction::eip4844().with_blob_fee(1 << 39);
+        pool.insert_tx(f.validated(tx), on_chain_balance, on_chain_nonce).unwrap();
+    }
+
+    #[test]
+    fn remove_blob_tx_removes_descendants() {
+        let on_chain_balance = U256::MAX;
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+
+        let first = MockTransaction::eip4844();
+        let second = first.next();
+        let third = second.next();
+        for tx in [first, second.clone(), third] {
+            pool.add_transaction(f.validated(tx), on_chain_balance, on_chain_nonce).unwrap();
+        }
+
+        // removing a blob transaction must not leave a nonce gap behind
+        let removed = pool.remove_transactions(vec![*second.hash()]);
+        assert_eq!(removed.len(), 2);
+        assert_eq!(pool.len(), 1);
+        pool.assert_invariants();
+    }
+
+    #[test]
+    fn evict_distant_blob_transactions_on_new_block() {
+        let on_chain_balance = U256::MAX;
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let config = PoolConfig {
+            blob_pool: BlobPoolConfig { max_fee_jumps: Some(4), ..Default::default() },
+            ..Default::default()
+        };
+        let mut pool = TxPool::new(MockOrdering::default(), config);
+
+        let distant = MockTransaction::eip4844();
+        let distant_descendant = distant.next();
+        let close = MockTransaction::eip4844().with_blob_fee(1 << 39);
+        for tx in [distant, distant_descendant, close.clone()] {
+            pool.add_transaction(f.validated(tx), on_chain_balance, on_chain_nonce).unwrap();
+        }
+        assert_eq!(pool.len(), 3);
+
+        let mut block_info = pool.block_info();
+        block_info.pending_blob_fee = Some(1 << 40);
+        pool.set_block_info(block_info);
+        let outcome = pool.on_canonical_state_change(block_info, vec![], Default::default());
+
+        assert_eq!(outcome.discarded.len(), 2);
+        assert_eq!(pool.len(), 1);
+        assert!(pool.contains(close.hash()));
+        pool.assert_invariants();
+    }
+
     #[test]
     fn reject_tx_over_gas_limit() {
         let on_chain_balance = U256::from(1_000);
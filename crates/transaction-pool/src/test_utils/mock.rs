@@ -4,8 +4,8 @@ use crate::{
     identifier::{SenderIdentifiers, TransactionId},
     pool::txpool::TxPool,
     traits::TransactionOrigin,
-    CoinbaseTipOrdering, EthBlobTransactionSidecar, EthPoolTransaction, PoolTransaction,
-    ValidPoolTransaction,
+    BlobTransactionSidecarVariant, CoinbaseTipOrdering, EthBlobTransactionSidecar,
+    EthPoolTransaction, PoolTransaction, ValidPoolTransaction,
 };
 use alloy_consensus::{
     constants::{EIP1559_TX_TYPE_ID, EIP2930_TX_TYPE_ID, EIP4844_TX_TYPE_ID, LEGACY_TX_TYPE_ID},
@@ -746,7 +746,9 @@ impl PoolTransaction for MockTransaction {
 impl EthPoolTransaction for MockTransaction {
     fn take_blob(&mut self) -> EthBlobTransactionSidecar {
         match self {
-            Self::Eip4844 { sidecar, .. } => EthBlobTransactionSidecar::Present(sidecar.clone()),
+            Self::Eip4844 { sidecar, .. } => {
+                EthBlobTransactionSidecar::Present(sidecar.clone().into())
+            }
             _ => EthBlobTransactionSidecar::None,
         }
     }
@@ -760,7 +762,7 @@ impl EthPoolTransaction for MockTransaction {
 
     fn validate_blob(
         &self,
-        _blob: &BlobTransactionSidecar,
+        _blob: &BlobTransactionSidecarVariant,
         _settings: &reth_primitives::kzg::KzgSettings,
     ) -> Result<(), reth_primitives::BlobTransactionValidationError> {
         match &self {
@@ -1149,6 +1151,7 @@ impl MockTransactionFactory {
             transaction,
             timestamp: Instant::now(),
             origin,
+            conditional: None,
         }
     }
 
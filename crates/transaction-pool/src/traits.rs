@@ -1,9 +1,13 @@
 #![allow(deprecated)]
 
 use crate::{
-    blobstore::BlobStoreError,
+    blobstore::{BlobAndProofV2, BlobStoreError, BlobTransactionSidecarVariant},
+    conditional::TransactionConditional,
     error::PoolResult,
-    pool::{state::SubPool, BestTransactionFilter, TransactionEvents},
+    pool::{
+        state::SubPool, BestTransactionFilter, TransactionEvents, TransactionHistory,
+        TransactionJournalEvents,
+    },
     validate::ValidPoolTransaction,
     AllTransactionsEvents,
 };
@@ -12,7 +16,7 @@ use alloy_consensus::{
     Transaction as _,
 };
 use alloy_eips::{eip2718::Encodable2718, eip2930::AccessList, eip4844::BlobAndProofV1};
-use alloy_primitives::{Address, TxHash, TxKind, B256, U256};
+use alloy_primitives::{Address, BlockHash, TxHash, TxKind, B256, U256};
 use futures_util::{ready, Stream};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_execution_types::ChangedAccount;
@@ -80,6 +84,21 @@ pub trait TransactionPool: Send + Sync + Clone {
         self.add_transactions(TransactionOrigin::External, transactions)
     }
 
+    /// Imports all _external_ transactions received from the given peer.
+    ///
+    /// This is the same as [`TransactionPool::add_external_transactions`], but admission policies
+    /// can tell the peers apart, for example to rate limit each peer separately.
+    ///
+    /// Consumer: P2P
+    fn add_external_transactions_from_peer(
+        &self,
+        peer_id: PeerId,
+        transactions: Vec<Self::Transaction>,
+    ) -> impl Future<Output = Vec<PoolResult<TxHash>>> + Send {
+        let _ = peer_id;
+        self.add_external_transactions(transactions)
+    }
+
     /// Adds an _unvalidated_ transaction into the pool and subscribe to state changes.
     ///
     /// This is the same as [`TransactionPool::add_transaction`] but returns an event stream for the
@@ -101,6 +120,20 @@ pub trait TransactionPool: Send + Sync + Clone {
         transaction: Self::Transaction,
     ) -> impl Future<Output = PoolResult<TxHash>> + Send;
 
+    /// Adds an _unvalidated_ transaction into the pool that may only be included in blocks that
+    /// satisfy the given [`TransactionConditional`].
+    ///
+    /// Conditional transactions are not propagated to peers and are evicted once their block
+    /// bounds are exceeded.
+    ///
+    /// Consumer: RPC
+    fn add_conditional_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+        conditional: TransactionConditional,
+    ) -> impl Future<Output = PoolResult<TxHash>> + Send;
+
     /// Adds the given _unvalidated_ transaction into the pool.
     ///
     /// Returns a list of results.
@@ -120,6 +153,15 @@ pub trait TransactionPool: Send + Sync + Clone {
     /// Returns a new transaction change event stream for _all_ transactions in the pool.
     fn all_transactions_event_listener(&self) -> AllTransactionsEvents<Self::Transaction>;
 
+    /// Returns a new stream that yields all entries recorded by the transaction journal.
+    fn transaction_journal_listener(&self) -> TransactionJournalEvents;
+
+    /// Returns the current status of the transaction and its recorded lifecycle events.
+    ///
+    /// The history only covers recent transactions, see
+    /// [`TransactionJournalConfig`](crate::pool::TransactionJournalConfig).
+    fn transaction_history(&self, tx_hash: TxHash) -> TransactionHistory;
+
     /// Returns a new Stream that yields transactions hashes for new __pending__ transactions
     /// inserted into the pool that are allowed to be propagated.
     ///
@@ -295,6 +337,9 @@ pub trait TransactionPool: Send + Sync + Clone {
 
     /// Removes all transactions corresponding to the given hashes.
     ///
+    /// The descendants of removed blob transactions are removed as well, since blob transactions
+    /// must not have nonce gaps.
+    ///
     /// Consumer: Utility
     fn remove_transactions(
         &self,
@@ -397,6 +442,9 @@ pub trait TransactionPool: Send + Sync + Clone {
         self.get_transactions_by_origin(TransactionOrigin::Local)
     }
 
+    /// Returns all transactions that were submitted with a [`TransactionConditional`].
+    fn get_conditional_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
+
     /// Returns all transactions that where submitted as [TransactionOrigin::Private]
     fn get_private_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
         self.get_transactions_by_origin(TransactionOrigin::Private)
@@ -431,6 +479,8 @@ pub trait TransactionPool: Send + Sync + Clone {
 
     /// Returns the [BlobTransactionSidecar] for the given transaction hash if it exists in the blob
     /// store.
+    ///
+    /// Stored sidecars with cell proofs are converted by computing their blob proofs.
     fn get_blob(&self, tx_hash: TxHash) -> Result<Option<BlobTransactionSidecar>, BlobStoreError>;
 
     /// Returns all [BlobTransactionSidecar] for the given transaction hashes if they exists in the
@@ -457,6 +507,14 @@ pub trait TransactionPool: Send + Sync + Clone {
         &self,
         versioned_hashes: &[B256],
     ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError>;
+
+    /// Return the blobs and their cell proofs for a list of blob versioned hashes.
+    ///
+    /// Only blobs of sidecars with cell proofs are returned, see [`BlobTransactionSidecarVariant`].
+    fn get_blobs_for_versioned_hashes_v2(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV2>>, BlobStoreError>;
 }
 
 /// Extension for [TransactionPool] trait that allows to set the current block info.
@@ -493,6 +551,19 @@ pub trait TransactionPoolExt: TransactionPool {
 
     /// Maintenance function to cleanup blobs that are no longer needed.
     fn cleanup_blobs(&self);
+
+    /// Deletes the blobs that the blob store kept from a previous run, unless their transaction is
+    /// in the pool.
+    ///
+    /// See [`BlobStore::delete_unclaimed`](crate::blobstore::BlobStore::delete_unclaimed).
+    fn delete_unclaimed_blobs(&self);
+
+    /// Marks the known accounts of all conditional transactions in the pool as checked against
+    /// the state of the given block.
+    ///
+    /// Conditional transactions with known accounts are only yielded by
+    /// [`TransactionPool::best_transactions_with_attributes`] for blocks on top of this block.
+    fn on_conditional_transactions_checked(&self, block_hash: BlockHash);
 }
 
 /// Determines what kind of new transactions should be emitted by a stream of transactions.
@@ -615,7 +686,7 @@ pub struct NewBlobSidecar {
     /// hash of the EIP-4844 transaction.
     pub tx_hash: TxHash,
     /// the blob transaction sidecar.
-    pub sidecar: Arc<BlobTransactionSidecar>,
+    pub sidecar: Arc<BlobTransactionSidecarVariant>,
 }
 
 /// Where the transaction originates from.
@@ -821,6 +892,12 @@ pub struct BestTransactionsAttributes {
     pub basefee: u64,
     /// The blob fee attribute for best transactions.
     pub blob_fee: Option<u64>,
+    /// The parent hash, number and timestamp of the block the transactions are selected for.
+    ///
+    /// If set, conditional transactions whose block bounds don't match are skipped, as well as
+    /// conditional transactions whose known accounts haven't been checked against the state of
+    /// the parent block.
+    pub block: Option<(B256, u64, u64)>,
 }
 
 // === impl BestTransactionsAttributes ===
@@ -828,7 +905,7 @@ pub struct BestTransactionsAttributes {
 impl BestTransactionsAttributes {
     /// Creates a new `BestTransactionsAttributes` with the given basefee and blob fee.
     pub const fn new(basefee: u64, blob_fee: Option<u64>) -> Self {
-        Self { basefee, blob_fee }
+        Self { basefee, blob_fee, block: None }
     }
 
     /// Creates a new `BestTransactionsAttributes` with the given basefee.
@@ -841,6 +918,18 @@ impl BestTransactionsAttributes {
         self.blob_fee = Some(blob_fee);
         self
     }
+
+    /// Sets the parent hash, number and timestamp of the block the transactions are selected
+    /// for.
+    pub const fn with_block(
+        mut self,
+        parent_hash: B256,
+        block_number: u64,
+        timestamp: u64,
+    ) -> Self {
+        self.block = Some((parent_hash, block_number, timestamp));
+        self
+    }
 }
 
 /// Trait for transaction types used inside the pool
@@ -979,7 +1068,7 @@ pub trait EthPoolTransaction:
     /// Validates the blob sidecar of the transaction with the given settings.
     fn validate_blob(
         &self,
-        blob: &BlobTransactionSidecar,
+        blob: &BlobTransactionSidecarVariant,
         settings: &KzgSettings,
     ) -> Result<(), BlobTransactionValidationError>;
 
@@ -1021,12 +1110,12 @@ pub enum EthBlobTransactionSidecar {
     /// without the blob sidecar
     Missing,
     /// The eip-4844 transaction was pulled from the network and still has its blob sidecar
-    Present(BlobTransactionSidecar),
+    Present(BlobTransactionSidecarVariant),
 }
 
 impl EthBlobTransactionSidecar {
     /// Returns the blob sidecar if it is present
-    pub const fn maybe_sidecar(&self) -> Option<&BlobTransactionSidecar> {
+    pub const fn maybe_sidecar(&self) -> Option<&BlobTransactionSidecarVariant> {
         match self {
             Self::Present(sidecar) => Some(sidecar),
             _ => None,
@@ -1075,6 +1164,14 @@ impl EthPooledTransaction {
         Self { transaction, cost, encoded_length, blob_sidecar }
     }
 
+    /// Sets the blob sidecar of an EIP-4844 transaction, e.g. a sidecar with cell proofs.
+    pub fn with_blob_sidecar(mut self, sidecar: impl Into<BlobTransactionSidecarVariant>) -> Self {
+        if self.is_eip4844() {
+            self.blob_sidecar = EthBlobTransactionSidecar::Present(sidecar.into());
+        }
+        self
+    }
+
     /// Return the reference to the underlying transaction.
     pub const fn transaction(&self) -> &TransactionSignedEcRecovered {
         &self.transaction
@@ -1092,7 +1189,7 @@ impl From<PooledTransactionsElementEcRecovered> for EthPooledTransaction {
                 let (tx, blob) = tx.into_parts();
                 let tx = TransactionSignedEcRecovered::from_signed_transaction(tx, signer);
                 let mut pooled = Self::new(tx, encoded_length);
-                pooled.blob_sidecar = EthBlobTransactionSidecar::Present(blob);
+                pooled.blob_sidecar = EthBlobTransactionSidecar::Present(blob.into());
                 pooled
             }
             tx => {
@@ -1242,11 +1339,11 @@ impl EthPoolTransaction for EthPooledTransaction {
 
     fn validate_blob(
         &self,
-        sidecar: &BlobTransactionSidecar,
+        sidecar: &BlobTransactionSidecarVariant,
         settings: &KzgSettings,
     ) -> Result<(), BlobTransactionValidationError> {
         match &self.transaction.transaction {
-            Transaction::Eip4844(tx) => tx.validate_blob(sidecar, settings),
+            Transaction::Eip4844(tx) => sidecar.validate(&tx.blob_versioned_hashes, settings),
             _ => Err(BlobTransactionValidationError::NotBlobTransaction(self.tx_type())),
         }
     }
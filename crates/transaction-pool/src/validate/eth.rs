@@ -104,6 +104,10 @@ where
     fn on_new_head_block(&self, new_tip_block: &SealedBlock) {
         self.inner.on_new_head_block(new_tip_block)
     }
+
+    fn kzg_settings(&self) -> EnvKzgSettings {
+        self.inner.kzg_settings.clone()
+    }
 }
 
 /// A [`TransactionValidator`] implementation that validates ethereum transaction.
@@ -125,6 +129,8 @@ pub(crate) struct EthTransactionValidatorInner<Client, T> {
     eip4844: bool,
     /// Fork indicator whether we are using EIP-7702 type transactions.
     eip7702: bool,
+    /// Fork indicator whether we accept EIP-7594 blob sidecars with cell proofs.
+    eip7594: bool,
     /// The current max gas limit
     block_gas_limit: u64,
     /// Minimum priority fee to enforce for acceptance into the pool.
@@ -424,6 +430,14 @@ where
                     }
                 }
                 EthBlobTransactionSidecar::Present(blob) => {
+                    if blob.is_eip7594() && !self.eip7594 {
+                        return TransactionValidationOutcome::Invalid(
+                            transaction,
+                            InvalidPoolTransactionError::Eip4844(
+                                Eip4844PoolTransactionError::UnexpectedEip7594Sidecar,
+                            ),
+                        )
+                    }
                     // validate the blob
                     if let Err(err) = transaction.validate_blob(&blob, self.kzg_settings.get()) {
                         return TransactionValidationOutcome::Invalid(
@@ -433,6 +447,23 @@ where
                             ),
                         )
                     }
+                    // once sidecars with cell proofs are accepted, blob sidecars are stored with
+                    // cell proofs so that they can be served by `engine_getBlobsV2`
+                    let blob = if self.eip7594 && !blob.is_eip7594() {
+                        match blob.try_into_eip7594() {
+                            Ok(blob) => blob.into(),
+                            Err(err) => {
+                                return TransactionValidationOutcome::Invalid(
+                                    transaction,
+                                    InvalidPoolTransactionError::Eip4844(
+                                        Eip4844PoolTransactionError::InvalidEip4844Blob(err),
+                                    ),
+                                )
+                            }
+                        }
+                    } else {
+                        blob
+                    };
                     // store the extracted blob
                     maybe_blob_sidecar = Some(blob);
                 }
@@ -489,6 +520,8 @@ pub struct EthTransactionValidatorBuilder {
     eip4844: bool,
     /// Whether using EIP-7702 type transactions is allowed
     eip7702: bool,
+    /// Whether EIP-7594 blob sidecars with cell proofs are accepted
+    eip7594: bool,
     /// The current max gas limit
     block_gas_limit: u64,
     /// Minimum priority fee to enforce for acceptance into the pool.
@@ -531,6 +564,9 @@ impl EthTransactionValidatorBuilder {
             eip4844: true,
             eip7702: true,
 
+            // sidecars with cell proofs are not accepted before the PeerDAS fork
+            eip7594: false,
+
             // shanghai is activated by default
             shanghai: true,
 
@@ -617,6 +653,17 @@ impl EthTransactionValidatorBuilder {
         self
     }
 
+    /// Enables the support for EIP-7594 blob sidecars with cell proofs.
+    pub const fn with_eip7594(self) -> Self {
+        self.set_eip7594(true)
+    }
+
+    /// Set the support for EIP-7594 blob sidecars with cell proofs.
+    pub const fn set_eip7594(mut self, eip7594: bool) -> Self {
+        self.eip7594 = eip7594;
+        self
+    }
+
     /// Sets the [`EnvKzgSettings`] to use for validating KZG proofs.
     pub fn kzg_settings(mut self, kzg_settings: EnvKzgSettings) -> Self {
         self.kzg_settings = kzg_settings;
@@ -677,6 +724,7 @@ impl EthTransactionValidatorBuilder {
             eip1559,
             eip4844,
             eip7702,
+            eip7594,
             block_gas_limit,
             minimum_priority_fee,
             kzg_settings,
@@ -699,6 +747,7 @@ impl EthTransactionValidatorBuilder {
             fork_tracker,
             eip4844,
             eip7702,
+            eip7594,
             block_gas_limit,
             minimum_priority_fee,
             blob_store: Box::new(blob_store),
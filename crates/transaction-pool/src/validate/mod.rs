@@ -1,13 +1,16 @@
 //! Transaction validation abstractions.
 
 use crate::{
+    blobstore::BlobTransactionSidecarVariant,
+    conditional::TransactionConditional,
     error::InvalidPoolTransactionError,
     identifier::{SenderId, TransactionId},
     traits::{PoolTransaction, TransactionOrigin},
 };
 use alloy_primitives::{Address, TxHash, B256, U256};
 use futures_util::future::Either;
-use reth_primitives::{BlobTransactionSidecar, SealedBlock, TransactionSignedEcRecovered};
+use reth_primitives::{SealedBlock, TransactionSignedEcRecovered};
+use revm::primitives::EnvKzgSettings;
 use std::{fmt, future::Future, time::Instant};
 
 mod constants;
@@ -98,13 +101,13 @@ pub enum ValidTransaction<T> {
         /// The valid EIP-4844 transaction.
         transaction: T,
         /// The extracted sidecar of that transaction
-        sidecar: BlobTransactionSidecar,
+        sidecar: BlobTransactionSidecarVariant,
     },
 }
 
 impl<T> ValidTransaction<T> {
     /// Creates a new valid transaction with an optional sidecar.
-    pub fn new(transaction: T, sidecar: Option<BlobTransactionSidecar>) -> Self {
+    pub fn new(transaction: T, sidecar: Option<BlobTransactionSidecarVariant>) -> Self {
         if let Some(sidecar) = sidecar {
             Self::ValidWithSidecar { transaction, sidecar }
         } else {
@@ -205,6 +208,13 @@ pub trait TransactionValidator: Send + Sync {
     ///
     /// This can be used to update fork specific values (timestamp).
     fn on_new_head_block(&self, _new_tip_block: &SealedBlock) {}
+
+    /// Returns the KZG settings used to validate blob sidecars.
+    ///
+    /// The pool uses the same settings to derive the proofs of stored blob sidecars.
+    fn kzg_settings(&self) -> EnvKzgSettings {
+        EnvKzgSettings::Default
+    }
 }
 
 impl<A, B> TransactionValidator for Either<A, B>
@@ -241,6 +251,13 @@ where
             Self::Right(v) => v.on_new_head_block(new_tip_block),
         }
     }
+
+    fn kzg_settings(&self) -> EnvKzgSettings {
+        match self {
+            Self::Left(v) => v.kzg_settings(),
+            Self::Right(v) => v.kzg_settings(),
+        }
+    }
 }
 
 /// A valid transaction in the pool.
@@ -260,6 +277,8 @@ pub struct ValidPoolTransaction<T: PoolTransaction> {
     pub timestamp: Instant,
     /// Where this transaction originated from.
     pub origin: TransactionOrigin,
+    /// Conditions that must hold for the transaction to be included in a block.
+    pub conditional: Option<Box<TransactionConditional>>,
 }
 
 // === impl ValidPoolTransaction ===
@@ -392,6 +411,7 @@ impl<T: PoolTransaction> Clone for ValidPoolTransaction<T> {
             propagate: self.propagate,
             timestamp: self.timestamp,
             origin: self.origin,
+            conditional: self.conditional.clone(),
         }
     }
 }
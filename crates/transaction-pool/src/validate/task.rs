@@ -10,6 +10,7 @@ use futures_util::{lock::Mutex, StreamExt};
 use reth_chainspec::ChainSpec;
 use reth_primitives::SealedBlock;
 use reth_tasks::TaskSpawner;
+use revm::primitives::EnvKzgSettings;
 use std::{future::Future, pin::Pin, sync::Arc};
 use tokio::{
     sync,
@@ -208,4 +209,8 @@ where
     fn on_new_head_block(&self, new_tip_block: &SealedBlock) {
         self.validator.on_new_head_block(new_tip_block)
     }
+
+    fn kzg_settings(&self) -> EnvKzgSettings {
+        self.validator.kzg_settings()
+    }
 }
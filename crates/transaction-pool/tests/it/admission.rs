@@ -0,0 +1,72 @@
+use assert_matches::assert_matches;
+use reth_transaction_pool::{
+    error::{InvalidPoolTransactionError, PoolErrorKind},
+    test_utils::{MockTransaction, MockTransactionFactory, TestPoolBuilder},
+    AdmissionConfig, AdmissionError, PoolConfig, PoolTransaction, TransactionOrigin,
+    TransactionPool,
+};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_admission_deny_sender() {
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let denied = mock_tx_factory.create_eip1559().transaction;
+    let allowed = mock_tx_factory.create_eip1559().transaction;
+
+    let txpool = TestPoolBuilder::default().with_config(PoolConfig {
+        admission: AdmissionConfig { deny_senders: vec![denied.sender()], ..Default::default() },
+        ..Default::default()
+    });
+
+    let err =
+        txpool.add_transaction(TransactionOrigin::External, denied.clone()).await.unwrap_err();
+    assert_eq!(err.hash, *denied.hash());
+    assert_matches!(
+        err.kind,
+        PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::Admission(
+            AdmissionError::SenderNotAllowed(sender)
+        )) if sender == denied.sender()
+    );
+    assert!(!err.is_bad_transaction());
+
+    assert!(txpool.add_transaction(TransactionOrigin::External, allowed).await.is_ok());
+    assert_eq!(txpool.len(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_admission_sender_calldata() {
+    let tx = MockTransaction::eip1559().with_input(vec![1u8; 64].into());
+
+    let txpool = TestPoolBuilder::default().with_config(PoolConfig {
+        admission: AdmissionConfig { max_sender_calldata: Some(100), ..Default::default() },
+        ..Default::default()
+    });
+
+    assert!(txpool.add_transaction(TransactionOrigin::External, tx.clone()).await.is_ok());
+
+    // the sender already has 64 bytes of calldata in the pool
+    let err = txpool.add_transaction(TransactionOrigin::External, tx.next()).await.unwrap_err();
+    assert_matches!(
+        err.kind,
+        PoolErrorKind::InvalidTransaction(InvalidPoolTransactionError::Admission(
+            AdmissionError::CalldataLimitExceeded { max: 100, .. }
+        ))
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_admission_sender_calldata_replacement() {
+    let tx = MockTransaction::eip1559().with_input(vec![1u8; 64].into());
+
+    let txpool = TestPoolBuilder::default().with_config(PoolConfig {
+        admission: AdmissionConfig { max_sender_calldata: Some(100), ..Default::default() },
+        ..Default::default()
+    });
+
+    assert!(txpool.add_transaction(TransactionOrigin::External, tx.clone()).await.is_ok());
+
+    // the replaced transaction doesn't count towards the sender's calldata
+    let replacement = tx.inc_price_by(tx.get_gas_price()).rng_hash();
+    assert!(txpool.add_transaction(TransactionOrigin::External, replacement.clone()).await.is_ok());
+    assert_eq!(txpool.len(), 1);
+    assert!(txpool.contains(replacement.hash()));
+}
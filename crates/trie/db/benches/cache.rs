@@ -0,0 +1,84 @@
+#![allow(missing_docs, unreachable_pub)]
+use criterion::{criterion_group, criterion_main, Criterion};
+use reth_db::tables;
+use reth_db_api::{cursor::DbCursorRW, transaction::DbTxMut};
+use reth_provider::{
+    test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+    ProviderFactory,
+};
+use reth_trie::{
+    trie_cursor::{TrieCursor, TrieCursorFactory},
+    BranchNodeCompact, Nibbles, StoredNibbles,
+};
+use reth_trie_db::{CachedTrieCursorFactory, DatabaseTrieCursorFactory, TrieNodeCache};
+use std::thread;
+
+/// Number of account trie nodes walked by every thread.
+const NODES: u32 = 10_000;
+
+/// Benchmarks walking the account trie through a warm [`TrieNodeCache`] from concurrent threads,
+/// compared to walking it without the cache.
+pub fn cached_trie_cursor(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Cached trie cursor");
+
+    let factory = create_test_provider_factory();
+    {
+        let provider = factory.provider_rw().unwrap();
+        let mut cursor = provider.tx_ref().cursor_write::<tables::AccountsTrie>().unwrap();
+        for i in 0..NODES {
+            let node = BranchNodeCompact::new(0b11, 0, 0, vec![], None);
+            cursor.upsert(StoredNibbles(Nibbles::unpack(i.to_be_bytes())), node).unwrap();
+        }
+        drop(cursor);
+        provider.commit().unwrap();
+    }
+
+    let cache = TrieNodeCache::new(usize::MAX);
+    assert_eq!(walk(&factory, Some(&cache)), NODES as usize);
+
+    for threads in [1, 4, 16] {
+        group.bench_function(format!("walk | threads: {threads} | cached"), |b| {
+            b.iter(|| walk_concurrently(&factory, Some(&cache), threads));
+        });
+        group.bench_function(format!("walk | threads: {threads} | uncached"), |b| {
+            b.iter(|| walk_concurrently(&factory, None, threads));
+        });
+    }
+}
+
+fn walk_concurrently(
+    factory: &ProviderFactory<MockNodeTypesWithDB>,
+    cache: Option<&TrieNodeCache>,
+    threads: usize,
+) {
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| walk(factory, cache));
+        }
+    });
+}
+
+/// Walks the account trie and returns the number of nodes.
+fn walk(factory: &ProviderFactory<MockNodeTypesWithDB>, cache: Option<&TrieNodeCache>) -> usize {
+    // the snapshot must be taken before the transaction is opened
+    let snapshot = cache.map(TrieNodeCache::snapshot).unwrap_or_default();
+    let provider = factory.provider().unwrap();
+    let cursor_factory =
+        CachedTrieCursorFactory::new(DatabaseTrieCursorFactory::new(provider.tx_ref()), snapshot);
+    let mut cursor = cursor_factory.account_trie_cursor().unwrap();
+
+    let mut nodes = 0;
+    let mut entry = cursor.seek(Nibbles::default()).unwrap();
+    while entry.is_some() {
+        nodes += 1;
+        entry = cursor.next().unwrap();
+    }
+    nodes
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default();
+    targets = cached_trie_cursor
+}
+criterion_main!(benches);
@@ -0,0 +1,578 @@
+use alloy_primitives::B256;
+use parking_lot::RwLock;
+use reth_storage_errors::db::DatabaseError;
+use reth_trie::{
+    trie_cursor::{TrieCursor, TrieCursorFactory},
+    updates::TrieUpdates,
+    BranchNodeCompact, Nibbles,
+};
+use std::{
+    collections::BTreeMap,
+    fmt, mem,
+    ops::Bound,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Key of a cached seek: the hashed address of the storage trie, or `None` for the account trie,
+/// and the seeked path.
+type CacheKey = (Option<B256>, Nibbles);
+
+/// Trie node entry as returned by [`TrieCursor`]s.
+type TrieNodeEntry = (Nibbles, BranchNodeCompact);
+
+/// In-memory cache of trie nodes read from the database, shared across blocks.
+///
+/// The cache stores the results of cursor seeks: for every seeked path, the first node at or after
+/// that path, or `None` if there is no such node. This allows [`CachedTrieCursor`] to serve
+/// `seek`, `seek_exact` and `next` without touching the database.
+///
+/// Database transactions only observe the cache if no trie write was in progress or committed
+/// since the cache was [snapshotted](Self::snapshot) for them. Writers must wrap trie updates with
+/// [`Self::begin_write`] to keep cached entries consistent with the database.
+///
+/// Cache hits only take a read lock, so concurrent cursors don't contend on them. Entries are
+/// evicted with the CLOCK algorithm, which approximates LRU with a reference bit per entry instead
+/// of reordering the entries on every hit.
+#[derive(Clone)]
+pub struct TrieNodeCache(Arc<RwLock<TrieNodeCacheInner>>);
+
+impl TrieNodeCache {
+    /// Create a new cache holding at most `max_memory` bytes of entries.
+    pub fn new(max_memory: usize) -> Self {
+        Self(Arc::new(RwLock::new(TrieNodeCacheInner::new(max_memory))))
+    }
+
+    /// Take a snapshot of the cache for a database transaction.
+    ///
+    /// The snapshot must be taken _before_ the transaction is opened, so that trie writes
+    /// committed in between are detected and the transaction bypasses the cache.
+    pub fn snapshot(&self) -> TrieNodeCacheSnapshot {
+        let inner = self.0.read();
+        let generation = (inner.writers == 0).then_some(inner.generation);
+        TrieNodeCacheSnapshot { cache: Some(self.clone()), generation }
+    }
+
+    /// Mark the start of a database write that may modify trie tables.
+    ///
+    /// Until the returned guard is finished or dropped, the cache is bypassed by all transactions.
+    /// The guard must only be finished after the write is committed.
+    pub fn begin_write(&self) -> TrieNodeCacheWrite<'_> {
+        let mut inner = self.0.write();
+        inner.writers += 1;
+        inner.generation += 1;
+        TrieNodeCacheWrite { cache: self, finished: false }
+    }
+
+    /// Remove all entries from the cache.
+    ///
+    /// Transactions with snapshots taken before the cache was cleared bypass the cache.
+    pub fn clear(&self) {
+        let mut inner = self.0.write();
+        inner.clear();
+        inner.generation += 1;
+    }
+
+    /// Returns the number of cached entries.
+    pub fn len(&self) -> usize {
+        self.0.read().entries.len()
+    }
+
+    /// Returns `true` if the cache has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.0.read().entries.is_empty()
+    }
+
+    /// Returns the approximate memory used by cached entries, in bytes.
+    pub fn memory_usage(&self) -> usize {
+        self.0.read().memory_usage
+    }
+}
+
+impl fmt::Debug for TrieNodeCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.0.read();
+        f.debug_struct("TrieNodeCache")
+            .field("entries", &inner.entries.len())
+            .field("memory_usage", &inner.memory_usage)
+            .field("max_memory", &inner.max_memory)
+            .field("generation", &inner.generation)
+            .finish()
+    }
+}
+
+/// Guard of a database write that may modify trie tables. See [`TrieNodeCache::begin_write`].
+///
+/// Dropping the guard without [finishing](Self::finish) it clears the cache.
+#[derive(Debug)]
+pub struct TrieNodeCacheWrite<'a> {
+    cache: &'a TrieNodeCache,
+    finished: bool,
+}
+
+impl TrieNodeCacheWrite<'_> {
+    /// Invalidate the cached entries affected by the committed trie updates, and resume caching.
+    pub fn finish<'u>(mut self, updates: impl IntoIterator<Item = &'u TrieUpdates>) {
+        let mut inner = self.cache.0.write();
+        for updates in updates {
+            inner.invalidate(updates);
+        }
+        inner.end_write();
+        self.finished = true;
+    }
+}
+
+impl Drop for TrieNodeCacheWrite<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            let mut inner = self.cache.0.write();
+            inner.clear();
+            inner.end_write();
+        }
+    }
+}
+
+/// Snapshot of a [`TrieNodeCache`] for a single database transaction.
+///
+/// The default snapshot has no cache, and is always bypassed.
+#[derive(Clone, Debug, Default)]
+pub struct TrieNodeCacheSnapshot {
+    cache: Option<TrieNodeCache>,
+    /// Generation of the cache at the time of the snapshot, or `None` if a write was in progress.
+    generation: Option<u64>,
+}
+
+impl TrieNodeCacheSnapshot {
+    /// Returns the cached seek result for the key, if the snapshot is still valid.
+    fn get(&self, key: &CacheKey) -> Option<Option<TrieNodeEntry>> {
+        let (cache, generation) = self.cache.as_ref().zip(self.generation)?;
+        let inner = cache.0.read();
+        if !inner.is_valid(generation) {
+            return None
+        }
+        inner.get(key)
+    }
+
+    /// Caches the seek result for the key, if the snapshot is still valid.
+    fn insert(&self, key: CacheKey, entry: Option<TrieNodeEntry>) {
+        let Some((cache, generation)) = self.cache.as_ref().zip(self.generation) else { return };
+        let mut inner = cache.0.write();
+        if inner.is_valid(generation) {
+            inner.insert(key, entry);
+        }
+    }
+}
+
+/// Cached seek result.
+#[derive(Debug)]
+struct CacheEntry {
+    /// First node at or after the seeked path.
+    node: Option<TrieNodeEntry>,
+    /// Whether the entry was hit since the eviction last passed it.
+    referenced: AtomicBool,
+    /// Approximate memory used by the entry.
+    size: usize,
+}
+
+#[derive(Debug)]
+struct TrieNodeCacheInner {
+    /// Seek results by trie and seeked path.
+    entries: BTreeMap<CacheKey, CacheEntry>,
+    /// Key at which the last eviction stopped, the next eviction continues after it.
+    hand: Option<CacheKey>,
+    /// Approximate memory used by the entries.
+    memory_usage: usize,
+    /// Maximum memory to use for the entries.
+    max_memory: usize,
+    /// Incremented on every write to the trie tables and on every clear.
+    generation: u64,
+    /// Number of writes in progress.
+    writers: usize,
+}
+
+impl TrieNodeCacheInner {
+    const fn new(max_memory: usize) -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            hand: None,
+            memory_usage: 0,
+            max_memory,
+            generation: 0,
+            writers: 0,
+        }
+    }
+
+    const fn is_valid(&self, generation: u64) -> bool {
+        self.writers == 0 && self.generation == generation
+    }
+
+    fn get(&self, key: &CacheKey) -> Option<Option<TrieNodeEntry>> {
+        let entry = self.entries.get(key)?;
+        entry.referenced.store(true, Ordering::Relaxed);
+        Some(entry.node.clone())
+    }
+
+    fn insert(&mut self, key: CacheKey, node: Option<TrieNodeEntry>) {
+        let size = mem::size_of::<CacheKey>() +
+            mem::size_of::<CacheEntry>() +
+            node.as_ref().map_or(0, |(_, node)| node.hashes.len() * mem::size_of::<B256>());
+        let entry = CacheEntry { node, referenced: AtomicBool::new(false), size };
+        if let Some(previous) = self.entries.insert(key, entry) {
+            self.memory_usage -= previous.size;
+        }
+        self.memory_usage += size;
+        self.evict();
+    }
+
+    /// Evict entries until the memory limit is met.
+    ///
+    /// The entries are swept in key order, wrapping around, starting after the [`Self::hand`].
+    /// Referenced entries get their reference bit cleared and are skipped, so they are only
+    /// evicted if they aren't hit again until the next sweep.
+    fn evict(&mut self) {
+        while self.memory_usage > self.max_memory {
+            let next = self
+                .hand
+                .as_ref()
+                .and_then(|hand| {
+                    self.entries.range((Bound::Excluded(hand), Bound::Unbounded)).next()
+                })
+                .or_else(|| self.entries.iter().next());
+            let Some((key, entry)) = next else { break };
+            let key = key.clone();
+            if !entry.referenced.swap(false, Ordering::Relaxed) {
+                self.remove(&key);
+            }
+            self.hand = Some(key);
+        }
+    }
+
+    fn remove(&mut self, key: &CacheKey) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.memory_usage -= entry.size;
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.hand = None;
+        self.memory_usage = 0;
+    }
+
+    fn end_write(&mut self) {
+        self.writers -= 1;
+        self.generation += 1;
+    }
+
+    /// Remove the entries affected by the trie updates.
+    fn invalidate(&mut self, updates: &TrieUpdates) {
+        for path in updates.account_nodes_ref().keys().chain(updates.removed_nodes_ref()) {
+            self.invalidate_path(None, path);
+        }
+
+        for (hashed_address, storage_updates) in updates.storage_tries_ref() {
+            if storage_updates.is_deleted() {
+                self.invalidate_trie(*hashed_address);
+            }
+            for path in storage_updates
+                .storage_nodes_ref()
+                .keys()
+                .chain(storage_updates.removed_nodes_ref())
+            {
+                self.invalidate_path(Some(*hashed_address), path);
+            }
+        }
+    }
+
+    /// Remove the entries whose seek result may change if the node at the path is inserted,
+    /// updated or removed.
+    ///
+    /// These are the entries seeking at or before the path, and resulting in a node at or after
+    /// the path. Since seek results are monotonic, they are the entries preceding the path up to
+    /// the first one resulting in a node before the path.
+    fn invalidate_path(&mut self, hashed_address: Option<B256>, path: &Nibbles) {
+        let invalidated = self
+            .entries
+            .range(..=(hashed_address, path.clone()))
+            .rev()
+            .take_while(|((address, _), entry)| {
+                *address == hashed_address &&
+                    entry.node.as_ref().map_or(true, |(nibbles, _)| nibbles >= path)
+            })
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        for key in invalidated {
+            self.remove(&key);
+        }
+    }
+
+    /// Remove all entries of the storage trie.
+    fn invalidate_trie(&mut self, hashed_address: B256) {
+        let invalidated = self
+            .entries
+            .range((Some(hashed_address), Nibbles::default())..)
+            .take_while(|((address, _), _)| *address == Some(hashed_address))
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        for key in invalidated {
+            self.remove(&key);
+        }
+    }
+}
+
+/// Trie cursor factory serving trie nodes from a [`TrieNodeCache`] before falling back to the
+/// underlying cursor factory.
+#[derive(Debug, Clone)]
+pub struct CachedTrieCursorFactory<CF> {
+    /// Underlying trie cursor factory.
+    cursor_factory: CF,
+    /// Snapshot of the cache for the transaction of the underlying cursor factory.
+    cache: TrieNodeCacheSnapshot,
+}
+
+impl<CF> CachedTrieCursorFactory<CF> {
+    /// Create a new cached trie cursor factory.
+    pub const fn new(cursor_factory: CF, cache: TrieNodeCacheSnapshot) -> Self {
+        Self { cursor_factory, cache }
+    }
+}
+
+impl<CF: TrieCursorFactory> TrieCursorFactory for CachedTrieCursorFactory<CF> {
+    type AccountTrieCursor = CachedTrieCursor<CF::AccountTrieCursor>;
+    type StorageTrieCursor = CachedTrieCursor<CF::StorageTrieCursor>;
+
+    fn account_trie_cursor(&self) -> Result<Self::AccountTrieCursor, DatabaseError> {
+        let cursor = self.cursor_factory.account_trie_cursor()?;
+        Ok(CachedTrieCursor::new(cursor, self.cache.clone(), None))
+    }
+
+    fn storage_trie_cursor(
+        &self,
+        hashed_address: B256,
+    ) -> Result<Self::StorageTrieCursor, DatabaseError> {
+        let cursor = self.cursor_factory.storage_trie_cursor(hashed_address)?;
+        Ok(CachedTrieCursor::new(cursor, self.cache.clone(), Some(hashed_address)))
+    }
+}
+
+/// Trie cursor serving trie nodes from a [`TrieNodeCache`] before falling back to the underlying
+/// cursor.
+///
+/// Every operation is translated into a seek, so the underlying cursor is only positioned on
+/// cache misses.
+#[derive(Debug)]
+pub struct CachedTrieCursor<C> {
+    /// The underlying cursor.
+    cursor: C,
+    /// Snapshot of the cache.
+    cache: TrieNodeCacheSnapshot,
+    /// The hashed address of the storage trie, or `None` for the account trie.
+    hashed_address: Option<B256>,
+    /// Last key returned by the cursor.
+    last_key: Option<Nibbles>,
+}
+
+impl<C> CachedTrieCursor<C> {
+    /// Create a new cached trie cursor.
+    pub const fn new(
+        cursor: C,
+        cache: TrieNodeCacheSnapshot,
+        hashed_address: Option<B256>,
+    ) -> Self {
+        Self { cursor, cache, hashed_address, last_key: None }
+    }
+}
+
+impl<C: TrieCursor> CachedTrieCursor<C> {
+    fn seek_inner(&mut self, key: Nibbles) -> Result<Option<TrieNodeEntry>, DatabaseError> {
+        let cache_key = (self.hashed_address, key);
+        if let Some(entry) = self.cache.get(&cache_key) {
+            return Ok(entry)
+        }
+
+        let entry = self.cursor.seek(cache_key.1.clone())?;
+        self.cache.insert(cache_key, entry.clone());
+        Ok(entry)
+    }
+}
+
+impl<C: TrieCursor> TrieCursor for CachedTrieCursor<C> {
+    fn seek_exact(
+        &mut self,
+        key: Nibbles,
+    ) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        let entry = self.seek_inner(key.clone())?.filter(|(nibbles, _)| nibbles == &key);
+        self.last_key = entry.as_ref().map(|(nibbles, _)| nibbles.clone());
+        Ok(entry)
+    }
+
+    fn seek(
+        &mut self,
+        key: Nibbles,
+    ) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        let entry = self.seek_inner(key)?;
+        self.last_key = entry.as_ref().map(|(nibbles, _)| nibbles.clone());
+        Ok(entry)
+    }
+
+    fn next(&mut self) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        let Some(mut key) = self.last_key.take() else {
+            // no previous entry was found
+            return Ok(None)
+        };
+
+        // The smallest path after the last key is the last key followed by a zero nibble.
+        key.push(0);
+        let entry = self.seek_inner(key)?;
+        self.last_key = entry.as_ref().map(|(nibbles, _)| nibbles.clone());
+        Ok(entry)
+    }
+
+    fn current(&mut self) -> Result<Option<Nibbles>, DatabaseError> {
+        match &self.last_key {
+            Some(key) => Ok(Some(key.clone())),
+            None => self.cursor.current(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DatabaseTrieCursorFactory;
+    use reth_db::tables;
+    use reth_db_api::{cursor::DbCursorRW, transaction::DbTxMut};
+    use reth_provider::test_utils::create_test_provider_factory;
+    use reth_trie::{updates::StorageTrieUpdates, StoredNibbles};
+
+    fn node() -> BranchNodeCompact {
+        BranchNodeCompact::new(0b11, 0, 0, vec![], None)
+    }
+
+    #[test]
+    fn serves_cursor_from_cache() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        {
+            let mut cursor = provider.tx_ref().cursor_write::<tables::AccountsTrie>().unwrap();
+            for key in [vec![0x1], vec![0x1, 0x2], vec![0x3]] {
+                cursor.upsert(StoredNibbles(Nibbles::from_nibbles(key)), node()).unwrap();
+            }
+        }
+
+        let cache = TrieNodeCache::new(usize::MAX);
+        let cursor_factory = CachedTrieCursorFactory::new(
+            DatabaseTrieCursorFactory::new(provider.tx_ref()),
+            cache.snapshot(),
+        );
+
+        let walk = |cursor: &mut CachedTrieCursor<_>| {
+            let mut keys = vec![cursor.seek(Nibbles::default()).unwrap().unwrap().0];
+            while let Some((key, _)) = cursor.next().unwrap() {
+                keys.push(key);
+            }
+            keys
+        };
+        let expected = vec![
+            Nibbles::from_nibbles([0x1]),
+            Nibbles::from_nibbles([0x1, 0x2]),
+            Nibbles::from_nibbles([0x3]),
+        ];
+        {
+            let mut cursor = cursor_factory.account_trie_cursor().unwrap();
+            assert_eq!(walk(&mut cursor), expected);
+            assert_eq!(cache.len(), 4);
+        }
+
+        // Entries are now served from the cache, even after being removed from the database.
+        provider.tx_ref().clear::<tables::AccountsTrie>().unwrap();
+        let mut cursor = cursor_factory.account_trie_cursor().unwrap();
+        assert_eq!(walk(&mut cursor), expected);
+        assert_eq!(
+            cursor.seek(Nibbles::from_nibbles([0x1, 0x0])).unwrap().map(|(key, _)| key),
+            Some(Nibbles::from_nibbles([0x1, 0x2]))
+        );
+        assert_eq!(cursor.seek_exact(Nibbles::from_nibbles([0x1, 0x0])).unwrap(), None);
+    }
+
+    #[test]
+    fn invalidates_seeks_around_updated_path() {
+        let hashed_address = B256::with_last_byte(1);
+        let cache = TrieNodeCache::new(usize::MAX);
+        let snapshot = cache.snapshot();
+        for (seek, found) in [
+            (vec![], Some(vec![0x1])),
+            (vec![0x1, 0x0], Some(vec![0x3])),
+            (vec![0x2], Some(vec![0x3])),
+            (vec![0x4], None),
+        ] {
+            let found = found.map(|found| (Nibbles::from_nibbles(found), node()));
+            snapshot.insert((Some(hashed_address), Nibbles::from_nibbles(seek)), found);
+        }
+        snapshot.insert((None, Nibbles::from_nibbles([0x2])), None);
+
+        let mut updates = TrieUpdates::default();
+        updates.insert_storage_updates(
+            hashed_address,
+            StorageTrieUpdates::new([(Nibbles::from_nibbles([0x2, 0x5]), node())]),
+        );
+        cache.begin_write().finish([&updates]);
+
+        // Seeks between the previous node and the updated path are invalidated, seeks in other
+        // tries are not.
+        let keys = cache.0.read().entries.keys().cloned().collect::<Vec<_>>();
+        assert_eq!(
+            keys,
+            vec![
+                (None, Nibbles::from_nibbles([0x2])),
+                (Some(hashed_address), Nibbles::default()),
+                (Some(hashed_address), Nibbles::from_nibbles([0x4])),
+            ]
+        );
+
+        // Deleting the storage trie invalidates all of its entries.
+        let mut updates = TrieUpdates::default();
+        updates.insert_storage_updates(hashed_address, StorageTrieUpdates::deleted());
+        cache.begin_write().finish([&updates]);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn bypasses_cache_during_and_after_write() {
+        let cache = TrieNodeCache::new(usize::MAX);
+        let snapshot = cache.snapshot();
+        let key = (None, Nibbles::from_nibbles([0x1]));
+
+        let write = cache.begin_write();
+        assert!(cache.snapshot().generation.is_none());
+        snapshot.insert(key.clone(), None);
+        assert!(cache.is_empty());
+
+        // Dropping the guard without finishing it clears the cache.
+        drop(write);
+        let current = cache.snapshot();
+        current.insert(key.clone(), None);
+        assert_eq!(current.get(&key), Some(None));
+        assert_eq!(snapshot.get(&key), None);
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let entry_size = mem::size_of::<CacheKey>() + mem::size_of::<CacheEntry>();
+        let cache = TrieNodeCache::new(entry_size * 2);
+        let snapshot = cache.snapshot();
+        let key = |nibble| (None, Nibbles::from_nibbles([nibble]));
+
+        snapshot.insert(key(0x1), None);
+        snapshot.insert(key(0x2), None);
+        assert!(snapshot.get(&key(0x1)).is_some());
+        snapshot.insert(key(0x3), None);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.memory_usage(), entry_size * 2);
+        assert!(snapshot.get(&key(0x1)).is_some());
+        assert!(snapshot.get(&key(0x2)).is_none());
+    }
+}
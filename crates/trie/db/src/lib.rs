@@ -1,18 +1,25 @@
 //! An integration of [`reth-trie`] with [`reth-db`].
 
+mod cache;
 mod hashed_cursor;
 mod prefix_set;
 mod proof;
+mod range;
 mod state;
 mod storage;
 mod trie_cursor;
 mod witness;
 
+pub use cache::{
+    CachedTrieCursor, CachedTrieCursorFactory, TrieNodeCache, TrieNodeCacheSnapshot,
+    TrieNodeCacheWrite,
+};
 pub use hashed_cursor::{
     DatabaseHashedAccountCursor, DatabaseHashedCursorFactory, DatabaseHashedStorageCursor,
 };
 pub use prefix_set::PrefixSetLoader;
 pub use proof::{DatabaseProof, DatabaseStorageProof};
+pub use range::DatabaseStateRange;
 pub use state::{DatabaseHashedPostState, DatabaseStateRoot};
 pub use storage::{DatabaseHashedStorage, DatabaseStorageRoot};
 pub use trie_cursor::{
@@ -1,4 +1,4 @@
-use alloy_primitives::{keccak256, BlockNumber, B256};
+use alloy_primitives::{keccak256, Address, BlockNumber, B256};
 use derive_more::Deref;
 use reth_db::tables;
 use reth_db_api::{
@@ -28,17 +28,50 @@ impl<'a, TX> PrefixSetLoader<'a, TX> {
 
 impl<TX: DbTx> PrefixSetLoader<'_, TX> {
     /// Load all account and storage changes for the given block range.
+    ///
+    /// The changes are read from the [`tables::AccountChangeSets`] and
+    /// [`tables::StorageChangeSets`] tables, see [`Self::load_changesets`] for changesets that
+    /// are stored elsewhere.
     pub fn load(self, range: RangeInclusive<BlockNumber>) -> Result<TriePrefixSets, DatabaseError> {
+        let mut account_changeset_cursor = self.cursor_read::<tables::AccountChangeSets>()?;
+        let accounts = account_changeset_cursor
+            .walk_range(range.clone())?
+            .map(|entry| entry.map(|(_, AccountBeforeTx { address, .. })| address));
+
+        let mut storage_cursor = self.cursor_dup_read::<tables::StorageChangeSets>()?;
+        let storages = storage_cursor.walk_range(BlockNumberAddress::range(range))?.map(|entry| {
+            entry.map(|(BlockNumberAddress((_, address)), StorageEntry { key, .. })| (address, key))
+        });
+
+        self.load_changes(accounts, storages)
+    }
+
+    /// Load the prefix sets of the given changed accounts and storage slots.
+    ///
+    /// The accounts are the addresses of the account changesets and the storage slots the
+    /// addresses and keys of the storage changesets of a block range.
+    pub fn load_changesets(
+        self,
+        accounts: impl IntoIterator<Item = Address>,
+        storages: impl IntoIterator<Item = (Address, B256)>,
+    ) -> Result<TriePrefixSets, DatabaseError> {
+        self.load_changes(accounts.into_iter().map(Ok), storages.into_iter().map(Ok))
+    }
+
+    fn load_changes(
+        &self,
+        accounts: impl Iterator<Item = Result<Address, DatabaseError>>,
+        storages: impl Iterator<Item = Result<(Address, B256), DatabaseError>>,
+    ) -> Result<TriePrefixSets, DatabaseError> {
         // Initialize prefix sets.
         let mut account_prefix_set = PrefixSetMut::default();
         let mut storage_prefix_sets = HashMap::<B256, PrefixSetMut>::default();
         let mut destroyed_accounts = HashSet::default();
 
         // Walk account changeset and insert account prefixes.
-        let mut account_changeset_cursor = self.cursor_read::<tables::AccountChangeSets>()?;
         let mut account_plain_state_cursor = self.cursor_read::<tables::PlainAccountState>()?;
-        for account_entry in account_changeset_cursor.walk_range(range.clone())? {
-            let (_, AccountBeforeTx { address, .. }) = account_entry?;
+        for address in accounts {
+            let address = address?;
             let hashed_address = keccak256(address);
             account_prefix_set.insert(Nibbles::unpack(hashed_address));
 
@@ -49,10 +82,8 @@ impl<TX: DbTx> PrefixSetLoader<'_, TX> {
 
         // Walk storage changeset and insert storage prefixes as well as account prefixes if missing
         // from the account prefix set.
-        let mut storage_cursor = self.cursor_dup_read::<tables::StorageChangeSets>()?;
-        let storage_range = BlockNumberAddress::range(range);
-        for storage_entry in storage_cursor.walk_range(storage_range)? {
-            let (BlockNumberAddress((_, address)), StorageEntry { key, .. }) = storage_entry?;
+        for storage in storages {
+            let (address, key) = storage?;
             let hashed_address = keccak256(address);
             account_prefix_set.insert(Nibbles::unpack(hashed_address));
             storage_prefix_sets
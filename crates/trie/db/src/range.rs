@@ -0,0 +1,104 @@
+use crate::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory};
+use alloy_primitives::{keccak256, Address, B256};
+use reth_db_api::transaction::DbTx;
+use reth_execution_errors::StateRootError;
+use reth_storage_errors::db::DatabaseError;
+use reth_trie::{
+    hashed_cursor::HashedPostStateCursorFactory,
+    range::{AccountRange, StateRange, StorageRange},
+    trie_cursor::InMemoryTrieCursorFactory,
+    HashedPostState, HashedStorage, TrieInput,
+};
+
+/// Extends [`StateRange`] with operations specific for working with a database transaction.
+pub trait DatabaseStateRange<'a, TX> {
+    /// Create a new [`StateRange`] from database transaction.
+    fn from_tx(tx: &'a TX) -> Self;
+
+    /// Returns a page of accounts of the state represented by the [`TrieInput`] on top of the
+    /// database state.
+    fn overlay_account_range(
+        tx: &'a TX,
+        input: TrieInput,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> Result<AccountRange, StateRootError>;
+
+    /// Returns a page of storage slots of the target account with [`HashedStorage`] applied on top
+    /// of the database state.
+    fn overlay_storage_range(
+        tx: &'a TX,
+        address: Address,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, DatabaseError>;
+
+    /// Returns a page of storage slots of the account with the given hashed address with
+    /// [`HashedStorage`] applied on top of the database state.
+    fn overlay_hashed_storage_range(
+        tx: &'a TX,
+        hashed_address: B256,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, DatabaseError>;
+}
+
+impl<'a, TX: DbTx> DatabaseStateRange<'a, TX>
+    for StateRange<DatabaseTrieCursorFactory<'a, TX>, DatabaseHashedCursorFactory<'a, TX>>
+{
+    fn from_tx(tx: &'a TX) -> Self {
+        Self::new(DatabaseTrieCursorFactory::new(tx), DatabaseHashedCursorFactory::new(tx))
+    }
+
+    fn overlay_account_range(
+        tx: &'a TX,
+        input: TrieInput,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> Result<AccountRange, StateRootError> {
+        let nodes_sorted = input.nodes.into_sorted();
+        let state_sorted = input.state.into_sorted();
+        Self::from_tx(tx)
+            .with_trie_cursor_factory(InMemoryTrieCursorFactory::new(
+                DatabaseTrieCursorFactory::new(tx),
+                &nodes_sorted,
+            ))
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(
+                DatabaseHashedCursorFactory::new(tx),
+                &state_sorted,
+            ))
+            .with_prefix_sets(input.prefix_sets.freeze())
+            .account_range(start, limit, storage_limit)
+    }
+
+    fn overlay_storage_range(
+        tx: &'a TX,
+        address: Address,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, DatabaseError> {
+        Self::overlay_hashed_storage_range(tx, keccak256(address), hashed_storage, start, limit)
+    }
+
+    fn overlay_hashed_storage_range(
+        tx: &'a TX,
+        hashed_address: B256,
+        hashed_storage: HashedStorage,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, DatabaseError> {
+        let state_sorted =
+            HashedPostState::from_hashed_storage(hashed_address, hashed_storage).into_sorted();
+        Self::from_tx(tx)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(
+                DatabaseHashedCursorFactory::new(tx),
+                &state_sorted,
+            ))
+            .storage_range(hashed_address, start, limit)
+    }
+}
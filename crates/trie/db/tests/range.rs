@@ -0,0 +1,124 @@
+#![allow(missing_docs)]
+
+use alloy_primitives::{keccak256, Address, B256, U256};
+use reth_primitives::{Account, StorageEntry};
+use reth_provider::{test_utils::create_test_provider_factory, HashingWriter};
+use reth_trie::{range::StateRange, HashedPostState, HashedStorage, StorageRoot, TrieInput};
+use reth_trie_db::{DatabaseStateRange, DatabaseStorageRoot};
+
+#[test]
+fn account_range_with_overlay() {
+    let factory = create_test_provider_factory();
+    let provider = factory.provider_rw().unwrap();
+
+    let addresses = (0..4).map(|_| Address::random()).collect::<Vec<_>>();
+    provider
+        .insert_account_for_hashing(
+            addresses.iter().map(|address| (*address, Some(Account::default()))),
+        )
+        .unwrap();
+    provider
+        .insert_storage_for_hashing(addresses.iter().map(|address| {
+            (*address, [StorageEntry { key: B256::with_last_byte(1), value: U256::from(1) }])
+        }))
+        .unwrap();
+
+    let mut hashed_addresses = addresses.iter().map(keccak256).collect::<Vec<_>>();
+    hashed_addresses.sort();
+
+    // database only
+    let range =
+        StateRange::from_tx(provider.tx_ref()).account_range(B256::ZERO, 3, usize::MAX).unwrap();
+    assert_eq!(
+        range.accounts.iter().map(|entry| entry.hashed_address).collect::<Vec<_>>(),
+        hashed_addresses[..3]
+    );
+    assert_eq!(range.next_key, Some(hashed_addresses[3]));
+    for entry in &range.accounts {
+        assert_eq!(
+            entry.storage_root,
+            StorageRoot::from_tx_hashed(provider.tx_ref(), entry.hashed_address).root().unwrap()
+        );
+        assert_eq!(entry.storage, vec![(keccak256(B256::with_last_byte(1)), U256::from(1))]);
+    }
+
+    // destroy the first account and change the storage of the second one
+    let mut state = HashedPostState::default();
+    state.accounts.insert(hashed_addresses[0], None);
+    state.storages.insert(hashed_addresses[0], HashedStorage::new(true));
+    state.storages.insert(
+        hashed_addresses[1],
+        HashedStorage::from_iter(false, [(keccak256(B256::with_last_byte(2)), U256::from(2))]),
+    );
+    let range = StateRange::overlay_account_range(
+        provider.tx_ref(),
+        TrieInput::from_state(state),
+        B256::ZERO,
+        usize::MAX,
+        0,
+    )
+    .unwrap();
+    assert_eq!(
+        range.accounts.iter().map(|entry| entry.hashed_address).collect::<Vec<_>>(),
+        hashed_addresses[1..]
+    );
+    assert_eq!(range.next_key, None);
+    assert_ne!(range.accounts[0].storage_root, range.accounts[1].storage_root);
+    assert!(range.accounts.iter().all(|entry| entry.storage.is_empty()));
+}
+
+#[test]
+fn storage_range_with_overlay() {
+    let factory = create_test_provider_factory();
+    let provider = factory.provider_rw().unwrap();
+
+    let address = Address::random();
+    let slots = (1..=4u8).map(B256::with_last_byte).collect::<Vec<_>>();
+    provider
+        .insert_storage_for_hashing([(
+            address,
+            slots.iter().map(|slot| StorageEntry { key: *slot, value: U256::from(1) }),
+        )])
+        .unwrap();
+
+    let mut hashed_slots = slots.iter().map(keccak256).collect::<Vec<_>>();
+    hashed_slots.sort();
+
+    // clear the first slot in the overlay
+    let overlay = HashedStorage::from_iter(false, [(hashed_slots[0], U256::ZERO)]);
+    let range = StateRange::overlay_storage_range(
+        provider.tx_ref(),
+        address,
+        overlay.clone(),
+        B256::ZERO,
+        2,
+    )
+    .unwrap();
+    assert_eq!(
+        range.slots,
+        vec![(hashed_slots[1], U256::from(1)), (hashed_slots[2], U256::from(1))]
+    );
+    assert_eq!(range.next_key, Some(hashed_slots[3]));
+
+    let range = StateRange::overlay_storage_range(
+        provider.tx_ref(),
+        address,
+        overlay,
+        range.next_key.unwrap(),
+        2,
+    )
+    .unwrap();
+    assert_eq!(range.slots, vec![(hashed_slots[3], U256::from(1))]);
+    assert_eq!(range.next_key, None);
+
+    // wiped storage has no slots
+    let range = StateRange::overlay_storage_range(
+        provider.tx_ref(),
+        address,
+        HashedStorage::new(true),
+        B256::ZERO,
+        2,
+    )
+    .unwrap();
+    assert!(range.slots.is_empty());
+}
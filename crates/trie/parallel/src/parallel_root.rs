@@ -16,7 +16,9 @@ use reth_trie::{
     walker::TrieWalker,
     HashBuilder, Nibbles, StorageRoot, TrieAccount, TrieInput,
 };
-use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory};
+use reth_trie_db::{
+    CachedTrieCursorFactory, DatabaseHashedCursorFactory, DatabaseTrieCursorFactory, TrieNodeCache,
+};
 use std::{collections::HashMap, sync::Arc};
 use thiserror::Error;
 use tracing::*;
@@ -38,6 +40,8 @@ pub struct ParallelStateRoot<Factory> {
     view: ConsistentDbView<Factory>,
     /// Trie input.
     input: TrieInput,
+    /// Optional cache of database trie nodes.
+    trie_node_cache: Option<TrieNodeCache>,
     /// Parallel state root metrics.
     #[cfg(feature = "metrics")]
     metrics: ParallelStateRootMetrics,
@@ -49,10 +53,17 @@ impl<Factory> ParallelStateRoot<Factory> {
         Self {
             view,
             input,
+            trie_node_cache: None,
             #[cfg(feature = "metrics")]
             metrics: ParallelStateRootMetrics::default(),
         }
     }
+
+    /// Set the cache of database trie nodes.
+    pub fn with_trie_node_cache(mut self, trie_node_cache: Option<TrieNodeCache>) -> Self {
+        self.trie_node_cache = trie_node_cache;
+        self
+    }
 }
 
 impl<Factory> ParallelStateRoot<Factory>
@@ -92,6 +103,7 @@ where
             storage_root_targets.into_iter().sorted_unstable_by_key(|(address, _)| *address)
         {
             let view = self.view.clone();
+            let trie_node_cache = self.trie_node_cache.clone();
             let hashed_state_sorted = hashed_state_sorted.clone();
             let trie_nodes_sorted = trie_nodes_sorted.clone();
             #[cfg(feature = "metrics")]
@@ -101,9 +113,14 @@ where
 
             rayon::spawn_fifo(move || {
                 let result = (|| -> Result<_, ParallelStateRootError> {
+                    let trie_node_cache =
+                        trie_node_cache.as_ref().map(TrieNodeCache::snapshot).unwrap_or_default();
                     let provider_ro = view.provider_ro()?;
                     let trie_cursor_factory = InMemoryTrieCursorFactory::new(
-                        DatabaseTrieCursorFactory::new(provider_ro.tx_ref()),
+                        CachedTrieCursorFactory::new(
+                            DatabaseTrieCursorFactory::new(provider_ro.tx_ref()),
+                            trie_node_cache,
+                        ),
                         &trie_nodes_sorted,
                     );
                     let hashed_state = HashedPostStateCursorFactory::new(
@@ -128,9 +145,15 @@ where
         trace!(target: "trie::parallel_state_root", "calculating state root");
         let mut trie_updates = TrieUpdates::default();
 
+        // The cache snapshot must be taken before the database transaction is opened.
+        let trie_node_cache =
+            self.trie_node_cache.as_ref().map(TrieNodeCache::snapshot).unwrap_or_default();
         let provider_ro = self.view.provider_ro()?;
         let trie_cursor_factory = InMemoryTrieCursorFactory::new(
-            DatabaseTrieCursorFactory::new(provider_ro.tx_ref()),
+            CachedTrieCursorFactory::new(
+                DatabaseTrieCursorFactory::new(provider_ro.tx_ref()),
+                trie_node_cache,
+            ),
             &trie_nodes_sorted,
         );
         let hashed_cursor_factory = HashedPostStateCursorFactory::new(
@@ -4,7 +4,7 @@ use reth_primitives::Account;
 use reth_storage_errors::db::DatabaseError;
 
 /// Noop hashed cursor factory.
-#[derive(Default, Debug)]
+#[derive(Clone, Default, Debug)]
 #[non_exhaustive]
 pub struct NoopHashedCursorFactory;
 
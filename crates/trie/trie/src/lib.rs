@@ -46,6 +46,9 @@ pub mod proof;
 /// Trie witness generation.
 pub mod witness;
 
+/// Paginated iteration over the hashed state.
+pub mod range;
+
 /// The implementation of the Merkle Patricia Trie.
 mod trie;
 pub use trie::{StateRoot, StorageRoot};
@@ -0,0 +1,297 @@
+use crate::{
+    hashed_cursor::{HashedCursor, HashedCursorFactory},
+    prefix_set::TriePrefixSets,
+    trie_cursor::TrieCursorFactory,
+    StorageRoot,
+};
+use alloy_primitives::{B256, U256};
+use reth_execution_errors::StateRootError;
+use reth_primitives::Account;
+use reth_storage_errors::db::DatabaseError;
+
+#[cfg(feature = "metrics")]
+use crate::metrics::{TrieRootMetrics, TrieType};
+
+/// A page of hashed accounts returned by [`StateRange::account_range`].
+#[derive(PartialEq, Eq, Clone, Default, Debug)]
+pub struct AccountRange {
+    /// Accounts in ascending order of their hashed address.
+    pub accounts: Vec<AccountRangeEntry>,
+    /// Hashed address of the first account that did not fit into the page, if any.
+    pub next_key: Option<B256>,
+}
+
+/// Account entry of an [`AccountRange`].
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct AccountRangeEntry {
+    /// The hashed address of the account.
+    pub hashed_address: B256,
+    /// The account info.
+    pub account: Account,
+    /// The storage root of the account.
+    pub storage_root: B256,
+    /// Hashed storage slots of the account in ascending order.
+    /// Only populated if storage was requested.
+    pub storage: Vec<(B256, U256)>,
+    /// Hashed key of the first storage slot that did not fit into the page, if the storage was
+    /// truncated.
+    pub storage_next_key: Option<B256>,
+}
+
+/// A page of hashed storage slots returned by [`StateRange::storage_range`].
+#[derive(PartialEq, Eq, Clone, Default, Debug)]
+pub struct StorageRange {
+    /// Non-zero storage slots in ascending order of their hashed key.
+    pub slots: Vec<(B256, U256)>,
+    /// Hashed key of the first slot that did not fit into the page, if any.
+    pub next_key: Option<B256>,
+}
+
+/// Paginated iterator over the hashed state.
+///
+/// Walks the hashed accounts and storages in key order, which is the same order the leaves appear
+/// in the state trie.
+#[derive(Debug)]
+pub struct StateRange<T, H> {
+    /// The factory for traversing trie nodes.
+    trie_cursor_factory: T,
+    /// The factory for hashed cursors.
+    hashed_cursor_factory: H,
+    /// A set of prefix sets that have changes.
+    prefix_sets: TriePrefixSets,
+}
+
+impl<T, H> StateRange<T, H> {
+    /// Create a new [`StateRange`] instance.
+    pub fn new(trie_cursor_factory: T, hashed_cursor_factory: H) -> Self {
+        Self { trie_cursor_factory, hashed_cursor_factory, prefix_sets: TriePrefixSets::default() }
+    }
+
+    /// Set the trie cursor factory.
+    pub fn with_trie_cursor_factory<TF>(self, trie_cursor_factory: TF) -> StateRange<TF, H> {
+        StateRange {
+            trie_cursor_factory,
+            hashed_cursor_factory: self.hashed_cursor_factory,
+            prefix_sets: self.prefix_sets,
+        }
+    }
+
+    /// Set the hashed cursor factory.
+    pub fn with_hashed_cursor_factory<HF>(self, hashed_cursor_factory: HF) -> StateRange<T, HF> {
+        StateRange {
+            trie_cursor_factory: self.trie_cursor_factory,
+            hashed_cursor_factory,
+            prefix_sets: self.prefix_sets,
+        }
+    }
+
+    /// Set the prefix sets. They are used to invalidate intermediate storage trie nodes when
+    /// computing the storage roots of returned accounts.
+    pub fn with_prefix_sets(mut self, prefix_sets: TriePrefixSets) -> Self {
+        self.prefix_sets = prefix_sets;
+        self
+    }
+}
+
+impl<T, H> StateRange<T, H>
+where
+    T: TrieCursorFactory + Clone,
+    H: HashedCursorFactory + Clone,
+{
+    /// Returns up to `limit` accounts starting at hashed address `start` (inclusive) together with
+    /// their storage roots. Up to `storage_limit` storage slots of the returned accounts are
+    /// collected as well, none if it is zero.
+    ///
+    /// The page ends before the first account whose storage doesn't fit into the remaining
+    /// storage limit. If that is the first account of the page, its storage is truncated instead.
+    pub fn account_range(
+        self,
+        start: B256,
+        limit: usize,
+        storage_limit: usize,
+    ) -> Result<AccountRange, StateRootError> {
+        let mut range = AccountRange::default();
+        let mut remaining_storage = storage_limit;
+        let mut cursor = self.hashed_cursor_factory.hashed_account_cursor()?;
+        let mut entry = cursor.seek(start)?;
+        while let Some((hashed_address, account)) = entry {
+            if range.accounts.len() >= limit {
+                range.next_key = Some(hashed_address);
+                break
+            }
+
+            let storage_root = StorageRoot::new_hashed(
+                self.trie_cursor_factory.clone(),
+                self.hashed_cursor_factory.clone(),
+                hashed_address,
+                #[cfg(feature = "metrics")]
+                TrieRootMetrics::new(TrieType::Storage),
+            )
+            .with_prefix_set(
+                self.prefix_sets
+                    .storage_prefix_sets
+                    .get(&hashed_address)
+                    .cloned()
+                    .unwrap_or_default(),
+            )
+            .root()?;
+            let storage = if storage_limit > 0 {
+                self.collect_storage(hashed_address, B256::ZERO, remaining_storage)?
+            } else {
+                StorageRange::default()
+            };
+            if storage.next_key.is_some() && !range.accounts.is_empty() {
+                range.next_key = Some(hashed_address);
+                break
+            }
+            remaining_storage -= storage.slots.len();
+            range.accounts.push(AccountRangeEntry {
+                hashed_address,
+                account,
+                storage_root,
+                storage: storage.slots,
+                storage_next_key: storage.next_key,
+            });
+
+            entry = cursor.next()?;
+        }
+        Ok(range)
+    }
+
+    /// Returns up to `limit` storage slots of the account with the given hashed address starting
+    /// at hashed slot `start` (inclusive).
+    pub fn storage_range(
+        self,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, DatabaseError> {
+        self.collect_storage(hashed_address, start, limit)
+    }
+
+    fn collect_storage(
+        &self,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> Result<StorageRange, DatabaseError> {
+        let mut range = StorageRange::default();
+        let mut cursor = self.hashed_cursor_factory.hashed_storage_cursor(hashed_address)?;
+        let mut entry = cursor.seek(start)?;
+        while let Some((hashed_slot, value)) = entry {
+            if range.slots.len() >= limit {
+                range.next_key = Some(hashed_slot);
+                break
+            }
+            range.slots.push((hashed_slot, value));
+            entry = cursor.next()?;
+        }
+        Ok(range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        hashed_cursor::{noop::NoopHashedCursorFactory, HashedPostStateCursorFactory},
+        trie_cursor::noop::NoopTrieCursorFactory,
+        HashedPostState, HashedStorage,
+    };
+
+    fn hashed_state() -> HashedPostState {
+        let mut state = HashedPostState::default();
+        for i in 1..=5u8 {
+            let hashed_address = B256::with_last_byte(i);
+            state
+                .accounts
+                .insert(hashed_address, Some(Account { nonce: i as u64, ..Default::default() }));
+            let mut storage = HashedStorage::new(false);
+            for j in 1..=3u8 {
+                storage.storage.insert(B256::with_last_byte(j), U256::from(j));
+            }
+            state.storages.insert(hashed_address, storage);
+        }
+        // destroyed accounts must not be returned
+        state.accounts.insert(B256::with_last_byte(3), None);
+        state
+    }
+
+    #[test]
+    fn account_range_pagination() {
+        let sorted = hashed_state().into_sorted();
+        let range = || {
+            StateRange::new(
+                NoopTrieCursorFactory,
+                HashedPostStateCursorFactory::new(NoopHashedCursorFactory, &sorted),
+            )
+        };
+
+        let page = range().account_range(B256::ZERO, 2, 0).unwrap();
+        assert_eq!(
+            page.accounts.iter().map(|entry| entry.hashed_address).collect::<Vec<_>>(),
+            vec![B256::with_last_byte(1), B256::with_last_byte(2)]
+        );
+        assert!(page.accounts.iter().all(|entry| entry.storage.is_empty()));
+        assert_eq!(page.next_key, Some(B256::with_last_byte(4)));
+
+        let page = range().account_range(page.next_key.unwrap(), 2, usize::MAX).unwrap();
+        assert_eq!(
+            page.accounts.iter().map(|entry| entry.hashed_address).collect::<Vec<_>>(),
+            vec![B256::with_last_byte(4), B256::with_last_byte(5)]
+        );
+        assert_eq!(page.accounts[0].storage.len(), 3);
+        assert_eq!(page.next_key, None);
+    }
+
+    #[test]
+    fn account_range_storage_limit() {
+        let sorted = hashed_state().into_sorted();
+        let range = || {
+            StateRange::new(
+                NoopTrieCursorFactory,
+                HashedPostStateCursorFactory::new(NoopHashedCursorFactory, &sorted),
+            )
+        };
+
+        // the page ends before the storage of the second account exceeds the limit
+        let page = range().account_range(B256::ZERO, 5, 5).unwrap();
+        assert_eq!(page.accounts.len(), 1);
+        assert_eq!(page.accounts[0].storage.len(), 3);
+        assert_eq!(page.accounts[0].storage_next_key, None);
+        assert_eq!(page.next_key, Some(B256::with_last_byte(2)));
+
+        // the storage of the first account of a page is truncated
+        let page = range().account_range(B256::ZERO, 5, 2).unwrap();
+        assert_eq!(page.accounts.len(), 1);
+        assert_eq!(page.accounts[0].storage.len(), 2);
+        assert_eq!(page.accounts[0].storage_next_key, Some(B256::with_last_byte(3)));
+        assert_eq!(page.next_key, Some(B256::with_last_byte(2)));
+    }
+
+    #[test]
+    fn storage_range_pagination() {
+        let sorted = hashed_state().into_sorted();
+        let range = || {
+            StateRange::new(
+                NoopTrieCursorFactory,
+                HashedPostStateCursorFactory::new(NoopHashedCursorFactory, &sorted),
+            )
+        };
+        let hashed_address = B256::with_last_byte(1);
+
+        let page = range().storage_range(hashed_address, B256::ZERO, 2).unwrap();
+        assert_eq!(
+            page.slots,
+            vec![
+                (B256::with_last_byte(1), U256::from(1)),
+                (B256::with_last_byte(2), U256::from(2))
+            ]
+        );
+        assert_eq!(page.next_key, Some(B256::with_last_byte(3)));
+
+        let page = range().storage_range(hashed_address, B256::with_last_byte(3), 2).unwrap();
+        assert_eq!(page.slots, vec![(B256::with_last_byte(3), U256::from(3))]);
+        assert_eq!(page.next_key, None);
+    }
+}
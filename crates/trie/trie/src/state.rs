@@ -77,6 +77,34 @@ impl HashedPostState {
         Self { accounts, storages }
     }
 
+    /// Initialize [`HashedPostState`] from plain state reverts ordered by ascending block number.
+    /// Only the value before the first occurring change of each account and storage slot is kept.
+    pub fn from_plain_reverts(
+        accounts: impl IntoIterator<Item = (Address, Option<Account>)>,
+        storages: impl IntoIterator<Item = (Address, B256, U256)>,
+    ) -> Self {
+        let mut hashed_accounts = HashMap::<B256, Option<Account>>::default();
+        for (address, info) in accounts {
+            if let hash_map::Entry::Vacant(entry) = hashed_accounts.entry(keccak256(address)) {
+                entry.insert(info);
+            }
+        }
+
+        let mut hashed_storages = HashMap::<B256, HashedStorage>::default();
+        for (address, slot, value) in storages {
+            // The `wiped` flag is a noop for reverts since all wiped changes had been written as
+            // storage reverts.
+            let storage = hashed_storages
+                .entry(keccak256(address))
+                .or_insert_with(|| HashedStorage::new(false));
+            if let hash_map::Entry::Vacant(entry) = storage.storage.entry(keccak256(slot)) {
+                entry.insert(value);
+            }
+        }
+
+        Self { accounts: hashed_accounts, storages: hashed_storages }
+    }
+
     /// Construct [`HashedPostState`] from a single [`HashedStorage`].
     pub fn from_hashed_storage(hashed_address: B256, storage: HashedStorage) -> Self {
         Self { accounts: HashMap::default(), storages: HashMap::from([(hashed_address, storage)]) }
@@ -32,7 +32,10 @@ use reth::rpc::builder::{
 };
 // Configuring the network parts, ideally also wouldn't need to think about this.
 use myrpc_ext::{MyRpcExt, MyRpcExtApiServer};
-use reth::{blockchain_tree::noop::NoopBlockchainTree, tasks::TokioTaskExecutor};
+use reth::{
+    beacon_consensus::EthBeaconConsensus, blockchain_tree::noop::NoopBlockchainTree,
+    tasks::TokioTaskExecutor,
+};
 use reth_node_ethereum::{EthEvmConfig, EthExecutorProvider, EthereumNode};
 use reth_provider::{test_utils::TestCanonStateSubscriptions, ChainSpecProvider};
 
@@ -66,9 +69,10 @@ async fn main() -> eyre::Result<()> {
         .with_noop_pool()
         .with_noop_network()
         .with_executor(TokioTaskExecutor::default())
-        .with_evm_config(EthEvmConfig::new(spec))
+        .with_evm_config(EthEvmConfig::new(spec.clone()))
         .with_events(TestCanonStateSubscriptions::default())
-        .with_block_executor(EthExecutorProvider::ethereum(provider.chain_spec()));
+        .with_block_executor(EthExecutorProvider::ethereum(provider.chain_spec()))
+        .with_consensus(EthBeaconConsensus::new(spec));
 
     // Pick which namespaces to expose.
     let config = TransportRpcModuleConfig::default().with_http([RethRpcModule::Eth]);